@@ -0,0 +1,43 @@
+//! Measures the iteration over the context attributes and the extensions of the events.
+//!
+//! Run with `cargo bench --bench attributes_iter`.
+
+use cloudevents::event::SpecVersion;
+use cloudevents::{Event, EventBuilder, EventBuilderV10};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+fn bench_iter(c: &mut Criterion, name: &str, event: &Event) {
+    c.bench_function(name, |b| {
+        b.iter(|| {
+            for attribute in black_box(event).iter() {
+                black_box(attribute);
+            }
+        })
+    });
+}
+
+fn attributes_iter(c: &mut Criterion) {
+    let minimal = EventBuilderV10::new()
+        .id("0001")
+        .ty("com.example.test")
+        .source("http://localhost/")
+        .build()
+        .unwrap();
+    let full_v10 = EventBuilderV10::from(minimal.clone())
+        .subject("books")
+        .time("2020-03-16T11:50:00Z")
+        .extension("tenant", "acme")
+        .extension("priority", 1)
+        .extension("sampled", true)
+        .data("application/json", serde_json::json!({"hello": "world"}))
+        .build()
+        .unwrap();
+    let (full_v03, _) = full_v10.clone().convert_to(SpecVersion::V03);
+
+    bench_iter(c, "minimal v1.0", &minimal);
+    bench_iter(c, "full v1.0", &full_v10);
+    bench_iter(c, "full v0.3", &full_v03);
+}
+
+criterion_group!(benches, attributes_iter);
+criterion_main!(benches);
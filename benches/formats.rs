@@ -0,0 +1,49 @@
+//! Compares the encoding and the decoding of an event in the JSON and the MessagePack structured
+//! formats.
+//!
+//! Run with `cargo bench --bench formats --features msgpack`.
+
+use cloudevents::message::{EventFormat, JsonFormat};
+use cloudevents::msgpack::MsgPackFormat;
+use cloudevents::{Event, EventBuilder, EventBuilderV10};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+fn event() -> Event {
+    EventBuilderV10::new()
+        .id("0001")
+        .ty("com.example.order.created")
+        .source("http://localhost/orders")
+        .subject("order-1")
+        .time("2020-03-16T11:50:00Z")
+        .extension("tenant", "acme")
+        .extension("priority", 1)
+        .data(
+            "application/json",
+            serde_json::json!({
+                "id": 1,
+                "items": [{"sku": "a-1", "quantity": 2}, {"sku": "b-2", "quantity": 1}],
+                "total": 42.5
+            }),
+        )
+        .build()
+        .unwrap()
+}
+
+fn bench_format(c: &mut Criterion, name: &str, format: &dyn EventFormat) {
+    let event = event();
+    let bytes = format.encode(&event).unwrap();
+    c.bench_function(&format!("{} encode", name), |b| {
+        b.iter(|| format.encode(black_box(&event)).unwrap())
+    });
+    c.bench_function(&format!("{} decode", name), |b| {
+        b.iter(|| format.decode(black_box(&bytes)).unwrap())
+    });
+}
+
+fn formats(c: &mut Criterion) {
+    bench_format(c, "json", &JsonFormat);
+    bench_format(c, "msgpack", &MsgPackFormat);
+}
+
+criterion_group!(benches, formats);
+criterion_main!(benches);
@@ -0,0 +1,36 @@
+//! Defines the cfg aliases of the features enabling the bindings performing I/O, so the modules
+//! they share are gated on a single list:
+//!
+//! * `http_receiver`: the HTTP servers and clients receiving events with the http binding
+//! * `event_receiver`: the bindings intercepting the received events with the installed pipeline
+//! * `io_binding`: the bindings reporting to the installed metrics
+
+use std::env;
+
+const HTTP_RECEIVERS: &[&str] = &[
+    "actix", "axum", "hyper", "tower", "warp", "rocket", "poem", "salvo", "tide", "reqwest",
+];
+
+fn enabled(feature: &str) -> bool {
+    env::var_os(format!("CARGO_FEATURE_{}", feature.to_uppercase())).is_some()
+}
+
+fn alias(name: &str, enabled: bool) {
+    println!("cargo:rustc-check-cfg=cfg({})", name);
+    if enabled {
+        println!("cargo:rustc-cfg={}", name);
+    }
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    let http_receiver = HTTP_RECEIVERS.iter().any(|feature| enabled(feature));
+    let kafka = enabled("rdkafka");
+
+    alias("http_receiver", http_receiver);
+    alias(
+        "event_receiver",
+        http_receiver || (kafka && enabled("stream")),
+    );
+    alias("io_binding", http_receiver || kafka);
+}
@@ -18,7 +18,7 @@ pub struct HttpRequestDeserializer<'a> {
 }
 
 impl HttpRequestDeserializer<'_> {
-    pub fn new(req: &HttpRequest, body: Bytes) -> HttpRequestDeserializer {
+    pub fn new(req: &HttpRequest, body: Bytes) -> HttpRequestDeserializer<'_> {
         HttpRequestDeserializer { req, body }
     }
 }
@@ -45,27 +45,23 @@ impl<'a> BinaryDeserializer for HttpRequestDeserializer<'a> {
             let name = &hn.as_str()["ce-".len()..];
 
             if attributes.contains(&name) {
-                visitor = visitor.set_attribute(
-                    name,
-                    MessageAttributeValue::String(String::from(header_value_to_str!(hv)?)),
-                )?
+                visitor = visitor
+                    .set_attribute(name, MessageAttributeValue::from(header_value_to_str!(hv)?))?
             } else {
-                visitor = visitor.set_extension(
-                    name,
-                    MessageAttributeValue::String(String::from(header_value_to_str!(hv)?)),
-                )?
+                visitor = visitor
+                    .set_extension(name, MessageAttributeValue::from(header_value_to_str!(hv)?))?
             }
         }
 
         if let Some(hv) = self.req.headers().get("content-type") {
             visitor = visitor.set_attribute(
                 "datacontenttype",
-                MessageAttributeValue::String(String::from(header_value_to_str!(hv)?)),
+                MessageAttributeValue::from(header_value_to_str!(hv)?),
             )?
         }
 
-        if self.body.len() != 0 {
-            visitor.end_with_data(self.body.to_vec())
+        if !self.body.is_empty() {
+            visitor.end_with_data(message::Bytes::from(self.body.to_vec()))
         } else {
             visitor.end()
         }
@@ -115,23 +111,16 @@ pub async fn request_to_event(
 mod tests {
     use super::*;
     use actix_web::test;
-    use url::Url;
 
-    use chrono::Utc;
     use cloudevents::{EventBuilder, EventBuilderV10};
     use serde_json::json;
-    use std::str::FromStr;
 
     #[actix_rt::test]
     async fn test_request() {
-        let time = Utc::now();
         let expected = EventBuilderV10::new()
             .id("0001")
             .ty("example.test")
             .source("http://localhost/")
-            //TODO this is required now because the message deserializer implictly set default values
-            // As soon as this defaulting doesn't happen anymore, we can remove it (Issues #40/#41)
-            .time(time)
             .extension("someint", "10")
             .build()
             .unwrap();
@@ -142,7 +131,6 @@ mod tests {
             .header("ce-type", "example.test")
             .header("ce-source", "http://localhost/")
             .header("ce-someint", "10")
-            .header("ce-time", time.to_rfc3339())
             .to_http_parts();
 
         let resp = request_to_event(&req, web::Payload(payload)).await.unwrap();
@@ -151,16 +139,12 @@ mod tests {
 
     #[actix_rt::test]
     async fn test_request_with_full_data() {
-        let time = Utc::now();
         let j = json!({"hello": "world"});
 
         let expected = EventBuilderV10::new()
             .id("0001")
             .ty("example.test")
-            .source(Url::from_str("http://localhost").unwrap())
-            //TODO this is required now because the message deserializer implictly set default values
-            // As soon as this defaulting doesn't happen anymore, we can remove it (Issues #40/#41)
-            .time(time)
+            .source("http://localhost")
             .data("application/json", j.clone())
             .extension("someint", "10")
             .build()
@@ -172,7 +156,6 @@ mod tests {
             .header("ce-type", "example.test")
             .header("ce-source", "http://localhost")
             .header("ce-someint", "10")
-            .header("ce-time", time.to_rfc3339())
             .header("content-type", "application/json")
             .set_json(&j)
             .to_http_parts();
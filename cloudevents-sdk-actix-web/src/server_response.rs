@@ -4,7 +4,8 @@ use actix_web::http::{HeaderName, HeaderValue};
 use actix_web::HttpResponse;
 use cloudevents::event::SpecVersion;
 use cloudevents::message::{
-    BinaryDeserializer, BinarySerializer, MessageAttributeValue, Result, StructuredSerializer,
+    BinaryDeserializer, BinarySerializer, Bytes, MessageAttributeValue, Result,
+    StructuredSerializer,
 };
 use cloudevents::Event;
 use std::str::FromStr;
@@ -32,7 +33,7 @@ impl BinarySerializer<HttpResponse> for HttpResponseSerializer {
     fn set_attribute(mut self, name: &str, value: MessageAttributeValue) -> Result<Self> {
         self.builder.set_header(
             headers::ATTRIBUTES_TO_HEADERS.get(name).unwrap().clone(),
-            str_to_header_value!(value.to_string().as_str())?,
+            str_to_header_value!(&value.as_str())?,
         );
         Ok(self)
     }
@@ -40,13 +41,13 @@ impl BinarySerializer<HttpResponse> for HttpResponseSerializer {
     fn set_extension(mut self, name: &str, value: MessageAttributeValue) -> Result<Self> {
         self.builder.set_header(
             attribute_name_to_header!(name)?,
-            str_to_header_value!(value.to_string().as_str())?,
+            str_to_header_value!(&value.as_str())?,
         );
         Ok(self)
     }
 
-    fn end_with_data(mut self, bytes: Vec<u8>) -> Result<HttpResponse> {
-        Ok(self.builder.body(bytes))
+    fn end_with_data(mut self, bytes: Bytes) -> Result<HttpResponse> {
+        Ok(self.builder.body(Vec::from(bytes)))
     }
 
     fn end(mut self) -> Result<HttpResponse> {
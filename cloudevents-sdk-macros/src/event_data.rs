@@ -0,0 +1,116 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{DeriveInput, Error, LitStr, Result};
+
+/// Attributes of the events declared with `#[event(...)]`
+#[derive(Default)]
+struct EventAttributes {
+    ty: Option<LitStr>,
+    source: Option<LitStr>,
+    datacontenttype: Option<LitStr>,
+}
+
+impl EventAttributes {
+    fn parse(input: &DeriveInput) -> Result<Self> {
+        let mut attributes = EventAttributes::default();
+        for attr in input.attrs.iter().filter(|a| a.path().is_ident("event")) {
+            attr.parse_nested_meta(|meta| {
+                let slot = if meta.path.is_ident("type") {
+                    &mut attributes.ty
+                } else if meta.path.is_ident("source") {
+                    &mut attributes.source
+                } else if meta.path.is_ident("datacontenttype") {
+                    &mut attributes.datacontenttype
+                } else {
+                    return Err(meta.error("expected `type`, `source` or `datacontenttype`"));
+                };
+                if slot.is_some() {
+                    return Err(meta.error("duplicate event attribute"));
+                }
+                let value: LitStr = meta.value()?.parse()?;
+                if value.value().is_empty() {
+                    return Err(Error::new_spanned(value, "empty event attribute"));
+                }
+                *slot = Some(value);
+                Ok(())
+            })?;
+        }
+        Ok(attributes)
+    }
+}
+
+pub(crate) fn expand(input: DeriveInput) -> Result<TokenStream> {
+    let attributes = EventAttributes::parse(&input)?;
+    let ty = attributes.ty.ok_or_else(|| {
+        Error::new_spanned(
+            &input.ident,
+            "missing the event type, add #[event(type = \"...\")]",
+        )
+    })?;
+    let source = attributes.source.map(|source| {
+        quote! {
+            const SOURCE: ::core::option::Option<&'static str> =
+                ::core::option::Option::Some(#source);
+        }
+    });
+    let datacontenttype = attributes.datacontenttype.map(|datacontenttype| {
+        quote! {
+            const DATACONTENTTYPE: &'static str = #datacontenttype;
+        }
+    });
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::cloudevents::event::EventData for #ident #ty_generics #where_clause {
+            const TYPE: &'static str = #ty;
+            #source
+            #datacontenttype
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    #[test]
+    fn expand_attributes() {
+        let tokens = expand(parse_quote! {
+            #[event(type = "com.example.order.created", datacontenttype = "application/vnd.order+json")]
+            struct OrderCreated<T> {
+                id: T,
+            }
+        })
+        .unwrap();
+        let expected = quote! {
+            impl<T> ::cloudevents::event::EventData for OrderCreated<T> {
+                const TYPE: &'static str = "com.example.order.created";
+                const DATACONTENTTYPE: &'static str = "application/vnd.order+json";
+            }
+        };
+        assert_eq!(expected.to_string(), tokens.to_string());
+    }
+
+    #[test]
+    fn invalid_attributes() {
+        let error = |input: DeriveInput| expand(input).unwrap_err().to_string();
+        assert!(error(parse_quote! { struct OrderCreated; }).contains("missing the event type"));
+        assert!(error(parse_quote! {
+            #[event(type = "a", type = "b")]
+            struct OrderCreated;
+        })
+        .contains("duplicate"));
+        assert!(error(parse_quote! {
+            #[event(type = "a", subject = "b")]
+            struct OrderCreated;
+        })
+        .contains("expected `type`"));
+        assert!(error(parse_quote! {
+            #[event(type = "")]
+            struct OrderCreated;
+        })
+        .contains("empty"));
+    }
+}
@@ -0,0 +1,205 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::parse::Parser;
+use syn::{Error, FnArg, ItemFn, LitStr, Result, ReturnType, Visibility};
+
+/// Arguments of `#[cloudevent_handler(...)]`
+fn parse_type(args: TokenStream) -> Result<Option<LitStr>> {
+    let mut ty: Option<LitStr> = None;
+    let parser = syn::meta::parser(|meta| {
+        if !meta.path.is_ident("type") {
+            return Err(meta.error("expected `type`"));
+        }
+        if ty.is_some() {
+            return Err(meta.error("duplicate `type`"));
+        }
+        let value: LitStr = meta.value()?.parse()?;
+        if value.value().is_empty() {
+            return Err(Error::new_spanned(value, "empty `type`"));
+        }
+        ty = Some(value);
+        Ok(())
+    });
+    parser.parse2(args)?;
+    Ok(ty)
+}
+
+pub(crate) fn expand(args: TokenStream, mut item: ItemFn) -> Result<TokenStream> {
+    let ty = parse_type(args)?;
+
+    let sig = &item.sig;
+    if sig.asyncness.is_none() {
+        return Err(Error::new_spanned(
+            sig.fn_token,
+            "a cloudevent_handler must be an async function",
+        ));
+    }
+    if !sig.generics.params.is_empty() {
+        return Err(Error::new_spanned(
+            &sig.generics,
+            "a cloudevent_handler can't be generic",
+        ));
+    }
+    let arg_ty = match (sig.inputs.len(), sig.inputs.first()) {
+        (1, Some(FnArg::Typed(arg))) => arg.ty.clone(),
+        _ => {
+            return Err(Error::new(
+                sig.paren_token.span.join(),
+                "a cloudevent_handler takes one argument, converted from the event",
+            ))
+        }
+    };
+    let output = match &sig.output {
+        ReturnType::Default => quote!(()),
+        ReturnType::Type(_, output) => quote!(#output),
+    };
+    let matcher = match ty {
+        Some(ty) => quote!(#ty),
+        None => quote!(<#arg_ty as ::cloudevents::event::EventData>::TYPE),
+    };
+
+    // The handler struct replaces the function, keeping its docs, while the other attributes
+    // stay on the function, nested in the impl
+    let (docs, attrs) = item
+        .attrs
+        .drain(..)
+        .partition::<Vec<_>, _>(|attr| attr.path().is_ident("doc"));
+    item.attrs = attrs;
+    let vis = std::mem::replace(&mut item.vis, Visibility::Inherited);
+    let name = &item.sig.ident;
+
+    Ok(quote! {
+        #(#docs)*
+        #[allow(non_camel_case_types)]
+        #[derive(Debug, Clone, Copy, Default)]
+        #vis struct #name;
+
+        impl ::cloudevents::router::RouteHandler<#output> for #name {
+            fn route(&self) -> ::cloudevents::router::Route {
+                ::cloudevents::router::Route::new()
+                    .ty(::cloudevents::router::Matcher::exact(#matcher))
+            }
+
+            fn into_handler(self) -> ::cloudevents::router::AsyncHandler<#output> {
+                #item
+
+                ::std::boxed::Box::new(|event: ::cloudevents::Event| {
+                    let future: ::cloudevents::router::HandlerFuture<#output> =
+                        ::std::boxed::Box::pin(async move {
+                            let data = <#arg_ty as ::cloudevents::event::TryFromEvent>::try_from_event(&event)?;
+                            ::core::result::Result::Ok(#name(data).await)
+                        });
+                    future
+                })
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    #[test]
+    fn expand_handler() {
+        let tokens = expand(
+            quote!(type = "com.example.order.created"),
+            parse_quote! {
+                /// Handle the created orders
+                #[allow(unused_variables)]
+                pub async fn on_order_created(order: OrderCreated) -> String {
+                    String::new()
+                }
+            },
+        )
+        .unwrap()
+        .to_string();
+        assert!(tokens.starts_with(
+            &quote! {
+                #[doc = r" Handle the created orders"]
+                #[allow(non_camel_case_types)]
+                #[derive(Debug, Clone, Copy, Default)]
+                pub struct on_order_created;
+            }
+            .to_string()
+        ));
+        assert!(tokens.contains(
+            &quote!(impl ::cloudevents::router::RouteHandler<String> for on_order_created)
+                .to_string()
+        ));
+        assert!(tokens.contains(
+            &quote!(::cloudevents::router::Matcher::exact(
+                "com.example.order.created"
+            ))
+            .to_string()
+        ));
+        assert!(tokens.contains(
+            &quote! {
+                #[allow(unused_variables)]
+                async fn on_order_created(order: OrderCreated) -> String
+            }
+            .to_string()
+        ));
+
+        let tokens = expand(
+            TokenStream::new(),
+            parse_quote!(
+                async fn on_order_created(order: OrderCreated) {}
+            ),
+        )
+        .unwrap()
+        .to_string();
+        assert!(tokens.contains(
+            &quote!(<OrderCreated as ::cloudevents::event::EventData>::TYPE).to_string()
+        ));
+        assert!(tokens.contains(&quote!(RouteHandler<()>).to_string()));
+    }
+
+    #[test]
+    fn invalid_handler() {
+        let error = |args: TokenStream, item: ItemFn| expand(args, item).unwrap_err().to_string();
+        assert!(error(
+            quote!(subject = "a"),
+            parse_quote!(
+                async fn f(a: A) {}
+            )
+        )
+        .contains("expected `type`"));
+        assert!(error(
+            quote!(type = ""),
+            parse_quote!(
+                async fn f(a: A) {}
+            )
+        )
+        .contains("empty"));
+        assert!(error(
+            TokenStream::new(),
+            parse_quote!(
+                fn f(a: A) {}
+            )
+        )
+        .contains("async"));
+        assert!(error(
+            TokenStream::new(),
+            parse_quote!(
+                async fn f<A>(a: A) {}
+            )
+        )
+        .contains("generic"));
+        assert!(error(
+            TokenStream::new(),
+            parse_quote!(
+                async fn f() {}
+            )
+        )
+        .contains("one argument"));
+        assert!(error(
+            TokenStream::new(),
+            parse_quote!(
+                async fn f(a: A, b: B) {}
+            )
+        )
+        .contains("one argument"));
+    }
+}
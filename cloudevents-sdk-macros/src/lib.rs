@@ -0,0 +1,62 @@
+//! Procedural macros of the [CloudEvents SDK](https://docs.rs/cloudevents-sdk), re-exported by
+//! `cloudevents` with the `macros` feature.
+//!
+//! The generated code refers to the SDK as `::cloudevents`, the name of its library.
+
+extern crate proc_macro;
+
+mod event_data;
+mod handler;
+
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, DeriveInput, ItemFn};
+
+/// Derive `cloudevents::event::EventData`, declaring the attributes of the events carrying the
+/// type with the `event` attribute:
+///
+/// * `type`, required: the `type` of the events
+/// * `source`, optional: the `source` of the events
+/// * `datacontenttype`, optional: the JSON `datacontenttype` of the events, `application/json` by
+///   default
+///
+/// ```ignore
+/// #[derive(Serialize, Deserialize, EventData)]
+/// #[event(type = "com.example.order.created", source = "/orders")]
+/// struct OrderCreated {
+///     id: u64,
+/// }
+///
+/// let event = OrderCreated { id: 1 }.to_event()?;
+/// ```
+#[proc_macro_derive(EventData, attributes(event))]
+pub fn derive_event_data(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    event_data::expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Turn an async function into a `cloudevents::router::RouteHandler`, registered with
+/// `EventRouter::handler`, dispatching the events of `type` to the function.
+///
+/// The function takes one argument implementing `cloudevents::event::TryFromEvent`, converted
+/// from the dispatched event. `type` is optional when the argument implements
+/// `cloudevents::event::EventData`, defaulting to its `TYPE`.
+///
+/// The function is replaced by a unit struct with its name, implementing the handler.
+///
+/// ```ignore
+/// #[cloudevent_handler(type = "com.example.order.created")]
+/// async fn on_order_created(order: OrderCreated) -> String {
+///     format!("order {}", order.id)
+/// }
+///
+/// let router = EventRouter::new().handler(on_order_created);
+/// ```
+#[proc_macro_attribute]
+pub fn cloudevent_handler(args: TokenStream, item: TokenStream) -> TokenStream {
+    let item = parse_macro_input!(item as ItemFn);
+    handler::expand(args.into(), item)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
@@ -1,7 +1,8 @@
 use super::headers;
 use cloudevents::event::SpecVersion;
 use cloudevents::message::{
-    BinaryDeserializer, BinarySerializer, MessageAttributeValue, Result, StructuredSerializer,
+    BinaryDeserializer, BinarySerializer, Bytes, MessageAttributeValue, Result,
+    StructuredSerializer,
 };
 use cloudevents::Event;
 use reqwest::RequestBuilder;
@@ -29,7 +30,7 @@ impl BinarySerializer<RequestBuilder> for RequestSerializer {
     fn set_attribute(mut self, name: &str, value: MessageAttributeValue) -> Result<Self> {
         self.req = self.req.header(
             headers::ATTRIBUTES_TO_HEADERS.get(name).unwrap().clone(),
-            value.to_string(),
+            &*value.as_str(),
         );
         Ok(self)
     }
@@ -37,12 +38,12 @@ impl BinarySerializer<RequestBuilder> for RequestSerializer {
     fn set_extension(mut self, name: &str, value: MessageAttributeValue) -> Result<Self> {
         self.req = self
             .req
-            .header(attribute_name_to_header!(name)?, value.to_string());
+            .header(attribute_name_to_header!(name)?, &*value.as_str());
         Ok(self)
     }
 
-    fn end_with_data(self, bytes: Vec<u8>) -> Result<RequestBuilder> {
-        Ok(self.req.body(bytes))
+    fn end_with_data(self, bytes: Bytes) -> Result<RequestBuilder> {
+        Ok(self.req.body(Vec::from(bytes)))
     }
 
     fn end(self) -> Result<RequestBuilder> {
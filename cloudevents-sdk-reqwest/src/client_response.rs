@@ -44,27 +44,23 @@ impl BinaryDeserializer for ResponseDeserializer {
             let name = &hn.as_str()["ce-".len()..];
 
             if attributes.contains(&name) {
-                visitor = visitor.set_attribute(
-                    name,
-                    MessageAttributeValue::String(String::from(header_value_to_str!(hv)?)),
-                )?
+                visitor = visitor
+                    .set_attribute(name, MessageAttributeValue::from(header_value_to_str!(hv)?))?
             } else {
-                visitor = visitor.set_extension(
-                    name,
-                    MessageAttributeValue::String(String::from(header_value_to_str!(hv)?)),
-                )?
+                visitor = visitor
+                    .set_extension(name, MessageAttributeValue::from(header_value_to_str!(hv)?))?
             }
         }
 
         if let Some(hv) = self.headers.get("content-type") {
             visitor = visitor.set_attribute(
                 "datacontenttype",
-                MessageAttributeValue::String(String::from(header_value_to_str!(hv)?)),
+                MessageAttributeValue::from(header_value_to_str!(hv)?),
             )?
         }
 
-        if self.body.len() != 0 {
-            visitor.end_with_data(self.body.to_vec())
+        if !self.body.is_empty() {
+            visitor.end_with_data(message::Bytes::from(self.body.to_vec()))
         } else {
             visitor.end()
         }
@@ -112,15 +108,11 @@ mod tests {
     use super::*;
     use mockito::mock;
 
-    use chrono::Utc;
     use cloudevents::{EventBuilder, EventBuilderV10};
     use serde_json::json;
-    use std::str::FromStr;
-    use url::Url;
 
     #[tokio::test]
     async fn test_response() {
-        let time = Utc::now();
         let url = mockito::server_url();
         let _m = mock("GET", "/")
             .with_status(200)
@@ -129,16 +121,12 @@ mod tests {
             .with_header("ce-type", "example.test")
             .with_header("ce-source", "http://localhost")
             .with_header("ce-someint", "10")
-            .with_header("ce-time", &time.to_rfc3339())
             .create();
 
         let expected = EventBuilderV10::new()
             .id("0001")
             .ty("example.test")
-            //TODO this is required now because the message deserializer implictly set default values
-            // As soon as this defaulting doesn't happen anymore, we can remove it (Issues #40/#41)
-            .time(time)
-            .source(Url::from_str("http://localhost").unwrap())
+            .source("http://localhost")
             .extension("someint", "10")
             .build()
             .unwrap();
@@ -152,7 +140,6 @@ mod tests {
 
     #[tokio::test]
     async fn test_response_with_full_data() {
-        let time = Utc::now();
         let j = json!({"hello": "world"});
 
         let url = mockito::server_url();
@@ -164,17 +151,13 @@ mod tests {
             .with_header("ce-source", "http://localhost/")
             .with_header("content-type", "application/json")
             .with_header("ce-someint", "10")
-            .with_header("ce-time", &time.to_rfc3339())
             .with_body(j.to_string())
             .create();
 
         let expected = EventBuilderV10::new()
             .id("0001")
             .ty("example.test")
-            //TODO this is required now because the message deserializer implictly set default values
-            // As soon as this defaulting doesn't happen anymore, we can remove it (Issues #40/#41)
-            .time(time)
-            .source(Url::from_str("http://localhost").unwrap())
+            .source("http://localhost/")
             .data("application/json", j.clone())
             .extension("someint", "10")
             .build()
@@ -189,16 +172,11 @@ mod tests {
 
     #[tokio::test]
     async fn test_structured_response_with_full_data() {
-        let time = Utc::now();
-
         let j = json!({"hello": "world"});
         let expected = EventBuilderV10::new()
             .id("0001")
             .ty("example.test")
-            //TODO this is required now because the message deserializer implictly set default values
-            // As soon as this defaulting doesn't happen anymore, we can remove it (Issues #40/#41)
-            .time(time)
-            .source(Url::from_str("http://localhost").unwrap())
+            .source("http://localhost")
             .data("application/json", j.clone())
             .extension("someint", "10")
             .build()
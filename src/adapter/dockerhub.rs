@@ -0,0 +1,71 @@
+use super::{field, string_field, Adapted, Error, Result, SourceAdapter, WebhookHeaders};
+use crate::Event;
+use chrono::{TimeZone, Utc};
+use serde_json::Value;
+
+/// Type of the events of the Docker Hub webhooks, which only notify the pushes
+pub const DOCKERHUB_PUSH_TYPE: &str = "com.docker.hub.push";
+
+/// [`SourceAdapter`] of the [Docker Hub webhooks](https://docs.docker.com/docker-hub/webhooks/),
+/// following the
+/// [Docker Hub adapter](https://github.com/cloudevents/spec/blob/main/cloudevents/adapters/docker-hub.md)
+/// specification:
+///
+/// * `id` is a generated UUID, Docker Hub doesn't identify the deliveries
+/// * `type` is `com.docker.hub.push`
+/// * `source` is the `repo_url` of the repository
+/// * `subject` is the pushed `tag`
+/// * `time` is the `pushed_at` timestamp
+#[derive(Debug, Default, Clone, Copy)]
+pub struct DockerHubAdapter;
+
+impl SourceAdapter for DockerHubAdapter {
+    fn adapt(&self, _headers: &dyn WebhookHeaders, payload: Value) -> Result<Event> {
+        let source =
+            string_field(&payload, &["repository", "repo_url"]).ok_or(Error::MissingField {
+                field_name: "repository.repo_url",
+            })?;
+        let time = field(&payload, &["push_data", "pushed_at"])
+            .and_then(Value::as_i64)
+            .map(|seconds| Utc.timestamp(seconds, 0));
+
+        Adapted {
+            id: None,
+            ty: DOCKERHUB_PUSH_TYPE.to_string(),
+            source,
+            subject: string_field(&payload, &["push_data", "tag"]),
+            time,
+        }
+        .build(payload)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::AttributesReader;
+    use serde_json::json;
+
+    #[test]
+    fn push() {
+        let payload = json!({
+            "callback_url": "https://registry.hub.docker.com/u/svendowideit/testhook/hook/1/",
+            "push_data": {"pushed_at": 1417566161, "pusher": "trustedbuilder", "tag": "latest"},
+            "repository": {
+                "repo_name": "svendowideit/testhook",
+                "repo_url": "https://registry.hub.docker.com/u/svendowideit/testhook/"
+            }
+        });
+        let event = DockerHubAdapter
+            .adapt(&Vec::<(String, String)>::new(), payload)
+            .unwrap();
+
+        assert_eq!(DOCKERHUB_PUSH_TYPE, event.get_type());
+        assert_eq!(
+            "https://registry.hub.docker.com/u/svendowideit/testhook/",
+            event.get_source().to_string()
+        );
+        assert_eq!(Some("latest"), event.get_subject());
+        assert_eq!(Some(&Utc.timestamp(1417566161, 0)), event.get_time());
+    }
+}
@@ -0,0 +1,147 @@
+use super::{string_field, Adapted, Error, Result, SourceAdapter, WebhookHeaders};
+use crate::Event;
+use serde_json::Value;
+
+/// Header carrying the name of the event
+const EVENT_HEADER: &str = "X-GitHub-Event";
+/// Header carrying the unique id of the delivery
+const DELIVERY_HEADER: &str = "X-GitHub-Delivery";
+
+/// [`SourceAdapter`] of the [GitHub webhooks](https://docs.github.com/en/webhooks), following the
+/// [GitHub adapter](https://github.com/cloudevents/spec/blob/main/cloudevents/adapters/github.md)
+/// specification:
+///
+/// * `id` is the `X-GitHub-Delivery` header
+/// * `type` is `com.github.<event>`, where `<event>` is the `X-GitHub-Event` header, suffixed
+///   with the `action` of the payload if any, e.g. `com.github.pull_request.opened`
+/// * `source` is the `html_url` of the repository, or of the organization or the sender when the
+///   event isn't related to a repository
+/// * `subject` identifies the object of the event, e.g. the number of the pull request or the
+///   `ref` of a push
+#[derive(Debug, Default, Clone, Copy)]
+pub struct GitHubAdapter;
+
+impl SourceAdapter for GitHubAdapter {
+    fn adapt(&self, headers: &dyn WebhookHeaders, payload: Value) -> Result<Event> {
+        let event = headers.header(EVENT_HEADER).ok_or(Error::MissingHeader {
+            header_name: EVENT_HEADER,
+        })?;
+        let id = headers
+            .header(DELIVERY_HEADER)
+            .ok_or(Error::MissingHeader {
+                header_name: DELIVERY_HEADER,
+            })?
+            .to_string();
+
+        let ty = match payload.get("action").and_then(Value::as_str) {
+            Some(action) => format!("com.github.{}.{}", event, action),
+            None => format!("com.github.{}", event),
+        };
+        let source = string_field(&payload, &["repository", "html_url"])
+            .or_else(|| string_field(&payload, &["organization", "html_url"]))
+            .or_else(|| string_field(&payload, &["sender", "html_url"]))
+            .ok_or(Error::MissingField {
+                field_name: "repository.html_url",
+            })?;
+
+        Adapted {
+            id: Some(id),
+            ty,
+            source,
+            subject: subject(event, &payload),
+            time: None,
+        }
+        .build(payload)
+    }
+}
+
+fn subject(event: &str, payload: &Value) -> Option<String> {
+    let path: &[&str] = match event {
+        "push" | "create" | "delete" => &["ref"],
+        "pull_request" | "pull_request_review" | "pull_request_review_comment" => {
+            &["pull_request", "number"]
+        }
+        "issues" | "issue_comment" => &["issue", "number"],
+        "release" => &["release", "tag_name"],
+        "check_run" => &["check_run", "id"],
+        "check_suite" => &["check_suite", "id"],
+        "workflow_run" => &["workflow_run", "id"],
+        "workflow_job" => &["workflow_job", "id"],
+        "deployment" => &["deployment", "id"],
+        "deployment_status" => &["deployment_status", "id"],
+        "status" => &["sha"],
+        "commit_comment" => &["comment", "commit_id"],
+        "label" => &["label", "name"],
+        "milestone" => &["milestone", "number"],
+        _ => return None,
+    };
+    string_field(payload, path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::AttributesReader;
+    use serde_json::json;
+
+    fn headers(event: &str) -> Vec<(&str, &str)> {
+        vec![
+            ("x-github-event", event),
+            ("x-github-delivery", "72d3162e-cc78-11e3-81ab-4c9367dc0958"),
+        ]
+    }
+
+    #[test]
+    fn push() {
+        let payload = json!({
+            "ref": "refs/heads/main",
+            "repository": {"html_url": "https://github.com/cloudevents/sdk-rust"},
+            "sender": {"html_url": "https://github.com/octocat"}
+        });
+        let event = GitHubAdapter
+            .adapt(&headers("push"), payload.clone())
+            .unwrap();
+
+        assert_eq!("72d3162e-cc78-11e3-81ab-4c9367dc0958", event.get_id());
+        assert_eq!("com.github.push", event.get_type());
+        assert_eq!(
+            "https://github.com/cloudevents/sdk-rust",
+            event.get_source().to_string()
+        );
+        assert_eq!(Some("refs/heads/main"), event.get_subject());
+        assert_eq!(Some("application/json"), event.get_datacontenttype());
+        assert_eq!(Some(payload), event.data_as::<Value>().unwrap());
+    }
+
+    #[test]
+    fn organization_event() {
+        let payload = json!({
+            "action": "member_added",
+            "organization": {"html_url": "https://github.com/cloudevents"}
+        });
+        let event = GitHubAdapter
+            .adapt(&headers("organization"), payload)
+            .unwrap();
+
+        assert_eq!("com.github.organization.member_added", event.get_type());
+        assert_eq!(
+            "https://github.com/cloudevents",
+            event.get_source().to_string()
+        );
+        assert_eq!(None, event.get_subject());
+    }
+
+    #[test]
+    fn missing_headers() {
+        assert!(matches!(
+            GitHubAdapter.adapt(&vec![("X-GitHub-Event", "push")], json!({})),
+            Err(Error::MissingHeader {
+                header_name: DELIVERY_HEADER
+            })
+        ));
+        assert!(matches!(
+            GitHubAdapter.adapt_body(&headers("push"), b"not json"),
+            Err(Error::InvalidPayload { .. })
+        ));
+    }
+}
@@ -0,0 +1,129 @@
+use super::{string_field, Adapted, Error, Result, SourceAdapter, WebhookHeaders};
+use crate::Event;
+use serde_json::Value;
+
+/// Header carrying the unique id of the delivery, sent by GitLab 15.0 and later
+const UUID_HEADER: &str = "X-Gitlab-Event-UUID";
+
+/// [`SourceAdapter`] of the
+/// [GitLab webhooks](https://docs.gitlab.com/ee/user/project/integrations/webhooks.html),
+/// following the
+/// [GitLab adapter](https://github.com/cloudevents/spec/blob/main/cloudevents/adapters/gitlab.md)
+/// specification:
+///
+/// * `id` is the `X-Gitlab-Event-UUID` header, or a generated UUID if missing
+/// * `type` is `com.gitlab.<object_kind>`, suffixed with the `action` of the object attributes
+///   if any, e.g. `com.gitlab.merge_request.open`
+/// * `source` is the `web_url` of the project
+/// * `subject` identifies the object of the event, e.g. the `iid` of the merge request or the
+///   `ref` of a push
+#[derive(Debug, Default, Clone, Copy)]
+pub struct GitLabAdapter;
+
+impl SourceAdapter for GitLabAdapter {
+    fn adapt(&self, headers: &dyn WebhookHeaders, payload: Value) -> Result<Event> {
+        let object_kind = payload
+            .get("object_kind")
+            .and_then(Value::as_str)
+            .ok_or(Error::MissingField {
+                field_name: "object_kind",
+            })?
+            .to_string();
+        let ty = match payload
+            .get("object_attributes")
+            .and_then(|attributes| attributes.get("action"))
+            .and_then(Value::as_str)
+        {
+            Some(action) => format!("com.gitlab.{}.{}", object_kind, action),
+            None => format!("com.gitlab.{}", object_kind),
+        };
+        let source =
+            string_field(&payload, &["project", "web_url"]).ok_or(Error::MissingField {
+                field_name: "project.web_url",
+            })?;
+
+        Adapted {
+            id: headers.header(UUID_HEADER).map(String::from),
+            ty,
+            source,
+            subject: subject(&object_kind, &payload),
+            time: None,
+        }
+        .build(payload)
+    }
+}
+
+fn subject(object_kind: &str, payload: &Value) -> Option<String> {
+    let path: &[&str] = match object_kind {
+        "push" | "tag_push" => &["ref"],
+        "issue" | "merge_request" => &["object_attributes", "iid"],
+        "note" | "pipeline" => &["object_attributes", "id"],
+        "wiki_page" => &["object_attributes", "slug"],
+        "build" => &["build_id"],
+        "deployment" => &["deployment_id"],
+        "release" => &["tag"],
+        _ => return None,
+    };
+    string_field(payload, path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::AttributesReader;
+    use serde_json::json;
+    use std::collections::HashMap;
+
+    #[test]
+    fn merge_request() {
+        let mut headers = HashMap::new();
+        headers.insert(
+            "X-Gitlab-Event".to_string(),
+            "Merge Request Hook".to_string(),
+        );
+        headers.insert(
+            "x-gitlab-event-uuid".to_string(),
+            "13792a34-cac6-4fda-95a8-c58e00a3954e".to_string(),
+        );
+        let payload = json!({
+            "object_kind": "merge_request",
+            "project": {"web_url": "https://gitlab.com/cloudevents/sdk-rust"},
+            "object_attributes": {"id": 99, "iid": 1, "action": "open"}
+        });
+        let event = GitLabAdapter.adapt(&headers, payload).unwrap();
+
+        assert_eq!("13792a34-cac6-4fda-95a8-c58e00a3954e", event.get_id());
+        assert_eq!("com.gitlab.merge_request.open", event.get_type());
+        assert_eq!(
+            "https://gitlab.com/cloudevents/sdk-rust",
+            event.get_source().to_string()
+        );
+        assert_eq!(Some("1"), event.get_subject());
+    }
+
+    #[test]
+    fn push_without_uuid() {
+        let payload = json!({
+            "object_kind": "push",
+            "ref": "refs/heads/main",
+            "project": {"web_url": "https://gitlab.com/cloudevents/sdk-rust"}
+        });
+        let event = GitLabAdapter
+            .adapt(&Vec::<(String, String)>::new(), payload)
+            .unwrap();
+
+        assert!(!event.get_id().is_empty());
+        assert_eq!("com.gitlab.push", event.get_type());
+        assert_eq!(Some("refs/heads/main"), event.get_subject());
+
+        assert!(matches!(
+            GitLabAdapter.adapt(
+                &Vec::<(String, String)>::new(),
+                json!({"object_kind": "push"})
+            ),
+            Err(Error::MissingField {
+                field_name: "project.web_url"
+            })
+        ));
+    }
+}
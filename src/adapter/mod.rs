@@ -0,0 +1,149 @@
+//! [CloudEvents Adapters](https://github.com/cloudevents/spec/blob/main/cloudevents/adapters/README.md)
+//! converting the webhook requests of third-party services to v1.0 [`Event`]s.
+//!
+//! A [`SourceAdapter`] reads the headers of a webhook request with [`WebhookHeaders`], and maps its
+//! JSON payload to the `type`, `source` and `subject` of the event, which carries the payload as
+//! `application/json` data:
+//!
+//! * [`GitHubAdapter`] for the [GitHub](https://docs.github.com/en/webhooks) events
+//! * [`GitLabAdapter`] for the [GitLab](https://docs.gitlab.com/ee/user/project/integrations/webhooks.html) events
+//! * [`DockerHubAdapter`] for the [Docker Hub](https://docs.docker.com/docker-hub/webhooks/) push events
+//!
+//! ```
+//! use cloudevents::adapter::{GitHubAdapter, SourceAdapter};
+//! use cloudevents::AttributesReader;
+//! use serde_json::json;
+//!
+//! let headers = vec![
+//!     ("X-GitHub-Event", "pull_request"),
+//!     ("X-GitHub-Delivery", "72d3162e-cc78-11e3-81ab-4c9367dc0958"),
+//! ];
+//! let payload = json!({
+//!     "action": "opened",
+//!     "number": 42,
+//!     "pull_request": {"number": 42},
+//!     "repository": {"html_url": "https://github.com/cloudevents/sdk-rust"}
+//! });
+//!
+//! let event = GitHubAdapter.adapt(&headers, payload).unwrap();
+//! assert_eq!("com.github.pull_request.opened", event.get_type());
+//! assert_eq!("https://github.com/cloudevents/sdk-rust", event.get_source().to_string());
+//! assert_eq!(Some("42"), event.get_subject());
+//! ```
+
+mod dockerhub;
+mod github;
+mod gitlab;
+
+pub use dockerhub::{DockerHubAdapter, DOCKERHUB_PUSH_TYPE};
+pub use github::GitHubAdapter;
+pub use gitlab::GitLabAdapter;
+
+use crate::event::{EventBuilderError, IdGenerator, UuidV4Generator};
+use crate::{Event, EventBuilder, EventBuilderV10};
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use snafu::Snafu;
+use std::collections::HashMap;
+
+/// Represents an error while converting a webhook request to an [`Event`]
+#[derive(Debug, Snafu)]
+pub enum Error {
+    #[snafu(display("Invalid webhook payload: {}", source))]
+    InvalidPayload { source: serde_json::Error },
+    #[snafu(display("Missing webhook header {}", header_name))]
+    MissingHeader { header_name: &'static str },
+    #[snafu(display("Missing webhook payload field {}", field_name))]
+    MissingField { field_name: &'static str },
+    #[snafu(display("Invalid adapted event: {}", source))]
+    InvalidEvent { source: EventBuilderError },
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Headers of a webhook request. The names are looked up ignoring the ASCII case.
+pub trait WebhookHeaders {
+    /// Get the value of the header `name`
+    fn header(&self, name: &str) -> Option<&str>;
+}
+
+impl<K: AsRef<str>, V: AsRef<str>> WebhookHeaders for Vec<(K, V)> {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.iter()
+            .find(|(k, _)| k.as_ref().eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_ref())
+    }
+}
+
+impl WebhookHeaders for HashMap<String, String> {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.get(name).map(String::as_str).or_else(|| {
+            self.iter()
+                .find(|(k, _)| k.eq_ignore_ascii_case(name))
+                .map(|(_, v)| v.as_str())
+        })
+    }
+}
+
+#[cfg(feature = "http")]
+impl WebhookHeaders for http::HeaderMap {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.get(name).and_then(|v| v.to_str().ok())
+    }
+}
+
+/// Adapter converting the webhook requests of a service to [`Event`]s
+pub trait SourceAdapter {
+    /// Convert the webhook request with `headers` and the JSON `payload` to an [`Event`]
+    fn adapt(&self, headers: &dyn WebhookHeaders, payload: Value) -> Result<Event>;
+
+    /// Convert the webhook request with `headers` and the JSON `body` to an [`Event`]
+    fn adapt_body(&self, headers: &dyn WebhookHeaders, body: &[u8]) -> Result<Event> {
+        let payload =
+            serde_json::from_slice(body).map_err(|e| Error::InvalidPayload { source: e })?;
+        self.adapt(headers, payload)
+    }
+}
+
+/// Attributes of an adapted event
+struct Adapted {
+    id: Option<String>,
+    ty: String,
+    source: String,
+    subject: Option<String>,
+    time: Option<DateTime<Utc>>,
+}
+
+impl Adapted {
+    /// Build the event carrying `payload`, with a generated id if missing
+    fn build(self, payload: Value) -> Result<Event> {
+        let mut builder = EventBuilderV10::new()
+            .id(self.id.unwrap_or_else(|| UuidV4Generator.generate()))
+            .ty(self.ty)
+            .source(self.source)
+            .data("application/json", payload);
+        if let Some(subject) = self.subject {
+            builder = builder.subject(subject);
+        }
+        if let Some(time) = self.time {
+            builder = builder.time(time);
+        }
+        builder
+            .build()
+            .map_err(|e| Error::InvalidEvent { source: e })
+    }
+}
+
+/// Get the field of `payload` at `path`
+fn field<'a>(payload: &'a Value, path: &[&str]) -> Option<&'a Value> {
+    path.iter().try_fold(payload, |value, key| value.get(key))
+}
+
+/// Get the field of `payload` at `path` as a string, formatting the numbers
+fn string_field(payload: &Value, path: &[&str]) -> Option<String> {
+    match field(payload, path)? {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
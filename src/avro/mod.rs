@@ -0,0 +1,345 @@
+//! [Avro Event Format](https://github.com/cloudevents/spec/blob/v1.0/avro-format.md)
+//! on top of the [`apache-avro`](https://docs.rs/apache-avro) crate, using the canonical
+//! CloudEvents Avro schema.
+//!
+//! Context attributes are stored in the `attribute` map, mapping Boolean to `boolean`,
+//! Integer to `int`, Binary to `bytes`, and every other type, including Timestamp, to `string`.
+//! Binary data is stored as `bytes`, while text and JSON data are stored as `string`.
+//!
+//! ```
+//! use cloudevents::avro::{decode_event, encode_event};
+//! use cloudevents::{EventBuilder, EventBuilderV10};
+//!
+//! let event = EventBuilderV10::new()
+//!     .id("0001")
+//!     .source("http://localhost/")
+//!     .ty("example.demo")
+//!     .build()
+//!     .unwrap();
+//!
+//! let bytes = encode_event(event.clone()).unwrap();
+//! assert_eq!(event, decode_event(&bytes).unwrap());
+//! ```
+
+use crate::event::{is_json_content_type, AttributeValue, Data, SpecVersion};
+use crate::message::{BinaryDeserializer, BinarySerializer, Error, MessageAttributeValue, Result};
+use crate::{AttributesReader, Event};
+use apache_avro::types::Value;
+use apache_avro::{from_avro_datum, to_avro_datum, Schema};
+use lazy_static::lazy_static;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+/// Content type of a structured mode message encoded with the Avro Event Format
+pub const CLOUDEVENTS_AVRO_CONTENT_TYPE: &str = "application/cloudevents+avro";
+
+lazy_static! {
+    static ref SCHEMA: Schema = Schema::parse_str(include_str!("cloudevents.avsc")).unwrap();
+}
+
+// Branch indexes of the `attribute` map values union
+const ATTRIBUTE_BOOLEAN: u32 = 1;
+const ATTRIBUTE_INT: u32 = 2;
+const ATTRIBUTE_STRING: u32 = 3;
+
+// Branch indexes of the `data` union
+const DATA_BYTES: u32 = 0;
+const DATA_NULL: u32 = 1;
+const DATA_STRING: u32 = 6;
+
+/// The canonical CloudEvents Avro schema
+pub fn schema() -> &'static Schema {
+    &SCHEMA
+}
+
+macro_rules! avro_error {
+    ($e:expr) => {
+        Error::Other {
+            source: Box::new($e),
+        }
+    };
+}
+
+fn attribute_to_value(value: AttributeValue<'_>) -> Value {
+    match value {
+        AttributeValue::Boolean(b) => Value::Union(ATTRIBUTE_BOOLEAN, Box::new(Value::Boolean(*b))),
+        // The CloudEvents Integer type is 32-bit, so larger values are sent as strings
+        AttributeValue::Integer(i) => match i32::try_from(*i) {
+            Ok(i) => Value::Union(ATTRIBUTE_INT, Box::new(Value::Int(i))),
+            Err(_) => Value::Union(ATTRIBUTE_STRING, Box::new(Value::String(i.to_string()))),
+        },
+        AttributeValue::Time(t) => {
+            Value::Union(ATTRIBUTE_STRING, Box::new(Value::String(t.to_rfc3339())))
+        }
+        v => Value::Union(ATTRIBUTE_STRING, Box::new(Value::String(v.to_string()))),
+    }
+}
+
+/// Convert an [`Event`] to an Avro [`Value`] of the CloudEvents schema
+pub fn event_to_value(event: Event) -> Value {
+    let attributes: HashMap<String, Value> = event
+        .attributes_iter()
+        .map(|(name, value)| (name.to_string(), attribute_to_value(value)))
+        .collect();
+
+    let data = match event.data {
+        Some(Data::Binary(b)) => Value::Union(DATA_BYTES, Box::new(Value::Bytes(b))),
+        Some(Data::String(s)) => Value::Union(DATA_STRING, Box::new(Value::String(s))),
+        Some(Data::Json(j)) => Value::Union(DATA_STRING, Box::new(Value::String(j.to_string()))),
+        None => Value::Union(DATA_NULL, Box::new(Value::Null)),
+    };
+
+    Value::Record(vec![
+        (String::from("attribute"), Value::Map(attributes)),
+        (String::from("data"), data),
+    ])
+}
+
+fn value_to_attribute(value: Value) -> Result<Option<MessageAttributeValue<'static>>> {
+    match value {
+        Value::Union(_, v) => value_to_attribute(*v),
+        Value::Null => Ok(None),
+        Value::Boolean(b) => Ok(Some(MessageAttributeValue::Boolean(b))),
+        Value::Int(i) => Ok(Some(MessageAttributeValue::Integer(i.into()))),
+        Value::String(s) => Ok(Some(MessageAttributeValue::from(s))),
+        Value::Bytes(b) => Ok(Some(MessageAttributeValue::from(b))),
+        v => Err(Error::Other {
+            source: format!("Unexpected attribute value: {:?}", v).into(),
+        }),
+    }
+}
+
+/// Wrapper for the `attribute` map of an Avro CloudEvent,
+/// which implements [`BinaryDeserializer`]
+struct AvroAttributes(HashMap<String, Value>);
+
+impl BinaryDeserializer for AvroAttributes {
+    fn deserialize_binary<R: Sized, V: BinarySerializer<R>>(mut self, mut visitor: V) -> Result<R> {
+        let spec_version = self
+            .0
+            .remove("specversion")
+            .map(value_to_attribute)
+            .transpose()?
+            .flatten()
+            .ok_or(Error::WrongEncoding {})?;
+        let spec_version = SpecVersion::try_from(spec_version.to_string().as_str())?;
+
+        visitor = visitor.set_spec_version(spec_version.clone())?;
+
+        let attributes = spec_version.attribute_names();
+
+        for (name, value) in self.0 {
+            if let Some(value) = value_to_attribute(value)? {
+                if attributes.contains(&name.as_str()) {
+                    visitor = visitor.set_attribute(&name, value)?
+                } else {
+                    visitor = visitor.set_extension(&name, value)?
+                }
+            }
+        }
+
+        visitor.end()
+    }
+}
+
+/// Convert an Avro [`Value`] of the CloudEvents schema to an [`Event`]
+pub fn value_to_event(value: Value) -> Result<Event> {
+    let mut fields = match value {
+        Value::Record(fields) => fields.into_iter().collect::<HashMap<String, Value>>(),
+        v => {
+            return Err(Error::Other {
+                source: format!("Expected a CloudEvent record, found: {:?}", v).into(),
+            })
+        }
+    };
+
+    let attributes = match fields.remove("attribute") {
+        Some(Value::Map(attributes)) => attributes,
+        _ => return Err(Error::WrongEncoding {}),
+    };
+
+    let mut event = BinaryDeserializer::into_event(AvroAttributes(attributes))?;
+
+    let data = match fields.remove("data") {
+        Some(Value::Union(_, v)) => *v,
+        Some(v) => v,
+        None => Value::Null,
+    };
+
+    event.data = match data {
+        Value::Null => None,
+        Value::Bytes(b) => Some(Data::Binary(b)),
+        Value::String(s) => match event.get_datacontenttype() {
+            Some(ct) if is_json_content_type(ct) => Some(Data::Json(serde_json::from_str(&s)?)),
+            _ => Some(Data::String(s)),
+        },
+        v => Some(Data::Json(
+            serde_json::Value::try_from(v).map_err(|e| avro_error!(e))?,
+        )),
+    };
+
+    Ok(event)
+}
+
+/// Encode an [`Event`] with the Avro Event Format
+pub fn encode_event(event: Event) -> Result<Vec<u8>> {
+    to_avro_datum(schema(), event_to_value(event)).map_err(|e| avro_error!(e))
+}
+
+/// Decode an [`Event`] encoded with the Avro Event Format
+pub fn decode_event(bytes: &[u8]) -> Result<Event> {
+    let value = from_avro_datum(schema(), &mut &bytes[..], None).map_err(|e| avro_error!(e))?;
+    value_to_event(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EventBuilder, EventBuilderV03, EventBuilderV10};
+    use chrono::{TimeZone, Utc};
+    use serde_json::json;
+
+    fn attribute<'a>(value: &'a Value, name: &str) -> Option<&'a Value> {
+        match value {
+            Value::Record(fields) => match &fields[0].1 {
+                Value::Map(attributes) => attributes.get(name),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_schema() {
+        assert!(matches!(schema(), Schema::Record(_)));
+    }
+
+    #[test]
+    fn test_attribute_types() {
+        let event = EventBuilderV10::new()
+            .id("0001")
+            .ty("example.test")
+            .source("http://localhost/")
+            .time(Utc.timestamp(1_600_000_000, 0))
+            .extension("someint", 10)
+            .extension("somebool", true)
+            .build()
+            .unwrap();
+
+        let value = event_to_value(event);
+
+        assert_eq!(
+            Some(&Value::Union(
+                ATTRIBUTE_STRING,
+                Box::new(Value::String(String::from("1.0")))
+            )),
+            attribute(&value, "specversion")
+        );
+        assert_eq!(
+            Some(&Value::Union(
+                ATTRIBUTE_STRING,
+                Box::new(Value::String(String::from("2020-09-13T12:26:40+00:00")))
+            )),
+            attribute(&value, "time")
+        );
+        assert_eq!(
+            Some(&Value::Union(ATTRIBUTE_INT, Box::new(Value::Int(10)))),
+            attribute(&value, "someint")
+        );
+        assert_eq!(
+            Some(&Value::Union(
+                ATTRIBUTE_BOOLEAN,
+                Box::new(Value::Boolean(true))
+            )),
+            attribute(&value, "somebool")
+        );
+    }
+
+    #[test]
+    fn test_roundtrip_json_data() {
+        let event = EventBuilderV10::new()
+            .id("0001")
+            .ty("example.test")
+            .source("http://localhost/")
+            .subject("cloudevent")
+            .time(Utc.timestamp(1_600_000_000, 0))
+            .data_with_schema(
+                "application/json",
+                "http://localhost/schema",
+                json!({"hello": "world"}),
+            )
+            .extension("someint", 10)
+            .extension("somebool", true)
+            .extension("somestring", "hello")
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            event.clone(),
+            decode_event(&encode_event(event).unwrap()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_roundtrip_binary_data() {
+        let event = EventBuilderV10::new()
+            .id("0001")
+            .ty("example.test")
+            .source("http://localhost/")
+            .data("application/octet-stream", vec![0u8, 1, 2, 3])
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            event.clone(),
+            decode_event(&encode_event(event).unwrap()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_roundtrip_v03_text_data() {
+        let event = EventBuilderV03::new()
+            .id("0001")
+            .ty("example.test")
+            .source("http://localhost/")
+            .data("text/plain", String::from("hello"))
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            event.clone(),
+            decode_event(&encode_event(event).unwrap()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_json_to_avro_roundtrip() {
+        let json = json!({
+            "specversion": "1.0",
+            "id": "0001",
+            "type": "example.test",
+            "source": "http://localhost/",
+            "datacontenttype": "application/json",
+            "someint": 10,
+            "data": {"hello": "world"}
+        });
+
+        let event: Event = serde_json::from_value(json.clone()).unwrap();
+        let decoded = decode_event(&encode_event(event).unwrap()).unwrap();
+
+        assert_eq!(json, serde_json::to_value(decoded).unwrap());
+    }
+
+    #[test]
+    fn test_missing_spec_version() {
+        let value = Value::Record(vec![
+            (String::from("attribute"), Value::Map(HashMap::new())),
+            (
+                String::from("data"),
+                Value::Union(DATA_NULL, Box::new(Value::Null)),
+            ),
+        ]);
+
+        assert!(value_to_event(value).is_err());
+    }
+}
@@ -0,0 +1,338 @@
+//! Integration with [Actix Web](https://github.com/actix/actix-web), built on top of the
+//! [`http`](super::http) binding.
+//!
+//! [`Event`] implements [`FromRequest`], parsing both binary and structured mode requests,
+//! and [`Responder`], writing the event as a binary mode response,
+//! so handlers can take and return events directly:
+//!
+//! ```
+//! use actix_web::{post, Responder};
+//! use cloudevents::Event;
+//!
+//! #[post("/")]
+//! async fn echo(event: Event) -> impl Responder {
+//!     event
+//! }
+//! ```
+//!
+//! [`Batch`] implements them too, for batched mode requests and responses.
+//!
+//! The [`WebhookValidation`] middleware replies to the abuse protection handshake of the
+//! HTTP WebHook spec, see [`WebhookValidator`]:
+//!
+//! ```
+//! use actix_web::App;
+//! use cloudevents::binding::actix::WebhookValidation;
+//! use cloudevents::binding::http::WebhookValidator;
+//!
+//! let app = App::new().wrap(WebhookValidation::new(
+//!     WebhookValidator::new().allow_origin("eventemitter.example.com"),
+//! ));
+//! ```
+
+use super::http::{batch_to_message, event_to_binary, receive_event, to_batch, WebhookValidator};
+use crate::message::Received;
+use crate::{Batch, Event};
+use actix_web::dev::{
+    HttpResponseBuilder, Payload, Service, ServiceRequest, ServiceResponse, Transform,
+};
+use actix_web::error::{ErrorInternalServerError, InternalError};
+use actix_web::http::{HeaderMap, StatusCode};
+use actix_web::web::Bytes;
+use actix_web::{Error, FromRequest, HttpRequest, HttpResponse, Responder};
+use futures::future::{ready, Either, LocalBoxFuture, Ready};
+use futures::FutureExt;
+use std::task::{Context, Poll};
+
+/// Copy the actix request headers into an [`http::HeaderMap`](::http::HeaderMap)
+fn to_http_headers(headers: &HeaderMap) -> ::http::HeaderMap {
+    headers
+        .iter()
+        .map(|(hn, hv)| (hn.clone(), hv.clone()))
+        .collect()
+}
+
+/// Read the body of `req`, parse it with `parse` and intercept it with the installed
+/// [`Pipeline`](crate::message::Pipeline), failing with the status code of the error
+fn parse_request<T: Received + 'static>(
+    req: &HttpRequest,
+    payload: &mut Payload,
+    parse: fn(&::http::HeaderMap, Vec<u8>) -> crate::message::Result<T>,
+) -> LocalBoxFuture<'static, Result<T, Error>> {
+    let headers = to_http_headers(req.headers());
+    let bytes = Bytes::from_request(req, payload);
+    async move {
+        let body = bytes.await?;
+        let mut value = parse(&headers, body.to_vec()).map_err(request_error)?;
+        value
+            .intercept_receive("http", None)
+            .await
+            .map_err(request_error)?;
+        Ok(value)
+    }
+    .boxed_local()
+}
+
+/// Fail a request which couldn't be read with the status code of `error`
+fn request_error(error: crate::message::Error) -> Error {
+    let status = StatusCode::from_u16(error.status_code()).unwrap_or(StatusCode::BAD_REQUEST);
+    InternalError::new(error, status).into()
+}
+
+/// Write the message returned by `serialize` in an `200 OK` response
+fn message_response(
+    serialize: crate::message::Result<(::http::HeaderMap, Vec<u8>)>,
+) -> Ready<Result<HttpResponse, Error>> {
+    ready(
+        serialize
+            .map_err(ErrorInternalServerError)
+            .map(|(headers, body)| {
+                let mut builder = HttpResponseBuilder::new(StatusCode::OK);
+                for (hn, hv) in headers.iter() {
+                    builder.header(hn.clone(), hv.clone());
+                }
+                builder.body(body)
+            }),
+    )
+}
+
+impl FromRequest for Event {
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self, Self::Error>>;
+    type Config = ();
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        parse_request(req, payload, receive_event)
+    }
+}
+
+impl Responder for Event {
+    type Error = Error;
+    type Future = Ready<Result<HttpResponse, Self::Error>>;
+
+    fn respond_to(self, _req: &HttpRequest) -> Self::Future {
+        message_response(event_to_binary(self))
+    }
+}
+
+impl FromRequest for Batch {
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self, Self::Error>>;
+    type Config = ();
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        parse_request(req, payload, to_batch)
+    }
+}
+
+impl Responder for Batch {
+    type Error = Error;
+    type Future = Ready<Result<HttpResponse, Self::Error>>;
+
+    fn respond_to(self, _req: &HttpRequest) -> Self::Future {
+        message_response(batch_to_message(self))
+    }
+}
+
+/// Middleware replying to the webhook validation requests with a [`WebhookValidator`], see
+/// [`WebhookValidator::validate`]. Any other request is passed to the wrapped service.
+#[derive(Debug, Clone)]
+pub struct WebhookValidation {
+    validator: WebhookValidator,
+}
+
+impl WebhookValidation {
+    /// Create a new [`WebhookValidation`] middleware replying with `validator`
+    pub fn new(validator: WebhookValidator) -> Self {
+        WebhookValidation { validator }
+    }
+}
+
+impl<S, B> Transform<S> for WebhookValidation
+where
+    S: Service<Request = ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+{
+    type Request = ServiceRequest;
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Transform = WebhookValidationService<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(WebhookValidationService {
+            service,
+            validator: self.validator.clone(),
+        }))
+    }
+}
+
+/// Service created by the [`WebhookValidation`] middleware
+#[derive(Debug)]
+pub struct WebhookValidationService<S> {
+    service: S,
+    validator: WebhookValidator,
+}
+
+impl<S, B> Service for WebhookValidationService<S>
+where
+    S: Service<Request = ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+{
+    type Request = ServiceRequest;
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = Either<S::Future, Ready<Result<Self::Response, Self::Error>>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: ServiceRequest) -> Self::Future {
+        let headers = to_http_headers(req.headers());
+        match self.validator.validate(req.method(), &headers) {
+            Some(response) => {
+                let mut builder = HttpResponseBuilder::new(response.status());
+                for (hn, hv) in response.headers() {
+                    builder.header(hn.clone(), hv.clone());
+                }
+                Either::Right(ready(Ok(req.into_response(builder.finish().into_body()))))
+            }
+            None => Either::Left(self.service.call(req)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::binding::http::to_event;
+    use crate::{EventBuilder, EventBuilderV10};
+    use actix_web::test;
+    use serde_json::json;
+
+    fn expected_event() -> Event {
+        EventBuilderV10::new()
+            .id("0001")
+            .ty("example.test")
+            .source("http://localhost/")
+            .data("application/json", json!({"hello": "world"}))
+            .extension("someint", "10")
+            .build()
+            .unwrap()
+    }
+
+    #[actix_rt::test]
+    async fn test_extract_binary() {
+        let expected = expected_event();
+
+        let (req, mut payload) = test::TestRequest::post()
+            .header("ce-specversion", "1.0")
+            .header("ce-id", "0001")
+            .header("ce-type", "example.test")
+            .header("ce-source", "http://localhost/")
+            .header("ce-someint", "10")
+            .header("content-type", "application/json")
+            .set_payload(json!({"hello": "world"}).to_string())
+            .to_http_parts();
+
+        let event = Event::from_request(&req, &mut payload).await.unwrap();
+
+        assert_eq!(expected, event);
+    }
+
+    #[actix_rt::test]
+    async fn test_extract_structured() {
+        let expected = expected_event();
+
+        let (req, mut payload) = test::TestRequest::post()
+            .header("content-type", "application/cloudevents+json")
+            .set_payload(serde_json::to_vec(&expected).unwrap())
+            .to_http_parts();
+
+        let event = Event::from_request(&req, &mut payload).await.unwrap();
+
+        assert_eq!(expected, event);
+    }
+
+    #[actix_rt::test]
+    async fn test_extract_not_an_event() {
+        let (req, mut payload) = test::TestRequest::post()
+            .header("content-type", "application/json")
+            .set_payload(json!({"hello": "world"}).to_string())
+            .to_http_parts();
+
+        assert!(Event::from_request(&req, &mut payload).await.is_err());
+    }
+
+    #[actix_rt::test]
+    async fn test_respond() {
+        let expected = expected_event();
+
+        let req = test::TestRequest::default().to_http_request();
+        let resp = expected.clone().respond_to(&req).await.unwrap();
+
+        let headers = to_http_headers(resp.headers());
+        let body = match resp.body().as_ref() {
+            Some(actix_web::body::Body::Bytes(b)) => b.to_vec(),
+            _ => panic!("body is not bytes"),
+        };
+
+        assert_eq!(expected, to_event(&headers, body).unwrap());
+    }
+
+    #[actix_rt::test]
+    async fn test_batch() {
+        let expected = Batch::from(vec![expected_event(), expected_event()]);
+
+        let (req, mut payload) = test::TestRequest::post()
+            .header("content-type", "application/cloudevents-batch+json")
+            .set_payload(serde_json::to_vec(&expected).unwrap())
+            .to_http_parts();
+        let batch = Batch::from_request(&req, &mut payload).await.unwrap();
+        assert_eq!(expected, batch);
+
+        let resp = batch.respond_to(&req).await.unwrap();
+        let headers = to_http_headers(resp.headers());
+        let body = match resp.body().as_ref() {
+            Some(actix_web::body::Body::Bytes(b)) => b.to_vec(),
+            _ => panic!("body is not bytes"),
+        };
+        assert_eq!(expected, to_batch(&headers, body).unwrap());
+    }
+
+    #[actix_rt::test]
+    async fn test_webhook_validation() {
+        let mut app = test::init_service(
+            actix_web::App::new()
+                .wrap(WebhookValidation::new(
+                    WebhookValidator::new().allow_origin("example.com"),
+                ))
+                .route("/", actix_web::web::post().to(|_: Event| async { "ok" })),
+        )
+        .await;
+
+        let request = |origin| {
+            test::TestRequest::with_uri("/")
+                .method(actix_web::http::Method::OPTIONS)
+                .header("webhook-request-origin", origin)
+                .to_request()
+        };
+        let response = test::call_service(&mut app, request("example.com")).await;
+        assert_eq!(StatusCode::OK, response.status());
+        assert_eq!(
+            "example.com",
+            response.headers().get("webhook-allowed-origin").unwrap()
+        );
+
+        let response = test::call_service(&mut app, request("other.com")).await;
+        assert_eq!(StatusCode::FORBIDDEN, response.status());
+
+        let request = test::TestRequest::post()
+            .uri("/")
+            .header("content-type", "application/cloudevents+json")
+            .set_payload(serde_json::to_vec(&expected_event()).unwrap())
+            .to_request();
+        let response = test::call_service(&mut app, request).await;
+        assert_eq!(StatusCode::OK, response.status());
+    }
+}
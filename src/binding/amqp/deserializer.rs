@@ -0,0 +1,240 @@
+use super::{AmqpMessage, EventMessage, ATTRIBUTE_PREFIX, LEGACY_ATTRIBUTE_PREFIX, SPEC_VERSION};
+use crate::event::SpecVersion;
+use crate::message::{
+    event_formats, BinaryDeserializer, BinarySerializer, Encoding, Error, MessageAttributeValue,
+    MessageDeserializer, Result, StructuredDeserializer, StructuredSerializer,
+};
+use crate::Event;
+use bytes::Bytes;
+use chrono::{TimeZone, Utc};
+use fe2o3_amqp_types::messaging::Body;
+use fe2o3_amqp_types::primitives::{SimpleValue, Value};
+use std::convert::TryFrom;
+
+impl TryFrom<SimpleValue> for MessageAttributeValue<'_> {
+    type Error = Error;
+
+    fn try_from(value: SimpleValue) -> Result<Self> {
+        match value {
+            SimpleValue::Bool(b) => Ok(MessageAttributeValue::Boolean(b)),
+            SimpleValue::Long(i) => Ok(MessageAttributeValue::Integer(i)),
+            SimpleValue::Int(i) => Ok(MessageAttributeValue::Integer(i.into())),
+            SimpleValue::Short(i) => Ok(MessageAttributeValue::Integer(i.into())),
+            SimpleValue::Byte(i) => Ok(MessageAttributeValue::Integer(i.into())),
+            SimpleValue::Uint(i) => Ok(MessageAttributeValue::Integer(i.into())),
+            SimpleValue::Ushort(i) => Ok(MessageAttributeValue::Integer(i.into())),
+            SimpleValue::Ubyte(i) => Ok(MessageAttributeValue::Integer(i.into())),
+            SimpleValue::String(s) => Ok(MessageAttributeValue::from(s)),
+            SimpleValue::Symbol(s) => Ok(MessageAttributeValue::from(s.0)),
+            SimpleValue::Binary(b) => Ok(MessageAttributeValue::from(b.into_vec())),
+            SimpleValue::Timestamp(t) => Ok(MessageAttributeValue::from(
+                Utc.timestamp_millis(t.milliseconds()),
+            )),
+            v => Err(Error::Other {
+                source: format!("Unsupported application property value: {:?}", v).into(),
+            }),
+        }
+    }
+}
+
+/// Strip the CloudEvents prefix from an application property name
+fn attribute_name(property: &str) -> Option<&str> {
+    property
+        .strip_prefix(ATTRIBUTE_PREFIX)
+        .or_else(|| property.strip_prefix(LEGACY_ATTRIBUTE_PREFIX))
+}
+
+fn body_to_bytes(body: Body<Value>) -> Result<Option<Vec<u8>>> {
+    match body {
+        Body::Data(data) => Ok(Some(
+            data.into_iter().flat_map(|d| d.0.into_vec()).collect(),
+        )),
+        Body::Value(v) => match v.0 {
+            Value::Binary(b) => Ok(Some(b.into_vec())),
+            Value::String(s) => Ok(Some(s.into_bytes())),
+            v => Err(Error::Other {
+                source: format!("Unsupported amqp-value body: {:?}", v).into(),
+            }),
+        },
+        Body::Empty => Ok(None),
+        Body::Sequence(_) => Err(Error::Other {
+            source: "Unsupported amqp-sequence body".into(),
+        }),
+    }
+}
+
+impl BinaryDeserializer for EventMessage {
+    fn deserialize_binary<R: Sized, V: BinarySerializer<R>>(self, mut visitor: V) -> Result<R> {
+        if self.encoding() != Encoding::BINARY {
+            return Err(Error::WrongEncoding {});
+        }
+
+        let mut properties = self
+            .application_properties
+            .map(|p| p.0.into_inner())
+            .unwrap_or_default();
+
+        let spec_version = properties
+            .iter()
+            .find(|(k, _)| attribute_name(k) == Some(SPEC_VERSION))
+            .map(|(k, _)| k.clone())
+            .and_then(|k| properties.shift_remove(&k))
+            .ok_or(Error::WrongEncoding {})?;
+        let spec_version = SpecVersion::try_from(
+            MessageAttributeValue::try_from(spec_version)?
+                .to_string()
+                .as_str(),
+        )?;
+
+        visitor = visitor.set_spec_version(spec_version.clone())?;
+
+        let attributes = spec_version.attribute_names();
+
+        for (property, value) in properties {
+            if let Some(name) = attribute_name(&property) {
+                let value = MessageAttributeValue::try_from(value)?;
+                if attributes.contains(&name) {
+                    visitor = visitor.set_attribute(name, value)?
+                } else {
+                    visitor = visitor.set_extension(name, value)?
+                }
+            }
+        }
+
+        if let Some(content_type) = self.content_type {
+            visitor = visitor.set_attribute(
+                "datacontenttype",
+                MessageAttributeValue::from(content_type.0),
+            )?
+        }
+
+        match body_to_bytes(self.body)? {
+            Some(bytes) => visitor.end_with_data(Bytes::from(bytes)),
+            None => visitor.end(),
+        }
+    }
+}
+
+impl StructuredDeserializer for EventMessage {
+    fn deserialize_structured<R: Sized, V: StructuredSerializer<R>>(self, visitor: V) -> Result<R> {
+        if self.encoding() != Encoding::STRUCTURED {
+            return Err(Error::WrongEncoding {});
+        }
+        let content_type = self.content_type.as_ref().map(|ct| ct.0.clone());
+        let payload = body_to_bytes(self.body)?.unwrap_or_default();
+        visitor.set_structured_event(event_formats().to_json(content_type.as_deref(), payload)?)
+    }
+}
+
+impl MessageDeserializer for EventMessage {
+    fn encoding(&self) -> Encoding {
+        let is_structured = self
+            .content_type
+            .as_ref()
+            .is_some_and(|ct| event_formats().contains(&ct.0));
+
+        let is_binary = self
+            .application_properties
+            .as_ref()
+            .map(|p| p.0.keys().any(|k| attribute_name(k) == Some(SPEC_VERSION)))
+            .unwrap_or(false);
+
+        if is_structured {
+            Encoding::STRUCTURED
+        } else if is_binary {
+            Encoding::BINARY
+        } else {
+            Encoding::UNKNOWN
+        }
+    }
+}
+
+/// Method to decode an [`AmqpMessage`] to [`Event`],
+/// detecting whether it's encoded in binary or structured mode
+pub fn amqp_message_to_event(message: AmqpMessage) -> Result<Event> {
+    Event::try_from(message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EventBuilder, EventBuilderV10};
+    use fe2o3_amqp_types::messaging::{ApplicationProperties, Data};
+    use fe2o3_amqp_types::primitives::{Binary, Symbol};
+    use serde_json::json;
+
+    fn expected_event() -> Event {
+        EventBuilderV10::new()
+            .id("0001")
+            .ty("example.test")
+            .source("http://localhost/")
+            .data("application/json", json!({"hello": "world"}))
+            .extension("someint", 10)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_binary_roundtrip() {
+        let expected = expected_event();
+
+        let message = AmqpMessage::try_from(expected.clone()).unwrap();
+
+        assert_eq!(expected, Event::try_from(message).unwrap());
+    }
+
+    #[test]
+    fn test_structured_roundtrip() {
+        let expected = expected_event();
+
+        let message =
+            AmqpMessage::from(EventMessage::from_structured_event(expected.clone()).unwrap());
+
+        assert_eq!(expected, amqp_message_to_event(message).unwrap());
+    }
+
+    #[test]
+    fn test_legacy_prefix() {
+        let mut properties = ApplicationProperties::default();
+        for (k, v) in &[
+            ("cloudEvents:specversion", "1.0"),
+            ("cloudEvents:id", "0001"),
+            ("cloudEvents:type", "example.test"),
+            ("cloudEvents:source", "http://localhost/"),
+        ] {
+            properties
+                .0
+                .insert(k.to_string(), SimpleValue::String(v.to_string()));
+        }
+        properties
+            .0
+            .insert(String::from("cloudEvents:someint"), SimpleValue::Long(10));
+
+        let message = EventMessage {
+            content_type: Some(Symbol::from("application/json")),
+            application_properties: Some(properties),
+            body: Body::Data(
+                vec![Data(Binary::from(
+                    json!({"hello": "world"}).to_string().into_bytes(),
+                ))]
+                .into(),
+            ),
+        };
+
+        assert_eq!(
+            expected_event(),
+            Event::try_from(AmqpMessage::from(message)).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_not_an_event() {
+        let message = EventMessage {
+            content_type: Some(Symbol::from("application/json")),
+            application_properties: None,
+            body: Body::Empty,
+        };
+
+        assert!(Event::try_from(AmqpMessage::from(message)).is_err());
+    }
+}
@@ -0,0 +1,119 @@
+//! [AMQP Protocol Binding](https://github.com/cloudevents/spec/blob/v1.0.1/amqp-protocol-binding.md)
+//! on top of the [`fe2o3-amqp`](https://docs.rs/fe2o3-amqp) message types, supporting both binary and structured mode.
+//!
+//! In binary mode, context attributes and extensions are mapped to `cloudEvents_`-prefixed
+//! application properties and `datacontenttype` to the `content-type` message property.
+//!
+//! ```
+//! use cloudevents::binding::amqp::AmqpMessage;
+//! use cloudevents::{Event, EventBuilder, EventBuilderV10};
+//! use std::convert::TryFrom;
+//!
+//! let event = EventBuilderV10::new()
+//!     .id("0001")
+//!     .source("http://localhost/")
+//!     .ty("example.demo")
+//!     .build()
+//!     .unwrap();
+//!
+//! let message = AmqpMessage::try_from(event.clone()).unwrap();
+//! assert_eq!(event, Event::try_from(message).unwrap());
+//! ```
+
+mod deserializer;
+mod serializer;
+
+use crate::message::{MessageDeserializer, Result};
+use crate::Event;
+use fe2o3_amqp_types::messaging::{ApplicationProperties, Body, Message, Properties};
+use fe2o3_amqp_types::primitives::{Symbol, Value};
+use std::convert::TryFrom;
+
+pub use deserializer::amqp_message_to_event;
+
+pub(crate) const ATTRIBUTE_PREFIX: &str = "cloudEvents_";
+pub(crate) const LEGACY_ATTRIBUTE_PREFIX: &str = "cloudEvents:";
+pub(crate) const SPEC_VERSION: &str = "specversion";
+pub(crate) const CLOUDEVENTS_JSON_CONTENT_TYPE: &str = "application/cloudevents+json";
+
+/// Type alias for an AMQP 1.0 message carrying an [`Event`]
+pub type AmqpMessage = Message<AmqpBody>;
+
+/// Type alias for the body of an [`AmqpMessage`]
+pub type AmqpBody = Body<Value>;
+
+/// The sections of an [`AmqpMessage`] used by the CloudEvents binding,
+/// which implements [`StructuredSerializer`](crate::message::StructuredSerializer),
+/// [`BinarySerializer`](crate::message::BinarySerializer) and [`MessageDeserializer`]
+pub struct EventMessage {
+    pub content_type: Option<Symbol>,
+    pub application_properties: Option<ApplicationProperties>,
+    pub body: AmqpBody,
+}
+
+impl EventMessage {
+    fn new() -> EventMessage {
+        EventMessage {
+            content_type: None,
+            application_properties: None,
+            body: Body::Empty,
+        }
+    }
+
+    /// Create an [`EventMessage`] from an [`Event`] in binary mode
+    pub fn from_binary_event(event: Event) -> Result<EventMessage> {
+        crate::message::BinaryDeserializer::deserialize_binary(event, EventMessage::new())
+    }
+
+    /// Create an [`EventMessage`] from an [`Event`] in structured mode
+    pub fn from_structured_event(event: Event) -> Result<EventMessage> {
+        crate::message::StructuredDeserializer::deserialize_structured(event, EventMessage::new())
+    }
+}
+
+impl From<EventMessage> for AmqpMessage {
+    fn from(message: EventMessage) -> Self {
+        let properties = message.content_type.map(|content_type| Properties {
+            content_type: Some(content_type),
+            ..Default::default()
+        });
+
+        Message {
+            header: None,
+            delivery_annotations: None,
+            message_annotations: None,
+            properties,
+            application_properties: message.application_properties,
+            body: message.body,
+            footer: None,
+        }
+    }
+}
+
+impl From<AmqpMessage> for EventMessage {
+    fn from(message: AmqpMessage) -> Self {
+        EventMessage {
+            content_type: message.properties.and_then(|p| p.content_type),
+            application_properties: message.application_properties,
+            body: message.body,
+        }
+    }
+}
+
+impl TryFrom<Event> for AmqpMessage {
+    type Error = crate::message::Error;
+
+    /// Convert an [`Event`] to an [`AmqpMessage`] in binary mode
+    fn try_from(event: Event) -> Result<Self> {
+        EventMessage::from_binary_event(event).map(AmqpMessage::from)
+    }
+}
+
+impl TryFrom<AmqpMessage> for Event {
+    type Error = crate::message::Error;
+
+    /// Convert an [`AmqpMessage`] to an [`Event`], detecting whether it's encoded in binary or structured mode
+    fn try_from(message: AmqpMessage) -> Result<Self> {
+        MessageDeserializer::into_event(EventMessage::from(message))
+    }
+}
@@ -0,0 +1,149 @@
+use super::{EventMessage, ATTRIBUTE_PREFIX, CLOUDEVENTS_JSON_CONTENT_TYPE, SPEC_VERSION};
+use crate::event::SpecVersion;
+use crate::message::{BinarySerializer, MessageAttributeValue, Result, StructuredSerializer};
+use bytes::Bytes;
+use fe2o3_amqp_types::messaging::{ApplicationProperties, Body, Data};
+use fe2o3_amqp_types::primitives::{Binary, SimpleValue, Symbol, Timestamp};
+
+impl From<MessageAttributeValue<'_>> for SimpleValue {
+    fn from(value: MessageAttributeValue) -> Self {
+        match value {
+            MessageAttributeValue::Boolean(b) => SimpleValue::Bool(b),
+            MessageAttributeValue::Integer(i) => SimpleValue::Long(i),
+            MessageAttributeValue::String(s) => SimpleValue::String(s.into_owned()),
+            MessageAttributeValue::Binary(b) => SimpleValue::Binary(Binary::from(b.into_owned())),
+            MessageAttributeValue::Uri(u) => SimpleValue::String(u.to_string()),
+            MessageAttributeValue::UriRef(u) => SimpleValue::String(u.to_string()),
+            MessageAttributeValue::DateTime(d) => {
+                SimpleValue::Timestamp(Timestamp::from_milliseconds(d.timestamp_millis()))
+            }
+        }
+    }
+}
+
+impl EventMessage {
+    fn insert_application_property(&mut self, name: &str, value: SimpleValue) {
+        self.application_properties
+            .get_or_insert_with(ApplicationProperties::default)
+            .0
+            .insert([ATTRIBUTE_PREFIX, name].concat(), value);
+    }
+}
+
+impl BinarySerializer<EventMessage> for EventMessage {
+    fn set_spec_version(mut self, spec_version: SpecVersion) -> Result<Self> {
+        self.insert_application_property(
+            SPEC_VERSION,
+            SimpleValue::String(spec_version.to_string()),
+        );
+        Ok(self)
+    }
+
+    fn set_attribute(mut self, name: &str, value: MessageAttributeValue) -> Result<Self> {
+        if name == "datacontenttype" {
+            self.content_type = Some(Symbol::from(value.to_string()));
+        } else {
+            self.insert_application_property(name, value.into());
+        }
+        Ok(self)
+    }
+
+    fn set_extension(mut self, name: &str, value: MessageAttributeValue) -> Result<Self> {
+        self.insert_application_property(name, value.into());
+        Ok(self)
+    }
+
+    fn end_with_data(mut self, bytes: Bytes) -> Result<EventMessage> {
+        self.body = Body::Data(vec![Data(Binary::from(Vec::from(bytes)))].into());
+        Ok(self)
+    }
+
+    fn end(self) -> Result<EventMessage> {
+        Ok(self)
+    }
+}
+
+impl StructuredSerializer<EventMessage> for EventMessage {
+    fn set_structured_event(mut self, bytes: Vec<u8>) -> Result<EventMessage> {
+        self.content_type = Some(Symbol::from(CLOUDEVENTS_JSON_CONTENT_TYPE));
+        self.body = Body::Data(vec![Data(Binary::from(bytes))].into());
+        Ok(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::binding::amqp::AmqpMessage;
+    use crate::{EventBuilder, EventBuilderV10};
+    use chrono::{TimeZone, Utc};
+    use serde_json::json;
+    use std::convert::TryFrom;
+
+    #[test]
+    fn test_binary_message() {
+        let event = EventBuilderV10::new()
+            .id("0001")
+            .ty("example.test")
+            .source("http://localhost/")
+            .time(Utc.timestamp_millis(1_600_000_000_000))
+            .data("application/json", json!({"hello": "world"}))
+            .extension("someint", 10)
+            .build()
+            .unwrap();
+
+        let message = AmqpMessage::try_from(event).unwrap();
+        let properties = message.application_properties.unwrap().0;
+
+        assert_eq!(
+            Some(&SimpleValue::String(String::from("1.0"))),
+            properties.get("cloudEvents_specversion")
+        );
+        assert_eq!(
+            Some(&SimpleValue::String(String::from("0001"))),
+            properties.get("cloudEvents_id")
+        );
+        assert_eq!(
+            Some(&SimpleValue::Timestamp(Timestamp::from_milliseconds(
+                1_600_000_000_000
+            ))),
+            properties.get("cloudEvents_time")
+        );
+        assert_eq!(
+            Some(&SimpleValue::Long(10)),
+            properties.get("cloudEvents_someint")
+        );
+        assert_eq!(None, properties.get("cloudEvents_datacontenttype"));
+        assert_eq!(
+            Some(Symbol::from("application/json")),
+            message.properties.unwrap().content_type
+        );
+        assert_eq!(
+            Body::Data(
+                vec![Data(Binary::from(
+                    json!({"hello": "world"}).to_string().into_bytes()
+                ))]
+                .into()
+            ),
+            message.body
+        );
+    }
+
+    #[test]
+    fn test_structured_message() {
+        let event = EventBuilderV10::new()
+            .id("0001")
+            .ty("example.test")
+            .source("http://localhost/")
+            .build()
+            .unwrap();
+
+        let message = AmqpMessage::from(EventMessage::from_structured_event(event).unwrap());
+
+        assert!(message.application_properties.is_none());
+        assert_eq!(
+            Some(Symbol::from("application/cloudevents+json")),
+            message.properties.unwrap().content_type
+        );
+    }
+}
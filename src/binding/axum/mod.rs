@@ -0,0 +1,287 @@
+//! Integration with [axum](https://github.com/tokio-rs/axum), built on top of the
+//! [`http`](super::http) binding.
+//!
+//! [`Event`] implements [`FromRequest`], parsing both binary and structured mode requests,
+//! and [`IntoResponse`], writing the event as a binary mode response,
+//! so handlers can take and return events directly:
+//!
+//! ```
+//! use axum::{routing::post, Router};
+//! use cloudevents::Event;
+//!
+//! async fn echo(event: Event) -> Event {
+//!     event
+//! }
+//!
+//! let app: Router = Router::new().route("/", post(echo));
+//! ```
+//!
+//! [`Batch`] implements them too, for batched mode requests and responses.
+//!
+//! The [`webhook_validation`] middleware replies to the abuse protection handshake of the
+//! HTTP WebHook spec, see [`WebhookValidator`]:
+//!
+//! ```
+//! use axum::{middleware, routing::post, Router};
+//! use cloudevents::binding::axum::webhook_validation;
+//! use cloudevents::binding::http::WebhookValidator;
+//! use cloudevents::Event;
+//!
+//! async fn handle(event: Event) {}
+//!
+//! let validator = WebhookValidator::new().allow_origin("eventemitter.example.com");
+//! let app: Router = Router::new()
+//!     .route("/", post(handle))
+//!     .layer(middleware::from_fn_with_state(validator, webhook_validation));
+//! ```
+
+use super::http::{batch_to_message, event_to_binary, receive_event, to_batch, WebhookValidator};
+use crate::message::{Error, Received};
+use crate::{Batch, Event};
+use axum::async_trait;
+use axum::body::{Bytes, HttpBody};
+use axum::extract::{FromRequest, State};
+use axum::http::{Request, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use axum::BoxError;
+
+/// Read the body of `req`, parse it with `parse` and intercept it with the installed
+/// [`Pipeline`](crate::message::Pipeline), rejecting with the status code of the error
+async fn parse_request<S, B, T: Received>(
+    req: Request<B>,
+    state: &S,
+    parse: impl FnOnce(&axum::http::HeaderMap, Bytes) -> Result<T, Error>,
+) -> Result<T, Response>
+where
+    B: HttpBody + Send + 'static,
+    B::Data: Send,
+    B::Error: Into<BoxError>,
+    S: Send + Sync,
+{
+    let headers = req.headers().clone();
+    let body = Bytes::from_request(req, state)
+        .await
+        .map_err(IntoResponse::into_response)?;
+
+    let mut value = parse(&headers, body).map_err(error_response)?;
+    value
+        .intercept_receive("http", None)
+        .await
+        .map_err(error_response)?;
+    Ok(value)
+}
+
+/// Reply to a request which couldn't be read with the status code of `error`
+fn error_response(error: Error) -> Response {
+    let status = StatusCode::from_u16(error.status_code()).unwrap_or(StatusCode::BAD_REQUEST);
+    (status, error.to_string()).into_response()
+}
+
+/// Write the message returned by `serialize`, replying with `500 Internal Server Error` on error
+fn message_response(serialize: Result<(axum::http::HeaderMap, Vec<u8>), Error>) -> Response {
+    match serialize {
+        Ok((headers, body)) => (headers, body).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
+
+#[async_trait]
+impl<S, B> FromRequest<S, B> for Event
+where
+    B: HttpBody + Send + 'static,
+    B::Data: Send,
+    B::Error: Into<BoxError>,
+    S: Send + Sync,
+{
+    type Rejection = Response;
+
+    async fn from_request(req: Request<B>, state: &S) -> Result<Self, Self::Rejection> {
+        parse_request(req, state, receive_event).await
+    }
+}
+
+impl IntoResponse for Event {
+    fn into_response(self) -> Response {
+        message_response(event_to_binary(self))
+    }
+}
+
+#[async_trait]
+impl<S, B> FromRequest<S, B> for Batch
+where
+    B: HttpBody + Send + 'static,
+    B::Data: Send,
+    B::Error: Into<BoxError>,
+    S: Send + Sync,
+{
+    type Rejection = Response;
+
+    async fn from_request(req: Request<B>, state: &S) -> Result<Self, Self::Rejection> {
+        parse_request(req, state, to_batch).await
+    }
+}
+
+impl IntoResponse for Batch {
+    fn into_response(self) -> Response {
+        message_response(batch_to_message(self))
+    }
+}
+
+/// Middleware replying to the webhook validation requests with the [`WebhookValidator`] state,
+/// to be used with [`from_fn_with_state`](axum::middleware::from_fn_with_state).
+/// Any other request is passed to the next handler.
+pub async fn webhook_validation<B>(
+    State(validator): State<WebhookValidator>,
+    request: Request<B>,
+    next: Next<B>,
+) -> Response {
+    match validator.validate(request.method(), request.headers()) {
+        Some(response) => (response.status(), response.headers().clone()).into_response(),
+        None => next.run(request).await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::binding::http::to_event;
+    use crate::{EventBuilder, EventBuilderV10};
+    use axum::body::Body;
+    use serde_json::json;
+
+    fn expected_event() -> Event {
+        EventBuilderV10::new()
+            .id("0001")
+            .ty("example.test")
+            .source("http://localhost/")
+            .data("application/json", json!({"hello": "world"}))
+            .extension("someint", "10")
+            .build()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_extract_binary() {
+        let request = Request::builder()
+            .method("POST")
+            .header("ce-specversion", "1.0")
+            .header("ce-id", "0001")
+            .header("ce-type", "example.test")
+            .header("ce-source", "http://localhost/")
+            .header("ce-someint", "10")
+            .header("content-type", "application/json")
+            .body(Body::from(json!({"hello": "world"}).to_string()))
+            .unwrap();
+
+        let event = Event::from_request(request, &()).await.unwrap();
+
+        assert_eq!(expected_event(), event);
+    }
+
+    #[tokio::test]
+    async fn test_extract_structured() {
+        let expected = expected_event();
+
+        let request = Request::builder()
+            .method("POST")
+            .header("content-type", "application/cloudevents+json")
+            .body(Body::from(serde_json::to_vec(&expected).unwrap()))
+            .unwrap();
+
+        let event = Event::from_request(request, &()).await.unwrap();
+
+        assert_eq!(expected, event);
+    }
+
+    #[tokio::test]
+    async fn test_extract_not_an_event() {
+        let request = Request::builder()
+            .method("POST")
+            .header("content-type", "application/json")
+            .body(Body::from(json!({"hello": "world"}).to_string()))
+            .unwrap();
+
+        let response = Event::from_request(request, &()).await.unwrap_err();
+
+        assert_eq!(StatusCode::BAD_REQUEST, response.status());
+    }
+
+    #[tokio::test]
+    async fn test_webhook_validation() {
+        use axum::routing::post;
+        use axum::Router;
+        use tower::ServiceExt;
+
+        let app = Router::new().route("/", post(|_: Event| async {})).layer(
+            axum::middleware::from_fn_with_state(
+                WebhookValidator::new().allow_origin("example.com"),
+                webhook_validation,
+            ),
+        );
+
+        let request = |origin| {
+            Request::builder()
+                .method("OPTIONS")
+                .header("webhook-request-origin", origin)
+                .body(Body::empty())
+                .unwrap()
+        };
+        let response = app.clone().oneshot(request("example.com")).await.unwrap();
+        assert_eq!(StatusCode::OK, response.status());
+        assert_eq!("example.com", response.headers()["webhook-allowed-origin"]);
+
+        let response = app.clone().oneshot(request("other.com")).await.unwrap();
+        assert_eq!(StatusCode::FORBIDDEN, response.status());
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .header("content-type", "application/cloudevents+json")
+                    .body(Body::from(serde_json::to_vec(&expected_event()).unwrap()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(StatusCode::OK, response.status());
+    }
+
+    #[tokio::test]
+    async fn test_batch() {
+        let expected = Batch::from(vec![expected_event(), expected_event()]);
+
+        let request = Request::builder()
+            .method("POST")
+            .header("content-type", "application/cloudevents-batch+json")
+            .body(Body::from(serde_json::to_vec(&expected).unwrap()))
+            .unwrap();
+        assert_eq!(expected, Batch::from_request(request, &()).await.unwrap());
+
+        let response = expected.clone().into_response();
+        assert_eq!(
+            "application/cloudevents-batch+json",
+            response.headers()["content-type"]
+        );
+        let headers = response.headers().clone();
+        let body = Bytes::from_request(Request::new(response.into_body()), &())
+            .await
+            .unwrap();
+        assert_eq!(expected, to_batch(&headers, body).unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_into_response() {
+        let expected = expected_event();
+
+        let response = expected.clone().into_response();
+        assert_eq!(StatusCode::OK, response.status());
+
+        let headers = response.headers().clone();
+        let body = Bytes::from_request(Request::new(response.into_body()), &())
+            .await
+            .unwrap();
+
+        assert_eq!(expected, to_event(&headers, body).unwrap());
+    }
+}
@@ -0,0 +1,172 @@
+use crate::event::{AttributesReader, Data};
+use crate::message::{Error, MessageAttributeValue, Result};
+use crate::{Event, EventBuilder, EventBuilderV10};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::convert::TryFrom;
+
+/// Extension carrying the `dataVersion` of an [`EventGridEvent`]
+pub const DATAVERSION: &str = "dataversion";
+
+/// Event in the [Event Grid event schema](https://learn.microsoft.com/azure/event-grid/event-schema).
+///
+/// It's converted to and from an [`Event`] following the
+/// [Event Grid mapping](https://learn.microsoft.com/azure/event-grid/cloud-event-schema):
+/// `topic` is the `source`, `eventType` the `type` and `eventTime` the `time`, while `id`,
+/// `subject` and `data` are mapped to the homonymous attributes. The `dataVersion` is carried by
+/// the [`DATAVERSION`] extension.
+///
+/// The Event Grid schema has no other attributes, so the conversion of an [`Event`] drops its
+/// `datacontenttype`, `dataschema` and other extensions, and fails if its data is binary.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EventGridEvent {
+    pub id: String,
+    /// Full resource path of the event source, set by Event Grid when publishing to a topic
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub topic: Option<String>,
+    pub subject: String,
+    pub event_type: String,
+    pub event_time: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data_version: Option<String>,
+    /// Schema version of the event metadata, set by Event Grid
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata_version: Option<String>,
+}
+
+impl TryFrom<EventGridEvent> for Event {
+    type Error = Error;
+
+    /// Convert an [`EventGridEvent`] to a v1.0 [`Event`], failing if it has no `topic`
+    fn try_from(event: EventGridEvent) -> Result<Self> {
+        let topic = event.topic.ok_or(Error::MissingRequiredAttribute {
+            attribute_name: "topic",
+        })?;
+        let mut builder = EventBuilderV10::new()
+            .id(event.id)
+            .source(topic)
+            .ty(event.event_type)
+            .time(event.event_time);
+        if !event.subject.is_empty() {
+            builder = builder.subject(event.subject);
+        }
+        if let Some(data) = event.data {
+            builder = builder.data("application/json", data);
+        }
+        if let Some(data_version) = event.data_version {
+            builder = builder.extension(DATAVERSION, data_version);
+        }
+        Ok(builder.build()?)
+    }
+}
+
+impl TryFrom<Event> for EventGridEvent {
+    type Error = Error;
+
+    /// Convert an [`Event`] to an [`EventGridEvent`], using the current time if the event has no
+    /// `time`, and failing if its data is binary
+    fn try_from(event: Event) -> Result<Self> {
+        let data_version = event
+            .get_extension(DATAVERSION)
+            .map(|version| String::from(MessageAttributeValue::from(version.clone())));
+        let data = match event.get_data::<Data>() {
+            Some(Data::Json(json)) => Some(json),
+            Some(Data::String(s)) => Some(Value::String(s)),
+            Some(Data::Binary(_)) => {
+                return Err(Error::Other {
+                    source: "the Event Grid schema doesn't support binary data".into(),
+                })
+            }
+            None => None,
+        };
+        Ok(EventGridEvent {
+            id: event.get_id().to_string(),
+            topic: Some(event.get_source().to_string()),
+            subject: event.get_subject().unwrap_or_default().to_string(),
+            event_type: event.get_type().to_string(),
+            event_time: event.get_time().copied().unwrap_or_else(Utc::now),
+            data,
+            data_version,
+            metadata_version: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use serde_json::json;
+
+    fn event_grid_event() -> EventGridEvent {
+        EventGridEvent {
+            id: "0001".to_string(),
+            topic: Some("/subscriptions/1234/resourceGroups/rg".to_string()),
+            subject: "/blobs/file.txt".to_string(),
+            event_type: "Microsoft.Storage.BlobCreated".to_string(),
+            event_time: Utc.timestamp(1_600_000_000, 0),
+            data: Some(json!({"contentType": "text/plain"})),
+            data_version: Some("1".to_string()),
+            metadata_version: None,
+        }
+    }
+
+    #[test]
+    fn roundtrip() {
+        let expected = event_grid_event();
+        let event = Event::try_from(expected.clone()).unwrap();
+
+        assert_eq!("0001", event.get_id());
+        assert_eq!(
+            "/subscriptions/1234/resourceGroups/rg",
+            event.get_source().to_string()
+        );
+        assert_eq!("Microsoft.Storage.BlobCreated", event.get_type());
+        assert_eq!(Some("/blobs/file.txt"), event.get_subject());
+        assert_eq!(Some(&"1".into()), event.get_extension(DATAVERSION));
+        assert_eq!(expected, EventGridEvent::try_from(event).unwrap());
+    }
+
+    #[test]
+    fn serde() {
+        let json = json!({
+            "id": "0001",
+            "topic": "/subscriptions/1234/resourceGroups/rg",
+            "subject": "/blobs/file.txt",
+            "eventType": "Microsoft.Storage.BlobCreated",
+            "eventTime": "2020-09-13T12:26:40Z",
+            "data": {"contentType": "text/plain"},
+            "dataVersion": "1"
+        });
+        assert_eq!(
+            event_grid_event(),
+            serde_json::from_value::<EventGridEvent>(json.clone()).unwrap()
+        );
+        assert_eq!(json, serde_json::to_value(event_grid_event()).unwrap());
+    }
+
+    #[test]
+    fn unsupported() {
+        let mut event_grid_event = event_grid_event();
+        event_grid_event.topic = None;
+        assert!(matches!(
+            Event::try_from(event_grid_event),
+            Err(Error::MissingRequiredAttribute {
+                attribute_name: "topic"
+            })
+        ));
+
+        let event = EventBuilderV10::new()
+            .id("0001")
+            .source("http://localhost/")
+            .ty("example.test")
+            .data("application/octet-stream", vec![1u8, 2, 3])
+            .build()
+            .unwrap();
+        assert!(EventGridEvent::try_from(event).is_err());
+    }
+}
@@ -0,0 +1,37 @@
+//! Interoperability with the Azure event infrastructure, enabled with the `azure` feature.
+//!
+//! * [`EventGridEvent`] is the native [Event Grid event schema](https://learn.microsoft.com/azure/event-grid/event-schema),
+//!   converted to and from [`Event`](crate::Event)
+//! * [`to_service_bus_message`] and [`from_service_bus_message`] map events to
+//!   [Service Bus](https://learn.microsoft.com/azure/service-bus-messaging/) messages, on top of
+//!   the [AMQP binding](super::amqp): the attributes are sent as `cloudEvents_` application
+//!   properties, and are mirrored in the broker properties read by the Service Bus features, e.g.
+//!   the duplicate detection and the subscription filters
+//!
+//! ```
+//! use cloudevents::binding::azure::EventGridEvent;
+//! use cloudevents::{AttributesReader, Event};
+//! use serde_json::json;
+//! use std::convert::TryFrom;
+//!
+//! let events: Vec<EventGridEvent> = serde_json::from_value(json!([{
+//!     "id": "831e1650-001e-001b-66ab-eeb76e069631",
+//!     "topic": "/subscriptions/1234/resourceGroups/rg/providers/Microsoft.Storage",
+//!     "subject": "/blobServices/default/containers/container/blobs/file.txt",
+//!     "eventType": "Microsoft.Storage.BlobCreated",
+//!     "eventTime": "2017-06-26T18:41:00.9584103Z",
+//!     "data": {"contentType": "text/plain"},
+//!     "dataVersion": "1",
+//!     "metadataVersion": "1"
+//! }]))
+//! .unwrap();
+//!
+//! let event = Event::try_from(events[0].clone()).unwrap();
+//! assert_eq!("Microsoft.Storage.BlobCreated", event.get_type());
+//! ```
+
+mod eventgrid;
+mod servicebus;
+
+pub use eventgrid::{EventGridEvent, DATAVERSION};
+pub use servicebus::{from_service_bus_message, to_service_bus_message};
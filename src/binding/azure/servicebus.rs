@@ -0,0 +1,92 @@
+use crate::binding::amqp::AmqpMessage;
+use crate::event::AttributesReader;
+use crate::extensions::CORRELATIONID;
+use crate::message::{MessageAttributeValue, Result};
+use crate::Event;
+use fe2o3_amqp_types::messaging::{MessageId, Properties};
+use std::convert::TryFrom;
+
+/// Convert `event` to a Service Bus message in binary mode.
+///
+/// Besides the `cloudEvents_` application properties of the [AMQP binding](crate::binding::amqp),
+/// the `id` is written to the `message-id` broker property, used by the duplicate detection, the
+/// `subject` to the `subject` (label) broker property, and the `correlationid` of the
+/// [`CorrelationExtension`](crate::extensions::CorrelationExtension) to the `correlation-id`
+/// broker property, used by the correlation filters and the request-response pattern.
+///
+/// ```
+/// use cloudevents::binding::azure::{from_service_bus_message, to_service_bus_message};
+/// use cloudevents::{EventBuilder, EventBuilderV10};
+///
+/// let event = EventBuilderV10::new()
+///     .id("0001")
+///     .source("http://localhost/")
+///     .ty("example.demo")
+///     .subject("orders")
+///     .build()
+///     .unwrap();
+///
+/// let message = to_service_bus_message(event.clone()).unwrap();
+/// let properties = message.properties.as_ref().unwrap();
+/// assert_eq!(Some("orders"), properties.subject.as_deref());
+/// assert_eq!(event, from_service_bus_message(message).unwrap());
+/// ```
+pub fn to_service_bus_message(event: Event) -> Result<AmqpMessage> {
+    let message_id = MessageId::String(event.get_id().to_string());
+    let subject = event.get_subject().map(String::from);
+    let correlation_id = event.get_extension(CORRELATIONID).map(|correlation_id| {
+        MessageId::String(String::from(MessageAttributeValue::from(
+            correlation_id.clone(),
+        )))
+    });
+
+    let mut message = AmqpMessage::try_from(event)?;
+    let properties = message.properties.get_or_insert_with(Properties::default);
+    properties.message_id = Some(message_id);
+    properties.subject = subject;
+    properties.correlation_id = correlation_id;
+    Ok(message)
+}
+
+/// Convert a Service Bus `message` to an [`Event`], detecting whether it's encoded in binary or
+/// structured mode. The attributes are read from the application properties, the broker
+/// properties are ignored.
+pub fn from_service_bus_message(message: AmqpMessage) -> Result<Event> {
+    Event::try_from(message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EventBuilder, EventBuilderV10};
+    use serde_json::json;
+
+    #[test]
+    fn broker_properties() {
+        let event = EventBuilderV10::new()
+            .id("0001")
+            .source("http://localhost/")
+            .ty("example.test")
+            .extension("correlationid", "order-42")
+            .data("application/json", json!({"hello": "world"}))
+            .build()
+            .unwrap();
+
+        let message = to_service_bus_message(event.clone()).unwrap();
+        let properties = message.properties.as_ref().unwrap();
+        assert_eq!(
+            Some(MessageId::String(String::from("0001"))),
+            properties.message_id
+        );
+        assert_eq!(None, properties.subject);
+        assert_eq!(
+            Some(MessageId::String(String::from("order-42"))),
+            properties.correlation_id
+        );
+        assert_eq!(
+            Some("application/json"),
+            properties.content_type.as_ref().map(|ct| ct.as_str())
+        );
+        assert_eq!(event, from_service_bus_message(message).unwrap());
+    }
+}
@@ -0,0 +1,86 @@
+use super::to_event;
+use crate::message::{Acknowledger, Delivery, Outcome, Result};
+use ::http::header::{HeaderMap, HeaderValue, RETRY_AFTER};
+use ::http::{Response, StatusCode};
+use bytes::Bytes;
+
+/// [`Acknowledger`] of an event received in an HTTP request, settling it with the response to
+/// reply with: `202 Accepted` when acknowledged, `500 Internal Server Error` when rejected, and
+/// `503 Service Unavailable` with the `Retry-After` header to retry after a delay.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HttpAcknowledger;
+
+impl Acknowledger for HttpAcknowledger {
+    type Output = Response<()>;
+
+    fn settle(self, outcome: Outcome) -> Response<()> {
+        let mut response = Response::new(());
+        *response.status_mut() = match outcome {
+            Outcome::Ack => StatusCode::ACCEPTED,
+            Outcome::Nack => StatusCode::INTERNAL_SERVER_ERROR,
+            Outcome::RetryAfter(delay) => {
+                // Retry-After is in whole seconds, rounded up not to retry too early
+                let seconds = delay.as_secs() + u64::from(delay.subsec_nanos() > 0);
+                response
+                    .headers_mut()
+                    .insert(RETRY_AFTER, HeaderValue::from(seconds));
+                StatusCode::SERVICE_UNAVAILABLE
+            }
+        };
+        response
+    }
+}
+
+/// Decode the request `headers` and `body` to a [`Delivery`], see [`to_event`]
+///
+/// ```
+/// use cloudevents::binding::http::{event_to_binary, to_delivery};
+/// use cloudevents::{EventBuilder, EventBuilderV10};
+/// use std::time::Duration;
+///
+/// let event = EventBuilderV10::new()
+///     .id("0001")
+///     .source("http://localhost/")
+///     .ty("example.demo")
+///     .build()
+///     .unwrap();
+/// let (headers, body) = event_to_binary(event).unwrap();
+///
+/// let delivery = to_delivery(&headers, body).unwrap();
+/// let response = delivery.retry_after(Duration::from_secs(30));
+/// assert_eq!(503, response.status());
+/// assert_eq!("30", response.headers()["retry-after"]);
+/// ```
+pub fn to_delivery(
+    headers: &HeaderMap,
+    body: impl Into<Bytes>,
+) -> Result<Delivery<HttpAcknowledger>> {
+    Ok(Delivery::new(to_event(headers, body)?, HttpAcknowledger))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn responses() {
+        assert_eq!(
+            StatusCode::ACCEPTED,
+            HttpAcknowledger.settle(Outcome::Ack).status()
+        );
+        assert_eq!(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            HttpAcknowledger.settle(Outcome::Nack).status()
+        );
+
+        let response = HttpAcknowledger.settle(Outcome::RetryAfter(Duration::from_millis(1500)));
+        assert_eq!(StatusCode::SERVICE_UNAVAILABLE, response.status());
+        assert_eq!("2", response.headers()[RETRY_AFTER]);
+        assert!(HttpAcknowledger
+            .settle(Outcome::Ack)
+            .headers()
+            .get(RETRY_AFTER)
+            .is_none());
+    }
+}
@@ -0,0 +1,510 @@
+use super::headers;
+use crate::event::SpecVersion;
+use crate::message::{
+    event_formats, BatchDeserializer, BatchSerializer, BinaryDeserializer, BinarySerializer,
+    Encoding, Error, EventFormats, MessageAttributeValue, MessageDeserializer, Result,
+    StructuredDeserializer, StructuredSerializer,
+};
+use crate::{Batch, Event};
+use ::http::header::{HeaderMap, CONTENT_TYPE};
+use bytes::Bytes;
+use std::cell::OnceCell;
+use std::convert::TryFrom;
+use std::sync::Arc;
+
+/// Wrapper for [`HeaderMap`] and a body that implements [`MessageDeserializer`]
+pub struct HeaderMapDeserializer<'a> {
+    headers: &'a HeaderMap,
+    body: Bytes,
+    formats: Arc<EventFormats>,
+    #[cfg(feature = "compression")]
+    decompressed_limit: usize,
+    /// Body decoded once, when [`MessageDeserializer::spec_version`] reads it before the
+    /// deserialization
+    decoded: OnceCell<Bytes>,
+}
+
+impl HeaderMapDeserializer<'_> {
+    /// Create a new [`HeaderMapDeserializer`]. A `body` passed as [`Bytes`] is not copied to
+    /// deserialize a binary mode message.
+    pub fn new(headers: &HeaderMap, body: impl Into<Bytes>) -> HeaderMapDeserializer<'_> {
+        HeaderMapDeserializer {
+            headers,
+            body: body.into(),
+            formats: event_formats(),
+            #[cfg(feature = "compression")]
+            decompressed_limit: crate::message::DEFAULT_DECOMPRESSED_LIMIT,
+            decoded: OnceCell::new(),
+        }
+    }
+
+    /// Read the structured mode messages with the `formats` registry, instead of the installed
+    /// one
+    pub fn with_formats(mut self, formats: Arc<EventFormats>) -> Self {
+        self.formats = formats;
+        self
+    }
+
+    /// Reject the compressed bodies decompressed to more than `limit` bytes with
+    /// [`Error::EventTooLarge`], instead of
+    /// [`DEFAULT_DECOMPRESSED_LIMIT`](crate::message::DEFAULT_DECOMPRESSED_LIMIT). Enabled with
+    /// the `compression` feature.
+    #[cfg(feature = "compression")]
+    pub fn with_decompressed_limit(mut self, limit: usize) -> Self {
+        self.decompressed_limit = limit;
+        self
+    }
+
+    /// Body of the message, decompressed according to its `content-encoding` with the
+    /// `compression` feature
+    fn decoded_body(&self) -> Result<Bytes> {
+        if let Some(body) = self.decoded.get() {
+            return Ok(body.clone());
+        }
+        let body = self.decode_body(self.body.clone())?;
+        Ok(self.decoded.get_or_init(|| body).clone())
+    }
+
+    /// Consume the decoded body, without copying an uncompressed body passed as [`Bytes`]
+    fn into_decoded_body(mut self) -> Result<Bytes> {
+        match self.decoded.take() {
+            Some(body) => Ok(body),
+            None => {
+                let body = std::mem::take(&mut self.body);
+                self.decode_body(body)
+            }
+        }
+    }
+
+    fn decode_body(&self, body: Bytes) -> Result<Bytes> {
+        #[cfg(feature = "compression")]
+        if let Some(content_encoding) = self.headers.get(::http::header::CONTENT_ENCODING) {
+            let content_encoding = header_value_to_str!(content_encoding)?;
+            if content_encoding.trim().eq_ignore_ascii_case("identity") {
+                return Ok(body);
+            }
+            return match crate::message::Compression::from_content_encoding(content_encoding) {
+                Some(compression) => Ok(Bytes::from(
+                    compression.decompress_with_limit(&body, self.decompressed_limit)?,
+                )),
+                None => Err(Error::PayloadDecodeError {
+                    source: format!("unsupported content-encoding {}", content_encoding).into(),
+                }),
+            };
+        }
+        Ok(body)
+    }
+
+    /// Payload of a structured mode message in the JSON event format
+    fn into_structured_payload(self) -> Result<Vec<u8>> {
+        let content_type = self
+            .headers
+            .get(CONTENT_TYPE)
+            .and_then(|hv| hv.to_str().ok());
+        let formats = self.formats.clone();
+        formats.to_json(content_type, Vec::from(self.into_decoded_body()?))
+    }
+}
+
+impl<'a> BinaryDeserializer for HeaderMapDeserializer<'a> {
+    fn deserialize_binary<R: Sized, V: BinarySerializer<R>>(self, mut visitor: V) -> Result<R> {
+        if self.encoding() != Encoding::BINARY {
+            return Err(Error::WrongEncoding {});
+        }
+
+        let spec_version = SpecVersion::try_from(header_value_to_str!(self
+            .headers
+            .get(headers::SPEC_VERSION_HEADER)
+            .ok_or(Error::WrongEncoding {})?)?)?;
+
+        visitor = visitor.set_spec_version(spec_version.clone())?;
+
+        let attributes = spec_version.attribute_names();
+
+        for (hn, hv) in self.headers.iter().filter(|(hn, _)| {
+            hn.as_str() != headers::SPEC_VERSION_HEADER
+                && hn.as_str().starts_with(headers::CE_PREFIX)
+        }) {
+            let name = &hn.as_str()[headers::CE_PREFIX.len()..];
+
+            if attributes.contains(&name) {
+                visitor = visitor
+                    .set_attribute(name, MessageAttributeValue::from(header_value_to_str!(hv)?))?
+            } else {
+                visitor = visitor
+                    .set_extension(name, MessageAttributeValue::from(header_value_to_str!(hv)?))?
+            }
+        }
+
+        if let Some(hv) = self.headers.get(CONTENT_TYPE) {
+            visitor = visitor.set_attribute(
+                "datacontenttype",
+                MessageAttributeValue::from(header_value_to_str!(hv)?),
+            )?
+        }
+
+        let body = self.into_decoded_body()?;
+        if !body.is_empty() {
+            visitor.end_with_data(body)
+        } else {
+            visitor.end()
+        }
+    }
+}
+
+impl<'a> StructuredDeserializer for HeaderMapDeserializer<'a> {
+    fn deserialize_structured<R: Sized, V: StructuredSerializer<R>>(self, visitor: V) -> Result<R> {
+        if self.encoding() != Encoding::STRUCTURED {
+            return Err(Error::WrongEncoding {});
+        }
+        visitor.set_structured_event(self.into_structured_payload()?)
+    }
+}
+
+impl<'a> BatchDeserializer for HeaderMapDeserializer<'a> {
+    fn deserialize_batch<R: Sized, V: BatchSerializer<R>>(self, visitor: V) -> Result<R> {
+        if self.encoding() != Encoding::BATCH {
+            return Err(Error::WrongEncoding {});
+        }
+        visitor.set_batch(Vec::from(self.into_decoded_body()?))
+    }
+}
+
+impl<'a> MessageDeserializer for HeaderMapDeserializer<'a> {
+    fn encoding(&self) -> Encoding {
+        headers::encoding_with_formats(self.headers, &self.formats)
+    }
+
+    fn spec_version(&self) -> Result<SpecVersion> {
+        match self.encoding() {
+            Encoding::BINARY => Ok(SpecVersion::try_from(header_value_to_str!(self
+                .headers
+                .get(headers::SPEC_VERSION_HEADER)
+                .ok_or(Error::WrongEncoding {})?)?)?),
+            Encoding::STRUCTURED => self.formats.spec_version(
+                self.headers
+                    .get(CONTENT_TYPE)
+                    .and_then(|hv| hv.to_str().ok()),
+                &self.decoded_body()?,
+            ),
+            _ => Err(Error::WrongEncoding {}),
+        }
+    }
+}
+
+/// Method to decode a binary mode message, made of `headers` and `body`, to [`Event`]
+pub fn binary_to_event(headers: &HeaderMap, body: impl Into<Bytes>) -> Result<Event> {
+    BinaryDeserializer::into_event(HeaderMapDeserializer::new(headers, body))
+}
+
+/// Method to decode a message, made of `headers` and `body`, to [`Event`],
+/// detecting from the `headers` whether it's encoded in binary or structured mode
+pub fn to_event(headers: &HeaderMap, body: impl Into<Bytes>) -> Result<Event> {
+    MessageDeserializer::into_event(HeaderMapDeserializer::new(headers, body))
+}
+
+/// Decode an event received by an HTTP server or client, see [`to_event`], reporting it to the
+/// [`metrics`](crate::metrics)
+#[cfg(http_receiver)]
+pub(crate) fn receive_event(headers: &HeaderMap, body: impl Into<Bytes>) -> Result<Event> {
+    let body = body.into();
+    let bytes = body.len();
+    let result = to_event(headers, body);
+    crate::metrics::record_receive("http", bytes, &result);
+    result
+}
+
+/// Method to decode a message, made of `headers` and `body`, to [`Batch`].
+/// A batched mode message is decoded as is, while a single event in binary or structured mode
+/// is decoded as a batch of one event.
+pub fn to_batch(headers: &HeaderMap, body: impl Into<Bytes>) -> Result<Batch> {
+    let deserializer = HeaderMapDeserializer::new(headers, body);
+    if deserializer.encoding() == Encoding::BATCH {
+        deserializer.into_batch()
+    } else {
+        Ok(Batch::from(vec![MessageDeserializer::into_event(
+            deserializer,
+        )?]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::binding::http::encoding;
+    use crate::event::Data;
+    use crate::message::{EventFormat, JsonFormat, ParseMode};
+    use crate::{AttributesReader, EventBuilder, EventBuilderV10};
+    use ::http::header::HeaderValue;
+    use serde_json::json;
+
+    #[test]
+    fn test_binary_to_event() {
+        let j = json!({"hello": "world"});
+        let expected = EventBuilderV10::new()
+            .id("0001")
+            .ty("example.test")
+            .source("http://localhost/")
+            .data("application/json", j.clone())
+            .extension("someint", "10")
+            .build()
+            .unwrap();
+
+        let mut headers = HeaderMap::new();
+        headers.insert("ce-specversion", HeaderValue::from_static("1.0"));
+        headers.insert("ce-id", HeaderValue::from_static("0001"));
+        headers.insert("ce-type", HeaderValue::from_static("example.test"));
+        headers.insert("ce-source", HeaderValue::from_static("http://localhost/"));
+        headers.insert("ce-someint", HeaderValue::from_static("10"));
+        headers.insert("content-type", HeaderValue::from_static("application/json"));
+
+        assert_eq!(
+            expected,
+            binary_to_event(&headers, j.to_string().into_bytes()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_binary_to_event_from_bytes() {
+        let body = Bytes::from(vec![0xCA, 0xFE, 0xBA, 0xBE]);
+        let ptr = body.as_ptr();
+
+        let mut headers = HeaderMap::new();
+        headers.insert("ce-specversion", HeaderValue::from_static("1.0"));
+        headers.insert("ce-id", HeaderValue::from_static("0001"));
+        headers.insert("ce-type", HeaderValue::from_static("example.test"));
+        headers.insert("ce-source", HeaderValue::from_static("http://localhost/"));
+        headers.insert(
+            "content-type",
+            HeaderValue::from_static("application/octet-stream"),
+        );
+
+        // The payload is moved into the event without copying it
+        match binary_to_event(&headers, body).unwrap().data {
+            Some(Data::Binary(data)) => {
+                assert_eq!(vec![0xCA, 0xFE, 0xBA, 0xBE], data);
+                assert_eq!(ptr, data.as_ptr());
+            }
+            data => panic!("unexpected data {:?}", data),
+        }
+    }
+
+    #[test]
+    fn test_spec_version() {
+        let mut headers = HeaderMap::new();
+        headers.insert("ce-specversion", HeaderValue::from_static("0.3"));
+        headers.insert("ce-id", HeaderValue::from_static("0001"));
+        headers.insert("ce-type", HeaderValue::from_static("example.test"));
+        headers.insert("ce-source", HeaderValue::from_static("http://localhost/"));
+
+        let deserializer = HeaderMapDeserializer::new(&headers, Vec::new());
+        assert_eq!(SpecVersion::V03, deserializer.spec_version().unwrap());
+        assert_eq!(
+            SpecVersion::V03,
+            MessageDeserializer::into_event(deserializer)
+                .unwrap()
+                .get_specversion()
+        );
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "content-type",
+            HeaderValue::from_static("application/cloudevents+json"),
+        );
+        let body = serde_json::to_vec(&json!({"specversion": "1.0"})).unwrap();
+        assert_eq!(
+            SpecVersion::V10,
+            HeaderMapDeserializer::new(&headers, body)
+                .spec_version()
+                .unwrap()
+        );
+
+        assert!(HeaderMapDeserializer::new(&HeaderMap::new(), Vec::new())
+            .spec_version()
+            .is_err());
+    }
+
+    #[test]
+    fn test_into_event_with_mode() {
+        let mut headers = HeaderMap::new();
+        headers.insert("ce-specversion", HeaderValue::from_static("1.0"));
+        headers.insert("ce-id", HeaderValue::from_static("0001"));
+        headers.insert("ce-type", HeaderValue::from_static("example.test"));
+        headers.insert("ce-source", HeaderValue::from_static("http://localhost/"));
+        headers.insert("ce-time", HeaderValue::from_static("yesterday"));
+
+        let (event, diagnostics) = HeaderMapDeserializer::new(&headers, Vec::new())
+            .into_event_with_mode(ParseMode::Lenient)
+            .unwrap();
+        assert_eq!("0001", event.get_id());
+        assert!(event.get_time().is_none());
+        assert_eq!(1, diagnostics.len());
+
+        assert!(HeaderMapDeserializer::new(&headers, Vec::new())
+            .into_event_with_mode(ParseMode::Strict)
+            .is_err());
+    }
+
+    #[test]
+    fn test_binary_to_event_missing_spec_version() {
+        let headers = HeaderMap::new();
+
+        assert!(binary_to_event(&headers, Vec::new()).is_err());
+    }
+
+    #[test]
+    fn test_structured_to_event() {
+        let expected = EventBuilderV10::new()
+            .id("0001")
+            .ty("example.test")
+            .source("http://localhost/")
+            .data("application/json", json!({"hello": "world"}))
+            .extension("someint", "10")
+            .build()
+            .unwrap();
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "content-type",
+            HeaderValue::from_static("application/cloudevents+json; charset=utf-8"),
+        );
+
+        assert_eq!(
+            expected,
+            to_event(&headers, serde_json::to_vec(&expected).unwrap()).unwrap()
+        );
+    }
+
+    /// Format writing the JSON event format backwards
+    struct Backwards;
+
+    impl EventFormat for Backwards {
+        fn content_type(&self) -> &str {
+            "application/vnd.example.backwards"
+        }
+
+        fn encode(&self, event: &Event) -> Result<Vec<u8>> {
+            let mut bytes = JsonFormat.encode(event)?;
+            bytes.reverse();
+            Ok(bytes)
+        }
+
+        fn decode(&self, bytes: &[u8]) -> Result<Event> {
+            let mut bytes = bytes.to_vec();
+            bytes.reverse();
+            JsonFormat.decode(&bytes)
+        }
+    }
+
+    #[test]
+    fn test_structured_to_event_with_format() {
+        let expected = EventBuilderV10::new()
+            .id("0001")
+            .ty("example.test")
+            .source("http://localhost/")
+            .data("application/json", json!({"hello": "world"}))
+            .build()
+            .unwrap();
+
+        let (headers, body) =
+            crate::binding::http::event_to_structured_with_format(&expected, &Backwards).unwrap();
+        assert_eq!("application/vnd.example.backwards", headers["content-type"]);
+        assert_eq!(Encoding::UNKNOWN, encoding(&headers));
+
+        let formats = Arc::new(EventFormats::new().with(Backwards));
+        let deserializer = HeaderMapDeserializer::new(&headers, body).with_formats(formats);
+        assert_eq!(Encoding::STRUCTURED, deserializer.encoding());
+        assert_eq!(SpecVersion::V10, deserializer.spec_version().unwrap());
+        assert_eq!(
+            expected,
+            MessageDeserializer::into_event(deserializer).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_batch_to_batch() {
+        let expected = Batch::from(vec![
+            EventBuilderV10::new()
+                .id("0001")
+                .ty("example.test")
+                .source("http://localhost/")
+                .build()
+                .unwrap(),
+            EventBuilderV10::new()
+                .id("0002")
+                .ty("example.test")
+                .source("http://localhost/")
+                .data("application/json", json!({"hello": "world"}))
+                .build()
+                .unwrap(),
+        ]);
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "content-type",
+            HeaderValue::from_static("application/cloudevents-batch+json"),
+        );
+
+        assert_eq!(
+            expected,
+            to_batch(&headers, serde_json::to_vec(&expected).unwrap()).unwrap()
+        );
+        assert!(to_event(&headers, serde_json::to_vec(&expected).unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_binary_to_batch() {
+        let mut headers = HeaderMap::new();
+        headers.insert("ce-specversion", HeaderValue::from_static("1.0"));
+        headers.insert("ce-id", HeaderValue::from_static("0001"));
+        headers.insert("ce-type", HeaderValue::from_static("example.test"));
+        headers.insert("ce-source", HeaderValue::from_static("http://localhost/"));
+
+        let batch = to_batch(&headers, Vec::new()).unwrap();
+        assert_eq!(1, batch.len());
+        assert_eq!("0001", batch[0].get_id());
+    }
+
+    #[cfg(feature = "compression")]
+    #[test]
+    fn test_compressed_body() {
+        use crate::binding::http::{compress_body, event_to_binary, event_to_structured};
+        use crate::message::Compression;
+
+        let event = EventBuilderV10::new()
+            .id("0001")
+            .ty("example.test")
+            .source("http://localhost/")
+            .data("application/json", json!({"hello": "world"}))
+            .build()
+            .unwrap();
+
+        for (mut headers, body) in [
+            event_to_binary(event.clone()).unwrap(),
+            event_to_structured(event.clone()).unwrap(),
+        ] {
+            let body = compress_body(&mut headers, &body, Compression::Gzip).unwrap();
+            assert_eq!("gzip", headers["content-encoding"]);
+            assert_eq!(event, to_event(&headers, body.clone()).unwrap());
+
+            let deserializer = HeaderMapDeserializer::new(&headers, body.clone());
+            assert_eq!(SpecVersion::V10, deserializer.spec_version().unwrap());
+            assert_eq!(
+                event,
+                MessageDeserializer::into_event(deserializer).unwrap()
+            );
+            assert!(matches!(
+                MessageDeserializer::into_event(
+                    HeaderMapDeserializer::new(&headers, body.clone()).with_decompressed_limit(16)
+                ),
+                Err(Error::EventTooLarge { limit: 16, .. })
+            ));
+
+            headers.insert("content-encoding", HeaderValue::from_static("br"));
+            assert!(matches!(
+                to_event(&headers, body),
+                Err(Error::PayloadDecodeError { .. })
+            ));
+        }
+    }
+}
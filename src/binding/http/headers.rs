@@ -0,0 +1,99 @@
+use crate::message::{event_formats, Encoding, EventFormats};
+use ::http::header::{HeaderMap, HeaderName, CONTENT_TYPE};
+use std::str::FromStr;
+
+macro_rules! header_value_to_str {
+    ($header_value:expr) => {
+        $header_value
+            .to_str()
+            .map_err(|e| crate::message::Error::Other {
+                source: Box::new(e),
+            })
+    };
+}
+
+macro_rules! str_to_header_value {
+    ($header_value:expr) => {
+        ::http::header::HeaderValue::from_str($header_value).map_err(|e| {
+            crate::message::Error::Other {
+                source: Box::new(e),
+            }
+        })
+    };
+}
+
+pub(crate) const CE_PREFIX: &str = "ce-";
+pub(crate) const SPEC_VERSION_HEADER: &str = "ce-specversion";
+pub(crate) const CLOUDEVENTS_JSON_CONTENT_TYPE: &str = "application/cloudevents+json";
+pub(crate) const CLOUDEVENTS_BATCH_JSON_CONTENT_TYPE: &str = "application/cloudevents-batch+json";
+
+/// Detect the [`Encoding`] of an HTTP message from its `headers`:
+/// a `content-type` of `application/cloudevents+json`, or of a format of the installed
+/// [`EventFormats`], means structured mode,
+/// `application/cloudevents-batch+json` means batched mode,
+/// otherwise the presence of `ce-specversion` means binary mode.
+pub fn encoding(headers: &HeaderMap) -> Encoding {
+    encoding_with_formats(headers, &event_formats())
+}
+
+/// Detect the [`Encoding`] of an HTTP message from its `headers`, like [`encoding`],
+/// with the `formats` registry
+pub(crate) fn encoding_with_formats(headers: &HeaderMap, formats: &EventFormats) -> Encoding {
+    let media_type = headers
+        .get(CONTENT_TYPE)
+        .and_then(|hv| hv.to_str().ok())
+        .and_then(|ct| ct.split(';').next())
+        .map(str::trim);
+
+    if media_type.is_some_and(|mt| formats.contains(mt)) {
+        Encoding::STRUCTURED
+    } else if media_type
+        .is_some_and(|mt| mt.eq_ignore_ascii_case(CLOUDEVENTS_BATCH_JSON_CONTENT_TYPE))
+    {
+        Encoding::BATCH
+    } else if headers.contains_key(SPEC_VERSION_HEADER) {
+        Encoding::BINARY
+    } else {
+        Encoding::UNKNOWN
+    }
+}
+
+/// Map a context attribute or extension name to its HTTP header
+pub(crate) fn attribute_name_to_header(name: &str) -> crate::message::Result<HeaderName> {
+    if name == "datacontenttype" {
+        Ok(CONTENT_TYPE)
+    } else {
+        HeaderName::from_str(&[CE_PREFIX, name].concat()).map_err(|e| {
+            crate::message::Error::Other {
+                source: Box::new(e),
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::http::header::HeaderValue;
+
+    #[test]
+    fn test_encoding() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(Encoding::UNKNOWN, encoding(&headers));
+
+        headers.insert("ce-specversion", HeaderValue::from_static("1.0"));
+        assert_eq!(Encoding::BINARY, encoding(&headers));
+
+        headers.insert(
+            "content-type",
+            HeaderValue::from_static("application/cloudevents+json; charset=utf-8"),
+        );
+        assert_eq!(Encoding::STRUCTURED, encoding(&headers));
+
+        headers.insert(
+            "content-type",
+            HeaderValue::from_static("application/cloudevents-batch+json"),
+        );
+        assert_eq!(Encoding::BATCH, encoding(&headers));
+    }
+}
@@ -0,0 +1,105 @@
+//! [HTTP Protocol Binding](https://github.com/cloudevents/spec/blob/v1.0/http-protocol-binding.md)
+//! on top of the [`http`](https://docs.rs/http) crate types, supporting both binary and structured mode.
+//!
+//! ```
+//! use cloudevents::binding::http::{event_to_binary, event_to_structured, to_event};
+//! use cloudevents::{EventBuilder, EventBuilderV10};
+//!
+//! let event = EventBuilderV10::new()
+//!     .id("0001")
+//!     .source("http://localhost/")
+//!     .ty("example.demo")
+//!     .build()
+//!     .unwrap();
+//!
+//! let (headers, body) = event_to_binary(event.clone()).unwrap();
+//! assert_eq!(event, to_event(&headers, body).unwrap());
+//!
+//! let (headers, body) = event_to_structured(event.clone()).unwrap();
+//! assert_eq!(event, to_event(&headers, body).unwrap());
+//! ```
+//!
+//! Several events can be sent in a single message using the
+//! [JSON batch format](https://github.com/cloudevents/spec/blob/v1.0/json-format.md#4-json-batch-format):
+//!
+//! ```
+//! use cloudevents::binding::http::{batch_to_message, to_batch};
+//! use cloudevents::{Batch, EventBuilder, EventBuilderV10};
+//!
+//! let batch = Batch::from(vec![EventBuilderV10::new()
+//!     .id("0001")
+//!     .source("http://localhost/")
+//!     .ty("example.demo")
+//!     .build()
+//!     .unwrap()]);
+//!
+//! let (headers, body) = batch_to_message(batch.clone()).unwrap();
+//! assert_eq!(batch, to_batch(&headers, body).unwrap());
+//! ```
+//!
+//! The frameworks without a dedicated binding are integrated by copying the headers of their
+//! requests to a [`HeaderMap`](::http::HeaderMap) for [`to_event`], and the headers returned by
+//! [`event_to_binary`] to their responses. A middleware of such frameworks
+//! replies to the webhook validation handshakes with [`WebhookValidator::validate`]:
+//!
+//! ```
+//! use cloudevents::binding::http::{event_to_binary, to_event};
+//! use http::header::{HeaderMap, HeaderName, HeaderValue};
+//!
+//! // The headers and the body of a request, as read from the framework
+//! let request_headers = vec![
+//!     ("ce-specversion", "1.0"),
+//!     ("ce-id", "0001"),
+//!     ("ce-type", "example.demo"),
+//!     ("ce-source", "http://localhost/"),
+//! ];
+//! let request_body = Vec::new();
+//!
+//! let mut headers = HeaderMap::new();
+//! for (name, value) in request_headers {
+//!     headers.append(
+//!         HeaderName::from_bytes(name.as_bytes()).unwrap(),
+//!         HeaderValue::from_str(value).unwrap(),
+//!     );
+//! }
+//! let event = to_event(&headers, request_body).unwrap();
+//!
+//! let (headers, body) = event_to_binary(event).unwrap();
+//! for (name, value) in &headers {
+//!     // Set the header of the framework response
+//!     assert!(name.as_str().starts_with("ce-"));
+//!     assert!(value.to_str().is_ok());
+//! }
+//! assert!(body.is_empty());
+//! ```
+
+#[macro_use]
+mod headers;
+mod delivery;
+mod deserializer;
+mod serializer;
+#[cfg(feature = "stream")]
+mod streaming;
+mod webhook;
+
+pub use delivery::{to_delivery, HttpAcknowledger};
+pub use deserializer::binary_to_event;
+#[cfg(http_receiver)]
+pub(crate) use deserializer::receive_event;
+pub use deserializer::to_batch;
+pub use deserializer::to_event;
+pub use deserializer::HeaderMapDeserializer;
+pub use headers::encoding;
+pub use serializer::batch_to_message;
+#[cfg(feature = "compression")]
+pub use serializer::compress_body;
+pub use serializer::event_to_binary;
+pub use serializer::event_to_structured;
+pub use serializer::event_to_structured_with_format;
+pub use serializer::HeaderMapSerializer;
+#[cfg(feature = "stream")]
+pub use streaming::{event_to_binary_stream, StreamingDeserializer};
+pub use webhook::{
+    WebhookValidator, WEBHOOK_ALLOWED_ORIGIN, WEBHOOK_ALLOWED_RATE, WEBHOOK_REQUEST_ORIGIN,
+    WEBHOOK_REQUEST_RATE,
+};
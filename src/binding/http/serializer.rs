@@ -0,0 +1,175 @@
+use super::headers;
+use crate::event::SpecVersion;
+use crate::message::{
+    BatchDeserializer, BatchSerializer, BinaryDeserializer, BinarySerializer, EventFormat,
+    MessageAttributeValue, Result, StructuredDeserializer, StructuredSerializer,
+};
+use crate::{Batch, Event};
+use ::http::header::{HeaderMap, HeaderName, HeaderValue, CONTENT_TYPE};
+use bytes::Bytes;
+
+/// Wrapper for [`HeaderMap`] that implements [`StructuredSerializer`], [`BinarySerializer`]
+/// and [`BatchSerializer`], returning the headers and the body of the message
+pub struct HeaderMapSerializer {
+    headers: HeaderMap,
+}
+
+impl HeaderMapSerializer {
+    pub fn new(headers: HeaderMap) -> HeaderMapSerializer {
+        HeaderMapSerializer { headers }
+    }
+}
+
+impl Default for HeaderMapSerializer {
+    fn default() -> Self {
+        HeaderMapSerializer::new(HeaderMap::new())
+    }
+}
+
+impl BinarySerializer<(HeaderMap, Vec<u8>)> for HeaderMapSerializer {
+    fn set_spec_version(mut self, spec_version: SpecVersion) -> Result<Self> {
+        self.headers.insert(
+            HeaderName::from_static(headers::SPEC_VERSION_HEADER),
+            str_to_header_value!(spec_version.as_str())?,
+        );
+        Ok(self)
+    }
+
+    fn set_attribute(mut self, name: &str, value: MessageAttributeValue) -> Result<Self> {
+        self.headers.insert(
+            headers::attribute_name_to_header(name)?,
+            str_to_header_value!(&value.as_str())?,
+        );
+        Ok(self)
+    }
+
+    fn set_extension(mut self, name: &str, value: MessageAttributeValue) -> Result<Self> {
+        self.headers.insert(
+            headers::attribute_name_to_header(name)?,
+            str_to_header_value!(&value.as_str())?,
+        );
+        Ok(self)
+    }
+
+    fn end_with_data(self, bytes: Bytes) -> Result<(HeaderMap, Vec<u8>)> {
+        Ok((self.headers, Vec::from(bytes)))
+    }
+
+    fn end(self) -> Result<(HeaderMap, Vec<u8>)> {
+        Ok((self.headers, Vec::new()))
+    }
+}
+
+impl StructuredSerializer<(HeaderMap, Vec<u8>)> for HeaderMapSerializer {
+    fn set_structured_event(mut self, bytes: Vec<u8>) -> Result<(HeaderMap, Vec<u8>)> {
+        self.headers.insert(
+            CONTENT_TYPE,
+            HeaderValue::from_static(headers::CLOUDEVENTS_JSON_CONTENT_TYPE),
+        );
+        Ok((self.headers, bytes))
+    }
+}
+
+impl BatchSerializer<(HeaderMap, Vec<u8>)> for HeaderMapSerializer {
+    fn set_batch(mut self, bytes: Vec<u8>) -> Result<(HeaderMap, Vec<u8>)> {
+        self.headers.insert(
+            CONTENT_TYPE,
+            HeaderValue::from_static(headers::CLOUDEVENTS_BATCH_JSON_CONTENT_TYPE),
+        );
+        Ok((self.headers, bytes))
+    }
+}
+
+/// Method to encode an [`Event`] in binary mode, returning the message headers and body
+pub fn event_to_binary(event: Event) -> Result<(HeaderMap, Vec<u8>)> {
+    BinaryDeserializer::deserialize_binary(event, HeaderMapSerializer::default())
+}
+
+/// Method to encode an [`Event`] in structured mode, returning the message headers and body
+pub fn event_to_structured(event: Event) -> Result<(HeaderMap, Vec<u8>)> {
+    StructuredDeserializer::deserialize_structured(event, HeaderMapSerializer::default())
+}
+
+/// Method to encode an [`Event`] in structured mode with `format`, returning the message
+/// headers and body
+pub fn event_to_structured_with_format(
+    event: &Event,
+    format: &dyn EventFormat,
+) -> Result<(HeaderMap, Vec<u8>)> {
+    let mut headers = HeaderMap::new();
+    headers.insert(CONTENT_TYPE, str_to_header_value!(format.content_type())?);
+    Ok((headers, format.encode(event)?))
+}
+
+/// Method to compress the `body` of a message with `compression`, setting the
+/// `content-encoding` of its `headers`. Enabled with the `compression` feature.
+///
+/// The messages received with a `content-encoding` are decompressed by [`to_event`](super::to_event)
+/// and the other deserialization methods.
+#[cfg(feature = "compression")]
+pub fn compress_body(
+    headers: &mut HeaderMap,
+    body: &[u8],
+    compression: crate::message::Compression,
+) -> Result<Vec<u8>> {
+    headers.insert(
+        ::http::header::CONTENT_ENCODING,
+        str_to_header_value!(compression.content_encoding())?,
+    );
+    compression.compress(body)
+}
+
+/// Method to encode a [`Batch`] in batched mode, returning the message headers and body
+pub fn batch_to_message(batch: Batch) -> Result<(HeaderMap, Vec<u8>)> {
+    BatchDeserializer::deserialize_batch(batch, HeaderMapSerializer::default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EventBuilder, EventBuilderV10};
+    use serde_json::json;
+
+    #[test]
+    fn test_event_to_binary() {
+        let j = json!({"hello": "world"});
+        let input = EventBuilderV10::new()
+            .id("0001")
+            .ty("example.test")
+            .source("http://localhost/")
+            .data("application/json", j.clone())
+            .extension("someint", "10")
+            .build()
+            .unwrap();
+
+        let (headers, body) = event_to_binary(input).unwrap();
+
+        assert_eq!("1.0", headers.get("ce-specversion").unwrap());
+        assert_eq!("0001", headers.get("ce-id").unwrap());
+        assert_eq!("example.test", headers.get("ce-type").unwrap());
+        assert_eq!("http://localhost/", headers.get("ce-source").unwrap());
+        assert_eq!("application/json", headers.get("content-type").unwrap());
+        assert_eq!("10", headers.get("ce-someint").unwrap());
+        assert_eq!(j.to_string().into_bytes(), body);
+    }
+
+    #[test]
+    fn test_event_to_structured() {
+        let input = EventBuilderV10::new()
+            .id("0001")
+            .ty("example.test")
+            .source("http://localhost/")
+            .data("application/json", json!({"hello": "world"}))
+            .build()
+            .unwrap();
+
+        let (headers, body) = event_to_structured(input.clone()).unwrap();
+
+        assert_eq!(
+            "application/cloudevents+json",
+            headers.get("content-type").unwrap()
+        );
+        assert!(headers.get("ce-specversion").is_none());
+        assert_eq!(serde_json::to_vec(&input).unwrap(), body);
+    }
+}
@@ -0,0 +1,332 @@
+use super::{event_to_binary, headers, HeaderMapDeserializer};
+use crate::event::StreamingEvent;
+use crate::message::{
+    collect, event_formats, write_binary, AsyncBinaryDeserializer, AsyncBinarySerializer,
+    AsyncStructuredDeserializer, DataStream, Encoding, Error, EventFormats, Message,
+    MessageDeserializer, Result, SerializerFuture, StructuredDeserializer, StructuredSerializer,
+};
+use crate::Event;
+use ::http::header::{HeaderMap, CONTENT_ENCODING};
+use bytes::Bytes;
+use futures::{Stream, StreamExt};
+use std::sync::Arc;
+
+/// Deserializer of an HTTP message made of a [`HeaderMap`] and a streamed body, e.g. the body of
+/// a hyper request, implementing [`AsyncBinaryDeserializer`] and [`AsyncStructuredDeserializer`].
+/// Enabled with the `stream` feature.
+///
+/// The body of a binary mode message is streamed to the [`AsyncBinarySerializer`] as it's
+/// received, unless it has a `content-encoding`. The body of a structured mode message is
+/// collected to be parsed.
+///
+/// ```
+/// use cloudevents::binding::http::{event_to_binary, StreamingDeserializer};
+/// use cloudevents::{EventBuilder, EventBuilderV10};
+/// use futures::stream;
+///
+/// # futures::executor::block_on(async {
+/// let event = EventBuilderV10::new()
+///     .id("0001")
+///     .source("http://localhost/")
+///     .ty("example.demo")
+///     .data("application/octet-stream", vec![1u8, 2, 3])
+///     .build()
+///     .unwrap();
+/// let (headers, body) = event_to_binary(event.clone()).unwrap();
+///
+/// let chunks = body.chunks(1).map(|chunk| Ok::<_, std::io::Error>(chunk.to_vec().into()));
+/// let deserializer = StreamingDeserializer::new(headers, stream::iter(chunks.collect::<Vec<_>>()));
+/// assert_eq!(event, deserializer.into_event().await.unwrap());
+/// # });
+/// ```
+pub struct StreamingDeserializer<S> {
+    headers: HeaderMap,
+    body: S,
+    formats: Arc<EventFormats>,
+}
+
+impl<S, E> StreamingDeserializer<S>
+where
+    S: Stream<Item = std::result::Result<Bytes, E>> + Send,
+    E: std::error::Error + Send + Sync + 'static,
+{
+    /// Create a new [`StreamingDeserializer`] of the message made of `headers` and `body`
+    pub fn new(headers: HeaderMap, body: S) -> Self {
+        StreamingDeserializer {
+            headers,
+            body,
+            formats: event_formats(),
+        }
+    }
+
+    /// Read the structured mode messages with the `formats` registry, instead of the installed
+    /// one
+    pub fn with_formats(mut self, formats: Arc<EventFormats>) -> Self {
+        self.formats = formats;
+        self
+    }
+
+    /// Get the [`Encoding`] of the message
+    pub fn encoding(&self) -> Encoding {
+        headers::encoding_with_formats(&self.headers, &self.formats)
+    }
+
+    /// Convert the message to [`Event`], buffering its body
+    pub async fn into_event(self) -> Result<Event> {
+        match self.encoding() {
+            Encoding::BINARY => self.into_event_async().await,
+            Encoding::STRUCTURED => StructuredDeserializer::into_event(self.buffered().await?),
+            Encoding::BATCH => Err(Error::UnsupportedContentMode {
+                content_mode: Encoding::BATCH,
+            }),
+            _ => Err(Error::WrongEncoding {}),
+        }
+    }
+
+    /// Convert a binary mode message to [`StreamingEvent`], without buffering its body, unless
+    /// it has a `content-encoding`
+    pub async fn into_streaming_event<'a>(self) -> Result<StreamingEvent<'a>>
+    where
+        S: 'a,
+    {
+        match self.encoding() {
+            Encoding::BINARY => {}
+            Encoding::STRUCTURED | Encoding::BATCH => {
+                return Err(Error::UnsupportedContentMode {
+                    content_mode: self.encoding(),
+                })
+            }
+            _ => return Err(Error::WrongEncoding {}),
+        }
+        if self.headers.contains_key(CONTENT_ENCODING) {
+            return StreamingEvent::from_event(MessageDeserializer::into_event(
+                self.buffered().await?.deserializer(),
+            )?);
+        }
+        let headers = HeaderMapDeserializer::new(&self.headers, Bytes::new());
+        let event = MessageDeserializer::into_event(headers)?;
+        Ok(StreamingEvent::new(event, Self::data_stream(self.body)))
+    }
+
+    fn data_stream<'a>(body: S) -> DataStream<'a>
+    where
+        S: 'a,
+    {
+        Box::pin(body.map(|chunk| {
+            chunk.map_err(|e| Error::Other {
+                source: Box::new(e),
+            })
+        }))
+    }
+
+    /// Collect the body, to read the message with [`HeaderMapDeserializer`]
+    async fn buffered(self) -> Result<Buffered> {
+        let body = collect(Self::data_stream(self.body)).await?;
+        Ok(Buffered {
+            headers: self.headers,
+            body,
+            formats: self.formats,
+        })
+    }
+}
+
+/// Method to encode a [`StreamingEvent`] in binary mode, returning the message headers and the
+/// stream of the body
+pub fn event_to_binary_stream(event: StreamingEvent<'_>) -> Result<(HeaderMap, DataStream<'_>)> {
+    let (event, data) = event.into_parts();
+    let (headers, _) = event_to_binary(event)?;
+    Ok((
+        headers,
+        data.unwrap_or_else(|| Box::pin(futures::stream::empty())),
+    ))
+}
+
+/// A message with its body collected
+struct Buffered {
+    headers: HeaderMap,
+    body: Bytes,
+    formats: Arc<EventFormats>,
+}
+
+impl Buffered {
+    fn deserializer(&self) -> HeaderMapDeserializer<'_> {
+        HeaderMapDeserializer::new(&self.headers, self.body.clone())
+            .with_formats(self.formats.clone())
+    }
+}
+
+impl StructuredDeserializer for Buffered {
+    fn deserialize_structured<R: Sized, V: StructuredSerializer<R>>(
+        self,
+        serializer: V,
+    ) -> Result<R> {
+        self.deserializer().deserialize_structured(serializer)
+    }
+}
+
+impl<S, E> AsyncBinaryDeserializer for StreamingDeserializer<S>
+where
+    S: Stream<Item = std::result::Result<Bytes, E>> + Send,
+    E: std::error::Error + Send + Sync + 'static,
+{
+    fn deserialize_binary_async<'a, R, V>(self, serializer: V) -> SerializerFuture<'a, R>
+    where
+        Self: 'a,
+        R: Send + 'a,
+        V: AsyncBinarySerializer<R> + 'a,
+    {
+        Box::pin(async move {
+            if self.encoding() != Encoding::BINARY {
+                return Err(Error::WrongEncoding {});
+            }
+            // The compressed bodies are decompressed by HeaderMapDeserializer once collected
+            let (message, body) = if self.headers.contains_key(CONTENT_ENCODING) {
+                let buffered = self.buffered().await?;
+                (Message::from_deserializer(buffered.deserializer())?, None)
+            } else {
+                let message = Message::from_deserializer(HeaderMapDeserializer::new(
+                    &self.headers,
+                    Bytes::new(),
+                ))?;
+                (message, Some(Self::data_stream(self.body)))
+            };
+            match message {
+                Message::Binary(message) => write_binary(message, body, serializer).await,
+                Message::Structured(_) => Err(Error::WrongEncoding {}),
+            }
+        })
+    }
+}
+
+impl<S, E> AsyncStructuredDeserializer for StreamingDeserializer<S>
+where
+    S: Stream<Item = std::result::Result<Bytes, E>> + Send,
+    E: std::error::Error + Send + Sync + 'static,
+{
+    fn deserialize_structured_async<'a, R, V>(self, serializer: V) -> SerializerFuture<'a, R>
+    where
+        Self: 'a,
+        R: Send + 'a,
+        V: StructuredSerializer<R> + Send + 'a,
+    {
+        Box::pin(async move {
+            if self.encoding() != Encoding::STRUCTURED {
+                return Err(Error::WrongEncoding {});
+            }
+            self.buffered().await?.deserialize_structured(serializer)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::binding::http::{event_to_binary, event_to_structured};
+    use crate::event::SpecVersion;
+    use crate::message::MessageAttributeValue;
+    use crate::{AttributesReader, EventBuilder, EventBuilderV10};
+    use futures::stream;
+    use serde_json::json;
+
+    fn body(body: Vec<u8>) -> impl Stream<Item = std::result::Result<Bytes, std::io::Error>> {
+        stream::iter(
+            body.chunks(2)
+                .map(|chunk| Ok(Bytes::copy_from_slice(chunk)))
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    /// Serializer collecting the sizes of the streamed chunks
+    #[derive(Default)]
+    struct ChunkSizes;
+
+    impl AsyncBinarySerializer<Vec<usize>> for ChunkSizes {
+        fn set_spec_version(self, _: SpecVersion) -> Result<Self> {
+            Ok(self)
+        }
+
+        fn set_attribute(self, _: &str, _: MessageAttributeValue) -> Result<Self> {
+            Ok(self)
+        }
+
+        fn set_extension(self, _: &str, _: MessageAttributeValue) -> Result<Self> {
+            Ok(self)
+        }
+
+        fn end_with_stream<'a>(self, data: DataStream<'a>) -> SerializerFuture<'a, Vec<usize>>
+        where
+            Self: 'a,
+        {
+            Box::pin(async move {
+                Ok(data
+                    .map(|chunk| chunk.map(|c| c.len()).unwrap_or_default())
+                    .collect()
+                    .await)
+            })
+        }
+
+        fn end(self) -> Result<Vec<usize>> {
+            Ok(Vec::new())
+        }
+    }
+
+    fn event() -> Event {
+        EventBuilderV10::new()
+            .id("0001")
+            .source("http://localhost/")
+            .ty("example.test")
+            .data("application/json", json!({"hello": "world"}))
+            .build()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn binary_body_is_streamed() {
+        let (headers, payload) = event_to_binary(event()).unwrap();
+        let len = payload.len();
+        let sizes = StreamingDeserializer::new(headers.clone(), body(payload.clone()))
+            .deserialize_binary_async(ChunkSizes)
+            .await
+            .unwrap();
+        assert_eq!(len.div_ceil(2), sizes.len());
+
+        let deserializer = StreamingDeserializer::new(headers, body(payload));
+        assert_eq!(event(), deserializer.into_event().await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn proxy() {
+        let (headers, payload) = event_to_binary(event()).unwrap();
+        let streaming = StreamingDeserializer::new(headers.clone(), body(payload.clone()))
+            .into_streaming_event()
+            .await
+            .unwrap();
+        assert_eq!("0001", streaming.event().get_id());
+
+        let (forwarded, data) = event_to_binary_stream(streaming).unwrap();
+        assert_eq!(headers, forwarded);
+        let chunks: Vec<Bytes> = data.map(|chunk| chunk.unwrap()).collect().await;
+        assert_eq!(payload.len().div_ceil(2), chunks.len());
+        assert_eq!(payload, chunks.concat());
+
+        let (headers, payload) = event_to_structured(event()).unwrap();
+        assert!(matches!(
+            StreamingDeserializer::new(headers, body(payload))
+                .into_streaming_event()
+                .await,
+            Err(Error::UnsupportedContentMode { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn structured_body_is_collected() {
+        let (headers, payload) = event_to_structured(event()).unwrap();
+        let deserializer = StreamingDeserializer::new(headers.clone(), body(payload.clone()));
+        assert!(matches!(
+            deserializer.deserialize_binary_async(ChunkSizes).await,
+            Err(Error::WrongEncoding {})
+        ));
+        let deserializer = StreamingDeserializer::new(headers, body(payload));
+        assert_eq!(event(), deserializer.into_event().await.unwrap());
+    }
+}
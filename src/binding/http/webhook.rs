@@ -0,0 +1,165 @@
+use ::http::header::{HeaderMap, HeaderValue, ALLOW};
+use ::http::{Method, Response, StatusCode};
+
+/// Header of a validation request carrying the origin of the sender
+pub const WEBHOOK_REQUEST_ORIGIN: &str = "webhook-request-origin";
+/// Header of a validation request carrying the rate of requests per minute the sender asks for
+pub const WEBHOOK_REQUEST_RATE: &str = "webhook-request-rate";
+/// Header of a validation response carrying the origin allowed to send events
+pub const WEBHOOK_ALLOWED_ORIGIN: &str = "webhook-allowed-origin";
+/// Header of a validation response carrying the rate of requests per minute the sender is allowed
+pub const WEBHOOK_ALLOWED_RATE: &str = "webhook-allowed-rate";
+
+/// Validator of the
+/// [abuse protection handshake](https://github.com/cloudevents/spec/blob/v1.0/http-webhook.md#4-abuse-protection)
+/// of the HTTP WebHook spec, replying to the `OPTIONS` validation requests of a delivery target.
+///
+/// By default, it allows any origin at any rate.
+///
+/// ```
+/// use cloudevents::binding::http::WebhookValidator;
+/// use http::{HeaderMap, HeaderValue, Method};
+///
+/// let validator = WebhookValidator::new()
+///     .allow_origin("eventemitter.example.com")
+///     .allowed_rate(120);
+///
+/// let mut headers = HeaderMap::new();
+/// headers.insert("webhook-request-origin", HeaderValue::from_static("eventemitter.example.com"));
+/// let response = validator.validate(&Method::OPTIONS, &headers).unwrap();
+/// assert_eq!(200, response.status());
+/// assert_eq!("eventemitter.example.com", response.headers()["webhook-allowed-origin"]);
+/// assert_eq!("120", response.headers()["webhook-allowed-rate"]);
+///
+/// // Other requests are not validation requests
+/// assert!(validator.validate(&Method::POST, &headers).is_none());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct WebhookValidator {
+    allowed_origins: Option<Vec<String>>,
+    allowed_rate: Option<u32>,
+}
+
+impl WebhookValidator {
+    /// Create a new [`WebhookValidator`] allowing any origin at any rate
+    pub fn new() -> Self {
+        WebhookValidator::default()
+    }
+
+    /// Allow `origin` to send events. Once an origin is allowed, the other ones are denied.
+    pub fn allow_origin(mut self, origin: impl Into<String>) -> Self {
+        self.allowed_origins
+            .get_or_insert_with(Vec::new)
+            .push(origin.into());
+        self
+    }
+
+    /// Limit the rate of requests per minute of the senders
+    pub fn allowed_rate(mut self, rate: u32) -> Self {
+        self.allowed_rate = Some(rate);
+        self
+    }
+
+    /// Returns `true` if `origin` is allowed to send events. Origins are compared ignoring case.
+    pub fn is_allowed(&self, origin: &str) -> bool {
+        match &self.allowed_origins {
+            Some(origins) => origins.iter().any(|o| o.eq_ignore_ascii_case(origin)),
+            None => true,
+        }
+    }
+
+    /// Reply to a validation request, made of `method` and `headers`, or return `None` if it's
+    /// not an `OPTIONS` request with the `WebHook-Request-Origin` header.
+    ///
+    /// An allowed origin gets a `200 OK` response with the `WebHook-Allowed-Origin` and
+    /// `WebHook-Allowed-Rate` headers, any other origin a `403 Forbidden` response.
+    pub fn validate(&self, method: &Method, headers: &HeaderMap) -> Option<Response<()>> {
+        if method != Method::OPTIONS {
+            return None;
+        }
+        let origin = headers.get(WEBHOOK_REQUEST_ORIGIN)?;
+
+        let mut response = Response::new(());
+        if !origin.to_str().is_ok_and(|o| self.is_allowed(o)) {
+            *response.status_mut() = StatusCode::FORBIDDEN;
+            return Some(response);
+        }
+
+        let requested_rate = headers
+            .get(WEBHOOK_REQUEST_RATE)
+            .and_then(|rate| rate.to_str().ok()?.trim().parse::<u32>().ok());
+        let allowed_rate = match (requested_rate, self.allowed_rate) {
+            (Some(requested), Some(allowed)) => HeaderValue::from(requested.min(allowed)),
+            (None, Some(allowed)) => HeaderValue::from(allowed),
+            (Some(requested), None) => HeaderValue::from(requested),
+            (None, None) => HeaderValue::from_static("*"),
+        };
+
+        let response_headers = response.headers_mut();
+        response_headers.insert(ALLOW, HeaderValue::from_static("POST"));
+        response_headers.insert(WEBHOOK_ALLOWED_ORIGIN, origin.clone());
+        response_headers.insert(WEBHOOK_ALLOWED_RATE, allowed_rate);
+        Some(response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(origin: &'static str, rate: Option<&'static str>) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(WEBHOOK_REQUEST_ORIGIN, HeaderValue::from_static(origin));
+        if let Some(rate) = rate {
+            headers.insert(WEBHOOK_REQUEST_RATE, HeaderValue::from_static(rate));
+        }
+        headers
+    }
+
+    #[test]
+    fn any_origin() {
+        let validator = WebhookValidator::new();
+        let response = validator
+            .validate(&Method::OPTIONS, &headers("example.com", Some("60")))
+            .unwrap();
+        assert_eq!(StatusCode::OK, response.status());
+        assert_eq!("POST", response.headers()[ALLOW]);
+        assert_eq!("example.com", response.headers()[WEBHOOK_ALLOWED_ORIGIN]);
+        assert_eq!("60", response.headers()[WEBHOOK_ALLOWED_RATE]);
+
+        let response = validator
+            .validate(&Method::OPTIONS, &headers("example.com", None))
+            .unwrap();
+        assert_eq!("*", response.headers()[WEBHOOK_ALLOWED_RATE]);
+    }
+
+    #[test]
+    fn allowed_origins() {
+        let validator = WebhookValidator::new()
+            .allow_origin("example.com")
+            .allowed_rate(100);
+
+        let response = validator
+            .validate(&Method::OPTIONS, &headers("EXAMPLE.com", Some("120")))
+            .unwrap();
+        assert_eq!(StatusCode::OK, response.status());
+        assert_eq!("100", response.headers()[WEBHOOK_ALLOWED_RATE]);
+
+        let response = validator
+            .validate(&Method::OPTIONS, &headers("attacker.com", None))
+            .unwrap();
+        assert_eq!(StatusCode::FORBIDDEN, response.status());
+        assert!(response.headers().get(WEBHOOK_ALLOWED_ORIGIN).is_none());
+    }
+
+    #[test]
+    fn not_validation_requests() {
+        let validator = WebhookValidator::new();
+        assert!(validator
+            .validate(&Method::POST, &headers("example.com", None))
+            .is_none());
+        assert!(validator
+            .validate(&Method::OPTIONS, &HeaderMap::new())
+            .is_none());
+    }
+}
@@ -0,0 +1,261 @@
+//! Integration with [hyper](https://hyper.rs), built on top of the [`http`](super::http)
+//! binding, for the servers not built on a framework.
+//!
+//! [`EventService`] is a hyper [`Service`] parsing both binary and structured mode requests to
+//! [`Event`], calling a handler with it, and writing the event it returns as a binary mode
+//! response:
+//!
+//! ```
+//! use cloudevents::binding::hyper::EventService;
+//! use cloudevents::Event;
+//! use hyper::service::Service;
+//! use hyper::{Body, Request, StatusCode};
+//! use std::convert::Infallible;
+//!
+//! async fn handle(event: Event) -> Result<Option<Event>, Infallible> {
+//!     Ok(Some(event))
+//! }
+//!
+//! # futures::executor::block_on(async {
+//! let mut service = EventService::new(handle);
+//! let request = Request::post("/")
+//!     .header("ce-specversion", "1.0")
+//!     .header("ce-id", "0001")
+//!     .header("ce-type", "example.demo")
+//!     .header("ce-source", "http://localhost/")
+//!     .body(Body::empty())
+//!     .unwrap();
+//!
+//! let response = service.call(request).await.unwrap();
+//! assert_eq!(StatusCode::OK, response.status());
+//! assert_eq!("0001", response.headers()["ce-id"]);
+//! # });
+//! ```
+//!
+//! It's served with `hyper::Server::serve(service.into_make_service())`.
+
+use super::http::{event_to_binary, receive_event};
+use crate::message::{Error, Received};
+use crate::Event;
+use futures::future::{ready, Ready};
+use hyper::http::StatusCode;
+use hyper::service::Service;
+use hyper::{Body, Request, Response};
+use std::convert::Infallible;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+/// Boxed error returned by the handlers of [`EventService`]
+pub type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// [`Service`] parsing the requests to [`Event`], intercepted by the installed
+/// [`Pipeline`](crate::message::Pipeline), and calling an `async fn(Event) -> Result<Option<Event>>`
+/// handler with them.
+///
+/// * The event returned by the handler is written as a binary mode `200 OK` response, and
+///   `None` is replied with `202 Accepted`.
+/// * A request which isn't an event is replied with the
+///   [status code](crate::message::Error::status_code) of the parsing error.
+/// * A failure of the handler is replied with `500 Internal Server Error`.
+pub struct EventService<F> {
+    handler: Arc<F>,
+}
+
+impl<F> EventService<F> {
+    /// Create a new [`EventService`] calling `handler` with the received events
+    pub fn new(handler: F) -> Self {
+        EventService {
+            handler: Arc::new(handler),
+        }
+    }
+
+    /// Get a service creating an [`EventService`] for each connection, to be passed to
+    /// `hyper::Server::serve`
+    pub fn into_make_service(self) -> MakeEventService<F> {
+        MakeEventService { service: self }
+    }
+}
+
+impl<F> Clone for EventService<F> {
+    fn clone(&self) -> Self {
+        EventService {
+            handler: self.handler.clone(),
+        }
+    }
+}
+
+impl<F, Fut, E> Service<Request<Body>> for EventService<F>
+where
+    F: Fn(Event) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<Option<Event>, E>> + Send + 'static,
+    E: Into<BoxError>,
+{
+    type Response = Response<Body>;
+    type Error = Infallible;
+    type Future = Pin<Box<dyn Future<Output = Result<Response<Body>, Infallible>> + Send>>;
+
+    fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Infallible>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: Request<Body>) -> Self::Future {
+        let handler = self.handler.clone();
+        Box::pin(async move {
+            let event = match parse_request(request).await {
+                Ok(event) => event,
+                Err(e) => return Ok(error_response(e)),
+            };
+            #[cfg(feature = "opentelemetry")]
+            let handled = {
+                use opentelemetry::trace::FutureExt;
+                let context = crate::binding::telemetry::parent_context(&event);
+                handler(event).with_context(context)
+            };
+            #[cfg(not(feature = "opentelemetry"))]
+            let handled = handler(event);
+            Ok(match handled.await {
+                Ok(Some(event)) => event_response(event),
+                Ok(None) => status_response(StatusCode::ACCEPTED, Body::empty()),
+                Err(e) => status_response(
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Body::from(e.into().to_string()),
+                ),
+            })
+        })
+    }
+}
+
+/// [`Service`] creating an [`EventService`] for each connection, see
+/// [`EventService::into_make_service`]
+pub struct MakeEventService<F> {
+    service: EventService<F>,
+}
+
+impl<F> Clone for MakeEventService<F> {
+    fn clone(&self) -> Self {
+        MakeEventService {
+            service: self.service.clone(),
+        }
+    }
+}
+
+impl<F, T> Service<T> for MakeEventService<F> {
+    type Response = EventService<F>;
+    type Error = Infallible;
+    type Future = Ready<Result<EventService<F>, Infallible>>;
+
+    fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Infallible>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, _: T) -> Self::Future {
+        ready(Ok(self.service.clone()))
+    }
+}
+
+/// Read the body of `request`, parse it and intercept it with the installed
+/// [`Pipeline`](crate::message::Pipeline)
+async fn parse_request(request: Request<Body>) -> Result<Event, Error> {
+    let (parts, body) = request.into_parts();
+    let body = hyper::body::to_bytes(body)
+        .await
+        .map_err(|e| Error::Other {
+            source: Box::new(e),
+        })?;
+    let mut event = receive_event(&parts.headers, body)?;
+    event.intercept_receive("http", None).await?;
+    Ok(event)
+}
+
+fn status_response(status: StatusCode, body: Body) -> Response<Body> {
+    let mut response = Response::new(body);
+    *response.status_mut() = status;
+    response
+}
+
+/// Reply to a request which couldn't be read with the status code of `error`
+fn error_response(error: Error) -> Response<Body> {
+    let status = StatusCode::from_u16(error.status_code()).unwrap_or(StatusCode::BAD_REQUEST);
+    status_response(status, Body::from(error.to_string()))
+}
+
+/// Write `event` as a binary mode response, replying with `500 Internal Server Error` on error
+fn event_response(event: Event) -> Response<Body> {
+    match event_to_binary(event) {
+        Ok((headers, body)) => {
+            let mut response = Response::new(Body::from(body));
+            *response.headers_mut() = headers;
+            response
+        }
+        Err(e) => status_response(StatusCode::INTERNAL_SERVER_ERROR, Body::from(e.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AttributesReader, AttributesWriter, EventBuilder, EventBuilderV10};
+    use hyper::http::header::CONTENT_TYPE;
+    use serde_json::json;
+
+    fn event() -> Event {
+        EventBuilderV10::new()
+            .id("0001")
+            .ty("example.test")
+            .source("http://localhost/")
+            .data("application/json", json!({"hello": "world"}))
+            .build()
+            .unwrap()
+    }
+
+    async fn reply(event: Event) -> Result<Option<Event>, BoxError> {
+        match event.get_type() {
+            "example.test" => Ok(Some(event)),
+            "example.ignored" => Ok(None),
+            _ => Err("unexpected type".into()),
+        }
+    }
+
+    fn request(event: Event) -> Request<Body> {
+        let (headers, body) = event_to_binary(event).unwrap();
+        let mut request = Request::new(Body::from(body));
+        *request.headers_mut() = headers;
+        request
+    }
+
+    #[tokio::test]
+    async fn reply_event() {
+        let mut service = EventService::new(reply);
+
+        let response = service.call(request(event())).await.unwrap();
+        assert_eq!(StatusCode::OK, response.status());
+        let (parts, body) = response.into_parts();
+        let body = hyper::body::to_bytes(body).await.unwrap();
+        assert_eq!(event(), receive_event(&parts.headers, body).unwrap());
+
+        let mut ignored = event();
+        ignored.set_type("example.ignored");
+        let response = service.call(request(ignored)).await.unwrap();
+        assert_eq!(StatusCode::ACCEPTED, response.status());
+
+        let mut failing = event();
+        failing.set_type("example.other");
+        let response = service.call(request(failing)).await.unwrap();
+        assert_eq!(StatusCode::INTERNAL_SERVER_ERROR, response.status());
+    }
+
+    #[tokio::test]
+    async fn not_an_event() {
+        let mut service = EventService::new(reply).into_make_service();
+        let mut service = service.call(()).await.unwrap();
+        let request = Request::post("/")
+            .header(CONTENT_TYPE, "application/json")
+            .body(Body::from(json!({"hello": "world"}).to_string()))
+            .unwrap();
+
+        let response = service.call(request).await.unwrap();
+        assert_eq!(StatusCode::BAD_REQUEST, response.status());
+    }
+}
@@ -0,0 +1,39 @@
+#[cfg(feature = "actix")]
+pub mod actix;
+#[cfg(feature = "amqp")]
+pub mod amqp;
+#[cfg(feature = "axum")]
+pub mod axum;
+#[cfg(feature = "azure")]
+pub mod azure;
+#[cfg(feature = "http")]
+pub mod http;
+#[cfg(feature = "hyper")]
+pub mod hyper;
+#[cfg(feature = "mqtt")]
+pub mod mqtt;
+#[cfg(feature = "poem")]
+pub mod poem;
+#[cfg(feature = "rdkafka")]
+pub mod rdkafka;
+#[cfg(feature = "reqwest")]
+pub mod reqwest;
+#[cfg(feature = "rocket")]
+pub mod rocket;
+#[cfg(feature = "salvo")]
+pub mod salvo;
+#[cfg(feature = "sse")]
+pub mod sse;
+#[cfg(feature = "opentelemetry")]
+pub mod telemetry;
+#[cfg(feature = "tide")]
+pub mod tide;
+#[cfg(feature = "tonic")]
+pub mod tonic;
+#[cfg(feature = "tower")]
+pub mod tower;
+pub mod transcode;
+#[cfg(feature = "warp")]
+pub mod warp;
+#[cfg(feature = "websocket")]
+pub mod websocket;
@@ -0,0 +1,238 @@
+use super::SPEC_VERSION;
+use crate::event::SpecVersion;
+use crate::message::{
+    event_formats, BinaryDeserializer, BinarySerializer, Encoding, Error, MessageAttributeValue,
+    MessageDeserializer, Result, StructuredDeserializer, StructuredSerializer,
+};
+use crate::Event;
+use bytes::Bytes;
+use rumqttc::v5::mqttbytes::v5::{Publish as PublishV5, PublishProperties};
+use rumqttc::Publish as PublishV3;
+use std::convert::TryFrom;
+
+/// Wrapper for the properties and the payload of an MQTT publish packet
+/// that implements [`MessageDeserializer`].
+///
+/// A packet without properties, like every MQTT 3.1.1 packet, is read in structured mode.
+pub struct PublishDeserializer<'a> {
+    properties: Option<&'a PublishProperties>,
+    payload: &'a [u8],
+}
+
+impl<'a> PublishDeserializer<'a> {
+    pub fn new(properties: Option<&'a PublishProperties>, payload: &'a [u8]) -> Self {
+        PublishDeserializer {
+            properties,
+            payload,
+        }
+    }
+}
+
+impl<'a> BinaryDeserializer for PublishDeserializer<'a> {
+    fn deserialize_binary<R: Sized, V: BinarySerializer<R>>(self, mut visitor: V) -> Result<R> {
+        if self.encoding() != Encoding::BINARY {
+            return Err(Error::WrongEncoding {});
+        }
+
+        let properties = self.properties.ok_or(Error::WrongEncoding {})?;
+
+        let spec_version = SpecVersion::try_from(
+            properties
+                .user_properties
+                .iter()
+                .find(|(k, _)| k == SPEC_VERSION)
+                .map(|(_, v)| v.as_str())
+                .ok_or(Error::WrongEncoding {})?,
+        )?;
+
+        visitor = visitor.set_spec_version(spec_version.clone())?;
+
+        let attributes = spec_version.attribute_names();
+
+        for (name, value) in properties
+            .user_properties
+            .iter()
+            .filter(|(k, _)| k != SPEC_VERSION)
+        {
+            let value = MessageAttributeValue::from(value.as_str());
+            if attributes.contains(&name.as_str()) {
+                visitor = visitor.set_attribute(name, value)?
+            } else {
+                visitor = visitor.set_extension(name, value)?
+            }
+        }
+
+        if let Some(content_type) = &properties.content_type {
+            visitor = visitor.set_attribute(
+                "datacontenttype",
+                MessageAttributeValue::from(content_type.as_str()),
+            )?
+        }
+
+        if !self.payload.is_empty() {
+            visitor.end_with_data(Bytes::copy_from_slice(self.payload))
+        } else {
+            visitor.end()
+        }
+    }
+}
+
+impl PublishDeserializer<'_> {
+    fn content_type(&self) -> Option<&str> {
+        self.properties.and_then(|p| p.content_type.as_deref())
+    }
+}
+
+impl<'a> StructuredDeserializer for PublishDeserializer<'a> {
+    fn deserialize_structured<R: Sized, V: StructuredSerializer<R>>(self, visitor: V) -> Result<R> {
+        if self.encoding() != Encoding::STRUCTURED {
+            return Err(Error::WrongEncoding {});
+        }
+        visitor.set_structured_event(
+            event_formats().to_json(self.content_type(), self.payload.to_vec())?,
+        )
+    }
+}
+
+impl<'a> MessageDeserializer for PublishDeserializer<'a> {
+    fn encoding(&self) -> Encoding {
+        let properties = match self.properties {
+            Some(properties) => properties,
+            None => return Encoding::STRUCTURED,
+        };
+
+        let is_structured = properties
+            .content_type
+            .as_deref()
+            .is_some_and(|ct| event_formats().contains(ct));
+
+        if is_structured {
+            Encoding::STRUCTURED
+        } else if properties
+            .user_properties
+            .iter()
+            .any(|(k, _)| k == SPEC_VERSION)
+        {
+            Encoding::BINARY
+        } else {
+            Encoding::UNKNOWN
+        }
+    }
+
+    fn spec_version(&self) -> Result<SpecVersion> {
+        match (self.encoding(), self.properties) {
+            (Encoding::BINARY, Some(properties)) => Ok(SpecVersion::try_from(
+                properties
+                    .user_properties
+                    .iter()
+                    .find(|(k, _)| k == SPEC_VERSION)
+                    .map(|(_, v)| v.as_str())
+                    .ok_or(Error::WrongEncoding {})?,
+            )?),
+            (Encoding::STRUCTURED, _) => {
+                event_formats().spec_version(self.content_type(), self.payload)
+            }
+            _ => Err(Error::WrongEncoding {}),
+        }
+    }
+}
+
+/// Extension trait for MQTT publish packets to read an [`Event`]
+pub trait PublishExt {
+    /// Read the [`Event`] from the packet, detecting whether it's encoded in binary or structured mode
+    fn to_event(&self) -> Result<Event>;
+}
+
+impl PublishExt for PublishV5 {
+    fn to_event(&self) -> Result<Event> {
+        MessageDeserializer::into_event(PublishDeserializer::new(
+            self.properties.as_ref(),
+            &self.payload,
+        ))
+    }
+}
+
+impl PublishExt for PublishV3 {
+    fn to_event(&self) -> Result<Event> {
+        MessageDeserializer::into_event(PublishDeserializer::new(None, &self.payload))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::binding::mqtt::{MessageRecord, MqttVersion};
+    use crate::{EventBuilder, EventBuilderV10};
+    use rumqttc::v5::mqttbytes::QoS as QoSV5;
+    use rumqttc::QoS;
+    use serde_json::json;
+
+    fn expected_event() -> Event {
+        EventBuilderV10::new()
+            .id("0001")
+            .ty("example.test")
+            .source("http://localhost/")
+            .data("application/json", json!({"hello": "world"}))
+            .extension("someint", "10")
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_v5_binary_publish() {
+        let properties = PublishProperties {
+            user_properties: vec![
+                (String::from("specversion"), String::from("1.0")),
+                (String::from("id"), String::from("0001")),
+                (String::from("type"), String::from("example.test")),
+                (String::from("source"), String::from("http://localhost/")),
+                (String::from("someint"), String::from("10")),
+            ],
+            content_type: Some(String::from("application/json")),
+            ..Default::default()
+        };
+        let publish = PublishV5::new(
+            "topic",
+            QoSV5::AtLeastOnce,
+            json!({"hello": "world"}).to_string(),
+            Some(properties),
+        );
+
+        assert_eq!(expected_event(), publish.to_event().unwrap());
+    }
+
+    #[test]
+    fn test_v5_structured_publish() {
+        let expected = expected_event();
+
+        let publish = MessageRecord::from_event(expected.clone(), MqttVersion::V3_1_1)
+            .unwrap()
+            .into_v5_publish("topic", QoSV5::AtLeastOnce);
+
+        assert_eq!(expected, publish.to_event().unwrap());
+    }
+
+    #[test]
+    fn test_v3_publish() {
+        let expected = expected_event();
+
+        let publish = PublishV3::new(
+            "topic",
+            QoS::AtLeastOnce,
+            serde_json::to_vec(&expected).unwrap(),
+        );
+
+        assert_eq!(expected, publish.to_event().unwrap());
+    }
+
+    #[test]
+    fn test_v5_not_an_event() {
+        let properties = PublishProperties {
+            content_type: Some(String::from("application/json")),
+            ..Default::default()
+        };
+        let publish = PublishV5::new("topic", QoSV5::AtLeastOnce, "hello", Some(properties));
+
+        assert!(publish.to_event().is_err());
+    }
+}
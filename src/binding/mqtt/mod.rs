@@ -0,0 +1,49 @@
+//! [MQTT Protocol Binding](https://github.com/cloudevents/spec/blob/v1.0/mqtt-protocol-binding.md)
+//! on top of the [`rumqttc`](https://docs.rs/rumqttc) publish packets.
+//!
+//! With MQTT 5, events are sent in binary mode, mapping context attributes and extensions to
+//! user properties and `datacontenttype` to the content type property.
+//! MQTT 3.1.1 has no message properties, so events are always sent in structured mode.
+//!
+//! ```
+//! use cloudevents::binding::mqtt::{MessageRecord, MqttVersion, PublishExt};
+//! use cloudevents::{EventBuilder, EventBuilderV10};
+//! use rumqttc::QoS;
+//!
+//! let event = EventBuilderV10::new()
+//!     .id("0001")
+//!     .source("http://localhost/")
+//!     .ty("example.demo")
+//!     .build()
+//!     .unwrap();
+//!
+//! let publish = MessageRecord::from_event(event.clone(), MqttVersion::V5)
+//!     .unwrap()
+//!     .into_v5_publish("topic", rumqttc::v5::mqttbytes::QoS::AtLeastOnce);
+//! assert_eq!(event, publish.to_event().unwrap());
+//!
+//! let publish = MessageRecord::from_event(event.clone(), MqttVersion::V3_1_1)
+//!     .unwrap()
+//!     .into_v3_publish("topic", QoS::AtLeastOnce);
+//! assert_eq!(event, publish.to_event().unwrap());
+//! ```
+
+mod deserializer;
+mod serializer;
+
+pub use deserializer::PublishDeserializer;
+pub use deserializer::PublishExt;
+pub use serializer::MessageRecord;
+
+pub(crate) const SPEC_VERSION: &str = "specversion";
+pub(crate) const CLOUDEVENTS_JSON_CONTENT_TYPE: &str = "application/cloudevents+json";
+
+/// MQTT protocol version, which determines the content mode used to encode an [`Event`](crate::Event)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(non_camel_case_types)]
+pub enum MqttVersion {
+    /// MQTT 3.1.1, supporting only structured mode
+    V3_1_1,
+    /// MQTT 5, using binary mode
+    V5,
+}
@@ -0,0 +1,159 @@
+use super::{MqttVersion, CLOUDEVENTS_JSON_CONTENT_TYPE, SPEC_VERSION};
+use crate::event::SpecVersion;
+use crate::message::{
+    BinaryDeserializer, BinarySerializer, MessageAttributeValue, Result, StructuredDeserializer,
+    StructuredSerializer,
+};
+use crate::Event;
+use bytes::Bytes;
+use rumqttc::v5::mqttbytes::v5::{Publish as PublishV5, PublishProperties};
+use rumqttc::v5::mqttbytes::QoS as QoSV5;
+use rumqttc::{Publish as PublishV3, QoS};
+
+/// Properties and payload of an MQTT publish packet,
+/// which implements [`StructuredSerializer`] and [`BinarySerializer`]
+#[derive(Debug, Clone, Default)]
+pub struct MessageRecord {
+    pub(crate) properties: PublishProperties,
+    pub(crate) payload: Vec<u8>,
+}
+
+impl MessageRecord {
+    pub fn new() -> MessageRecord {
+        MessageRecord::default()
+    }
+
+    /// Create a [`MessageRecord`] from an [`Event`],
+    /// using binary mode for [`MqttVersion::V5`] and structured mode for [`MqttVersion::V3_1_1`]
+    pub fn from_event(event: Event, version: MqttVersion) -> Result<MessageRecord> {
+        match version {
+            MqttVersion::V5 => BinaryDeserializer::deserialize_binary(event, MessageRecord::new()),
+            MqttVersion::V3_1_1 => {
+                StructuredDeserializer::deserialize_structured(event, MessageRecord::new())
+            }
+        }
+    }
+
+    /// MQTT 5 publish properties, to be used with `publish_with_properties`
+    pub fn properties(&self) -> &PublishProperties {
+        &self.properties
+    }
+
+    pub fn payload(&self) -> &[u8] {
+        &self.payload
+    }
+
+    /// Create an MQTT 5 publish packet
+    pub fn into_v5_publish(self, topic: impl Into<String>, qos: QoSV5) -> PublishV5 {
+        PublishV5::new(topic, qos, self.payload, Some(self.properties))
+    }
+
+    /// Create an MQTT 3.1.1 publish packet, dropping the properties
+    pub fn into_v3_publish(self, topic: impl Into<String>, qos: QoS) -> PublishV3 {
+        PublishV3::new(topic, qos, self.payload)
+    }
+}
+
+impl BinarySerializer<MessageRecord> for MessageRecord {
+    fn set_spec_version(mut self, spec_version: SpecVersion) -> Result<Self> {
+        self.properties
+            .user_properties
+            .push((String::from(SPEC_VERSION), spec_version.to_string()));
+        Ok(self)
+    }
+
+    fn set_attribute(mut self, name: &str, value: MessageAttributeValue) -> Result<Self> {
+        if name == "datacontenttype" {
+            self.properties.content_type = Some(String::from(value));
+        } else {
+            self.properties
+                .user_properties
+                .push((String::from(name), String::from(value)));
+        }
+        Ok(self)
+    }
+
+    fn set_extension(mut self, name: &str, value: MessageAttributeValue) -> Result<Self> {
+        self.properties
+            .user_properties
+            .push((String::from(name), String::from(value)));
+        Ok(self)
+    }
+
+    fn end_with_data(mut self, bytes: Bytes) -> Result<MessageRecord> {
+        self.payload = Vec::from(bytes);
+        Ok(self)
+    }
+
+    fn end(self) -> Result<MessageRecord> {
+        Ok(self)
+    }
+}
+
+impl StructuredSerializer<MessageRecord> for MessageRecord {
+    fn set_structured_event(mut self, bytes: Vec<u8>) -> Result<MessageRecord> {
+        self.properties.content_type = Some(String::from(CLOUDEVENTS_JSON_CONTENT_TYPE));
+        self.payload = bytes;
+        Ok(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EventBuilder, EventBuilderV10};
+    use serde_json::json;
+
+    fn event() -> Event {
+        EventBuilderV10::new()
+            .id("0001")
+            .ty("example.test")
+            .source("http://localhost/")
+            .data("application/json", json!({"hello": "world"}))
+            .extension("someint", "10")
+            .build()
+            .unwrap()
+    }
+
+    fn user_property<'a>(record: &'a MessageRecord, name: &str) -> Option<&'a str> {
+        record
+            .properties
+            .user_properties
+            .iter()
+            .find(|(k, _)| k == name)
+            .map(|(_, v)| v.as_str())
+    }
+
+    #[test]
+    fn test_v5_binary_record() {
+        let record = MessageRecord::from_event(event(), MqttVersion::V5).unwrap();
+
+        assert_eq!(Some("1.0"), user_property(&record, "specversion"));
+        assert_eq!(Some("0001"), user_property(&record, "id"));
+        assert_eq!(Some("10"), user_property(&record, "someint"));
+        assert_eq!(None, user_property(&record, "datacontenttype"));
+        assert_eq!(
+            Some("application/json"),
+            record.properties().content_type.as_deref()
+        );
+        assert_eq!(
+            json!({"hello": "world"}).to_string().as_bytes(),
+            record.payload()
+        );
+    }
+
+    #[test]
+    fn test_v3_structured_record() {
+        let expected = event();
+
+        let publish = MessageRecord::from_event(expected.clone(), MqttVersion::V3_1_1)
+            .unwrap()
+            .into_v3_publish("topic", QoS::AtMostOnce);
+
+        assert_eq!("topic", publish.topic);
+        assert_eq!(
+            expected,
+            serde_json::from_slice::<Event>(&publish.payload).unwrap()
+        );
+    }
+}
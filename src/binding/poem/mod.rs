@@ -0,0 +1,303 @@
+//! Integration with [Poem](https://github.com/poem-web/poem), built on top of the
+//! [`http`](super::http) binding.
+//!
+//! [`Event`] implements [`FromRequest`], parsing both binary and structured mode requests,
+//! and [`IntoResponse`], writing the event as a binary mode response,
+//! so handlers can take and return events directly:
+//!
+//! ```
+//! use cloudevents::Event;
+//! use poem::{handler, post, Route};
+//!
+//! #[handler]
+//! fn echo(event: Event) -> Event {
+//!     event
+//! }
+//!
+//! let app = Route::new().at("/", post(echo));
+//! ```
+//!
+//! [`Batch`] implements them too, for batched mode requests and responses.
+//!
+//! The [`WebhookValidation`] middleware replies to the abuse protection handshake of the
+//! HTTP WebHook spec, see [`WebhookValidator`]:
+//!
+//! ```
+//! use cloudevents::binding::http::WebhookValidator;
+//! use cloudevents::binding::poem::WebhookValidation;
+//! use cloudevents::Event;
+//! use poem::{handler, post, EndpointExt, Route};
+//!
+//! #[handler]
+//! fn handle(event: Event) {}
+//!
+//! let app = Route::new().at("/", post(handle)).with(WebhookValidation::new(
+//!     WebhookValidator::new().allow_origin("eventemitter.example.com"),
+//! ));
+//! ```
+
+use super::http::{batch_to_message, event_to_binary, receive_event, to_batch, WebhookValidator};
+use crate::message::{Error, Received};
+use crate::{Batch, Event};
+use ::http::header::{HeaderMap, HeaderName, HeaderValue};
+use poem::http::{Method, StatusCode};
+use poem::{Endpoint, FromRequest, IntoResponse, Middleware, Request, RequestBody, Response};
+
+/// Copy the headers of a Poem request to a [`HeaderMap`]
+fn to_header_map(headers: &poem::http::HeaderMap) -> Result<HeaderMap, Error> {
+    let mut map = HeaderMap::new();
+    for (name, value) in headers {
+        let name = HeaderName::from_bytes(name.as_str().as_bytes()).map_err(|e| Error::Other {
+            source: Box::new(e),
+        })?;
+        let value = HeaderValue::from_bytes(value.as_bytes()).map_err(|e| Error::Other {
+            source: Box::new(e),
+        })?;
+        map.append(name, value);
+    }
+    Ok(map)
+}
+
+/// Reply to a request which couldn't be read with the status code of `error`
+fn to_poem_error(error: Error) -> poem::Error {
+    let status = StatusCode::from_u16(error.status_code()).unwrap_or(StatusCode::BAD_REQUEST);
+    poem::Error::new(error, status)
+}
+
+/// Read the body of `req`, parse it with `parse` and intercept it with the installed
+/// [`Pipeline`](crate::message::Pipeline), failing with the status code of the error
+async fn parse_request<T: Received>(
+    req: &Request,
+    body: &mut RequestBody,
+    parse: fn(&HeaderMap, Vec<u8>) -> Result<T, Error>,
+) -> poem::Result<T> {
+    let headers = to_header_map(req.headers()).map_err(to_poem_error)?;
+    let body = body.take()?.into_vec().await?;
+    let mut value = parse(&headers, body).map_err(to_poem_error)?;
+    value
+        .intercept_receive("http", None)
+        .await
+        .map_err(to_poem_error)?;
+    Ok(value)
+}
+
+/// Write the message returned by `serialize`, replying with `500 Internal Server Error` on error
+fn message_response(serialize: Result<(HeaderMap, Vec<u8>), Error>) -> Response {
+    match serialize {
+        Ok((headers, body)) => headers
+            .iter()
+            .fold(Response::builder(), |response, (name, value)| {
+                response.header(name.as_str(), value.as_bytes())
+            })
+            .body(body),
+        Err(e) => Response::builder()
+            .status(StatusCode::INTERNAL_SERVER_ERROR)
+            .body(e.to_string()),
+    }
+}
+
+impl<'a> FromRequest<'a> for Event {
+    async fn from_request(req: &'a Request, body: &mut RequestBody) -> poem::Result<Self> {
+        parse_request(req, body, receive_event).await
+    }
+}
+
+impl IntoResponse for Event {
+    fn into_response(self) -> Response {
+        message_response(event_to_binary(self))
+    }
+}
+
+impl<'a> FromRequest<'a> for Batch {
+    async fn from_request(req: &'a Request, body: &mut RequestBody) -> poem::Result<Self> {
+        parse_request(req, body, to_batch).await
+    }
+}
+
+impl IntoResponse for Batch {
+    fn into_response(self) -> Response {
+        message_response(batch_to_message(self))
+    }
+}
+
+/// Middleware replying to the webhook validation requests with a [`WebhookValidator`], see
+/// [`WebhookValidator::validate`], and passing the other requests to the wrapped endpoint
+#[derive(Debug, Clone)]
+pub struct WebhookValidation {
+    validator: WebhookValidator,
+}
+
+impl WebhookValidation {
+    /// Create a new [`WebhookValidation`] middleware replying with `validator`
+    pub fn new(validator: WebhookValidator) -> Self {
+        WebhookValidation { validator }
+    }
+}
+
+impl<E: Endpoint> Middleware<E> for WebhookValidation {
+    type Output = WebhookValidationEndpoint<E>;
+
+    fn transform(&self, inner: E) -> Self::Output {
+        WebhookValidationEndpoint {
+            inner,
+            validator: self.validator.clone(),
+        }
+    }
+}
+
+/// Endpoint created by the [`WebhookValidation`] middleware
+#[derive(Debug)]
+pub struct WebhookValidationEndpoint<E> {
+    inner: E,
+    validator: WebhookValidator,
+}
+
+impl<E: Endpoint> Endpoint for WebhookValidationEndpoint<E> {
+    type Output = Response;
+
+    async fn call(&self, req: Request) -> poem::Result<Self::Output> {
+        if req.method() == Method::OPTIONS {
+            let headers = to_header_map(req.headers()).map_err(to_poem_error)?;
+            if let Some(validation) = self.validator.validate(&::http::Method::OPTIONS, &headers) {
+                let status = StatusCode::from_u16(validation.status().as_u16())
+                    .unwrap_or(StatusCode::BAD_REQUEST);
+                return Ok(validation
+                    .headers()
+                    .iter()
+                    .fold(Response::builder(), |response, (name, value)| {
+                        response.header(name.as_str(), value.as_bytes())
+                    })
+                    .status(status)
+                    .finish());
+            }
+        }
+        self.inner.call(req).await.map(IntoResponse::into_response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AttributesReader, EventBuilder, EventBuilderV10};
+    use poem::{handler, post, EndpointExt, Route};
+    use serde_json::json;
+
+    fn expected_event() -> Event {
+        EventBuilderV10::new()
+            .id("0001")
+            .ty("example.test")
+            .source("http://localhost/")
+            .data("application/json", json!({"hello": "world"}))
+            .extension("someint", "10")
+            .build()
+            .unwrap()
+    }
+
+    #[handler]
+    fn echo(event: Event) -> Event {
+        event
+    }
+
+    #[handler]
+    fn echo_batch(batch: Batch) -> Batch {
+        batch
+    }
+
+    fn app() -> impl Endpoint {
+        Route::new()
+            .at("/", post(echo))
+            .at("/batch", post(echo_batch))
+            .with(WebhookValidation::new(
+                WebhookValidator::new().allow_origin("example.com"),
+            ))
+    }
+
+    #[tokio::test]
+    async fn test_binary_and_structured() {
+        let app = app();
+
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri_str("/")
+            .header("ce-specversion", "1.0")
+            .header("ce-id", "0001")
+            .header("ce-type", "example.test")
+            .header("ce-source", "http://localhost/")
+            .header("ce-someint", "10")
+            .content_type("application/json")
+            .body(json!({"hello": "world"}).to_string());
+        let response = app.get_response(request).await;
+        assert_eq!(StatusCode::OK, response.status());
+        assert_eq!(Some("0001"), response.header("ce-id"));
+        assert_eq!(Some("10"), response.header("ce-someint"));
+        assert_eq!(Some("application/json"), response.header("content-type"));
+        let body = response.into_body().into_vec().await.unwrap();
+        assert_eq!(
+            json!({"hello": "world"}),
+            serde_json::from_slice::<serde_json::Value>(&body).unwrap()
+        );
+
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri_str("/")
+            .content_type("application/cloudevents+json")
+            .body(serde_json::to_vec(&expected_event()).unwrap());
+        let response = app.get_response(request).await;
+        assert_eq!(StatusCode::OK, response.status());
+        assert_eq!(Some("example.test"), response.header("ce-type"));
+
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri_str("/")
+            .content_type("application/json")
+            .body(json!({"hello": "world"}).to_string());
+        let response = app.get_response(request).await;
+        assert_eq!(StatusCode::BAD_REQUEST, response.status());
+    }
+
+    #[tokio::test]
+    async fn test_batch() {
+        let expected = Batch::from(vec![expected_event(), expected_event()]);
+
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri_str("/batch")
+            .content_type("application/cloudevents-batch+json")
+            .body(serde_json::to_vec(&expected).unwrap());
+        let response = app().get_response(request).await;
+        assert_eq!(StatusCode::OK, response.status());
+        assert_eq!(
+            Some("application/cloudevents-batch+json"),
+            response.header("content-type")
+        );
+        let body = response.into_body().into_vec().await.unwrap();
+        let batch: Batch = serde_json::from_slice(&body).unwrap();
+        assert_eq!(2, batch.len());
+        assert_eq!("0001", batch[0].get_id());
+    }
+
+    #[tokio::test]
+    async fn test_webhook_validation() {
+        let app = app();
+
+        let request = Request::builder()
+            .method(Method::OPTIONS)
+            .uri_str("/")
+            .header("webhook-request-origin", "example.com")
+            .finish();
+        let response = app.get_response(request).await;
+        assert_eq!(StatusCode::OK, response.status());
+        assert_eq!(
+            Some("example.com"),
+            response.header("webhook-allowed-origin")
+        );
+
+        let request = Request::builder()
+            .method(Method::OPTIONS)
+            .uri_str("/")
+            .header("webhook-request-origin", "other.com")
+            .finish();
+        let response = app.get_response(request).await;
+        assert_eq!(StatusCode::FORBIDDEN, response.status());
+    }
+}
@@ -0,0 +1,127 @@
+use super::kafka_consumer_record::receive_record;
+use crate::message::{Acknowledger, Delivery, Error, Outcome, Result};
+use rdkafka::consumer::{CommitMode, Consumer, ConsumerContext, DefaultConsumerContext};
+use rdkafka::message::BorrowedMessage;
+use rdkafka::{Message, Offset, TopicPartitionList};
+use std::marker::PhantomData;
+use std::time::Duration;
+
+const SEEK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// [`Acknowledger`] of an event received from a Kafka consumer: it commits the offset of the
+/// message when acknowledged, and seeks the partition back to the message when rejected, so
+/// it's consumed again.
+///
+/// Kafka can't delay the redelivery, so [`Outcome::RetryAfter`] seeks back as
+/// [`Outcome::Nack`] does: the consumer should wait the delay before polling the partition.
+pub struct KafkaAcknowledger<'a, K, C = DefaultConsumerContext> {
+    consumer: &'a K,
+    topic: String,
+    partition: i32,
+    offset: i64,
+    context: PhantomData<C>,
+}
+
+impl<'a, K, C> KafkaAcknowledger<'a, K, C>
+where
+    K: Consumer<C>,
+    C: ConsumerContext,
+{
+    /// Create a new [`KafkaAcknowledger`] of the message at `offset` of `topic` and `partition`
+    pub fn new(consumer: &'a K, topic: impl Into<String>, partition: i32, offset: i64) -> Self {
+        KafkaAcknowledger {
+            consumer,
+            topic: topic.into(),
+            partition,
+            offset,
+            context: PhantomData,
+        }
+    }
+
+    fn commit(&self) -> rdkafka::error::KafkaResult<()> {
+        let mut offsets = TopicPartitionList::new();
+        offsets.add_partition_offset(
+            &self.topic,
+            self.partition,
+            Offset::Offset(self.offset + 1),
+        )?;
+        self.consumer.commit(&offsets, CommitMode::Async)
+    }
+
+    fn seek(&self) -> rdkafka::error::KafkaResult<()> {
+        self.consumer.seek(
+            &self.topic,
+            self.partition,
+            Offset::Offset(self.offset),
+            SEEK_TIMEOUT,
+        )
+    }
+}
+
+impl<K, C> Acknowledger for KafkaAcknowledger<'_, K, C>
+where
+    K: Consumer<C>,
+    C: ConsumerContext,
+{
+    type Output = Result<()>;
+
+    fn settle(self, outcome: Outcome) -> Result<()> {
+        match outcome {
+            Outcome::Ack => self.commit(),
+            Outcome::Nack | Outcome::RetryAfter(_) => self.seek(),
+        }
+        .map_err(|e| Error::Other {
+            source: Box::new(e),
+        })
+    }
+}
+
+impl<K, C> std::fmt::Debug for KafkaAcknowledger<'_, K, C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("KafkaAcknowledger")
+            .field("topic", &self.topic)
+            .field("partition", &self.partition)
+            .field("offset", &self.offset)
+            .finish()
+    }
+}
+
+/// Read the [`Delivery`] of `message`, received from `consumer`, see
+/// [`MessageExt::to_event`](super::MessageExt::to_event).
+///
+/// The installed [`Pipeline`](crate::message::Pipeline) is asynchronous, so it doesn't intercept
+/// the delivered event: call [`Pipeline::receive`](crate::message::Pipeline::receive) on it if
+/// needed.
+///
+/// ```no_run
+/// use cloudevents::binding::rdkafka::to_delivery;
+/// use rdkafka::consumer::StreamConsumer;
+///
+/// # async fn example(consumer: StreamConsumer) -> Result<(), Box<dyn std::error::Error>> {
+/// let message = consumer.recv().await?;
+/// let delivery = to_delivery(&consumer, &message)?;
+/// if process(&delivery) {
+///     delivery.ack()?;
+/// } else {
+///     delivery.nack()?;
+/// }
+/// # Ok(())
+/// # }
+/// # fn process(_: &cloudevents::Event) -> bool { true }
+/// ```
+pub fn to_delivery<'a, K, C>(
+    consumer: &'a K,
+    message: &BorrowedMessage<'_>,
+) -> Result<Delivery<KafkaAcknowledger<'a, K, C>>>
+where
+    K: Consumer<C>,
+    C: ConsumerContext,
+{
+    let acknowledger = KafkaAcknowledger::new(
+        consumer,
+        message.topic(),
+        message.partition(),
+        message.offset(),
+    );
+    Ok(Delivery::new(receive_record(message)?, acknowledger))
+}
@@ -0,0 +1,23 @@
+macro_rules! bytes_to_str {
+    ($bytes:expr) => {
+        std::str::from_utf8($bytes).map_err(|e| crate::message::Error::Other {
+            source: Box::new(e),
+        })
+    };
+}
+
+pub(crate) const CE_PREFIX: &str = "ce_";
+pub(crate) const SPEC_VERSION_HEADER: &str = "ce_specversion";
+pub(crate) const CONTENT_TYPE: &str = "content-type";
+pub(crate) const CLOUDEVENTS_JSON_CONTENT_TYPE: &str = "application/cloudevents+json";
+pub(crate) const CLOUDEVENTS_BATCH_JSON_CONTENT_TYPE: &str = "application/cloudevents-batch+json";
+pub(crate) const PARTITION_KEY_EXTENSION: &str = "partitionkey";
+
+/// Map a context attribute or extension name to its Kafka header
+pub(crate) fn attribute_name_to_header(name: &str) -> String {
+    if name == "datacontenttype" {
+        String::from(CONTENT_TYPE)
+    } else {
+        [CE_PREFIX, name].concat()
+    }
+}
@@ -0,0 +1,363 @@
+use super::headers;
+use crate::event::SpecVersion;
+use crate::message::{
+    event_formats, BatchDeserializer, BatchSerializer, BinaryDeserializer, BinarySerializer,
+    Encoding, Error, MessageAttributeValue, MessageDeserializer, Result, StructuredDeserializer,
+    StructuredSerializer,
+};
+use crate::{Batch, Event};
+use bytes::Bytes;
+use rdkafka::message::{BorrowedMessage, Headers, Message, OwnedMessage};
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+/// Wrapper for a Kafka [`Message`] that implements [`MessageDeserializer`]
+pub struct ConsumerRecordDeserializer {
+    pub(crate) headers: HashMap<String, Vec<u8>>,
+    pub(crate) payload: Option<Vec<u8>>,
+    pub(crate) key: Option<Vec<u8>>,
+}
+
+impl ConsumerRecordDeserializer {
+    pub fn new<M: Message>(message: &M) -> ConsumerRecordDeserializer {
+        let headers = message
+            .headers()
+            .map(|headers| {
+                headers
+                    .iter()
+                    .filter_map(|h| h.value.map(|v| (h.key.to_string(), v.to_vec())))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        ConsumerRecordDeserializer {
+            headers,
+            payload: message.payload().map(Vec::from),
+            key: message.key().map(Vec::from),
+        }
+    }
+}
+
+impl BinaryDeserializer for ConsumerRecordDeserializer {
+    fn deserialize_binary<R: Sized, V: BinarySerializer<R>>(self, mut visitor: V) -> Result<R> {
+        if self.encoding() != Encoding::BINARY {
+            return Err(Error::WrongEncoding {});
+        }
+
+        let spec_version = SpecVersion::try_from(bytes_to_str!(self
+            .headers
+            .get(headers::SPEC_VERSION_HEADER)
+            .ok_or(Error::WrongEncoding {})?)?)?;
+
+        visitor = visitor.set_spec_version(spec_version.clone())?;
+
+        let attributes = spec_version.attribute_names();
+
+        for (hn, hv) in self.headers.iter().filter(|(hn, _)| {
+            hn.as_str() != headers::SPEC_VERSION_HEADER && hn.starts_with(headers::CE_PREFIX)
+        }) {
+            let name = &hn[headers::CE_PREFIX.len()..];
+            let value = MessageAttributeValue::from(bytes_to_str!(hv)?);
+
+            if attributes.contains(&name) {
+                visitor = visitor.set_attribute(name, value)?
+            } else {
+                visitor = visitor.set_extension(name, value)?
+            }
+        }
+
+        let partition_key_header =
+            headers::attribute_name_to_header(headers::PARTITION_KEY_EXTENSION);
+        // Kafka keys can be binary, which can't be mapped to the string `partitionkey`
+        let key = self
+            .key
+            .as_deref()
+            .and_then(|k| std::str::from_utf8(k).ok());
+        if let (Some(key), false) = (key, self.headers.contains_key(&partition_key_header)) {
+            visitor = visitor.set_extension(
+                headers::PARTITION_KEY_EXTENSION,
+                MessageAttributeValue::from(key),
+            )?
+        }
+
+        if let Some(hv) = self.headers.get(headers::CONTENT_TYPE) {
+            visitor = visitor.set_attribute(
+                "datacontenttype",
+                MessageAttributeValue::from(bytes_to_str!(hv)?),
+            )?
+        }
+
+        match self.payload {
+            Some(payload) if !payload.is_empty() => visitor.end_with_data(Bytes::from(payload)),
+            _ => visitor.end(),
+        }
+    }
+}
+
+impl ConsumerRecordDeserializer {
+    fn content_type(&self) -> Option<&str> {
+        self.headers
+            .get(headers::CONTENT_TYPE)
+            .and_then(|v| std::str::from_utf8(v).ok())
+    }
+}
+
+impl StructuredDeserializer for ConsumerRecordDeserializer {
+    fn deserialize_structured<R: Sized, V: StructuredSerializer<R>>(self, visitor: V) -> Result<R> {
+        if self.encoding() != Encoding::STRUCTURED {
+            return Err(Error::WrongEncoding {});
+        }
+        let content_type = self.content_type().map(str::to_string);
+        let payload = self.payload.unwrap_or_default();
+        let payload = event_formats().to_json(content_type.as_deref(), payload)?;
+        visitor.set_structured_event(payload)
+    }
+}
+
+impl BatchDeserializer for ConsumerRecordDeserializer {
+    fn deserialize_batch<R: Sized, V: BatchSerializer<R>>(self, visitor: V) -> Result<R> {
+        if self.encoding() != Encoding::BATCH {
+            return Err(Error::WrongEncoding {});
+        }
+        visitor.set_batch(self.payload.unwrap_or_default())
+    }
+}
+
+impl MessageDeserializer for ConsumerRecordDeserializer {
+    fn encoding(&self) -> Encoding {
+        let media_type = self
+            .content_type()
+            .and_then(|ct| ct.split(';').next())
+            .map(str::trim);
+
+        if media_type.is_some_and(|mt| event_formats().contains(mt)) {
+            Encoding::STRUCTURED
+        } else if media_type
+            .is_some_and(|mt| mt.eq_ignore_ascii_case(headers::CLOUDEVENTS_BATCH_JSON_CONTENT_TYPE))
+        {
+            Encoding::BATCH
+        } else if self.headers.contains_key(headers::SPEC_VERSION_HEADER) {
+            Encoding::BINARY
+        } else {
+            Encoding::UNKNOWN
+        }
+    }
+
+    fn spec_version(&self) -> Result<SpecVersion> {
+        match self.encoding() {
+            Encoding::BINARY => Ok(SpecVersion::try_from(bytes_to_str!(self
+                .headers
+                .get(headers::SPEC_VERSION_HEADER)
+                .ok_or(Error::WrongEncoding {})?)?)?),
+            Encoding::STRUCTURED => event_formats().spec_version(
+                self.content_type(),
+                self.payload.as_deref().unwrap_or_default(),
+            ),
+            _ => Err(Error::WrongEncoding {}),
+        }
+    }
+}
+
+/// Method to decode a Kafka [`Message`] to [`Event`],
+/// detecting from its headers whether it's encoded in binary or structured mode
+pub fn record_to_event<M: Message>(message: &M) -> Result<Event> {
+    MessageDeserializer::into_event(ConsumerRecordDeserializer::new(message))
+}
+
+/// Decode an event consumed from Kafka, see [`record_to_event`], reporting it to the
+/// [`metrics`](crate::metrics)
+pub(crate) fn receive_record<M: Message>(message: &M) -> Result<Event> {
+    let result = record_to_event(message);
+    let bytes = message.payload().map_or(0, <[u8]>::len);
+    crate::metrics::record_receive("kafka", bytes, &result);
+    result
+}
+
+/// Method to decode a Kafka [`Message`] to [`Batch`].
+/// A batched mode message is decoded as is, while a single event in binary or structured mode
+/// is decoded as a batch of one event.
+pub fn record_to_batch<M: Message>(message: &M) -> Result<Batch> {
+    let deserializer = ConsumerRecordDeserializer::new(message);
+    if deserializer.encoding() == Encoding::BATCH {
+        deserializer.into_batch()
+    } else {
+        Ok(Batch::from(vec![MessageDeserializer::into_event(
+            deserializer,
+        )?]))
+    }
+}
+
+/// Extension trait for Kafka messages to read an [`Event`] or a [`Batch`]
+pub trait MessageExt {
+    /// Read the [`Event`] from the message, detecting whether it's encoded in binary or structured mode
+    fn to_event(&self) -> Result<Event>;
+
+    /// Read the [`Batch`] from the message, see [`record_to_batch`]
+    fn to_batch(&self) -> Result<Batch>;
+}
+
+impl MessageExt for BorrowedMessage<'_> {
+    fn to_event(&self) -> Result<Event> {
+        record_to_event(self)
+    }
+
+    fn to_batch(&self) -> Result<Batch> {
+        record_to_batch(self)
+    }
+}
+
+impl MessageExt for OwnedMessage {
+    fn to_event(&self) -> Result<Event> {
+        record_to_event(self)
+    }
+
+    fn to_batch(&self) -> Result<Batch> {
+        record_to_batch(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::binding::rdkafka::MessageRecord;
+    use crate::{AttributesWriter, EventBuilder, EventBuilderV10};
+    use rdkafka::message::{Header, OwnedHeaders, Timestamp};
+    use serde_json::json;
+
+    fn to_owned_message(record: MessageRecord) -> OwnedMessage {
+        OwnedMessage::new(
+            record.payload,
+            record.key.map(String::into_bytes),
+            String::from("topic"),
+            Timestamp::NotAvailable,
+            0,
+            0,
+            Some(record.headers),
+        )
+    }
+
+    fn expected_event() -> Event {
+        EventBuilderV10::new()
+            .id("0001")
+            .ty("example.test")
+            .source("http://localhost/")
+            .data("application/json", json!({"hello": "world"}))
+            .extension("partitionkey", "my-key")
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_binary_record_to_event() {
+        let expected = expected_event();
+
+        let message = to_owned_message(MessageRecord::from_event(expected.clone()).unwrap());
+
+        assert_eq!(expected, message.to_event().unwrap());
+    }
+
+    #[test]
+    fn test_binary_record_with_binary_key_to_event() {
+        let mut expected = expected_event();
+        expected.remove_extension("partitionkey");
+        let record = MessageRecord::from_event(expected.clone()).unwrap();
+        let message = OwnedMessage::new(
+            record.payload,
+            Some(vec![0xff, 0xfe, 0x00]),
+            String::from("topic"),
+            Timestamp::NotAvailable,
+            0,
+            0,
+            Some(record.headers),
+        );
+
+        assert_eq!(expected, message.to_event().unwrap());
+    }
+
+    #[test]
+    fn test_structured_record_to_event() {
+        let expected = expected_event();
+
+        let message =
+            to_owned_message(MessageRecord::from_event_structured(expected.clone()).unwrap());
+
+        assert_eq!(expected, message.to_event().unwrap());
+    }
+
+    #[test]
+    fn test_batch_record_to_batch() {
+        let expected = Batch::from(vec![expected_event(), expected_event()]);
+
+        let message = to_owned_message(MessageRecord::from_batch(expected.clone()).unwrap());
+
+        assert_eq!(expected, message.to_batch().unwrap());
+        assert!(message.to_event().is_err());
+    }
+
+    #[test]
+    fn test_binary_record_to_batch() {
+        let message = to_owned_message(MessageRecord::from_event(expected_event()).unwrap());
+
+        assert_eq!(
+            Batch::from(vec![expected_event()]),
+            message.to_batch().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_spec_version() {
+        let binary = ConsumerRecordDeserializer::new(&to_owned_message(
+            MessageRecord::from_event(expected_event()).unwrap(),
+        ));
+        assert_eq!(SpecVersion::V10, binary.spec_version().unwrap());
+
+        let structured = ConsumerRecordDeserializer::new(&to_owned_message(
+            MessageRecord::from_event_structured(expected_event()).unwrap(),
+        ));
+        assert_eq!(SpecVersion::V10, structured.spec_version().unwrap());
+    }
+
+    #[test]
+    fn test_key_to_partition_key() {
+        let mut headers = OwnedHeaders::new();
+        for (key, value) in &[
+            ("ce_specversion", "1.0"),
+            ("ce_id", "0001"),
+            ("ce_type", "example.test"),
+            ("ce_source", "http://localhost/"),
+            ("content-type", "application/json"),
+        ] {
+            headers = headers.insert(Header {
+                key,
+                value: Some(*value),
+            });
+        }
+
+        let message = OwnedMessage::new(
+            Some(json!({"hello": "world"}).to_string().into_bytes()),
+            Some(b"my-key".to_vec()),
+            String::from("topic"),
+            Timestamp::NotAvailable,
+            0,
+            0,
+            Some(headers),
+        );
+
+        assert_eq!(expected_event(), message.to_event().unwrap());
+    }
+
+    #[test]
+    fn test_not_an_event() {
+        let message = OwnedMessage::new(
+            Some(b"hello".to_vec()),
+            None,
+            String::from("topic"),
+            Timestamp::NotAvailable,
+            0,
+            0,
+            None,
+        );
+
+        assert!(message.to_event().is_err());
+    }
+}
@@ -0,0 +1,271 @@
+use super::headers;
+use crate::event::SpecVersion;
+use crate::extensions::{PartitionKeyExtensionStrategy, PartitionKeyStrategy};
+use crate::message::{
+    BatchDeserializer, BatchSerializer, BinaryDeserializer, BinarySerializer,
+    MessageAttributeValue, Result, StructuredDeserializer, StructuredSerializer,
+};
+use crate::{Batch, Event};
+use bytes::Bytes;
+use rdkafka::message::{Header, OwnedHeaders, ToBytes};
+use rdkafka::producer::FutureRecord;
+
+/// Headers, payload and key of a Kafka message, which implements [`StructuredSerializer`],
+/// [`BinarySerializer`] and [`BatchSerializer`]
+pub struct MessageRecord {
+    pub(crate) headers: OwnedHeaders,
+    pub(crate) payload: Option<Vec<u8>>,
+    pub(crate) key: Option<String>,
+}
+
+impl MessageRecord {
+    pub fn new() -> MessageRecord {
+        MessageRecord {
+            headers: OwnedHeaders::new(),
+            payload: None,
+            key: None,
+        }
+    }
+
+    /// Create a [`MessageRecord`] from an [`Event`] in binary mode
+    pub fn from_event(event: Event) -> Result<MessageRecord> {
+        BinaryDeserializer::deserialize_binary(event, MessageRecord::new())
+    }
+
+    /// Create a [`MessageRecord`] from an [`Event`] in structured mode.
+    /// The message key is set from the `partitionkey` extension, if present.
+    pub fn from_event_structured(event: Event) -> Result<MessageRecord> {
+        MessageRecord::from_event_structured_with_key(event, &PartitionKeyExtensionStrategy)
+    }
+
+    /// Create a [`MessageRecord`] from an [`Event`] in binary mode,
+    /// setting the message key with the provided [`PartitionKeyStrategy`]
+    pub fn from_event_with_key(
+        event: Event,
+        strategy: &impl PartitionKeyStrategy,
+    ) -> Result<MessageRecord> {
+        let key = strategy.partition_key(&event);
+        let mut record = BinaryDeserializer::deserialize_binary(event, MessageRecord::new())?;
+        record.key = key;
+        Ok(record)
+    }
+
+    /// Create a [`MessageRecord`] from an [`Event`] in structured mode,
+    /// setting the message key with the provided [`PartitionKeyStrategy`]
+    pub fn from_event_structured_with_key(
+        event: Event,
+        strategy: &impl PartitionKeyStrategy,
+    ) -> Result<MessageRecord> {
+        let key = strategy.partition_key(&event);
+        let mut record =
+            StructuredDeserializer::deserialize_structured(event, MessageRecord::new())?;
+        record.key = key;
+        Ok(record)
+    }
+
+    /// Create a [`MessageRecord`] from a [`Batch`] in batched mode
+    pub fn from_batch(batch: Batch) -> Result<MessageRecord> {
+        BatchDeserializer::deserialize_batch(batch, MessageRecord::new())
+    }
+
+    /// Message key, taken from the `partitionkey` extension
+    pub fn key(&self) -> Option<&str> {
+        self.key.as_deref()
+    }
+
+    fn insert_header(mut self, key: &str, value: &str) -> Self {
+        self.headers = self.headers.insert(Header {
+            key,
+            value: Some(value),
+        });
+        self
+    }
+}
+
+impl Default for MessageRecord {
+    fn default() -> Self {
+        MessageRecord::new()
+    }
+}
+
+impl BinarySerializer<MessageRecord> for MessageRecord {
+    fn set_spec_version(self, spec_version: SpecVersion) -> Result<Self> {
+        Ok(self.insert_header(headers::SPEC_VERSION_HEADER, spec_version.as_str()))
+    }
+
+    fn set_attribute(self, name: &str, value: MessageAttributeValue) -> Result<Self> {
+        Ok(self.insert_header(&headers::attribute_name_to_header(name), &value.as_str()))
+    }
+
+    fn set_extension(mut self, name: &str, value: MessageAttributeValue) -> Result<Self> {
+        if name == headers::PARTITION_KEY_EXTENSION {
+            self.key = Some(value.as_str().into_owned());
+        }
+        Ok(self.insert_header(&headers::attribute_name_to_header(name), &value.as_str()))
+    }
+
+    fn end_with_data(mut self, bytes: Bytes) -> Result<MessageRecord> {
+        self.payload = Some(Vec::from(bytes));
+        Ok(self)
+    }
+
+    fn end(self) -> Result<MessageRecord> {
+        Ok(self)
+    }
+}
+
+impl StructuredSerializer<MessageRecord> for MessageRecord {
+    fn set_structured_event(self, bytes: Vec<u8>) -> Result<MessageRecord> {
+        let mut record = self.insert_header(
+            headers::CONTENT_TYPE,
+            headers::CLOUDEVENTS_JSON_CONTENT_TYPE,
+        );
+        record.payload = Some(bytes);
+        Ok(record)
+    }
+}
+
+impl BatchSerializer<MessageRecord> for MessageRecord {
+    fn set_batch(self, bytes: Vec<u8>) -> Result<MessageRecord> {
+        let mut record = self.insert_header(
+            headers::CONTENT_TYPE,
+            headers::CLOUDEVENTS_BATCH_JSON_CONTENT_TYPE,
+        );
+        record.payload = Some(bytes);
+        Ok(record)
+    }
+}
+
+/// Extension trait for [`FutureRecord`] to attach the headers, payload and key of a [`MessageRecord`]
+pub trait FutureRecordExt<'a, K: ToBytes + ?Sized> {
+    /// Attach the headers and the payload of the provided [`MessageRecord`].
+    /// The record key is set from the `partitionkey` extension, if present.
+    fn message_record(self, message_record: &'a MessageRecord) -> FutureRecord<'a, K, Vec<u8>>;
+}
+
+impl<'a, K> FutureRecordExt<'a, K> for FutureRecord<'a, K, Vec<u8>>
+where
+    K: ToBytes + ?Sized,
+    str: AsRef<K>,
+{
+    fn message_record(self, message_record: &'a MessageRecord) -> FutureRecord<'a, K, Vec<u8>> {
+        let mut record = self.headers(message_record.headers.clone());
+        if let Some(payload) = &message_record.payload {
+            record = record.payload(payload);
+        }
+        if let Some(key) = &message_record.key {
+            record = record.key(key.as_str().as_ref());
+        }
+        record
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::extensions::AttributePartitionKeyStrategy;
+    use crate::{EventBuilder, EventBuilderV10};
+    use rdkafka::message::Headers;
+    use serde_json::json;
+
+    fn header<'a>(record: &'a MessageRecord, key: &str) -> Option<&'a str> {
+        record
+            .headers
+            .iter()
+            .find(|h| h.key == key)
+            .and_then(|h| h.value)
+            .map(|v| std::str::from_utf8(v).unwrap())
+    }
+
+    #[test]
+    fn test_binary_record() {
+        let event = EventBuilderV10::new()
+            .id("0001")
+            .ty("example.test")
+            .source("http://localhost/")
+            .data("application/json", json!({"hello": "world"}))
+            .extension("partitionkey", "my-key")
+            .build()
+            .unwrap();
+
+        let message_record = MessageRecord::from_event(event).unwrap();
+
+        assert_eq!(Some("1.0"), header(&message_record, "ce_specversion"));
+        assert_eq!(Some("0001"), header(&message_record, "ce_id"));
+        assert_eq!(
+            Some("application/json"),
+            header(&message_record, "content-type")
+        );
+        assert_eq!(Some("my-key"), header(&message_record, "ce_partitionkey"));
+        assert_eq!(Some("my-key"), message_record.key());
+        assert_eq!(
+            Some(json!({"hello": "world"}).to_string().into_bytes()),
+            message_record.payload
+        );
+
+        let record: FutureRecord<str, Vec<u8>> =
+            FutureRecord::to("topic").message_record(&message_record);
+        assert_eq!(Some("my-key"), record.key);
+        assert_eq!(message_record.payload.as_ref(), record.payload);
+    }
+
+    #[test]
+    fn test_structured_record() {
+        let event = EventBuilderV10::new()
+            .id("0001")
+            .ty("example.test")
+            .source("http://localhost/")
+            .build()
+            .unwrap();
+
+        let message_record = MessageRecord::from_event_structured(event.clone()).unwrap();
+
+        assert_eq!(
+            Some("application/cloudevents+json"),
+            header(&message_record, "content-type")
+        );
+        assert_eq!(None, header(&message_record, "ce_id"));
+        assert_eq!(
+            event,
+            serde_json::from_slice::<Event>(&message_record.payload.unwrap()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_structured_record_key() {
+        let event = EventBuilderV10::new()
+            .id("0001")
+            .ty("example.test")
+            .source("http://localhost/")
+            .extension("partitionkey", "my-key")
+            .build()
+            .unwrap();
+
+        let message_record = MessageRecord::from_event_structured(event).unwrap();
+
+        assert_eq!(None, header(&message_record, "ce_partitionkey"));
+        assert_eq!(Some("my-key"), message_record.key());
+    }
+
+    #[test]
+    fn test_record_key_strategy() {
+        let event = EventBuilderV10::new()
+            .id("0001")
+            .ty("example.test")
+            .source("http://localhost/")
+            .subject("my-subject")
+            .extension("partitionkey", "my-key")
+            .build()
+            .unwrap();
+
+        let strategy = AttributePartitionKeyStrategy::new("subject");
+
+        let message_record = MessageRecord::from_event_with_key(event.clone(), &strategy).unwrap();
+        assert_eq!(Some("my-subject"), message_record.key());
+        assert_eq!(Some("my-key"), header(&message_record, "ce_partitionkey"));
+
+        let message_record =
+            MessageRecord::from_event_structured_with_key(event, &|_: &Event| None).unwrap();
+        assert_eq!(None, message_record.key());
+    }
+}
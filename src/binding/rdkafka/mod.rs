@@ -0,0 +1,47 @@
+//! [Kafka Protocol Binding](https://github.com/cloudevents/spec/blob/v1.0/kafka-protocol-binding.md)
+//! on top of the [`rdkafka`](https://docs.rs/rdkafka) crate types, supporting both binary and structured mode.
+//!
+//! Context attributes and extensions are mapped to `ce_`-prefixed headers, `datacontenttype` to
+//! the `content-type` header, and the `partitionkey` extension to the message key.
+//! Use [`MessageRecord::from_event_with_key`] to compute the message key with another
+//! [`PartitionKeyStrategy`](crate::extensions::PartitionKeyStrategy).
+//!
+//! With the `stream` feature, `event_stream` and `event_sink` adapt a consumer and a producer to
+//! an `message::EventStream` and an `message::EventSink`.
+//!
+//! ```
+//! use cloudevents::binding::rdkafka::{FutureRecordExt, MessageExt, MessageRecord};
+//! use cloudevents::{EventBuilder, EventBuilderV10};
+//! use rdkafka::producer::FutureRecord;
+//!
+//! let event = EventBuilderV10::new()
+//!     .id("0001")
+//!     .source("http://localhost/")
+//!     .ty("example.demo")
+//!     .extension("partitionkey", "my-key")
+//!     .build()
+//!     .unwrap();
+//!
+//! let message_record = MessageRecord::from_event(event).unwrap();
+//! let record: FutureRecord<str, Vec<u8>> = FutureRecord::to("topic").message_record(&message_record);
+//! ```
+
+#[macro_use]
+mod headers;
+mod delivery;
+mod kafka_consumer_record;
+mod kafka_producer_record;
+mod sender;
+#[cfg(feature = "stream")]
+mod stream;
+
+pub use delivery::{to_delivery, KafkaAcknowledger};
+pub use kafka_consumer_record::record_to_batch;
+pub use kafka_consumer_record::record_to_event;
+pub use kafka_consumer_record::ConsumerRecordDeserializer;
+pub use kafka_consumer_record::MessageExt;
+pub use kafka_producer_record::FutureRecordExt;
+pub use kafka_producer_record::MessageRecord;
+pub use sender::event_sender;
+#[cfg(feature = "stream")]
+pub use stream::{event_sink, event_stream};
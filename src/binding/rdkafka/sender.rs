@@ -0,0 +1,93 @@
+use super::{FutureRecordExt, MessageRecord};
+use crate::message::{intercept_send, Error, EventSender, Result};
+use crate::Event;
+use rdkafka::client::ClientContext;
+use rdkafka::producer::{FutureProducer, FutureRecord};
+use rdkafka::util::AsyncRuntime;
+use std::future::Future;
+use std::time::Instant;
+
+/// Get an [`EventSender`] producing the events to `topic` in binary mode, see
+/// [`MessageRecord::from_event`], e.g. as destination of a
+/// [`DeadLetterPolicy`](crate::message::DeadLetterPolicy).
+///
+/// Sending fails with [`Error::Other`] if the producer queue is full or the message can't be
+/// delivered.
+pub fn event_sender<C, R>(
+    producer: FutureProducer<C, R>,
+    topic: impl Into<String>,
+) -> impl EventSender
+where
+    C: ClientContext + 'static,
+    R: AsyncRuntime,
+{
+    let topic = topic.into();
+    move |event| intercept_and_produce(&producer, &topic, event)
+}
+
+/// Intercept `event` with the installed [`Pipeline`](crate::message::Pipeline), then produce it
+/// and wait for its delivery report
+pub(crate) fn intercept_and_produce<C, R>(
+    producer: &FutureProducer<C, R>,
+    topic: &str,
+    mut event: Event,
+) -> impl Future<Output = Result<()>>
+where
+    C: ClientContext + 'static,
+    R: AsyncRuntime,
+{
+    let producer = producer.clone();
+    let topic = topic.to_string();
+    async move {
+        intercept_send(&mut event).await?;
+        produce(&producer, &topic, event)?.await
+    }
+}
+
+/// Enqueue `event` in the producer, returning the future waiting for its delivery report
+fn produce<C, R>(
+    producer: &FutureProducer<C, R>,
+    topic: &str,
+    event: Event,
+) -> Result<impl Future<Output = Result<()>>>
+where
+    C: ClientContext + 'static,
+    R: AsyncRuntime,
+{
+    #[cfg(feature = "opentelemetry")]
+    let (event, span) = {
+        let mut event = event;
+        let span = crate::binding::telemetry::send_span(&mut event, "kafka", Some(topic));
+        (event, span)
+    };
+
+    let start = Instant::now();
+    let message_record = MessageRecord::from_event(event)?;
+    crate::metrics::record(|metrics| metrics.serialization_latency("kafka", start.elapsed()));
+    let bytes = message_record.payload.as_ref().map_or(0, Vec::len);
+
+    let record: FutureRecord<str, Vec<u8>> =
+        FutureRecord::to(topic).message_record(&message_record);
+    let delivery = producer
+        .send_result(record)
+        .map_err(|(e, _)| Error::Other {
+            source: Box::new(e),
+        })?;
+    Ok(async move {
+        let result = match delivery.await {
+            Ok(Ok(_)) => Ok(()),
+            Ok(Err((e, _))) => Err(Error::Other {
+                source: Box::new(e),
+            }),
+            Err(e) => Err(Error::Other {
+                source: Box::new(e),
+            }),
+        };
+        if result.is_ok() {
+            crate::metrics::record(|metrics| metrics.event_sent("kafka", bytes));
+        }
+        #[cfg(feature = "opentelemetry")]
+        crate::binding::telemetry::end_span(span, &result);
+        result
+    })
+}
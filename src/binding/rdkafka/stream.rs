@@ -0,0 +1,79 @@
+use super::kafka_consumer_record::receive_record;
+use super::sender::intercept_and_produce;
+use crate::message::{Error, EventSink, EventStream, InFlightSink, Received};
+use futures::StreamExt;
+use rdkafka::client::ClientContext;
+use rdkafka::consumer::{ConsumerContext, StreamConsumer};
+use rdkafka::producer::FutureProducer;
+use rdkafka::util::AsyncRuntime;
+use rdkafka::Message;
+
+/// Get the [`EventStream`] of the events received by `consumer`, see
+/// [`MessageExt::to_event`](super::MessageExt::to_event), intercepted with the installed
+/// [`Pipeline`](crate::message::Pipeline).
+///
+/// Kafka errors are returned as [`Error::Other`], and don't terminate the stream.
+pub fn event_stream<C, R>(consumer: &StreamConsumer<C, R>) -> impl EventStream + '_
+where
+    C: ConsumerContext + 'static,
+{
+    consumer.stream().then(|message| {
+        let received = message
+            .map_err(|e| Error::Other {
+                source: Box::new(e),
+            })
+            .and_then(|message| Ok((receive_record(&message)?, message.topic().to_string())));
+        async move {
+            let (mut event, topic) = received?;
+            event.intercept_receive("kafka", Some(&topic)).await?;
+            Ok(event)
+        }
+    })
+}
+
+/// Get an [`EventSink`] producing the events to `topic` in binary mode, waiting for at most
+/// `capacity` delivery reports at a time, see [`MessageRecord::from_event`].
+///
+/// The sink fails if the producer queue is full or a message can't be delivered,
+/// with [`Error::Other`].
+///
+/// ```no_run
+/// use cloudevents::binding::rdkafka::{event_sink, event_stream};
+/// use cloudevents::AttributesReader;
+/// use futures::{StreamExt, TryStreamExt};
+/// use rdkafka::config::ClientConfig;
+/// use rdkafka::consumer::{Consumer, StreamConsumer};
+/// use rdkafka::producer::FutureProducer;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let consumer: StreamConsumer = ClientConfig::new()
+///     .set("group.id", "forwarder")
+///     .set("bootstrap.servers", "localhost:9092")
+///     .create()?;
+/// consumer.subscribe(&["input"])?;
+/// let producer: FutureProducer = ClientConfig::new()
+///     .set("bootstrap.servers", "localhost:9092")
+///     .create()?;
+///
+/// // Forward the events with a filter, at most 16 in flight
+/// event_stream(&consumer)
+///     .try_filter(|event| futures::future::ready(event.get_extension("priority").is_some()))
+///     .forward(event_sink(producer, "output", 16))
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn event_sink<C, R>(
+    producer: FutureProducer<C, R>,
+    topic: impl Into<String>,
+    capacity: usize,
+) -> impl EventSink
+where
+    C: ClientContext + 'static,
+    R: AsyncRuntime,
+{
+    let topic = topic.into();
+    InFlightSink::new(capacity, move |event| {
+        Ok(intercept_and_produce(&producer, &topic, event))
+    })
+}
@@ -0,0 +1,118 @@
+use super::super::http::{
+    batch_to_message, event_to_binary, event_to_structured, event_to_structured_with_format,
+};
+use crate::message::{EventFormat, Result};
+use crate::{Batch, Event};
+use reqwest::RequestBuilder;
+
+/// Extension trait for [`RequestBuilder`] to set an [`Event`] as the request payload
+pub trait RequestBuilderExt: Sized {
+    /// Write the provided [`Event`] in the request, using the binary mode
+    fn event(self, event: Event) -> Result<Self>;
+    /// Write the provided [`Event`] in the request, using the structured mode
+    fn structured_event(self, event: Event) -> Result<Self>;
+    /// Write the provided [`Event`] in the request, using the structured mode with `format`
+    fn structured_event_with_format(self, event: &Event, format: &dyn EventFormat) -> Result<Self>;
+    /// Write the provided [`Batch`] in the request, using the batched mode
+    fn batch(self, batch: Batch) -> Result<Self>;
+}
+
+impl RequestBuilderExt for RequestBuilder {
+    fn event(self, event: Event) -> Result<Self> {
+        let (headers, body) = event_to_binary(event)?;
+        Ok(self.headers(headers).body(body))
+    }
+
+    fn structured_event(self, event: Event) -> Result<Self> {
+        let (headers, body) = event_to_structured(event)?;
+        Ok(self.headers(headers).body(body))
+    }
+
+    fn structured_event_with_format(self, event: &Event, format: &dyn EventFormat) -> Result<Self> {
+        let (headers, body) = event_to_structured_with_format(event, format)?;
+        Ok(self.headers(headers).body(body))
+    }
+
+    fn batch(self, batch: Batch) -> Result<Self> {
+        let (headers, body) = batch_to_message(batch)?;
+        Ok(self.headers(headers).body(body))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::binding::http::{to_batch, to_event};
+    use crate::{EventBuilder, EventBuilderV10};
+    use serde_json::json;
+
+    fn expected_event() -> Event {
+        EventBuilderV10::new()
+            .id("0001")
+            .ty("example.test")
+            .source("http://localhost/")
+            .data("application/json", json!({"hello": "world"}))
+            .extension("someint", "10")
+            .build()
+            .unwrap()
+    }
+
+    fn to_request_event(request: reqwest::Request) -> Event {
+        let body = request.body().unwrap().as_bytes().unwrap().to_vec();
+        to_event(request.headers(), body).unwrap()
+    }
+
+    #[test]
+    fn test_binary_request() {
+        let expected = expected_event();
+
+        let request = reqwest::Client::new()
+            .post("http://localhost/")
+            .event(expected.clone())
+            .unwrap()
+            .build()
+            .unwrap();
+
+        assert_eq!("1.0", request.headers()["ce-specversion"]);
+        assert_eq!("0001", request.headers()["ce-id"]);
+        assert_eq!("application/json", request.headers()["content-type"]);
+        assert_eq!(expected, to_request_event(request));
+    }
+
+    #[test]
+    fn test_structured_request() {
+        let expected = expected_event();
+
+        let request = reqwest::Client::new()
+            .post("http://localhost/")
+            .structured_event(expected.clone())
+            .unwrap()
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            "application/cloudevents+json",
+            request.headers()["content-type"]
+        );
+        assert_eq!(expected, to_request_event(request));
+    }
+
+    #[test]
+    fn test_batch_request() {
+        let expected = Batch::from(vec![expected_event(), expected_event()]);
+
+        let request = reqwest::Client::new()
+            .post("http://localhost/")
+            .batch(expected.clone())
+            .unwrap()
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            "application/cloudevents-batch+json",
+            request.headers()["content-type"]
+        );
+        let body = request.body().unwrap().as_bytes().unwrap().to_vec();
+        assert_eq!(expected, to_batch(request.headers(), body).unwrap());
+    }
+}
@@ -0,0 +1,103 @@
+use super::super::http::{receive_event, to_batch};
+use crate::message::{Error, Received, Result};
+use crate::{Batch, Event};
+use async_trait::async_trait;
+use reqwest::Response;
+
+/// Extension trait for [`Response`] to read an [`Event`] or a [`Batch`] from the response payload
+#[async_trait]
+pub trait ResponseExt {
+    /// Read the [`Event`] from the response, detecting whether it's encoded in binary or structured mode
+    async fn into_event(self) -> Result<Event>;
+
+    /// Read the [`Batch`] from the response, see [`to_batch`]
+    async fn into_batch(self) -> Result<Batch>;
+}
+
+#[async_trait]
+impl ResponseExt for Response {
+    async fn into_event(self) -> Result<Event> {
+        let headers = self.headers().clone();
+        let body = self.bytes().await.map_err(|e| Error::Other {
+            source: Box::new(e),
+        })?;
+
+        let mut event = receive_event(&headers, body)?;
+        event.intercept_receive("http", None).await?;
+        Ok(event)
+    }
+
+    async fn into_batch(self) -> Result<Batch> {
+        let headers = self.headers().clone();
+        let body = self.bytes().await.map_err(|e| Error::Other {
+            source: Box::new(e),
+        })?;
+
+        let mut batch = to_batch(&headers, body)?;
+        batch.intercept_receive("http", None).await?;
+        Ok(batch)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EventBuilder, EventBuilderV10};
+    use serde_json::json;
+
+    fn expected_event() -> Event {
+        EventBuilderV10::new()
+            .id("0001")
+            .ty("example.test")
+            .source("http://localhost/")
+            .data("application/json", json!({"hello": "world"}))
+            .extension("someint", "10")
+            .build()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_binary_response() {
+        let response = ::http::Response::builder()
+            .header("ce-specversion", "1.0")
+            .header("ce-id", "0001")
+            .header("ce-type", "example.test")
+            .header("ce-source", "http://localhost/")
+            .header("ce-someint", "10")
+            .header("content-type", "application/json")
+            .body(json!({"hello": "world"}).to_string())
+            .unwrap();
+
+        let event = Response::from(response).into_event().await.unwrap();
+
+        assert_eq!(expected_event(), event);
+    }
+
+    #[tokio::test]
+    async fn test_structured_response() {
+        let expected = expected_event();
+
+        let response = ::http::Response::builder()
+            .header("content-type", "application/cloudevents+json")
+            .body(serde_json::to_vec(&expected).unwrap())
+            .unwrap();
+
+        let event = Response::from(response).into_event().await.unwrap();
+
+        assert_eq!(expected, event);
+    }
+
+    #[tokio::test]
+    async fn test_batch_response() {
+        let expected = Batch::from(vec![expected_event(), expected_event()]);
+
+        let response = ::http::Response::builder()
+            .header("content-type", "application/cloudevents-batch+json")
+            .body(serde_json::to_vec(&expected).unwrap())
+            .unwrap();
+
+        let batch = Response::from(response).into_batch().await.unwrap();
+
+        assert_eq!(expected, batch);
+    }
+}
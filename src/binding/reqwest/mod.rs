@@ -0,0 +1,39 @@
+//! Integration with [reqwest](https://github.com/seanmonstar/reqwest), built on top of the
+//! [`http`](super::http) binding.
+//!
+//! ```no_run
+//! use cloudevents::binding::reqwest::{RequestBuilderExt, ResponseExt};
+//! use cloudevents::{EventBuilder, EventBuilderV10};
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let event = EventBuilderV10::new()
+//!     .id("0001")
+//!     .source("http://localhost/")
+//!     .ty("example.demo")
+//!     .build()?;
+//!
+//! let response = reqwest::Client::new()
+//!     .post("http://localhost:8080")
+//!     .event(event)?
+//!     .send()
+//!     .await?;
+//!
+//! let reply = response.into_event().await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! With the `stream` feature, `event_sink` posts the events written to an
+//! `message::EventSink`, keeping a bounded number of requests in flight.
+
+mod client_request;
+mod client_response;
+mod sender;
+#[cfg(feature = "stream")]
+mod sink;
+
+pub use client_request::RequestBuilderExt;
+pub use client_response::ResponseExt;
+pub use sender::event_sender;
+#[cfg(feature = "stream")]
+pub use sink::event_sink;
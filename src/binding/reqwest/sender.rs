@@ -0,0 +1,63 @@
+use super::super::http::event_to_binary;
+use crate::message::{intercept_send, Error, EventSender, Result};
+use crate::Event;
+use reqwest::{Client, Url};
+use std::future::Future;
+use std::time::Instant;
+
+/// Get an [`EventSender`] posting the events to `url` in binary mode, e.g. as destination of a
+/// [`DeadLetterPolicy`](crate::message::DeadLetterPolicy).
+///
+/// Sending fails with [`Error::Other`] if the request fails or the response status is not a
+/// success.
+pub fn event_sender(client: Client, url: Url) -> impl EventSender {
+    move |event| intercept_and_post(&client, &url, event)
+}
+
+/// Intercept `event` with the installed [`Pipeline`](crate::message::Pipeline), then post it
+/// and wait for the response
+pub(crate) fn intercept_and_post(
+    client: &Client,
+    url: &Url,
+    mut event: Event,
+) -> impl Future<Output = Result<()>> {
+    let client = client.clone();
+    let url = url.clone();
+    async move {
+        intercept_send(&mut event).await?;
+        post(&client, &url, event)?.await
+    }
+}
+
+/// Build the request posting `event`, returning the future waiting for its response
+fn post(client: &Client, url: &Url, event: Event) -> Result<impl Future<Output = Result<()>>> {
+    #[cfg(feature = "opentelemetry")]
+    let (event, span) = {
+        let mut event = event;
+        let span = crate::binding::telemetry::send_span(&mut event, "http", None);
+        (event, span)
+    };
+
+    let start = Instant::now();
+    let (headers, body) = event_to_binary(event)?;
+    crate::metrics::record(|metrics| metrics.serialization_latency("http", start.elapsed()));
+    let bytes = body.len();
+
+    let request = client.post(url.clone()).headers(headers).body(body);
+    Ok(async move {
+        let result = request
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map(|_| ())
+            .map_err(|e| Error::Other {
+                source: Box::new(e),
+            });
+        if result.is_ok() {
+            crate::metrics::record(|metrics| metrics.event_sent("http", bytes));
+        }
+        #[cfg(feature = "opentelemetry")]
+        crate::binding::telemetry::end_span(span, &result);
+        result
+    })
+}
@@ -0,0 +1,37 @@
+use super::sender::intercept_and_post;
+use crate::message::{EventSink, InFlightSink};
+use reqwest::{Client, Url};
+
+/// Get an [`EventSink`] posting each event to `url` in binary mode, with at most `capacity`
+/// requests in flight.
+///
+/// The sink fails with [`Error::Other`](crate::message::Error::Other) if a request fails or the
+/// response status is not a success.
+///
+/// ```no_run
+/// use cloudevents::binding::reqwest::event_sink;
+/// use cloudevents::{EventBuilder, EventBuilderV10};
+/// use futures::{stream, StreamExt};
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let url = reqwest::Url::parse("http://localhost:8080")?;
+/// let events = (0..10).map(|i| {
+///     EventBuilderV10::new()
+///         .id(i.to_string())
+///         .source("http://localhost/")
+///         .ty("example.demo")
+///         .build()
+///         .map_err(Into::into)
+/// });
+///
+/// stream::iter(events)
+///     .forward(event_sink(reqwest::Client::new(), url, 4))
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn event_sink(client: Client, url: Url, capacity: usize) -> impl EventSink {
+    InFlightSink::new(capacity, move |event| {
+        Ok(intercept_and_post(&client, &url, event))
+    })
+}
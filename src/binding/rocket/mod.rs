@@ -0,0 +1,246 @@
+//! Integration with [Rocket](https://rocket.rs), built on top of the [`http`](super::http)
+//! binding.
+//!
+//! [`Event`] implements [`FromData`], parsing both binary and structured mode requests, and
+//! [`Responder`], writing the event as a binary mode response, so route handlers can take and
+//! return events directly:
+//!
+//! ```
+//! use cloudevents::Event;
+//! use rocket::{post, routes};
+//!
+//! #[post("/", data = "<event>")]
+//! fn echo(event: Event) -> Event {
+//!     event
+//! }
+//!
+//! let rocket = rocket::build().mount("/", routes![echo]);
+//! ```
+//!
+//! [`Batch`] implements them too, for batched mode requests and responses.
+//!
+//! The body of the requests is limited by the `cloudevents` limit of the Rocket configuration,
+//! 1 MiB by default.
+
+use super::http::{batch_to_message, event_to_binary, receive_event, to_batch};
+use crate::message::{Error, Received};
+use crate::{Batch, Event};
+use ::http::header::{HeaderMap, HeaderName, HeaderValue};
+use rocket::data::{ByteUnit, Data, FromData, Outcome};
+use rocket::http::Status;
+use rocket::request::Request;
+use rocket::response::{self, Responder, Response};
+use std::io::Cursor;
+
+/// Name of the limit of the request bodies in the Rocket configuration
+pub const LIMIT_NAME: &str = "cloudevents";
+
+/// Default limit of the request bodies
+const DEFAULT_LIMIT: ByteUnit = ByteUnit::Mebibyte(1);
+
+/// Copy the headers of a Rocket request to a [`HeaderMap`]
+fn to_header_map(headers: &rocket::http::HeaderMap<'_>) -> Result<HeaderMap, Error> {
+    let mut map = HeaderMap::new();
+    for header in headers.iter() {
+        let name = HeaderName::from_bytes(header.name().as_str().as_bytes()).map_err(|e| {
+            Error::Other {
+                source: Box::new(e),
+            }
+        })?;
+        let value = HeaderValue::from_str(header.value()).map_err(|e| Error::Other {
+            source: Box::new(e),
+        })?;
+        map.append(name, value);
+    }
+    Ok(map)
+}
+
+/// Read the body of `req`, parse it with `parse` and intercept it with the installed
+/// [`Pipeline`](crate::message::Pipeline), failing with the status code of the error
+async fn parse_request<'r, T: Received>(
+    req: &'r Request<'_>,
+    data: Data<'r>,
+    parse: impl FnOnce(&HeaderMap, Vec<u8>) -> Result<T, Error>,
+) -> Outcome<'r, T, Error> {
+    let limit = req.limits().get(LIMIT_NAME).unwrap_or(DEFAULT_LIMIT);
+    let result = async {
+        let body = data.open(limit).into_bytes().await?;
+        if !body.is_complete() {
+            return Err(Error::EventTooLarge {
+                size: body.len(),
+                limit: limit.as_u64() as usize,
+            });
+        }
+        let mut value = parse(&to_header_map(req.headers())?, body.into_inner())?;
+        value.intercept_receive("http", None).await?;
+        Ok(value)
+    };
+    match result.await {
+        Ok(value) => Outcome::Success(value),
+        Err(e) => {
+            let status = Status::from_code(e.status_code()).unwrap_or(Status::BadRequest);
+            Outcome::Error((status, e))
+        }
+    }
+}
+
+/// Write the message returned by `serialize`, failing with `500 Internal Server Error` on error
+fn message_response(serialize: Result<(HeaderMap, Vec<u8>), Error>) -> response::Result<'static> {
+    let (headers, body) = serialize.map_err(|_| Status::InternalServerError)?;
+    let mut response = Response::build();
+    for (name, value) in &headers {
+        let value = value.to_str().map_err(|_| Status::InternalServerError)?;
+        response.raw_header_adjoin(name.as_str().to_string(), value.to_string());
+    }
+    response.sized_body(body.len(), Cursor::new(body)).ok()
+}
+
+#[rocket::async_trait]
+impl<'r> FromData<'r> for Event {
+    type Error = Error;
+
+    async fn from_data(req: &'r Request<'_>, data: Data<'r>) -> Outcome<'r, Self> {
+        parse_request(req, data, receive_event).await
+    }
+}
+
+impl<'r> Responder<'r, 'static> for Event {
+    fn respond_to(self, _: &'r Request<'_>) -> response::Result<'static> {
+        message_response(event_to_binary(self))
+    }
+}
+
+#[rocket::async_trait]
+impl<'r> FromData<'r> for Batch {
+    type Error = Error;
+
+    async fn from_data(req: &'r Request<'_>, data: Data<'r>) -> Outcome<'r, Self> {
+        parse_request(req, data, to_batch).await
+    }
+}
+
+impl<'r> Responder<'r, 'static> for Batch {
+    fn respond_to(self, _: &'r Request<'_>) -> response::Result<'static> {
+        message_response(batch_to_message(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AttributesReader, EventBuilder, EventBuilderV10};
+    use rocket::http::{ContentType, Header};
+    use rocket::local::asynchronous::Client;
+    use rocket::{post, routes};
+    use serde_json::json;
+
+    fn expected_event() -> Event {
+        EventBuilderV10::new()
+            .id("0001")
+            .ty("example.test")
+            .source("http://localhost/")
+            .data("application/json", json!({"hello": "world"}))
+            .extension("someint", "10")
+            .build()
+            .unwrap()
+    }
+
+    #[post("/", data = "<event>")]
+    fn echo(event: Event) -> Event {
+        event
+    }
+
+    #[post("/batch", data = "<batch>")]
+    fn echo_batch(batch: Batch) -> Batch {
+        batch
+    }
+
+    async fn client() -> Client {
+        let figment = rocket::Config::figment().merge(("limits.cloudevents", 1024));
+        let rocket = rocket::custom(figment).mount("/", routes![echo, echo_batch]);
+        Client::tracked(rocket).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_binary_and_structured() {
+        let client = client().await;
+
+        let response = client
+            .post("/")
+            .header(Header::new("ce-specversion", "1.0"))
+            .header(Header::new("ce-id", "0001"))
+            .header(Header::new("ce-type", "example.test"))
+            .header(Header::new("ce-source", "http://localhost/"))
+            .header(Header::new("ce-someint", "10"))
+            .header(ContentType::JSON)
+            .body(json!({"hello": "world"}).to_string())
+            .dispatch()
+            .await;
+        assert_eq!(Status::Ok, response.status());
+        assert_eq!(Some("0001"), response.headers().get_one("ce-id"));
+        assert_eq!(Some("10"), response.headers().get_one("ce-someint"));
+        assert_eq!(
+            Some("application/json"),
+            response.headers().get_one("content-type")
+        );
+        let body = response.into_bytes().await.unwrap();
+        assert_eq!(
+            json!({"hello": "world"}),
+            serde_json::from_slice::<serde_json::Value>(&body).unwrap()
+        );
+
+        let response = client
+            .post("/")
+            .header(ContentType::new("application", "cloudevents+json"))
+            .body(serde_json::to_vec(&expected_event()).unwrap())
+            .dispatch()
+            .await;
+        assert_eq!(Status::Ok, response.status());
+        assert_eq!(Some("example.test"), response.headers().get_one("ce-type"));
+    }
+
+    #[tokio::test]
+    async fn test_rejections() {
+        let client = client().await;
+
+        let response = client
+            .post("/")
+            .header(ContentType::JSON)
+            .body(json!({"hello": "world"}).to_string())
+            .dispatch()
+            .await;
+        assert_eq!(Status::BadRequest, response.status());
+
+        let mut event = expected_event();
+        event.write_data("text/plain", "a".repeat(2048));
+        let response = client
+            .post("/")
+            .header(ContentType::new("application", "cloudevents+json"))
+            .body(serde_json::to_vec(&event).unwrap())
+            .dispatch()
+            .await;
+        assert_eq!(Status::PayloadTooLarge, response.status());
+    }
+
+    #[tokio::test]
+    async fn test_batch() {
+        let client = client().await;
+        let expected = Batch::from(vec![expected_event(), expected_event()]);
+
+        let response = client
+            .post("/batch")
+            .header(ContentType::new("application", "cloudevents-batch+json"))
+            .body(serde_json::to_vec(&expected).unwrap())
+            .dispatch()
+            .await;
+        assert_eq!(Status::Ok, response.status());
+        assert_eq!(
+            Some("application/cloudevents-batch+json"),
+            response.headers().get_one("content-type")
+        );
+        let body = response.into_bytes().await.unwrap();
+        let batch: Batch = serde_json::from_slice(&body).unwrap();
+        assert_eq!(2, batch.len());
+        assert_eq!("0001", batch[0].get_id());
+    }
+}
@@ -0,0 +1,332 @@
+//! Integration with [Salvo](https://salvo.rs), built on top of the [`http`](super::http)
+//! binding.
+//!
+//! [`Event`] implements [`Extractible`], parsing both binary and structured mode requests,
+//! and [`Scribe`], writing the event as a binary mode response,
+//! so handlers can take and return events directly:
+//!
+//! ```
+//! use cloudevents::Event;
+//! use salvo::prelude::*;
+//!
+//! #[handler]
+//! async fn echo(event: Event) -> Event {
+//!     event
+//! }
+//!
+//! let router = Router::new().post(echo);
+//! ```
+//!
+//! [`Batch`] implements them too, for batched mode requests and responses.
+//!
+//! The [`WebhookValidation`] handler replies to the abuse protection handshake of the
+//! HTTP WebHook spec, see [`WebhookValidator`], when added as a hoop of the [`Service`], which
+//! runs even if no route handles the `OPTIONS` requests:
+//!
+//! ```
+//! use cloudevents::binding::http::WebhookValidator;
+//! use cloudevents::binding::salvo::WebhookValidation;
+//! use cloudevents::Event;
+//! use salvo::prelude::*;
+//!
+//! #[handler]
+//! async fn handle(event: Event) {}
+//!
+//! let service = Service::new(Router::new().post(handle)).hoop(WebhookValidation::new(
+//!     WebhookValidator::new().allow_origin("eventemitter.example.com"),
+//! ));
+//! ```
+//!
+//! [`Service`]: salvo::Service
+
+use super::http::{batch_to_message, event_to_binary, receive_event, to_batch, WebhookValidator};
+use crate::message::{Error, Received};
+use crate::{Batch, Event};
+use ::http::header::{HeaderMap, HeaderName, HeaderValue};
+use salvo::extract::Metadata;
+use salvo::http::{Method, StatusCode, StatusError};
+use salvo::{
+    async_trait, Depot, Extractible, FlowCtrl, Handler, Request, Response, Scribe, Writer,
+};
+use std::fmt::Debug;
+
+/// Copy the headers of a Salvo request to a [`HeaderMap`]
+fn to_header_map(headers: &salvo::http::HeaderMap) -> Result<HeaderMap, Error> {
+    let mut map = HeaderMap::new();
+    for (name, value) in headers {
+        let name = HeaderName::from_bytes(name.as_str().as_bytes()).map_err(|e| Error::Other {
+            source: Box::new(e),
+        })?;
+        let value = HeaderValue::from_bytes(value.as_bytes()).map_err(|e| Error::Other {
+            source: Box::new(e),
+        })?;
+        map.append(name, value);
+    }
+    Ok(map)
+}
+
+/// Copy `headers` to the headers of a Salvo response
+fn write_headers(headers: &HeaderMap, res: &mut Response) {
+    for (name, value) in headers {
+        // The names and the values of a `HeaderMap` are valid in any version of `http`
+        if let (Ok(name), Ok(value)) = (
+            salvo::http::HeaderName::from_bytes(name.as_str().as_bytes()),
+            salvo::http::HeaderValue::from_bytes(value.as_bytes()),
+        ) {
+            res.headers_mut().append(name, value);
+        }
+    }
+}
+
+/// Reply to a request which couldn't be read with the status code of `error`
+fn to_status_error(error: Error) -> StatusError {
+    StatusCode::from_u16(error.status_code())
+        .ok()
+        .and_then(StatusError::from_code)
+        .unwrap_or_else(StatusError::bad_request)
+        .brief(error.to_string())
+}
+
+/// Read the body of `req`, parse it with `parse` and intercept it with the installed
+/// [`Pipeline`](crate::message::Pipeline), failing with the status code of the error
+async fn parse_request<T: Received>(
+    req: &mut Request,
+    parse: fn(&HeaderMap, Vec<u8>) -> Result<T, Error>,
+) -> Result<T, StatusError> {
+    let headers = to_header_map(req.headers()).map_err(to_status_error)?;
+    let body = req
+        .payload()
+        .await
+        .map_err(|e| StatusError::bad_request().brief(e.to_string()))?
+        .to_vec();
+    let mut value = parse(&headers, body).map_err(to_status_error)?;
+    value
+        .intercept_receive("http", None)
+        .await
+        .map_err(to_status_error)?;
+    Ok(value)
+}
+
+/// Write the message returned by `serialize`, replying with `500 Internal Server Error` on error
+fn message_response(serialize: Result<(HeaderMap, Vec<u8>), Error>, res: &mut Response) {
+    match serialize {
+        Ok((headers, body)) => {
+            write_headers(&headers, res);
+            res.body(body);
+        }
+        Err(e) => res.render(StatusError::internal_server_error().brief(e.to_string())),
+    }
+}
+
+impl<'ex> Extractible<'ex> for Event {
+    fn metadata() -> &'ex Metadata {
+        static METADATA: Metadata = Metadata::new("cloudevents::Event");
+        &METADATA
+    }
+
+    async fn extract(req: &'ex mut Request) -> Result<Self, impl Writer + Send + Debug + 'static> {
+        parse_request(req, receive_event).await
+    }
+}
+
+impl Scribe for Event {
+    fn render(self, res: &mut Response) {
+        message_response(event_to_binary(self), res)
+    }
+}
+
+impl<'ex> Extractible<'ex> for Batch {
+    fn metadata() -> &'ex Metadata {
+        static METADATA: Metadata = Metadata::new("cloudevents::Batch");
+        &METADATA
+    }
+
+    async fn extract(req: &'ex mut Request) -> Result<Self, impl Writer + Send + Debug + 'static> {
+        parse_request(req, to_batch).await
+    }
+}
+
+impl Scribe for Batch {
+    fn render(self, res: &mut Response) {
+        message_response(batch_to_message(self), res)
+    }
+}
+
+/// Handler replying to the webhook validation requests with a [`WebhookValidator`], see
+/// [`WebhookValidator::validate`], and passing the other requests to the next handlers
+#[derive(Debug, Clone)]
+pub struct WebhookValidation {
+    validator: WebhookValidator,
+}
+
+impl WebhookValidation {
+    /// Create a new [`WebhookValidation`] handler replying with `validator`
+    pub fn new(validator: WebhookValidator) -> Self {
+        WebhookValidation { validator }
+    }
+}
+
+#[async_trait]
+impl Handler for WebhookValidation {
+    async fn handle(
+        &self,
+        req: &mut Request,
+        _depot: &mut Depot,
+        res: &mut Response,
+        ctrl: &mut FlowCtrl,
+    ) {
+        if req.method() != Method::OPTIONS {
+            return;
+        }
+        let validation = match to_header_map(req.headers()) {
+            Ok(headers) => self.validator.validate(&::http::Method::OPTIONS, &headers),
+            Err(e) => {
+                res.render(to_status_error(e));
+                ctrl.skip_rest();
+                return;
+            }
+        };
+        if let Some(validation) = validation {
+            res.status_code(
+                StatusCode::from_u16(validation.status().as_u16())
+                    .unwrap_or(StatusCode::BAD_REQUEST),
+            );
+            write_headers(validation.headers(), res);
+            ctrl.skip_rest();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AttributesReader, EventBuilder, EventBuilderV10};
+    use salvo::http::ResBody;
+    use serde_json::json;
+
+    fn expected_event() -> Event {
+        EventBuilderV10::new()
+            .id("0001")
+            .ty("example.test")
+            .source("http://localhost/")
+            .data("application/json", json!({"hello": "world"}))
+            .extension("someint", "10")
+            .build()
+            .unwrap()
+    }
+
+    fn request(method: Method, headers: &[(&'static str, &'static str)], body: Vec<u8>) -> Request {
+        let mut req = Request::new();
+        *req.method_mut() = method;
+        for (name, value) in headers {
+            req.headers_mut()
+                .append(*name, salvo::http::HeaderValue::from_static(value));
+        }
+        req.replace_body(body.into());
+        req
+    }
+
+    fn body(res: &Response) -> &[u8] {
+        match &res.body {
+            ResBody::Once(bytes) => bytes,
+            _ => &[],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_binary_and_structured() {
+        let mut req = request(
+            Method::POST,
+            &[
+                ("ce-specversion", "1.0"),
+                ("ce-id", "0001"),
+                ("ce-type", "example.test"),
+                ("ce-source", "http://localhost/"),
+                ("ce-someint", "10"),
+                ("content-type", "application/json"),
+            ],
+            json!({"hello": "world"}).to_string().into_bytes(),
+        );
+        let event = Event::extract(&mut req).await.unwrap();
+        assert_eq!(expected_event(), event);
+
+        let mut res = Response::new();
+        res.render(event);
+        assert_eq!("0001", res.headers()["ce-id"]);
+        assert_eq!("10", res.headers()["ce-someint"]);
+        assert_eq!("application/json", res.headers()["content-type"]);
+        assert_eq!(
+            json!({"hello": "world"}),
+            serde_json::from_slice::<serde_json::Value>(body(&res)).unwrap()
+        );
+
+        let mut req = request(
+            Method::POST,
+            &[("content-type", "application/cloudevents+json")],
+            serde_json::to_vec(&expected_event()).unwrap(),
+        );
+        assert_eq!(expected_event(), Event::extract(&mut req).await.unwrap());
+
+        let mut req = request(
+            Method::POST,
+            &[("content-type", "application/json")],
+            json!({"hello": "world"}).to_string().into_bytes(),
+        );
+        let error = Event::extract(&mut req).await.unwrap_err();
+        let mut res = Response::new();
+        error.write(&mut req, &mut Depot::new(), &mut res).await;
+        assert_eq!(Some(StatusCode::BAD_REQUEST), res.status_code);
+    }
+
+    #[tokio::test]
+    async fn test_batch() {
+        let expected = Batch::from(vec![expected_event(), expected_event()]);
+
+        let mut req = request(
+            Method::POST,
+            &[("content-type", "application/cloudevents-batch+json")],
+            serde_json::to_vec(&expected).unwrap(),
+        );
+        let batch = Batch::extract(&mut req).await.unwrap();
+        assert_eq!(expected, batch);
+
+        let mut res = Response::new();
+        res.render(batch);
+        assert_eq!(
+            "application/cloudevents-batch+json",
+            res.headers()["content-type"]
+        );
+        let batch: Batch = serde_json::from_slice(body(&res)).unwrap();
+        assert_eq!(2, batch.len());
+        assert_eq!("0001", batch[0].get_id());
+    }
+
+    #[tokio::test]
+    async fn test_webhook_validation() {
+        let validation =
+            WebhookValidation::new(WebhookValidator::new().allow_origin("example.com"));
+        let validate = |origin: &'static str| {
+            let validation = validation.clone();
+            async move {
+                let mut req = request(
+                    Method::OPTIONS,
+                    &[("webhook-request-origin", origin)],
+                    Vec::new(),
+                );
+                let mut res = Response::new();
+                let mut ctrl = FlowCtrl::new(Vec::new());
+                validation
+                    .handle(&mut req, &mut Depot::new(), &mut res, &mut ctrl)
+                    .await;
+                res
+            }
+        };
+
+        let res = validate("example.com").await;
+        assert_eq!(Some(StatusCode::OK), res.status_code);
+        assert_eq!("example.com", res.headers()["webhook-allowed-origin"]);
+
+        let res = validate("other.com").await;
+        assert_eq!(Some(StatusCode::FORBIDDEN), res.status_code);
+    }
+}
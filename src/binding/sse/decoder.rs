@@ -0,0 +1,148 @@
+use crate::message::{Error, Result};
+use crate::Event;
+
+/// Incremental decoder of an SSE stream, parsing the event in the `data` field of each frame.
+///
+/// The frames without data, like the comments sent to keep the connection alive, are skipped,
+/// and the `id`, `event` and `retry` fields are ignored.
+#[derive(Debug, Default)]
+pub struct SseDecoder {
+    /// Bytes of the incomplete line
+    line: Vec<u8>,
+    /// Lines of the `data` field of the current frame
+    data: Option<String>,
+    /// Whether the previous chunk ended with `\r`, which may be followed by `\n`
+    after_cr: bool,
+}
+
+impl SseDecoder {
+    /// Create a new [`SseDecoder`]
+    pub fn new() -> Self {
+        SseDecoder::default()
+    }
+
+    /// Decode the frames completed by `chunk`
+    pub fn feed(&mut self, chunk: &[u8]) -> Vec<Result<Event>> {
+        let mut events = Vec::new();
+        for &byte in chunk {
+            let after_cr = std::mem::replace(&mut self.after_cr, byte == b'\r');
+            match byte {
+                b'\n' if after_cr => {}
+                b'\r' | b'\n' => {
+                    let line = std::mem::take(&mut self.line);
+                    if let Some(event) = self.process_line(&line) {
+                        events.push(event);
+                    }
+                }
+                _ => self.line.push(byte),
+            }
+        }
+        events
+    }
+
+    /// Decode the last frame, if the stream ended without a blank line
+    pub fn finish(&mut self) -> Option<Result<Event>> {
+        let line = std::mem::take(&mut self.line);
+        if !line.is_empty() {
+            if let Some(event) = self.process_line(&line) {
+                return Some(event);
+            }
+        }
+        self.dispatch()
+    }
+
+    fn process_line(&mut self, line: &[u8]) -> Option<Result<Event>> {
+        if line.is_empty() {
+            return self.dispatch();
+        }
+        let line = match std::str::from_utf8(line) {
+            Ok(line) => line,
+            Err(e) => {
+                return Some(Err(Error::Other {
+                    source: Box::new(e),
+                }))
+            }
+        };
+        let (field, value) = match line.find(':') {
+            // Comment
+            Some(0) => return None,
+            Some(i) => {
+                let value = &line[i + 1..];
+                (&line[..i], value.strip_prefix(' ').unwrap_or(value))
+            }
+            None => (line, ""),
+        };
+        if field == "data" {
+            match &mut self.data {
+                Some(data) => {
+                    data.push('\n');
+                    data.push_str(value);
+                }
+                None => self.data = Some(value.to_string()),
+            }
+        }
+        None
+    }
+
+    fn dispatch(&mut self) -> Option<Result<Event>> {
+        let data = self.data.take()?;
+        Some(crate::event::parse_json(data.into_bytes()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::binding::sse::encode_event;
+    use crate::{EventBuilder, EventBuilderV10};
+    use serde_json::json;
+
+    fn event() -> Event {
+        EventBuilderV10::new()
+            .id("0001")
+            .source("http://localhost/")
+            .ty("example.test")
+            .data("application/json", json!({"hello": "world"}))
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn chunked_frames() {
+        let frames = format!(
+            ": keep-alive\r\n\r\n{}{}",
+            encode_event(&event()).unwrap(),
+            encode_event(&event()).unwrap().replace('\n', "\r\n")
+        );
+
+        // Split the frames at every position
+        for i in 0..frames.len() {
+            let mut decoder = SseDecoder::new();
+            let (first, second) = frames.as_bytes().split_at(i);
+            let mut events = decoder.feed(first);
+            events.extend(decoder.feed(second));
+            events.extend(decoder.finish());
+
+            assert_eq!(2, events.len(), "split at {}", i);
+            for e in events {
+                assert_eq!(event(), e.unwrap());
+            }
+        }
+    }
+
+    #[test]
+    fn multiline_data() {
+        let json = serde_json::to_string_pretty(&event()).unwrap();
+        let frame = json
+            .lines()
+            .map(|line| format!("data:{}\n", line))
+            .collect::<String>();
+
+        let mut decoder = SseDecoder::new();
+        assert!(decoder.feed(frame.as_bytes()).is_empty());
+        assert_eq!(event(), decoder.finish().unwrap().unwrap());
+
+        assert!(decoder.feed(b"data: not an event\n\n")[0].is_err());
+        assert!(decoder.finish().is_none());
+    }
+}
@@ -0,0 +1,88 @@
+//! [Server-Sent Events](https://html.spec.whatwg.org/multipage/server-sent-events.html) codec,
+//! enabled with the `sse` feature, to push events to browsers and lightweight clients.
+//!
+//! Each event is written in its own SSE frame, with the `type` of the event in the `event`
+//! field, its `id` in the `id` field, and the event in structured mode with the JSON event
+//! format in the `data` field. A browser can listen to the events of a type with
+//! `EventSource.addEventListener`.
+//!
+//! ```
+//! use cloudevents::binding::sse::{encode_event, event_stream};
+//! use cloudevents::{EventBuilder, EventBuilderV10};
+//! use futures::{stream, StreamExt};
+//!
+//! let event = EventBuilderV10::new()
+//!     .id("0001")
+//!     .source("http://localhost/")
+//!     .ty("example.demo")
+//!     .build()
+//!     .unwrap();
+//!
+//! let frame = encode_event(&event).unwrap();
+//! assert!(frame.starts_with("id: 0001\nevent: example.demo\ndata: {"));
+//!
+//! // Split the frame in chunks, as received from the network
+//! let (first, second) = frame.as_bytes().split_at(10);
+//! let chunks = stream::iter(vec![Ok::<_, std::io::Error>(first), Ok(second)]);
+//! let events: Vec<_> = futures::executor::block_on(event_stream(chunks).collect());
+//! assert_eq!(event, *events[0].as_ref().unwrap());
+//! ```
+
+mod decoder;
+
+pub use decoder::SseDecoder;
+
+use crate::event::AttributesReader;
+use crate::message::{Error, EventStream, Result};
+use crate::Event;
+use futures::{stream, Stream, StreamExt};
+use std::fmt::Write;
+
+/// Content type of an SSE stream
+pub const SSE_CONTENT_TYPE: &str = "text/event-stream";
+
+/// Encode `event` in an SSE frame, terminated by a blank line
+pub fn encode_event(event: &Event) -> Result<String> {
+    let json = serde_json::to_string(event)?;
+    let mut frame = String::with_capacity(json.len() + 64);
+    // The values can't contain line breaks, which would end the field
+    let single_line = |value: &str| value.replace(&['\r', '\n'][..], " ");
+    let _ = writeln!(frame, "id: {}", single_line(event.get_id()));
+    let _ = writeln!(frame, "event: {}", single_line(event.get_type()));
+    let _ = writeln!(frame, "data: {}", json);
+    frame.push('\n');
+    Ok(frame)
+}
+
+/// Get the stream of the SSE frames of `events`, e.g. to be written in the body of a
+/// `text/event-stream` response
+pub fn frame_stream<S: EventStream>(events: S) -> impl Stream<Item = Result<String>> {
+    events.map(|event| encode_event(&event?))
+}
+
+/// Get the [`EventStream`] of the events decoded from the `chunks` of an SSE stream with a
+/// [`SseDecoder`].
+///
+/// A frame which can't be decoded is returned as an error, and an error of the connection as
+/// [`Error::Other`], without terminating the stream.
+pub fn event_stream<S, B, E>(chunks: S) -> impl EventStream
+where
+    S: Stream<Item = std::result::Result<B, E>>,
+    B: AsRef<[u8]>,
+    E: std::error::Error + Send + Sync + 'static,
+{
+    chunks
+        .map(Some)
+        .chain(stream::once(futures::future::ready(None)))
+        .scan(SseDecoder::new(), |decoder, chunk| {
+            let events = match chunk {
+                Some(Ok(bytes)) => decoder.feed(bytes.as_ref()),
+                Some(Err(e)) => vec![Err(Error::Other {
+                    source: Box::new(e),
+                })],
+                None => decoder.finish().into_iter().collect(),
+            };
+            futures::future::ready(Some(stream::iter(events)))
+        })
+        .flatten()
+}
@@ -0,0 +1,287 @@
+//! OpenTelemetry instrumentation of the bindings, enabled with the `opentelemetry` feature.
+//!
+//! The bindings performing I/O record a messaging span with the
+//! [CloudEvents semantic conventions](https://opentelemetry.io/docs/specs/semconv/cloudevents/cloudevents-spans/)
+//! attributes for each event they send or receive:
+//!
+//! * the reqwest and Kafka [`EventSender`](crate::message::EventSender)s, and the sinks built on
+//!   them, for the sent events
+//! * the reqwest `ResponseExt`, the Kafka event stream, the extractors of the actix, axum, poem,
+//!   rocket, salvo, tide and warp servers, the hyper service and the tower layer for the received
+//!   events, with a receive span covering the `on_receive` hooks of the installed
+//!   [`Pipeline`](crate::message::Pipeline)
+//!
+//! The trace context is propagated with the [`DistributedTraceExtension`]: a sent event gets the
+//! context of its send span, unless it already carries the context it was created in, and the
+//! receive span of an event is a child of the context it carries, its [`parent_context`]. The
+//! hyper service and the tower layer call their handler in this context, while the handlers of
+//! the other bindings can attach it to their processing themselves.
+//!
+//! The spans are created with the tracer named `cloudevents` of the global tracer provider. The
+//! other bindings can use [`send_span`], [`receive_span`] and [`receive_context`] to instrument
+//! their transport.
+
+use crate::event::AttributesReader;
+use crate::extensions::DistributedTraceExtension;
+use crate::Event;
+use opentelemetry::global::{self, BoxedSpan};
+use opentelemetry::trace::{FutureExt, Span, SpanKind, Status, TraceContextExt, Tracer};
+use opentelemetry::{Context, KeyValue};
+use std::fmt::Display;
+use std::future::Future;
+
+/// Name of the tracer creating the spans
+pub const TRACER_NAME: &str = "cloudevents";
+
+/// Start the span of sending `event` to `destination` with the messaging `system`, e.g. `kafka`,
+/// writing its context to the [`DistributedTraceExtension`] of `event` if missing.
+///
+/// ```
+/// use cloudevents::binding::telemetry::send_span;
+/// use cloudevents::{EventBuilder, EventBuilderV10};
+/// use opentelemetry::trace::Span;
+///
+/// let mut event = EventBuilderV10::new()
+///     .id("0001")
+///     .source("http://localhost/")
+///     .ty("example.demo")
+///     .build()
+///     .unwrap();
+///
+/// let mut span = send_span(&mut event, "kafka", Some("orders"));
+/// // Send the event
+/// span.end();
+/// ```
+pub fn send_span(event: &mut Event, system: &'static str, destination: Option<&str>) -> BoxedSpan {
+    let tracer = global::tracer(TRACER_NAME);
+    let span = tracer
+        .span_builder(span_name(destination, "publish"))
+        .with_kind(SpanKind::Producer)
+        .with_attributes(attributes(event, system, destination, "publish"))
+        .start(&tracer);
+
+    if event.get_extension("traceparent").is_none() {
+        if let Some(extension) = DistributedTraceExtension::from_span_context(span.span_context()) {
+            extension.write_to(event);
+        }
+    }
+    span
+}
+
+/// Get the context the processing of a received `event` belongs to: the context of its
+/// [`DistributedTraceExtension`], or the current context if missing or invalid.
+///
+/// The bindings only instrument the reception of the events, so the handlers can attach it to
+/// their processing for its spans to be part of the trace of the event:
+///
+/// ```
+/// use cloudevents::binding::telemetry::parent_context;
+/// use cloudevents::Event;
+/// use opentelemetry::trace::FutureExt;
+///
+/// async fn handle(event: Event) {}
+///
+/// # async fn example(event: Event) {
+/// let context = parent_context(&event);
+/// handle(event).with_context(context).await;
+/// # }
+/// ```
+pub fn parent_context(event: &Event) -> Context {
+    match DistributedTraceExtension::from_event(event) {
+        Ok(Some(extension)) => {
+            Context::current().with_remote_span_context(extension.to_span_context())
+        }
+        _ => Context::current(),
+    }
+}
+
+/// Start the span of receiving `event` from `destination` with the messaging `system`, as a
+/// child of the [`parent_context`] of `event`.
+///
+/// ```
+/// use cloudevents::binding::telemetry::receive_span;
+/// use cloudevents::{EventBuilder, EventBuilderV10};
+/// use opentelemetry::trace::Span;
+///
+/// let event = EventBuilderV10::new()
+///     .id("0001")
+///     .source("http://localhost/")
+///     .ty("example.demo")
+///     .extension("traceparent", "00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01")
+///     .build()
+///     .unwrap();
+///
+/// let mut span = receive_span(&event, "kafka", Some("orders"));
+/// assert_eq!(
+///     "0af7651916cd43dd8448eb211c80319c",
+///     span.span_context().trace_id().to_string()
+/// );
+/// span.end();
+/// ```
+pub fn receive_span(event: &Event, system: &'static str, destination: Option<&str>) -> BoxedSpan {
+    let tracer = global::tracer(TRACER_NAME);
+    tracer
+        .span_builder(span_name(destination, "receive"))
+        .with_kind(SpanKind::Consumer)
+        .with_attributes(attributes(event, system, destination, "receive"))
+        .start_with_context(&tracer, &parent_context(event))
+}
+
+/// Start the [`receive_span`] of `event`, returning the context carrying it, e.g. to run the
+/// reception of the event in with [`FutureExt::with_context`](opentelemetry::trace::FutureExt)
+/// and end it with [`end_context`].
+pub fn receive_context(event: &Event, system: &'static str, destination: Option<&str>) -> Context {
+    parent_context(event).with_span(receive_span(event, system, destination))
+}
+
+/// End `span` with the outcome of the operation, setting its status to error on failure
+pub fn end_span<T, E: Display>(mut span: BoxedSpan, result: &Result<T, E>) {
+    if let Err(e) = result {
+        span.set_status(Status::error(e.to_string()));
+    }
+    span.end();
+}
+
+/// End the span of `context` with the outcome of the operation, see [`end_span`]
+pub fn end_context<T, E: Display>(context: &Context, result: &Result<T, E>) {
+    let span = context.span();
+    if let Err(e) = result {
+        span.set_status(Status::error(e.to_string()));
+    }
+    span.end();
+}
+
+/// Run `future` in `context`, then end its span with the outcome, see [`end_context`]
+pub(crate) async fn in_context<T, E: Display>(
+    context: Context,
+    future: impl Future<Output = Result<T, E>>,
+) -> Result<T, E> {
+    let result = future.with_context(context.clone()).await;
+    end_context(&context, &result);
+    result
+}
+
+fn span_name(destination: Option<&str>, operation: &'static str) -> String {
+    match destination {
+        Some(destination) => format!("{} {}", destination, operation),
+        None => String::from(operation),
+    }
+}
+
+fn attributes(
+    event: &Event,
+    system: &'static str,
+    destination: Option<&str>,
+    operation: &'static str,
+) -> Vec<KeyValue> {
+    let mut attributes = vec![
+        KeyValue::new("messaging.system", system),
+        KeyValue::new("messaging.operation", operation),
+        KeyValue::new("cloudevents.event_id", event.get_id().to_string()),
+        KeyValue::new("cloudevents.event_source", event.get_source().to_string()),
+        KeyValue::new(
+            "cloudevents.event_spec_version",
+            event.get_specversion().to_string(),
+        ),
+        KeyValue::new("cloudevents.event_type", event.get_type().to_string()),
+    ];
+    if let Some(subject) = event.get_subject() {
+        attributes.push(KeyValue::new(
+            "cloudevents.event_subject",
+            subject.to_string(),
+        ));
+    }
+    if let Some(destination) = destination {
+        attributes.push(KeyValue::new(
+            "messaging.destination.name",
+            destination.to_string(),
+        ));
+    }
+    attributes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::AttributesWriter;
+    use crate::{EventBuilder, EventBuilderV10};
+
+    const TRACEPARENT: &str = "00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01";
+
+    fn event() -> Event {
+        EventBuilderV10::new()
+            .id("0001")
+            .source("http://localhost/")
+            .ty("example.test")
+            .build()
+            .unwrap()
+    }
+
+    fn remote_context() -> Context {
+        let extension = DistributedTraceExtension::new(TRACEPARENT, None::<String>).unwrap();
+        Context::new().with_remote_span_context(extension.to_span_context())
+    }
+
+    #[test]
+    fn send_injects_context() {
+        // Without an active span, there's no context to propagate
+        let mut e = event();
+        end_span(
+            send_span(&mut e, "kafka", Some("topic")),
+            &Ok::<(), String>(()),
+        );
+        assert!(e.get_extension("traceparent").is_none());
+
+        // The no-op tracer propagates the active span context
+        let _guard = remote_context().attach();
+        let mut e = event();
+        end_span(send_span(&mut e, "kafka", None), &Err::<(), _>("failed"));
+        assert_eq!(Some(&TRACEPARENT.into()), e.get_extension("traceparent"));
+
+        // The creation context is preserved
+        let creation = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+        let mut e = event();
+        e.set_extension("traceparent", creation);
+        send_span(&mut e, "kafka", None).end();
+        assert_eq!(Some(&creation.into()), e.get_extension("traceparent"));
+    }
+
+    #[test]
+    fn receive_extracts_context() {
+        let mut e = event();
+        assert!(!receive_span(&e, "http", None).span_context().is_valid());
+
+        e.set_extension("traceparent", TRACEPARENT);
+        let expected = DistributedTraceExtension::new(TRACEPARENT, None::<String>)
+            .unwrap()
+            .to_span_context();
+        let span = receive_span(&e, "http", None);
+        assert_eq!(expected, *span.span_context());
+
+        let context = receive_context(&e, "http", None);
+        assert_eq!(expected, *context.span().span_context());
+        assert_eq!(expected, *parent_context(&e).span().span_context());
+        end_context(&context, &Err::<(), _>("rejected"));
+    }
+
+    #[test]
+    fn span_attributes() {
+        let mut e = event();
+        e.set_subject(Some("my-subject"));
+        let attributes = attributes(&e, "kafka", Some("topic"), "publish");
+        for expected in &[
+            KeyValue::new("messaging.system", "kafka"),
+            KeyValue::new("messaging.operation", "publish"),
+            KeyValue::new("messaging.destination.name", "topic"),
+            KeyValue::new("cloudevents.event_id", "0001"),
+            KeyValue::new("cloudevents.event_source", "http://localhost/"),
+            KeyValue::new("cloudevents.event_spec_version", "1.0"),
+            KeyValue::new("cloudevents.event_type", "example.test"),
+            KeyValue::new("cloudevents.event_subject", "my-subject"),
+        ] {
+            assert!(attributes.contains(expected), "missing {:?}", expected);
+        }
+        assert_eq!("topic publish", span_name(Some("topic"), "publish"));
+        assert_eq!("receive", span_name(None, "receive"));
+    }
+}
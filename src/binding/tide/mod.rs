@@ -0,0 +1,276 @@
+//! Integration with [Tide](https://github.com/http-rs/tide), built on top of the
+//! [`http`](super::http) binding.
+//!
+//! [`RequestExt`] reads an [`Event`] or a [`Batch`] from a [`Request`], in binary, structured
+//! or batched mode, and [`ResponseExt`] writes them to a [`Response`], so endpoints can take
+//! and return events:
+//!
+//! ```
+//! use cloudevents::binding::tide::{RequestExt, ResponseExt};
+//! use tide::{Request, Response};
+//!
+//! let mut app = tide::new();
+//! app.at("/").post(|mut req: Request<()>| async move {
+//!     let event = req.to_event().await?;
+//!     Response::from_event(event)
+//! });
+//! ```
+//!
+//! The errors of [`RequestExt`] carry the [status code](crate::message::Error::status_code) of
+//! the parse error, usually `400 Bad Request`, so `?` rejects the invalid requests.
+//!
+//! The [`WebhookValidation`] middleware replies to the abuse protection handshake of the
+//! HTTP WebHook spec, see [`WebhookValidator`]:
+//!
+//! ```
+//! use cloudevents::binding::http::WebhookValidator;
+//! use cloudevents::binding::tide::WebhookValidation;
+//!
+//! let mut app = tide::new();
+//! app.with(WebhookValidation::new(
+//!     WebhookValidator::new().allow_origin("eventemitter.example.com"),
+//! ));
+//! ```
+
+use super::http::{batch_to_message, event_to_binary, receive_event, to_batch, WebhookValidator};
+use crate::message::{Error, Received};
+use crate::{Batch, Event};
+use ::http::header::{HeaderMap, HeaderName, HeaderValue};
+use async_trait::async_trait;
+use tide::http::headers::CONTENT_TYPE;
+use tide::http::Method;
+use tide::{Middleware, Next, Request, Response, StatusCode};
+
+/// Map `error` to a [`tide::Error`] with its status code
+fn to_tide_error(error: Error) -> tide::Error {
+    tide::Error::new(error.status_code(), error)
+}
+
+/// Copy the headers of a Tide request to a [`HeaderMap`]
+fn to_header_map<State>(req: &Request<State>) -> Result<HeaderMap, Error> {
+    let mut map = HeaderMap::new();
+    for (name, values) in req.iter() {
+        let name = HeaderName::from_bytes(name.as_str().as_bytes()).map_err(|e| Error::Other {
+            source: Box::new(e),
+        })?;
+        for value in values {
+            let value = HeaderValue::from_str(value.as_str()).map_err(|e| Error::Other {
+                source: Box::new(e),
+            })?;
+            map.append(name.clone(), value);
+        }
+    }
+    Ok(map)
+}
+
+/// Read the body of `req`, parse it with `parse` and intercept it with the installed
+/// [`Pipeline`](crate::message::Pipeline), failing with the status code of the error
+async fn parse_request<State, T: Received>(
+    req: &mut Request<State>,
+    parse: fn(&HeaderMap, Vec<u8>) -> Result<T, Error>,
+) -> tide::Result<T> {
+    let headers = to_header_map(req).map_err(to_tide_error)?;
+    let body = req.body_bytes().await?;
+    let mut value = parse(&headers, body).map_err(to_tide_error)?;
+    value
+        .intercept_receive("http", None)
+        .await
+        .map_err(to_tide_error)?;
+    Ok(value)
+}
+
+/// Write the message returned by `serialize`, failing with `500 Internal Server Error` on error
+fn message_response(serialize: Result<(HeaderMap, Vec<u8>), Error>) -> tide::Result<Response> {
+    let (headers, body) = serialize?;
+    let mut response = Response::new(StatusCode::Ok);
+    response.set_body(body);
+    // Tide sets the content type of the bytes bodies, the one of the message is in `headers`
+    response.remove_header(CONTENT_TYPE);
+    for (name, value) in &headers {
+        response.append_header(name.as_str(), value.to_str()?);
+    }
+    Ok(response)
+}
+
+/// Extension trait for [`Request`] to read an [`Event`] or a [`Batch`] from the request payload
+#[async_trait]
+pub trait RequestExt {
+    /// Read the [`Event`] of the request, in binary or structured mode
+    async fn to_event(&mut self) -> tide::Result<Event>;
+    /// Read the [`Batch`] of the request, in batched mode
+    async fn to_batch(&mut self) -> tide::Result<Batch>;
+}
+
+#[async_trait]
+impl<State: Clone + Send + Sync + 'static> RequestExt for Request<State> {
+    async fn to_event(&mut self) -> tide::Result<Event> {
+        parse_request(self, receive_event).await
+    }
+
+    async fn to_batch(&mut self) -> tide::Result<Batch> {
+        parse_request(self, to_batch).await
+    }
+}
+
+/// Extension trait for [`Response`] to write an [`Event`] or a [`Batch`] as the response payload
+pub trait ResponseExt: Sized {
+    /// Create a response with the [`Event`], in binary mode
+    fn from_event(event: Event) -> tide::Result<Self>;
+    /// Create a response with the [`Batch`], in batched mode
+    fn from_batch(batch: Batch) -> tide::Result<Self>;
+}
+
+impl ResponseExt for Response {
+    fn from_event(event: Event) -> tide::Result<Self> {
+        message_response(event_to_binary(event))
+    }
+
+    fn from_batch(batch: Batch) -> tide::Result<Self> {
+        message_response(batch_to_message(batch))
+    }
+}
+
+/// Middleware replying to the webhook validation requests with a [`WebhookValidator`], see
+/// [`WebhookValidator::validate`], and passing the other requests to the next endpoint
+#[derive(Debug, Clone)]
+pub struct WebhookValidation {
+    validator: WebhookValidator,
+}
+
+impl WebhookValidation {
+    /// Create a new [`WebhookValidation`] middleware replying with `validator`
+    pub fn new(validator: WebhookValidator) -> Self {
+        WebhookValidation { validator }
+    }
+}
+
+#[async_trait]
+impl<State: Clone + Send + Sync + 'static> Middleware<State> for WebhookValidation {
+    async fn handle(&self, req: Request<State>, next: Next<'_, State>) -> tide::Result {
+        if req.method() != Method::Options {
+            return Ok(next.run(req).await);
+        }
+        let headers = to_header_map(&req).map_err(to_tide_error)?;
+        match self.validator.validate(&::http::Method::OPTIONS, &headers) {
+            Some(validation) => {
+                let mut response = Response::new(validation.status().as_u16());
+                for (name, value) in validation.headers() {
+                    response.append_header(name.as_str(), value.to_str()?);
+                }
+                Ok(response)
+            }
+            None => Ok(next.run(req).await),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AttributesReader, EventBuilder, EventBuilderV10};
+    use serde_json::json;
+    use tide::http::{Request as HttpRequest, Response as HttpResponse, Url};
+
+    fn expected_event() -> Event {
+        EventBuilderV10::new()
+            .id("0001")
+            .ty("example.test")
+            .source("http://localhost/")
+            .data("application/json", json!({"hello": "world"}))
+            .extension("someint", "10")
+            .build()
+            .unwrap()
+    }
+
+    fn app() -> tide::Server<()> {
+        let mut app = tide::new();
+        app.with(WebhookValidation::new(
+            WebhookValidator::new().allow_origin("example.com"),
+        ));
+        app.at("/").post(|mut req: Request<()>| async move {
+            Response::from_event(req.to_event().await?)
+        });
+        app.at("/batch").post(|mut req: Request<()>| async move {
+            Response::from_batch(req.to_batch().await?)
+        });
+        app
+    }
+
+    fn request(method: Method, path: &str) -> HttpRequest {
+        HttpRequest::new(
+            method,
+            Url::parse("http://localhost/").unwrap().join(path).unwrap(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_binary_and_structured() {
+        let app = app();
+
+        let mut req = request(Method::Post, "/");
+        req.insert_header("ce-specversion", "1.0");
+        req.insert_header("ce-id", "0001");
+        req.insert_header("ce-type", "example.test");
+        req.insert_header("ce-source", "http://localhost/");
+        req.insert_header("ce-someint", "10");
+        req.insert_header("content-type", "application/json");
+        req.set_body(json!({"hello": "world"}).to_string());
+        let mut res: HttpResponse = app.respond(req).await.unwrap();
+        assert_eq!(StatusCode::Ok, res.status());
+        assert_eq!("0001", res["ce-id"].as_str());
+        assert_eq!("10", res["ce-someint"].as_str());
+        assert_eq!("application/json", res["content-type"].as_str());
+        assert_eq!(
+            json!({"hello": "world"}),
+            serde_json::from_slice::<serde_json::Value>(&res.body_bytes().await.unwrap()).unwrap()
+        );
+
+        let mut req = request(Method::Post, "/");
+        req.insert_header("content-type", "application/cloudevents+json");
+        req.set_body(serde_json::to_vec(&expected_event()).unwrap());
+        let res: HttpResponse = app.respond(req).await.unwrap();
+        assert_eq!(StatusCode::Ok, res.status());
+        assert_eq!("example.test", res["ce-type"].as_str());
+
+        let mut req = request(Method::Post, "/");
+        req.insert_header("content-type", "application/json");
+        req.set_body(json!({"hello": "world"}).to_string());
+        let res: HttpResponse = app.respond(req).await.unwrap();
+        assert_eq!(StatusCode::BadRequest, res.status());
+    }
+
+    #[tokio::test]
+    async fn test_batch() {
+        let app = app();
+        let expected = Batch::from(vec![expected_event(), expected_event()]);
+
+        let mut req = request(Method::Post, "/batch");
+        req.insert_header("content-type", "application/cloudevents-batch+json");
+        req.set_body(serde_json::to_vec(&expected).unwrap());
+        let mut res: HttpResponse = app.respond(req).await.unwrap();
+        assert_eq!(StatusCode::Ok, res.status());
+        assert_eq!(
+            "application/cloudevents-batch+json",
+            res["content-type"].as_str()
+        );
+        let batch: Batch = serde_json::from_slice(&res.body_bytes().await.unwrap()).unwrap();
+        assert_eq!(2, batch.len());
+        assert_eq!("0001", batch[0].get_id());
+    }
+
+    #[tokio::test]
+    async fn test_webhook_validation() {
+        let app = app();
+
+        let mut req = request(Method::Options, "/");
+        req.insert_header("webhook-request-origin", "example.com");
+        let res: HttpResponse = app.respond(req).await.unwrap();
+        assert_eq!(StatusCode::Ok, res.status());
+        assert_eq!("example.com", res["webhook-allowed-origin"].as_str());
+
+        let mut req = request(Method::Options, "/");
+        req.insert_header("webhook-request-origin", "other.com");
+        let res: HttpResponse = app.respond(req).await.unwrap();
+        assert_eq!(StatusCode::Forbidden, res.status());
+    }
+}
@@ -0,0 +1,73 @@
+//! [tonic](https://github.com/hyperium/tonic) codec to send and receive [`Event`]s over gRPC,
+//! as messages of the Protobuf Event Format [`CloudEvent`] type.
+//!
+//! To use [`EventCodec`] in a service, set it as the codec of the methods exchanging
+//! `io.cloudevents.v1.CloudEvent` messages with `tonic-build`:
+//!
+//! ```ignore
+//! tonic_build::manual::Method::builder()
+//!     .name("publish")
+//!     .route_name("Publish")
+//!     .input_type("cloudevents::Event")
+//!     .output_type("cloudevents::Event")
+//!     .codec_path("cloudevents::binding::tonic::EventCodec")
+//!     .client_streaming()
+//!     .build()
+//! ```
+
+use crate::proto::CloudEvent;
+use crate::Event;
+use prost::Message;
+use std::convert::TryFrom;
+use tonic::codec::{Codec, DecodeBuf, Decoder, EncodeBuf, Encoder};
+use tonic::Status;
+
+/// [`Codec`] encoding and decoding [`Event`]s as [`CloudEvent`] protobuf messages
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EventCodec;
+
+impl Codec for EventCodec {
+    type Encode = Event;
+    type Decode = Event;
+    type Encoder = EventEncoder;
+    type Decoder = EventDecoder;
+
+    fn encoder(&mut self) -> Self::Encoder {
+        EventEncoder
+    }
+
+    fn decoder(&mut self) -> Self::Decoder {
+        EventDecoder
+    }
+}
+
+/// [`Encoder`] writing an [`Event`] as a [`CloudEvent`] protobuf message
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EventEncoder;
+
+impl Encoder for EventEncoder {
+    type Item = Event;
+    type Error = Status;
+
+    fn encode(&mut self, item: Self::Item, dst: &mut EncodeBuf<'_>) -> Result<(), Self::Error> {
+        CloudEvent::from(item)
+            .encode(dst)
+            .map_err(|e| Status::internal(e.to_string()))
+    }
+}
+
+/// [`Decoder`] reading an [`Event`] from a [`CloudEvent`] protobuf message
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EventDecoder;
+
+impl Decoder for EventDecoder {
+    type Item = Event;
+    type Error = Status;
+
+    fn decode(&mut self, src: &mut DecodeBuf<'_>) -> Result<Option<Self::Item>, Self::Error> {
+        let proto = CloudEvent::decode(src).map_err(|e| Status::internal(e.to_string()))?;
+        Event::try_from(proto)
+            .map(Some)
+            .map_err(|e| Status::invalid_argument(e.to_string()))
+    }
+}
@@ -0,0 +1,338 @@
+//! [tower](https://github.com/tower-rs/tower) middleware converting HTTP requests and responses
+//! to [`Event`]s, built on top of the [`http`](super::http) binding, so any tower-based stack,
+//! e.g. hyper, axum or tonic, can serve CloudEvents.
+//!
+//! [`EventLayer`] wraps a `Service<Event, Response = Option<Event>>`: the requests in binary and
+//! structured mode are parsed to [`Event`] and passed to the inner service, and the event it
+//! returns is written as a binary mode response.
+//!
+//! ```
+//! use cloudevents::binding::tower::EventLayer;
+//! use cloudevents::Event;
+//! use http::{Request, StatusCode};
+//! use tower::{service_fn, Layer, ServiceExt};
+//!
+//! let handler = service_fn(|event: Event| async move {
+//!     Ok::<_, std::convert::Infallible>(Some(event))
+//! });
+//! let service = EventLayer::new().max_size(64 * 1024).validate(true).layer(handler);
+//!
+//! let request = Request::post("/")
+//!     .header("ce-specversion", "1.0")
+//!     .header("ce-id", "0001")
+//!     .header("ce-type", "example.demo")
+//!     .header("ce-source", "http://localhost/")
+//!     .body(String::new())
+//!     .unwrap();
+//! # futures::executor::block_on(async {
+//! let response = service.oneshot(request).await.unwrap();
+//! assert_eq!(StatusCode::OK, response.status());
+//! assert_eq!("0001", response.headers()["ce-id"]);
+//! # });
+//! ```
+
+use super::http::{event_to_binary, receive_event};
+use crate::message::{Error, Received};
+use crate::Event;
+use ::http::header::CONTENT_LENGTH;
+use ::http::{Request, Response, StatusCode};
+use bytes::{BufMut, Bytes, BytesMut};
+use http_body::{Body, Full};
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tower_layer::Layer;
+use tower_service::Service;
+
+/// Boxed error of the request bodies
+pub type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// [`Layer`] converting the HTTP requests to [`Event`] for the inner service, and the events it
+/// returns to HTTP responses, see [`EventLayerService`].
+#[derive(Debug, Clone, Default)]
+pub struct EventLayer {
+    max_size: Option<usize>,
+    validate: bool,
+}
+
+impl EventLayer {
+    /// Create a new [`EventLayer`] accepting the requests of any size, without validating them
+    pub fn new() -> Self {
+        EventLayer::default()
+    }
+
+    /// Reply `413 Payload Too Large` to the requests with a body larger than `limit` bytes
+    pub fn max_size(mut self, limit: usize) -> Self {
+        self.max_size = Some(limit);
+        self
+    }
+
+    /// Reply `400 Bad Request` to the events failing [`Event::validate`] if `validate` is `true`
+    pub fn validate(mut self, validate: bool) -> Self {
+        self.validate = validate;
+        self
+    }
+}
+
+impl<S> Layer<S> for EventLayer {
+    type Service = EventLayerService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        EventLayerService {
+            inner,
+            config: self.clone(),
+        }
+    }
+}
+
+/// [`Service`] built by [`EventLayer`], calling the `inner` service with the requests parsed to
+/// [`Event`] and intercepted by the installed [`Pipeline`](crate::message::Pipeline).
+///
+/// * The event returned by the inner service is written as a binary mode `200 OK` response, and
+///   `None` is replied with `202 Accepted`.
+/// * A request which isn't an event is replied with the
+///   [status code](crate::message::Error::status_code) of the parsing error.
+/// * The errors of the inner service are returned as is.
+#[derive(Debug, Clone)]
+pub struct EventLayerService<S> {
+    inner: S,
+    config: EventLayer,
+}
+
+impl<S, B> Service<Request<B>> for EventLayerService<S>
+where
+    S: Service<Event, Response = Option<Event>> + Clone + Send + 'static,
+    S::Future: Send,
+    B: Body + Send + 'static,
+    B::Data: Send,
+    B::Error: Into<BoxError>,
+{
+    type Response = Response<Full<Bytes>>;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), S::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<B>) -> Self::Future {
+        // The inner service polled ready is moved to the future, leaving a clone in its place
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let config = self.config.clone();
+        Box::pin(async move {
+            let event = match parse_request(request, &config).await {
+                Ok(event) => event,
+                Err(response) => return Ok(response),
+            };
+            #[cfg(feature = "opentelemetry")]
+            let called = {
+                use opentelemetry::trace::FutureExt;
+                let context = crate::binding::telemetry::parent_context(&event);
+                inner.call(event).with_context(context)
+            };
+            #[cfg(not(feature = "opentelemetry"))]
+            let called = inner.call(event);
+            Ok(match called.await? {
+                Some(event) => event_response(event),
+                None => status_response(StatusCode::ACCEPTED, Bytes::new()),
+            })
+        })
+    }
+}
+
+/// Read the body of `request`, parse it and intercept it with the installed
+/// [`Pipeline`](crate::message::Pipeline), rejecting it if it doesn't comply with `config`
+async fn parse_request<B>(
+    request: Request<B>,
+    config: &EventLayer,
+) -> Result<Event, Response<Full<Bytes>>>
+where
+    B: Body,
+    B::Error: Into<BoxError>,
+{
+    let (parts, body) = request.into_parts();
+    let body = read_body(body, &parts.headers, config.max_size)
+        .await
+        .map_err(error_response)?;
+    let mut event = receive_event(&parts.headers, body).map_err(error_response)?;
+    if config.validate {
+        if let Err(errors) = event.validate() {
+            let reasons: Vec<String> = errors.iter().map(ToString::to_string).collect();
+            return Err(status_response(
+                StatusCode::BAD_REQUEST,
+                Bytes::from(reasons.join("\n")),
+            ));
+        }
+    }
+    event
+        .intercept_receive("http", None)
+        .await
+        .map_err(error_response)?;
+    Ok(event)
+}
+
+/// Collect `body`, failing with [`Error::EventTooLarge`] once it exceeds `limit`
+async fn read_body<B>(
+    body: B,
+    headers: &::http::HeaderMap,
+    limit: Option<usize>,
+) -> Result<Bytes, Error>
+where
+    B: Body,
+    B::Error: Into<BoxError>,
+{
+    let length = headers
+        .get(CONTENT_LENGTH)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<usize>().ok());
+    if let (Some(size), Some(limit)) = (length, limit) {
+        if size > limit {
+            return Err(Error::EventTooLarge { size, limit });
+        }
+    }
+
+    let mut body = Box::pin(body);
+    let mut buffer = BytesMut::new();
+    while let Some(chunk) = body.data().await {
+        buffer.put(chunk.map_err(|e| Error::Other { source: e.into() })?);
+        if let Some(limit) = limit {
+            if buffer.len() > limit {
+                return Err(Error::EventTooLarge {
+                    size: buffer.len(),
+                    limit,
+                });
+            }
+        }
+    }
+    Ok(buffer.freeze())
+}
+
+fn status_response(status: StatusCode, body: Bytes) -> Response<Full<Bytes>> {
+    let mut response = Response::new(Full::new(body));
+    *response.status_mut() = status;
+    response
+}
+
+/// Reply to a request which couldn't be read with the status code of `error`
+fn error_response(error: Error) -> Response<Full<Bytes>> {
+    let status = StatusCode::from_u16(error.status_code()).unwrap_or(StatusCode::BAD_REQUEST);
+    status_response(status, Bytes::from(error.to_string()))
+}
+
+/// Write `event` as a binary mode response, replying with `500 Internal Server Error` on error
+fn event_response(event: Event) -> Response<Full<Bytes>> {
+    match event_to_binary(event) {
+        Ok((headers, body)) => {
+            let mut response = Response::new(Full::new(Bytes::from(body)));
+            *response.headers_mut() = headers;
+            response
+        }
+        Err(e) => status_response(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Bytes::from(e.to_string()),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::binding::http::event_to_structured;
+    use crate::{AttributesReader, EventBuilder, EventBuilderV10};
+    use serde_json::json;
+    use std::convert::Infallible;
+    use tower::{service_fn, ServiceExt};
+
+    fn event() -> Event {
+        EventBuilderV10::new()
+            .id("0001")
+            .ty("example.test")
+            .source("http://localhost/")
+            .data("application/json", json!({"hello": "world"}))
+            .build()
+            .unwrap()
+    }
+
+    async fn reply(event: Event) -> Result<Option<Event>, Infallible> {
+        Ok(match event.get_type() {
+            "example.ignored" => None,
+            _ => Some(event),
+        })
+    }
+
+    fn request(message: (::http::HeaderMap, Vec<u8>)) -> Request<String> {
+        let (headers, body) = message;
+        let mut request = Request::new(String::from_utf8(body).unwrap());
+        *request.headers_mut() = headers;
+        request
+    }
+
+    async fn into_parts(response: Response<Full<Bytes>>) -> (StatusCode, ::http::HeaderMap, Bytes) {
+        let (parts, mut body) = response.into_parts();
+        let body = body.data().await.transpose().unwrap().unwrap_or_default();
+        (parts.status, parts.headers, body)
+    }
+
+    #[tokio::test]
+    async fn reply_event() {
+        let service = EventLayer::new().layer(service_fn(reply));
+        let response = service
+            .clone()
+            .oneshot(request(event_to_structured(event()).unwrap()))
+            .await
+            .unwrap();
+        let (status, headers, body) = into_parts(response).await;
+        assert_eq!(StatusCode::OK, status);
+        assert_eq!(event(), receive_event(&headers, body).unwrap());
+
+        let mut ignored = event();
+        crate::AttributesWriter::set_type(&mut ignored, "example.ignored");
+        let response = service
+            .oneshot(request(event_to_binary(ignored).unwrap()))
+            .await
+            .unwrap();
+        assert_eq!(StatusCode::ACCEPTED, response.status());
+    }
+
+    #[tokio::test]
+    async fn rejections() {
+        let message = event_to_binary(event()).unwrap();
+        let response = EventLayer::new()
+            .max_size(4)
+            .layer(service_fn(reply))
+            .oneshot(request(message))
+            .await
+            .unwrap();
+        assert_eq!(StatusCode::PAYLOAD_TOO_LARGE, response.status());
+
+        let invalid = EventBuilderV10::new()
+            .id("")
+            .ty("example.test")
+            .source("http://localhost/")
+            .build()
+            .unwrap();
+        let message = event_to_binary(invalid).unwrap();
+        let layer = EventLayer::new().validate(true);
+        let response = layer
+            .layer(service_fn(reply))
+            .oneshot(request(message.clone()))
+            .await
+            .unwrap();
+        assert_eq!(StatusCode::BAD_REQUEST, response.status());
+        let response = EventLayer::new()
+            .layer(service_fn(reply))
+            .oneshot(request(message))
+            .await
+            .unwrap();
+        assert_eq!(StatusCode::OK, response.status());
+
+        let not_an_event = Request::new(json!({"hello": "world"}).to_string());
+        let response = EventLayer::new()
+            .layer(service_fn(reply))
+            .oneshot(not_an_event)
+            .await
+            .unwrap();
+        assert_eq!(StatusCode::BAD_REQUEST, response.status());
+    }
+}
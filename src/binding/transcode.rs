@@ -0,0 +1,312 @@
+//! Transcode messages between protocol bindings, e.g. to bridge a Kafka topic to an HTTP
+//! endpoint, without building the [`Event`](crate::Event) when possible.
+//!
+//! A message is forwarded as is when the source and target content modes match: the attributes
+//! and extensions of a binary mode message are passed to the target [`BinarySerializer`], and the
+//! payload of a structured mode message to the target [`StructuredSerializer`]. A binary mode
+//! message is written in structured mode with [`JsonStructuredSerializer`], and only a structured
+//! mode message written in binary mode is parsed to an event.
+//!
+//! The forwarded messages aren't validated, so a proxy preserves the extensions it doesn't know,
+//! and the attributes it can't represent as [`Event`](crate::Event) attributes.
+//!
+//! ```
+//! # #[cfg(feature = "http")]
+//! # {
+//! use cloudevents::binding::http::{HeaderMapDeserializer, HeaderMapSerializer};
+//! use cloudevents::binding::transcode;
+//! use http::HeaderMap;
+//!
+//! let mut headers = HeaderMap::new();
+//! headers.insert("ce-specversion", "1.0".parse().unwrap());
+//! headers.insert("ce-id", "0001".parse().unwrap());
+//! headers.insert("ce-source", "http://localhost/".parse().unwrap());
+//! headers.insert("ce-type", "example.demo".parse().unwrap());
+//! headers.insert("ce-someext", "value".parse().unwrap());
+//!
+//! let (headers, body) = transcode::to_structured(
+//!     HeaderMapDeserializer::new(&headers, Vec::new()),
+//!     HeaderMapSerializer::default(),
+//! )
+//! .unwrap();
+//! assert_eq!("application/cloudevents+json", headers["content-type"]);
+//! assert_eq!(
+//!     "value",
+//!     serde_json::from_slice::<serde_json::Value>(&body).unwrap()["someext"]
+//! );
+//! # }
+//! ```
+
+use crate::event::{is_json_content_type, SpecVersion};
+use crate::message::{
+    BinarySerializer, Encoding, Error, MessageAttributeValue, MessageDeserializer, Result,
+    StructuredSerializer,
+};
+use bytes::Bytes;
+use serde_json::{Map, Value};
+
+/// Transcode `message` to `serializer` in binary mode, parsing it to an event if it's in
+/// structured mode
+pub fn to_binary<R, S: BinarySerializer<R>>(
+    message: impl MessageDeserializer,
+    serializer: S,
+) -> Result<R> {
+    message.deserialize_to_binary(serializer)
+}
+
+/// Transcode `message` to `serializer` in structured mode, writing it in the JSON event format
+/// with [`JsonStructuredSerializer`] if it's in binary mode
+pub fn to_structured<R, S: StructuredSerializer<R>>(
+    message: impl MessageDeserializer,
+    serializer: S,
+) -> Result<R> {
+    match message.encoding() {
+        Encoding::STRUCTURED => message.deserialize_structured(serializer),
+        Encoding::BINARY => message.deserialize_binary(JsonStructuredSerializer::new(serializer)),
+        Encoding::BATCH => Err(Error::UnsupportedContentMode {
+            content_mode: Encoding::BATCH,
+        }),
+        _ => Err(Error::WrongEncoding {}),
+    }
+}
+
+/// Transcode `message` to `serializer` in the `encoding` content mode, see [`to_binary`] and
+/// [`to_structured`].
+///
+/// Fails with [`Error::UnsupportedContentMode`] if `encoding` is not binary or structured.
+pub fn transcode<R, S: BinarySerializer<R> + StructuredSerializer<R>>(
+    message: impl MessageDeserializer,
+    encoding: Encoding,
+    serializer: S,
+) -> Result<R> {
+    match encoding {
+        Encoding::BINARY => to_binary(message, serializer),
+        Encoding::STRUCTURED => to_structured(message, serializer),
+        content_mode => Err(Error::UnsupportedContentMode { content_mode }),
+    }
+}
+
+/// [`BinarySerializer`] writing a binary mode message in the JSON event format to a
+/// [`StructuredSerializer`].
+///
+/// The attributes and extensions are written without validation. As when parsing the message to
+/// an event, the payload is written in the `data` field if the `datacontenttype` is a JSON
+/// content type or absent, and encoded in base64 otherwise.
+pub struct JsonStructuredSerializer<S> {
+    serializer: S,
+    spec_version: Option<SpecVersion>,
+    attributes: Map<String, Value>,
+}
+
+impl<S> JsonStructuredSerializer<S> {
+    /// Create a new [`JsonStructuredSerializer`] writing to `serializer`
+    pub fn new(serializer: S) -> Self {
+        JsonStructuredSerializer {
+            serializer,
+            spec_version: None,
+            attributes: Map::new(),
+        }
+    }
+
+    fn insert(mut self, name: &str, value: MessageAttributeValue) -> Self {
+        let value = match value {
+            MessageAttributeValue::Boolean(b) => Value::Bool(b),
+            MessageAttributeValue::Integer(i) => Value::from(i),
+            v => Value::String(v.to_string()),
+        };
+        self.attributes.insert(name.to_string(), value);
+        self
+    }
+}
+
+impl<R, S: StructuredSerializer<R>> BinarySerializer<R> for JsonStructuredSerializer<S> {
+    fn set_spec_version(mut self, spec_version: SpecVersion) -> Result<Self> {
+        self.attributes.insert(
+            "specversion".to_string(),
+            Value::String(spec_version.to_string()),
+        );
+        self.spec_version = Some(spec_version);
+        Ok(self)
+    }
+
+    fn set_attribute(self, name: &str, value: MessageAttributeValue) -> Result<Self> {
+        Ok(self.insert(name, value))
+    }
+
+    fn set_extension(self, name: &str, value: MessageAttributeValue) -> Result<Self> {
+        Ok(self.insert(name, value))
+    }
+
+    fn end_with_data(mut self, bytes: Bytes) -> Result<R> {
+        let spec_version = self
+            .spec_version
+            .clone()
+            .ok_or(Error::MissingRequiredAttribute {
+                attribute_name: "specversion",
+            })?;
+        let content_type = self
+            .attributes
+            .get("datacontenttype")
+            .and_then(Value::as_str);
+        if is_json_content_type(content_type.unwrap_or("application/json")) {
+            let data = serde_json::from_slice(&bytes).map_err(|e| Error::PayloadDecodeError {
+                source: Box::new(e),
+            })?;
+            self.attributes.insert("data".to_string(), data);
+        } else {
+            let data = Value::String(base64::encode(&bytes));
+            match spec_version {
+                SpecVersion::V03 => {
+                    self.attributes.insert("data".to_string(), data);
+                    self.attributes.insert(
+                        "datacontentencoding".to_string(),
+                        Value::String("base64".to_string()),
+                    );
+                }
+                SpecVersion::V10 => {
+                    self.attributes.insert("data_base64".to_string(), data);
+                }
+            }
+        }
+        self.end()
+    }
+
+    fn end(self) -> Result<R> {
+        if self.spec_version.is_none() {
+            return Err(Error::MissingRequiredAttribute {
+                attribute_name: "specversion",
+            });
+        }
+        let bytes = serde_json::to_vec(&self.attributes)?;
+        self.serializer.set_structured_event(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::message::{BinaryDeserializer, StructuredDeserializer};
+    use crate::{Event, EventBuilder, EventBuilderV03, EventBuilderV10};
+    use chrono::{TimeZone, Utc};
+    use serde_json::json;
+
+    /// Message wrapping an event, in binary mode if the flag is set and structured mode otherwise
+    struct Message(Event, bool);
+
+    impl BinaryDeserializer for Message {
+        fn deserialize_binary<R: Sized, V: BinarySerializer<R>>(self, serializer: V) -> Result<R> {
+            self.0.deserialize_binary(serializer)
+        }
+    }
+
+    impl StructuredDeserializer for Message {
+        fn deserialize_structured<R: Sized, V: StructuredSerializer<R>>(
+            self,
+            serializer: V,
+        ) -> Result<R> {
+            self.0.deserialize_structured(serializer)
+        }
+    }
+
+    impl MessageDeserializer for Message {
+        fn encoding(&self) -> Encoding {
+            if self.1 {
+                Encoding::BINARY
+            } else {
+                Encoding::STRUCTURED
+            }
+        }
+    }
+
+    /// Serializer collecting the structured mode payload
+    struct Payload;
+
+    impl StructuredSerializer<Value> for Payload {
+        fn set_structured_event(self, bytes: Vec<u8>) -> Result<Value> {
+            Ok(serde_json::from_slice(&bytes)?)
+        }
+    }
+
+    #[test]
+    fn binary_to_structured() {
+        let events = vec![
+            EventBuilderV10::new()
+                .id("0001")
+                .source("http://localhost/")
+                .ty("example.test")
+                .time(Utc.timestamp(1_600_000_000, 123_000_000))
+                .extension("someint", 10)
+                .extension("somebool", true)
+                .data("application/json", json!({"hello": "world"}))
+                .build()
+                .unwrap(),
+            EventBuilderV10::new()
+                .id("0001")
+                .source("http://localhost/")
+                .ty("example.test")
+                .data("application/octet-stream", vec![1u8, 2, 3])
+                .build()
+                .unwrap(),
+            EventBuilderV03::new()
+                .id("0001")
+                .source("http://localhost/")
+                .ty("example.test")
+                .data("application/octet-stream", vec![1u8, 2, 3])
+                .build()
+                .unwrap(),
+            EventBuilderV10::new()
+                .id("0001")
+                .source("http://localhost/")
+                .ty("example.test")
+                .build()
+                .unwrap(),
+        ];
+
+        for event in events {
+            let expected = serde_json::to_value(&event).unwrap();
+            let transcoded = to_structured(Message(event, true), Payload).unwrap();
+            assert_eq!(expected, transcoded);
+        }
+    }
+
+    #[test]
+    fn unknown_extensions_are_preserved() {
+        let serializer = JsonStructuredSerializer::new(Payload)
+            .set_spec_version(SpecVersion::V10)
+            .unwrap()
+            .set_attribute("id", MessageAttributeValue::String("0001".into()))
+            .unwrap()
+            .set_extension("Not-An-Extension", MessageAttributeValue::Integer(1))
+            .unwrap();
+        assert_eq!(
+            json!({"specversion": "1.0", "id": "0001", "Not-An-Extension": 1}),
+            BinarySerializer::end(serializer).unwrap()
+        );
+    }
+
+    #[test]
+    fn transcode_modes() {
+        let event = EventBuilderV10::new()
+            .id("0001")
+            .source("http://localhost/")
+            .ty("example.test")
+            .time(Utc.timestamp(1_600_000_000, 0))
+            .build()
+            .unwrap();
+
+        let structured = Message(event.clone(), false);
+        assert_eq!(
+            event,
+            transcode(structured, Encoding::BINARY, Event::default()).unwrap()
+        );
+        let batch = Message(event, false);
+        assert!(matches!(
+            transcode(batch, Encoding::BATCH, Event::default()),
+            Err(Error::UnsupportedContentMode { .. })
+        ));
+        assert!(matches!(
+            BinarySerializer::end(JsonStructuredSerializer::new(Payload)),
+            Err(Error::MissingRequiredAttribute { .. })
+        ));
+    }
+}
@@ -0,0 +1,210 @@
+use super::super::http::{receive_event, to_batch as http_to_batch, WebhookValidator};
+use crate::message::{Error, Received};
+use crate::{Batch, Event};
+use warp::http::{HeaderMap, Method, StatusCode};
+use warp::hyper::body::Bytes;
+use warp::hyper::Body;
+use warp::reject::Reject;
+use warp::{Filter, Rejection, Reply};
+
+/// Rejection returned by [`to_event`] and [`to_batch`] when the request cannot be parsed as an
+/// [`Event`] or a [`Batch`]
+#[derive(Debug)]
+pub struct EventFilterError {
+    error: Error,
+}
+
+impl EventFilterError {
+    /// The error that caused the rejection
+    pub fn error(&self) -> &Error {
+        &self.error
+    }
+}
+
+impl Reject for EventFilterError {}
+
+/// Filter extracting an [`Event`] from a binary or structured mode request,
+/// rejecting with [`EventFilterError`] when the request is not a valid event
+pub fn to_event() -> impl Filter<Extract = (Event,), Error = Rejection> + Copy {
+    warp::header::headers_cloned()
+        .and(warp::body::bytes())
+        .and_then(create_event)
+}
+
+async fn create_event(headers: HeaderMap, body: Bytes) -> Result<Event, Rejection> {
+    intercepted(receive_event(&headers, body)).await
+}
+
+/// Filter extracting a [`Batch`] from a batched mode request, or a batch of one event from a
+/// binary or structured mode request, rejecting with [`EventFilterError`] when the request is not
+/// valid
+pub fn to_batch() -> impl Filter<Extract = (Batch,), Error = Rejection> + Copy {
+    warp::header::headers_cloned()
+        .and(warp::body::bytes())
+        .and_then(create_batch)
+}
+
+async fn create_batch(headers: HeaderMap, body: Bytes) -> Result<Batch, Rejection> {
+    intercepted(http_to_batch(&headers, body)).await
+}
+
+/// Intercept the parsed value with the installed [`Pipeline`](crate::message::Pipeline),
+/// rejecting with [`EventFilterError`] on error
+async fn intercepted<T: Received>(parsed: Result<T, Error>) -> Result<T, Rejection> {
+    let reject = |error| warp::reject::custom(EventFilterError { error });
+    let mut value = parsed.map_err(reject)?;
+    value
+        .intercept_receive("http", None)
+        .await
+        .map_err(reject)?;
+    Ok(value)
+}
+
+/// Recover function mapping [`EventFilterError`] rejections to a response with the
+/// [status code](Error::status_code) of the error, usually `400 Bad Request`,
+/// to be used with [`Filter::recover`]
+pub async fn handle_rejection(rejection: Rejection) -> Result<impl Reply, Rejection> {
+    match rejection.find::<EventFilterError>() {
+        Some(e) => Ok(warp::reply::with_status(
+            e.error.to_string(),
+            StatusCode::from_u16(e.error.status_code()).unwrap_or(StatusCode::BAD_REQUEST),
+        )),
+        None => Err(rejection),
+    }
+}
+
+/// Filter replying to the webhook validation requests with `validator`, see
+/// [`WebhookValidator::validate`], and rejecting any other request, so it can be combined with
+/// the event routes with [`Filter::or`]:
+///
+/// ```
+/// use cloudevents::binding::http::WebhookValidator;
+/// use cloudevents::binding::warp::{filter, reply};
+/// use warp::Filter;
+///
+/// let validator = WebhookValidator::new().allow_origin("eventemitter.example.com");
+/// let routes = filter::webhook_validation(validator)
+///     .or(warp::post().and(filter::to_event()).map(reply::from_event))
+///     .recover(filter::handle_rejection);
+/// ```
+pub fn webhook_validation(
+    validator: WebhookValidator,
+) -> impl Filter<Extract = (warp::reply::Response,), Error = Rejection> + Clone {
+    warp::options()
+        .and(warp::header::headers_cloned())
+        .and_then(move |headers: HeaderMap| {
+            let response = validator.validate(&Method::OPTIONS, &headers);
+            async move {
+                response
+                    .map(|response| response.map(|()| Body::empty()))
+                    .ok_or_else(warp::reject::not_found)
+            }
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EventBuilder, EventBuilderV10};
+    use serde_json::json;
+
+    fn expected_event() -> Event {
+        EventBuilderV10::new()
+            .id("0001")
+            .ty("example.test")
+            .source("http://localhost/")
+            .data("application/json", json!({"hello": "world"}))
+            .extension("someint", "10")
+            .build()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_binary_request() {
+        let result = warp::test::request()
+            .method("POST")
+            .header("ce-specversion", "1.0")
+            .header("ce-id", "0001")
+            .header("ce-type", "example.test")
+            .header("ce-source", "http://localhost/")
+            .header("ce-someint", "10")
+            .header("content-type", "application/json")
+            .body(json!({"hello": "world"}).to_string())
+            .filter(&to_event())
+            .await
+            .unwrap();
+
+        assert_eq!(expected_event(), result);
+    }
+
+    #[tokio::test]
+    async fn test_structured_request() {
+        let expected = expected_event();
+
+        let result = warp::test::request()
+            .method("POST")
+            .header("content-type", "application/cloudevents+json")
+            .body(serde_json::to_vec(&expected).unwrap())
+            .filter(&to_event())
+            .await
+            .unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[tokio::test]
+    async fn test_batch_request() {
+        let expected = Batch::from(vec![expected_event(), expected_event()]);
+
+        let result = warp::test::request()
+            .method("POST")
+            .header("content-type", "application/cloudevents-batch+json")
+            .body(serde_json::to_vec(&expected).unwrap())
+            .filter(&to_batch())
+            .await
+            .unwrap();
+
+        assert_eq!(expected, result);
+    }
+
+    #[tokio::test]
+    async fn test_bad_request() {
+        let routes = to_event().map(|_| StatusCode::OK).recover(handle_rejection);
+
+        let response = warp::test::request()
+            .method("POST")
+            .header("content-type", "application/json")
+            .body(json!({"hello": "world"}).to_string())
+            .reply(&routes)
+            .await;
+
+        assert_eq!(StatusCode::BAD_REQUEST, response.status());
+    }
+
+    #[tokio::test]
+    async fn test_webhook_validation() {
+        let filter = webhook_validation(WebhookValidator::new().allow_origin("example.com"));
+
+        let response = warp::test::request()
+            .method("OPTIONS")
+            .header("webhook-request-origin", "example.com")
+            .reply(&filter)
+            .await;
+        assert_eq!(StatusCode::OK, response.status());
+        assert_eq!("example.com", response.headers()["webhook-allowed-origin"]);
+
+        let response = warp::test::request()
+            .method("OPTIONS")
+            .header("webhook-request-origin", "other.com")
+            .reply(&filter)
+            .await;
+        assert_eq!(StatusCode::FORBIDDEN, response.status());
+
+        assert!(
+            !warp::test::request()
+                .method("OPTIONS")
+                .matches(&filter)
+                .await
+        );
+    }
+}
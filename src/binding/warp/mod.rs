@@ -0,0 +1,20 @@
+//! Integration with [warp](https://github.com/seanmonstar/warp), built on top of the
+//! [`http`](super::http) binding.
+//!
+//! [`filter::to_event`] extracts an [`Event`](crate::Event) from both binary and structured mode
+//! requests, while [`reply::from_event`] writes an [`Event`](crate::Event) as a binary mode response:
+//!
+//! ```
+//! use cloudevents::binding::warp::{filter, reply};
+//! use warp::Filter;
+//!
+//! let routes = warp::any()
+//!     .and(filter::to_event())
+//!     .map(|event| reply::from_event(event))
+//!     .recover(filter::handle_rejection);
+//! ```
+//!
+//! [`filter::to_batch`] and [`reply::from_batch`] do the same for batched mode messages.
+
+pub mod filter;
+pub mod reply;
@@ -0,0 +1,81 @@
+use super::super::http::{batch_to_message, event_to_binary};
+use crate::message::Result;
+use crate::{Batch, Event};
+use warp::http::{HeaderMap, StatusCode};
+use warp::reply::Response;
+use warp::Reply;
+
+/// Create a binary mode [`Response`] from an [`Event`],
+/// replying with `500 Internal Server Error` if the event cannot be serialized
+pub fn from_event(event: Event) -> Response {
+    from_message(event_to_binary(event))
+}
+
+/// Create a batched mode [`Response`] from a [`Batch`],
+/// replying with `500 Internal Server Error` if the batch cannot be serialized
+pub fn from_batch(batch: Batch) -> Response {
+    from_message(batch_to_message(batch))
+}
+
+fn from_message(message: Result<(HeaderMap, Vec<u8>)>) -> Response {
+    match message {
+        Ok((headers, body)) => {
+            let mut response = Response::new(body.into());
+            *response.headers_mut() = headers;
+            response
+        }
+        Err(e) => warp::reply::with_status(e.to_string(), StatusCode::INTERNAL_SERVER_ERROR)
+            .into_response(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::binding::http::{to_batch, to_event};
+    use crate::{EventBuilder, EventBuilderV10};
+    use serde_json::json;
+    use warp::hyper::body::to_bytes;
+
+    #[tokio::test]
+    async fn test_from_event() {
+        let expected = EventBuilderV10::new()
+            .id("0001")
+            .ty("example.test")
+            .source("http://localhost/")
+            .data("application/json", json!({"hello": "world"}))
+            .extension("someint", "10")
+            .build()
+            .unwrap();
+
+        let response = from_event(expected.clone());
+        assert_eq!(StatusCode::OK, response.status());
+
+        let headers = response.headers().clone();
+        let body = to_bytes(response.into_body()).await.unwrap();
+
+        assert_eq!(expected, to_event(&headers, body).unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_from_batch() {
+        let event = EventBuilderV10::new()
+            .id("0001")
+            .ty("example.test")
+            .source("http://localhost/")
+            .build()
+            .unwrap();
+        let expected = Batch::from(vec![event.clone(), event]);
+
+        let response = from_batch(expected.clone());
+        assert_eq!(
+            "application/cloudevents-batch+json",
+            response.headers()["content-type"]
+        );
+
+        let headers = response.headers().clone();
+        let body = to_bytes(response.into_body()).await.unwrap();
+
+        assert_eq!(expected, to_batch(&headers, body).unwrap());
+    }
+}
@@ -0,0 +1,190 @@
+//! [WebSocket Protocol Binding](https://github.com/cloudevents/spec/blob/main/cloudevents/bindings/websockets-protocol-binding.md),
+//! enabled with the `websocket` feature.
+//!
+//! The client offers the [`Subprotocol`]s it supports in the `Sec-WebSocket-Protocol` header,
+//! and the server picks one with [`Subprotocol::negotiate`]. Each event is then sent in its own
+//! message, in structured mode: a text message with the JSON event format for
+//! `cloudevents.json`, and a binary message with the Protobuf or Avro event format for
+//! `cloudevents.proto` and `cloudevents.avro`.
+//!
+//! The binding is independent of the WebSocket library: the messages are [`Frame`]s, and
+//! [`event_stream`] and [`event_sink`] adapt any stream and sink of frames, e.g. a
+//! [tokio-tungstenite](https://docs.rs/tokio-tungstenite) `WebSocketStream`:
+//!
+//! ```ignore
+//! use cloudevents::binding::websocket::{event_stream, Frame, Subprotocol};
+//! use futures::{future, StreamExt};
+//! use tokio_tungstenite::tungstenite::Message;
+//!
+//! let frames = websocket.filter_map(|message| {
+//!     future::ready(match message {
+//!         Ok(Message::Text(text)) => Some(Ok(Frame::Text(text))),
+//!         Ok(Message::Binary(bytes)) => Some(Ok(Frame::Binary(bytes))),
+//!         Ok(_) => None,
+//!         Err(e) => Some(Err(e)),
+//!     })
+//! });
+//! let mut events = event_stream(frames, Subprotocol::Json);
+//! ```
+
+mod stream;
+
+pub use stream::{event_sink, event_stream};
+
+use crate::message::{Error, Result};
+use crate::Event;
+
+/// Name of the `Sec-WebSocket-Protocol` header, carrying the offered and the selected
+/// subprotocols
+pub const SUBPROTOCOL_HEADER: &str = "Sec-WebSocket-Protocol";
+
+/// WebSocket subprotocol selecting the event format of the messages.
+///
+/// The variants depend on the enabled features, so matching on them requires a wildcard arm.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Subprotocol {
+    /// `cloudevents.json`, with the JSON event format in text messages
+    Json,
+    /// `cloudevents.proto`, with the Protobuf event format in binary messages, enabled with the
+    /// `protobuf` feature
+    #[cfg(feature = "protobuf")]
+    Protobuf,
+    /// `cloudevents.avro`, with the Avro event format in binary messages, enabled with the
+    /// `avro` feature
+    #[cfg(feature = "avro")]
+    Avro,
+}
+
+impl Subprotocol {
+    /// The subprotocols supported with the enabled features, in order of preference
+    pub const SUPPORTED: &'static [Subprotocol] = &[
+        #[cfg(feature = "protobuf")]
+        Subprotocol::Protobuf,
+        #[cfg(feature = "avro")]
+        Subprotocol::Avro,
+        Subprotocol::Json,
+    ];
+
+    /// Get the name of the subprotocol
+    pub fn name(&self) -> &'static str {
+        match self {
+            Subprotocol::Json => "cloudevents.json",
+            #[cfg(feature = "protobuf")]
+            Subprotocol::Protobuf => "cloudevents.proto",
+            #[cfg(feature = "avro")]
+            Subprotocol::Avro => "cloudevents.avro",
+        }
+    }
+
+    /// Get the subprotocol named `name`, if supported
+    pub fn from_name(name: &str) -> Option<Subprotocol> {
+        Subprotocol::SUPPORTED
+            .iter()
+            .copied()
+            .find(|subprotocol| subprotocol.name().eq_ignore_ascii_case(name.trim()))
+    }
+
+    /// Get the value of the `Sec-WebSocket-Protocol` header offering the supported subprotocols
+    pub fn offer() -> String {
+        Subprotocol::SUPPORTED
+            .iter()
+            .map(Subprotocol::name)
+            .collect::<Vec<&str>>()
+            .join(", ")
+    }
+
+    /// Select the first supported subprotocol of the comma separated list `offered` by the
+    /// client in the `Sec-WebSocket-Protocol` header, which is listed in order of preference.
+    ///
+    /// ```
+    /// use cloudevents::binding::websocket::Subprotocol;
+    ///
+    /// assert_eq!(
+    ///     Some(Subprotocol::Json),
+    ///     Subprotocol::negotiate("mqtt, cloudevents.json")
+    /// );
+    /// assert_eq!(None, Subprotocol::negotiate("mqtt"));
+    /// ```
+    pub fn negotiate(offered: &str) -> Option<Subprotocol> {
+        offered.split(',').find_map(Subprotocol::from_name)
+    }
+
+    /// Encode `event` in a message with the event format of the subprotocol
+    pub fn encode(&self, event: Event) -> Result<Frame> {
+        match self {
+            Subprotocol::Json => Ok(Frame::Text(serde_json::to_string(&event)?)),
+            #[cfg(feature = "protobuf")]
+            Subprotocol::Protobuf => Ok(Frame::Binary(crate::proto::encode_event(event))),
+            #[cfg(feature = "avro")]
+            Subprotocol::Avro => crate::avro::encode_event(event).map(Frame::Binary),
+        }
+    }
+
+    /// Decode the event of a message with the event format of the subprotocol, failing with
+    /// [`Error::WrongEncoding`] if the message is not of the expected type
+    pub fn decode(&self, frame: Frame) -> Result<Event> {
+        match (self, frame) {
+            (Subprotocol::Json, Frame::Text(text)) => crate::event::parse_json(text.into_bytes()),
+            #[cfg(feature = "protobuf")]
+            (Subprotocol::Protobuf, Frame::Binary(bytes)) => crate::proto::decode_event(&bytes),
+            #[cfg(feature = "avro")]
+            (Subprotocol::Avro, Frame::Binary(bytes)) => crate::avro::decode_event(&bytes),
+            _ => Err(Error::WrongEncoding {}),
+        }
+    }
+}
+
+/// Data message of a WebSocket connection
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Frame {
+    /// Text message, carrying UTF-8 text
+    Text(String),
+    /// Binary message
+    Binary(Vec<u8>),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EventBuilder, EventBuilderV10};
+    use serde_json::json;
+
+    fn event() -> Event {
+        EventBuilderV10::new()
+            .id("0001")
+            .source("http://localhost/")
+            .ty("example.test")
+            .data("application/json", json!({"hello": "world"}))
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn negotiate() {
+        assert_eq!(
+            Some(Subprotocol::Json),
+            Subprotocol::negotiate("cloudevents.xml,  CloudEvents.JSON ")
+        );
+        assert_eq!(None, Subprotocol::negotiate(""));
+        assert!(Subprotocol::offer().ends_with("cloudevents.json"));
+        for subprotocol in Subprotocol::SUPPORTED {
+            assert_eq!(
+                Some(*subprotocol),
+                Subprotocol::from_name(subprotocol.name())
+            );
+        }
+    }
+
+    #[test]
+    fn roundtrip() {
+        for subprotocol in Subprotocol::SUPPORTED {
+            let frame = subprotocol.encode(event()).unwrap();
+            assert_eq!(event(), subprotocol.decode(frame).unwrap());
+        }
+        assert!(matches!(
+            Subprotocol::Json.decode(Frame::Binary(vec![])),
+            Err(Error::WrongEncoding {})
+        ));
+    }
+}
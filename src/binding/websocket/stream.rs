@@ -0,0 +1,69 @@
+use super::{Frame, Subprotocol};
+use crate::message::{Error, EventSink, EventStream};
+use futures::{future, Sink, SinkExt, Stream, StreamExt};
+
+/// Get the [`EventStream`] of the events decoded from `frames` with `subprotocol`.
+///
+/// The errors of the connection are returned as [`Error::Other`], and the messages which can't
+/// be decoded as errors, without terminating the stream.
+pub fn event_stream<S, E>(frames: S, subprotocol: Subprotocol) -> impl EventStream
+where
+    S: Stream<Item = std::result::Result<Frame, E>>,
+    E: std::error::Error + Send + Sync + 'static,
+{
+    frames.map(move |frame| {
+        subprotocol.decode(frame.map_err(|e| Error::Other {
+            source: Box::new(e),
+        })?)
+    })
+}
+
+/// Get the [`EventSink`] encoding the events with `subprotocol` and sending them to `frames`.
+///
+/// The sink fails with [`Error::Other`] if the connection fails.
+pub fn event_sink<S, E>(frames: S, subprotocol: Subprotocol) -> impl EventSink
+where
+    S: Sink<Frame, Error = E>,
+    E: std::error::Error + Send + Sync + 'static,
+{
+    frames
+        .sink_map_err(|e| Error::Other {
+            source: Box::new(e),
+        })
+        .with(move |event| future::ready(subprotocol.encode(event)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Event, EventBuilder, EventBuilderV10};
+    use futures::channel::mpsc;
+
+    #[test]
+    fn stream_and_sink() {
+        let event = EventBuilderV10::new()
+            .id("0001")
+            .source("http://localhost/")
+            .ty("example.test")
+            .build()
+            .unwrap();
+
+        futures::executor::block_on(async {
+            let (sender, receiver) = mpsc::unbounded::<Frame>();
+            let mut sink = Box::pin(event_sink(sender, Subprotocol::Json));
+            sink.send(event.clone()).await.unwrap();
+            sink.close().await.unwrap();
+
+            let frames = receiver
+                .map(Ok::<Frame, std::io::Error>)
+                .chain(futures::stream::once(future::ready(Ok(Frame::Text(
+                    String::from("not an event"),
+                )))));
+            let events: Vec<crate::message::Result<Event>> =
+                event_stream(frames, Subprotocol::Json).collect().await;
+            assert_eq!(2, events.len());
+            assert_eq!(&event, events[0].as_ref().unwrap());
+            assert!(events[1].is_err());
+        });
+    }
+}
@@ -0,0 +1,495 @@
+//! [CBOR](https://www.rfc-editor.org/rfc/rfc8949) structured format of an [`Event`], enabled
+//! with the `cbor` feature, e.g. for IoT and constrained clients.
+//!
+//! The event is encoded with the same fields as the JSON event format, as a CBOR map. The
+//! decoder accepts the indefinite-length strings, arrays and maps, the half-precision floats,
+//! and ignores the tags.
+//!
+//! The format plugs into the structured mode of the bindings through the same
+//! [`StructuredSerializer`] trait as the JSON format: [`CborEvent`] writes an event in CBOR to
+//! any [`StructuredSerializer`], and [`CborEventSerializer`] parses a CBOR structured mode message.
+//!
+//! ```
+//! use cloudevents::cbor::{CborEvent, CborEventSerializer};
+//! use cloudevents::message::{StructuredDeserializer, StructuredSerializer};
+//! use cloudevents::{EventBuilder, EventBuilderV10};
+//!
+//! let event = EventBuilderV10::new()
+//!     .id("0001")
+//!     .source("http://localhost/")
+//!     .ty("example.demo")
+//!     .build()
+//!     .unwrap();
+//!
+//! /// Structured mode message collecting the payload
+//! struct Payload;
+//!
+//! impl StructuredSerializer<Vec<u8>> for Payload {
+//!     fn set_structured_event(self, bytes: Vec<u8>) -> cloudevents::message::Result<Vec<u8>> {
+//!         Ok(bytes)
+//!     }
+//! }
+//!
+//! let payload = CborEvent(event.clone()).deserialize_structured(Payload).unwrap();
+//! assert_eq!(event, CborEventSerializer.set_structured_event(payload).unwrap());
+//! ```
+
+use crate::message::{Error, EventFormat, Result, StructuredDeserializer, StructuredSerializer};
+use crate::Event;
+use serde::Deserialize;
+use serde_value::Value;
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+
+/// Content type of the CBOR structured format
+pub const CLOUDEVENTS_CBOR_CONTENT_TYPE: &str = "application/cloudevents+cbor";
+
+/// [`EventFormat`] of the CBOR structured format, to register in the
+/// [`EventFormats`](crate::message::EventFormats) read by the bindings
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CborFormat;
+
+impl EventFormat for CborFormat {
+    fn content_type(&self) -> &str {
+        CLOUDEVENTS_CBOR_CONTENT_TYPE
+    }
+
+    fn encode(&self, event: &Event) -> Result<Vec<u8>> {
+        encode_event(event)
+    }
+
+    fn decode(&self, bytes: &[u8]) -> Result<Event> {
+        decode_event(bytes)
+    }
+}
+
+macro_rules! cbor_error {
+    ($($arg:tt)*) => {
+        Error::Other {
+            source: format!($($arg)*).into(),
+        }
+    };
+}
+
+/// Encode an [`Event`] with the CBOR structured format
+pub fn encode_event(event: &Event) -> Result<Vec<u8>> {
+    let value = serde_value::to_value(event).map_err(|e| cbor_error!("{}", e))?;
+    let mut out = Vec::new();
+    write_value(&mut out, &value);
+    Ok(out)
+}
+
+/// Decode an [`Event`] encoded with the CBOR structured format
+pub fn decode_event(bytes: &[u8]) -> Result<Event> {
+    let mut reader = Reader { bytes, position: 0 };
+    let value = reader.read_value(0)?;
+    if reader.position != bytes.len() {
+        return Err(cbor_error!(
+            "Trailing bytes after the event at offset {}",
+            reader.position
+        ));
+    }
+    Event::deserialize(value).map_err(|e| cbor_error!("{}", e))
+}
+
+/// [`StructuredDeserializer`] writing the wrapped [`Event`] in the CBOR structured format
+#[derive(Debug, Clone, PartialEq)]
+pub struct CborEvent(pub Event);
+
+impl StructuredDeserializer for CborEvent {
+    fn deserialize_structured<R: Sized, V: StructuredSerializer<R>>(
+        self,
+        serializer: V,
+    ) -> Result<R> {
+        serializer.set_structured_event(encode_event(&self.0)?)
+    }
+}
+
+/// [`StructuredSerializer`] parsing a structured mode message in the CBOR format into an [`Event`]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CborEventSerializer;
+
+impl StructuredSerializer<Event> for CborEventSerializer {
+    fn set_structured_event(self, bytes: Vec<u8>) -> Result<Event> {
+        decode_event(&bytes)
+    }
+}
+
+const UNSIGNED: u8 = 0;
+const NEGATIVE: u8 = 1;
+const BYTES: u8 = 2;
+const TEXT: u8 = 3;
+const ARRAY: u8 = 4;
+const MAP: u8 = 5;
+const TAG: u8 = 6;
+const SIMPLE: u8 = 7;
+
+/// Additional information of the indefinite-length items
+const INDEFINITE: u8 = 31;
+/// Terminator of the indefinite-length items
+const BREAK: u8 = 0xff;
+
+/// Write the head of an item of the `major` type with the `argument`
+fn write_head(out: &mut Vec<u8>, major: u8, argument: u64) {
+    let major = major << 5;
+    if argument < 24 {
+        out.push(major | argument as u8);
+    } else if argument <= u64::from(u8::MAX) {
+        out.extend_from_slice(&[major | 24, argument as u8]);
+    } else if argument <= u64::from(u16::MAX) {
+        out.push(major | 25);
+        out.extend_from_slice(&(argument as u16).to_be_bytes());
+    } else if argument <= u64::from(u32::MAX) {
+        out.push(major | 26);
+        out.extend_from_slice(&(argument as u32).to_be_bytes());
+    } else {
+        out.push(major | 27);
+        out.extend_from_slice(&argument.to_be_bytes());
+    }
+}
+
+fn write_signed(out: &mut Vec<u8>, n: i64) {
+    if n >= 0 {
+        write_head(out, UNSIGNED, n as u64);
+    } else {
+        // -1 - n, without overflowing on i64::MIN
+        write_head(out, NEGATIVE, !n as u64);
+    }
+}
+
+fn write_value(out: &mut Vec<u8>, value: &Value) {
+    match value {
+        Value::Unit | Value::Option(None) => out.push(0xf6),
+        Value::Bool(false) => out.push(0xf4),
+        Value::Bool(true) => out.push(0xf5),
+        Value::U8(n) => write_head(out, UNSIGNED, u64::from(*n)),
+        Value::U16(n) => write_head(out, UNSIGNED, u64::from(*n)),
+        Value::U32(n) => write_head(out, UNSIGNED, u64::from(*n)),
+        Value::U64(n) => write_head(out, UNSIGNED, *n),
+        Value::I8(n) => write_signed(out, i64::from(*n)),
+        Value::I16(n) => write_signed(out, i64::from(*n)),
+        Value::I32(n) => write_signed(out, i64::from(*n)),
+        Value::I64(n) => write_signed(out, *n),
+        Value::F32(n) => {
+            out.push(0xfa);
+            out.extend_from_slice(&n.to_be_bytes());
+        }
+        Value::F64(n) => {
+            out.push(0xfb);
+            out.extend_from_slice(&n.to_be_bytes());
+        }
+        Value::Char(c) => write_text(out, c.encode_utf8(&mut [0; 4])),
+        Value::String(s) => write_text(out, s),
+        Value::Bytes(b) => {
+            write_head(out, BYTES, b.len() as u64);
+            out.extend_from_slice(b);
+        }
+        Value::Option(Some(v)) | Value::Newtype(v) => write_value(out, v),
+        Value::Seq(values) => {
+            write_head(out, ARRAY, values.len() as u64);
+            for v in values {
+                write_value(out, v);
+            }
+        }
+        Value::Map(entries) => {
+            write_head(out, MAP, entries.len() as u64);
+            for (k, v) in entries {
+                write_value(out, k);
+                write_value(out, v);
+            }
+        }
+    }
+}
+
+fn write_text(out: &mut Vec<u8>, s: &str) {
+    write_head(out, TEXT, s.len() as u64);
+    out.extend_from_slice(s.as_bytes());
+}
+
+/// Convert a half-precision float to a single-precision float
+fn f16_to_f32(half: u16) -> f32 {
+    let exponent = (half >> 10) & 0x1f;
+    let mantissa = f32::from(half & 0x3ff);
+    let magnitude = match exponent {
+        0 => mantissa * 2f32.powi(-24),
+        31 if mantissa == 0.0 => f32::INFINITY,
+        31 => f32::NAN,
+        e => (1024.0 + mantissa) * 2f32.powi(i32::from(e) - 25),
+    };
+    if half & 0x8000 != 0 {
+        -magnitude
+    } else {
+        magnitude
+    }
+}
+
+/// Maximum nesting of arrays, maps and tags, to bound the recursion on untrusted input
+const MAX_DEPTH: usize = 128;
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: u64) -> Result<&'a [u8]> {
+        let end = usize::try_from(len)
+            .ok()
+            .and_then(|len| self.position.checked_add(len))
+            .filter(|end| *end <= self.bytes.len())
+            .ok_or_else(|| cbor_error!("Unexpected end of input at offset {}", self.position))?;
+        let bytes = &self.bytes[self.position..end];
+        self.position = end;
+        Ok(bytes)
+    }
+
+    fn read<const N: usize>(&mut self) -> Result<[u8; N]> {
+        let mut array = [0; N];
+        array.copy_from_slice(self.take(N as u64)?);
+        Ok(array)
+    }
+
+    /// Returns `true` and consumes the break if the next byte terminates an indefinite item
+    fn at_break(&mut self) -> Result<bool> {
+        match self.bytes.get(self.position) {
+            Some(&BREAK) => {
+                self.position += 1;
+                Ok(true)
+            }
+            Some(_) => Ok(false),
+            None => Err(cbor_error!(
+                "Unexpected end of input at offset {}",
+                self.position
+            )),
+        }
+    }
+
+    /// Read the argument of the head with the additional information `info`, or `None` for the
+    /// indefinite-length items
+    fn read_argument(&mut self, info: u8) -> Result<Option<u64>> {
+        Ok(Some(match info {
+            0..=23 => u64::from(info),
+            24 => u64::from(self.read::<1>()?[0]),
+            25 => u64::from(u16::from_be_bytes(self.read()?)),
+            26 => u64::from(u32::from_be_bytes(self.read()?)),
+            27 => u64::from_be_bytes(self.read()?),
+            INDEFINITE => return Ok(None),
+            _ => return Err(cbor_error!("Invalid additional information {}", info)),
+        }))
+    }
+
+    /// Read the content of a byte or text string of the `major` type
+    fn read_string(&mut self, major: u8, argument: Option<u64>) -> Result<Vec<u8>> {
+        match argument {
+            Some(len) => Ok(self.take(len)?.to_vec()),
+            None => {
+                let mut content = Vec::new();
+                while !self.at_break()? {
+                    let head = self.read::<1>()?[0];
+                    let len = match self.read_argument(head & 0x1f)? {
+                        Some(len) if head >> 5 == major => len,
+                        _ => return Err(cbor_error!("Invalid chunk of indefinite-length string")),
+                    };
+                    content.extend_from_slice(self.take(len)?);
+                }
+                Ok(content)
+            }
+        }
+    }
+
+    fn read_value(&mut self, depth: usize) -> Result<Value> {
+        if depth > MAX_DEPTH {
+            return Err(cbor_error!("Values nested deeper than {}", MAX_DEPTH));
+        }
+        let head = self.read::<1>()?[0];
+        let (major, info) = (head >> 5, head & 0x1f);
+        if major == SIMPLE {
+            return self.read_simple(info);
+        }
+        let argument = self.read_argument(info)?;
+        Ok(match (major, argument) {
+            (UNSIGNED, Some(n)) => Value::U64(n),
+            (NEGATIVE, Some(n)) => match i64::try_from(n) {
+                Ok(n) => Value::I64(-1 - n),
+                Err(_) => return Err(cbor_error!("Negative integer out of range")),
+            },
+            (BYTES, len) => Value::Bytes(self.read_string(BYTES, len)?),
+            (TEXT, len) => {
+                let content = self.read_string(TEXT, len)?;
+                Value::String(String::from_utf8(content).map_err(|e| cbor_error!("{}", e))?)
+            }
+            (ARRAY, len) => {
+                let mut values = Vec::new();
+                while !self.at_end(len, values.len())? {
+                    values.push(self.read_value(depth + 1)?);
+                }
+                Value::Seq(values)
+            }
+            (MAP, len) => {
+                let (mut entries, mut read) = (BTreeMap::new(), 0);
+                while !self.at_end(len, read)? {
+                    let key = self.read_value(depth + 1)?;
+                    if entries.contains_key(&key) {
+                        return Err(cbor_error!("Duplicate map key"));
+                    }
+                    entries.insert(key, self.read_value(depth + 1)?);
+                    read += 1;
+                }
+                Value::Map(entries)
+            }
+            (TAG, Some(_)) => self.read_value(depth + 1)?,
+            _ => return Err(cbor_error!("Invalid indefinite-length item")),
+        })
+    }
+
+    /// Returns `true` when the `read` elements of an array or map of `len` elements are read
+    fn at_end(&mut self, len: Option<u64>, read: usize) -> Result<bool> {
+        match len {
+            Some(len) => Ok(read as u64 >= len),
+            None => self.at_break(),
+        }
+    }
+
+    fn read_simple(&mut self, info: u8) -> Result<Value> {
+        Ok(match info {
+            20 => Value::Bool(false),
+            21 => Value::Bool(true),
+            22 | 23 => Value::Unit,
+            25 => Value::F32(f16_to_f32(u16::from_be_bytes(self.read()?))),
+            26 => Value::F32(f32::from_be_bytes(self.read()?)),
+            27 => Value::F64(f64::from_be_bytes(self.read()?)),
+            _ => return Err(cbor_error!("Unsupported simple value {}", info)),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EventBuilder, EventBuilderV03, EventBuilderV10};
+    use chrono::{TimeZone, Utc};
+    use serde_json::json;
+
+    #[test]
+    fn roundtrip() {
+        let events = vec![
+            EventBuilderV10::new()
+                .id("0001")
+                .source("http://localhost/")
+                .ty("example.test")
+                .time(Utc.timestamp(1_600_000_000, 0))
+                .extension("someint", -1_000_000)
+                .extension("somebool", true)
+                .data(
+                    "application/json",
+                    json!({"n": [0, 24, 70000, -1, -200, 5_000_000_000u64, 1.5, null]}),
+                )
+                .build()
+                .unwrap(),
+            EventBuilderV10::new()
+                .id("0002")
+                .source("http://localhost/")
+                .ty("example.test")
+                .data("application/octet-stream", vec![1u8, 2, 3])
+                .build()
+                .unwrap(),
+            EventBuilderV03::new()
+                .id("0003")
+                .source("http://localhost/")
+                .ty("example.test")
+                .data("text/plain", "hello")
+                .build()
+                .unwrap(),
+        ];
+
+        for event in events {
+            let bytes = encode_event(&event).unwrap();
+            assert_eq!(event, decode_event(&bytes).unwrap());
+        }
+    }
+
+    #[test]
+    fn encoding() {
+        let mut out = Vec::new();
+        for n in &[10i64, 100, 1000, -1, -1000, i64::MIN] {
+            write_signed(&mut out, *n);
+        }
+        write_text(&mut out, "a");
+        assert_eq!(
+            vec![
+                0x0a, 0x18, 0x64, 0x19, 0x03, 0xe8, 0x20, 0x39, 0x03, 0xe7, 0x3b, 0x7f, 0xff, 0xff,
+                0xff, 0xff, 0xff, 0xff, 0xff, 0x61, b'a'
+            ],
+            out
+        );
+    }
+
+    #[test]
+    fn decode_indefinite_and_tagged_items() {
+        // {_ "specversion": "1.0", "id": (_ "00", "01"), "source": 32("http://localhost/"),
+        //  "type": "example.test", "datacontenttype": "application/json", "data": [_ 1.0 (half)]}
+        let mut bytes = vec![0xbf];
+        write_text(&mut bytes, "specversion");
+        write_text(&mut bytes, "1.0");
+        write_text(&mut bytes, "id");
+        bytes.push(0x7f);
+        write_text(&mut bytes, "00");
+        write_text(&mut bytes, "01");
+        bytes.push(BREAK);
+        write_text(&mut bytes, "source");
+        bytes.extend_from_slice(&[0xd8, 0x20]);
+        write_text(&mut bytes, "http://localhost/");
+        write_text(&mut bytes, "type");
+        write_text(&mut bytes, "example.test");
+        write_text(&mut bytes, "datacontenttype");
+        write_text(&mut bytes, "application/json");
+        write_text(&mut bytes, "data");
+        bytes.extend_from_slice(&[0x9f, 0xf9, 0x3c, 0x00, BREAK, BREAK]);
+
+        let expected = EventBuilderV10::new()
+            .id("0001")
+            .source("http://localhost/")
+            .ty("example.test")
+            .data("application/json", json!([1.0]))
+            .build()
+            .unwrap();
+        assert_eq!(expected, decode_event(&bytes).unwrap());
+    }
+
+    #[test]
+    fn invalid_input() {
+        let event = EventBuilderV10::new()
+            .id("0001")
+            .source("http://localhost/")
+            .ty("example.test")
+            .build()
+            .unwrap();
+        let bytes = encode_event(&event).unwrap();
+
+        assert!(decode_event(&bytes[..bytes.len() - 1]).is_err());
+        assert!(decode_event(&[bytes.as_slice(), &[0xf6]].concat()).is_err());
+        assert!(decode_event(&[0x1c]).is_err());
+        assert!(decode_event(&[0x81; 1000]).is_err());
+        assert!(decode_event(&[0x9b, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff]).is_err());
+        assert!(decode_event(&[0x7f, 0x41, 0x00, BREAK]).is_err());
+        // {"id": "0001", "id": "0002"}
+        let duplicate = [
+            0xa2, 0x62, b'i', b'd', 0x64, b'0', b'0', b'0', b'1', 0x62, b'i', b'd', 0x64, b'0',
+            b'0', b'0', b'2',
+        ];
+        assert!(decode_event(&duplicate)
+            .unwrap_err()
+            .to_string()
+            .contains("Duplicate map key"));
+    }
+
+    #[test]
+    fn half_floats() {
+        assert_eq!(1.0, f16_to_f32(0x3c00));
+        assert_eq!(-2.0, f16_to_f32(0xc000));
+        assert_eq!(65504.0, f16_to_f32(0x7bff));
+        assert_eq!(5.960_464_5e-8, f16_to_f32(0x0001));
+        assert_eq!(f32::INFINITY, f16_to_f32(0x7c00));
+        assert!(f16_to_f32(0x7e00).is_nan());
+    }
+}
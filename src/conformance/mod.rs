@@ -0,0 +1,553 @@
+//! Loader of the [CloudEvents conformance](https://github.com/cloudevents/conformance) test
+//! vectors, enabled with the `conformance` feature, checking that the SDK produces the wire
+//! output the specifications require.
+//!
+//! A test vector describes an event with the same YAML layout as the conformance tool:
+//!
+//! ```
+//! use cloudevents::conformance;
+//!
+//! let vectors = conformance::from_yaml(
+//!     r#"
+//! Mode: binary
+//! ContextAttributes:
+//!   specversion: "1.0"
+//!   type: com.example.someevent
+//!   id: "1234-1234-1234"
+//!   source: /mycontext/subcontext
+//!   datacontenttype: application/json
+//!   Extensions:
+//!     comexampleextension1: value
+//! Data: '{"hello": "world"}'
+//! ---
+//! Mode: structured
+//! ContextAttributes:
+//!   specversion: "1.0"
+//!   type: com.example.someevent
+//!   id: "1234-1234-1234"
+//!   source: /mycontext/subcontext
+//!   time: "2018-04-05T17:31:00Z"
+//! "#,
+//! )
+//! .unwrap();
+//!
+//! for vector in &vectors {
+//!     conformance::assert_http(vector).unwrap();
+//! }
+//! ```
+
+use crate::binding::http::{event_to_binary, event_to_structured};
+use crate::event::{is_json_content_type, Data, SpecVersion};
+use crate::message::{self, BinaryDeserializer, BinarySerializer, MessageAttributeValue};
+use crate::Event;
+use chrono::{DateTime, SecondsFormat};
+use http::header::HeaderMap;
+use serde::{Deserialize, Serialize};
+use snafu::Snafu;
+use std::collections::{BTreeMap, BTreeSet};
+use std::convert::TryFrom;
+use std::path::Path;
+
+const CLOUDEVENTS_JSON_CONTENT_TYPE: &str = "application/cloudevents+json";
+
+/// Represents an error while loading or checking a [`TestVector`]
+#[derive(Debug, Snafu)]
+#[non_exhaustive]
+pub enum Error {
+    #[snafu(display("Error while reading the test vectors: {}", source))]
+    #[snafu(context(false))]
+    Io { source: std::io::Error },
+    #[snafu(display("Error while parsing the JSON test vectors: {}", source))]
+    #[snafu(context(false))]
+    Json { source: serde_json::Error },
+    #[snafu(display("Error while parsing the YAML test vectors: {}", source))]
+    #[snafu(context(false))]
+    Yaml { source: serde_yaml::Error },
+    #[snafu(display("Invalid test vector: {}", source))]
+    InvalidVector { source: message::Error },
+    #[snafu(display("Error while serializing the event: {}", source))]
+    Serialization { source: message::Error },
+    #[snafu(display("Wire output doesn't conform to the test vector:\n{}", differences.join("\n")))]
+    Mismatch { differences: Vec<String> },
+}
+
+/// Content mode a [`TestVector`] is transferred with
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Mode {
+    #[default]
+    Binary,
+    Structured,
+}
+
+/// Event described by a conformance test vector
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TestVector {
+    #[serde(rename = "Mode", default)]
+    pub mode: Mode,
+    #[serde(rename = "ContextAttributes")]
+    pub attributes: ContextAttributes,
+    /// Data of the event, parsed as JSON when the `datacontenttype` is a JSON content type
+    #[serde(rename = "Data", default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<String>,
+}
+
+/// Context attributes and extensions of a [`TestVector`], in their string representation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ContextAttributes {
+    pub specversion: String,
+    #[serde(rename = "type")]
+    pub ty: String,
+    pub id: String,
+    pub source: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub datacontenttype: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dataschema: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schemaurl: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subject: Option<String>,
+    #[serde(
+        rename = "Extensions",
+        default,
+        skip_serializing_if = "BTreeMap::is_empty"
+    )]
+    pub extensions: BTreeMap<String, String>,
+}
+
+impl ContextAttributes {
+    /// Iterate over the context attributes which are set, by name, without the extensions
+    pub fn iter(&self) -> impl Iterator<Item = (&'static str, &str)> {
+        vec![
+            ("specversion", Some(&self.specversion)),
+            ("id", Some(&self.id)),
+            ("type", Some(&self.ty)),
+            ("source", Some(&self.source)),
+            ("datacontenttype", self.datacontenttype.as_ref()),
+            ("dataschema", self.dataschema.as_ref()),
+            ("schemaurl", self.schemaurl.as_ref()),
+            ("subject", self.subject.as_ref()),
+            ("time", self.time.as_ref()),
+        ]
+        .into_iter()
+        .filter_map(|(name, value)| value.map(|v| (name, v.as_str())))
+    }
+}
+
+/// HTTP message as headers, with lowercase names, and body
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct HttpMessage {
+    pub headers: BTreeMap<String, String>,
+    pub body: Vec<u8>,
+}
+
+impl HttpMessage {
+    /// Create a new [`HttpMessage`] from the output of the [`http`](crate::binding::http)
+    /// binding serializers
+    pub fn from_parts(headers: &HeaderMap, body: Vec<u8>) -> Result<Self, Error> {
+        let headers = headers
+            .iter()
+            .map(|(name, value)| {
+                let value = value.to_str().map_err(|e| Error::Serialization {
+                    source: message::Error::Other {
+                        source: Box::new(e),
+                    },
+                })?;
+                Ok((name.as_str().to_string(), value.to_string()))
+            })
+            .collect::<Result<_, Error>>()?;
+        Ok(HttpMessage { headers, body })
+    }
+
+    fn content_type(&self) -> Option<&str> {
+        self.headers
+            .get("content-type")
+            .and_then(|ct| ct.split(';').next())
+            .map(str::trim)
+    }
+
+    /// Body parsed as JSON if the content type is a JSON one, with the `time` of a structured
+    /// event in its canonical representation
+    fn json_body(&self) -> Option<serde_json::Value> {
+        let content_type = self.content_type()?;
+        if !is_json_content_type(content_type) {
+            return None;
+        }
+        let mut body: serde_json::Value = serde_json::from_slice(&self.body).ok()?;
+        if content_type == CLOUDEVENTS_JSON_CONTENT_TYPE {
+            if let Some(time) = body.get_mut("time") {
+                if let Some(t) = time.as_str().and_then(canonical_time) {
+                    *time = serde_json::Value::String(t);
+                }
+            }
+        }
+        Some(body)
+    }
+}
+
+impl TestVector {
+    /// Build the [`Event`] described by this test vector
+    pub fn to_event(&self) -> Result<Event, Error> {
+        let mut event = BinaryDeserializer::into_event(self.clone())
+            .map_err(|source| Error::InvalidVector { source })?;
+        if let Some(data) = &self.data {
+            event.data = Some(if self.is_json_data() {
+                Data::Json(
+                    serde_json::from_str(data)
+                        .map_err(|e| Error::InvalidVector { source: e.into() })?,
+                )
+            } else {
+                Data::String(data.clone())
+            });
+        }
+        Ok(event)
+    }
+
+    /// Get the HTTP message the [HTTP protocol binding](https://github.com/cloudevents/spec/blob/main/cloudevents/bindings/http-protocol-binding.md)
+    /// requires for this test vector, in its [`Mode`]
+    pub fn expected_http(&self) -> HttpMessage {
+        match self.mode {
+            Mode::Binary => {
+                let headers = self
+                    .attributes
+                    .iter()
+                    .map(|(name, value)| (header_name(name), value.to_string()))
+                    .chain(
+                        self.attributes
+                            .extensions
+                            .iter()
+                            .map(|(name, value)| (header_name(name), value.clone())),
+                    )
+                    .collect();
+                let body = self.data.clone().unwrap_or_default().into_bytes();
+                HttpMessage { headers, body }
+            }
+            Mode::Structured => {
+                let mut body: serde_json::Map<String, serde_json::Value> = self
+                    .attributes
+                    .iter()
+                    .map(|(name, value)| (name.to_string(), value.into()))
+                    .chain(
+                        self.attributes
+                            .extensions
+                            .iter()
+                            .map(|(name, value)| (name.clone(), value.as_str().into())),
+                    )
+                    .collect();
+                if let Some(data) = &self.data {
+                    let data = match self.is_json_data() {
+                        true => serde_json::from_str(data).unwrap_or_else(|_| data.as_str().into()),
+                        false => data.as_str().into(),
+                    };
+                    body.insert(String::from("data"), data);
+                }
+                let mut headers = BTreeMap::new();
+                headers.insert(
+                    String::from("content-type"),
+                    String::from(CLOUDEVENTS_JSON_CONTENT_TYPE),
+                );
+                HttpMessage {
+                    headers,
+                    body: serde_json::to_vec(&body).expect("JSON object serialization failed"),
+                }
+            }
+        }
+    }
+
+    fn is_json_data(&self) -> bool {
+        is_json_content_type(
+            self.attributes
+                .datacontenttype
+                .as_deref()
+                .unwrap_or("application/json"),
+        )
+    }
+}
+
+impl BinaryDeserializer for TestVector {
+    fn deserialize_binary<R: Sized, V: BinarySerializer<R>>(
+        self,
+        mut visitor: V,
+    ) -> message::Result<R> {
+        let attributes = self.attributes;
+        visitor =
+            visitor.set_spec_version(SpecVersion::try_from(attributes.specversion.as_str())?)?;
+        for (name, value) in attributes.iter().filter(|(n, _)| *n != "specversion") {
+            visitor = visitor.set_attribute(name, MessageAttributeValue::from(value))?;
+        }
+        for (name, value) in attributes.extensions {
+            visitor = visitor.set_extension(&name, MessageAttributeValue::from(value))?;
+        }
+        visitor.end()
+    }
+}
+
+fn header_name(name: &str) -> String {
+    match name {
+        "datacontenttype" => String::from("content-type"),
+        name => format!("ce-{}", name.to_lowercase()),
+    }
+}
+
+fn canonical_time(time: &str) -> Option<String> {
+    DateTime::parse_from_rfc3339(time)
+        .ok()
+        .map(|t| t.to_rfc3339_opts(SecondsFormat::AutoSi, true))
+}
+
+/// Load the test vectors of a JSON document, either a single test vector or an array of them
+pub fn from_json(json: &str) -> Result<Vec<TestVector>, Error> {
+    Ok(serde_json::from_str::<OneOrMany>(json)?.into())
+}
+
+/// Load the test vectors of a YAML stream, where each document is either a single test vector
+/// or a sequence of them
+pub fn from_yaml(yaml: &str) -> Result<Vec<TestVector>, Error> {
+    let mut vectors = Vec::new();
+    for document in serde_yaml::Deserializer::from_str(yaml) {
+        vectors.extend(Vec::from(OneOrMany::deserialize(document)?));
+    }
+    Ok(vectors)
+}
+
+/// Load the test vectors of a file, parsed as JSON if its extension is `json`, as YAML otherwise
+pub fn load(path: impl AsRef<Path>) -> Result<Vec<TestVector>, Error> {
+    let path = path.as_ref();
+    let content = std::fs::read_to_string(path)?;
+    match path.extension().and_then(|e| e.to_str()) {
+        Some("json") => from_json(&content),
+        _ => from_yaml(&content),
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum OneOrMany {
+    One(Box<TestVector>),
+    Many(Vec<TestVector>),
+}
+
+impl From<OneOrMany> for Vec<TestVector> {
+    fn from(value: OneOrMany) -> Self {
+        match value {
+            OneOrMany::One(vector) => vec![*vector],
+            OneOrMany::Many(vectors) => vectors,
+        }
+    }
+}
+
+/// Check that the [`http`](crate::binding::http) binding serializes the event of `vector` to
+/// the HTTP message the specification requires, in the [`Mode`] of the vector.
+///
+/// Header names are compared case insensitively, times and JSON bodies semantically.
+pub fn assert_http(vector: &TestVector) -> Result<(), Error> {
+    let event = vector.to_event()?;
+    let (headers, body) = match vector.mode {
+        Mode::Binary => event_to_binary(event),
+        Mode::Structured => event_to_structured(event),
+    }
+    .map_err(|source| Error::Serialization { source })?;
+    let differences = diff(
+        &vector.expected_http(),
+        &HttpMessage::from_parts(&headers, body)?,
+    );
+    match differences.is_empty() {
+        true => Ok(()),
+        false => Err(Error::Mismatch { differences }),
+    }
+}
+
+fn diff(expected: &HttpMessage, actual: &HttpMessage) -> Vec<String> {
+    let names: BTreeSet<&String> = expected
+        .headers
+        .keys()
+        .chain(actual.headers.keys())
+        .collect();
+    let normalize = |name: &str, value: &String| match name {
+        "ce-time" => canonical_time(value).unwrap_or_else(|| value.clone()),
+        _ => value.clone(),
+    };
+
+    let mut differences = Vec::new();
+    for name in names {
+        let (e, a) = (expected.headers.get(name), actual.headers.get(name));
+        if e.map(|v| normalize(name, v)) != a.map(|v| normalize(name, v)) {
+            differences.push(format!("  {}: {} != {}", name, describe(e), describe(a)));
+        }
+    }
+    let body_matches = match (expected.json_body(), actual.json_body()) {
+        (Some(e), Some(a)) => e == a,
+        _ => expected.body == actual.body,
+    };
+    if !body_matches {
+        differences.push(format!(
+            "  body: `{}` != `{}`",
+            String::from_utf8_lossy(&expected.body),
+            String::from_utf8_lossy(&actual.body)
+        ));
+    }
+    differences
+}
+
+fn describe(value: Option<&String>) -> String {
+    match value {
+        Some(value) => format!("`{}`", value),
+        None => String::from("<missing>"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AttributesReader;
+    use serde_json::json;
+
+    const VECTORS: &str = r#"
+- Mode: binary
+  ContextAttributes:
+    specversion: "1.0"
+    type: com.example.someevent
+    id: "1234-1234-1234"
+    source: /mycontext/subcontext
+    subject: orders
+    time: "2018-04-05T17:31:00+00:00"
+    datacontenttype: application/json
+    Extensions:
+      comexampleextension1: value
+  Data: '{"hello": "world"}'
+- Mode: structured
+  ContextAttributes:
+    specversion: "0.3"
+    type: com.example.someevent
+    id: "1234-1234-1234"
+    source: /mycontext/subcontext
+    schemaurl: https://example.com/schema.json
+    datacontenttype: text/plain
+  Data: hello
+---
+Mode: structured
+ContextAttributes:
+  specversion: "1.0"
+  type: com.example.someevent
+  id: "1234-1234-1234"
+  source: /mycontext/subcontext
+  datacontenttype: application/json
+  Extensions:
+    comexampleextension1: value
+Data: '{"hello": "world"}'
+"#;
+
+    #[test]
+    fn load_yaml() {
+        let vectors = from_yaml(VECTORS).unwrap();
+        assert_eq!(3, vectors.len());
+        assert_eq!(Mode::Binary, vectors[0].mode);
+        assert_eq!(Some("orders"), vectors[0].attributes.subject.as_deref());
+        assert_eq!(Mode::Structured, vectors[2].mode);
+        assert_eq!(
+            Some(&String::from("value")),
+            vectors[2].attributes.extensions.get("comexampleextension1")
+        );
+    }
+
+    #[test]
+    fn load_json() {
+        let vector = json!({
+            "Mode": "structured",
+            "ContextAttributes": {
+                "specversion": "1.0",
+                "type": "com.example.someevent",
+                "id": "1234",
+                "source": "/mycontext"
+            }
+        });
+        assert_eq!(1, from_json(&vector.to_string()).unwrap().len());
+        assert_eq!(
+            2,
+            from_json(&json!([vector, vector]).to_string())
+                .unwrap()
+                .len()
+        );
+        assert!(from_json(&json!({"Mode": "binary"}).to_string()).is_err());
+    }
+
+    #[test]
+    fn to_event() {
+        let vectors = from_yaml(VECTORS).unwrap();
+
+        let event = vectors[0].to_event().unwrap();
+        assert_eq!(SpecVersion::V10, event.get_specversion());
+        assert_eq!("com.example.someevent", event.get_type());
+        assert!(event.get_time().is_some());
+        assert_eq!(
+            Some(&Data::Json(json!({"hello": "world"}))),
+            event.data.as_ref()
+        );
+
+        let event = vectors[1].to_event().unwrap();
+        assert_eq!(SpecVersion::V03, event.get_specversion());
+        assert_eq!(
+            Some(&Data::String(String::from("hello"))),
+            event.data.as_ref()
+        );
+    }
+
+    #[test]
+    fn expected_http() {
+        let vectors = from_yaml(VECTORS).unwrap();
+
+        let binary = vectors[0].expected_http();
+        assert_eq!(
+            Some(&String::from("application/json")),
+            binary.headers.get("content-type")
+        );
+        assert_eq!(
+            Some(&String::from("value")),
+            binary.headers.get("ce-comexampleextension1")
+        );
+        assert_eq!(br#"{"hello": "world"}"#.to_vec(), binary.body);
+
+        let structured = vectors[1].expected_http();
+        assert_eq!(
+            json!({
+                "specversion": "0.3",
+                "type": "com.example.someevent",
+                "id": "1234-1234-1234",
+                "source": "/mycontext/subcontext",
+                "schemaurl": "https://example.com/schema.json",
+                "datacontenttype": "text/plain",
+                "data": "hello"
+            }),
+            serde_json::from_slice::<serde_json::Value>(&structured.body).unwrap()
+        );
+    }
+
+    #[test]
+    fn http_conformance() {
+        for vector in from_yaml(VECTORS).unwrap() {
+            assert_http(&vector).unwrap();
+        }
+    }
+
+    #[test]
+    fn mismatch() {
+        let expected = from_yaml(VECTORS).unwrap()[0].expected_http();
+        let mut actual = expected.clone();
+        actual.headers.remove("ce-subject");
+        actual.headers.insert(
+            String::from("ce-time"),
+            String::from("2018-04-05T17:31:00Z"),
+        );
+        actual.body = br#"{"hello":"world"}"#.to_vec();
+        assert_eq!(
+            vec!["  ce-subject: `orders` != <missing>"],
+            diff(&expected, &actual)
+        );
+
+        actual.body = br#"{"hello":"rust"}"#.to_vec();
+        assert_eq!(2, diff(&expected, &actual).len());
+    }
+}
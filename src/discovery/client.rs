@@ -0,0 +1,173 @@
+use super::{Service, TypeEntry};
+use reqwest::{Client, RequestBuilder, StatusCode, Url};
+use serde::de::DeserializeOwned;
+use snafu::{ResultExt, Snafu};
+use std::collections::BTreeMap;
+
+/// Represents an error of a [`DiscoveryClient`] request
+#[derive(Debug, Snafu)]
+pub enum Error {
+    #[snafu(display("Discovery API request failed: {}", source))]
+    Request { source: reqwest::Error },
+    #[snafu(display("Discovery API replied {}: {}", status, body))]
+    Status { status: u16, body: String },
+    #[snafu(display("Invalid Discovery API response: {}", source))]
+    InvalidResponse { source: serde_json::Error },
+    #[snafu(display("Invalid resource name {}", name))]
+    InvalidName { name: String },
+}
+
+/// Client of a Discovery API endpoint, querying its `/services` and `/types` resources under a
+/// base URL. Enabled with the `reqwest` feature.
+///
+/// ```no_run
+/// use cloudevents::discovery::DiscoveryClient;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = DiscoveryClient::new(
+///     reqwest::Client::new(),
+///     "https://discovery.example.com/".parse()?,
+/// );
+///
+/// for service in client.services().await? {
+///     for event_type in &service.events {
+///         println!("{} emits {}", service.name, event_type.ty);
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct DiscoveryClient {
+    client: Client,
+    base: Url,
+}
+
+impl DiscoveryClient {
+    /// Create a new [`DiscoveryClient`] sending the requests with `client` to the Discovery API at
+    /// `base`
+    pub fn new(client: Client, base: Url) -> Self {
+        DiscoveryClient { client, base }
+    }
+
+    /// List the services
+    pub async fn services(&self) -> Result<Vec<Service>, Error> {
+        send_json(self.client.get(self.url(&["services"])?)).await
+    }
+
+    /// List the services named `name`
+    pub async fn services_named(&self, name: &str) -> Result<Vec<Service>, Error> {
+        let request = self
+            .client
+            .get(self.url(&["services"])?)
+            .query(&[("name", name)]);
+        send_json(request).await
+    }
+
+    /// Get the service `id`
+    pub async fn service(&self, id: &str) -> Result<Service, Error> {
+        send_json(self.client.get(self.url(&["services", id])?)).await
+    }
+
+    /// List the event types, with the services emitting them, by type
+    pub async fn types(&self) -> Result<BTreeMap<String, TypeEntry>, Error> {
+        send_json(self.client.get(self.url(&["types"])?)).await
+    }
+
+    /// Get the services emitting the events of type `ty`.
+    ///
+    /// Fails with [`Error::InvalidName`] if the response doesn't describe `ty`.
+    pub async fn event_type(&self, ty: &str) -> Result<TypeEntry, Error> {
+        let mut types: BTreeMap<String, TypeEntry> =
+            send_json(self.client.get(self.url(&["types", ty])?)).await?;
+        types.remove(ty).ok_or_else(|| Error::InvalidName {
+            name: ty.to_string(),
+        })
+    }
+
+    /// Get the URL of the resource at `path` under the base URL
+    fn url(&self, path: &[&str]) -> Result<Url, Error> {
+        let mut url = self.base.clone();
+        if let Some(name) = path.iter().find(|segment| segment.is_empty()) {
+            return Err(Error::InvalidName {
+                name: name.to_string(),
+            });
+        }
+        match url.path_segments_mut() {
+            Ok(mut segments) => {
+                segments.pop_if_empty().extend(path);
+            }
+            Err(_) => {
+                return Err(Error::InvalidName {
+                    name: path.join("/"),
+                })
+            }
+        }
+        Ok(url)
+    }
+}
+
+/// Send `request`, parsing the JSON body of the response, failing if its status is not a success
+async fn send_json<T: DeserializeOwned>(request: RequestBuilder) -> Result<T, Error> {
+    let response = request.send().await.context(Request)?;
+    let status = response.status();
+    let body = response.bytes().await.context(Request)?;
+    check_status(status, &body)?;
+    serde_json::from_slice(&body).context(InvalidResponse)
+}
+
+fn check_status(status: StatusCode, body: &[u8]) -> Result<(), Error> {
+    if status.is_success() {
+        Ok(())
+    } else {
+        Err(Error::Status {
+            status: status.as_u16(),
+            body: String::from_utf8_lossy(body).into_owned(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn urls() {
+        let client = DiscoveryClient::new(
+            Client::new(),
+            "https://discovery.example.com/api/".parse().unwrap(),
+        );
+        assert_eq!(
+            "https://discovery.example.com/api/services",
+            client.url(&["services"]).unwrap().as_str()
+        );
+        assert_eq!(
+            "https://discovery.example.com/api/types/com.example%2Fcreated",
+            client
+                .url(&["types", "com.example/created"])
+                .unwrap()
+                .as_str()
+        );
+        assert!(matches!(
+            client.url(&["services", ""]),
+            Err(Error::InvalidName { .. })
+        ));
+
+        let request = client
+            .client
+            .get(client.url(&["services"]).unwrap())
+            .query(&[("name", "storage")])
+            .build()
+            .unwrap();
+        assert_eq!(Some("name=storage"), request.url().query());
+    }
+
+    #[test]
+    fn status() {
+        assert!(check_status(StatusCode::OK, b"").is_ok());
+        assert!(matches!(
+            check_status(StatusCode::NOT_FOUND, b""),
+            Err(Error::Status { status: 404, .. })
+        ));
+    }
+}
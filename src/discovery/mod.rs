@@ -0,0 +1,169 @@
+//! Resources of the [CloudEvents Discovery API](https://github.com/cloudevents/spec/blob/v1.0.2/discovery/spec.md),
+//! serialized with their JSON representation.
+//!
+//! A [`Service`] describes a producer: the [`EventType`]s it emits, the protocols and the
+//! subscription endpoint to receive them. With the `reqwest` feature, [`DiscoveryClient`] queries
+//! the services and types of a discovery endpoint.
+//!
+//! ```
+//! use cloudevents::discovery::Service;
+//! use serde_json::json;
+//!
+//! let service: Service = serde_json::from_value(json!({
+//!     "id": "3db45e6e-3f0b-4e8b-a3d5-5c8d5c9b2b1a",
+//!     "url": "https://discovery.example.com/services/3db45e6e-3f0b-4e8b-a3d5-5c8d5c9b2b1a",
+//!     "name": "storage",
+//!     "epoch": 1,
+//!     "specversions": ["1.0"],
+//!     "subscriptionurl": "https://storage.example.com/subscriptions",
+//!     "protocols": ["HTTP"],
+//!     "events": [
+//!         {"type": "com.example.object.created", "datacontenttype": "application/json"}
+//!     ]
+//! }))
+//! .unwrap();
+//!
+//! assert!(service.event_type("com.example.object.created").is_some());
+//! assert!(service.event_type("com.example.object.deleted").is_none());
+//! ```
+
+#[cfg(feature = "reqwest")]
+mod client;
+
+#[cfg(feature = "reqwest")]
+pub use client::{DiscoveryClient, Error};
+
+use crate::subscriptions::Protocol;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use url::Url;
+
+/// Service resource of the Discovery API, describing the events a producer emits
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Service {
+    /// Identifier of the service
+    pub id: String,
+    /// URL of this resource in the discovery endpoint
+    pub url: Url,
+    /// Name of the service
+    pub name: String,
+    /// Version of the resource, incremented when it's modified
+    pub epoch: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// URL of the documentation of the service
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub docsurl: Option<Url>,
+    /// Spec versions of the emitted events
+    pub specversions: Vec<String>,
+    /// URL of the Subscriptions API of the service
+    pub subscriptionurl: Url,
+    /// Configuration accepted by the subscriptions, with their description
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub subscriptionconfig: BTreeMap<String, String>,
+    /// Filter dialects accepted by the subscriptions
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub subscriptiondialects: Vec<String>,
+    /// Authorization scope required to subscribe
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub authscope: Option<String>,
+    /// Protocols the events can be delivered with
+    pub protocols: Vec<Protocol>,
+    /// Types of the emitted events
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub events: Vec<EventType>,
+}
+
+impl Service {
+    /// Get the description of the events of type `ty` emitted by this service, if any
+    pub fn event_type(&self, ty: &str) -> Option<&EventType> {
+        self.events.iter().find(|e| e.ty == ty)
+    }
+}
+
+/// Description of a type of events emitted by a [`Service`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EventType {
+    /// `type` of the events
+    #[serde(rename = "type")]
+    pub ty: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// `datacontenttype` of the events
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub datacontenttype: Option<String>,
+    /// `dataschema` of the events
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dataschema: Option<String>,
+    /// Inline schema of the data of the events
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dataschemacontent: Option<String>,
+    /// [URI template](https://www.rfc-editor.org/rfc/rfc6570) of the `source` of the events
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sourcetemplate: Option<String>,
+    /// Extensions carried by the events
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extensions: Vec<ExtensionDescription>,
+}
+
+/// Description of an extension carried by an [`EventType`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExtensionDescription {
+    /// Name of the extension
+    pub name: String,
+    /// CloudEvents type system type of the extension value, e.g. `String` or `Integer`
+    #[serde(rename = "type")]
+    pub ty: String,
+    /// URL of the specification of the extension
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub specurl: Option<Url>,
+}
+
+/// Entry of the `/types` resource of the Discovery API: the services emitting a type of events
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TypeEntry {
+    /// `type` of the events
+    pub name: String,
+    /// Services emitting the events of this type
+    pub services: Vec<Service>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn deserialize() {
+        let service: Service = serde_json::from_value(json!({
+            "id": "storage",
+            "url": "https://discovery.example.com/services/storage",
+            "name": "storage",
+            "epoch": 3,
+            "specversions": ["1.0"],
+            "subscriptionurl": "https://storage.example.com/subscriptions",
+            "subscriptiondialects": ["basic"],
+            "protocols": ["HTTP", "KAFKA"],
+            "events": [{
+                "type": "com.example.object.created",
+                "sourcetemplate": "/buckets/{bucket}",
+                "extensions": [{"name": "bucket", "type": "String"}]
+            }]
+        }))
+        .unwrap();
+        assert_eq!(vec![Protocol::Http, Protocol::Kafka], service.protocols);
+        let event_type = service.event_type("com.example.object.created").unwrap();
+        assert_eq!(
+            Some("/buckets/{bucket}"),
+            event_type.sourcetemplate.as_deref()
+        );
+        assert_eq!("bucket", event_type.extensions[0].name);
+
+        let value = serde_json::to_value(&service).unwrap();
+        assert_eq!(
+            json!("com.example.object.created"),
+            value["events"][0]["type"]
+        );
+        assert!(value.get("description").is_none());
+    }
+}
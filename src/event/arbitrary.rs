@@ -0,0 +1,257 @@
+//! [`Arbitrary`] implementations and [`Strategy`]s generating valid events, enabled with the
+//! `proptest` feature, to property test bindings and round-trips.
+//!
+//! ```
+//! use cloudevents::Event;
+//! use proptest::prelude::*;
+//!
+//! proptest!(|(event: Event)| {
+//!     let json = serde_json::to_string(&event).unwrap();
+//!     prop_assert_eq!(event, serde_json::from_str::<Event>(&json).unwrap());
+//! });
+//! ```
+
+use super::{
+    Attributes, AttributesV03, AttributesV10, Data, Event, ExtensionValue, SpecVersion,
+    UriReference,
+};
+use chrono::{DateTime, TimeZone, Utc};
+use proptest::collection::{btree_map, hash_map, vec};
+use proptest::option;
+use proptest::prelude::*;
+use std::collections::HashMap;
+
+/// Names the JSON event format uses for members which are not extensions
+const RESERVED_NAMES: [&str; 3] = ["data", "data_base64", "datacontentencoding"];
+
+/// Strategy generating a valid extension name, not clashing with any context attribute name
+pub fn extension_name() -> impl Strategy<Value = String> {
+    "[a-z][a-z0-9]{0,9}".prop_filter("context attribute name", |name| {
+        !SpecVersion::all_attribute_names().any(|n| n == name)
+            && !RESERVED_NAMES.contains(&name.as_str())
+    })
+}
+
+/// Strategy generating up to 4 extensions
+pub fn extensions() -> impl Strategy<Value = HashMap<String, ExtensionValue>> {
+    hash_map(extension_name(), any::<ExtensionValue>(), 0..4)
+}
+
+/// Strategy generating an [`Event`] of the given [`SpecVersion`]
+pub fn event(spec_version: SpecVersion) -> BoxedStrategy<Event> {
+    let attributes = match spec_version {
+        SpecVersion::V03 => any::<AttributesV03>().prop_map(Attributes::V03).boxed(),
+        SpecVersion::V10 => any::<AttributesV10>().prop_map(Attributes::V10).boxed(),
+    };
+    (attributes, option::of(any::<Data>()))
+        .prop_map(|(mut attributes, data)| {
+            // The datacontenttype is consistent with the data, so the event round-trips
+            let datacontenttype = data.as_ref().map(|d| content_type(d).to_string());
+            match &mut attributes {
+                Attributes::V03(a) => a.datacontenttype = datacontenttype,
+                Attributes::V10(a) => a.datacontenttype = datacontenttype,
+            }
+            Event { attributes, data }
+        })
+        .boxed()
+}
+
+fn content_type(data: &Data) -> &'static str {
+    match data {
+        Data::Json(_) => "application/json",
+        Data::String(_) => "text/plain",
+        Data::Binary(_) => "application/octet-stream",
+    }
+}
+
+fn id() -> impl Strategy<Value = String> {
+    "[a-zA-Z0-9._-]{1,16}"
+}
+
+fn ty() -> impl Strategy<Value = String> {
+    "[a-z]{1,8}(\\.[a-z]{1,8}){0,3}"
+}
+
+fn source() -> impl Strategy<Value = UriReference> {
+    prop_oneof![
+        "https://example\\.com(/[a-z0-9]{1,8}){0,3}",
+        "/[a-z0-9]{1,8}(/[a-z0-9]{1,8}){0,2}",
+        "urn:[a-z]{1,8}:[a-z0-9]{1,8}",
+    ]
+    .prop_map(|s| UriReference::parse(&s).expect("generated invalid URI-reference"))
+}
+
+fn schema() -> impl Strategy<Value = UriReference> {
+    "https://example\\.com/schemas/[a-z]{1,8}\\.json"
+        .prop_map(|s| UriReference::parse(&s).expect("generated invalid URI-reference"))
+}
+
+fn datacontenttype() -> impl Strategy<Value = String> {
+    prop_oneof![
+        Just(String::from("application/json")),
+        Just(String::from("text/plain")),
+        Just(String::from("application/octet-stream")),
+    ]
+}
+
+fn subject() -> impl Strategy<Value = String> {
+    "[a-zA-Z0-9/_-]{1,16}"
+}
+
+/// Times between 1970 and 2100 with a millisecond precision
+fn time() -> impl Strategy<Value = DateTime<Utc>> {
+    (0_i64..4_102_444_800_000).prop_map(|millis| Utc.timestamp_millis(millis))
+}
+
+fn json() -> impl Strategy<Value = serde_json::Value> {
+    let leaf = prop_oneof![
+        any::<bool>().prop_map(serde_json::Value::from),
+        any::<i64>().prop_map(serde_json::Value::from),
+        "[a-zA-Z0-9 ]{0,16}".prop_map(serde_json::Value::from),
+    ];
+    prop_oneof![
+        leaf.clone(),
+        btree_map("[a-z]{1,8}", leaf, 0..4)
+            .prop_map(|m| serde_json::Value::Object(m.into_iter().collect())),
+    ]
+}
+
+impl Arbitrary for ExtensionValue {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        prop_oneof![
+            "[a-zA-Z0-9._-]{0,16}".prop_map(ExtensionValue::String),
+            any::<bool>().prop_map(ExtensionValue::Boolean),
+            any::<i64>().prop_map(ExtensionValue::Integer),
+        ]
+        .boxed()
+    }
+}
+
+impl Arbitrary for Data {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        prop_oneof![
+            json().prop_map(Data::Json),
+            "[a-zA-Z0-9 ]{0,32}".prop_map(Data::String),
+            vec(any::<u8>(), 0..32).prop_map(Data::Binary),
+        ]
+        .boxed()
+    }
+}
+
+impl Arbitrary for AttributesV03 {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            id(),
+            ty(),
+            source(),
+            option::of(datacontenttype()),
+            option::of(schema()),
+            option::of(subject()),
+            option::of(time()),
+            extensions(),
+        )
+            .prop_map(
+                |(id, ty, source, datacontenttype, schemaurl, subject, time, extensions)| {
+                    AttributesV03 {
+                        id,
+                        ty: ty.into(),
+                        source,
+                        datacontenttype,
+                        schemaurl,
+                        subject,
+                        time,
+                        time_offset: None,
+                        extensions,
+                    }
+                },
+            )
+            .boxed()
+    }
+}
+
+impl Arbitrary for AttributesV10 {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            id(),
+            ty(),
+            source(),
+            option::of(datacontenttype()),
+            option::of(schema()),
+            option::of(subject()),
+            option::of(time()),
+            extensions(),
+        )
+            .prop_map(
+                |(id, ty, source, datacontenttype, dataschema, subject, time, extensions)| {
+                    AttributesV10 {
+                        id,
+                        ty: ty.into(),
+                        source,
+                        datacontenttype,
+                        dataschema,
+                        subject,
+                        time,
+                        time_offset: None,
+                        extensions,
+                    }
+                },
+            )
+            .boxed()
+    }
+}
+
+impl Arbitrary for Event {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        prop_oneof![event(SpecVersion::V03), event(SpecVersion::V10)].boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::message::{BinaryDeserializer, StructuredDeserializer};
+
+    proptest! {
+        #[test]
+        fn valid_events(event: Event) {
+            prop_assert_eq!(Ok(()), event.validate());
+        }
+
+        #[test]
+        fn json_round_trip(event: Event) {
+            let json = serde_json::to_vec(&event).unwrap();
+            prop_assert_eq!(event, serde_json::from_slice::<Event>(&json).unwrap());
+        }
+
+        #[test]
+        fn message_round_trip(event: Event) {
+            prop_assert_eq!(
+                &event,
+                &StructuredDeserializer::into_event(event.clone()).unwrap()
+            );
+            // Binary mode doesn't preserve the string data, read back as binary
+            let binary = BinaryDeserializer::into_event(event.clone()).unwrap();
+            prop_assert_eq!(&event.attributes, &binary.attributes);
+        }
+
+        #[test]
+        fn spec_version(event in event(SpecVersion::V03)) {
+            prop_assert_eq!(SpecVersion::V03, crate::AttributesReader::get_specversion(&event));
+        }
+    }
+}
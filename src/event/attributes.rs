@@ -1,26 +1,102 @@
 use super::{
-    AttributesIntoIteratorV03, AttributesIntoIteratorV10, AttributesV03, AttributesV10, SpecVersion,
+    AttributesIntoIteratorV03, AttributesIntoIteratorV10, AttributesV03, AttributesV10,
+    ExtensionValue, InvalidUriReference, SpecVersion, UriReference,
 };
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, FixedOffset, Offset, Utc};
+use std::borrow::Cow;
+use std::collections::{hash_map, HashMap};
 use std::fmt;
 use url::Url;
 
+/// Define the context attributes of a spec version from their names and values, listed in the
+/// order of the spec: the `ATTRIBUTE_NAMES` and the `AttributesIntoIterator` over the attributes
+/// which are set, followed by the extensions sorted by name. Each value is an expression of type
+/// `Option<AttributeValue>` reading the attributes bound to the given identifier.
+macro_rules! context_attributes {
+    ($(#[$meta:meta])* $attributes:ty as $a:ident { $($name:literal => $value:expr),+ $(,)? }) => {
+        $(#[$meta])*
+        pub const ATTRIBUTE_NAMES: [&str; ATTRIBUTE_COUNT] = [$($name),+];
+
+        const ATTRIBUTE_COUNT: usize = [$($name),+].len();
+
+        impl<'a> IntoIterator for &'a $attributes {
+            type Item = (&'a str, $crate::event::AttributeValue<'a>);
+            type IntoIter = AttributesIntoIterator<'a>;
+
+            fn into_iter(self) -> Self::IntoIter {
+                AttributesIntoIterator {
+                    attributes: self,
+                    index: 0,
+                    extensions: $crate::event::attributes::sorted_extensions(&self.extensions),
+                }
+            }
+        }
+
+        /// Iterator over the context attributes in the order of the spec, followed by the
+        /// extensions sorted by name
+        #[derive(Debug, Clone)]
+        pub struct AttributesIntoIterator<'a> {
+            attributes: &'a $attributes,
+            index: usize,
+            extensions: std::vec::IntoIter<(&'a String, &'a $crate::event::ExtensionValue)>,
+        }
+
+        impl<'a> Iterator for AttributesIntoIterator<'a> {
+            type Item = (&'a str, $crate::event::AttributeValue<'a>);
+
+            // The attributes are visited in the order of the list, each once, `index` counting
+            // the ones before
+            #[allow(unused_assignments)]
+            fn next(&mut self) -> Option<Self::Item> {
+                let $a = self.attributes;
+                let mut index = 0;
+                $(
+                    if self.index == index {
+                        self.index += 1;
+                        if let Some(value) = $value {
+                            return Some(($name, value));
+                        }
+                    }
+                    index += 1;
+                )+
+                self.extensions
+                    .next()
+                    .map(|(k, v)| (k.as_str(), $crate::event::AttributeValue::from(v)))
+            }
+        }
+    };
+}
+
 #[derive(Debug, PartialEq)]
 pub enum AttributeValue<'a> {
     SpecVersion(SpecVersion),
     String(&'a str),
     URI(&'a Url),
-    URIRef(&'a Url),
+    URIRef(&'a UriReference),
+    Boolean(&'a bool),
+    Integer(&'a i64),
     Time(&'a DateTime<Utc>),
 }
 
+impl<'a> From<&'a ExtensionValue> for AttributeValue<'a> {
+    fn from(value: &'a ExtensionValue) -> Self {
+        match value {
+            ExtensionValue::String(s) => AttributeValue::String(s),
+            ExtensionValue::Boolean(b) => AttributeValue::Boolean(b),
+            ExtensionValue::Integer(i) => AttributeValue::Integer(i),
+        }
+    }
+}
+
 impl fmt::Display for AttributeValue<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             AttributeValue::SpecVersion(s) => s.fmt(f),
             AttributeValue::String(s) => f.write_str(s),
-            AttributeValue::URI(s) => f.write_str(&s.as_str()),
-            AttributeValue::URIRef(s) => f.write_str(&s.as_str()),
+            AttributeValue::URI(s) => f.write_str(s.as_str()),
+            AttributeValue::URIRef(s) => f.write_str(s.as_str()),
+            AttributeValue::Boolean(b) => b.fmt(f),
+            AttributeValue::Integer(i) => i.fmt(f),
             AttributeValue::Time(s) => f.write_str(&s.to_rfc3339()),
         }
     }
@@ -31,7 +107,7 @@ pub trait AttributesReader {
     /// Get the [id](https://github.com/cloudevents/spec/blob/master/spec.md#id).
     fn get_id(&self) -> &str;
     /// Get the [source](https://github.com/cloudevents/spec/blob/master/spec.md#source-1).
-    fn get_source(&self) -> &Url;
+    fn get_source(&self) -> &UriReference;
     /// Get the [specversion](https://github.com/cloudevents/spec/blob/master/spec.md#specversion).
     fn get_specversion(&self) -> SpecVersion;
     /// Get the [type](https://github.com/cloudevents/spec/blob/master/spec.md#type).
@@ -39,11 +115,28 @@ pub trait AttributesReader {
     /// Get the [datacontenttype](https://github.com/cloudevents/spec/blob/master/spec.md#datacontenttype).
     fn get_datacontenttype(&self) -> Option<&str>;
     /// Get the [dataschema](https://github.com/cloudevents/spec/blob/master/spec.md#dataschema).
-    fn get_dataschema(&self) -> Option<&Url>;
+    fn get_dataschema(&self) -> Option<&UriReference>;
     /// Get the [subject](https://github.com/cloudevents/spec/blob/master/spec.md#subject).
     fn get_subject(&self) -> Option<&str>;
     /// Get the [time](https://github.com/cloudevents/spec/blob/master/spec.md#time).
     fn get_time(&self) -> Option<&DateTime<Utc>>;
+    /// Get the [time](https://github.com/cloudevents/spec/blob/master/spec.md#time) with the
+    /// offset it was parsed or set with, UTC by default.
+    fn get_time_with_offset(&self) -> Option<DateTime<FixedOffset>> {
+        self.get_time().map(|time| time.with_timezone(&Utc.fix()))
+    }
+    /// Get the [time](https://github.com/cloudevents/spec/blob/master/spec.md#time) as a
+    /// [`time::OffsetDateTime`] with its offset, `None` if it's out of the range of the `time`
+    /// crate. Enabled with the `time` feature.
+    #[cfg(feature = "time")]
+    fn get_offset_date_time(&self) -> Option<time::OffsetDateTime> {
+        self.get_time_with_offset()
+            .and_then(super::types::to_offset_date_time)
+    }
+    /// Get the [extension](https://github.com/cloudevents/spec/blob/master/spec.md#extension-context-attributes) named `extension_name`.
+    fn get_extension(&self, extension_name: &str) -> Option<&ExtensionValue>;
+    /// Get an [`Iterator`] over all the [extensions](https://github.com/cloudevents/spec/blob/master/spec.md#extension-context-attributes).
+    fn iter_extensions(&self) -> hash_map::Iter<'_, String, ExtensionValue>;
 }
 
 /// Trait to set [CloudEvents Context attributes](https://github.com/cloudevents/spec/blob/master/spec.md#context-attributes).
@@ -51,13 +144,22 @@ pub trait AttributesWriter {
     /// Set the [id](https://github.com/cloudevents/spec/blob/master/spec.md#id).
     fn set_id(&mut self, id: impl Into<String>);
     /// Set the [source](https://github.com/cloudevents/spec/blob/master/spec.md#source-1).
-    fn set_source(&mut self, source: impl Into<Url>);
+    fn set_source(&mut self, source: impl Into<UriReference>);
     /// Set the [type](https://github.com/cloudevents/spec/blob/master/spec.md#type).
     fn set_type(&mut self, ty: impl Into<String>);
     /// Set the [subject](https://github.com/cloudevents/spec/blob/master/spec.md#subject).
     fn set_subject(&mut self, subject: Option<impl Into<String>>);
     /// Set the [time](https://github.com/cloudevents/spec/blob/master/spec.md#time).
     fn set_time(&mut self, time: Option<impl Into<DateTime<Utc>>>);
+    /// Set the [time](https://github.com/cloudevents/spec/blob/master/spec.md#time), keeping its
+    /// offset when the event is serialized.
+    fn set_time_with_offset(&mut self, time: Option<impl Into<DateTime<FixedOffset>>>) {
+        self.set_time(time.map(|time| DateTime::<Utc>::from(time.into())))
+    }
+    /// Set the [extension](https://github.com/cloudevents/spec/blob/master/spec.md#extension-context-attributes) named `extension_name` with `extension_value`.
+    fn set_extension(&mut self, extension_name: &str, extension_value: impl Into<ExtensionValue>);
+    /// Remove the [extension](https://github.com/cloudevents/spec/blob/master/spec.md#extension-context-attributes) named `extension_name`.
+    fn remove_extension(&mut self, extension_name: &str) -> Option<ExtensionValue>;
 }
 
 pub(crate) trait AttributesConverter {
@@ -67,10 +169,10 @@ pub(crate) trait AttributesConverter {
 
 pub(crate) trait DataAttributesWriter {
     fn set_datacontenttype(&mut self, datacontenttype: Option<impl Into<String>>);
-    fn set_dataschema(&mut self, dataschema: Option<impl Into<Url>>);
+    fn set_dataschema(&mut self, dataschema: Option<impl Into<UriReference>>);
 }
 
-#[derive(PartialEq, Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub(crate) enum AttributesIter<'a> {
     IterV03(AttributesIntoIteratorV03<'a>),
     IterV10(AttributesIntoIteratorV10<'a>),
@@ -101,7 +203,7 @@ impl AttributesReader for Attributes {
         }
     }
 
-    fn get_source(&self) -> &Url {
+    fn get_source(&self) -> &UriReference {
         match self {
             Attributes::V03(a) => a.get_source(),
             Attributes::V10(a) => a.get_source(),
@@ -129,7 +231,7 @@ impl AttributesReader for Attributes {
         }
     }
 
-    fn get_dataschema(&self) -> Option<&Url> {
+    fn get_dataschema(&self) -> Option<&UriReference> {
         match self {
             Attributes::V03(a) => a.get_dataschema(),
             Attributes::V10(a) => a.get_dataschema(),
@@ -149,6 +251,27 @@ impl AttributesReader for Attributes {
             Attributes::V10(a) => a.get_time(),
         }
     }
+
+    fn get_time_with_offset(&self) -> Option<DateTime<FixedOffset>> {
+        match self {
+            Attributes::V03(a) => a.get_time_with_offset(),
+            Attributes::V10(a) => a.get_time_with_offset(),
+        }
+    }
+
+    fn get_extension(&self, extension_name: &str) -> Option<&ExtensionValue> {
+        match self {
+            Attributes::V03(a) => a.get_extension(extension_name),
+            Attributes::V10(a) => a.get_extension(extension_name),
+        }
+    }
+
+    fn iter_extensions(&self) -> hash_map::Iter<'_, String, ExtensionValue> {
+        match self {
+            Attributes::V03(a) => a.iter_extensions(),
+            Attributes::V10(a) => a.iter_extensions(),
+        }
+    }
 }
 
 impl AttributesWriter for Attributes {
@@ -159,7 +282,7 @@ impl AttributesWriter for Attributes {
         }
     }
 
-    fn set_source(&mut self, source: impl Into<Url>) {
+    fn set_source(&mut self, source: impl Into<UriReference>) {
         match self {
             Attributes::V03(a) => a.set_source(source),
             Attributes::V10(a) => a.set_source(source),
@@ -186,6 +309,27 @@ impl AttributesWriter for Attributes {
             Attributes::V10(a) => a.set_time(time),
         }
     }
+
+    fn set_time_with_offset(&mut self, time: Option<impl Into<DateTime<FixedOffset>>>) {
+        match self {
+            Attributes::V03(a) => a.set_time_with_offset(time),
+            Attributes::V10(a) => a.set_time_with_offset(time),
+        }
+    }
+
+    fn set_extension(&mut self, extension_name: &str, extension_value: impl Into<ExtensionValue>) {
+        match self {
+            Attributes::V03(a) => a.set_extension(extension_name, extension_value),
+            Attributes::V10(a) => a.set_extension(extension_name, extension_value),
+        }
+    }
+
+    fn remove_extension(&mut self, extension_name: &str) -> Option<ExtensionValue> {
+        match self {
+            Attributes::V03(a) => a.remove_extension(extension_name),
+            Attributes::V10(a) => a.remove_extension(extension_name),
+        }
+    }
 }
 
 impl DataAttributesWriter for Attributes {
@@ -196,7 +340,7 @@ impl DataAttributesWriter for Attributes {
         }
     }
 
-    fn set_dataschema(&mut self, dataschema: Option<impl Into<Url>>) {
+    fn set_dataschema(&mut self, dataschema: Option<impl Into<UriReference>>) {
         match self {
             Attributes::V03(a) => a.set_dataschema(dataschema),
             Attributes::V10(a) => a.set_dataschema(dataschema),
@@ -217,34 +361,96 @@ impl Attributes {
             _ => self,
         }
     }
+
+    pub(crate) fn reset(&mut self) {
+        match self {
+            Attributes::V03(a) => a.reset(),
+            Attributes::V10(a) => a.reset(),
+        }
+    }
+}
+
+/// Empty `value` keeping its allocation, if it's owned
+pub(crate) fn clear_cow(value: &mut Cow<'static, str>) {
+    match value {
+        Cow::Owned(s) => s.clear(),
+        Cow::Borrowed(_) => *value = Cow::Borrowed(""),
+    }
+}
+
+/// Split `time` in its UTC value and its offset, `None` if it's UTC
+pub(crate) fn split_time_offset(
+    time: Option<DateTime<FixedOffset>>,
+) -> (Option<DateTime<Utc>>, Option<FixedOffset>) {
+    match time {
+        Some(time) => {
+            let offset = Some(*time.offset()).filter(|o| o.local_minus_utc() != 0);
+            (Some(time.into()), offset)
+        }
+        None => (None, None),
+    }
+}
+
+/// Join the UTC `time` with its offset split with [`split_time_offset`]
+pub(crate) fn join_time_offset(
+    time: &DateTime<Utc>,
+    offset: Option<FixedOffset>,
+) -> DateTime<FixedOffset> {
+    time.with_timezone(&offset.unwrap_or_else(|| Utc.fix()))
+}
+
+/// Iterate over `extensions` sorted by name, to get a deterministic order
+pub(crate) fn sorted_extensions(
+    extensions: &HashMap<String, ExtensionValue>,
+) -> std::vec::IntoIter<(&String, &ExtensionValue)> {
+    let mut extensions: Vec<(&String, &ExtensionValue)> = extensions.iter().collect();
+    extensions.sort_unstable_by_key(|(name, _)| *name);
+    extensions.into_iter()
 }
 
+/// Environment variable overriding the default `source` of the events, skipping the hostname
+/// lookup, e.g. in containers where the hostname is a random identifier
+pub const SOURCE_ENV_VAR: &str = "CLOUDEVENTS_SOURCE";
+
+/// Get the default `source`, falling back to `http://localhost/` if
+/// [`try_default_hostname`] fails
+pub(crate) fn default_hostname() -> UriReference {
+    try_default_hostname().unwrap_or_else(|_| Url::parse("http://localhost").unwrap().into())
+}
+
+/// Get the default `source`: the [`SOURCE_ENV_VAR`] environment variable if set, otherwise
+/// `http://` followed by the hostname
 #[cfg(not(target_arch = "wasm32"))]
-pub(crate) fn default_hostname() -> Url {
-    Url::parse(
-        format!(
-            "http://{}",
-            hostname::get()
-                .ok()
-                .map(|s| s.into_string().ok())
-                .flatten()
-                .unwrap_or(String::from("localhost".to_string()))
-        )
-        .as_ref(),
-    )
-    .unwrap()
+pub(crate) fn try_default_hostname() -> Result<UriReference, InvalidUriReference> {
+    if let Some(source) = std::env::var_os(SOURCE_ENV_VAR) {
+        return UriReference::parse(&source.to_string_lossy());
+    }
+    let hostname = hostname::get()
+        .ok()
+        .and_then(|s| s.into_string().ok())
+        .unwrap_or_else(|| String::from("localhost"));
+    hostname_uri_reference(&format!("http://{}", hostname))
 }
 
+/// Get the default `source`, the origin of the page, see [`page_origin`]
 #[cfg(target_arch = "wasm32")]
-pub(crate) fn default_hostname() -> Url {
-    use std::str::FromStr;
-
-    Url::from_str(
-        web_sys::window()
-            .map(|w| w.location().host().ok())
-            .flatten()
-            .unwrap_or(String::from("http://localhost"))
-            .as_str(),
-    )
-    .unwrap()
+pub(crate) fn try_default_hostname() -> Result<UriReference, InvalidUriReference> {
+    let origin = web_sys::window().and_then(|w| w.location().origin().ok());
+    hostname_uri_reference(&page_origin(origin))
+}
+
+/// Get the origin of the page, with its scheme, e.g. `https://example.com:8080`, falling back
+/// to `http://localhost` if it's unknown or opaque, e.g. `null` for a `file:` page
+// Only called on wasm32
+#[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
+pub(crate) fn page_origin(origin: Option<String>) -> String {
+    origin
+        .filter(|origin| origin != "null")
+        .unwrap_or_else(|| String::from("http://localhost"))
+}
+
+pub(crate) fn hostname_uri_reference(value: &str) -> Result<UriReference, InvalidUriReference> {
+    Url::parse(value)
+        .map(Into::into)
+        .map_err(|_| InvalidUriReference::new(value, "invalid hostname URL"))
 }
@@ -0,0 +1,232 @@
+use super::Event;
+use crate::message::{BatchDeserializer, BatchSerializer, Error, Result};
+use serde::{Deserialize, Serialize};
+use std::iter::FromIterator;
+use std::ops::{Deref, DerefMut};
+
+/// A list of [`Event`]s, serialized with the
+/// [JSON batch format](https://github.com/cloudevents/spec/blob/v1.0/json-format.md#4-json-batch-format)
+/// to send or receive several events in a single message.
+///
+/// ```
+/// use cloudevents::{Batch, EventBuilder, EventBuilderV10};
+///
+/// let batch: Batch = (0..3)
+///     .map(|i| {
+///         EventBuilderV10::new()
+///             .id(i.to_string())
+///             .source("http://localhost/")
+///             .ty("example.demo")
+///             .build()
+///             .unwrap()
+///     })
+///     .collect();
+///
+/// let json = serde_json::to_string(&batch).unwrap();
+/// assert_eq!(batch, serde_json::from_str(&json).unwrap());
+/// ```
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Batch(pub Vec<Event>);
+
+impl Batch {
+    /// Create a new empty [`Batch`]
+    pub fn new() -> Self {
+        Batch(Vec::new())
+    }
+
+    /// Consume the batch, returning the contained events
+    pub fn into_events(self) -> Vec<Event> {
+        self.0
+    }
+
+    /// Split the batch into consecutive batches of at most `max_events` events (at least one),
+    /// each serialized in the JSON batch format in at most `max_bytes` bytes.
+    ///
+    /// Fails with [`Error::EventTooLarge`] if an event alone doesn't fit in `max_bytes`.
+    ///
+    /// ```
+    /// use cloudevents::{Batch, EventBuilder, EventBuilderV10};
+    ///
+    /// let batch: Batch = (0..5)
+    ///     .map(|i| {
+    ///         EventBuilderV10::new()
+    ///             .id(i.to_string())
+    ///             .source("http://localhost/")
+    ///             .ty("example.demo")
+    ///             .build()
+    ///             .unwrap()
+    ///     })
+    ///     .collect();
+    ///
+    /// let batches = batch.split(64 * 1024, 2).unwrap();
+    /// assert_eq!(vec![2, 2, 1], batches.iter().map(|b| b.len()).collect::<Vec<_>>());
+    /// ```
+    pub fn split(self, max_bytes: usize, max_events: usize) -> Result<Vec<Batch>> {
+        let max_events = max_events.max(1);
+        let mut batches = Vec::new();
+        let mut current = Batch::new();
+        // Serialized size of the current batch: the brackets, the events and the commas
+        let mut current_size = 2;
+        for event in self {
+            let size = serde_json::to_vec(&event)?.len();
+            if size + 2 > max_bytes {
+                return Err(Error::EventTooLarge {
+                    size: size + 2,
+                    limit: max_bytes,
+                });
+            }
+            let separator = if current.is_empty() { 0 } else { 1 };
+            if current.len() == max_events || current_size + separator + size > max_bytes {
+                batches.push(std::mem::take(&mut current));
+                current_size = 2;
+            }
+            current_size += size + if current.is_empty() { 0 } else { 1 };
+            current.push(event);
+        }
+        if !current.is_empty() {
+            batches.push(current);
+        }
+        Ok(batches)
+    }
+}
+
+impl From<Vec<Event>> for Batch {
+    fn from(events: Vec<Event>) -> Self {
+        Batch(events)
+    }
+}
+
+impl From<Batch> for Vec<Event> {
+    fn from(batch: Batch) -> Self {
+        batch.0
+    }
+}
+
+impl Deref for Batch {
+    type Target = Vec<Event>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for Batch {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl IntoIterator for Batch {
+    type Item = Event;
+    type IntoIter = std::vec::IntoIter<Event>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Batch {
+    type Item = &'a Event;
+    type IntoIter = std::slice::Iter<'a, Event>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl FromIterator<Event> for Batch {
+    fn from_iter<I: IntoIterator<Item = Event>>(iter: I) -> Self {
+        Batch(iter.into_iter().collect())
+    }
+}
+
+impl BatchDeserializer for Batch {
+    fn deserialize_batch<R: Sized, V: BatchSerializer<R>>(self, serializer: V) -> Result<R> {
+        serializer.set_batch(serde_json::to_vec(&self)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EventBuilder, EventBuilderV03, EventBuilderV10};
+    use serde_json::json;
+
+    #[test]
+    fn serialize_mixed_spec_versions() {
+        let batch = Batch::from(vec![
+            EventBuilderV10::new()
+                .id("0001")
+                .source("http://localhost/")
+                .ty("example.demo")
+                .build()
+                .unwrap(),
+            EventBuilderV03::new()
+                .id("0002")
+                .source("http://localhost/")
+                .ty("example.demo")
+                .build()
+                .unwrap(),
+        ]);
+
+        let value = serde_json::to_value(&batch).unwrap();
+        assert_eq!(
+            json!([
+                {"specversion": "1.0", "id": "0001", "source": "http://localhost/", "type": "example.demo"},
+                {"specversion": "0.3", "id": "0002", "source": "http://localhost/", "type": "example.demo"}
+            ]),
+            value
+        );
+        assert_eq!(batch, serde_json::from_value(value).unwrap());
+    }
+
+    #[test]
+    fn split() {
+        let batch: Batch = (0..10)
+            .map(|i| {
+                EventBuilderV10::new()
+                    .id(i.to_string())
+                    .source("http://localhost/")
+                    .ty("example.demo")
+                    .build()
+                    .unwrap()
+            })
+            .collect();
+        let event_size = serde_json::to_vec(&batch[0]).unwrap().len();
+
+        // Three events with the brackets and the two commas
+        let batches = batch.clone().split(3 * event_size + 4, 10).unwrap();
+        assert_eq!(4, batches.len());
+        for b in &batches {
+            assert!(serde_json::to_vec(b).unwrap().len() <= 3 * event_size + 4);
+        }
+        assert!(batch.clone().split(3 * event_size + 3, 10).unwrap()[0].len() == 2);
+        assert_eq!(batch, batches.into_iter().flatten().collect());
+
+        assert_eq!(10, batch.clone().split(usize::MAX, 0).unwrap().len());
+        assert!(matches!(
+            batch.split(event_size + 1, 10),
+            Err(Error::EventTooLarge { .. })
+        ));
+        assert!(Batch::new().split(0, 0).unwrap().is_empty());
+    }
+
+    #[test]
+    fn empty_batch() {
+        assert_eq!(Batch::new(), serde_json::from_str("[]").unwrap());
+        assert!(serde_json::from_str::<Batch>("{}").is_err());
+    }
+
+    #[test]
+    fn into_batch() {
+        let batch = Batch::from(vec![EventBuilderV10::new()
+            .id("0001")
+            .source("http://localhost/")
+            .ty("example.demo")
+            .build()
+            .unwrap()]);
+
+        assert_eq!(batch.clone(), batch.into_batch().unwrap());
+    }
+}
@@ -0,0 +1,403 @@
+use super::{parse_json, Event};
+use crate::message::{Error, Result};
+use std::io::Read;
+
+const BUFFER_SIZE: usize = 8 * 1024;
+
+/// Reader incrementally parsing the events of a
+/// [JSON batch](https://github.com/cloudevents/spec/blob/v1.0/json-format.md#4-json-batch-format),
+/// or of newline delimited JSON events, buffering a single event at a time.
+///
+/// With a [`Read`] it's an [`Iterator`] of events, with a `tokio::io::AsyncRead` and the
+/// `tokio` feature it's a `futures::Stream` of events. It stops after the first error.
+///
+/// ```
+/// use cloudevents::event::BatchReader;
+/// use cloudevents::AttributesReader;
+///
+/// let batch = r#"[
+///     {"specversion": "1.0", "id": "1", "type": "example.test", "source": "/"},
+///     {"specversion": "1.0", "id": "2", "type": "example.test", "source": "/"}
+/// ]"#;
+///
+/// let ids: Vec<String> = BatchReader::new(batch.as_bytes())
+///     .map(|event| event.unwrap().get_id().to_string())
+///     .collect();
+/// assert_eq!(vec!["1", "2"], ids);
+/// ```
+#[derive(Debug)]
+pub struct BatchReader<R> {
+    reader: R,
+    buffer: Box<[u8]>,
+    pos: usize,
+    len: usize,
+    splitter: EventSplitter,
+}
+
+impl<R> BatchReader<R> {
+    /// Create a new [`BatchReader`] parsing the events read from `reader`
+    pub fn new(reader: R) -> Self {
+        BatchReader {
+            reader,
+            buffer: vec![0; BUFFER_SIZE].into_boxed_slice(),
+            pos: 0,
+            len: 0,
+            splitter: EventSplitter::default(),
+        }
+    }
+
+    /// Consume the [`BatchReader`], returning the underlying reader
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    /// Feed the buffered bytes to the splitter, returning the next parsed event if any
+    fn scan_buffer(&mut self) -> Option<Result<Event>> {
+        let (consumed, event) = self.splitter.next_event(&self.buffer[self.pos..self.len]);
+        self.pos += consumed;
+        event
+    }
+
+    fn finish(&mut self) -> Option<Result<Event>> {
+        self.splitter.finish()
+    }
+
+    fn fail(&mut self, e: Error) -> Result<Event> {
+        self.splitter.done = true;
+        Err(e)
+    }
+}
+
+/// Push parser splitting the input in events, which can be fed chunk by chunk. It stops after the
+/// first error.
+#[derive(Debug, Default)]
+pub(crate) struct EventSplitter {
+    scanner: Scanner,
+    done: bool,
+}
+
+impl EventSplitter {
+    /// Parse the next event of `input`, returning the number of bytes consumed, which are all the
+    /// bytes of `input` if no event is complete
+    pub(crate) fn next_event(&mut self, input: &[u8]) -> (usize, Option<Result<Event>>) {
+        let mut pos = 0;
+        while !self.done && pos < input.len() {
+            let (consumed, step) = self.scanner.feed(&input[pos..]);
+            pos += consumed;
+            match step {
+                Ok(Step::NeedMore) | Ok(Step::End) => {}
+                Ok(Step::Value(bytes)) => {
+                    let event = parse_json(bytes);
+                    self.done = event.is_err();
+                    return (pos, Some(event));
+                }
+                Err(e) => {
+                    self.done = true;
+                    return (pos, Some(Err(e)));
+                }
+            }
+        }
+        (input.len(), None)
+    }
+
+    /// Handle the end of the input, returning an error if it's truncated
+    pub(crate) fn finish(&mut self) -> Option<Result<Event>> {
+        if std::mem::replace(&mut self.done, true) {
+            return None;
+        }
+        self.scanner.finish().err().map(Err)
+    }
+
+    pub(crate) fn is_done(&self) -> bool {
+        self.done
+    }
+}
+
+impl<R: Read> Iterator for BatchReader<R> {
+    type Item = Result<Event>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.splitter.is_done() {
+            if let Some(event) = self.scan_buffer() {
+                return Some(event);
+            }
+            match self.reader.read(&mut self.buffer) {
+                Ok(0) => return self.finish(),
+                Ok(n) => {
+                    self.pos = 0;
+                    self.len = n;
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+                Err(e) => return Some(self.fail(e.into())),
+            }
+        }
+        None
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<R: tokio::io::AsyncRead + Unpin> futures::Stream for BatchReader<R> {
+    type Item = Result<Event>;
+
+    fn poll_next(
+        self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<Self::Item>> {
+        use std::task::Poll;
+
+        let this = self.get_mut();
+        while !this.splitter.is_done() {
+            if let Some(event) = this.scan_buffer() {
+                return Poll::Ready(Some(event));
+            }
+            let mut buf = tokio::io::ReadBuf::new(&mut this.buffer);
+            match std::pin::Pin::new(&mut this.reader).poll_read(cx, &mut buf) {
+                Poll::Pending => return Poll::Pending,
+                Poll::Ready(Ok(())) if buf.filled().is_empty() => {
+                    return Poll::Ready(this.finish())
+                }
+                Poll::Ready(Ok(())) => {
+                    this.len = buf.filled().len();
+                    this.pos = 0;
+                }
+                Poll::Ready(Err(e)) => return Poll::Ready(Some(this.fail(e.into()))),
+            }
+        }
+        Poll::Ready(None)
+    }
+}
+
+/// Result of feeding bytes to the [`Scanner`]
+enum Step {
+    NeedMore,
+    Value(Vec<u8>),
+    End,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum State {
+    /// Before the first byte, detecting the format
+    Start,
+    /// After `[`, expecting the first event or `]`
+    ArrayFirst,
+    /// After `,` in an array, expecting an event
+    ArrayValue,
+    /// After an event in an array, expecting `,` or `]`
+    ArrayNext,
+    /// After an event of newline delimited JSON
+    LinesNext,
+    /// Inside an event
+    Value {
+        in_array: bool,
+        depth: usize,
+        in_string: bool,
+        escape: bool,
+    },
+    /// After `]`
+    End,
+}
+
+/// Push scanner splitting the input in the JSON objects of the events, tracking the nesting
+/// depth of the objects and arrays outside strings.
+#[derive(Debug)]
+struct Scanner {
+    state: State,
+    value: Vec<u8>,
+}
+
+impl Default for Scanner {
+    fn default() -> Self {
+        Scanner {
+            state: State::Start,
+            value: Vec::new(),
+        }
+    }
+}
+
+impl Scanner {
+    /// Feed `input`, returning the number of bytes consumed and the outcome
+    fn feed(&mut self, input: &[u8]) -> (usize, Result<Step>) {
+        for (i, &b) in input.iter().enumerate() {
+            match self.state {
+                State::Value {
+                    in_array,
+                    depth,
+                    in_string,
+                    escape,
+                } => {
+                    self.value.push(b);
+                    let depth = match (in_string, b) {
+                        (false, b'{') | (false, b'[') => depth + 1,
+                        (false, b'}') | (false, b']') => depth - 1,
+                        _ => depth,
+                    };
+                    if depth == 0 {
+                        self.state = match in_array {
+                            true => State::ArrayNext,
+                            false => State::LinesNext,
+                        };
+                        return (i + 1, Ok(Step::Value(std::mem::take(&mut self.value))));
+                    }
+                    self.state = State::Value {
+                        in_array,
+                        depth,
+                        in_string: in_string != (b == b'"' && !escape),
+                        escape: in_string && b == b'\\' && !escape,
+                    };
+                }
+                _ if b.is_ascii_whitespace() => {}
+                State::Start if b == b'[' => self.state = State::ArrayFirst,
+                State::Start | State::LinesNext if b == b'{' => self.begin_value(b, false),
+                State::ArrayFirst | State::ArrayValue if b == b'{' => self.begin_value(b, true),
+                State::ArrayFirst if b == b']' => {
+                    self.state = State::End;
+                    return (i + 1, Ok(Step::End));
+                }
+                State::ArrayNext if b == b',' => self.state = State::ArrayValue,
+                State::ArrayNext if b == b']' => {
+                    self.state = State::End;
+                    return (i + 1, Ok(Step::End));
+                }
+                state => return (i + 1, Err(unexpected(b, state))),
+            }
+        }
+        (input.len(), Ok(Step::NeedMore))
+    }
+
+    fn begin_value(&mut self, b: u8, in_array: bool) {
+        self.value.push(b);
+        self.state = State::Value {
+            in_array,
+            depth: 1,
+            in_string: false,
+            escape: false,
+        };
+    }
+
+    /// Check the input can end in the current state
+    fn finish(&self) -> Result<()> {
+        match self.state {
+            State::Start | State::LinesNext | State::End => Ok(()),
+            _ => Err(Error::PayloadDecodeError {
+                source: "Unexpected end of the batch".into(),
+            }),
+        }
+    }
+}
+
+fn unexpected(b: u8, state: State) -> Error {
+    let expected = match state {
+        State::Start => "'[' or '{'",
+        State::ArrayFirst => "'{' or ']'",
+        State::ArrayValue | State::LinesNext => "'{'",
+        State::ArrayNext => "',' or ']'",
+        _ => "the end of the batch",
+    };
+    Error::PayloadDecodeError {
+        source: format!(
+            "Unexpected character {:?}, expected {}",
+            b as char, expected
+        )
+        .into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AttributesReader, EventBuilder, EventBuilderV10};
+    use serde_json::json;
+
+    fn event(id: &str) -> Event {
+        EventBuilderV10::new()
+            .id(id)
+            .source("http://localhost/")
+            .ty("example.test")
+            .data("application/json", json!({"text": "}{\"]["}))
+            .build()
+            .unwrap()
+    }
+
+    /// Reader returning a single byte at a time
+    struct ByteReader<'a>(&'a [u8]);
+
+    impl Read for ByteReader<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            match self.0.split_first() {
+                Some((b, rest)) => {
+                    buf[0] = *b;
+                    self.0 = rest;
+                    Ok(1)
+                }
+                None => Ok(0),
+            }
+        }
+    }
+
+    fn ids<R: Read>(reader: BatchReader<R>) -> Vec<String> {
+        reader
+            .map(|e| e.unwrap().get_id().to_string())
+            .collect::<Vec<String>>()
+    }
+
+    #[test]
+    fn json_batch() {
+        let json = serde_json::to_string_pretty(&vec![event("1"), event("2")]).unwrap();
+        assert_eq!(vec!["1", "2"], ids(BatchReader::new(json.as_bytes())));
+        assert_eq!(
+            vec![event("1"), event("2")],
+            BatchReader::new(ByteReader(json.as_bytes()))
+                .collect::<Result<Vec<Event>>>()
+                .unwrap()
+        );
+
+        assert!(ids(BatchReader::new(" [ ] ".as_bytes())).is_empty());
+        assert!(ids(BatchReader::new("".as_bytes())).is_empty());
+    }
+
+    #[test]
+    fn newline_delimited() {
+        let ndjson = format!(
+            "{}\n{}\n",
+            serde_json::to_string(&event("1")).unwrap(),
+            serde_json::to_string(&event("2")).unwrap()
+        );
+        assert_eq!(vec!["1", "2"], ids(BatchReader::new(ndjson.as_bytes())));
+        assert_eq!(
+            vec!["1", "2"],
+            ids(BatchReader::new(ByteReader(ndjson.as_bytes())))
+        );
+    }
+
+    #[test]
+    fn invalid_batches() {
+        let event = serde_json::to_string(&event("1")).unwrap();
+        for invalid in [
+            format!("[{}", event),
+            format!("[{},", event),
+            format!("[{}]]", event),
+            format!("[{} {}]", event, event),
+            format!("[{}, 1]", event),
+            String::from("[{\"id\": \"1\"}]"),
+            String::from("\"event\""),
+        ] {
+            let results: Vec<Result<Event>> = BatchReader::new(invalid.as_bytes()).collect();
+            assert!(results.last().unwrap().is_err(), "{} is valid", invalid);
+            assert!(results.iter().filter(|r| r.is_err()).count() == 1);
+        }
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn stream() {
+        use futures::StreamExt;
+
+        let json = serde_json::to_string(&vec![event("1"), event("2")]).unwrap();
+        let events: Vec<Result<Event>> =
+            StreamExt::collect(BatchReader::new(json.as_bytes())).await;
+        assert_eq!(
+            vec![event("1"), event("2")],
+            events.into_iter().collect::<Result<Vec<Event>>>().unwrap()
+        );
+    }
+}
@@ -52,4 +52,22 @@ pub enum Error {
         attribute_name: &'static str,
         source: url::ParseError,
     },
+    #[snafu(display(
+        "Error while setting attribute '{}' with uriref type: {}",
+        attribute_name,
+        source
+    ))]
+    ParseUriReferenceError {
+        attribute_name: &'static str,
+        source: super::InvalidUriReference,
+    },
+    #[snafu(display(
+        "Invalid event: {}",
+        errors
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<String>>()
+            .join(", ")
+    ))]
+    InvalidEvent { errors: Vec<super::ValidationError> },
 }
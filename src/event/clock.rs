@@ -0,0 +1,157 @@
+use chrono::{DateTime, Duration, Utc};
+use std::sync::Mutex;
+
+/// Source of the event `time`, used by [`EventBuilderDefaults`](super::EventBuilderDefaults).
+///
+/// This trait is implemented for closures returning a [`DateTime<Utc>`], so an ad hoc clock can be
+/// provided without implementing it.
+///
+/// ```
+/// use chrono::{Duration, TimeZone, Utc};
+/// use cloudevents::event::{Clock, EventBuilderDefaults, FixedClock};
+/// use cloudevents::{AttributesReader, EventBuilder};
+/// use std::sync::Arc;
+///
+/// let clock = Arc::new(FixedClock::new(Utc.ymd(2020, 1, 1).and_hms(0, 0, 0)));
+/// let defaults = EventBuilderDefaults::new().clock(clock.clone());
+///
+/// let event = defaults.builder_v10("example.test").build().unwrap();
+/// assert_eq!(Some(&Utc.ymd(2020, 1, 1).and_hms(0, 0, 0)), event.get_time());
+///
+/// clock.advance(Duration::seconds(1));
+/// assert_eq!(Utc.ymd(2020, 1, 1).and_hms(0, 0, 1), defaults.now());
+/// ```
+pub trait Clock {
+    /// Get the current time
+    fn now(&self) -> DateTime<Utc>;
+}
+
+impl<F> Clock for F
+where
+    F: Fn() -> DateTime<Utc>,
+{
+    fn now(&self) -> DateTime<Utc> {
+        self()
+    }
+}
+
+impl<C: Clock + ?Sized> Clock for std::sync::Arc<C> {
+    fn now(&self) -> DateTime<Utc> {
+        (**self).now()
+    }
+}
+
+/// [`Clock`] reading the system wall clock, the default of [`Event::default`](super::Event::default)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// [`Clock`] frozen at a given time, which changes only when explicitly set or advanced.
+/// Useful to write reproducible tests of serialized events.
+#[derive(Debug)]
+pub struct FixedClock {
+    time: Mutex<DateTime<Utc>>,
+}
+
+impl FixedClock {
+    /// Create a new [`FixedClock`] frozen at `time`
+    pub fn new(time: DateTime<Utc>) -> Self {
+        FixedClock {
+            time: Mutex::new(time),
+        }
+    }
+
+    /// Set the current time
+    pub fn set(&self, time: DateTime<Utc>) {
+        *self.time.lock().unwrap() = time;
+    }
+
+    /// Move the current time forward by `duration`
+    pub fn advance(&self, duration: Duration) {
+        let mut time = self.time.lock().unwrap();
+        *time = *time + duration;
+    }
+}
+
+impl Clock for FixedClock {
+    fn now(&self) -> DateTime<Utc> {
+        *self.time.lock().unwrap()
+    }
+}
+
+/// [`Clock`] wrapping another clock, usually the [`SystemClock`], so it never goes backwards:
+/// when the wrapped clock is adjusted back in time, the last returned time is returned until the
+/// wrapped clock catches up
+#[derive(Debug, Default)]
+pub struct MonotonicClock<C = SystemClock> {
+    clock: C,
+    last: Mutex<Option<DateTime<Utc>>>,
+}
+
+impl<C: Clock> MonotonicClock<C> {
+    /// Create a new [`MonotonicClock`] wrapping `clock`
+    pub fn new(clock: C) -> Self {
+        MonotonicClock {
+            clock,
+            last: Mutex::new(None),
+        }
+    }
+}
+
+impl<C: Clock> Clock for MonotonicClock<C> {
+    fn now(&self) -> DateTime<Utc> {
+        let now = self.clock.now();
+        let mut last = self.last.lock().unwrap();
+        let now = match *last {
+            Some(last) if last > now => last,
+            _ => now,
+        };
+        *last = Some(now);
+        now
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use std::sync::Arc;
+
+    #[test]
+    fn fixed_clock() {
+        let clock = FixedClock::new(Utc.ymd(2020, 1, 1).and_hms(0, 0, 0));
+        assert_eq!(clock.now(), clock.now());
+
+        clock.advance(Duration::minutes(1));
+        assert_eq!(Utc.ymd(2020, 1, 1).and_hms(0, 1, 0), clock.now());
+
+        clock.set(Utc.ymd(2021, 1, 1).and_hms(0, 0, 0));
+        assert_eq!(Utc.ymd(2021, 1, 1).and_hms(0, 0, 0), clock.now());
+    }
+
+    #[test]
+    fn monotonic_clock() {
+        let wall = Arc::new(FixedClock::new(Utc.ymd(2020, 1, 1).and_hms(0, 0, 10)));
+        let clock = MonotonicClock::new(wall.clone());
+        assert_eq!(Utc.ymd(2020, 1, 1).and_hms(0, 0, 10), clock.now());
+
+        wall.set(Utc.ymd(2020, 1, 1).and_hms(0, 0, 5));
+        assert_eq!(Utc.ymd(2020, 1, 1).and_hms(0, 0, 10), clock.now());
+
+        wall.set(Utc.ymd(2020, 1, 1).and_hms(0, 0, 15));
+        assert_eq!(Utc.ymd(2020, 1, 1).and_hms(0, 0, 15), clock.now());
+    }
+
+    #[test]
+    fn system_clock() {
+        let clock = MonotonicClock::<SystemClock>::default();
+        let first = clock.now();
+        assert!(first <= clock.now());
+        assert!(SystemClock.now() >= first);
+    }
+}
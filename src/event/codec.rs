@@ -0,0 +1,174 @@
+use super::Data;
+use serde_json::Value;
+use std::sync::{Arc, RwLock};
+
+/// Error returned by a [`DataCodec`]
+pub type CodecError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Decoder of the `data` of a `datacontenttype`, used by
+/// [`Event::data_as`](super::Event::data_as) to read the data of the media types which are not
+/// JSON.
+///
+/// The codec decodes the data to a JSON [`Value`], which is then deserialized to the requested
+/// type. It's implemented by the functions with the signature of [`DataCodec::decode`].
+pub trait DataCodec: Send + Sync {
+    /// Decode `data` of the `content_type` media type
+    fn decode(&self, content_type: &str, data: &Data) -> Result<Value, CodecError>;
+}
+
+impl<F> DataCodec for F
+where
+    F: Fn(&str, &Data) -> Result<Value, CodecError> + Send + Sync,
+{
+    fn decode(&self, content_type: &str, data: &Data) -> Result<Value, CodecError> {
+        self(content_type, data)
+    }
+}
+
+/// [`DataCodec`] of text media types like `text/plain`, decoding the data to a JSON string
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TextCodec;
+
+impl DataCodec for TextCodec {
+    fn decode(&self, _: &str, data: &Data) -> Result<Value, CodecError> {
+        match data {
+            Data::String(s) => Ok(Value::String(s.clone())),
+            Data::Binary(b) => Ok(Value::String(String::from_utf8(b.clone())?)),
+            Data::Json(v) => Ok(v.clone()),
+        }
+    }
+}
+
+/// Registry of [`DataCodec`]s keyed by media type.
+///
+/// The JSON media types are always decoded as JSON, a codec registered for any other media
+/// type is used by [`Event::data_as`](super::Event::data_as) once the registry is installed with
+/// [`set_data_codecs`].
+///
+/// ```
+/// use cloudevents::event::{set_data_codecs, CodecError, Data, DataCodecs, TextCodec};
+/// use cloudevents::Event;
+/// use serde_json::{json, Value};
+///
+/// /// Decode `key=value` lines to a JSON object
+/// fn properties(_: &str, data: &Data) -> Result<Value, CodecError> {
+///     let text = match data {
+///         Data::String(s) => s.clone(),
+///         Data::Binary(b) => String::from_utf8(b.clone())?,
+///         Data::Json(v) => return Ok(v.clone()),
+///     };
+///     Ok(text
+///         .lines()
+///         .filter_map(|line| line.split_once('='))
+///         .map(|(k, v)| (k.to_string(), Value::from(v)))
+///         .collect())
+/// }
+///
+/// set_data_codecs(
+///     DataCodecs::new()
+///         .with("text/plain", TextCodec)
+///         .with("text/x-java-properties", properties),
+/// );
+///
+/// let mut event = Event::default();
+/// event.write_data("text/x-java-properties", "hello=world");
+/// assert_eq!(
+///     Some(json!({"hello": "world"})),
+///     event.data_as::<Value>().unwrap()
+/// );
+/// ```
+#[derive(Clone, Default)]
+pub struct DataCodecs {
+    codecs: Vec<(String, Arc<dyn DataCodec>)>,
+}
+
+impl DataCodecs {
+    /// Create a new empty registry
+    pub fn new() -> Self {
+        DataCodecs::default()
+    }
+
+    /// Register `codec` for the `content_type` media type, replacing the codec previously
+    /// registered for it
+    pub fn with(
+        mut self,
+        content_type: impl Into<String>,
+        codec: impl DataCodec + 'static,
+    ) -> Self {
+        let content_type = content_type.into();
+        self.codecs
+            .retain(|(ct, _)| !ct.eq_ignore_ascii_case(&content_type));
+        self.codecs.push((content_type, Arc::new(codec)));
+        self
+    }
+
+    /// Get the codec registered for the media type of `content_type`, ignoring its parameters
+    /// and case
+    pub fn get(&self, content_type: &str) -> Option<&dyn DataCodec> {
+        let media_type = content_type.split(';').next().unwrap_or_default().trim();
+        self.codecs
+            .iter()
+            .find(|(ct, _)| ct.eq_ignore_ascii_case(media_type))
+            .map(|(_, codec)| codec.as_ref())
+    }
+}
+
+impl std::fmt::Debug for DataCodecs {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list()
+            .entries(self.codecs.iter().map(|(ct, _)| ct))
+            .finish()
+    }
+}
+
+static CODECS: RwLock<Option<Arc<DataCodecs>>> = RwLock::new(None);
+
+/// Install the [`DataCodecs`] used by [`Event::data_as`](super::Event::data_as), replacing the
+/// previous one
+pub fn set_data_codecs(codecs: DataCodecs) {
+    if let Ok(mut current) = CODECS.write() {
+        *current = Some(Arc::new(codecs));
+    }
+}
+
+/// Remove the installed [`DataCodecs`], so only the JSON data can be decoded
+pub fn clear_data_codecs() {
+    if let Ok(mut current) = CODECS.write() {
+        *current = None;
+    }
+}
+
+/// Decode `data` with the codec installed for `content_type`, returning `None` if there's none
+pub(crate) fn decode_data(content_type: &str, data: &Data) -> Option<Result<Value, CodecError>> {
+    let codecs = CODECS.read().ok()?.clone()?;
+    let codec = codecs.get(content_type)?;
+    Some(codec.decode(content_type, data))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registry() {
+        let codecs = DataCodecs::new()
+            .with(
+                "text/plain",
+                |_: &str, _: &Data| -> Result<Value, CodecError> { Err("replaced".into()) },
+            )
+            .with("Text/Plain", TextCodec);
+        assert_eq!(1, codecs.codecs.len());
+        assert!(codecs.get("application/json").is_none());
+
+        let codec = codecs.get("text/plain; charset=utf-8").unwrap();
+        assert_eq!(
+            Value::from("hello"),
+            codec
+                .decode("text/plain", &Data::Binary(b"hello".to_vec()))
+                .unwrap()
+        );
+        assert!(codec
+            .decode("text/plain", &Data::Binary(vec![0xff]))
+            .is_err());
+    }
+}
@@ -0,0 +1,197 @@
+use super::{AttributesReader, AttributesWriter, Data, Event, ExtensionValue, SpecVersion};
+
+const DATACONTENTENCODING: &str = "datacontentencoding";
+
+/// Represents a change applied while converting an [`Event`] between spec versions,
+/// which may cause a loss of information or a different interpretation of the event
+#[derive(Debug, Clone, PartialEq)]
+pub enum LossyChange {
+    /// The v0.3 `datacontentencoding` with the provided value couldn't be applied to the data,
+    /// so it's kept as an extension in v1.0
+    UnsupportedDataContentEncoding { value: String },
+    /// The extension has the name of a context attribute of the target spec version
+    ExtensionConflict { extension_name: String },
+}
+
+/// Convert `event` to `spec_version`, following the spec version conversion rules:
+///
+/// * `schemaurl` (v0.3) is mapped to `dataschema` (v1.0) and back
+/// * the v0.3 `datacontentencoding`, read as an extension in binary mode, is removed converting
+///   to v1.0, decoding the base64 data
+/// * extensions are preserved
+pub(crate) fn convert(mut event: Event, spec_version: SpecVersion) -> (Event, Vec<LossyChange>) {
+    if event.get_specversion() == spec_version {
+        return (event, Vec::new());
+    }
+
+    let mut changes = Vec::new();
+
+    if spec_version == SpecVersion::V10 {
+        if let Some(change) = apply_datacontentencoding(&mut event) {
+            changes.push(change);
+        }
+    }
+
+    event.attributes = match spec_version {
+        SpecVersion::V03 => event.attributes.into_v03(),
+        SpecVersion::V10 => event.attributes.into_v10(),
+    };
+
+    let attribute_names = spec_version.attribute_names();
+    let mut conflicts: Vec<&String> = event
+        .iter_extensions()
+        .map(|(name, _)| name)
+        .filter(|name| {
+            attribute_names.contains(&name.as_str())
+                || (spec_version == SpecVersion::V03 && name.as_str() == DATACONTENTENCODING)
+        })
+        .collect();
+    conflicts.sort();
+    changes.extend(
+        conflicts
+            .into_iter()
+            .map(|name| LossyChange::ExtensionConflict {
+                extension_name: name.clone(),
+            }),
+    );
+
+    (event, changes)
+}
+
+fn apply_datacontentencoding(event: &mut Event) -> Option<LossyChange> {
+    let value = match event.get_extension(DATACONTENTENCODING)? {
+        ExtensionValue::String(s) => s.clone(),
+        other => {
+            return Some(LossyChange::UnsupportedDataContentEncoding {
+                value: format!("{:?}", other),
+            })
+        }
+    };
+    if !value.eq_ignore_ascii_case("base64") {
+        return Some(LossyChange::UnsupportedDataContentEncoding { value });
+    }
+
+    let decoded = match &event.data {
+        Some(Data::String(s)) => base64::decode(s).ok(),
+        Some(Data::Binary(b)) => base64::decode(b).ok(),
+        None => Some(Vec::new()),
+        Some(Data::Json(_)) => None,
+    };
+    let decoded = match decoded {
+        Some(decoded) => decoded,
+        None => return Some(LossyChange::UnsupportedDataContentEncoding { value }),
+    };
+
+    if event.data.is_some() {
+        event.data = Some(
+            Data::from_binary(event.get_datacontenttype(), &decoded)
+                .unwrap_or(Data::Binary(decoded)),
+        );
+    }
+    event.remove_extension(DATACONTENTENCODING);
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EventBuilder, EventBuilderV03, EventBuilderV10};
+    use serde_json::json;
+    use url::Url;
+
+    #[test]
+    fn roundtrip_preserves_extensions() {
+        let v10 = EventBuilderV10::new()
+            .id("0001")
+            .source("http://localhost/")
+            .ty("example.test")
+            .data_with_schema(
+                "application/json",
+                Url::parse("http://localhost/schema").unwrap(),
+                json!({"hello": "world"}),
+            )
+            .extension("someint", 10)
+            .build()
+            .unwrap();
+
+        let (v03, changes) = v10.clone().convert_to(SpecVersion::V03);
+        assert!(changes.is_empty());
+        assert_eq!(SpecVersion::V03, v03.get_specversion());
+        assert_eq!(v10.get_dataschema(), v03.get_dataschema());
+        assert_eq!(
+            Some(&ExtensionValue::Integer(10)),
+            v03.get_extension("someint")
+        );
+
+        let (back, changes) = v03.convert_to(SpecVersion::V10);
+        assert!(changes.is_empty());
+        assert_eq!(v10, back);
+    }
+
+    #[test]
+    fn datacontentencoding_is_decoded() {
+        let v03 = EventBuilderV03::new()
+            .id("0001")
+            .source("http://localhost/")
+            .ty("example.test")
+            .data("application/octet-stream", base64::encode(b"hello"))
+            .extension("datacontentencoding", "base64")
+            .build()
+            .unwrap();
+
+        let (v10, changes) = v03.convert_to(SpecVersion::V10);
+        assert!(changes.is_empty());
+        assert!(v10.get_extension("datacontentencoding").is_none());
+        assert_eq!(Some(Data::Binary(b"hello".to_vec())), v10.get_data());
+    }
+
+    #[test]
+    fn lossy_changes() {
+        let v03 = EventBuilderV03::new()
+            .id("0001")
+            .source("http://localhost/")
+            .ty("example.test")
+            .data("text/plain", "hello")
+            .extension("datacontentencoding", "gzip")
+            .extension("dataschema", "http://localhost/schema")
+            .build()
+            .unwrap();
+
+        let (v10, changes) = v03.convert_to(SpecVersion::V10);
+        assert_eq!(
+            vec![
+                LossyChange::UnsupportedDataContentEncoding {
+                    value: String::from("gzip")
+                },
+                LossyChange::ExtensionConflict {
+                    extension_name: String::from("dataschema")
+                },
+            ],
+            changes
+        );
+        assert_eq!(
+            Some(&ExtensionValue::from("gzip")),
+            v10.get_extension("datacontentencoding")
+        );
+    }
+
+    #[test]
+    fn conflicting_extension_to_v03() {
+        let mut v10 = Event::default();
+        v10.set_extension("schemaurl", "http://localhost/schema");
+        v10.set_extension("datacontentencoding", "base64");
+
+        let (_, changes) = v10.convert_to(SpecVersion::V03);
+        assert_eq!(
+            vec![
+                LossyChange::ExtensionConflict {
+                    extension_name: String::from("datacontentencoding")
+                },
+                LossyChange::ExtensionConflict {
+                    extension_name: String::from("schemaurl")
+                },
+            ],
+            changes
+        );
+    }
+}
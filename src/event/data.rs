@@ -1,6 +1,21 @@
-use std::convert::{Into, TryFrom};
+use bytes::Bytes;
+use snafu::Snafu;
+use std::convert::TryFrom;
 
-/// Event [data attribute](https://github.com/cloudevents/spec/blob/master/spec.md#event-data) representation
+/// Event [data attribute](https://github.com/cloudevents/spec/blob/master/spec.md#event-data) representation.
+///
+/// When serialized to the JSON format, [`Data::Binary`] is base64 encoded
+/// (`data_base64` in v1.0, `datacontentencoding: base64` in v0.3),
+/// while [`Data::String`] and [`Data::Json`] are written as the `data` field.
+///
+/// ```
+/// use cloudevents::event::Data;
+/// use serde_json::json;
+///
+/// assert_eq!(Data::Binary(vec![1, 2]), Data::from(&[1u8, 2][..]));
+/// assert_eq!(Data::String(String::from("hello")), Data::from("hello"));
+/// assert_eq!(Data::Json(json!({"hello": "world"})), Data::from(json!({"hello": "world"})));
+/// ```
 #[derive(Debug, PartialEq, Clone)]
 pub enum Data {
     /// Event has a binary payload
@@ -32,6 +47,17 @@ impl Data {
         Ok(base64::decode(&i)?.into())
     }
 
+    /// Create a new [`Data`] from the payload `bytes` of a binary mode message, parsing it as JSON
+    /// if `content_type` is a JSON content type, or absent. A binary payload is moved without
+    /// copying if `bytes` is the unique owner of its buffer.
+    pub fn from_bytes(content_type: Option<&str>, bytes: Bytes) -> Result<Self, serde_json::Error> {
+        if is_json_content_type(content_type.unwrap_or("application/json")) {
+            serde_json::from_slice::<serde_json::Value>(&bytes).map(Data::Json)
+        } else {
+            Ok(Data::Binary(Vec::from(bytes)))
+        }
+    }
+
     pub fn from_binary<I>(content_type: Option<&str>, i: I) -> Result<Self, serde_json::Error>
     where
         I: AsRef<[u8]>,
@@ -45,25 +71,66 @@ impl Data {
     }
 }
 
+/// Represents an error while deserializing [`Data`] into a user type
+#[derive(Debug, Snafu)]
+pub enum DataError {
+    #[snafu(display(
+        "Cannot deserialize data with datacontenttype '{}', expected a JSON content type or a \
+         content type with a registered codec",
+        content_type
+    ))]
+    UnsupportedContentType { content_type: String },
+    #[snafu(display(
+        "Error while decoding data with datacontenttype '{}': {}",
+        content_type,
+        source
+    ))]
+    DecodeError {
+        content_type: String,
+        source: super::CodecError,
+    },
+    #[snafu(display(
+        "Error while deserializing data with datacontenttype '{}': {}",
+        content_type,
+        source
+    ))]
+    DeserializeError {
+        content_type: String,
+        source: serde_json::Error,
+    },
+}
+
 pub(crate) fn is_json_content_type(ct: &str) -> bool {
     ct == "application/json" || ct == "text/json" || ct.ends_with("+json")
 }
 
-impl Into<Data> for serde_json::Value {
-    fn into(self) -> Data {
-        Data::Json(self)
+impl From<serde_json::Value> for Data {
+    fn from(value: serde_json::Value) -> Self {
+        Data::Json(value)
+    }
+}
+
+impl From<Vec<u8>> for Data {
+    fn from(value: Vec<u8>) -> Self {
+        Data::Binary(value)
+    }
+}
+
+impl From<&[u8]> for Data {
+    fn from(value: &[u8]) -> Self {
+        Data::Binary(value.to_vec())
     }
 }
 
-impl Into<Data> for Vec<u8> {
-    fn into(self) -> Data {
-        Data::Binary(self)
+impl From<String> for Data {
+    fn from(value: String) -> Self {
+        Data::String(value)
     }
 }
 
-impl Into<Data> for String {
-    fn into(self) -> Data {
-        Data::String(self)
+impl From<&str> for Data {
+    fn from(value: &str) -> Self {
+        Data::String(value.to_string())
     }
 }
 
@@ -0,0 +1,264 @@
+use super::attributes::{default_hostname, try_default_hostname};
+use super::{
+    Attributes, AttributesV10, Clock, Event, EventBuilder, EventBuilderV03, EventBuilderV10,
+    IdGenerator, InvalidUriReference, SystemClock, TryIntoUriReference, UriReference,
+    UuidV4Generator,
+};
+use chrono::{DateTime, Utc};
+use std::fmt;
+use std::sync::Arc;
+
+/// Defaults used to create new events: the `source`, a prefix prepended to the event `type`,
+/// the generator of `id` and the clock setting `time`.
+///
+/// By default, `id` is a random UUID v4, `source` is the hostname, `time` is read from the
+/// [`SystemClock`] and the `type` has no prefix, like for [`Event::default`](super::Event::default).
+/// Overriding the defaults is useful for deterministic tests and for producers sending events
+/// on behalf of several tenants.
+///
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// use cloudevents::event::EventBuilderDefaults;
+/// use cloudevents::{AttributesReader, EventBuilder};
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+///
+/// let counter = AtomicUsize::new(0);
+/// let defaults = EventBuilderDefaults::new()
+///     .source("/tenants/acme")
+///     .unwrap()
+///     .type_prefix("com.example.")
+///     .id_generator(move || counter.fetch_add(1, Ordering::SeqCst).to_string())
+///     .clock(|| Utc.ymd(2020, 1, 1).and_hms(0, 0, 0));
+///
+/// let event = defaults.builder_v10("order.created").build().unwrap();
+///
+/// assert_eq!("0", event.get_id());
+/// assert_eq!("/tenants/acme", event.get_source().as_str());
+/// assert_eq!("com.example.order.created", event.get_type());
+/// assert_eq!(Some(&Utc.ymd(2020, 1, 1).and_hms(0, 0, 0)), event.get_time());
+///
+/// assert_eq!("1", defaults.builder_v10("order.shipped").build().unwrap().get_id());
+/// ```
+#[derive(Clone)]
+pub struct EventBuilderDefaults {
+    source: UriReference,
+    type_prefix: String,
+    id_generator: Arc<dyn IdGenerator + Send + Sync>,
+    clock: Arc<dyn Clock + Send + Sync>,
+}
+
+impl EventBuilderDefaults {
+    /// Create new [`EventBuilderDefaults`] with the same defaults used by
+    /// [`Event::default`](super::Event::default)
+    pub fn new() -> Self {
+        EventBuilderDefaults::with_source(default_hostname())
+    }
+
+    /// Create new [`EventBuilderDefaults`] like [`EventBuilderDefaults::new`], failing instead of
+    /// falling back to `http://localhost/` if the default `source` is not a valid URI-reference
+    pub fn try_new() -> Result<Self, InvalidUriReference> {
+        Ok(EventBuilderDefaults::with_source(try_default_hostname()?))
+    }
+
+    fn with_source(source: UriReference) -> Self {
+        EventBuilderDefaults {
+            source,
+            type_prefix: String::new(),
+            id_generator: Arc::new(UuidV4Generator),
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    /// Set the default `source`, failing if it's not a valid URI-reference
+    pub fn source(mut self, source: impl TryIntoUriReference) -> Result<Self, InvalidUriReference> {
+        self.source = source.into_uri_reference()?;
+        Ok(self)
+    }
+
+    /// Set the prefix prepended as is to the event `type`
+    pub fn type_prefix(mut self, type_prefix: impl Into<String>) -> Self {
+        self.type_prefix = type_prefix.into();
+        self
+    }
+
+    /// Set the [`IdGenerator`] of the event `id`
+    pub fn id_generator(mut self, id_generator: impl IdGenerator + Send + Sync + 'static) -> Self {
+        self.id_generator = Arc::new(id_generator);
+        self
+    }
+
+    /// Set the [`Clock`] used to set the event `time`
+    pub fn clock(mut self, clock: impl Clock + Send + Sync + 'static) -> Self {
+        self.clock = Arc::new(clock);
+        self
+    }
+
+    /// Get the default `source`
+    pub fn get_source(&self) -> &UriReference {
+        &self.source
+    }
+
+    /// Get the prefix of the event `type`
+    pub fn get_type_prefix(&self) -> &str {
+        &self.type_prefix
+    }
+
+    /// Generate a new `id`
+    pub fn next_id(&self) -> String {
+        self.id_generator.generate()
+    }
+
+    /// Get the current time from the clock
+    pub fn now(&self) -> DateTime<Utc> {
+        self.clock.now()
+    }
+
+    /// Get the event `type` with the configured prefix
+    pub fn ty(&self, ty: &str) -> String {
+        format!("{}{}", self.type_prefix, ty)
+    }
+
+    /// Create a new [`Event`] like [`Event::default`], using these defaults
+    pub fn event(&self) -> Event {
+        Event {
+            attributes: Attributes::V10(AttributesV10 {
+                id: self.next_id(),
+                ty: self.ty("type").into(),
+                source: self.source.clone(),
+                time: Some(self.now()),
+                ..AttributesV10::default()
+            }),
+            data: None,
+        }
+    }
+
+    /// Create a new [`EventBuilderV10`] with a new `id`, the default `source`, the current `time`
+    /// and `type` prefixed with the configured prefix
+    pub fn builder_v10(&self, ty: &str) -> EventBuilderV10 {
+        EventBuilderV10::new()
+            .id(self.next_id())
+            .source(self.source.clone())
+            .ty(self.ty(ty))
+            .time(self.now())
+    }
+
+    /// Create a new [`EventBuilderV03`] with a new `id`, the default `source`, the current `time`
+    /// and `type` prefixed with the configured prefix
+    pub fn builder_v03(&self, ty: &str) -> EventBuilderV03 {
+        EventBuilderV03::new()
+            .id(self.next_id())
+            .source(self.source.clone())
+            .ty(self.ty(ty))
+            .time(self.now())
+    }
+}
+
+impl Default for EventBuilderDefaults {
+    fn default() -> Self {
+        EventBuilderDefaults::new()
+    }
+}
+
+impl fmt::Debug for EventBuilderDefaults {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EventBuilderDefaults")
+            .field("source", &self.source)
+            .field("type_prefix", &self.type_prefix)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::attributes::{hostname_uri_reference, page_origin};
+    use crate::event::{AttributesReader, AttributesWriter, SpecVersion};
+    use chrono::TimeZone;
+    use uuid::Uuid;
+
+    #[test]
+    fn default_values() {
+        let defaults = EventBuilderDefaults::default();
+        let event = defaults.builder_v03("example.test").build().unwrap();
+
+        assert_eq!(SpecVersion::V03, event.get_specversion());
+        assert_eq!(default_hostname(), *event.get_source());
+        assert_eq!("example.test", event.get_type());
+        assert!(Uuid::parse_str(event.get_id()).is_ok());
+        assert!(event.get_time().is_some());
+        assert_ne!(defaults.next_id(), defaults.next_id());
+    }
+
+    #[test]
+    fn deterministic_values() {
+        let time = Utc.ymd(2020, 1, 1).and_hms(0, 0, 0);
+        let defaults = EventBuilderDefaults::new()
+            .source("/tenants/acme")
+            .unwrap()
+            .type_prefix("com.example.")
+            .id_generator(|| String::from("fixed"))
+            .clock(move || time);
+
+        let event = defaults
+            .builder_v10("order.created")
+            .subject("order-1")
+            .build()
+            .unwrap();
+        let expected = EventBuilderV10::new()
+            .id("fixed")
+            .source("/tenants/acme")
+            .ty("com.example.order.created")
+            .time(time)
+            .subject("order-1")
+            .build()
+            .unwrap();
+        assert_eq!(expected, event);
+        assert_eq!(
+            expected,
+            defaults
+                .clone()
+                .builder_v10("order.created")
+                .subject("order-1")
+                .build()
+                .unwrap()
+        );
+
+        let mut expected = Event::default();
+        expected.set_id("fixed");
+        expected.set_source(UriReference::parse("/tenants/acme").unwrap());
+        expected.set_type("com.example.type");
+        expected.set_time(Some(time));
+        assert_eq!(expected, defaults.event());
+
+        assert!(EventBuilderDefaults::new().source("not a uri").is_err());
+    }
+
+    #[test]
+    fn try_default_values() {
+        assert_eq!(
+            default_hostname(),
+            *EventBuilderDefaults::try_new().unwrap().get_source()
+        );
+        assert_eq!(
+            default_hostname(),
+            *Event::try_default().unwrap().get_source()
+        );
+        assert_eq!(
+            Ok("http://localhost/"),
+            hostname_uri_reference("http://localhost")
+                .as_ref()
+                .map(UriReference::as_str)
+        );
+        assert!(hostname_uri_reference("http://exotic host").is_err());
+
+        // The origin of the page carries the scheme the host lacks
+        assert_eq!(
+            Ok("https://example.com:8080/"),
+            hostname_uri_reference(&page_origin(Some(String::from("https://example.com:8080"))))
+                .as_ref()
+                .map(UriReference::as_str)
+        );
+        assert_eq!("http://localhost", page_origin(Some(String::from("null"))));
+        assert_eq!("http://localhost", page_origin(None));
+    }
+}
@@ -1,13 +1,22 @@
+use super::message::AttributesSerializer;
 use super::{
     AttributeValue, Attributes, AttributesIter, AttributesReader, AttributesV10, AttributesWriter,
-    Data, ExtensionValue, SpecVersion,
+    Data, DataError, EventBuilder, EventBuilderV10, ExtensionValue, IdGenerator,
+    InvalidUriReference, LossyChange, Redactor, SpecVersion, TryIntoUriReference, UriReference,
+    UuidV4Generator, ValidationError, ValidationWarning,
 };
-use crate::event::attributes::DataAttributesWriter;
-use chrono::{DateTime, Utc};
+use crate::event::codec::decode_data;
+use crate::event::{is_json_content_type, DataAttributesWriter};
+use crate::extensions::{CorrelationExtension, CAUSATIONID, CORRELATIONID};
+use crate::message::{EventFormat, MessageAttributeValue};
+use chrono::{DateTime, FixedOffset, Utc};
 use delegate::delegate;
-use std::collections::HashMap;
+use serde::de::DeserializeOwned;
+use std::collections::hash_map;
 use std::convert::TryFrom;
-use url::Url;
+
+const REQUEST_TYPE_SUFFIX: &str = ".request";
+const RESPONSE_TYPE_SUFFIX: &str = ".response";
 
 /// Data structure that represents a [CloudEvent](https://github.com/cloudevents/spec/blob/master/spec.md).
 /// It provides methods to get the attributes through [`AttributesReader`]
@@ -37,20 +46,22 @@ use url::Url;
 pub struct Event {
     pub(crate) attributes: Attributes,
     pub(crate) data: Option<Data>,
-    pub(crate) extensions: HashMap<String, ExtensionValue>,
 }
 
 impl AttributesReader for Event {
     delegate! {
         to self.attributes {
             fn get_id(&self) -> &str;
-            fn get_source(&self) -> &Url;
+            fn get_source(&self) -> &UriReference;
             fn get_specversion(&self) -> SpecVersion;
             fn get_type(&self) -> &str;
             fn get_datacontenttype(&self) -> Option<&str>;
-            fn get_dataschema(&self) -> Option<&Url>;
+            fn get_dataschema(&self) -> Option<&UriReference>;
             fn get_subject(&self) -> Option<&str>;
             fn get_time(&self) -> Option<&DateTime<Utc>>;
+            fn get_time_with_offset(&self) -> Option<DateTime<FixedOffset>>;
+            fn get_extension(&self, extension_name: &str) -> Option<&ExtensionValue>;
+            fn iter_extensions(&self) -> hash_map::Iter<'_, String, ExtensionValue>;
         }
     }
 }
@@ -59,10 +70,13 @@ impl AttributesWriter for Event {
     delegate! {
         to self.attributes {
             fn set_id(&mut self, id: impl Into<String>);
-            fn set_source(&mut self, source: impl Into<Url>);
+            fn set_source(&mut self, source: impl Into<UriReference>);
             fn set_type(&mut self, ty: impl Into<String>);
             fn set_subject(&mut self, subject: Option<impl Into<String>>);
             fn set_time(&mut self, time: Option<impl Into<DateTime<Utc>>>);
+            fn set_time_with_offset(&mut self, time: Option<impl Into<DateTime<FixedOffset>>>);
+            fn set_extension(&mut self, extension_name: &str, extension_value: impl Into<ExtensionValue>);
+            fn remove_extension(&mut self, extension_name: &str) -> Option<ExtensionValue>;
         }
     }
 }
@@ -72,13 +86,49 @@ impl Default for Event {
         Event {
             attributes: Attributes::V10(AttributesV10::default()),
             data: None,
-            extensions: HashMap::default(),
         }
     }
 }
 
 impl Event {
-    /// Returns an [`Iterator`] for [`Attributes`]
+    /// Create a new [`Event`] like [`Event::default`], failing instead of falling back to
+    /// `http://localhost/` if the default `source` is not a valid URI-reference.
+    ///
+    /// The default `source` is read from the [`SOURCE_ENV_VAR`](crate::event::SOURCE_ENV_VAR)
+    /// environment variable if set, skipping the hostname lookup.
+    pub fn try_default() -> Result<Self, InvalidUriReference> {
+        Ok(Event {
+            attributes: Attributes::V10(AttributesV10::try_default()?),
+            data: None,
+        })
+    }
+
+    /// Returns an [`Iterator`] over the context attributes followed by the extensions,
+    /// sorted by name, as `(name, value)` pairs.
+    ///
+    /// ```
+    /// use cloudevents::{EventBuilder, EventBuilderV10};
+    ///
+    /// let e = EventBuilderV10::new()
+    ///     .id("0001")
+    ///     .source("http://localhost/")
+    ///     .ty("example.demo")
+    ///     .extension("someint", 10)
+    ///     .extension("anotherext", "value")
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// let names: Vec<&str> = e.iter().map(|(name, _)| name).collect();
+    /// assert_eq!(
+    ///     vec!["specversion", "id", "type", "source", "anotherext", "someint"],
+    ///     names
+    /// );
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = (&str, AttributeValue<'_>)> {
+        self.attributes_iter()
+    }
+
+    /// Returns an [`Iterator`] for [`Attributes`], see [`Event::iter`]
     pub fn attributes_iter<'a>(&'a self) -> impl Iterator<Item = (&'a str, AttributeValue<'a>)> {
         match &self.attributes {
             Attributes::V03(a) => AttributesIter::IterV03(a.into_iter()),
@@ -86,13 +136,80 @@ impl Event {
         }
     }
 
+    /// Get the context attribute or extension named `name`
+    ///
+    /// ```
+    /// use cloudevents::event::AttributeValue;
+    /// use cloudevents::{EventBuilder, EventBuilderV10};
+    ///
+    /// let e = EventBuilderV10::new()
+    ///     .id("0001")
+    ///     .source("http://localhost/")
+    ///     .ty("example.demo")
+    ///     .extension("someint", 10)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(Some(AttributeValue::String("example.demo")), e.attribute("type"));
+    /// assert_eq!(Some(AttributeValue::Integer(&10)), e.attribute("someint"));
+    /// assert_eq!(None, e.attribute("subject"));
+    /// ```
+    pub fn attribute(&self, name: &str) -> Option<AttributeValue<'_>> {
+        self.attributes_iter()
+            .find(|(n, _)| *n == name)
+            .map(|(_, v)| v)
+    }
+
+    /// Set the context attribute or extension named `name`.
+    /// Setting `specversion` converts this `Event` to the provided spec version.
+    ///
+    /// ```
+    /// use cloudevents::message::MessageAttributeValue;
+    /// use cloudevents::{AttributesReader, Event};
+    ///
+    /// let mut e = Event::default();
+    /// e.set_attribute("subject", MessageAttributeValue::from(String::from("my-subject")))
+    ///     .unwrap();
+    /// e.set_attribute("someint", MessageAttributeValue::Integer(10))
+    ///     .unwrap();
+    ///
+    /// assert_eq!(Some("my-subject"), e.get_subject());
+    /// assert!(e.get_extension("someint").is_some());
+    /// ```
+    pub fn set_attribute(
+        &mut self,
+        name: &str,
+        value: MessageAttributeValue,
+    ) -> crate::message::Result<()> {
+        if name == "specversion" {
+            let spec_version = SpecVersion::try_from(value.to_string().as_str())?;
+            let event = std::mem::take(self);
+            *self = event.convert_to(spec_version).0;
+        } else if self.get_specversion().attribute_names().contains(&name) {
+            self.attributes.serialize_attribute(name, value)?;
+        } else {
+            self.attributes.set_extension(name, value);
+        }
+        Ok(())
+    }
+
     /// Remove `data`, `dataschema` and `datacontenttype` from this `Event`
     pub fn remove_data(&mut self) {
         self.data = None;
-        self.attributes.set_dataschema(None as Option<Url>);
+        self.attributes.set_dataschema(None as Option<UriReference>);
         self.attributes.set_datacontenttype(None as Option<String>);
     }
 
+    /// Reset this `Event` to be reused for another event, returning its `data`.
+    ///
+    /// `id` and `type` are emptied keeping their allocations, the extensions are removed keeping
+    /// the capacity of their map and the other optional attributes are removed. The spec version
+    /// and the `source` are kept, so the attributes must be set again before sending the event.
+    pub fn reset(&mut self) -> Option<Data> {
+        self.attributes.reset();
+        self.data.take()
+    }
+
     /// Write `data` into this `Event` with the specified `datacontenttype`.
     ///
     /// ```
@@ -105,7 +222,7 @@ impl Event {
     /// ```
     pub fn write_data(&mut self, datacontenttype: impl Into<String>, data: impl Into<Data>) {
         self.attributes.set_datacontenttype(Some(datacontenttype));
-        self.attributes.set_dataschema(None as Option<Url>);
+        self.attributes.set_dataschema(None as Option<UriReference>);
         self.data = Some(data.into());
     }
 
@@ -127,7 +244,7 @@ impl Event {
     pub fn write_data_with_schema(
         &mut self,
         datacontenttype: impl Into<String>,
-        dataschema: impl Into<Url>,
+        dataschema: impl Into<UriReference>,
         data: impl Into<Data>,
     ) {
         self.attributes.set_datacontenttype(Some(datacontenttype));
@@ -137,65 +254,225 @@ impl Event {
 
     /// Get `data` from this `Event`
     pub fn get_data<T: Sized + From<Data>>(&self) -> Option<T> {
-        match self.data.as_ref() {
-            Some(d) => Some(T::from(d.clone())),
-            None => None,
-        }
+        self.data.as_ref().map(|d| T::from(d.clone()))
     }
 
     /// Try to get `data` from this `Event`
     pub fn try_get_data<T: Sized + TryFrom<Data>>(&self) -> Result<Option<T>, T::Error> {
-        match self.data.as_ref() {
-            Some(d) => Some(T::try_from(d.clone())),
-            None => None,
-        }
-        .transpose()
+        self.data
+            .as_ref()
+            .map(|d| T::try_from(d.clone()))
+            .transpose()
+    }
+
+    /// Deserialize `data` from this `Event` into `T`, checking that `datacontenttype` is a JSON
+    /// content type. A missing `datacontenttype` is treated as `application/json`.
+    ///
+    /// The data of the other content types is decoded with the [`DataCodec`](super::DataCodec)
+    /// installed for the content type with [`set_data_codecs`](super::set_data_codecs).
+    ///
+    /// ```
+    /// use cloudevents::Event;
+    /// use serde::Deserialize;
+    /// use serde_json::json;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Greeting {
+    ///     hello: String,
+    /// }
+    ///
+    /// let mut e = Event::default();
+    /// e.write_data("application/json", json!({"hello": "world"}));
+    ///
+    /// let greeting: Greeting = e.data_as().unwrap().unwrap();
+    /// assert_eq!("world", greeting.hello);
+    /// ```
+    pub fn data_as<T: DeserializeOwned>(&self) -> Result<Option<T>, DataError> {
+        let data = match &self.data {
+            Some(data) => data,
+            None => return Ok(None),
+        };
+
+        let content_type = self
+            .attributes
+            .get_datacontenttype()
+            .unwrap_or("application/json");
+        let media_type = content_type.split(';').next().unwrap_or_default().trim();
+        let result = if is_json_content_type(media_type) {
+            match data {
+                Data::Json(v) => T::deserialize(v),
+                Data::String(s) => serde_json::from_str(s),
+                Data::Binary(b) => serde_json::from_slice(b),
+            }
+        } else {
+            match decode_data(content_type, data) {
+                Some(Ok(value)) => T::deserialize(value),
+                Some(Err(source)) => {
+                    return Err(DataError::DecodeError {
+                        content_type: content_type.to_string(),
+                        source,
+                    })
+                }
+                None => {
+                    return Err(DataError::UnsupportedContentType {
+                        content_type: content_type.to_string(),
+                    })
+                }
+            }
+        };
+
+        result
+            .map(Some)
+            .map_err(|source| DataError::DeserializeError {
+                content_type: content_type.to_string(),
+                source,
+            })
     }
 
     /// Transform this `Event` into the content of `data`
     pub fn into_data<T: Sized + TryFrom<Data>>(self) -> Result<Option<T>, T::Error> {
-        match self.data {
-            Some(d) => Some(T::try_from(d)),
-            None => None,
+        self.data.map(T::try_from).transpose()
+    }
+
+    /// Convert this `Event` to `spec_version`, returning the converted event together with
+    /// the [`LossyChange`]s applied during the conversion.
+    /// `schemaurl` and `dataschema` are mapped to each other, extensions are preserved and the v0.3
+    /// `datacontentencoding`, read as an extension in binary mode, is applied to the data
+    /// converting to v1.0.
+    ///
+    /// ```
+    /// use cloudevents::event::SpecVersion;
+    /// use cloudevents::{AttributesReader, Event};
+    ///
+    /// let (e, lossy_changes) = Event::default().convert_to(SpecVersion::V03);
+    /// assert_eq!(SpecVersion::V03, e.get_specversion());
+    /// assert!(lossy_changes.is_empty());
+    /// ```
+    pub fn convert_to(self, spec_version: SpecVersion) -> (Event, Vec<LossyChange>) {
+        super::conversion::convert(self, spec_version)
+    }
+
+    /// Create an [`EventBuilderV10`] of the reply to this `Event`, sent by `source`.
+    ///
+    /// The reply gets a new UUID v4 `id` and the `type` of this event with the `.request` suffix
+    /// replaced by `.response`, or with the `.response` suffix appended. The `correlationid` and
+    /// `causationid` extensions are set with [`CorrelationExtension::derive_from`], so the
+    /// requester can match the reply.
+    ///
+    /// ```
+    /// use cloudevents::{AttributesReader, EventBuilder, EventBuilderV10};
+    ///
+    /// let request = EventBuilderV10::new()
+    ///     .id("0001")
+    ///     .source("https://example.com/client")
+    ///     .ty("com.example.quote.request")
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// let reply = request
+    ///     .reply("https://example.com/quotes")
+    ///     .data("application/json", serde_json::json!({"price": 42}))
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!("com.example.quote.response", reply.get_type());
+    /// assert_eq!("https://example.com/quotes", reply.get_source().as_str());
+    /// assert_eq!(Some(&"0001".into()), reply.get_extension("correlationid"));
+    /// assert_eq!(Some(&"0001".into()), reply.get_extension("causationid"));
+    /// ```
+    pub fn reply(&self, source: impl TryIntoUriReference) -> EventBuilderV10 {
+        let ty = self.get_type();
+        let ty = match ty.strip_suffix(REQUEST_TYPE_SUFFIX) {
+            Some(prefix) => [prefix, RESPONSE_TYPE_SUFFIX].concat(),
+            None => [ty, RESPONSE_TYPE_SUFFIX].concat(),
+        };
+        let correlation = CorrelationExtension::derive_from(self);
+
+        EventBuilderV10::new()
+            .id(UuidV4Generator.generate())
+            .source(source)
+            .ty(ty)
+            .extension(CORRELATIONID, correlation.correlation_id())
+            .extension(CAUSATIONID, self.get_id())
+    }
+
+    /// Validate this `Event` against the CloudEvents spec, returning all the violations found.
+    /// `source` and `dataschema` are not checked, since they are parsed as URI-references when written.
+    ///
+    /// ```
+    /// use cloudevents::event::ValidationError;
+    /// use cloudevents::{AttributesWriter, Event};
+    ///
+    /// let mut e = Event::default();
+    /// assert!(e.validate().is_ok());
+    ///
+    /// e.set_id("");
+    /// e.set_extension("Invalid_Name", "value");
+    /// assert_eq!(2, e.validate().unwrap_err().len());
+    /// ```
+    pub fn validate(&self) -> Result<(), Vec<ValidationError>> {
+        let errors = super::validation::validate(self);
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            #[cfg(feature = "tracing")]
+            crate::logging::validation_failed(self, &errors);
+            Err(errors)
         }
-        .transpose()
     }
 
-    /// Get the [extension](https://github.com/cloudevents/spec/blob/master/spec.md#extension-context-attributes) named `extension_name`
-    pub fn get_extension(&self, extension_name: &str) -> Option<&ExtensionValue> {
-        self.extensions.get(extension_name)
+    /// Check this `Event` against the recommendations of the CloudEvents spec, returning the
+    /// deviations found, which unlike the violations of [`Event::validate`] don't make it invalid.
+    ///
+    /// ```
+    /// use cloudevents::{AttributesWriter, Event};
+    ///
+    /// let mut e = Event::default();
+    /// e.set_extension("averyveryverylongextensionname", "value");
+    /// assert!(e.validate().is_ok());
+    /// assert_eq!(1, e.lint().len());
+    /// ```
+    pub fn lint(&self) -> Vec<ValidationWarning> {
+        super::validation::lint(self)
     }
 
-    /// Get all the [extensions](https://github.com/cloudevents/spec/blob/master/spec.md#extension-context-attributes)
-    pub fn get_extensions(&self) -> Vec<(&str, &ExtensionValue)> {
-        self.extensions
-            .iter()
-            .map(|(k, v)| (k.as_str(), v))
-            .collect()
+    /// Size in bytes of this `Event` encoded in the structured mode `format`, e.g. to check it
+    /// against the message size limit of a broker, see
+    /// [`SizeLimitPolicy`](crate::message::SizeLimitPolicy)
+    pub fn encoded_size(&self, format: &dyn EventFormat) -> crate::message::Result<usize> {
+        Ok(format.encode(self)?.len())
     }
 
-    /// Set the [extension](https://github.com/cloudevents/spec/blob/master/spec.md#extension-context-attributes) named `extension_name` with `extension_value`
-    pub fn set_extension<'name, 'event: 'name>(
-        &'event mut self,
-        extension_name: &'name str,
-        extension_value: impl Into<ExtensionValue>,
-    ) {
-        self.extensions
-            .insert(extension_name.to_owned(), extension_value.into());
+    /// Copy of this `Event` with its sensitive content redacted by the [`Redactor`] installed with
+    /// [`set_redactor`](super::set_redactor), to be logged or debugged safely.
+    ///
+    /// Without an installed redactor, only the binary data is replaced with a `<N bytes>`
+    /// placeholder, see [`DataRedactor`](super::DataRedactor).
+    pub fn redacted(&self) -> Event {
+        let mut event = self.clone();
+        super::redact::redact(&mut event);
+        event
+    }
+
+    /// Copy of this `Event` with its sensitive content redacted by `redactor`
+    pub fn redacted_with(&self, redactor: &dyn Redactor) -> Event {
+        let mut event = self.clone();
+        redactor.redact(&mut event);
+        event
     }
 
-    /// Remove the [extension](https://github.com/cloudevents/spec/blob/master/spec.md#extension-context-attributes) named `extension_name`
-    pub fn remove_extension<'name, 'event: 'name>(
-        &'event mut self,
-        extension_name: &'name str,
-    ) -> Option<ExtensionValue> {
-        self.extensions.remove(extension_name)
+    /// Get all the [extensions](https://github.com/cloudevents/spec/blob/master/spec.md#extension-context-attributes)
+    pub fn get_extensions(&self) -> Vec<(&str, &ExtensionValue)> {
+        self.attributes
+            .iter_extensions()
+            .map(|(k, v)| (k.as_str(), v))
+            .collect()
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use url::Url;
 
     #[test]
     fn try_get_data_json() {
@@ -219,6 +496,151 @@ mod tests {
         )
     }
 
+    #[derive(serde::Deserialize, Debug, PartialEq)]
+    struct Greeting {
+        hello: String,
+    }
+
+    #[test]
+    fn data_as_json() {
+        let mut e = Event::default();
+        assert!(e.data_as::<Greeting>().unwrap().is_none());
+
+        e.write_data(
+            "application/cloudevents+json; charset=utf-8",
+            serde_json::json!({"hello": "world"}),
+        );
+        assert_eq!(
+            Greeting {
+                hello: String::from("world")
+            },
+            e.data_as().unwrap().unwrap()
+        );
+
+        e.write_data("text/json", br#"{"hello": "binary"}"#.to_vec());
+        assert_eq!(
+            Greeting {
+                hello: String::from("binary")
+            },
+            e.data_as().unwrap().unwrap()
+        );
+    }
+
+    #[test]
+    fn data_as_mismatch() {
+        let mut e = Event::default();
+        e.write_data("text/plain", String::from("hello"));
+        let err = e.data_as::<Greeting>().unwrap_err();
+        assert!(matches!(err, DataError::UnsupportedContentType { .. }));
+        assert!(err.to_string().contains("text/plain"));
+
+        e.write_data("application/json", serde_json::json!({"goodbye": "world"}));
+        assert!(matches!(
+            e.data_as::<Greeting>().unwrap_err(),
+            DataError::DeserializeError { .. }
+        ));
+    }
+
+    #[test]
+    fn data_as_codec() {
+        use crate::event::{set_data_codecs, CodecError, DataCodecs};
+
+        fn greeting(_: &str, data: &Data) -> Result<serde_json::Value, CodecError> {
+            match data {
+                Data::String(s) if !s.is_empty() => Ok(serde_json::json!({ "hello": s })),
+                _ => Err("empty greeting".into()),
+            }
+        }
+        set_data_codecs(DataCodecs::new().with("application/x-greeting", greeting));
+
+        let mut e = Event::default();
+        e.write_data("application/x-greeting", String::from("world"));
+        assert_eq!(
+            Greeting {
+                hello: String::from("world")
+            },
+            e.data_as().unwrap().unwrap()
+        );
+
+        e.write_data("application/x-greeting", String::new());
+        assert!(matches!(
+            e.data_as::<Greeting>().unwrap_err(),
+            DataError::DecodeError { .. }
+        ));
+    }
+
+    #[test]
+    fn reply() {
+        let request = EventBuilderV10::new()
+            .id("0001")
+            .source("https://example.com/client")
+            .ty("com.example.order")
+            .build()
+            .unwrap();
+
+        let reply = request.reply("/orders").build().unwrap();
+        assert_ne!(request.get_id(), reply.get_id());
+        assert_eq!("com.example.order.response", reply.get_type());
+        assert_eq!("/orders", reply.get_source().as_str());
+        assert_eq!(
+            Some(&ExtensionValue::from("0001")),
+            reply.get_extension(CORRELATIONID)
+        );
+
+        // The correlation id is propagated along the chain of replies
+        let second = reply.reply("/payments").build().unwrap();
+        assert_eq!(
+            Some(&ExtensionValue::from("0001")),
+            second.get_extension(CORRELATIONID)
+        );
+        assert_eq!(
+            Some(&ExtensionValue::from(reply.get_id())),
+            second.get_extension(CAUSATIONID)
+        );
+        assert_eq!("com.example.order.response.response", second.get_type());
+
+        assert!(request.reply("not a uri reference").build().is_err());
+    }
+
+    #[test]
+    fn set_attribute() {
+        let mut e = Event::default();
+        e.set_attribute("id", MessageAttributeValue::from(String::from("0001")))
+            .unwrap();
+        e.set_attribute(
+            "source",
+            MessageAttributeValue::from(UriReference::parse("http://localhost/").unwrap()),
+        )
+        .unwrap();
+        e.set_attribute("someint", MessageAttributeValue::Integer(10))
+            .unwrap();
+
+        assert_eq!(Some(AttributeValue::String("0001")), e.attribute("id"));
+        assert_eq!(
+            "http://localhost/",
+            e.attribute("source").unwrap().to_string()
+        );
+        assert_eq!(Some(AttributeValue::Integer(&10)), e.attribute("someint"));
+
+        e.set_attribute(
+            "specversion",
+            MessageAttributeValue::from(String::from("0.3")),
+        )
+        .unwrap();
+        assert_eq!(SpecVersion::V03, e.get_specversion());
+        assert_eq!(Some(AttributeValue::String("0001")), e.attribute("id"));
+
+        assert!(e
+            .set_attribute("time", MessageAttributeValue::from(String::from("now")))
+            .is_err());
+        assert!(e
+            .set_attribute(
+                "specversion",
+                MessageAttributeValue::from(String::from("2.0"))
+            )
+            .is_err());
+    }
+
     #[test]
     fn remove_data() {
         let mut e = Event::default();
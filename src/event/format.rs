@@ -2,11 +2,11 @@ use super::{
     Attributes, Data, Event, EventFormatDeserializerV03, EventFormatDeserializerV10,
     EventFormatSerializerV03, EventFormatSerializerV10,
 };
-use crate::event::{AttributesReader, ExtensionValue};
+use crate::event::{AttributesReader, AttributesWriter, ExtensionValue, SpecVersion};
 use serde::de::{Error, IntoDeserializer, Unexpected};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde_value::Value;
-use std::collections::{BTreeMap, HashMap};
+use std::collections::BTreeMap;
 
 macro_rules! parse_optional_field {
     ($map:ident, $name:literal, $value_variant:ident, $error:ty) => {
@@ -104,24 +104,21 @@ pub(crate) trait EventFormatDeserializer {
     fn deserialize_event<E: serde::de::Error>(
         mut map: BTreeMap<String, Value>,
     ) -> Result<Event, E> {
-        let attributes = Self::deserialize_attributes(&mut map)?;
+        let mut attributes = Self::deserialize_attributes(&mut map)?;
         let data = Self::deserialize_data(
             attributes
                 .get_datacontenttype()
                 .unwrap_or("application/json"),
             &mut map,
         )?;
-        let extensions = map
-            .into_iter()
-            .map(|(k, v)| Ok((k, ExtensionValue::deserialize(v.into_deserializer())?)))
-            .collect::<Result<HashMap<String, ExtensionValue>, serde_value::DeserializerError>>()
-            .map_err(|e| E::custom(e))?;
-
-        Ok(Event {
-            attributes,
-            data,
-            extensions,
-        })
+        for (k, v) in map.into_iter() {
+            let extension_value: ExtensionValue =
+                ExtensionValue::deserialize(v.into_deserializer())
+                    .map_err(|e: serde_value::DeserializerError| E::custom(e))?;
+            attributes.set_extension(&k, extension_value);
+        }
+
+        Ok(Event { attributes, data })
     }
 }
 
@@ -129,7 +126,6 @@ pub(crate) trait EventFormatSerializer<S: Serializer, A: Sized> {
     fn serialize(
         attributes: &A,
         data: &Option<Data>,
-        extensions: &HashMap<String, ExtensionValue>,
         serializer: S,
     ) -> Result<<S as Serializer>::Ok, <S as Serializer>::Error>;
 }
@@ -139,18 +135,7 @@ impl<'de> Deserialize<'de> for Event {
     where
         D: Deserializer<'de>,
     {
-        let map = match Value::deserialize(deserializer)? {
-            Value::Map(m) => Ok(m),
-            v => Err(Error::invalid_type(value_to_unexpected(&v), &"a map")),
-        }?;
-
-        let mut map: BTreeMap<String, Value> = map
-            .into_iter()
-            .map(|(k, v)| match k {
-                Value::String(s) => Ok((s, v)),
-                k => Err(Error::invalid_type(value_to_unexpected(&k), &"a string")),
-            })
-            .collect::<Result<BTreeMap<String, Value>, <D as Deserializer<'de>>::Error>>()?;
+        let mut map = deserialize_to_map(deserializer)?;
 
         match parse_field!(map, "specversion", String, <D as Deserializer<'de>>::Error)?.as_str() {
             "0.3" => EventFormatDeserializerV03::deserialize_event(map),
@@ -169,18 +154,49 @@ impl Serialize for Event {
         S: Serializer,
     {
         match &self.attributes {
-            Attributes::V03(a) => {
-                EventFormatSerializerV03::serialize(a, &self.data, &self.extensions, serializer)
-            }
-            Attributes::V10(a) => {
-                EventFormatSerializerV10::serialize(a, &self.data, &self.extensions, serializer)
-            }
+            Attributes::V03(a) => EventFormatSerializerV03::serialize(a, &self.data, serializer),
+            Attributes::V10(a) => EventFormatSerializerV10::serialize(a, &self.data, serializer),
         }
     }
 }
 
+pub(crate) fn deserialize_to_map<'de, D>(
+    deserializer: D,
+) -> Result<BTreeMap<String, Value>, <D as Deserializer<'de>>::Error>
+where
+    D: Deserializer<'de>,
+{
+    let map = match Value::deserialize(deserializer)? {
+        Value::Map(m) => Ok(m),
+        v => Err(Error::invalid_type(value_to_unexpected(&v), &"a map")),
+    }?;
+
+    map.into_iter()
+        .map(|(k, v)| match k {
+            Value::String(s) => Ok((s, v)),
+            k => Err(Error::invalid_type(value_to_unexpected(&k), &"a string")),
+        })
+        .collect()
+}
+
+/// Remove the `specversion` field from `map`, failing if it doesn't match `expected`
+pub(crate) fn expect_spec_version<E: serde::de::Error>(
+    map: &mut BTreeMap<String, Value>,
+    expected: SpecVersion,
+) -> Result<(), E> {
+    let spec_version = parse_field!(map, "specversion", String, E)?;
+    if spec_version == expected.as_str() {
+        Ok(())
+    } else {
+        Err(E::invalid_value(
+            Unexpected::Str(&spec_version),
+            &expected.as_str(),
+        ))
+    }
+}
+
 // This should be provided by the Value package itself
-pub(crate) fn value_to_unexpected(v: &Value) -> Unexpected {
+pub(crate) fn value_to_unexpected(v: &Value) -> Unexpected<'_> {
     match v {
         Value::Bool(b) => serde::de::Unexpected::Bool(*b),
         Value::U8(n) => serde::de::Unexpected::Unsigned(*n as u64),
@@ -0,0 +1,181 @@
+use chrono::Utc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use uuid::Uuid;
+
+/// Generator of event `id`s, used by [`EventBuilderDefaults`](super::EventBuilderDefaults).
+///
+/// This trait is implemented for closures returning a [`String`], so an ad hoc generator can be
+/// provided without implementing it.
+///
+/// ```
+/// use cloudevents::event::{
+///     EventBuilderDefaults, IdGenerator, SequentialIdGenerator, UlidGenerator, UuidV7Generator,
+/// };
+/// use cloudevents::{AttributesReader, EventBuilder};
+///
+/// assert_eq!(36, UuidV7Generator.generate().len());
+/// assert_eq!(26, UlidGenerator.generate().len());
+///
+/// let defaults =
+///     EventBuilderDefaults::new().id_generator(SequentialIdGenerator::with_prefix("order-"));
+/// assert_eq!("order-1", defaults.builder_v10("order.created").build().unwrap().get_id());
+/// assert_eq!("order-2", defaults.builder_v10("order.created").build().unwrap().get_id());
+/// ```
+pub trait IdGenerator {
+    /// Generate a new `id`
+    fn generate(&self) -> String;
+}
+
+impl<F> IdGenerator for F
+where
+    F: Fn() -> String,
+{
+    fn generate(&self) -> String {
+        self()
+    }
+}
+
+/// Generator of random [UUID v4](https://tools.ietf.org/html/rfc4122#section-4.4) `id`s,
+/// the default of [`Event::default`](super::Event::default)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct UuidV4Generator;
+
+impl IdGenerator for UuidV4Generator {
+    fn generate(&self) -> String {
+        Uuid::new_v4().to_string()
+    }
+}
+
+/// Generator of [UUID v7](https://datatracker.ietf.org/doc/html/draft-peabody-dispatch-new-uuid-format#section-5.2)
+/// `id`s, starting with the Unix timestamp in milliseconds, hence sortable by generation time
+/// with a millisecond precision
+#[derive(Debug, Clone, Copy, Default)]
+pub struct UuidV7Generator;
+
+impl IdGenerator for UuidV7Generator {
+    fn generate(&self) -> String {
+        let mut bytes = random_bytes();
+        bytes[..6].copy_from_slice(&unix_millis().to_be_bytes()[2..]);
+        bytes[6] = 0x70 | (bytes[6] & 0x0f);
+        bytes[8] = 0x80 | (bytes[8] & 0x3f);
+        Uuid::from_bytes(bytes).to_string()
+    }
+}
+
+/// Generator of [ULID](https://github.com/ulid/spec) `id`s, 26 characters long and
+/// lexicographically sortable by generation time with a millisecond precision
+#[derive(Debug, Clone, Copy, Default)]
+pub struct UlidGenerator;
+
+const CROCKFORD_BASE32: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+impl IdGenerator for UlidGenerator {
+    fn generate(&self) -> String {
+        // Bytes 6 and 8 of a UUID v4 carry the version and the variant, the others are random
+        let bytes = random_bytes();
+        let randomness = bytes[..6]
+            .iter()
+            .chain(&bytes[10..14])
+            .fold(0_u128, |acc, b| (acc << 8) | u128::from(*b));
+        let value = (u128::from(unix_millis()) << 80) | randomness;
+        (0..26)
+            .rev()
+            .map(|i| CROCKFORD_BASE32[((value >> (5 * i)) & 0x1f) as usize] as char)
+            .collect()
+    }
+}
+
+/// Generator of sequential `id`s starting from 1, optionally prefixed.
+/// The counter is thread safe, so it can be shared between producers.
+#[derive(Debug)]
+pub struct SequentialIdGenerator {
+    prefix: String,
+    next: AtomicU64,
+}
+
+impl SequentialIdGenerator {
+    /// Create a new [`SequentialIdGenerator`] starting from 1
+    pub fn new() -> Self {
+        SequentialIdGenerator::with_prefix("")
+    }
+
+    /// Create a new [`SequentialIdGenerator`] starting from 1, prepending `prefix` to the `id`s
+    pub fn with_prefix(prefix: impl Into<String>) -> Self {
+        SequentialIdGenerator {
+            prefix: prefix.into(),
+            next: AtomicU64::new(1),
+        }
+    }
+}
+
+impl Default for SequentialIdGenerator {
+    fn default() -> Self {
+        SequentialIdGenerator::new()
+    }
+}
+
+impl IdGenerator for SequentialIdGenerator {
+    fn generate(&self) -> String {
+        format!(
+            "{}{}",
+            self.prefix,
+            self.next.fetch_add(1, Ordering::SeqCst)
+        )
+    }
+}
+
+fn unix_millis() -> u64 {
+    Utc::now().timestamp_millis().max(0) as u64
+}
+
+/// 16 random bytes, taken from a random UUID v4
+fn random_bytes() -> [u8; 16] {
+    *Uuid::new_v4().as_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn uuid_v7() {
+        let id = UuidV7Generator.generate();
+        let uuid = Uuid::parse_str(&id).unwrap();
+        assert_eq!(7, uuid.as_bytes()[6] >> 4);
+        assert_eq!(0b10, uuid.as_bytes()[8] >> 6);
+
+        let millis = u64::from_str_radix(&id.replace('-', "")[..12], 16).unwrap();
+        assert!(unix_millis() - millis < 60_000);
+        assert_ne!(id, UuidV7Generator.generate());
+    }
+
+    #[test]
+    fn ulid() {
+        let id = UlidGenerator.generate();
+        assert_eq!(26, id.len());
+        assert!(id.bytes().all(|b| CROCKFORD_BASE32.contains(&b)));
+
+        let millis = id[..10].bytes().fold(0_u64, |acc, b| {
+            (acc << 5) | CROCKFORD_BASE32.iter().position(|c| *c == b).unwrap() as u64
+        });
+        assert!(unix_millis() - millis < 60_000);
+        assert_ne!(id, UlidGenerator.generate());
+    }
+
+    #[test]
+    fn time_sortable() {
+        let (v7, ulid) = (UuidV7Generator.generate(), UlidGenerator.generate());
+        std::thread::sleep(std::time::Duration::from_millis(2));
+        assert!(v7 < UuidV7Generator.generate());
+        assert!(ulid < UlidGenerator.generate());
+    }
+
+    #[test]
+    fn sequential() {
+        let generator = SequentialIdGenerator::new();
+        assert_eq!("1", generator.generate());
+        assert_eq!("2", generator.generate());
+        assert_eq!("a-1", SequentialIdGenerator::with_prefix("a-").generate());
+        assert_eq!("fixed", (|| String::from("fixed")).generate());
+    }
+}
@@ -0,0 +1,165 @@
+/// Create a new v1.0 [`Event`](crate::Event), returning a
+/// `Result<Event, EventBuilderError>` like [`EventBuilder::build`](crate::EventBuilder::build).
+/// The built event is checked with [`Event::validate`](crate::Event::validate), failing with
+/// [`EventBuilderError::InvalidEvent`](crate::event::EventBuilderError::InvalidEvent).
+///
+/// `type` is required, while `id`, `source` and `time` get the defaults of
+/// [`EventBuilderDefaults`](crate::event::EventBuilderDefaults): a random UUID, the hostname and
+/// the current time. `data` is converted to [`Data`](crate::event::Data) and, if not specified,
+/// the `datacontenttype` follows its variant: `application/json` for JSON, `text/plain` for a
+/// string and `application/octet-stream` for binary data. `ext` sets the extensions.
+///
+/// ```
+/// use cloudevents::{event, AttributesReader};
+/// use serde_json::json;
+///
+/// let event = event! {
+///     type: "com.example.order.created",
+///     source: "https://example.com/orders",
+///     subject: "order-1",
+///     data: json!({"id": 1}),
+///     ext: {"tenant": "acme", "priority": 2},
+/// }
+/// .unwrap();
+///
+/// assert_eq!("com.example.order.created", event.get_type());
+/// assert_eq!(Some("application/json"), event.get_datacontenttype());
+/// assert_eq!(Some(&2.into()), event.get_extension("priority"));
+/// ```
+///
+/// The other keys are `id`, `time` and `datacontenttype`.
+#[macro_export]
+macro_rules! event {
+    (@fields $builder:ident, $datacontenttype:ident, $data:ident;) => {};
+    (@fields $builder:ident, $datacontenttype:ident, $data:ident; id: $value:expr $(, $($rest:tt)*)?) => {
+        $builder = $builder.id($value);
+        $crate::event!(@fields $builder, $datacontenttype, $data; $($($rest)*)?);
+    };
+    (@fields $builder:ident, $datacontenttype:ident, $data:ident; type: $value:expr $(, $($rest:tt)*)?) => {
+        $builder = $builder.ty($value);
+        $crate::event!(@fields $builder, $datacontenttype, $data; $($($rest)*)?);
+    };
+    (@fields $builder:ident, $datacontenttype:ident, $data:ident; source: $value:expr $(, $($rest:tt)*)?) => {
+        $builder = $builder.source($value);
+        $crate::event!(@fields $builder, $datacontenttype, $data; $($($rest)*)?);
+    };
+    (@fields $builder:ident, $datacontenttype:ident, $data:ident; subject: $value:expr $(, $($rest:tt)*)?) => {
+        $builder = $builder.subject($value);
+        $crate::event!(@fields $builder, $datacontenttype, $data; $($($rest)*)?);
+    };
+    (@fields $builder:ident, $datacontenttype:ident, $data:ident; time: $value:expr $(, $($rest:tt)*)?) => {
+        $builder = $builder.time($value);
+        $crate::event!(@fields $builder, $datacontenttype, $data; $($($rest)*)?);
+    };
+    (@fields $builder:ident, $datacontenttype:ident, $data:ident; datacontenttype: $value:expr $(, $($rest:tt)*)?) => {
+        $datacontenttype = ::core::option::Option::Some(::std::string::String::from($value));
+        $crate::event!(@fields $builder, $datacontenttype, $data; $($($rest)*)?);
+    };
+    (@fields $builder:ident, $datacontenttype:ident, $data:ident; data: $value:expr $(, $($rest:tt)*)?) => {
+        $data = ::core::option::Option::Some($crate::event::Data::from($value));
+        $crate::event!(@fields $builder, $datacontenttype, $data; $($($rest)*)?);
+    };
+    (@fields $builder:ident, $datacontenttype:ident, $data:ident; ext: { $($name:literal: $value:expr),* $(,)? } $(, $($rest:tt)*)?) => {
+        $($builder = $builder.extension($name, $value);)*
+        $crate::event!(@fields $builder, $datacontenttype, $data; $($($rest)*)?);
+    };
+    (@fields $builder:ident, $datacontenttype:ident, $data:ident; $($rest:tt)*) => {
+        ::core::compile_error!(
+            "expected one of the event! keys: id, type, source, subject, time, datacontenttype, data, ext"
+        );
+    };
+    ($($fields:tt)*) => {{
+        let defaults = $crate::event::EventBuilderDefaults::new();
+        let mut builder = <$crate::EventBuilderV10 as $crate::EventBuilder>::new()
+            .id(defaults.next_id())
+            .source(defaults.get_source().clone())
+            .time(defaults.now());
+        #[allow(unused_mut, unused_assignments)]
+        let mut datacontenttype: ::core::option::Option<::std::string::String> =
+            ::core::option::Option::None;
+        #[allow(unused_mut, unused_assignments)]
+        let mut data: ::core::option::Option<$crate::event::Data> = ::core::option::Option::None;
+        $crate::event!(@fields builder, datacontenttype, data; $($fields)*);
+        if let ::core::option::Option::Some(data) = data {
+            let datacontenttype = datacontenttype.unwrap_or_else(|| {
+                ::std::string::String::from(match &data {
+                    $crate::event::Data::Json(_) => "application/json",
+                    $crate::event::Data::String(_) => "text/plain",
+                    $crate::event::Data::Binary(_) => "application/octet-stream",
+                })
+            });
+            builder = builder.data(datacontenttype, data);
+        }
+        $crate::EventBuilder::build(builder).and_then(|event| match event.validate() {
+            ::core::result::Result::Ok(()) => ::core::result::Result::Ok(event),
+            ::core::result::Result::Err(errors) => ::core::result::Result::Err(
+                $crate::event::EventBuilderError::InvalidEvent { errors },
+            ),
+        })
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::event::{Data, EventBuilderError};
+    use crate::AttributesReader;
+    use chrono::{TimeZone, Utc};
+
+    #[test]
+    fn event_macro() {
+        let event = event! {
+            id: "0001",
+            type: "com.example.order.created",
+            source: "/orders",
+            time: Utc.timestamp(0, 0),
+            datacontenttype: "text/plain",
+            data: "hello",
+            ext: {"tenant": "acme"}
+        }
+        .unwrap();
+        assert_eq!("0001", event.get_id());
+        assert_eq!("/orders", event.get_source().as_str());
+        assert_eq!(Some(&Utc.timestamp(0, 0)), event.get_time());
+        assert_eq!(Some("text/plain"), event.get_datacontenttype());
+        assert_eq!(Some(Data::String("hello".to_string())), event.get_data());
+        assert_eq!(Some(&"acme".into()), event.get_extension("tenant"));
+
+        let event = event!(type: "com.example.order.created").unwrap();
+        assert!(event.get_time().is_some());
+        assert_eq!(None, event.get_datacontenttype());
+    }
+
+    #[test]
+    fn event_macro_default_datacontenttype() {
+        for (data, datacontenttype) in &[
+            (Data::from(serde_json::json!({"id": 1})), "application/json"),
+            (Data::from("hello"), "text/plain"),
+            (Data::from(vec![1u8, 2, 3]), "application/octet-stream"),
+        ] {
+            let event = event!(type: "com.example.order.created", data: data.clone()).unwrap();
+            assert_eq!(Some(*datacontenttype), event.get_datacontenttype());
+        }
+    }
+
+    #[test]
+    fn event_macro_validation() {
+        assert!(matches!(
+            event!(source: "/orders"),
+            Err(EventBuilderError::MissingRequiredAttribute {
+                attribute_name: "type"
+            })
+        ));
+        assert!(matches!(
+            event!(type: "com.example.order.created", source: "not a uri reference\n"),
+            Err(EventBuilderError::ParseUriReferenceError { .. })
+        ));
+        assert!(matches!(
+            event!(type: "com.example.order.created", ext: {"Invalid_Name": "value"}),
+            Err(EventBuilderError::InvalidEvent { errors }) if errors.len() == 1
+        ));
+        assert!(matches!(
+            event!(type: "", subject: ""),
+            Err(EventBuilderError::InvalidEvent { errors }) if errors.len() == 2
+        ));
+    }
+}
@@ -1,40 +1,90 @@
+use super::Batch;
 use super::Data;
 use super::Event;
-use super::{Attributes, AttributesReader};
-use crate::event::SpecVersion;
+use super::{Attributes, AttributesReader, AttributesWriter, EventBuilderV03, EventBuilderV10};
+use crate::event::{EventBuilder, SpecVersion};
 use crate::message::{
-    BinaryDeserializer, BinarySerializer, MessageAttributeValue, Result, StructuredDeserializer,
-    StructuredSerializer,
+    BatchSerializer, BinaryDeserializer, BinarySerializer, MessageAttributeValue, Result,
+    StructuredDeserializer, StructuredSerializer,
 };
+use bytes::Bytes;
+use serde::de::DeserializeOwned;
 
 impl StructuredDeserializer for Event {
     fn deserialize_structured<R, V: StructuredSerializer<R>>(self, visitor: V) -> Result<R> {
-        let vec: Vec<u8> = serde_json::to_vec(&self)?;
-        visitor.set_structured_event(vec)
+        (&self).deserialize_structured(visitor)
     }
 }
 
 impl BinaryDeserializer for Event {
-    fn deserialize_binary<R: Sized, V: BinarySerializer<R>>(self, mut visitor: V) -> Result<R> {
-        visitor = visitor.set_spec_version(self.get_specversion())?;
-        visitor = self.attributes.deserialize_attributes(visitor)?;
-        for (k, v) in self.extensions.into_iter() {
-            visitor = visitor.set_extension(&k, v.into())?;
+    fn deserialize_binary<R: Sized, V: BinarySerializer<R>>(self, visitor: V) -> Result<R> {
+        #[cfg(feature = "tracing")]
+        let fields = crate::logging::EventFields::capture(&self);
+        let result = serialize_binary(self, visitor);
+        #[cfg(feature = "tracing")]
+        crate::logging::inspect_serialization(fields, &result);
+        result
+    }
+}
+
+fn serialize_binary<R: Sized, V: BinarySerializer<R>>(event: Event, mut visitor: V) -> Result<R> {
+    visitor = visitor.set_spec_version(event.get_specversion())?;
+    visitor = event.attributes.deserialize_attributes(visitor)?;
+    match event.data {
+        Some(Data::String(s)) => visitor.end_with_data(Bytes::from(s)),
+        Some(Data::Binary(v)) => visitor.end_with_data(Bytes::from(v)),
+        Some(Data::Json(j)) => {
+            let vec: Vec<u8> = serde_json::to_vec(&j)?;
+            visitor.end_with_data(Bytes::from(vec))
         }
-        match self.data {
-            Some(Data::String(s)) => visitor.end_with_data(s.into_bytes()),
-            Some(Data::Binary(v)) => visitor.end_with_data(v),
-            Some(Data::Json(j)) => {
-                let vec: Vec<u8> = serde_json::to_vec(&j)?;
-                visitor.end_with_data(vec)
-            }
-            None => visitor.end(),
+        None => visitor.end(),
+    }
+}
+
+impl StructuredDeserializer for &Event {
+    fn deserialize_structured<R, V: StructuredSerializer<R>>(self, visitor: V) -> Result<R> {
+        let result = serialize_structured(self, visitor);
+        #[cfg(feature = "tracing")]
+        crate::logging::inspect_serialization(crate::logging::EventFields::capture(self), &result);
+        result
+    }
+}
+
+fn serialize_structured<R, V: StructuredSerializer<R>>(event: &Event, visitor: V) -> Result<R> {
+    let vec: Vec<u8> = serde_json::to_vec(event)?;
+    visitor.set_structured_event(vec)
+}
+
+/// Serializes the event without cloning its attributes, which are passed borrowed to the
+/// [`BinarySerializer`]. The data is copied.
+impl BinaryDeserializer for &Event {
+    fn deserialize_binary<R: Sized, V: BinarySerializer<R>>(self, visitor: V) -> Result<R> {
+        let result = serialize_borrowed_binary(self, visitor);
+        #[cfg(feature = "tracing")]
+        crate::logging::inspect_serialization(crate::logging::EventFields::capture(self), &result);
+        result
+    }
+}
+
+fn serialize_borrowed_binary<R: Sized, V: BinarySerializer<R>>(
+    event: &Event,
+    mut visitor: V,
+) -> Result<R> {
+    visitor = visitor.set_spec_version(event.get_specversion())?;
+    visitor = event.attributes.deserialize_attributes(visitor)?;
+    match &event.data {
+        Some(Data::String(s)) => visitor.end_with_data(Bytes::copy_from_slice(s.as_bytes())),
+        Some(Data::Binary(v)) => visitor.end_with_data(Bytes::copy_from_slice(v)),
+        Some(Data::Json(j)) => {
+            let vec: Vec<u8> = serde_json::to_vec(j)?;
+            visitor.end_with_data(Bytes::from(vec))
         }
+        None => visitor.end(),
     }
 }
 
 pub(crate) trait AttributesDeserializer {
-    fn deserialize_attributes<R: Sized, V: BinarySerializer<R>>(self, visitor: V) -> Result<V>;
+    fn deserialize_attributes<R: Sized, V: BinarySerializer<R>>(&self, visitor: V) -> Result<V>;
 }
 
 pub(crate) trait AttributesSerializer {
@@ -42,7 +92,7 @@ pub(crate) trait AttributesSerializer {
 }
 
 impl AttributesDeserializer for Attributes {
-    fn deserialize_attributes<R: Sized, V: BinarySerializer<R>>(self, visitor: V) -> Result<V> {
+    fn deserialize_attributes<R: Sized, V: BinarySerializer<R>>(&self, visitor: V) -> Result<V> {
         match self {
             Attributes::V03(v03) => v03.deserialize_attributes(visitor),
             Attributes::V10(v10) => v10.deserialize_attributes(visitor),
@@ -61,10 +111,9 @@ impl AttributesSerializer for Attributes {
 
 impl StructuredSerializer<Event> for Event {
     fn set_structured_event(mut self, bytes: Vec<u8>) -> Result<Event> {
-        let new_event: Event = serde_json::from_slice(&bytes)?;
+        let new_event: Event = parse_json(bytes)?;
         self.attributes = new_event.attributes;
         self.data = new_event.data;
-        self.extensions = new_event.extensions;
         Ok(self)
     }
 }
@@ -84,12 +133,14 @@ impl BinarySerializer<Event> for Event {
     }
 
     fn set_extension(mut self, name: &str, value: MessageAttributeValue) -> Result<Self> {
-        self.extensions.insert(name.to_string(), value.into());
+        self.attributes.set_extension(name, value);
         Ok(self)
     }
 
-    fn end_with_data(mut self, bytes: Vec<u8>) -> Result<Event> {
-        self.data = Some(Data::from_binary(self.get_datacontenttype(), bytes)?);
+    fn end_with_data(mut self, bytes: Bytes) -> Result<Event> {
+        self.data = Some(
+            Data::from_bytes(self.get_datacontenttype(), bytes).map_err(payload_decode_error)?,
+        );
         Ok(self)
     }
 
@@ -97,3 +148,234 @@ impl BinarySerializer<Event> for Event {
         Ok(self)
     }
 }
+
+/// [`StructuredSerializer`] parsing a structured mode message into a new [`Event`].
+pub(crate) struct EventStructuredSerializer {}
+
+impl StructuredSerializer<Event> for EventStructuredSerializer {
+    fn set_structured_event(self, bytes: Vec<u8>) -> Result<Event> {
+        parse_json(bytes)
+    }
+}
+
+/// [`BatchSerializer`] parsing a batched mode message into a new [`Batch`].
+pub(crate) struct EventBatchSerializer {}
+
+impl BatchSerializer<Batch> for EventBatchSerializer {
+    fn set_batch(self, bytes: Vec<u8>) -> Result<Batch> {
+        parse_json(bytes)
+    }
+}
+
+/// [`BinarySerializer`] building a new [`Event`] with the [`EventBuilder`] of the message spec version,
+/// so required attributes are validated rather than filled with default values.
+pub(crate) struct EventBinarySerializer {
+    builder: Option<VersionedEventBuilder>,
+}
+
+enum VersionedEventBuilder {
+    V03(EventBuilderV03),
+    V10(EventBuilderV10),
+}
+
+impl EventBinarySerializer {
+    pub(crate) fn new() -> Self {
+        EventBinarySerializer { builder: None }
+    }
+
+    fn builder_mut(&mut self) -> Result<&mut VersionedEventBuilder> {
+        self.builder.as_mut().ok_or_else(missing_spec_version)
+    }
+}
+
+impl BinarySerializer<Event> for EventBinarySerializer {
+    fn set_spec_version(mut self, spec_version: SpecVersion) -> Result<Self> {
+        self.builder = Some(match spec_version {
+            SpecVersion::V03 => VersionedEventBuilder::V03(EventBuilderV03::new()),
+            SpecVersion::V10 => VersionedEventBuilder::V10(EventBuilderV10::new()),
+        });
+        Ok(self)
+    }
+
+    fn set_attribute(mut self, name: &str, value: MessageAttributeValue) -> Result<Self> {
+        match self.builder_mut()? {
+            VersionedEventBuilder::V03(b) => b.serialize_attribute(name, value)?,
+            VersionedEventBuilder::V10(b) => b.serialize_attribute(name, value)?,
+        }
+        Ok(self)
+    }
+
+    fn set_extension(mut self, name: &str, value: MessageAttributeValue) -> Result<Self> {
+        let extensions = match self.builder_mut()? {
+            VersionedEventBuilder::V03(b) => &mut b.extensions,
+            VersionedEventBuilder::V10(b) => &mut b.extensions,
+        };
+        extensions.insert(name.to_string(), value.into());
+        Ok(self)
+    }
+
+    fn end_with_data(mut self, bytes: Bytes) -> Result<Event> {
+        match self.builder_mut()? {
+            VersionedEventBuilder::V03(b) => {
+                b.data = Some(
+                    Data::from_bytes(b.datacontenttype.as_deref(), bytes)
+                        .map_err(payload_decode_error)?,
+                )
+            }
+            VersionedEventBuilder::V10(b) => {
+                b.data = Some(
+                    Data::from_bytes(b.datacontenttype.as_deref(), bytes)
+                        .map_err(payload_decode_error)?,
+                )
+            }
+        }
+        self.end()
+    }
+
+    fn end(self) -> Result<Event> {
+        match self.builder {
+            Some(VersionedEventBuilder::V03(b)) => Ok(b.build()?),
+            Some(VersionedEventBuilder::V10(b)) => Ok(b.build()?),
+            None => Err(missing_spec_version()),
+        }
+    }
+}
+
+fn missing_spec_version() -> crate::message::Error {
+    crate::message::Error::MissingRequiredAttribute {
+        attribute_name: "specversion",
+    }
+}
+
+/// Parse a payload in the JSON event format, with [simd-json](https://docs.rs/simd-json)
+/// when the `simd-json` feature is enabled
+pub(crate) fn parse_json<T: DeserializeOwned>(bytes: Vec<u8>) -> Result<T> {
+    #[cfg(feature = "simd-json")]
+    {
+        let mut bytes = bytes;
+        simd_json::serde::from_slice(&mut bytes).map_err(|e| {
+            crate::message::Error::PayloadDecodeError {
+                source: Box::new(e),
+            }
+        })
+    }
+    #[cfg(not(feature = "simd-json"))]
+    {
+        serde_json::from_slice(&bytes).map_err(payload_decode_error)
+    }
+}
+
+fn payload_decode_error(e: serde_json::Error) -> crate::message::Error {
+    crate::message::Error::PayloadDecodeError {
+        source: Box::new(e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::UriReference;
+    use crate::message::Error;
+    use std::borrow::Cow;
+
+    #[test]
+    fn binary_serializer_missing_id() {
+        let res = EventBinarySerializer::new()
+            .set_spec_version(SpecVersion::V10)
+            .unwrap()
+            .set_attribute(
+                "type",
+                MessageAttributeValue::from("example.test".to_string()),
+            )
+            .unwrap()
+            .set_attribute(
+                "source",
+                MessageAttributeValue::from(UriReference::parse("http://localhost/").unwrap()),
+            )
+            .unwrap()
+            .end();
+
+        match res {
+            Err(Error::MissingRequiredAttribute { attribute_name }) => {
+                assert_eq!("id", attribute_name)
+            }
+            r => panic!("Unexpected result {:?}", r.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn binary_serializer_wrong_attribute_type() {
+        let res = EventBinarySerializer::new()
+            .set_spec_version(SpecVersion::V10)
+            .unwrap()
+            .set_attribute("time", MessageAttributeValue::from("yesterday"));
+
+        match res {
+            Err(Error::WrongAttributeType {
+                attribute_name,
+                expected,
+                ..
+            }) => assert_eq!(("time", "timestamp"), (attribute_name, expected)),
+            r => panic!("Unexpected result {:?}", r.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn binary_serializer_missing_spec_version() {
+        let res = EventBinarySerializer::new()
+            .set_attribute("id", MessageAttributeValue::from("0001".to_string()));
+
+        assert!(res.is_err());
+    }
+
+    /// Counts the attributes and extensions passed borrowed
+    struct BorrowedValues(usize);
+
+    impl BinarySerializer<usize> for BorrowedValues {
+        fn set_spec_version(self, _: SpecVersion) -> Result<Self> {
+            Ok(self)
+        }
+
+        fn set_attribute(mut self, _: &str, value: MessageAttributeValue) -> Result<Self> {
+            if matches!(
+                value,
+                MessageAttributeValue::String(Cow::Borrowed(_))
+                    | MessageAttributeValue::UriRef(Cow::Borrowed(_))
+            ) {
+                self.0 += 1;
+            }
+            Ok(self)
+        }
+
+        fn set_extension(self, name: &str, value: MessageAttributeValue) -> Result<Self> {
+            self.set_attribute(name, value)
+        }
+
+        fn end_with_data(self, _: Bytes) -> Result<usize> {
+            self.end()
+        }
+
+        fn end(self) -> Result<usize> {
+            Ok(self.0)
+        }
+    }
+
+    #[test]
+    fn borrowed_event() {
+        let event = EventBuilderV10::new()
+            .id("0001")
+            .ty("example.test")
+            .source("http://localhost/")
+            .subject("orders")
+            .extension("someext", "value")
+            .extension("someint", 10)
+            .data("application/json", serde_json::json!({"hello": "world"}))
+            .build()
+            .unwrap();
+
+        // id, type, source, datacontenttype, subject and someext
+        assert_eq!(6, (&event).deserialize_binary(BorrowedValues(0)).unwrap());
+        assert_eq!(event, BinaryDeserializer::into_event(&event).unwrap());
+        assert_eq!(event, StructuredDeserializer::into_event(&event).unwrap());
+    }
+}
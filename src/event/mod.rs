@@ -1,25 +1,80 @@
+#[cfg(feature = "proptest")]
+pub mod arbitrary;
+#[macro_use]
 mod attributes;
+mod batch;
+mod batch_reader;
 mod builder;
+mod clock;
+mod codec;
+mod conversion;
 mod data;
+mod defaults;
+#[allow(clippy::module_inception)]
 mod event;
 mod extensions;
+mod id_generator;
+#[macro_use]
+mod macros;
 #[macro_use]
 mod format;
 mod message;
+mod pool;
+mod redact;
+mod shared;
 mod spec_version;
+#[cfg(feature = "stream")]
+mod streaming;
+mod typed;
 mod types;
+mod uri_reference;
+mod validation;
 
 pub use attributes::Attributes;
 pub(crate) use attributes::AttributesIter;
-pub use attributes::{AttributeValue, AttributesReader, AttributesWriter};
+pub(crate) use attributes::DataAttributesWriter;
+pub use attributes::{AttributeValue, AttributesReader, AttributesWriter, SOURCE_ENV_VAR};
+pub use batch::Batch;
+pub use batch_reader::BatchReader;
+pub(crate) use batch_reader::EventSplitter;
 pub use builder::Error as EventBuilderError;
 pub use builder::EventBuilder;
+pub use clock::{Clock, FixedClock, MonotonicClock, SystemClock};
+#[cfg(feature = "macros")]
+pub use cloudevents_sdk_macros::EventData;
+pub use codec::{clear_data_codecs, set_data_codecs, CodecError, DataCodec, DataCodecs, TextCodec};
+pub use conversion::LossyChange;
+pub(crate) use data::is_json_content_type;
 pub use data::Data;
+pub use data::DataError;
+pub use defaults::EventBuilderDefaults;
 pub use event::Event;
 pub use extensions::ExtensionValue;
+pub use id_generator::{
+    IdGenerator, SequentialIdGenerator, UlidGenerator, UuidV4Generator, UuidV7Generator,
+};
+pub(crate) use message::{
+    parse_json, EventBatchSerializer, EventBinarySerializer, EventStructuredSerializer,
+};
+pub use pool::EventPool;
+#[cfg(feature = "tracing")]
+pub(crate) use redact::redacted_with_installed;
+#[cfg(all(test, feature = "tracing"))]
+pub(crate) use redact::INSTALLED_REDACTOR_LOCK;
+pub use redact::{clear_redactor, set_redactor, DataRedactor, Redactor};
+pub use shared::SharedEvent;
 pub use spec_version::InvalidSpecVersion;
-pub use spec_version::SpecVersion;
-pub use types::{TryIntoTime, TryIntoUrl};
+pub use spec_version::{is_spec_attribute, SpecVersion};
+#[cfg(feature = "stream")]
+pub use streaming::StreamingEvent;
+pub use typed::Error as TypedEventError;
+pub use typed::{EventData, ToEvent, TryFromEvent, TypedEvent};
+pub use types::{TryIntoTime, TryIntoUriReference, TryIntoUrl};
+pub use uri_reference::{InvalidUriReference, UriReference};
+pub(crate) use validation::{invalid_extension_name_reason, is_long_extension_name};
+pub use validation::{
+    ValidationError, ValidationWarning, MAX_ATTRIBUTE_VALUE_SIZE, MAX_EXTENSION_NAME_LENGTH,
+};
 
 mod v03;
 
@@ -28,6 +83,7 @@ pub(crate) use v03::AttributesIntoIterator as AttributesIntoIteratorV03;
 pub use v03::EventBuilder as EventBuilderV03;
 pub(crate) use v03::EventFormatDeserializer as EventFormatDeserializerV03;
 pub(crate) use v03::EventFormatSerializer as EventFormatSerializerV03;
+pub use v03::ATTRIBUTE_NAMES as ATTRIBUTE_NAMES_V03;
 
 mod v10;
 
@@ -36,3 +92,4 @@ pub(crate) use v10::AttributesIntoIterator as AttributesIntoIteratorV10;
 pub use v10::EventBuilder as EventBuilderV10;
 pub(crate) use v10::EventFormatDeserializer as EventFormatDeserializerV10;
 pub(crate) use v10::EventFormatSerializer as EventFormatSerializerV10;
+pub use v10::ATTRIBUTE_NAMES as ATTRIBUTE_NAMES_V10;
@@ -0,0 +1,146 @@
+use super::{Data, Event};
+
+/// Pool recycling the allocations of [`Event`]s and of their data buffers between sends,
+/// reducing the allocator pressure of producers sending events in a tight loop.
+///
+/// The events returned by [`EventPool::get`] are [reset](Event::reset), so their attributes
+/// must be set again, and the buffers returned by [`EventPool::take_buffer`] are empty.
+///
+/// ```
+/// use cloudevents::event::EventPool;
+/// use cloudevents::{AttributesReader, AttributesWriter};
+///
+/// let mut pool = EventPool::new(16);
+///
+/// for i in 0..3 {
+///     let mut event = pool.get();
+///     event.set_id(i.to_string());
+///     event.set_type("com.example.reading");
+///
+///     let mut buffer = pool.take_buffer();
+///     buffer.extend_from_slice(b"21.5");
+///     event.write_data("text/plain", buffer);
+///
+///     // send the event, then give it back to the pool
+///     assert_eq!(i.to_string(), event.get_id());
+///     pool.put(event);
+/// }
+/// assert_eq!(1, pool.len());
+/// ```
+#[derive(Debug)]
+pub struct EventPool {
+    events: Vec<Event>,
+    buffers: Vec<Vec<u8>>,
+    capacity: usize,
+}
+
+impl EventPool {
+    /// Create a new [`EventPool`] keeping up to `capacity` events and `capacity` buffers
+    pub fn new(capacity: usize) -> Self {
+        EventPool {
+            events: Vec::with_capacity(capacity),
+            buffers: Vec::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// Get a recycled event, or a new [`Event::default`] reset like the recycled ones if the pool
+    /// is empty
+    pub fn get(&mut self) -> Event {
+        self.events.pop().unwrap_or_else(|| {
+            let mut event = Event::default();
+            event.reset();
+            event
+        })
+    }
+
+    /// Give `event` back to the pool, recycling its binary or string data as a buffer. The event
+    /// is dropped if the pool is full.
+    pub fn put(&mut self, mut event: Event) {
+        match event.reset() {
+            Some(Data::Binary(buffer)) => self.put_buffer(buffer),
+            Some(Data::String(buffer)) => self.put_buffer(buffer.into_bytes()),
+            _ => (),
+        }
+        if self.events.len() < self.capacity {
+            self.events.push(event);
+        }
+    }
+
+    /// Get an empty recycled buffer to serialize the data of an event into, or a new one if the
+    /// pool has none
+    pub fn take_buffer(&mut self) -> Vec<u8> {
+        self.buffers.pop().unwrap_or_default()
+    }
+
+    /// Give `buffer` back to the pool. The buffer is dropped if the pool is full.
+    pub fn put_buffer(&mut self, mut buffer: Vec<u8>) {
+        if self.buffers.len() < self.capacity {
+            buffer.clear();
+            self.buffers.push(buffer);
+        }
+    }
+
+    /// Get the number of events in the pool
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    /// Returns `true` if the pool has no events
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::{AttributesReader, AttributesWriter};
+    use crate::{EventBuilder, EventBuilderV10};
+
+    #[test]
+    fn reset() {
+        let mut event = EventBuilderV10::new()
+            .id("0001")
+            .ty("example.test")
+            .source("http://localhost/")
+            .subject("books")
+            .extension("tenant", "acme")
+            .data("text/plain", "hello")
+            .build()
+            .unwrap();
+        assert_eq!(Some(Data::String("hello".to_string())), event.reset());
+        assert_eq!("", event.get_id());
+        assert_eq!("", event.get_type());
+        assert_eq!("http://localhost/", event.get_source().as_str());
+        assert_eq!(None, event.get_subject());
+        assert_eq!(None, event.get_datacontenttype());
+        assert_eq!(None, event.get_time());
+        assert_eq!(0, event.iter_extensions().count());
+        assert_eq!(None, event.reset());
+    }
+
+    #[test]
+    fn recycle() {
+        let mut pool = EventPool::new(1);
+        assert!(pool.is_empty());
+
+        let mut event = pool.get();
+        assert_eq!("", event.get_id());
+        event.set_id("0001");
+        let mut buffer = pool.take_buffer();
+        buffer.extend_from_slice(b"hello");
+        let ptr = buffer.as_ptr();
+        event.write_data("application/octet-stream", buffer);
+        pool.put(event);
+        pool.put(Event::default());
+        assert_eq!(1, pool.len());
+
+        let event = pool.get();
+        assert_eq!("", event.get_id());
+        assert_eq!(None, event.get_data::<Data>());
+        let buffer = pool.take_buffer();
+        assert!(buffer.is_empty());
+        assert_eq!(ptr, buffer.as_ptr());
+    }
+}
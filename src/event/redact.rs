@@ -0,0 +1,286 @@
+use super::{is_json_content_type, AttributesReader, AttributesWriter, Data, Event};
+use serde_json::Value;
+use std::sync::{Arc, RwLock};
+
+/// Redactor of the sensitive content of an event, used by [`Event::redacted`] to log or debug
+/// the events safely.
+///
+/// It's implemented by the functions with the signature of [`Redactor::redact`].
+pub trait Redactor: Send + Sync {
+    /// Redact `event` in place
+    fn redact(&self, event: &mut Event);
+}
+
+impl<F> Redactor for F
+where
+    F: Fn(&mut Event) + Send + Sync,
+{
+    fn redact(&self, event: &mut Event) {
+        self(event)
+    }
+}
+
+/// [`Redactor`] masking the configured paths of the JSON data and extensions, and replacing the
+/// binary data with a `<N bytes>` placeholder.
+///
+/// The paths are [JSON Pointers](https://www.rfc-editor.org/rfc/rfc6901), where the `*` segment
+/// matches all the fields of an object or the elements of an array. The data of a JSON
+/// `datacontenttype` stored as a string or as bytes is parsed to be masked.
+///
+/// ```
+/// use cloudevents::event::DataRedactor;
+/// use cloudevents::{AttributesReader, EventBuilder, EventBuilderV10};
+/// use serde_json::{json, Value};
+///
+/// let event = EventBuilderV10::new()
+///     .id("0001")
+///     .source("http://localhost/")
+///     .ty("com.example.payment")
+///     .extension("customer", "alice@example.com")
+///     .data(
+///         "application/json",
+///         json!({"amount": 10, "cards": [{"number": "4111111111111111"}]}),
+///     )
+///     .build()
+///     .unwrap();
+///
+/// let redactor = DataRedactor::new()
+///     .path("/cards/*/number")
+///     .extension("customer");
+/// let redacted = event.redacted_with(&redactor);
+/// assert_eq!(
+///     Some(json!({"amount": 10, "cards": [{"number": "***"}]})),
+///     redacted.data_as::<Value>().unwrap()
+/// );
+/// assert_eq!(Some(&"***".into()), redacted.get_extension("customer"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct DataRedactor {
+    paths: Vec<Vec<String>>,
+    extensions: Vec<String>,
+    mask: String,
+}
+
+impl DataRedactor {
+    /// Create a new [`DataRedactor`] masking nothing but the binary data
+    pub fn new() -> Self {
+        DataRedactor::default()
+    }
+
+    /// Mask the values of the JSON data at `path`
+    pub fn path(mut self, path: &str) -> Self {
+        let segments = path
+            .split('/')
+            .skip(1)
+            .map(|s| s.replace("~1", "/").replace("~0", "~"))
+            .collect();
+        self.paths.push(segments);
+        self
+    }
+
+    /// Mask the value of the extension `name`
+    pub fn extension(mut self, name: impl Into<String>) -> Self {
+        self.extensions.push(name.into());
+        self
+    }
+
+    /// Replace the masked values with `mask`, instead of `***`
+    pub fn mask(mut self, mask: impl Into<String>) -> Self {
+        self.mask = mask.into();
+        self
+    }
+
+    fn mask_path(&self, value: &mut Value, segments: &[String]) {
+        let (segment, rest) = match segments.split_first() {
+            Some(split) => split,
+            None => {
+                *value = Value::String(self.mask.clone());
+                return;
+            }
+        };
+        match value {
+            Value::Object(fields) if segment == "*" => {
+                fields.values_mut().for_each(|v| self.mask_path(v, rest))
+            }
+            Value::Object(fields) => {
+                if let Some(v) = fields.get_mut(segment) {
+                    self.mask_path(v, rest)
+                }
+            }
+            Value::Array(values) if segment == "*" => {
+                values.iter_mut().for_each(|v| self.mask_path(v, rest))
+            }
+            Value::Array(values) => {
+                if let Some(v) = segment.parse().ok().and_then(|i: usize| values.get_mut(i)) {
+                    self.mask_path(v, rest)
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl Default for DataRedactor {
+    fn default() -> Self {
+        DataRedactor {
+            paths: Vec::new(),
+            extensions: Vec::new(),
+            mask: "***".to_string(),
+        }
+    }
+}
+
+impl Redactor for DataRedactor {
+    fn redact(&self, event: &mut Event) {
+        for name in &self.extensions {
+            if event.get_extension(name).is_some() {
+                event.set_extension(name, self.mask.clone());
+            }
+        }
+
+        let json = event
+            .get_datacontenttype()
+            .map(|ct| is_json_content_type(ct.split(';').next().unwrap_or_default().trim()))
+            .unwrap_or(true);
+        let parsed = match &event.data {
+            Some(Data::String(s)) if json => serde_json::from_str(s).ok(),
+            Some(Data::Binary(b)) if json => serde_json::from_slice(b).ok(),
+            _ => None,
+        };
+        if let Some(value) = parsed {
+            event.data = Some(Data::Json(value));
+        }
+        match &mut event.data {
+            Some(Data::Json(value)) => {
+                for path in &self.paths {
+                    self.mask_path(value, path);
+                }
+            }
+            Some(Data::Binary(b)) => {
+                event.data = Some(Data::String(format!("<{} bytes>", b.len())));
+            }
+            _ => {}
+        }
+    }
+}
+
+static REDACTOR: RwLock<Option<Arc<dyn Redactor>>> = RwLock::new(None);
+
+/// Held by the tests installing a redactor, so they don't run in parallel
+#[cfg(test)]
+pub(crate) static INSTALLED_REDACTOR_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+/// Install the [`Redactor`] used by [`Event::redacted`] and by the failure logs of the `tracing`
+/// feature, replacing the previous one
+pub fn set_redactor(redactor: impl Redactor + 'static) {
+    if let Ok(mut current) = REDACTOR.write() {
+        *current = Some(Arc::new(redactor));
+    }
+}
+
+/// Remove the installed [`Redactor`], so [`Event::redacted`] only replaces the binary data
+pub fn clear_redactor() {
+    if let Ok(mut current) = REDACTOR.write() {
+        *current = None;
+    }
+}
+
+/// Copy of `event` redacted with the installed redactor, or `None` if none is installed
+#[cfg(feature = "tracing")]
+pub(crate) fn redacted_with_installed(event: &Event) -> Option<Event> {
+    let redactor = REDACTOR.read().ok()?.clone()?;
+    Some(event.redacted_with(&*redactor))
+}
+
+/// Redact `event` with the installed redactor, or a default [`DataRedactor`] if none is installed
+pub(crate) fn redact(event: &mut Event) {
+    let redactor = REDACTOR.read().ok().and_then(|r| r.clone());
+    match redactor {
+        Some(redactor) => redactor.redact(event),
+        None => DataRedactor::new().redact(event),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EventBuilder, EventBuilderV10};
+    use serde_json::json;
+
+    fn event(content_type: &str, data: impl Into<Data>) -> Event {
+        EventBuilderV10::new()
+            .id("0001")
+            .source("http://localhost/")
+            .ty("example.test")
+            .data(content_type, data)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn paths() {
+        let redactor = DataRedactor::new()
+            .path("/a~1b")
+            .path("/list/1")
+            .path("/nested/*/secret")
+            .path("/missing/path")
+            .mask("<redacted>");
+        let data = br#"{"a/b": 1, "list": [1, 2], "nested": {"x": {"secret": 1, "y": 2}}}"#;
+        let mut e = event("application/json", data.to_vec());
+        redactor.redact(&mut e);
+        assert_eq!(
+            Some(Data::Json(json!({
+                "a/b": "<redacted>",
+                "list": [1, "<redacted>"],
+                "nested": {"x": {"secret": "<redacted>", "y": 2}}
+            }))),
+            e.data
+        );
+    }
+
+    #[test]
+    fn binary_placeholder() {
+        let mut e = event("application/octet-stream", vec![0u8; 42]);
+        DataRedactor::new().redact(&mut e);
+        assert_eq!(Some(Data::String("<42 bytes>".to_string())), e.data);
+
+        let mut e = event("text/plain", "hello");
+        DataRedactor::new().path("/a").redact(&mut e);
+        assert_eq!(Some(Data::String("hello".to_string())), e.data);
+    }
+
+    #[test]
+    fn redacted() {
+        let _lock = INSTALLED_REDACTOR_LOCK
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let e = event("application/json", json!({"card": "4111111111111111"}));
+        let local = DataRedactor::new().path("/card");
+        assert_eq!(
+            Some(Data::Json(json!({"card": "***"}))),
+            e.redacted_with(&local).data
+        );
+
+        // Without an installed redactor, only the binary data is replaced
+        let binary = event("application/octet-stream", vec![0u8; 3]);
+        assert_eq!(e, e.redacted());
+        assert_eq!(
+            Some(Data::String("<3 bytes>".to_string())),
+            binary.redacted().data
+        );
+
+        set_redactor(local);
+        let redacted = e.redacted();
+        let redacted_binary = binary.redacted();
+        clear_redactor();
+        assert_eq!(Some(Data::Json(json!({"card": "***"}))), redacted.data);
+        assert_eq!(
+            Some(Data::String("<3 bytes>".to_string())),
+            redacted_binary.data
+        );
+        assert_eq!(
+            Some(Data::Json(json!({"card": "4111111111111111"}))),
+            e.data
+        );
+    }
+}
@@ -0,0 +1,271 @@
+use super::message::AttributesDeserializer;
+use super::{is_spec_attribute, AttributesReader, Data, Event, SpecVersion};
+use crate::message::{
+    BinaryDeserializer, BinarySerializer, MessageAttributeValue, Result, StructuredDeserializer,
+    StructuredSerializer,
+};
+use bytes::Bytes;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+/// [`Event`] shared between several deliveries, e.g. to many subscribers or transports, without
+/// cloning its attributes and its data.
+///
+/// The data is encoded once when the [`SharedEvent`] is created, and each clone can override
+/// some attributes, applied when it's serialized in binary mode, while sharing the same event.
+///
+/// ```
+/// use cloudevents::event::SharedEvent;
+/// use cloudevents::message::BinaryDeserializer;
+/// use cloudevents::{AttributesReader, EventBuilder, EventBuilderV10};
+///
+/// let event = EventBuilderV10::new()
+///     .id("0001")
+///     .ty("com.example.order.created")
+///     .source("/orders")
+///     .data("application/json", serde_json::json!({"id": 1}))
+///     .build()
+///     .unwrap();
+/// let shared = SharedEvent::new(event).unwrap();
+///
+/// for subscriber in &["billing", "shipping"] {
+///     let delivery = shared
+///         .clone()
+///         .with_attribute("subject", *subscriber)
+///         .with_attribute("deliveryattempt", 1);
+///     let event = BinaryDeserializer::into_event(&delivery).unwrap();
+///     assert_eq!(Some(*subscriber), event.get_subject());
+/// }
+/// assert_eq!(None, shared.event().get_subject());
+/// ```
+#[derive(Debug, Clone)]
+pub struct SharedEvent {
+    event: Arc<Event>,
+    data: Option<Bytes>,
+    /// Variant of the encoded data, restored by [`SharedEvent::to_event`]
+    kind: DataKind,
+    overrides: BTreeMap<String, Option<MessageAttributeValue<'static>>>,
+}
+
+impl SharedEvent {
+    /// Create a new [`SharedEvent`], encoding the data of `event`, failing only if its JSON data
+    /// can't be serialized
+    pub fn new(mut event: Event) -> Result<Self> {
+        let (kind, data) = match event.data.take() {
+            Some(Data::Binary(v)) => (DataKind::Binary, Some(Bytes::from(v))),
+            Some(Data::String(s)) => (DataKind::String, Some(Bytes::from(s))),
+            Some(Data::Json(j)) => (DataKind::Json, Some(Bytes::from(serde_json::to_vec(&j)?))),
+            None => (DataKind::Binary, None),
+        };
+        Ok(SharedEvent {
+            event: Arc::new(event),
+            data,
+            kind,
+            overrides: BTreeMap::new(),
+        })
+    }
+
+    /// Get the shared event, without its data and the overrides
+    pub fn event(&self) -> &Event {
+        &self.event
+    }
+
+    /// Get the encoded data
+    pub fn data(&self) -> Option<&Bytes> {
+        self.data.as_ref()
+    }
+
+    /// Override the context attribute or the extension `name` with `value`.
+    ///
+    /// The spec version can't be overridden, so `specversion` is ignored.
+    pub fn with_attribute(
+        mut self,
+        name: impl Into<String>,
+        value: impl Into<MessageAttributeValue<'static>>,
+    ) -> Self {
+        self.overrides.insert(name.into(), Some(value.into()));
+        self
+    }
+
+    /// Remove the extension `name`
+    pub fn without_extension(mut self, name: impl Into<String>) -> Self {
+        self.overrides.insert(name.into(), None);
+        self
+    }
+
+    /// Create a new [`Event`] with the overrides applied, copying the data, with the variant of
+    /// the data of the shared event
+    pub fn to_event(&self) -> Result<Event> {
+        let mut event = BinaryDeserializer::into_event(self)?;
+        if let Some(Data::Binary(v)) = &event.data {
+            match self.kind {
+                // The data was encoded from a valid string
+                DataKind::String => {
+                    event.data = Some(Data::String(String::from_utf8_lossy(v).into_owned()))
+                }
+                DataKind::Json => event.data = Some(Data::Json(serde_json::from_slice(v)?)),
+                DataKind::Binary => {}
+            }
+        }
+        Ok(event)
+    }
+
+    fn spec_version(&self) -> SpecVersion {
+        self.event.get_specversion()
+    }
+}
+
+/// Variant of the [`Data`] of a [`SharedEvent`]
+#[derive(Debug, Clone, Copy)]
+enum DataKind {
+    Binary,
+    String,
+    Json,
+}
+
+/// Serializes the event without cloning its attributes and sharing its encoded data
+impl BinaryDeserializer for &SharedEvent {
+    fn deserialize_binary<R: Sized, V: BinarySerializer<R>>(self, visitor: V) -> Result<R> {
+        let spec_version = self.spec_version();
+        let visitor = visitor.set_spec_version(spec_version.clone())?;
+        let mut visitor = self
+            .event
+            .attributes
+            .deserialize_attributes(OverridingSerializer {
+                visitor,
+                overrides: &self.overrides,
+            })?
+            .visitor;
+        for (name, value) in &self.overrides {
+            let value = match value {
+                Some(value) if name != "specversion" => value.clone(),
+                _ => continue,
+            };
+            visitor = if is_spec_attribute(name, spec_version.clone()) {
+                visitor.set_attribute(name, value)?
+            } else {
+                visitor.set_extension(name, value)?
+            };
+        }
+        match &self.data {
+            Some(data) => visitor.end_with_data(data.clone()),
+            None => visitor.end(),
+        }
+    }
+}
+
+impl BinaryDeserializer for SharedEvent {
+    fn deserialize_binary<R: Sized, V: BinarySerializer<R>>(self, visitor: V) -> Result<R> {
+        (&self).deserialize_binary(visitor)
+    }
+}
+
+/// Serializes the event created with [`SharedEvent::to_event`]
+impl StructuredDeserializer for &SharedEvent {
+    fn deserialize_structured<R, V: StructuredSerializer<R>>(self, visitor: V) -> Result<R> {
+        self.to_event()?.deserialize_structured(visitor)
+    }
+}
+
+/// [`BinarySerializer`] skipping the attributes of the shared event which are overridden
+struct OverridingSerializer<'a, V> {
+    visitor: V,
+    overrides: &'a BTreeMap<String, Option<MessageAttributeValue<'static>>>,
+}
+
+impl<R, V: BinarySerializer<R>> BinarySerializer<R> for OverridingSerializer<'_, V> {
+    fn set_spec_version(mut self, spec_version: SpecVersion) -> Result<Self> {
+        self.visitor = self.visitor.set_spec_version(spec_version)?;
+        Ok(self)
+    }
+
+    fn set_attribute(mut self, name: &str, value: MessageAttributeValue) -> Result<Self> {
+        if !self.overrides.contains_key(name) {
+            self.visitor = self.visitor.set_attribute(name, value)?;
+        }
+        Ok(self)
+    }
+
+    fn set_extension(mut self, name: &str, value: MessageAttributeValue) -> Result<Self> {
+        if !self.overrides.contains_key(name) {
+            self.visitor = self.visitor.set_extension(name, value)?;
+        }
+        Ok(self)
+    }
+
+    fn end_with_data(self, bytes: Bytes) -> Result<R> {
+        self.visitor.end_with_data(bytes)
+    }
+
+    fn end(self) -> Result<R> {
+        self.visitor.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::AttributesWriter;
+    use crate::{EventBuilder, EventBuilderV10};
+
+    fn event() -> Event {
+        EventBuilderV10::new()
+            .id("0001")
+            .ty("example.test")
+            .source("http://localhost/")
+            .subject("books")
+            .extension("tenant", "acme")
+            .extension("region", "eu")
+            .data("text/plain", "hello")
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn overrides() {
+        let shared = SharedEvent::new(event()).unwrap();
+        assert_eq!(Some(&Bytes::from("hello")), shared.data());
+        assert_eq!(event(), shared.to_event().unwrap());
+
+        let overridden = shared
+            .clone()
+            .with_attribute("id", "0002")
+            .with_attribute("specversion", "0.3")
+            .with_attribute("tenant", "other")
+            .with_attribute("priority", 1)
+            .without_extension("region")
+            .to_event()
+            .unwrap();
+        let mut expected = event();
+        expected.set_id("0002");
+        AttributesWriter::set_extension(&mut expected, "tenant", "other");
+        AttributesWriter::set_extension(&mut expected, "priority", 1);
+        expected.remove_extension("region");
+        assert_eq!(expected, overridden);
+        assert_eq!("0001", shared.event().get_id());
+    }
+
+    #[test]
+    fn structured() {
+        let shared = SharedEvent::new(event())
+            .unwrap()
+            .with_attribute("subject", "movies");
+        let event = StructuredDeserializer::into_event(&shared).unwrap();
+        assert_eq!(Some("movies"), event.get_subject());
+        assert_eq!(Some(Data::String("hello".to_string())), event.get_data());
+    }
+
+    #[test]
+    fn data_variants() {
+        for (datacontenttype, data) in &[
+            ("application/json", Data::Json(serde_json::json!({"id": 1}))),
+            ("application/octet-stream", Data::Binary(vec![1, 2, 3])),
+        ] {
+            let mut e = event();
+            e.write_data(*datacontenttype, data.clone());
+            let shared = SharedEvent::new(e.clone()).unwrap();
+            assert_eq!(e, shared.to_event().unwrap());
+            assert_eq!(e, StructuredDeserializer::into_event(&shared).unwrap());
+        }
+    }
+}
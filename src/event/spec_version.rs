@@ -1,9 +1,10 @@
 use super::{v03, v10};
-use serde::export::Formatter;
 use std::convert::TryFrom;
 use std::fmt;
+use std::fmt::Formatter;
+use std::str::FromStr;
 
-pub(crate) const SPEC_VERSIONS: [&'static str; 2] = ["0.3", "1.0"];
+pub(crate) const SPEC_VERSIONS: [&str; 2] = ["0.3", "1.0"];
 
 /// CloudEvent specification version
 #[derive(PartialEq, Eq, Hash, Debug, Clone)]
@@ -13,7 +14,8 @@ pub enum SpecVersion {
 }
 
 impl SpecVersion {
-    pub fn as_str(&self) -> &str {
+    /// Get the `specversion` value of this [`SpecVersion`], e.g. `1.0`
+    pub fn as_str(&self) -> &'static str {
         match self {
             SpecVersion::V03 => "0.3",
             SpecVersion::V10 => "1.0",
@@ -28,6 +30,7 @@ impl SpecVersion {
             SpecVersion::V10 => &v10::ATTRIBUTE_NAMES,
         }
     }
+
     /// Get all attribute names for all Spec versions.
     /// Note that the result iterator could contain duplicate entries.
     pub fn all_attribute_names() -> impl Iterator<Item = &'static str> {
@@ -37,6 +40,19 @@ impl SpecVersion {
     }
 }
 
+/// Returns `true` if `name` is a context attribute of `spec_version`, rather than an extension.
+///
+/// ```
+/// use cloudevents::event::{is_spec_attribute, SpecVersion};
+///
+/// assert!(is_spec_attribute("dataschema", SpecVersion::V10));
+/// assert!(!is_spec_attribute("dataschema", SpecVersion::V03));
+/// assert!(!is_spec_attribute("traceparent", SpecVersion::V10));
+/// ```
+pub fn is_spec_attribute(name: &str, spec_version: SpecVersion) -> bool {
+    spec_version.attribute_names().contains(&name)
+}
+
 impl fmt::Display for SpecVersion {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.as_str())
@@ -70,3 +86,26 @@ impl TryFrom<&str> for SpecVersion {
         }
     }
 }
+
+impl FromStr for SpecVersion {
+    type Err = InvalidSpecVersion;
+
+    fn from_str(s: &str) -> Result<Self, InvalidSpecVersion> {
+        SpecVersion::try_from(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        assert_eq!(SpecVersion::V03, "0.3".parse::<SpecVersion>().unwrap());
+        assert_eq!(SpecVersion::V10, "1.0".parse::<SpecVersion>().unwrap());
+        assert!("1.1".parse::<SpecVersion>().is_err());
+        for spec_version in [SpecVersion::V03, SpecVersion::V10] {
+            assert_eq!(spec_version, spec_version.as_str().parse().unwrap());
+        }
+    }
+}
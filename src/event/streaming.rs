@@ -0,0 +1,162 @@
+use super::{Data, Event};
+use crate::message::{
+    write_binary, AsyncBinaryDeserializer, AsyncBinarySerializer, DataStream, Message, Result,
+    SerializerFuture,
+};
+use bytes::Bytes;
+use futures::Stream;
+use std::fmt;
+
+/// An [`Event`] with its data streamed, so large binary payloads can be proxied from a binding
+/// to another without holding them in memory. Enabled with the `stream` feature.
+///
+/// It's written in binary mode by the bindings supporting it, e.g. with
+/// [`event_to_binary_stream`](crate::binding::http::event_to_binary_stream), or through
+/// [`AsyncBinaryDeserializer`]. The structured mode requires the data to be collected first, with
+/// [`StreamingEvent::collect`].
+///
+/// An `AsyncRead` can be adapted to the data stream with e.g. `tokio_util::io::ReaderStream`.
+///
+/// ```
+/// use cloudevents::event::{Data, StreamingEvent};
+/// use cloudevents::message::Bytes;
+/// use cloudevents::{AttributesReader, EventBuilder, EventBuilderV10};
+/// use futures::stream;
+///
+/// # futures::executor::block_on(async {
+/// let event = EventBuilderV10::new()
+///     .id("0001")
+///     .source("http://localhost/")
+///     .ty("example.demo")
+///     .data("application/octet-stream", Vec::new())
+///     .build()
+///     .unwrap();
+///
+/// let chunks = vec![Ok(Bytes::from_static(b"hel")), Ok(Bytes::from_static(b"lo"))];
+/// let streaming = StreamingEvent::new(event, stream::iter(chunks));
+/// assert_eq!(Some("application/octet-stream"), streaming.event().get_datacontenttype());
+///
+/// let event = streaming.collect().await.unwrap();
+/// assert_eq!(Some(Data::Binary(b"hello".to_vec())), event.get_data());
+/// # });
+/// ```
+pub struct StreamingEvent<'a> {
+    event: Event,
+    data: Option<DataStream<'a>>,
+}
+
+impl<'a> StreamingEvent<'a> {
+    /// Create a new [`StreamingEvent`] with the attributes and extensions of `event`, and its data
+    /// streamed by `data`. The data of `event` is discarded, but not its `datacontenttype`.
+    pub fn new(mut event: Event, data: impl Stream<Item = Result<Bytes>> + Send + 'a) -> Self {
+        event.data = None;
+        StreamingEvent {
+            event,
+            data: Some(Box::pin(data)),
+        }
+    }
+
+    /// Create a new [`StreamingEvent`] streaming the data of `event` in a single chunk
+    pub fn from_event(mut event: Event) -> Result<Self> {
+        let data = match event.data.take() {
+            Some(Data::String(s)) => Bytes::from(s),
+            Some(Data::Binary(v)) => Bytes::from(v),
+            Some(Data::Json(j)) => Bytes::from(serde_json::to_vec(&j)?),
+            None => return Ok(StreamingEvent::without_data(event)),
+        };
+        Ok(StreamingEvent::new(
+            event,
+            futures::stream::once(futures::future::ready(Ok(data))),
+        ))
+    }
+
+    /// Create a new [`StreamingEvent`] without data, from the attributes and extensions of
+    /// `event`
+    pub fn without_data(mut event: Event) -> Self {
+        event.data = None;
+        StreamingEvent { event, data: None }
+    }
+
+    /// The attributes and extensions of the event, without its data
+    pub fn event(&self) -> &Event {
+        &self.event
+    }
+
+    /// The attributes and extensions of the event, to modify them while the data is streamed
+    pub fn event_mut(&mut self) -> &mut Event {
+        &mut self.event
+    }
+
+    /// Split the event into its attributes and extensions, and its data stream
+    pub fn into_parts(self) -> (Event, Option<DataStream<'a>>) {
+        (self.event, self.data)
+    }
+
+    /// Collect the data stream, to get an in-memory [`Event`]
+    pub async fn collect(self) -> Result<Event> {
+        self.into_event_async().await
+    }
+}
+
+impl<'a> AsyncBinaryDeserializer for StreamingEvent<'a> {
+    fn deserialize_binary_async<'b, R, V>(self, serializer: V) -> SerializerFuture<'b, R>
+    where
+        Self: 'b,
+        R: Send + 'b,
+        V: AsyncBinarySerializer<R> + 'b,
+    {
+        Box::pin(async move {
+            match Message::from_event(self.event)? {
+                Message::Binary(message) => write_binary(message, self.data, serializer).await,
+                Message::Structured(_) => Err(crate::message::Error::WrongEncoding {}),
+            }
+        })
+    }
+}
+
+impl fmt::Debug for StreamingEvent<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StreamingEvent")
+            .field("event", &self.event)
+            .field("data", &self.data.as_ref().map(|_| "<stream>"))
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EventBuilder, EventBuilderV10};
+    use futures::stream;
+    use serde_json::json;
+
+    fn event() -> Event {
+        EventBuilderV10::new()
+            .id("0001")
+            .source("http://localhost/")
+            .ty("example.test")
+            .extension("someint", 10)
+            .data("application/json", json!({"hello": "world"}))
+            .build()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn round_trip() {
+        let streaming = StreamingEvent::from_event(event()).unwrap();
+        assert!(streaming.event().data.is_none());
+        assert_eq!(event(), streaming.collect().await.unwrap());
+
+        let chunks = [&b"{\"hello\""[..], b": ", b"\"world\"}"]
+            .iter()
+            .map(|c| Ok(Bytes::from_static(c)))
+            .collect::<Vec<_>>();
+        let streaming = StreamingEvent::new(event(), stream::iter(chunks));
+        assert_eq!(event(), streaming.collect().await.unwrap());
+
+        let mut without_data = event();
+        without_data.data = None;
+        let streaming = StreamingEvent::without_data(event());
+        assert_eq!(without_data, streaming.collect().await.unwrap());
+    }
+}
@@ -0,0 +1,423 @@
+use super::{
+    Attributes, AttributesReader, AttributesWriter, DataError, Event, EventBuilder,
+    EventBuilderDefaults, EventBuilderError, ExtensionValue, SpecVersion, UriReference,
+};
+use chrono::{DateTime, FixedOffset, Utc};
+use delegate::delegate;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use snafu::{ResultExt, Snafu};
+use std::collections::hash_map;
+use std::convert::TryFrom;
+
+/// Represents an error while converting an [`EventData`] type or a [`TypedEvent`] to and from an
+/// [`Event`]
+#[derive(Debug, Snafu)]
+pub enum Error {
+    #[snafu(display("Cannot serialize the data of the {} event: {}", ty, source))]
+    SerializeData {
+        ty: String,
+        source: serde_json::Error,
+    },
+    #[snafu(display("Cannot build the {} event: {}", ty, source))]
+    InvalidEvent {
+        ty: String,
+        source: EventBuilderError,
+    },
+    #[snafu(display("Expected an event of type {}, found {}", expected, actual))]
+    UnexpectedType {
+        expected: &'static str,
+        actual: String,
+    },
+    #[snafu(display("The {} event has no data", ty))]
+    MissingData { ty: String },
+    #[snafu(display("Invalid data of the {} event: {}", ty, source))]
+    InvalidData { ty: String, source: DataError },
+}
+
+/// Rust type of the data of the events of a `type`.
+///
+/// It declares the `type`, the default `source` and the `datacontenttype` of the events, and gets
+/// [`ToEvent`] and [`TryFromEvent`] implementations:
+///
+/// ```
+/// use cloudevents::event::{EventData, ToEvent, TryFromEvent};
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct OrderCreated {
+///     id: u64,
+/// }
+///
+/// impl EventData for OrderCreated {
+///     const TYPE: &'static str = "com.example.order.created";
+///     const SOURCE: Option<&'static str> = Some("/orders");
+/// }
+///
+/// let event = OrderCreated { id: 1 }.to_event().unwrap();
+/// assert_eq!(1, OrderCreated::try_from_event(&event).unwrap().id);
+/// ```
+///
+/// With the `macros` feature, it's implemented with `#[derive(EventData)]` and the `event`
+/// attribute, e.g. `#[event(type = "com.example.order.created", source = "/orders")]`.
+/// `datacontenttype` is optional like `source`.
+///
+/// The data is serialized to JSON, so `DATACONTENTTYPE` must be a JSON content type.
+pub trait EventData: Serialize + DeserializeOwned {
+    /// `type` of the events
+    const TYPE: &'static str;
+    /// `source` of the events, the hostname like [`Event::default`] if `None`
+    const SOURCE: Option<&'static str> = None;
+    /// `datacontenttype` of the events
+    const DATACONTENTTYPE: &'static str = "application/json";
+}
+
+/// Conversion of a value to a new [`Event`], implemented for the [`EventData`] types
+pub trait ToEvent {
+    /// Create a new [`Event`] carrying this value
+    fn to_event(&self) -> Result<Event, Error>;
+}
+
+/// Conversion of an [`Event`] to a value, implemented for the [`EventData`] types
+pub trait TryFromEvent: Sized {
+    /// Read the value carried by `event`
+    fn try_from_event(event: &Event) -> Result<Self, Error>;
+}
+
+impl<T: EventData> ToEvent for T {
+    /// Create a new v1.0 [`Event`] with a new `id`, the current `time`, the `TYPE`, the `SOURCE`
+    /// and this value as JSON data
+    fn to_event(&self) -> Result<Event, Error> {
+        let data = serde_json::to_value(self).context(SerializeData { ty: T::TYPE })?;
+        let mut builder = EventBuilderDefaults::new()
+            .builder_v10(T::TYPE)
+            .data(T::DATACONTENTTYPE, data);
+        if let Some(source) = T::SOURCE {
+            builder = builder.source(source);
+        }
+        builder.build().context(InvalidEvent { ty: T::TYPE })
+    }
+}
+
+impl<T: EventData> TryFromEvent for T {
+    /// Deserialize the data of `event`, failing if its `type` is not `TYPE`
+    fn try_from_event(event: &Event) -> Result<Self, Error> {
+        if event.get_type() != T::TYPE {
+            return Err(Error::UnexpectedType {
+                expected: T::TYPE,
+                actual: event.get_type().to_string(),
+            });
+        }
+        read_data(event)
+    }
+}
+
+/// Deserialize the data of `event`, failing if it has no data
+fn read_data<T: DeserializeOwned>(event: &Event) -> Result<T, Error> {
+    event
+        .data_as()
+        .context(InvalidData {
+            ty: event.get_type(),
+        })?
+        .ok_or_else(|| Error::MissingData {
+            ty: event.get_type().to_string(),
+        })
+}
+
+/// Create an [`Event`] with `attributes` and `data` serialized to JSON
+fn write_data<T: Serialize>(attributes: Attributes, data: &T) -> Result<Event, Error> {
+    let mut event = Event {
+        attributes,
+        data: None,
+    };
+    let data = serde_json::to_value(data).context(SerializeData {
+        ty: event.get_type(),
+    })?;
+    let datacontenttype = event
+        .get_datacontenttype()
+        .unwrap_or("application/json")
+        .to_string();
+    match event.get_dataschema().cloned() {
+        Some(dataschema) => event.write_data_with_schema(datacontenttype, dataschema, data),
+        None => event.write_data(datacontenttype, data),
+    }
+    Ok(event)
+}
+
+/// [`Event`] with its data deserialized to `T`, for the application code handling strongly typed
+/// events while the bindings send and receive [`Event`]s.
+///
+/// The attributes are read and written with [`AttributesReader`] and [`AttributesWriter`]. It's
+/// converted from an [`Event`] with [`TryFrom`] and [`TryFromEvent`], and to an [`Event`] with
+/// [`TryFrom`] and [`ToEvent`], serializing the data to JSON with the `datacontenttype` of the
+/// event, `application/json` if missing.
+///
+/// ```
+/// use cloudevents::event::TypedEvent;
+/// use cloudevents::{AttributesReader, Event, EventBuilder, EventBuilderV10};
+/// use serde::{Deserialize, Serialize};
+/// use serde_json::json;
+/// use std::convert::TryFrom;
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Order {
+///     id: u64,
+/// }
+///
+/// let event = EventBuilderV10::new()
+///     .id("0001")
+///     .source("/orders")
+///     .ty("com.example.order.created")
+///     .data("application/json", json!({"id": 1}))
+///     .build()
+///     .unwrap();
+///
+/// let mut order = TypedEvent::<Order>::try_from(event).unwrap();
+/// assert_eq!("com.example.order.created", order.get_type());
+/// order.data_mut().id += 1;
+///
+/// let event = Event::try_from(order).unwrap();
+/// assert_eq!(Some(json!({"id": 2})), event.data_as().unwrap());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypedEvent<T> {
+    attributes: Attributes,
+    data: T,
+}
+
+impl<T> TypedEvent<T> {
+    /// Create a new [`TypedEvent`] with the attributes of `event` and `data`, discarding the data
+    /// of `event`
+    pub fn new(event: Event, data: T) -> Self {
+        TypedEvent {
+            attributes: event.attributes,
+            data,
+        }
+    }
+
+    /// Get the data
+    pub fn data(&self) -> &T {
+        &self.data
+    }
+
+    /// Get a mutable reference to the data
+    pub fn data_mut(&mut self) -> &mut T {
+        &mut self.data
+    }
+
+    /// Take the data, discarding the attributes
+    pub fn into_data(self) -> T {
+        self.data
+    }
+
+    /// Convert the data with `f`, keeping the attributes
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> TypedEvent<U> {
+        TypedEvent {
+            attributes: self.attributes,
+            data: f(self.data),
+        }
+    }
+
+    /// Split in the [`Event`] with the attributes and without data, and the data
+    pub fn into_parts(self) -> (Event, T) {
+        (
+            Event {
+                attributes: self.attributes,
+                data: None,
+            },
+            self.data,
+        )
+    }
+}
+
+impl<T> AttributesReader for TypedEvent<T> {
+    delegate! {
+        to self.attributes {
+            fn get_id(&self) -> &str;
+            fn get_source(&self) -> &UriReference;
+            fn get_specversion(&self) -> SpecVersion;
+            fn get_type(&self) -> &str;
+            fn get_datacontenttype(&self) -> Option<&str>;
+            fn get_dataschema(&self) -> Option<&UriReference>;
+            fn get_subject(&self) -> Option<&str>;
+            fn get_time(&self) -> Option<&DateTime<Utc>>;
+            fn get_time_with_offset(&self) -> Option<DateTime<FixedOffset>>;
+            fn get_extension(&self, extension_name: &str) -> Option<&ExtensionValue>;
+            fn iter_extensions(&self) -> hash_map::Iter<'_, String, ExtensionValue>;
+        }
+    }
+}
+
+impl<T> AttributesWriter for TypedEvent<T> {
+    delegate! {
+        to self.attributes {
+            fn set_id(&mut self, id: impl Into<String>);
+            fn set_source(&mut self, source: impl Into<UriReference>);
+            fn set_type(&mut self, ty: impl Into<String>);
+            fn set_subject(&mut self, subject: Option<impl Into<String>>);
+            fn set_time(&mut self, time: Option<impl Into<DateTime<Utc>>>);
+            fn set_time_with_offset(&mut self, time: Option<impl Into<DateTime<FixedOffset>>>);
+            fn set_extension(&mut self, extension_name: &str, extension_value: impl Into<ExtensionValue>);
+            fn remove_extension(&mut self, extension_name: &str) -> Option<ExtensionValue>;
+        }
+    }
+}
+
+impl<T: DeserializeOwned> TryFrom<Event> for TypedEvent<T> {
+    type Error = Error;
+
+    /// Deserialize the data of `event`, failing if it has no data
+    fn try_from(event: Event) -> Result<Self, Error> {
+        let data = read_data(&event)?;
+        Ok(TypedEvent::new(event, data))
+    }
+}
+
+impl<T: Serialize> TryFrom<TypedEvent<T>> for Event {
+    type Error = Error;
+
+    fn try_from(event: TypedEvent<T>) -> Result<Self, Error> {
+        write_data(event.attributes, &event.data)
+    }
+}
+
+impl<T: DeserializeOwned> TryFromEvent for TypedEvent<T> {
+    fn try_from_event(event: &Event) -> Result<Self, Error> {
+        let data = read_data(event)?;
+        Ok(TypedEvent {
+            attributes: event.attributes.clone(),
+            data,
+        })
+    }
+}
+
+impl<T: Serialize> ToEvent for TypedEvent<T> {
+    fn to_event(&self) -> Result<Event, Error> {
+        write_data(self.attributes.clone(), &self.data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EventBuilderV10;
+    use serde::Deserialize;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct OrderCreated {
+        id: u64,
+    }
+
+    impl EventData for OrderCreated {
+        const TYPE: &'static str = "com.example.order.created";
+        const SOURCE: Option<&'static str> = Some("/orders");
+    }
+
+    #[test]
+    fn to_event() {
+        let event = OrderCreated { id: 1 }.to_event().unwrap();
+        assert_eq!("com.example.order.created", event.get_type());
+        assert_eq!("/orders", event.get_source().as_str());
+        assert_eq!(Some("application/json"), event.get_datacontenttype());
+        assert!(event.get_time().is_some());
+        assert_eq!(
+            OrderCreated { id: 1 },
+            OrderCreated::try_from_event(&event).unwrap()
+        );
+    }
+
+    #[test]
+    fn try_from_event() {
+        let builder = EventBuilderV10::new().id("0001").source("/orders");
+        let event = builder
+            .clone()
+            .ty("com.example.order.deleted")
+            .build()
+            .unwrap();
+        assert!(matches!(
+            OrderCreated::try_from_event(&event),
+            Err(Error::UnexpectedType { actual, .. }) if actual == "com.example.order.deleted"
+        ));
+
+        let event = builder
+            .clone()
+            .ty("com.example.order.created")
+            .build()
+            .unwrap();
+        assert!(matches!(
+            OrderCreated::try_from_event(&event),
+            Err(Error::MissingData { .. })
+        ));
+
+        let event = builder
+            .ty("com.example.order.created")
+            .data("application/json", serde_json::json!({"id": "1"}))
+            .build()
+            .unwrap();
+        assert!(matches!(
+            OrderCreated::try_from_event(&event),
+            Err(Error::InvalidData { .. })
+        ));
+    }
+
+    #[test]
+    fn typed_event() {
+        let event = EventBuilderV10::new()
+            .id("0001")
+            .source("/orders")
+            .ty("com.example.order.created")
+            .data("application/json", serde_json::json!({"id": 1}))
+            .build()
+            .unwrap();
+
+        let mut typed = TypedEvent::<OrderCreated>::try_from(event.clone()).unwrap();
+        assert_eq!(&OrderCreated { id: 1 }, typed.data());
+        assert_eq!(
+            typed,
+            TypedEvent::<OrderCreated>::try_from_event(&event).unwrap()
+        );
+        assert_eq!(event, typed.to_event().unwrap());
+
+        typed.set_subject(Some("order-2"));
+        typed.data_mut().id = 2;
+        let event = Event::try_from(typed).unwrap();
+        assert_eq!(Some("order-2"), event.get_subject());
+        assert_eq!(
+            Some(OrderCreated { id: 2 }),
+            event.data_as::<OrderCreated>().unwrap()
+        );
+
+        let (event, _) = TypedEvent::<OrderCreated>::try_from(event)
+            .unwrap()
+            .into_parts();
+        assert!(matches!(
+            TypedEvent::<OrderCreated>::try_from(event),
+            Err(Error::MissingData { .. })
+        ));
+    }
+
+    #[cfg(feature = "macros")]
+    #[test]
+    fn derive() {
+        #[derive(Debug, PartialEq, Serialize, Deserialize, crate::event::EventData)]
+        #[event(
+            type = "com.example.order.shipped",
+            datacontenttype = "application/vnd.order+json"
+        )]
+        struct OrderShipped {
+            id: u64,
+        }
+
+        assert_eq!("com.example.order.shipped", OrderShipped::TYPE);
+        assert_eq!(None, OrderShipped::SOURCE);
+        let event = OrderShipped { id: 1 }.to_event().unwrap();
+        assert_eq!(
+            Some("application/vnd.order+json"),
+            event.get_datacontenttype()
+        );
+        assert_eq!(
+            OrderShipped { id: 1 },
+            OrderShipped::try_from_event(&event).unwrap()
+        );
+    }
+}
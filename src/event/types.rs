@@ -1,4 +1,5 @@
-use chrono::{DateTime, Utc};
+use super::{InvalidUriReference, UriReference};
+use chrono::{DateTime, FixedOffset, Utc};
 use url::Url;
 
 /// Trait to define conversion to [`Url`]
@@ -24,8 +25,45 @@ impl TryIntoUrl for String {
     }
 }
 
+/// Trait to define conversion to [`UriReference`]
+pub trait TryIntoUriReference {
+    fn into_uri_reference(self) -> Result<UriReference, InvalidUriReference>;
+}
+
+impl TryIntoUriReference for UriReference {
+    fn into_uri_reference(self) -> Result<UriReference, InvalidUriReference> {
+        Ok(self)
+    }
+}
+
+impl TryIntoUriReference for Url {
+    fn into_uri_reference(self) -> Result<UriReference, InvalidUriReference> {
+        Ok(self.into())
+    }
+}
+
+impl TryIntoUriReference for &str {
+    fn into_uri_reference(self) -> Result<UriReference, InvalidUriReference> {
+        UriReference::parse(self)
+    }
+}
+
+impl TryIntoUriReference for String {
+    fn into_uri_reference(self) -> Result<UriReference, InvalidUriReference> {
+        std::convert::TryFrom::try_from(self)
+    }
+}
+
 pub trait TryIntoTime {
     fn into_time(self) -> Result<DateTime<Utc>, chrono::ParseError>;
+
+    /// Convert to a time keeping its offset, UTC by default
+    fn into_time_with_offset(self) -> Result<DateTime<FixedOffset>, chrono::ParseError>
+    where
+        Self: Sized,
+    {
+        self.into_time().map(DateTime::from)
+    }
 }
 
 impl TryIntoTime for DateTime<Utc> {
@@ -34,15 +72,82 @@ impl TryIntoTime for DateTime<Utc> {
     }
 }
 
+impl TryIntoTime for DateTime<FixedOffset> {
+    fn into_time(self) -> Result<DateTime<Utc>, chrono::ParseError> {
+        Ok(self.into())
+    }
+
+    fn into_time_with_offset(self) -> Result<DateTime<FixedOffset>, chrono::ParseError> {
+        Ok(self)
+    }
+}
+
+impl TryIntoTime for std::time::SystemTime {
+    fn into_time(self) -> Result<DateTime<Utc>, chrono::ParseError> {
+        Ok(self.into())
+    }
+}
+
+/// Conversion of a [`time::OffsetDateTime`] keeping its offset, enabled with the `time` feature
+#[cfg(feature = "time")]
+impl TryIntoTime for time::OffsetDateTime {
+    fn into_time(self) -> Result<DateTime<Utc>, chrono::ParseError> {
+        use chrono::TimeZone;
+        Ok(Utc.timestamp(self.unix_timestamp(), self.nanosecond()))
+    }
+
+    fn into_time_with_offset(self) -> Result<DateTime<FixedOffset>, chrono::ParseError> {
+        let seconds = self.offset().whole_seconds();
+        let offset = match FixedOffset::east_opt(seconds) {
+            Some(offset) => offset,
+            None => return Err(out_of_range_offset(seconds)),
+        };
+        Ok(self.into_time()?.with_timezone(&offset))
+    }
+}
+
+/// Error of an offset of a day or more, valid in `time` but not in `chrono`, whose errors can
+/// only be created by its parser
+#[cfg(feature = "time")]
+fn out_of_range_offset(seconds: i32) -> chrono::ParseError {
+    let mut parsed = chrono::format::Parsed::new();
+    match parsed
+        .set_offset(i64::from(seconds))
+        .and_then(|()| parsed.to_fixed_offset())
+    {
+        Err(e) => e,
+        Ok(_) => unreachable!("the offset {} is out of the range of chrono", seconds),
+    }
+}
+
+/// Convert `time` to a [`time::OffsetDateTime`], returning `None` if it's out of its range
+#[cfg(feature = "time")]
+pub(crate) fn to_offset_date_time(time: DateTime<FixedOffset>) -> Option<time::OffsetDateTime> {
+    let offset = time::UtcOffset::from_whole_seconds(time.offset().local_minus_utc()).ok()?;
+    time::OffsetDateTime::from_unix_timestamp(time.timestamp())
+        .ok()?
+        .replace_nanosecond(time.timestamp_subsec_nanos())
+        .ok()?
+        .checked_to_offset(offset)
+}
+
 /// Trait to define conversion to [`DateTime`]
 impl TryIntoTime for &str {
     fn into_time(self) -> Result<DateTime<Utc>, chrono::ParseError> {
         Ok(DateTime::<Utc>::from(DateTime::parse_from_rfc3339(self)?))
     }
+
+    fn into_time_with_offset(self) -> Result<DateTime<FixedOffset>, chrono::ParseError> {
+        DateTime::parse_from_rfc3339(self)
+    }
 }
 
 impl TryIntoTime for String {
     fn into_time(self) -> Result<DateTime<Utc>, chrono::ParseError> {
         self.as_str().into_time()
     }
+
+    fn into_time_with_offset(self) -> Result<DateTime<FixedOffset>, chrono::ParseError> {
+        self.as_str().into_time_with_offset()
+    }
 }
@@ -0,0 +1,310 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::borrow::Cow;
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+use url::Url;
+
+/// A [URI-reference](https://tools.ietf.org/html/rfc3986#section-4.1), used by the `source`
+/// and `dataschema` (`schemaurl` in v0.3) context attributes.
+///
+/// Unlike [`Url`], a [`UriReference`] can be relative, and it's kept exactly as provided,
+/// without any normalization.
+///
+/// ```
+/// use cloudevents::event::UriReference;
+///
+/// let relative = UriReference::parse("/sensors/tn-1234567/alerts").unwrap();
+/// assert!(!relative.is_absolute());
+///
+/// let absolute = UriReference::parse("urn:uuid:6e8bc430-9c3a-11d9-9669-0800200c9a66").unwrap();
+/// assert!(absolute.is_absolute());
+///
+/// assert!(UriReference::parse("not a uri").is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct UriReference(Cow<'static, str>);
+
+impl UriReference {
+    /// Parse and validate a URI-reference
+    pub fn parse(value: &str) -> Result<Self, InvalidUriReference> {
+        validate(value)?;
+        Ok(UriReference(Cow::Owned(value.to_string())))
+    }
+
+    /// Parse and validate a constant URI-reference, borrowing it instead of allocating a copy
+    pub fn from_static(value: &'static str) -> Result<Self, InvalidUriReference> {
+        validate(value)?;
+        Ok(UriReference(Cow::Borrowed(value)))
+    }
+
+    /// Get the URI-reference as string
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns `true` if the URI-reference has a scheme, hence it's an absolute URI
+    pub fn is_absolute(&self) -> bool {
+        scheme_end(&self.0).is_some()
+    }
+
+    /// Convert to [`Url`], failing if the URI-reference is relative
+    pub fn to_url(&self) -> Result<Url, url::ParseError> {
+        Url::parse(&self.0)
+    }
+}
+
+/// Error returned when a string is not a valid URI-reference
+#[derive(Debug, Clone, PartialEq)]
+pub struct InvalidUriReference {
+    value: String,
+    reason: &'static str,
+}
+
+impl InvalidUriReference {
+    pub(crate) fn new(value: &str, reason: &'static str) -> Self {
+        InvalidUriReference {
+            value: value.to_string(),
+            reason,
+        }
+    }
+}
+
+impl fmt::Display for InvalidUriReference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Invalid URI-reference '{}': {}", self.value, self.reason)
+    }
+}
+
+impl std::error::Error for InvalidUriReference {}
+
+/// Characters terminating the scheme or the first path segment
+const DELIMITERS: [char; 4] = [':', '/', '?', '#'];
+
+/// Position of the `:` terminating the scheme, if `value` starts with a scheme
+fn scheme_end(value: &str) -> Option<usize> {
+    let end = value.find(DELIMITERS)?;
+    if value.as_bytes()[end] != b':' {
+        return None;
+    }
+    let mut scheme = value[..end].bytes();
+    match scheme.next() {
+        Some(first) if first.is_ascii_alphabetic() => {}
+        _ => return None,
+    }
+    if scheme.all(|b| b.is_ascii_alphanumeric() || matches!(b, b'+' | b'-' | b'.')) {
+        Some(end)
+    } else {
+        None
+    }
+}
+
+fn validate(value: &str) -> Result<(), InvalidUriReference> {
+    let invalid = |reason| {
+        Err(InvalidUriReference {
+            value: value.to_string(),
+            reason,
+        })
+    };
+
+    if value.is_empty() {
+        return invalid("must not be empty");
+    }
+
+    // A relative reference can't start with a path segment containing `:`,
+    // which would be ambiguous with the scheme
+    let first_delimiter = value.find(DELIMITERS);
+    if first_delimiter.map(|i| value.as_bytes()[i]) == Some(b':') && scheme_end(value).is_none() {
+        return invalid("invalid scheme");
+    }
+
+    let bytes = value.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'%' => {
+                if bytes.len() < i + 3
+                    || !bytes[i + 1].is_ascii_hexdigit()
+                    || !bytes[i + 2].is_ascii_hexdigit()
+                {
+                    return invalid("invalid percent-encoding");
+                }
+                i += 3;
+                continue;
+            }
+            b if b.is_ascii_alphanumeric() => {}
+            // unreserved, gen-delims and sub-delims characters of RFC 3986
+            b'-' | b'.' | b'_' | b'~' | b':' | b'/' | b'?' | b'#' | b'[' | b']' | b'@' | b'!'
+            | b'$' | b'&' | b'\'' | b'(' | b')' | b'*' | b'+' | b',' | b';' | b'=' => {}
+            _ => return invalid("contains characters not allowed in a URI"),
+        }
+        i += 1;
+    }
+
+    Ok(())
+}
+
+impl fmt::Display for UriReference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl AsRef<str> for UriReference {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl FromStr for UriReference {
+    type Err = InvalidUriReference;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        UriReference::parse(s)
+    }
+}
+
+impl TryFrom<&str> for UriReference {
+    type Error = InvalidUriReference;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        UriReference::parse(value)
+    }
+}
+
+impl TryFrom<String> for UriReference {
+    type Error = InvalidUriReference;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        validate(&value)?;
+        Ok(UriReference(Cow::Owned(value)))
+    }
+}
+
+impl From<Url> for UriReference {
+    fn from(url: Url) -> Self {
+        UriReference(Cow::Owned(url.into()))
+    }
+}
+
+impl From<&Url> for UriReference {
+    fn from(url: &Url) -> Self {
+        UriReference(Cow::Owned(url.as_str().to_string()))
+    }
+}
+
+impl From<UriReference> for String {
+    fn from(uri_reference: UriReference) -> Self {
+        uri_reference.0.into_owned()
+    }
+}
+
+impl PartialEq<Url> for UriReference {
+    fn eq(&self, other: &Url) -> bool {
+        self.0 == other.as_str()
+    }
+}
+
+impl PartialEq<UriReference> for Url {
+    fn eq(&self, other: &UriReference) -> bool {
+        self.as_str() == other.0
+    }
+}
+
+impl PartialEq<str> for UriReference {
+    fn eq(&self, other: &str) -> bool {
+        self.0 == other
+    }
+}
+
+impl PartialEq<&str> for UriReference {
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+    }
+}
+
+impl Serialize for UriReference {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for UriReference {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        UriReference::try_from(String::deserialize(deserializer)?).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn valid_uri_references() {
+        for value in &[
+            "https://github.com/cloudevents/spec/pull",
+            "http://localhost:8080/path?query=1#fragment",
+            "urn:event:from:myapi/resource/123",
+            "mailto:cncf-wg-serverless@lists.cncf.io",
+            "/sensors/tn-1234567/alerts",
+            "1-555-123-4567",
+            "relative/path%20with%2Fescapes",
+            "./a:b",
+            "?query",
+            "#fragment",
+        ] {
+            let uri_reference = UriReference::parse(value).unwrap();
+            assert_eq!(*value, uri_reference.to_string());
+            assert_eq!(uri_reference, UriReference::from_static(value).unwrap());
+        }
+    }
+
+    #[test]
+    fn invalid_uri_references() {
+        for value in &[
+            "",
+            "with space",
+            "1abc:def",
+            "%zz",
+            "trailing%2",
+            "http://localhost/\u{e8}",
+        ] {
+            assert!(UriReference::parse(value).is_err(), "{}", value);
+            assert!(UriReference::from_static(value).is_err(), "{}", value);
+        }
+    }
+
+    #[test]
+    fn absolute_and_url_conversion() {
+        let absolute = UriReference::parse("http://localhost/").unwrap();
+        assert!(absolute.is_absolute());
+        assert_eq!(Url::parse("http://localhost/").unwrap(), absolute);
+        assert_eq!(absolute.to_url().unwrap(), absolute);
+        assert_eq!(
+            absolute,
+            UriReference::from(Url::parse("http://localhost/").unwrap())
+        );
+
+        let relative = UriReference::parse("/path").unwrap();
+        assert!(!relative.is_absolute());
+        assert!(relative.to_url().is_err());
+    }
+
+    #[test]
+    fn serde() {
+        let uri_reference = UriReference::parse("/path").unwrap();
+        let json = serde_json::to_value(&uri_reference).unwrap();
+        assert_eq!(serde_json::json!("/path"), json);
+        assert_eq!(
+            uri_reference,
+            serde_json::from_value::<UriReference>(json).unwrap()
+        );
+        assert!(serde_json::from_value::<UriReference>(serde_json::json!("a b")).is_err());
+    }
+}
@@ -1,32 +1,46 @@
+use crate::event::attributes::{join_time_offset, split_time_offset};
+use crate::event::ExtensionValue;
 use crate::message::{BinarySerializer, Error, MessageAttributeValue, Result};
+use std::borrow::Cow;
 use std::convert::TryInto;
 
 impl crate::event::message::AttributesDeserializer for super::Attributes {
-    fn deserialize_attributes<R: Sized, V: BinarySerializer<R>>(self, mut visitor: V) -> Result<V> {
-        visitor = visitor.set_attribute("id", MessageAttributeValue::String(self.id))?;
-        visitor = visitor.set_attribute("type", MessageAttributeValue::String(self.ty))?;
-        visitor = visitor.set_attribute("source", MessageAttributeValue::UriRef(self.source))?;
-        if self.datacontenttype.is_some() {
+    fn deserialize_attributes<R: Sized, V: BinarySerializer<R>>(
+        &self,
+        mut visitor: V,
+    ) -> Result<V> {
+        visitor = visitor.set_attribute("id", MessageAttributeValue::from(self.id.as_str()))?;
+        visitor = visitor.set_attribute("type", MessageAttributeValue::from(&*self.ty))?;
+        visitor = visitor.set_attribute(
+            "source",
+            MessageAttributeValue::UriRef(Cow::Borrowed(&self.source)),
+        )?;
+        if let Some(datacontenttype) = &self.datacontenttype {
             visitor = visitor.set_attribute(
                 "datacontenttype",
-                MessageAttributeValue::String(self.datacontenttype.unwrap()),
+                MessageAttributeValue::from(datacontenttype.as_str()),
             )?;
         }
-        if self.schemaurl.is_some() {
+        if let Some(schemaurl) = &self.schemaurl {
             visitor = visitor.set_attribute(
                 "schemaurl",
-                MessageAttributeValue::Uri(self.schemaurl.unwrap()),
+                MessageAttributeValue::UriRef(Cow::Borrowed(schemaurl)),
             )?;
         }
-        if self.subject.is_some() {
+        if let Some(subject) = &self.subject {
+            visitor =
+                visitor.set_attribute("subject", MessageAttributeValue::from(subject.as_str()))?;
+        }
+        if let Some(time) = &self.time {
             visitor = visitor.set_attribute(
-                "subject",
-                MessageAttributeValue::String(self.subject.unwrap()),
+                "time",
+                MessageAttributeValue::DateTime(join_time_offset(time, self.time_offset)),
             )?;
         }
-        if self.time.is_some() {
-            visitor = visitor
-                .set_attribute("time", MessageAttributeValue::DateTime(self.time.unwrap()))?;
+        let mut extensions: Vec<(&String, &ExtensionValue)> = self.extensions.iter().collect();
+        extensions.sort_unstable_by_key(|(k, _)| *k);
+        for (k, v) in extensions {
+            visitor = visitor.set_extension(k, v.into())?;
         }
         Ok(visitor)
     }
@@ -35,13 +49,31 @@ impl crate::event::message::AttributesDeserializer for super::Attributes {
 impl crate::event::message::AttributesSerializer for super::Attributes {
     fn serialize_attribute(&mut self, name: &str, value: MessageAttributeValue) -> Result<()> {
         match name {
-            "id" => self.id = value.to_string(),
-            "type" => self.ty = value.to_string(),
-            "source" => self.source = value.try_into()?,
-            "datacontenttype" => self.datacontenttype = Some(value.to_string()),
-            "schemaurl" => self.schemaurl = Some(value.try_into()?),
-            "subject" => self.subject = Some(value.to_string()),
-            "time" => self.time = Some(value.try_into()?),
+            "id" => self.id = value.into(),
+            "type" => self.ty = Cow::Owned(value.into()),
+            "source" => {
+                self.source = value
+                    .try_into()
+                    .map_err(Error::wrong_attribute_type("source", "URI-reference"))?
+            }
+            "datacontenttype" => self.datacontenttype = Some(value.into()),
+            "schemaurl" => {
+                self.schemaurl = Some(
+                    value
+                        .try_into()
+                        .map_err(Error::wrong_attribute_type("schemaurl", "URI-reference"))?,
+                )
+            }
+            "subject" => self.subject = Some(value.into()),
+            "time" => {
+                let (time, offset) = split_time_offset(Some(
+                    value
+                        .try_into()
+                        .map_err(Error::wrong_attribute_type("time", "timestamp"))?,
+                ));
+                self.time = time;
+                self.time_offset = offset;
+            }
             _ => {
                 return Err(Error::UnrecognizedAttributeName {
                     name: name.to_string(),
@@ -51,3 +83,43 @@ impl crate::event::message::AttributesSerializer for super::Attributes {
         Ok(())
     }
 }
+
+impl crate::event::message::AttributesSerializer for super::EventBuilder {
+    fn serialize_attribute(&mut self, name: &str, value: MessageAttributeValue) -> Result<()> {
+        match name {
+            "id" => self.id = Some(value.into()),
+            "type" => self.ty = Some(Cow::Owned(value.into())),
+            "source" => {
+                self.source = Some(
+                    value
+                        .try_into()
+                        .map_err(Error::wrong_attribute_type("source", "URI-reference"))?,
+                )
+            }
+            "datacontenttype" => self.datacontenttype = Some(value.into()),
+            "schemaurl" => {
+                self.schemaurl = Some(
+                    value
+                        .try_into()
+                        .map_err(Error::wrong_attribute_type("schemaurl", "URI-reference"))?,
+                )
+            }
+            "subject" => self.subject = Some(value.into()),
+            "time" => {
+                let (time, offset) = split_time_offset(Some(
+                    value
+                        .try_into()
+                        .map_err(Error::wrong_attribute_type("time", "timestamp"))?,
+                ));
+                self.time = time;
+                self.time_offset = offset;
+            }
+            _ => {
+                return Err(Error::UnrecognizedAttributeName {
+                    name: name.to_string(),
+                })
+            }
+        }
+        Ok(())
+    }
+}
@@ -1,88 +1,43 @@
 use crate::event::attributes::{
-    default_hostname, AttributeValue, AttributesConverter, DataAttributesWriter,
+    clear_cow, default_hostname, join_time_offset, split_time_offset, try_default_hostname,
+    AttributeValue, AttributesConverter, DataAttributesWriter,
 };
 use crate::event::{AttributesReader, AttributesV03, AttributesWriter, SpecVersion};
-use chrono::{DateTime, Utc};
+use crate::event::{
+    Clock, ExtensionValue, IdGenerator, InvalidUriReference, SystemClock, UriReference,
+    UuidV4Generator,
+};
+use chrono::{DateTime, FixedOffset, Utc};
 use core::fmt::Debug;
-use url::Url;
-use uuid::Uuid;
-
-pub(crate) const ATTRIBUTE_NAMES: [&'static str; 8] = [
-    "specversion",
-    "id",
-    "type",
-    "source",
-    "datacontenttype",
-    "dataschema",
-    "subject",
-    "time",
-];
+use std::borrow::Cow;
+use std::collections::{hash_map, HashMap};
 
 /// Data structure representing [CloudEvents V1.0 context attributes](https://github.com/cloudevents/spec/blob/v1.0/spec.md#context-attributes)
 #[derive(PartialEq, Debug, Clone)]
 pub struct Attributes {
     pub(crate) id: String,
-    pub(crate) ty: String,
-    pub(crate) source: Url,
+    pub(crate) ty: Cow<'static, str>,
+    pub(crate) source: UriReference,
     pub(crate) datacontenttype: Option<String>,
-    pub(crate) dataschema: Option<Url>,
+    pub(crate) dataschema: Option<UriReference>,
     pub(crate) subject: Option<String>,
     pub(crate) time: Option<DateTime<Utc>>,
+    /// Offset `time` was parsed or set with, `None` if it's UTC
+    pub(crate) time_offset: Option<FixedOffset>,
+    pub(crate) extensions: HashMap<String, ExtensionValue>,
 }
 
-impl<'a> IntoIterator for &'a Attributes {
-    type Item = (&'a str, AttributeValue<'a>);
-    type IntoIter = AttributesIntoIterator<'a>;
-
-    fn into_iter(self) -> Self::IntoIter {
-        AttributesIntoIterator {
-            attributes: self,
-            index: 0,
-        }
-    }
-}
-
-#[derive(PartialEq, Debug, Clone, Copy)]
-pub struct AttributesIntoIterator<'a> {
-    pub(crate) attributes: &'a Attributes,
-    pub(crate) index: usize,
-}
-
-impl<'a> Iterator for AttributesIntoIterator<'a> {
-    type Item = (&'a str, AttributeValue<'a>);
-    fn next(&mut self) -> Option<Self::Item> {
-        let result = match self.index {
-            0 => Some(("specversion", AttributeValue::SpecVersion(SpecVersion::V10))),
-            1 => Some(("id", AttributeValue::String(&self.attributes.id))),
-            2 => Some(("type", AttributeValue::String(&self.attributes.ty))),
-            3 => Some(("source", AttributeValue::URIRef(&self.attributes.source))),
-            4 => self
-                .attributes
-                .datacontenttype
-                .as_ref()
-                .map(|v| ("datacontenttype", AttributeValue::String(v))),
-            5 => self
-                .attributes
-                .dataschema
-                .as_ref()
-                .map(|v| ("dataschema", AttributeValue::URI(v))),
-            6 => self
-                .attributes
-                .subject
-                .as_ref()
-                .map(|v| ("subject", AttributeValue::String(v))),
-            7 => self
-                .attributes
-                .time
-                .as_ref()
-                .map(|v| ("time", AttributeValue::Time(v))),
-            _ => return None,
-        };
-        self.index += 1;
-        if result.is_none() {
-            return self.next();
-        }
-        result
+context_attributes! {
+    /// Names of the CloudEvents V1.0 context attributes
+    Attributes as attributes {
+        "specversion" => Some(AttributeValue::SpecVersion(SpecVersion::V10)),
+        "id" => Some(AttributeValue::String(&attributes.id)),
+        "type" => Some(AttributeValue::String(&attributes.ty)),
+        "source" => Some(AttributeValue::URIRef(&attributes.source)),
+        "datacontenttype" => attributes.datacontenttype.as_deref().map(AttributeValue::String),
+        "dataschema" => attributes.dataschema.as_ref().map(AttributeValue::URIRef),
+        "subject" => attributes.subject.as_deref().map(AttributeValue::String),
+        "time" => attributes.time.as_ref().map(AttributeValue::Time),
     }
 }
 
@@ -91,7 +46,7 @@ impl AttributesReader for Attributes {
         &self.id
     }
 
-    fn get_source(&self) -> &Url {
+    fn get_source(&self) -> &UriReference {
         &self.source
     }
 
@@ -107,7 +62,7 @@ impl AttributesReader for Attributes {
         self.datacontenttype.as_deref()
     }
 
-    fn get_dataschema(&self) -> Option<&Url> {
+    fn get_dataschema(&self) -> Option<&UriReference> {
         self.dataschema.as_ref()
     }
 
@@ -118,6 +73,20 @@ impl AttributesReader for Attributes {
     fn get_time(&self) -> Option<&DateTime<Utc>> {
         self.time.as_ref()
     }
+
+    fn get_time_with_offset(&self) -> Option<DateTime<FixedOffset>> {
+        self.time
+            .as_ref()
+            .map(|time| join_time_offset(time, self.time_offset))
+    }
+
+    fn get_extension(&self, extension_name: &str) -> Option<&ExtensionValue> {
+        self.extensions.get(extension_name)
+    }
+
+    fn iter_extensions(&self) -> hash_map::Iter<'_, String, ExtensionValue> {
+        self.extensions.iter()
+    }
 }
 
 impl AttributesWriter for Attributes {
@@ -125,12 +94,12 @@ impl AttributesWriter for Attributes {
         self.id = id.into()
     }
 
-    fn set_source(&mut self, source: impl Into<Url>) {
+    fn set_source(&mut self, source: impl Into<UriReference>) {
         self.source = source.into()
     }
 
     fn set_type(&mut self, ty: impl Into<String>) {
-        self.ty = ty.into()
+        self.ty = Cow::Owned(ty.into())
     }
 
     fn set_subject(&mut self, subject: Option<impl Into<String>>) {
@@ -138,7 +107,23 @@ impl AttributesWriter for Attributes {
     }
 
     fn set_time(&mut self, time: Option<impl Into<DateTime<Utc>>>) {
-        self.time = time.map(Into::into)
+        self.time = time.map(Into::into);
+        self.time_offset = None;
+    }
+
+    fn set_time_with_offset(&mut self, time: Option<impl Into<DateTime<FixedOffset>>>) {
+        let (time, offset) = split_time_offset(time.map(Into::into));
+        self.time = time;
+        self.time_offset = offset;
+    }
+
+    fn set_extension(&mut self, extension_name: &str, extension_value: impl Into<ExtensionValue>) {
+        self.extensions
+            .insert(extension_name.to_owned(), extension_value.into());
+    }
+
+    fn remove_extension(&mut self, extension_name: &str) -> Option<ExtensionValue> {
+        self.extensions.remove(extension_name)
     }
 }
 
@@ -147,25 +132,51 @@ impl DataAttributesWriter for Attributes {
         self.datacontenttype = datacontenttype.map(Into::into)
     }
 
-    fn set_dataschema(&mut self, dataschema: Option<impl Into<Url>>) {
+    fn set_dataschema(&mut self, dataschema: Option<impl Into<UriReference>>) {
         self.dataschema = dataschema.map(Into::into)
     }
 }
 
-impl Default for Attributes {
-    fn default() -> Self {
+impl Attributes {
+    /// Create new [`Attributes`] like [`Default::default`], failing instead of falling back to
+    /// `http://localhost/` if the default `source` is not a valid URI-reference
+    pub fn try_default() -> Result<Self, InvalidUriReference> {
+        Ok(Attributes::with_source(try_default_hostname()?))
+    }
+
+    /// Reset the attributes, see [`Event::reset`](crate::Event::reset)
+    pub(crate) fn reset(&mut self) {
+        self.id.clear();
+        clear_cow(&mut self.ty);
+        self.datacontenttype = None;
+        self.dataschema = None;
+        self.subject = None;
+        self.time = None;
+        self.time_offset = None;
+        self.extensions.clear();
+    }
+
+    fn with_source(source: UriReference) -> Self {
         Attributes {
-            id: Uuid::new_v4().to_string(),
-            ty: "type".to_string(),
-            source: default_hostname(),
+            id: UuidV4Generator.generate(),
+            ty: Cow::Borrowed("type"),
+            source,
             datacontenttype: None,
             dataschema: None,
             subject: None,
-            time: Some(Utc::now()),
+            time: Some(SystemClock.now()),
+            time_offset: None,
+            extensions: HashMap::new(),
         }
     }
 }
 
+impl Default for Attributes {
+    fn default() -> Self {
+        Attributes::with_source(default_hostname())
+    }
+}
+
 impl AttributesConverter for Attributes {
     fn into_v10(self) -> Self {
         self
@@ -180,6 +191,8 @@ impl AttributesConverter for Attributes {
             schemaurl: self.dataschema,
             subject: self.subject,
             time: self.time,
+            time_offset: self.time_offset,
+            extensions: self.extensions,
         }
     }
 }
@@ -193,8 +206,8 @@ mod tests {
     fn iterator_test_v10() {
         let a = Attributes {
             id: String::from("1"),
-            ty: String::from("someType"),
-            source: Url::parse("https://example.net").unwrap(),
+            ty: Cow::Borrowed("someType"),
+            source: UriReference::parse("https://example.net").unwrap(),
             datacontenttype: None,
             dataschema: None,
             subject: None,
@@ -202,6 +215,8 @@ mod tests {
                 NaiveDateTime::from_timestamp(61, 0),
                 Utc,
             )),
+            time_offset: None,
+            extensions: HashMap::new(),
         };
         let b = &mut a.into_iter();
         let time = DateTime::<Utc>::from_utc(NaiveDateTime::from_timestamp(61, 0), Utc);
@@ -218,7 +233,7 @@ mod tests {
         assert_eq!(
             (
                 "source",
-                AttributeValue::URIRef(&Url::parse("https://example.net").unwrap())
+                AttributeValue::URIRef(&UriReference::parse("https://example.net").unwrap())
             ),
             b.next().unwrap()
         );
@@ -1,24 +1,27 @@
 use super::Attributes as AttributesV10;
+use crate::event::attributes::split_time_offset;
 use crate::event::{
-    Attributes, Data, Event, EventBuilderError, ExtensionValue, TryIntoTime, TryIntoUrl,
+    Attributes, Data, Event, EventBuilderError, ExtensionValue, SpecVersion, TryIntoTime,
+    TryIntoUriReference, UriReference,
 };
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, FixedOffset, Utc};
+use std::borrow::Cow;
 use std::collections::HashMap;
-use url::Url;
 
 /// Builder to create a CloudEvent V1.0
 #[derive(Clone)]
 pub struct EventBuilder {
-    id: Option<String>,
-    ty: Option<String>,
-    source: Option<Url>,
-    datacontenttype: Option<String>,
-    dataschema: Option<Url>,
-    subject: Option<String>,
-    time: Option<DateTime<Utc>>,
-    data: Option<Data>,
-    extensions: HashMap<String, ExtensionValue>,
-    error: Option<EventBuilderError>,
+    pub(crate) id: Option<String>,
+    pub(crate) ty: Option<Cow<'static, str>>,
+    pub(crate) source: Option<UriReference>,
+    pub(crate) datacontenttype: Option<String>,
+    pub(crate) dataschema: Option<UriReference>,
+    pub(crate) subject: Option<String>,
+    pub(crate) time: Option<DateTime<Utc>>,
+    pub(crate) time_offset: Option<FixedOffset>,
+    pub(crate) data: Option<Data>,
+    pub(crate) extensions: HashMap<String, ExtensionValue>,
+    pub(crate) error: Option<EventBuilderError>,
 }
 
 impl EventBuilder {
@@ -27,11 +30,11 @@ impl EventBuilder {
         self
     }
 
-    pub fn source(mut self, source: impl TryIntoUrl) -> Self {
-        match source.into_url() {
+    pub fn source(mut self, source: impl TryIntoUriReference) -> Self {
+        match source.into_uri_reference() {
             Ok(u) => self.source = Some(u),
             Err(e) => {
-                self.error = Some(EventBuilderError::ParseUrlError {
+                self.error = Some(EventBuilderError::ParseUriReferenceError {
                     attribute_name: "source",
                     source: e,
                 })
@@ -41,7 +44,13 @@ impl EventBuilder {
     }
 
     pub fn ty(mut self, ty: impl Into<String>) -> Self {
-        self.ty = Some(ty.into());
+        self.ty = Some(Cow::Owned(ty.into()));
+        self
+    }
+
+    /// Set a constant `type`, borrowing it instead of allocating a copy for each event
+    pub fn static_ty(mut self, ty: &'static str) -> Self {
+        self.ty = Some(Cow::Borrowed(ty));
         self
     }
 
@@ -51,8 +60,12 @@ impl EventBuilder {
     }
 
     pub fn time(mut self, time: impl TryIntoTime) -> Self {
-        match time.into_time() {
-            Ok(u) => self.time = Some(u),
+        match time.into_time_with_offset() {
+            Ok(u) => {
+                let (time, offset) = split_time_offset(Some(u));
+                self.time = time;
+                self.time_offset = offset;
+            }
             Err(e) => {
                 self.error = Some(EventBuilderError::ParseTimeError {
                     attribute_name: "time",
@@ -82,14 +95,14 @@ impl EventBuilder {
     pub fn data_with_schema(
         mut self,
         datacontenttype: impl Into<String>,
-        schemaurl: impl TryIntoUrl,
+        schemaurl: impl TryIntoUriReference,
         data: impl Into<Data>,
     ) -> Self {
         self.datacontenttype = Some(datacontenttype.into());
-        match schemaurl.into_url() {
+        match schemaurl.into_uri_reference() {
             Ok(u) => self.dataschema = Some(u),
             Err(e) => {
-                self.error = Some(EventBuilderError::ParseUrlError {
+                self.error = Some(EventBuilderError::ParseUriReferenceError {
                     attribute_name: "dataschema",
                     source: e,
                 })
@@ -102,9 +115,10 @@ impl EventBuilder {
 
 impl From<Event> for EventBuilder {
     fn from(event: Event) -> Self {
-        let attributes = match event.attributes.into_v10() {
+        let (event, _) = event.convert_to(SpecVersion::V10);
+        let attributes = match event.attributes {
             Attributes::V10(attr) => attr,
-            // This branch is unreachable because into_v10() returns
+            // This branch is unreachable because convert_to() returns
             // always a Attributes::V10
             _ => unreachable!(),
         };
@@ -117,8 +131,9 @@ impl From<Event> for EventBuilder {
             dataschema: attributes.dataschema,
             subject: attributes.subject,
             time: attributes.time,
+            time_offset: attributes.time_offset,
             data: event.data,
-            extensions: event.extensions,
+            extensions: attributes.extensions,
             error: None,
         }
     }
@@ -140,6 +155,7 @@ impl crate::event::builder::EventBuilder for EventBuilder {
             dataschema: None,
             subject: None,
             time: None,
+            time_offset: None,
             data: None,
             extensions: Default::default(),
             error: None,
@@ -166,9 +182,10 @@ impl crate::event::builder::EventBuilder for EventBuilder {
                     dataschema: self.dataschema,
                     subject: self.subject,
                     time: self.time,
+                    time_offset: self.time_offset,
+                    extensions: self.extensions,
                 }),
                 data: self.data,
-                extensions: self.extensions,
             }),
         }
     }
@@ -1,13 +1,15 @@
 use super::Attributes;
-use crate::event::data::is_json_content_type;
-use crate::event::{Data, ExtensionValue};
-use chrono::{DateTime, Utc};
+use crate::event::attributes::split_time_offset;
+use crate::event::is_json_content_type;
+use crate::event::Data;
+use crate::event::SpecVersion;
+use crate::event::UriReference;
+use chrono::DateTime;
 use serde::de::IntoDeserializer;
 use serde::ser::SerializeMap;
-use serde::{Deserialize, Serializer};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde_value::Value;
 use std::collections::{BTreeMap, HashMap};
-use url::Url;
 
 pub(crate) struct EventFormatDeserializer {}
 
@@ -15,17 +17,23 @@ impl crate::event::format::EventFormatDeserializer for EventFormatDeserializer {
     fn deserialize_attributes<E: serde::de::Error>(
         map: &mut BTreeMap<String, Value>,
     ) -> Result<crate::event::Attributes, E> {
+        let (time, time_offset) = split_time_offset(parse_optional_field!(
+            map,
+            "time",
+            String,
+            E,
+            DateTime::parse_from_rfc3339
+        )?);
         Ok(crate::event::Attributes::V10(Attributes {
             id: parse_field!(map, "id", String, E)?,
-            ty: parse_field!(map, "type", String, E)?,
-            source: parse_field!(map, "source", String, E, Url::parse)?,
+            ty: parse_field!(map, "type", String, E)?.into(),
+            source: parse_field!(map, "source", String, E, UriReference::parse)?,
             datacontenttype: parse_optional_field!(map, "datacontenttype", String, E)?,
-            dataschema: parse_optional_field!(map, "dataschema", String, E, Url::parse)?,
+            dataschema: parse_optional_field!(map, "dataschema", String, E, UriReference::parse)?,
             subject: parse_optional_field!(map, "subject", String, E)?,
-            time: parse_optional_field!(map, "time", String, E, |s| DateTime::parse_from_rfc3339(
-                s
-            )
-            .map(DateTime::<Utc>::from))?,
+            time,
+            time_offset,
+            extensions: HashMap::new(),
         }))
     }
 
@@ -57,11 +65,11 @@ impl<S: serde::Serializer> crate::event::format::EventFormatSerializer<S, Attrib
     fn serialize(
         attributes: &Attributes,
         data: &Option<Data>,
-        extensions: &HashMap<String, ExtensionValue>,
         serializer: S,
     ) -> Result<<S as Serializer>::Ok, <S as Serializer>::Error> {
+        // The length of the map is required by the length prefixed formats, e.g. MessagePack
         let num =
-            3 + if attributes.datacontenttype.is_some() {
+            4 + if attributes.datacontenttype.is_some() {
                 1
             } else {
                 0
@@ -72,7 +80,7 @@ impl<S: serde::Serializer> crate::event::format::EventFormatSerializer<S, Attrib
             } + if attributes.subject.is_some() { 1 } else { 0 }
                 + if attributes.time.is_some() { 1 } else { 0 }
                 + if data.is_some() { 1 } else { 0 }
-                + extensions.len();
+                + attributes.extensions.len();
         let mut state = serializer.serialize_map(Some(num))?;
         state.serialize_entry("specversion", "1.0")?;
         state.serialize_entry("id", &attributes.id)?;
@@ -87,8 +95,12 @@ impl<S: serde::Serializer> crate::event::format::EventFormatSerializer<S, Attrib
         if let Some(subject) = &attributes.subject {
             state.serialize_entry("subject", subject)?;
         }
-        if let Some(time) = &attributes.time {
-            state.serialize_entry("time", time)?;
+        match (&attributes.time, attributes.time_offset) {
+            (Some(time), Some(offset)) => {
+                state.serialize_entry("time", &time.with_timezone(&offset))?
+            }
+            (Some(time), None) => state.serialize_entry("time", time)?,
+            (None, _) => (),
         }
         match data {
             Some(Data::Json(j)) => state.serialize_entry("data", j)?,
@@ -96,9 +108,40 @@ impl<S: serde::Serializer> crate::event::format::EventFormatSerializer<S, Attrib
             Some(Data::Binary(v)) => state.serialize_entry("data_base64", &base64::encode(v))?,
             _ => (),
         };
-        for (k, v) in extensions {
+        for (k, v) in &attributes.extensions {
             state.serialize_entry(k, v)?;
         }
         state.end()
     }
 }
+
+impl Serialize for Attributes {
+    fn serialize<S>(&self, serializer: S) -> Result<<S as Serializer>::Ok, <S as Serializer>::Error>
+    where
+        S: Serializer,
+    {
+        <EventFormatSerializer as crate::event::format::EventFormatSerializer<S, Attributes>>::serialize(
+            self, &None, serializer,
+        )
+    }
+}
+
+/// Deserialize the context attributes and extensions of a JSON event, ignoring its data.
+impl<'de> Deserialize<'de> for Attributes {
+    fn deserialize<D>(deserializer: D) -> Result<Self, <D as Deserializer<'de>>::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let mut map = crate::event::format::deserialize_to_map(deserializer)?;
+        crate::event::format::expect_spec_version(&mut map, SpecVersion::V10)?;
+
+        match <EventFormatDeserializer as crate::event::format::EventFormatDeserializer>::deserialize_event(map)?
+            .attributes
+        {
+            crate::event::Attributes::V10(a) => Ok(a),
+            // This branch is unreachable because this EventFormatDeserializer
+            // always returns a Attributes::V10
+            _ => unreachable!(),
+        }
+    }
+}
@@ -5,7 +5,7 @@ mod message;
 
 pub use attributes::Attributes;
 pub(crate) use attributes::AttributesIntoIterator;
-pub(crate) use attributes::ATTRIBUTE_NAMES;
+pub use attributes::ATTRIBUTE_NAMES;
 pub use builder::EventBuilder;
 pub(crate) use format::EventFormatDeserializer;
 pub(crate) use format::EventFormatSerializer;
@@ -0,0 +1,246 @@
+use super::{AttributesReader, Event, SpecVersion};
+use chrono::Datelike;
+use snafu::Snafu;
+
+/// Maximum size in bytes of a context attribute or extension value accepted by [`Event::validate`].
+/// The spec doesn't mandate a limit, this value is conservative enough to fit in the headers of
+/// the header based protocol bindings.
+pub const MAX_ATTRIBUTE_VALUE_SIZE: usize = 4096;
+
+/// Maximum length of an extension name recommended by the spec, see [`ValidationWarning`]
+pub const MAX_EXTENSION_NAME_LENGTH: usize = 20;
+
+/// Represents a violation of the CloudEvents spec found by [`Event::validate`]
+#[derive(Debug, Snafu, Clone, PartialEq)]
+pub enum ValidationError {
+    #[snafu(display("Attribute '{}' must be a non-empty string", attribute_name))]
+    EmptyAttribute { attribute_name: String },
+    #[snafu(display("Time '{}' is outside the RFC 3339 range", time))]
+    InvalidTime { time: String },
+    #[snafu(display("Invalid extension name '{}': {}", extension_name, reason))]
+    InvalidExtensionName {
+        extension_name: String,
+        reason: &'static str,
+    },
+    #[snafu(display(
+        "Attribute '{}' value is {} bytes long, exceeding the maximum of {} bytes",
+        attribute_name,
+        size,
+        max_size
+    ))]
+    AttributeValueTooLarge {
+        attribute_name: String,
+        size: usize,
+        max_size: usize,
+    },
+}
+
+/// Represents a deviation from a recommendation of the CloudEvents spec found by
+/// [`Event::lint`], which doesn't make the event invalid
+#[derive(Debug, Snafu, Clone, PartialEq)]
+pub enum ValidationWarning {
+    #[snafu(display(
+        "Extension name '{}' is longer than {} characters",
+        extension_name,
+        max_length
+    ))]
+    LongExtensionName {
+        extension_name: String,
+        max_length: usize,
+    },
+}
+
+pub(crate) fn validate(event: &Event) -> Vec<ValidationError> {
+    let mut errors = Vec::new();
+
+    for (attribute_name, value) in &[
+        ("id", Some(event.get_id())),
+        ("type", Some(event.get_type())),
+        ("subject", event.get_subject()),
+        ("datacontenttype", event.get_datacontenttype()),
+    ] {
+        if *value == Some("") {
+            errors.push(ValidationError::EmptyAttribute {
+                attribute_name: attribute_name.to_string(),
+            });
+        }
+    }
+
+    if let Some(time) = event.get_time() {
+        if !(0..=9999).contains(&time.year()) {
+            errors.push(ValidationError::InvalidTime {
+                time: time.to_string(),
+            });
+        }
+    }
+
+    let mut extension_names: Vec<&str> = event
+        .iter_extensions()
+        .map(|(name, _)| name.as_str())
+        .collect();
+    extension_names.sort_unstable();
+    for extension_name in extension_names {
+        if let Some(reason) = invalid_extension_name_reason(extension_name) {
+            errors.push(ValidationError::InvalidExtensionName {
+                extension_name: extension_name.to_string(),
+                reason,
+            });
+        }
+    }
+
+    for (attribute_name, value) in event.attributes_iter() {
+        let size = value.to_string().len();
+        if size > MAX_ATTRIBUTE_VALUE_SIZE {
+            errors.push(ValidationError::AttributeValueTooLarge {
+                attribute_name: attribute_name.to_string(),
+                size,
+                max_size: MAX_ATTRIBUTE_VALUE_SIZE,
+            });
+        }
+    }
+
+    errors
+}
+
+pub(crate) fn lint(event: &Event) -> Vec<ValidationWarning> {
+    let mut extension_names: Vec<&str> = event
+        .iter_extensions()
+        .map(|(name, _)| name.as_str())
+        .filter(|name| is_long_extension_name(name))
+        .collect();
+    extension_names.sort_unstable();
+    extension_names
+        .into_iter()
+        .map(|extension_name| ValidationWarning::LongExtensionName {
+            extension_name: extension_name.to_string(),
+            max_length: MAX_EXTENSION_NAME_LENGTH,
+        })
+        .collect()
+}
+
+/// The spec only recommends the extension names to be at most 20 characters long, so a longer
+/// name is a [`ValidationWarning`] instead of an invalid name
+pub(crate) fn is_long_extension_name(name: &str) -> bool {
+    name.len() > MAX_EXTENSION_NAME_LENGTH
+}
+
+pub(crate) fn invalid_extension_name_reason(name: &str) -> Option<&'static str> {
+    if name.is_empty() {
+        Some("must not be empty")
+    } else if !name
+        .bytes()
+        .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit())
+    {
+        Some("must contain only lowercase letters and digits")
+    } else if name == "data" || SpecVersion::all_attribute_names().any(|n| n == name) {
+        Some("must not collide with a context attribute name")
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::AttributesWriter;
+    use crate::{EventBuilder, EventBuilderV10};
+    use chrono::{TimeZone, Utc};
+
+    fn event() -> Event {
+        EventBuilderV10::new()
+            .id("0001")
+            .source("http://localhost/")
+            .ty("example.test")
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn valid_event() {
+        let mut e = event();
+        e.set_extension("someint", 10);
+        e.set_extension("ext2", "value");
+        assert_eq!(Ok(()), e.validate());
+    }
+
+    #[test]
+    fn empty_attributes() {
+        let mut e = event();
+        e.set_id("");
+        e.set_type("");
+        e.set_subject(Some(""));
+        assert_eq!(
+            Err(vec![
+                ValidationError::EmptyAttribute {
+                    attribute_name: String::from("id")
+                },
+                ValidationError::EmptyAttribute {
+                    attribute_name: String::from("type")
+                },
+                ValidationError::EmptyAttribute {
+                    attribute_name: String::from("subject")
+                },
+            ]),
+            e.validate()
+        );
+    }
+
+    #[test]
+    fn invalid_time() {
+        let mut e = event();
+        e.set_time(Some(Utc.ymd(10000, 1, 1).and_hms(0, 0, 0)));
+        assert!(matches!(
+            e.validate().unwrap_err().as_slice(),
+            [ValidationError::InvalidTime { .. }]
+        ));
+    }
+
+    #[test]
+    fn invalid_extension_names() {
+        let mut e = event();
+        e.set_extension("Upper", "value");
+        e.set_extension("with_underscore", "value");
+        e.set_extension("dataschema", "value");
+
+        let names: Vec<String> = e
+            .validate()
+            .unwrap_err()
+            .into_iter()
+            .map(|err| match err {
+                ValidationError::InvalidExtensionName { extension_name, .. } => extension_name,
+                other => panic!("unexpected error {}", other),
+            })
+            .collect();
+        assert_eq!(vec!["Upper", "dataschema", "with_underscore"], names);
+    }
+
+    #[test]
+    fn long_extension_names() {
+        let mut e = event();
+        e.set_extension("averyveryverylongextensionname", "value");
+        e.set_extension("exactlytwentycharsxx", "value");
+        assert_eq!(Ok(()), e.validate());
+        assert_eq!(
+            vec![ValidationWarning::LongExtensionName {
+                extension_name: String::from("averyveryverylongextensionname"),
+                max_length: MAX_EXTENSION_NAME_LENGTH,
+            }],
+            e.lint()
+        );
+        assert!(event().lint().is_empty());
+    }
+
+    #[test]
+    fn attribute_value_too_large() {
+        let mut e = event();
+        e.set_extension("large", "a".repeat(MAX_ATTRIBUTE_VALUE_SIZE + 1));
+        assert_eq!(
+            Err(vec![ValidationError::AttributeValueTooLarge {
+                attribute_name: String::from("large"),
+                size: MAX_ATTRIBUTE_VALUE_SIZE + 1,
+                max_size: MAX_ATTRIBUTE_VALUE_SIZE,
+            }]),
+            e.validate()
+        );
+    }
+}
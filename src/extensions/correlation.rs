@@ -0,0 +1,161 @@
+use super::{get_string_extension, Result};
+use crate::event::{AttributesReader, AttributesWriter};
+use crate::Event;
+
+pub(crate) const CORRELATIONID: &str = "correlationid";
+pub(crate) const CAUSATIONID: &str = "causationid";
+
+/// Correlation extension, carrying the `correlationid` shared by all the events of a workflow
+/// and the `causationid` of the event which caused this one, as used in event-sourced systems.
+///
+/// ```
+/// use cloudevents::extensions::CorrelationExtension;
+/// use cloudevents::{EventBuilder, EventBuilderV10};
+///
+/// let order_placed = EventBuilderV10::new()
+///     .id("0001")
+///     .source("http://localhost/orders")
+///     .ty("order.placed")
+///     .build()
+///     .unwrap();
+///
+/// let mut payment_requested = EventBuilderV10::new()
+///     .id("0002")
+///     .source("http://localhost/payments")
+///     .ty("payment.requested")
+///     .build()
+///     .unwrap();
+/// CorrelationExtension::derive_from(&order_placed).write_to(&mut payment_requested);
+///
+/// let extension = CorrelationExtension::from_event(&payment_requested)
+///     .unwrap()
+///     .unwrap();
+/// assert_eq!("0001", extension.correlation_id());
+/// assert_eq!(Some("0001"), extension.causation_id());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct CorrelationExtension {
+    correlationid: String,
+    causationid: Option<String>,
+}
+
+impl CorrelationExtension {
+    /// Create a new [`CorrelationExtension`]
+    pub fn new(correlationid: impl Into<String>, causationid: Option<impl Into<String>>) -> Self {
+        CorrelationExtension {
+            correlationid: correlationid.into(),
+            causationid: causationid.map(Into::into),
+        }
+    }
+
+    /// Create the [`CorrelationExtension`] of an event caused by `parent`: the `causationid` is
+    /// the `id` of `parent`, and the `correlationid` is propagated from `parent`, or set to its
+    /// `id` if `parent` starts the workflow.
+    ///
+    /// A `correlationid` of `parent` of another type than string is ignored.
+    pub fn derive_from(parent: &Event) -> Self {
+        let correlationid = get_string_extension(parent, CORRELATIONID)
+            .ok()
+            .flatten()
+            .unwrap_or_else(|| parent.get_id());
+        CorrelationExtension::new(correlationid, Some(parent.get_id()))
+    }
+
+    /// Read the extension from `event`, returning `None` if it has no `correlationid`
+    pub fn from_event(event: &Event) -> Result<Option<Self>> {
+        match get_string_extension(event, CORRELATIONID)? {
+            Some(correlationid) => Ok(Some(CorrelationExtension::new(
+                correlationid,
+                get_string_extension(event, CAUSATIONID)?,
+            ))),
+            None => Ok(None),
+        }
+    }
+
+    /// Write `correlationid` and `causationid` to `event`, removing any previous `causationid`
+    pub fn write_to(self, event: &mut Event) {
+        event.set_extension(CORRELATIONID, self.correlationid);
+        match self.causationid {
+            Some(causationid) => event.set_extension(CAUSATIONID, causationid),
+            None => {
+                event.remove_extension(CAUSATIONID);
+            }
+        }
+    }
+
+    /// Remove `correlationid` and `causationid` from `event`
+    pub fn remove_from(event: &mut Event) {
+        event.remove_extension(CORRELATIONID);
+        event.remove_extension(CAUSATIONID);
+    }
+
+    /// Get the `correlationid` value
+    pub fn correlation_id(&self) -> &str {
+        &self.correlationid
+    }
+
+    /// Get the `causationid` value
+    pub fn causation_id(&self) -> Option<&str> {
+        self.causationid.as_deref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::ExtensionValue;
+    use crate::extensions::Error;
+    use crate::{EventBuilder, EventBuilderV10};
+
+    fn event(id: &str) -> Event {
+        EventBuilderV10::new()
+            .id(id)
+            .source("http://localhost/")
+            .ty("example.test")
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn roundtrip() {
+        let mut e = event("0001");
+        assert_eq!(None, CorrelationExtension::from_event(&e).unwrap());
+
+        let extension = CorrelationExtension::new("abc", None::<String>);
+        extension.clone().write_to(&mut e);
+        assert_eq!(
+            Some(&ExtensionValue::from("abc")),
+            e.get_extension(CORRELATIONID)
+        );
+        assert_eq!(
+            Some(extension),
+            CorrelationExtension::from_event(&e).unwrap()
+        );
+
+        e.set_extension(CAUSATIONID, 1);
+        assert!(matches!(
+            CorrelationExtension::from_event(&e),
+            Err(Error::InvalidExtensionType { .. })
+        ));
+
+        CorrelationExtension::remove_from(&mut e);
+        assert!(e.get_extension(CORRELATIONID).is_none());
+        assert!(e.get_extension(CAUSATIONID).is_none());
+    }
+
+    #[test]
+    fn derive_from() {
+        let root = event("0001");
+        let extension = CorrelationExtension::derive_from(&root);
+        assert_eq!(CorrelationExtension::new("0001", Some("0001")), extension);
+
+        let mut child = event("0002");
+        extension.write_to(&mut child);
+        let mut grandchild = event("0003");
+        CorrelationExtension::derive_from(&child).write_to(&mut grandchild);
+        assert_eq!(
+            Some(CorrelationExtension::new("0001", Some("0002"))),
+            CorrelationExtension::from_event(&grandchild).unwrap()
+        );
+    }
+}
@@ -0,0 +1,268 @@
+use super::{get_string_extension, Error, Result};
+use crate::event::{AttributesWriter, Data};
+use crate::Event;
+
+pub(crate) const DATAREF: &str = "dataref";
+
+/// Storage for event data externalized with the [`DatarefExtension`].
+///
+/// It's implemented by `Arc`s of [`DataStore`], to share a store.
+pub trait DataStore {
+    /// Store `data` of `event`, returning the URI-reference to set as `dataref`
+    fn store(
+        &self,
+        event: &Event,
+        data: Data,
+    ) -> std::result::Result<String, Box<dyn std::error::Error + Send + Sync>>;
+
+    /// Load the data referenced by `dataref`
+    fn load(
+        &self,
+        dataref: &str,
+    ) -> std::result::Result<Data, Box<dyn std::error::Error + Send + Sync>>;
+
+    /// Delete the data referenced by `dataref`, e.g. when the event it was stored for isn't sent.
+    /// Does nothing by default.
+    fn delete(
+        &self,
+        dataref: &str,
+    ) -> std::result::Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let _ = dataref;
+        Ok(())
+    }
+}
+
+impl<S: DataStore + ?Sized> DataStore for std::sync::Arc<S> {
+    fn store(
+        &self,
+        event: &Event,
+        data: Data,
+    ) -> std::result::Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        (**self).store(event, data)
+    }
+
+    fn load(
+        &self,
+        dataref: &str,
+    ) -> std::result::Result<Data, Box<dyn std::error::Error + Send + Sync>> {
+        (**self).load(dataref)
+    }
+
+    fn delete(
+        &self,
+        dataref: &str,
+    ) -> std::result::Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        (**self).delete(dataref)
+    }
+}
+
+/// [Dataref extension](https://github.com/cloudevents/spec/blob/v1.0/extensions/dataref.md),
+/// referencing the location of the event data, to implement the
+/// [claim check pattern](https://www.enterpriseintegrationpatterns.com/patterns/messaging/StoreInLibrary.html)
+/// for large payloads.
+///
+/// ```
+/// use cloudevents::event::Data;
+/// use cloudevents::extensions::{DataStore, DatarefExtension};
+/// use cloudevents::{AttributesReader, Event};
+/// use std::collections::HashMap;
+/// use std::error::Error;
+/// use std::sync::Mutex;
+///
+/// #[derive(Default)]
+/// struct MemoryStore(Mutex<HashMap<String, Data>>);
+///
+/// impl DataStore for MemoryStore {
+///     fn store(&self, event: &Event, data: Data) -> Result<String, Box<dyn Error + Send + Sync>> {
+///         let dataref = format!("memory://{}", event.get_id());
+///         self.0.lock().unwrap().insert(dataref.clone(), data);
+///         Ok(dataref)
+///     }
+///
+///     fn load(&self, dataref: &str) -> Result<Data, Box<dyn Error + Send + Sync>> {
+///         self.0.lock().unwrap().get(dataref).cloned().ok_or_else(|| "not found".into())
+///     }
+/// }
+///
+/// let store = MemoryStore::default();
+/// let mut event = Event::default();
+/// event.write_data("text/plain", "a very large payload");
+/// let original = event.clone();
+///
+/// // Producer side
+/// DatarefExtension::externalize(&mut event, &store).unwrap();
+/// assert!(event.get_data::<Data>().is_none());
+///
+/// // Consumer side
+/// DatarefExtension::resolve(&mut event, &store).unwrap();
+/// assert_eq!(original, event);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct DatarefExtension {
+    dataref: String,
+}
+
+impl DatarefExtension {
+    /// Create a new [`DatarefExtension`]
+    pub fn new(dataref: impl Into<String>) -> Self {
+        DatarefExtension {
+            dataref: dataref.into(),
+        }
+    }
+
+    /// Read the extension from `event`, returning `None` if it has no `dataref`
+    pub fn from_event(event: &Event) -> Result<Option<Self>> {
+        Ok(get_string_extension(event, DATAREF)?.map(DatarefExtension::new))
+    }
+
+    /// Write `dataref` to `event`
+    pub fn write_to(self, event: &mut Event) {
+        event.set_extension(DATAREF, self.dataref);
+    }
+
+    /// Remove `dataref` from `event`
+    pub fn remove_from(event: &mut Event) {
+        event.remove_extension(DATAREF);
+    }
+
+    /// Get the `dataref` value
+    pub fn dataref(&self) -> &str {
+        &self.dataref
+    }
+
+    /// Move the data of `event` to `store`, setting `dataref` to its location.
+    /// `datacontenttype` and `dataschema` are kept, since they describe the referenced data.
+    /// Returns `false` if the event has no data.
+    pub fn externalize(event: &mut Event, store: &impl DataStore) -> Result<bool> {
+        let data = match event.data.take() {
+            Some(data) => data,
+            None => return Ok(false),
+        };
+        match store.store(event, data.clone()) {
+            Ok(dataref) => {
+                DatarefExtension::new(dataref).write_to(event);
+                Ok(true)
+            }
+            Err(source) => {
+                event.data = Some(data);
+                Err(Error::DataStoreError { source })
+            }
+        }
+    }
+
+    /// Load the data referenced by `dataref` from `store` into `event`, removing `dataref`.
+    /// Returns `false` if the event already has data or has no `dataref`.
+    pub fn resolve(event: &mut Event, store: &impl DataStore) -> Result<bool> {
+        if event.data.is_some() {
+            return Ok(false);
+        }
+        let extension = match DatarefExtension::from_event(event)? {
+            Some(extension) => extension,
+            None => return Ok(false),
+        };
+        event.data = Some(
+            store
+                .load(extension.dataref())
+                .map_err(|source| Error::DataStoreError { source })?,
+        );
+        DatarefExtension::remove_from(event);
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::{AttributesReader, ExtensionValue};
+    use serde_json::json;
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+    use url::Url;
+
+    #[derive(Default)]
+    struct MemoryStore {
+        data: RefCell<HashMap<String, Data>>,
+        fail: bool,
+    }
+
+    impl DataStore for MemoryStore {
+        fn store(
+            &self,
+            event: &Event,
+            data: Data,
+        ) -> std::result::Result<String, Box<dyn std::error::Error + Send + Sync>> {
+            if self.fail {
+                return Err("store unavailable".into());
+            }
+            let dataref = format!("memory://{}", event.get_id());
+            self.data.borrow_mut().insert(dataref.clone(), data);
+            Ok(dataref)
+        }
+
+        fn load(
+            &self,
+            dataref: &str,
+        ) -> std::result::Result<Data, Box<dyn std::error::Error + Send + Sync>> {
+            self.data
+                .borrow()
+                .get(dataref)
+                .cloned()
+                .ok_or_else(|| "not found".into())
+        }
+    }
+
+    fn event() -> Event {
+        let mut e = Event::default();
+        e.set_id("0001");
+        e.write_data_with_schema(
+            "application/json",
+            Url::parse("http://localhost/schema").unwrap(),
+            json!({"hello": "world"}),
+        );
+        e
+    }
+
+    #[test]
+    fn externalize_and_resolve() {
+        let store = MemoryStore::default();
+        let mut e = event();
+        let original = e.clone();
+
+        assert!(DatarefExtension::externalize(&mut e, &store).unwrap());
+        assert!(e.get_data::<Data>().is_none());
+        assert_eq!(Some("application/json"), e.get_datacontenttype());
+        assert!(e.get_dataschema().is_some());
+        assert_eq!(
+            Some(&ExtensionValue::from("memory://0001")),
+            e.get_extension("dataref")
+        );
+        assert!(!DatarefExtension::externalize(&mut e, &store).unwrap());
+
+        assert!(DatarefExtension::resolve(&mut e, &store).unwrap());
+        assert_eq!(original, e);
+        assert!(!DatarefExtension::resolve(&mut e, &store).unwrap());
+    }
+
+    #[test]
+    fn store_errors() {
+        let failing = MemoryStore {
+            fail: true,
+            ..MemoryStore::default()
+        };
+        let mut e = event();
+        let original = e.clone();
+        assert!(matches!(
+            DatarefExtension::externalize(&mut e, &failing),
+            Err(Error::DataStoreError { .. })
+        ));
+        assert_eq!(original, e);
+
+        let mut e = Event::default();
+        DatarefExtension::new("memory://missing").write_to(&mut e);
+        assert!(matches!(
+            DatarefExtension::resolve(&mut e, &MemoryStore::default()),
+            Err(Error::DataStoreError { .. })
+        ));
+        assert!(e.get_extension("dataref").is_some());
+    }
+}
@@ -0,0 +1,367 @@
+use super::{get_string_extension, Error, Result};
+use crate::event::AttributesWriter;
+use crate::Event;
+
+pub(crate) const TRACEPARENT: &str = "traceparent";
+pub(crate) const TRACESTATE: &str = "tracestate";
+
+/// [Distributed Tracing extension](https://github.com/cloudevents/spec/blob/v1.0/extensions/distributed-tracing.md),
+/// carrying the [W3C Trace Context](https://www.w3.org/TR/trace-context/) `traceparent` and `tracestate`
+/// of the operation that produced the event.
+///
+/// ```
+/// use cloudevents::extensions::DistributedTraceExtension;
+/// use cloudevents::{EventBuilder, EventBuilderV10};
+///
+/// let mut event = EventBuilderV10::new()
+///     .id("0001")
+///     .source("http://localhost/")
+///     .ty("example.demo")
+///     .build()
+///     .unwrap();
+///
+/// DistributedTraceExtension::new(
+///     "00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01",
+///     Some("congo=t61rcWkgMzE"),
+/// )
+/// .unwrap()
+/// .write_to(&mut event);
+///
+/// let extension = DistributedTraceExtension::from_event(&event).unwrap().unwrap();
+/// assert_eq!("0af7651916cd43dd8448eb211c80319c", extension.trace_id());
+/// assert!(extension.sampled());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct DistributedTraceExtension {
+    traceparent: String,
+    tracestate: Option<String>,
+}
+
+impl DistributedTraceExtension {
+    /// Create a new [`DistributedTraceExtension`], validating `traceparent` and `tracestate`
+    pub fn new(
+        traceparent: impl Into<String>,
+        tracestate: Option<impl Into<String>>,
+    ) -> Result<Self> {
+        let traceparent = traceparent.into();
+        validate_traceparent(&traceparent)?;
+        let tracestate = tracestate.map(Into::into);
+        if let Some(tracestate) = &tracestate {
+            validate_tracestate(tracestate)?;
+        }
+        Ok(DistributedTraceExtension {
+            traceparent,
+            tracestate,
+        })
+    }
+
+    /// Read the extension from `event`, returning `None` if it has no `traceparent`
+    pub fn from_event(event: &Event) -> Result<Option<Self>> {
+        match get_string_extension(event, TRACEPARENT)? {
+            Some(traceparent) => Ok(Some(DistributedTraceExtension::new(
+                traceparent,
+                get_string_extension(event, TRACESTATE)?,
+            )?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Write `traceparent` and `tracestate` to `event`, removing any previous `tracestate`
+    pub fn write_to(self, event: &mut Event) {
+        event.set_extension(TRACEPARENT, self.traceparent);
+        match self.tracestate {
+            Some(tracestate) => event.set_extension(TRACESTATE, tracestate),
+            None => {
+                event.remove_extension(TRACESTATE);
+            }
+        }
+    }
+
+    /// Remove `traceparent` and `tracestate` from `event`
+    pub fn remove_from(event: &mut Event) {
+        event.remove_extension(TRACEPARENT);
+        event.remove_extension(TRACESTATE);
+    }
+
+    /// Get the `traceparent` value
+    pub fn traceparent(&self) -> &str {
+        &self.traceparent
+    }
+
+    /// Get the `tracestate` value
+    pub fn tracestate(&self) -> Option<&str> {
+        self.tracestate.as_deref()
+    }
+
+    /// Get the trace id part of `traceparent`
+    pub fn trace_id(&self) -> &str {
+        &self.traceparent[3..35]
+    }
+
+    /// Get the parent span id part of `traceparent`
+    pub fn parent_id(&self) -> &str {
+        &self.traceparent[36..52]
+    }
+
+    /// Get the trace flags part of `traceparent`
+    pub fn trace_flags(&self) -> u8 {
+        // Validated as two hex digits in the constructor
+        u8::from_str_radix(&self.traceparent[53..55], 16).unwrap_or_default()
+    }
+
+    /// Check whether the `sampled` trace flag is set
+    pub fn sampled(&self) -> bool {
+        self.trace_flags() & 0x01 == 0x01
+    }
+}
+
+#[cfg(feature = "opentelemetry")]
+impl DistributedTraceExtension {
+    /// Create a [`DistributedTraceExtension`] from an OpenTelemetry [`SpanContext`](opentelemetry::trace::SpanContext),
+    /// returning `None` if the span context is not valid
+    pub fn from_span_context(span_context: &opentelemetry::trace::SpanContext) -> Option<Self> {
+        if !span_context.is_valid() {
+            return None;
+        }
+        let tracestate = span_context.trace_state().header();
+        Some(DistributedTraceExtension {
+            traceparent: format!(
+                "00-{}-{}-{:02x}",
+                span_context.trace_id(),
+                span_context.span_id(),
+                span_context.trace_flags().to_u8()
+            ),
+            tracestate: if tracestate.is_empty() {
+                None
+            } else {
+                Some(tracestate)
+            },
+        })
+    }
+
+    /// Create a [`DistributedTraceExtension`] from the span of the current OpenTelemetry
+    /// [`Context`](opentelemetry::Context), returning `None` if there is no active span
+    pub fn from_current_context() -> Option<Self> {
+        use opentelemetry::trace::TraceContextExt;
+
+        DistributedTraceExtension::from_span_context(
+            opentelemetry::Context::current().span().span_context(),
+        )
+    }
+
+    /// Convert to a remote OpenTelemetry [`SpanContext`](opentelemetry::trace::SpanContext),
+    /// to continue the trace on the receiver side
+    pub fn to_span_context(&self) -> opentelemetry::trace::SpanContext {
+        use opentelemetry::trace::{SpanContext, SpanId, TraceFlags, TraceId, TraceState};
+        use std::str::FromStr;
+
+        SpanContext::new(
+            TraceId::from_hex(self.trace_id()).unwrap_or(TraceId::INVALID),
+            SpanId::from_hex(self.parent_id()).unwrap_or(SpanId::INVALID),
+            TraceFlags::new(self.trace_flags()),
+            true,
+            self.tracestate()
+                .and_then(|ts| TraceState::from_str(ts).ok())
+                .unwrap_or_default(),
+        )
+    }
+}
+
+fn is_lower_hex(s: &str) -> bool {
+    s.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+}
+
+fn validate_traceparent(traceparent: &str) -> Result<()> {
+    let invalid = |reason| Error::InvalidTraceParent {
+        traceparent: traceparent.to_string(),
+        reason,
+    };
+
+    let parts: Vec<&str> = traceparent.splitn(5, '-').collect();
+    if parts.len() < 4 {
+        return Err(invalid("expected version-traceid-parentid-traceflags"));
+    }
+    let (version, trace_id, parent_id, flags) = (parts[0], parts[1], parts[2], parts[3]);
+
+    if version.len() != 2 || !is_lower_hex(version) || version == "ff" {
+        return Err(invalid("invalid version"));
+    }
+    if version == "00" && parts.len() != 4 {
+        return Err(invalid("unexpected fields for version 00"));
+    }
+    if trace_id.len() != 32 || !is_lower_hex(trace_id) || trace_id.bytes().all(|b| b == b'0') {
+        return Err(invalid(
+            "trace id must be 32 lowercase hex digits, not all zero",
+        ));
+    }
+    if parent_id.len() != 16 || !is_lower_hex(parent_id) || parent_id.bytes().all(|b| b == b'0') {
+        return Err(invalid(
+            "parent id must be 16 lowercase hex digits, not all zero",
+        ));
+    }
+    if flags.len() != 2 || !is_lower_hex(flags) {
+        return Err(invalid("trace flags must be 2 lowercase hex digits"));
+    }
+    Ok(())
+}
+
+fn validate_tracestate(tracestate: &str) -> Result<()> {
+    let invalid = |reason| Error::InvalidTraceState {
+        tracestate: tracestate.to_string(),
+        reason,
+    };
+
+    let members: Vec<&str> = tracestate
+        .split(',')
+        .map(str::trim)
+        .filter(|m| !m.is_empty())
+        .collect();
+    if members.len() > 32 {
+        return Err(invalid("more than 32 list members"));
+    }
+    for member in members {
+        let (key, value) = match member.find('=') {
+            Some(i) => (&member[..i], &member[i + 1..]),
+            None => return Err(invalid("list member without '='")),
+        };
+        let valid_key = !key.is_empty()
+            && key.len() <= 256
+            && key
+                .bytes()
+                .all(|b| matches!(b, b'a'..=b'z' | b'0'..=b'9' | b'_' | b'-' | b'*' | b'/' | b'@'));
+        if !valid_key {
+            return Err(invalid("invalid list member key"));
+        }
+        let valid_value = !value.is_empty()
+            && value.len() <= 256
+            && value
+                .bytes()
+                .all(|b| (0x20..=0x7e).contains(&b) && b != b',' && b != b'=')
+            && !value.ends_with(' ');
+        if !valid_value {
+            return Err(invalid("invalid list member value"));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::{AttributesReader, ExtensionValue};
+    use crate::{EventBuilder, EventBuilderV10};
+
+    const TRACEPARENT_VALUE: &str = "00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01";
+
+    fn event() -> Event {
+        EventBuilderV10::new()
+            .id("0001")
+            .source("http://localhost/")
+            .ty("example.test")
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn roundtrip() {
+        let mut e = event();
+        assert_eq!(None, DistributedTraceExtension::from_event(&e).unwrap());
+
+        let extension =
+            DistributedTraceExtension::new(TRACEPARENT_VALUE, Some("rojo=00f067aa0ba902b7"))
+                .unwrap();
+        extension.clone().write_to(&mut e);
+
+        assert_eq!(
+            Some(&ExtensionValue::from(TRACEPARENT_VALUE)),
+            e.get_extension("traceparent")
+        );
+        assert_eq!(
+            Some(extension),
+            DistributedTraceExtension::from_event(&e).unwrap()
+        );
+
+        DistributedTraceExtension::remove_from(&mut e);
+        assert!(e.get_extension("traceparent").is_none());
+        assert!(e.get_extension("tracestate").is_none());
+    }
+
+    #[test]
+    fn traceparent_parts() {
+        let extension = DistributedTraceExtension::new(TRACEPARENT_VALUE, None::<String>).unwrap();
+        assert_eq!("0af7651916cd43dd8448eb211c80319c", extension.trace_id());
+        assert_eq!("b7ad6b7169203331", extension.parent_id());
+        assert_eq!(1, extension.trace_flags());
+        assert!(extension.sampled());
+    }
+
+    #[test]
+    fn invalid_traceparent() {
+        for traceparent in &[
+            "",
+            "00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331",
+            "ff-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01",
+            "00-0AF7651916CD43DD8448EB211C80319C-b7ad6b7169203331-01",
+            "00-00000000000000000000000000000000-b7ad6b7169203331-01",
+            "00-0af7651916cd43dd8448eb211c80319c-0000000000000000-01",
+            "00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01-extra",
+        ] {
+            assert!(matches!(
+                DistributedTraceExtension::new(*traceparent, None::<String>),
+                Err(Error::InvalidTraceParent { .. })
+            ));
+        }
+
+        // Future versions can carry more fields
+        assert!(DistributedTraceExtension::new(
+            "01-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01-extra",
+            None::<String>
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn invalid_tracestate() {
+        for tracestate in &["rojo", "Rojo=1", "rojo=a,b", "=value"] {
+            assert!(matches!(
+                DistributedTraceExtension::new(TRACEPARENT_VALUE, Some(*tracestate)),
+                Err(Error::InvalidTraceState { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn invalid_extension_type() {
+        let mut e = event();
+        e.set_extension("traceparent", 10);
+        assert!(matches!(
+            DistributedTraceExtension::from_event(&e),
+            Err(Error::InvalidExtensionType {
+                extension_name: "traceparent",
+                expected: "string"
+            })
+        ));
+    }
+
+    #[cfg(feature = "opentelemetry")]
+    #[test]
+    fn span_context_roundtrip() {
+        let extension =
+            DistributedTraceExtension::new(TRACEPARENT_VALUE, Some("rojo=00f067aa0ba902b7"))
+                .unwrap();
+        let span_context = extension.to_span_context();
+        assert!(span_context.is_valid());
+        assert!(span_context.is_remote());
+        assert_eq!(
+            Some(extension),
+            DistributedTraceExtension::from_span_context(&span_context)
+        );
+
+        assert_eq!(
+            None,
+            DistributedTraceExtension::from_span_context(
+                &opentelemetry::trace::SpanContext::empty_context()
+            )
+        );
+    }
+}
@@ -0,0 +1,69 @@
+//! Typed APIs to read and write the
+//! [documented extensions](https://github.com/cloudevents/spec/blob/v1.0/documented-extensions.md)
+//! on an [`Event`](crate::Event).
+
+mod correlation;
+mod dataref;
+mod distributed_tracing;
+mod partitioning;
+mod sequence;
+
+pub use correlation::CorrelationExtension;
+pub(crate) use correlation::{CAUSATIONID, CORRELATIONID};
+pub use dataref::{DataStore, DatarefExtension};
+pub use distributed_tracing::DistributedTraceExtension;
+pub use partitioning::{
+    AttributePartitionKeyStrategy, PartitionKeyExtensionStrategy, PartitionKeyStrategy,
+    PartitioningExtension,
+};
+pub use sequence::{IntegerSequence, SequenceExtension, INTEGER_SEQUENCE_TYPE};
+
+use snafu::Snafu;
+
+/// Represents an error while reading or writing an extension
+#[derive(Debug, Snafu)]
+pub enum Error {
+    #[snafu(display("Extension '{}' must be a {}", extension_name, expected))]
+    InvalidExtensionType {
+        extension_name: &'static str,
+        expected: &'static str,
+    },
+    #[snafu(display("Invalid traceparent '{}': {}", traceparent, reason))]
+    InvalidTraceParent {
+        traceparent: String,
+        reason: &'static str,
+    },
+    #[snafu(display("Invalid tracestate '{}': {}", tracestate, reason))]
+    InvalidTraceState {
+        tracestate: String,
+        reason: &'static str,
+    },
+    #[snafu(display("Error while accessing the data store: {}", source))]
+    DataStoreError {
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+    #[snafu(display("Invalid sequence '{}': {}", sequence, reason))]
+    InvalidSequence {
+        sequence: String,
+        reason: &'static str,
+    },
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Read a string extension, failing if it has another type
+pub(crate) fn get_string_extension<'a>(
+    event: &'a crate::Event,
+    extension_name: &'static str,
+) -> Result<Option<&'a str>> {
+    use crate::event::{AttributesReader, ExtensionValue};
+
+    match event.get_extension(extension_name) {
+        Some(ExtensionValue::String(s)) => Ok(Some(s)),
+        Some(_) => Err(Error::InvalidExtensionType {
+            extension_name,
+            expected: "string",
+        }),
+        None => Ok(None),
+    }
+}
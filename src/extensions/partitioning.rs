@@ -0,0 +1,175 @@
+use super::{get_string_extension, Result};
+use crate::event::AttributesWriter;
+use crate::Event;
+
+pub(crate) const PARTITIONKEY: &str = "partitionkey";
+
+/// [Partitioning extension](https://github.com/cloudevents/spec/blob/v1.0/extensions/partitioning.md),
+/// carrying the `partitionkey` used by brokers to group related events in the same partition.
+///
+/// ```
+/// use cloudevents::extensions::PartitioningExtension;
+/// use cloudevents::{EventBuilder, EventBuilderV10};
+///
+/// let mut event = EventBuilderV10::new()
+///     .id("0001")
+///     .source("http://localhost/")
+///     .ty("example.demo")
+///     .build()
+///     .unwrap();
+///
+/// PartitioningExtension::new("my-key").write_to(&mut event);
+///
+/// let extension = PartitioningExtension::from_event(&event).unwrap().unwrap();
+/// assert_eq!("my-key", extension.partition_key());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct PartitioningExtension {
+    partitionkey: String,
+}
+
+impl PartitioningExtension {
+    /// Create a new [`PartitioningExtension`]
+    pub fn new(partitionkey: impl Into<String>) -> Self {
+        PartitioningExtension {
+            partitionkey: partitionkey.into(),
+        }
+    }
+
+    /// Read the extension from `event`, returning `None` if it has no `partitionkey`
+    pub fn from_event(event: &Event) -> Result<Option<Self>> {
+        Ok(get_string_extension(event, PARTITIONKEY)?.map(PartitioningExtension::new))
+    }
+
+    /// Write `partitionkey` to `event`
+    pub fn write_to(self, event: &mut Event) {
+        event.set_extension(PARTITIONKEY, self.partitionkey);
+    }
+
+    /// Remove `partitionkey` from `event`
+    pub fn remove_from(event: &mut Event) {
+        event.remove_extension(PARTITIONKEY);
+    }
+
+    /// Get the `partitionkey` value
+    pub fn partition_key(&self) -> &str {
+        &self.partitionkey
+    }
+}
+
+/// Strategy to compute the partition key of an [`Event`], used by bindings to fill the
+/// partition or record key of the message.
+///
+/// Closures taking an [`Event`] and returning an `Option<String>` implement this trait.
+pub trait PartitionKeyStrategy {
+    /// Compute the partition key of `event`, or `None` if it shouldn't be partitioned
+    fn partition_key(&self, event: &Event) -> Option<String>;
+}
+
+/// [`PartitionKeyStrategy`] reading the `partitionkey` extension.
+/// A `partitionkey` of another type than string is ignored.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PartitionKeyExtensionStrategy;
+
+impl PartitionKeyStrategy for PartitionKeyExtensionStrategy {
+    fn partition_key(&self, event: &Event) -> Option<String> {
+        PartitioningExtension::from_event(event)
+            .ok()
+            .flatten()
+            .map(|e| e.partitionkey)
+    }
+}
+
+/// [`PartitionKeyStrategy`] using the value of a context attribute or extension,
+/// for example `subject` or `source`, converted to a string.
+#[derive(Debug, Clone)]
+pub struct AttributePartitionKeyStrategy {
+    attribute_name: String,
+}
+
+impl AttributePartitionKeyStrategy {
+    /// Use the attribute or extension named `attribute_name`
+    pub fn new(attribute_name: impl Into<String>) -> Self {
+        AttributePartitionKeyStrategy {
+            attribute_name: attribute_name.into(),
+        }
+    }
+}
+
+impl PartitionKeyStrategy for AttributePartitionKeyStrategy {
+    fn partition_key(&self, event: &Event) -> Option<String> {
+        event
+            .attributes_iter()
+            .find(|(name, _)| *name == self.attribute_name)
+            .map(|(_, value)| value.to_string())
+    }
+}
+
+impl<F> PartitionKeyStrategy for F
+where
+    F: Fn(&Event) -> Option<String>,
+{
+    fn partition_key(&self, event: &Event) -> Option<String> {
+        self(event)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::AttributesReader;
+    use crate::{EventBuilder, EventBuilderV10};
+
+    fn event() -> Event {
+        EventBuilderV10::new()
+            .id("0001")
+            .source("http://localhost/")
+            .ty("example.test")
+            .subject("my-subject")
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn roundtrip() {
+        let mut e = event();
+        assert_eq!(None, PartitioningExtension::from_event(&e).unwrap());
+
+        PartitioningExtension::new("my-key").write_to(&mut e);
+        assert_eq!(
+            Some(PartitioningExtension::new("my-key")),
+            PartitioningExtension::from_event(&e).unwrap()
+        );
+
+        PartitioningExtension::remove_from(&mut e);
+        assert!(e.get_extension("partitionkey").is_none());
+    }
+
+    #[test]
+    fn strategies() {
+        let mut e = event();
+        assert_eq!(None, PartitionKeyExtensionStrategy.partition_key(&e));
+
+        e.set_extension("partitionkey", "my-key");
+        assert_eq!(
+            Some(String::from("my-key")),
+            PartitionKeyExtensionStrategy.partition_key(&e)
+        );
+        assert_eq!(
+            Some(String::from("my-subject")),
+            AttributePartitionKeyStrategy::new("subject").partition_key(&e)
+        );
+        assert_eq!(
+            Some(String::from("my-key")),
+            AttributePartitionKeyStrategy::new("partitionkey").partition_key(&e)
+        );
+        assert_eq!(
+            None,
+            AttributePartitionKeyStrategy::new("missing").partition_key(&e)
+        );
+        assert_eq!(
+            Some(String::from("0001")),
+            (|e: &Event| Some(e.get_id().to_string())).partition_key(&e)
+        );
+    }
+}
@@ -0,0 +1,237 @@
+use super::{get_string_extension, Error, Result};
+use crate::event::AttributesWriter;
+use crate::Event;
+use std::sync::atomic::{AtomicI32, Ordering};
+
+pub(crate) const SEQUENCE: &str = "sequence";
+pub(crate) const SEQUENCETYPE: &str = "sequencetype";
+
+/// The `Integer` sequence type, defined by the spec as a signed 32-bit positive integer
+/// starting at 1 and wrapping back to 1 after `2147483647`
+pub const INTEGER_SEQUENCE_TYPE: &str = "Integer";
+
+/// [Sequence extension](https://github.com/cloudevents/spec/blob/v1.0/extensions/sequence.md),
+/// carrying the position of the event in an ordered sequence of events.
+///
+/// ```
+/// use cloudevents::extensions::SequenceExtension;
+/// use cloudevents::{EventBuilder, EventBuilderV10};
+///
+/// let mut event = EventBuilderV10::new()
+///     .id("0001")
+///     .source("http://localhost/")
+///     .ty("example.demo")
+///     .build()
+///     .unwrap();
+///
+/// SequenceExtension::integer(42).unwrap().write_to(&mut event);
+///
+/// let extension = SequenceExtension::from_event(&event).unwrap().unwrap();
+/// assert_eq!("42", extension.sequence());
+/// assert_eq!(Some(42), extension.as_integer());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct SequenceExtension {
+    sequence: String,
+    sequencetype: Option<String>,
+}
+
+impl SequenceExtension {
+    /// Create a new [`SequenceExtension`].
+    /// When `sequencetype` is `Integer`, `sequence` must be a positive 32-bit integer.
+    pub fn new(
+        sequence: impl Into<String>,
+        sequencetype: Option<impl Into<String>>,
+    ) -> Result<Self> {
+        let extension = SequenceExtension {
+            sequence: sequence.into(),
+            sequencetype: sequencetype.map(Into::into),
+        };
+        if extension.sequencetype() == Some(INTEGER_SEQUENCE_TYPE)
+            && extension.as_integer().is_none()
+        {
+            return Err(Error::InvalidSequence {
+                sequence: extension.sequence,
+                reason: "expected a positive 32-bit integer for the Integer sequence type",
+            });
+        }
+        Ok(extension)
+    }
+
+    /// Create a new [`SequenceExtension`] with the `Integer` sequence type
+    pub fn integer(sequence: i32) -> Result<Self> {
+        SequenceExtension::new(sequence.to_string(), Some(INTEGER_SEQUENCE_TYPE))
+    }
+
+    /// Read the extension from `event`, returning `None` if it has no `sequence`
+    pub fn from_event(event: &Event) -> Result<Option<Self>> {
+        match get_string_extension(event, SEQUENCE)? {
+            Some(sequence) => Ok(Some(SequenceExtension::new(
+                sequence,
+                get_string_extension(event, SEQUENCETYPE)?,
+            )?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Write `sequence` and `sequencetype` to `event`, removing any previous `sequencetype`
+    pub fn write_to(self, event: &mut Event) {
+        event.set_extension(SEQUENCE, self.sequence);
+        match self.sequencetype {
+            Some(sequencetype) => event.set_extension(SEQUENCETYPE, sequencetype),
+            None => {
+                event.remove_extension(SEQUENCETYPE);
+            }
+        }
+    }
+
+    /// Remove `sequence` and `sequencetype` from `event`
+    pub fn remove_from(event: &mut Event) {
+        event.remove_extension(SEQUENCE);
+        event.remove_extension(SEQUENCETYPE);
+    }
+
+    /// Get the `sequence` value
+    pub fn sequence(&self) -> &str {
+        &self.sequence
+    }
+
+    /// Get the `sequencetype` value
+    pub fn sequencetype(&self) -> Option<&str> {
+        self.sequencetype.as_deref()
+    }
+
+    /// Get `sequence` as an integer, if `sequencetype` is `Integer`
+    pub fn as_integer(&self) -> Option<i32> {
+        if self.sequencetype() != Some(INTEGER_SEQUENCE_TYPE) {
+            return None;
+        }
+        self.sequence.parse::<i32>().ok().filter(|s| *s > 0)
+    }
+}
+
+/// Monotonic counter stamping events with the `Integer` sequence type,
+/// wrapping back to 1 after `2147483647`.
+/// The counter is thread safe, so it can be shared between producers.
+///
+/// ```
+/// use cloudevents::extensions::{IntegerSequence, SequenceExtension};
+/// use cloudevents::Event;
+///
+/// let sequence = IntegerSequence::new();
+///
+/// let mut first = Event::default();
+/// sequence.stamp(&mut first);
+/// let mut second = Event::default();
+/// sequence.stamp(&mut second);
+///
+/// assert_eq!(Some(1), SequenceExtension::from_event(&first).unwrap().unwrap().as_integer());
+/// assert_eq!(Some(2), SequenceExtension::from_event(&second).unwrap().unwrap().as_integer());
+/// ```
+#[derive(Debug)]
+pub struct IntegerSequence {
+    next: AtomicI32,
+}
+
+impl IntegerSequence {
+    /// Create a new [`IntegerSequence`] starting at 1
+    pub fn new() -> Self {
+        IntegerSequence {
+            next: AtomicI32::new(1),
+        }
+    }
+
+    /// Create a new [`IntegerSequence`] starting at `start`, or at 1 if `start` is not positive
+    pub fn starting_at(start: i32) -> Self {
+        IntegerSequence {
+            next: AtomicI32::new(if start > 0 { start } else { 1 }),
+        }
+    }
+
+    /// Get the next value of the sequence
+    pub fn next_value(&self) -> i32 {
+        self.next
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |current| {
+                Some(if current == i32::MAX { 1 } else { current + 1 })
+            })
+            // The closure always returns Some
+            .unwrap_or(1)
+    }
+
+    /// Get the next [`SequenceExtension`] of the sequence
+    pub fn next_extension(&self) -> SequenceExtension {
+        SequenceExtension {
+            sequence: self.next_value().to_string(),
+            sequencetype: Some(String::from(INTEGER_SEQUENCE_TYPE)),
+        }
+    }
+
+    /// Write the next value of the sequence to `event`
+    pub fn stamp(&self, event: &mut Event) {
+        self.next_extension().write_to(event)
+    }
+}
+
+impl Default for IntegerSequence {
+    fn default() -> Self {
+        IntegerSequence::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::{AttributesReader, ExtensionValue};
+
+    #[test]
+    fn roundtrip() {
+        let mut e = Event::default();
+        assert_eq!(None, SequenceExtension::from_event(&e).unwrap());
+
+        let extension = SequenceExtension::new("abc", None::<String>).unwrap();
+        extension.clone().write_to(&mut e);
+        assert_eq!(
+            Some(&ExtensionValue::from("abc")),
+            e.get_extension("sequence")
+        );
+        assert!(e.get_extension("sequencetype").is_none());
+        assert_eq!(Some(extension), SequenceExtension::from_event(&e).unwrap());
+
+        SequenceExtension::integer(10).unwrap().write_to(&mut e);
+        assert_eq!(
+            Some(&ExtensionValue::from("Integer")),
+            e.get_extension("sequencetype")
+        );
+
+        SequenceExtension::remove_from(&mut e);
+        assert!(e.get_extension("sequence").is_none());
+        assert!(e.get_extension("sequencetype").is_none());
+    }
+
+    #[test]
+    fn invalid_integer_sequence() {
+        for sequence in &["abc", "0", "-1", "2147483648"] {
+            assert!(matches!(
+                SequenceExtension::new(*sequence, Some(INTEGER_SEQUENCE_TYPE)),
+                Err(Error::InvalidSequence { .. })
+            ));
+        }
+        assert!(SequenceExtension::new("abc", Some("Custom")).is_ok());
+        assert_eq!(
+            None,
+            SequenceExtension::new("1", None::<String>)
+                .unwrap()
+                .as_integer()
+        );
+    }
+
+    #[test]
+    fn integer_sequence_wraps() {
+        let sequence = IntegerSequence::starting_at(i32::MAX - 1);
+        assert_eq!(i32::MAX - 1, sequence.next_value());
+        assert_eq!(i32::MAX, sequence.next_value());
+        assert_eq!(1, sequence.next_value());
+
+        assert_eq!(1, IntegerSequence::starting_at(-5).next_value());
+    }
+}
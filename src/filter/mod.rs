@@ -0,0 +1,192 @@
+use crate::event::Event;
+use crate::sql::Expression;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Filter of the [CloudEvents Subscriptions API](https://github.com/cloudevents/spec/blob/main/subscriptions/spec.md#324-filters),
+/// serialized with the JSON representation of the filter dialects.
+///
+/// Attribute values are compared with their string representation, and a filter on an attribute
+/// missing in the event doesn't match.
+///
+/// ```
+/// use cloudevents::filter::Filter;
+/// use cloudevents::{EventBuilder, EventBuilderV10};
+/// use serde_json::json;
+///
+/// let filter: Filter = serde_json::from_value(json!({
+///     "all": [
+///         {"prefix": {"type": "com.example."}},
+///         {"not": {"exact": {"subject": "internal"}}},
+///         {"sql": "NOT EXISTS priority OR priority > 5"}
+///     ]
+/// }))
+/// .unwrap();
+///
+/// let event = EventBuilderV10::new()
+///     .id("0001")
+///     .source("http://localhost/")
+///     .ty("com.example.order.created")
+///     .subject("orders")
+///     .build()
+///     .unwrap();
+/// assert!(filter.matches(&event));
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Filter {
+    /// Matches if the value of every attribute is equal to the provided one
+    Exact(BTreeMap<String, String>),
+    /// Matches if the value of every attribute starts with the provided one
+    Prefix(BTreeMap<String, String>),
+    /// Matches if the value of every attribute ends with the provided one
+    Suffix(BTreeMap<String, String>),
+    /// Matches if all the nested filters match
+    All(Vec<Filter>),
+    /// Matches if at least one of the nested filters matches
+    Any(Vec<Filter>),
+    /// Matches if the nested filter doesn't match
+    Not(Box<Filter>),
+    /// Matches if the [CESQL](crate::sql) expression evaluates to `TRUE`
+    Sql(Expression),
+}
+
+impl Filter {
+    /// Create a new [`Filter::Exact`] on a single attribute
+    pub fn exact(attribute: impl Into<String>, value: impl Into<String>) -> Self {
+        Filter::Exact(single(attribute, value))
+    }
+
+    /// Create a new [`Filter::Prefix`] on a single attribute
+    pub fn prefix(attribute: impl Into<String>, value: impl Into<String>) -> Self {
+        Filter::Prefix(single(attribute, value))
+    }
+
+    /// Create a new [`Filter::Suffix`] on a single attribute
+    pub fn suffix(attribute: impl Into<String>, value: impl Into<String>) -> Self {
+        Filter::Suffix(single(attribute, value))
+    }
+
+    /// Create a new [`Filter::Not`]
+    pub fn negate(filter: Filter) -> Self {
+        Filter::Not(Box::new(filter))
+    }
+
+    /// Returns `true` if `event` matches this filter
+    pub fn matches(&self, event: &Event) -> bool {
+        match self {
+            Filter::Exact(attributes) => match_attributes(attributes, event, |v, f| v == f),
+            Filter::Prefix(attributes) => {
+                match_attributes(attributes, event, |v, f| v.starts_with(f))
+            }
+            Filter::Suffix(attributes) => {
+                match_attributes(attributes, event, |v, f| v.ends_with(f))
+            }
+            Filter::All(filters) => filters.iter().all(|f| f.matches(event)),
+            Filter::Any(filters) => filters.iter().any(|f| f.matches(event)),
+            Filter::Not(filter) => !filter.matches(event),
+            Filter::Sql(expression) => expression.matches(event),
+        }
+    }
+}
+
+fn single(attribute: impl Into<String>, value: impl Into<String>) -> BTreeMap<String, String> {
+    let mut attributes = BTreeMap::new();
+    attributes.insert(attribute.into(), value.into());
+    attributes
+}
+
+fn match_attributes(
+    attributes: &BTreeMap<String, String>,
+    event: &Event,
+    matches: impl Fn(&str, &str) -> bool,
+) -> bool {
+    attributes.iter().all(|(name, expected)| {
+        event
+            .attribute(name)
+            .is_some_and(|value| matches(&value.to_string(), expected))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EventBuilder, EventBuilderV10};
+    use serde_json::json;
+
+    fn event() -> Event {
+        EventBuilderV10::new()
+            .id("0001")
+            .source("https://example.com/orders")
+            .ty("com.example.order.created")
+            .extension("priority", 3)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn dialects() {
+        let event = event();
+        assert!(Filter::exact("type", "com.example.order.created").matches(&event));
+        assert!(!Filter::exact("type", "com.example.order").matches(&event));
+        assert!(Filter::prefix("source", "https://example.com/").matches(&event));
+        assert!(Filter::suffix("type", ".created").matches(&event));
+        assert!(Filter::exact("priority", "3").matches(&event));
+        assert!(!Filter::exact("subject", "").matches(&event));
+        assert!(!Filter::prefix("subject", "").matches(&event));
+        assert!(Filter::negate(Filter::exact("subject", "")).matches(&event));
+
+        assert!(Filter::All(vec![]).matches(&event));
+        assert!(!Filter::Any(vec![]).matches(&event));
+        assert!(Filter::Any(vec![
+            Filter::exact("id", "0002"),
+            Filter::suffix("source", "/orders")
+        ])
+        .matches(&event));
+        assert!(!Filter::All(vec![
+            Filter::exact("id", "0002"),
+            Filter::suffix("source", "/orders")
+        ])
+        .matches(&event));
+        assert!(Filter::Sql(Expression::parse("priority < 5").unwrap()).matches(&event));
+    }
+
+    #[test]
+    fn multiple_attributes() {
+        let filter: Filter = serde_json::from_value(json!({
+            "exact": {"id": "0001", "type": "com.example.order.created"}
+        }))
+        .unwrap();
+        assert!(filter.matches(&event()));
+
+        let filter: Filter = serde_json::from_value(json!({
+            "exact": {"id": "0001", "type": "com.example.order"}
+        }))
+        .unwrap();
+        assert!(!filter.matches(&event()));
+    }
+
+    #[test]
+    fn serde() {
+        let json = json!({
+            "any": [
+                {"exact": {"type": "a"}},
+                {"not": {"suffix": {"source": "/b"}}},
+                {"sql": "EXISTS subject"}
+            ]
+        });
+        let filter: Filter = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(
+            Filter::Any(vec![
+                Filter::exact("type", "a"),
+                Filter::negate(Filter::suffix("source", "/b")),
+                Filter::Sql(Expression::parse("EXISTS subject").unwrap()),
+            ]),
+            filter
+        );
+        assert_eq!(json, serde_json::to_value(&filter).unwrap());
+
+        assert!(serde_json::from_value::<Filter>(json!({"sql": "a ="})).is_err());
+        assert!(serde_json::from_value::<Filter>(json!({"unknown": {}})).is_err());
+    }
+}
@@ -0,0 +1,163 @@
+use crate::event::{is_json_content_type, Data};
+use crate::message::MessageAttributeValue;
+use crate::{AttributesReader, Event};
+use serde_json::{Map, Value};
+
+/// Serialize `event` to its canonical JSON form.
+///
+/// The canonical form is the JSON event format without whitespace, with the keys of the objects
+/// sorted, and with the strings and numbers written as by `serde_json`. The extensions are written
+/// in their string form, as they are transferred by the binary mode bindings, which don't keep
+/// their type, so an integer extension `10` is written `"10"`. The data is independent
+/// of how it's stored in the event: the data of a JSON `datacontenttype`, or without
+/// `datacontenttype`, is written in canonical JSON in the `data` field when it's valid JSON, and
+/// any other data is written in `data_base64`, whether it's stored as a string or as bytes.
+pub fn canonical_json(event: &Event) -> Vec<u8> {
+    canonical_json_excluding(event, &[])
+}
+
+/// Serialize `event` to its canonical JSON form without the `excluded` extensions
+pub(crate) fn canonical_json_excluding(event: &Event, excluded: &[&str]) -> Vec<u8> {
+    let attributes = Event {
+        attributes: event.attributes.clone(),
+        data: None,
+    };
+    let mut fields = match serde_json::to_value(attributes) {
+        Ok(Value::Object(fields)) => fields,
+        _ => Map::new(),
+    };
+    fields.remove("datacontentencoding");
+    for (name, value) in event.iter_extensions() {
+        let value = MessageAttributeValue::from(value).to_string();
+        fields.insert(name.clone(), Value::String(value));
+    }
+    for name in excluded {
+        fields.remove(*name);
+    }
+
+    let json = event
+        .get_datacontenttype()
+        .map(|ct| is_json_content_type(ct.split(';').next().unwrap_or_default().trim()))
+        .unwrap_or(true);
+    match &event.data {
+        Some(Data::Json(v)) => {
+            fields.insert("data".to_string(), v.clone());
+        }
+        Some(Data::String(s)) => insert_bytes(&mut fields, s.as_bytes(), json),
+        Some(Data::Binary(b)) => insert_bytes(&mut fields, b, json),
+        None => {}
+    }
+
+    let mut out = Vec::new();
+    write_value(&mut out, &Value::Object(fields));
+    out
+}
+
+fn insert_bytes(fields: &mut Map<String, Value>, bytes: &[u8], json: bool) {
+    match serde_json::from_slice(bytes) {
+        Ok(value) if json => fields.insert("data".to_string(), value),
+        _ => fields.insert(
+            "data_base64".to_string(),
+            Value::String(base64::encode(bytes)),
+        ),
+    };
+}
+
+fn write_value(out: &mut Vec<u8>, value: &Value) {
+    match value {
+        Value::Object(fields) => {
+            let mut fields: Vec<(&String, &Value)> = fields.iter().collect();
+            fields.sort_by_key(|(k, _)| *k);
+            out.push(b'{');
+            for (i, (k, v)) in fields.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(b',');
+                }
+                write_scalar(out, &Value::String(k.clone()));
+                out.push(b':');
+                write_value(out, v);
+            }
+            out.push(b'}');
+        }
+        Value::Array(values) => {
+            out.push(b'[');
+            for (i, v) in values.iter().enumerate() {
+                if i > 0 {
+                    out.push(b',');
+                }
+                write_value(out, v);
+            }
+            out.push(b']');
+        }
+        v => write_scalar(out, v),
+    }
+}
+
+fn write_scalar(out: &mut Vec<u8>, value: &Value) {
+    // Serializing a scalar JSON value to a Vec can't fail
+    let _ = serde_json::to_writer(out, value);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EventBuilder, EventBuilderV03, EventBuilderV10};
+    use chrono::{TimeZone, Utc};
+    use serde_json::json;
+
+    #[test]
+    fn canonical_form() {
+        let event = EventBuilderV10::new()
+            .id("0001")
+            .source("http://localhost/")
+            .ty("example.test")
+            .time(Utc.timestamp(1_600_000_000, 0))
+            .extension("someint", 10)
+            .data(
+                "application/json",
+                json!({"b": [1, {"d": "é", "c": null}], "a": 1.5}),
+            )
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            concat!(
+                r#"{"data":{"a":1.5,"b":[1,{"c":null,"d":"é"}]},"#,
+                r#""datacontenttype":"application/json","id":"0001","someint":"10","#,
+                r#""source":"http://localhost/","specversion":"1.0","#,
+                r#""time":"2020-09-13T12:26:40Z","type":"example.test"}"#
+            ),
+            String::from_utf8(canonical_json(&event)).unwrap()
+        );
+    }
+
+    #[test]
+    fn data_representation() {
+        let builder = || {
+            EventBuilderV03::new()
+                .id("0001")
+                .source("http://localhost/")
+                .ty("example.test")
+        };
+
+        let json = builder()
+            .data("application/json", json!({"hello": "world"}))
+            .build()
+            .unwrap();
+        let json_bytes = builder()
+            .data("application/json", br#"{ "hello" : "world" }"#.to_vec())
+            .build()
+            .unwrap();
+        assert_eq!(canonical_json(&json), canonical_json(&json_bytes));
+
+        let text = builder().data("text/plain", "hello").build().unwrap();
+        let text_bytes = builder()
+            .data("text/plain", b"hello".to_vec())
+            .build()
+            .unwrap();
+        assert_eq!(canonical_json(&text), canonical_json(&text_bytes));
+        assert!(String::from_utf8(canonical_json(&text))
+            .unwrap()
+            .contains(r#""data_base64":"aGVsbG8=""#));
+    }
+}
@@ -0,0 +1,212 @@
+//! Integrity of the events, enabled with the `integrity` feature: a deterministic canonical
+//! serialization of an event, and the `datadigest` extension carrying its SHA-256 digest, so
+//! the consumers can detect the events corrupted or tampered with in transit.
+//!
+//! The [canonical serialization](canonical_json) doesn't depend on the protocol binding or the
+//! content mode the event was transferred with, so the digest computed by the producer can be
+//! verified by the consumer after the event was parsed:
+//!
+//! ```
+//! use cloudevents::integrity::{attach_digest, verify_digest};
+//! use cloudevents::{EventBuilder, EventBuilderV10};
+//! use serde_json::json;
+//!
+//! let mut event = EventBuilderV10::new()
+//!     .id("0001")
+//!     .source("http://localhost/")
+//!     .ty("com.example.order")
+//!     .data("application/json", json!({"amount": 10, "currency": "EUR"}))
+//!     .build()
+//!     .unwrap();
+//! attach_digest(&mut event);
+//! assert!(verify_digest(&event).is_ok());
+//!
+//! event.write_data("application/json", json!({"amount": 1000, "currency": "EUR"}));
+//! assert!(verify_digest(&event).is_err());
+//! ```
+
+mod canonical;
+
+pub use canonical::canonical_json;
+pub(crate) use canonical::canonical_json_excluding;
+
+use crate::event::ExtensionValue;
+use crate::message::{EventInterceptor, Result as MessageResult};
+use crate::{AttributesReader, AttributesWriter, Event};
+use snafu::Snafu;
+
+/// Name of the extension carrying the digest of the event
+pub const DATADIGEST: &str = "datadigest";
+
+/// Prefix of the digests computed with SHA-256
+const SHA_256_PREFIX: &str = "sha-256=";
+
+/// Represents an error while verifying the digest of an event
+#[derive(Debug, Snafu)]
+pub enum Error {
+    #[snafu(display("Missing {} extension", DATADIGEST))]
+    MissingDigest {},
+    #[snafu(display("Unsupported digest {}, expected sha-256=<base64 digest>", digest))]
+    UnsupportedDigest { digest: String },
+    #[snafu(display("The event doesn't match its digest {}", digest))]
+    DigestMismatch { digest: String },
+}
+
+/// Compute the digest of `event`, `sha-256=` followed by the base64 SHA-256 digest of its
+/// canonical serialization without the `datadigest` extension
+pub fn compute_digest(event: &Event) -> String {
+    let canonical = canonical_json_excluding(event, &[DATADIGEST]);
+    let digest = ring::digest::digest(&ring::digest::SHA256, &canonical);
+    format!("{}{}", SHA_256_PREFIX, base64::encode(digest.as_ref()))
+}
+
+/// Compute the digest of `event` and set it in the `datadigest` extension
+pub fn attach_digest(event: &mut Event) {
+    let digest = compute_digest(event);
+    event.set_extension(DATADIGEST, digest);
+}
+
+/// Verify that `event` matches the digest of its `datadigest` extension
+pub fn verify_digest(event: &Event) -> Result<(), Error> {
+    let digest = match event.get_extension(DATADIGEST) {
+        Some(ExtensionValue::String(digest)) => digest,
+        Some(v) => {
+            return Err(Error::UnsupportedDigest {
+                digest: serde_json::to_string(v).unwrap_or_default(),
+            })
+        }
+        None => return Err(Error::MissingDigest {}),
+    };
+    if !digest.starts_with(SHA_256_PREFIX) {
+        return Err(Error::UnsupportedDigest {
+            digest: digest.clone(),
+        });
+    }
+    let expected = compute_digest(event);
+    if ring::constant_time::verify_slices_are_equal(expected.as_bytes(), digest.as_bytes()).is_ok()
+    {
+        Ok(())
+    } else {
+        Err(Error::DigestMismatch {
+            digest: digest.clone(),
+        })
+    }
+}
+
+/// [`EventInterceptor`] attaching the digest to the sent events, and rejecting the received
+/// events which don't match their digest.
+///
+/// The received events without digest are accepted, unless the interceptor is
+/// [`required`](DigestInterceptor::required).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DigestInterceptor {
+    required: bool,
+}
+
+impl DigestInterceptor {
+    /// Create a new [`DigestInterceptor`]
+    pub fn new() -> Self {
+        DigestInterceptor::default()
+    }
+
+    /// Reject the received events without digest
+    pub fn required(mut self) -> Self {
+        self.required = true;
+        self
+    }
+}
+
+impl EventInterceptor for DigestInterceptor {
+    fn on_send(&self, event: &mut Event) -> MessageResult<()> {
+        attach_digest(event);
+        Ok(())
+    }
+
+    fn on_receive(&self, event: &mut Event) -> MessageResult<()> {
+        match verify_digest(event) {
+            Err(Error::MissingDigest {}) if !self.required => Ok(()),
+            result => result.map_err(|e| crate::message::Error::Other {
+                source: Box::new(e),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EventBuilder, EventBuilderV10};
+    use serde_json::json;
+
+    fn event() -> Event {
+        EventBuilderV10::new()
+            .id("0001")
+            .source("http://localhost/")
+            .ty("example.test")
+            .data("application/json", json!({"hello": "world"}))
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn digest() {
+        let mut e = event();
+        assert!(matches!(verify_digest(&e), Err(Error::MissingDigest {})));
+
+        attach_digest(&mut e);
+        let digest = e.get_extension(DATADIGEST).cloned().unwrap();
+        assert!(matches!(&digest, ExtensionValue::String(d) if d.starts_with("sha-256=")));
+        assert!(verify_digest(&e).is_ok());
+
+        // Attaching the digest again doesn't change it
+        attach_digest(&mut e);
+        assert_eq!(Some(&digest), e.get_extension(DATADIGEST));
+
+        e.set_extension("tampered", true);
+        assert!(matches!(
+            verify_digest(&e),
+            Err(Error::DigestMismatch { .. })
+        ));
+
+        e.set_extension(DATADIGEST, "md5=abc");
+        assert!(matches!(
+            verify_digest(&e),
+            Err(Error::UnsupportedDigest { .. })
+        ));
+    }
+
+    #[test]
+    fn interceptor() {
+        let optional = DigestInterceptor::new();
+        let required = DigestInterceptor::new().required();
+
+        let mut e = event();
+        assert!(optional.on_receive(&mut e).is_ok());
+        assert!(required.on_receive(&mut e).is_err());
+
+        optional.on_send(&mut e).unwrap();
+        assert!(required.on_receive(&mut e).is_ok());
+
+        e.set_extension("tampered", true);
+        assert!(optional.on_receive(&mut e).is_err());
+    }
+
+    #[cfg(feature = "http")]
+    #[test]
+    fn binary_mode_round_trip() {
+        use crate::binding::http::{event_to_binary, to_event};
+
+        let mut e = event();
+        e.set_extension("someint", 10);
+        e.set_extension("somebool", true);
+        attach_digest(&mut e);
+
+        let (headers, body) = event_to_binary(e).unwrap();
+        let received = to_event(&headers, body).unwrap();
+        assert_eq!(
+            Some(&ExtensionValue::String("10".to_string())),
+            received.get_extension("someint")
+        );
+        assert!(verify_digest(&received).is_ok());
+    }
+}
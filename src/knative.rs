@@ -0,0 +1,200 @@
+//! Helpers for the event sources deployed with a Knative
+//! [SinkBinding](https://knative.dev/docs/eventing/custom-event-source/sinkbinding/), which
+//! injects the `K_SINK` and `CE_OVERRIDES` environment variables in the source containers.
+//!
+//! [`SinkBinding::from_env`] reads them: `K_SINK` is the URL the events are sent to, and
+//! `CE_OVERRIDES` the [`Overrides`] of the extensions of the sent events. With the `reqwest`
+//! feature, [`SinkBinding::event_sender`] gets an [`EventSender`](crate::message::EventSender)
+//! posting the events to the sink with the overrides applied.
+//!
+//! ```
+//! use cloudevents::knative::SinkBinding;
+//! use cloudevents::{AttributesReader, EventBuilder, EventBuilderV10};
+//!
+//! let binding = SinkBinding::from_values(
+//!     Some("http://broker-ingress.knative-eventing.svc.cluster.local/default/default"),
+//!     Some(r#"{"extensions": {"cluster": "production"}}"#),
+//! )
+//! .unwrap();
+//!
+//! let mut event = EventBuilderV10::new()
+//!     .id("0001")
+//!     .source("http://localhost/")
+//!     .ty("example.demo")
+//!     .build()
+//!     .unwrap();
+//! binding.overrides().apply(&mut event);
+//! assert_eq!(Some(&"production".into()), event.get_extension("cluster"));
+//! ```
+
+use crate::event::ExtensionValue;
+use crate::message::EventInterceptor;
+use crate::{AttributesWriter, Event};
+use serde::Deserialize;
+use snafu::{ResultExt, Snafu};
+use std::collections::BTreeMap;
+use url::Url;
+
+/// Name of the environment variable carrying the URL of the sink
+pub const K_SINK: &str = "K_SINK";
+
+/// Name of the environment variable carrying the [`Overrides`] of the sent events
+pub const CE_OVERRIDES: &str = "CE_OVERRIDES";
+
+/// Represents an error while reading the environment injected by a SinkBinding
+#[derive(Debug, Snafu)]
+pub enum Error {
+    #[snafu(display("The {} environment variable is not set", K_SINK))]
+    MissingSink {},
+    #[snafu(display("Invalid {} URL: {}", K_SINK, source))]
+    InvalidSink { source: url::ParseError },
+    #[snafu(display("Invalid {} JSON: {}", CE_OVERRIDES, source))]
+    InvalidOverrides { source: serde_json::Error },
+}
+
+/// Overrides of the events sent by a source, read from the `CE_OVERRIDES` environment variable,
+/// e.g. `{"extensions": {"cluster": "production"}}`.
+///
+/// It's an [`EventInterceptor`], applying the overrides in its `on_send` hook.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct Overrides {
+    #[serde(default)]
+    extensions: BTreeMap<String, ExtensionValue>,
+}
+
+impl Overrides {
+    /// Parse the `CE_OVERRIDES` JSON value
+    pub fn parse(json: &str) -> Result<Self, Error> {
+        serde_json::from_str(json).context(InvalidOverrides)
+    }
+
+    /// The extensions set on the events, replacing their previous value
+    pub fn extensions(&self) -> impl Iterator<Item = (&str, &ExtensionValue)> {
+        self.extensions
+            .iter()
+            .map(|(name, value)| (name.as_str(), value))
+    }
+
+    /// Set the overridden extensions on `event`
+    pub fn apply(&self, event: &mut Event) {
+        for (name, value) in &self.extensions {
+            event.set_extension(name, value.clone());
+        }
+    }
+}
+
+impl EventInterceptor for Overrides {
+    fn on_send(&self, event: &mut Event) -> crate::message::Result<()> {
+        self.apply(event);
+        Ok(())
+    }
+}
+
+/// Configuration injected by a SinkBinding: the URL of the sink and the [`Overrides`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct SinkBinding {
+    sink: Url,
+    overrides: Overrides,
+}
+
+impl SinkBinding {
+    /// Read the `K_SINK` and `CE_OVERRIDES` environment variables.
+    ///
+    /// Fails with [`Error::MissingSink`] if `K_SINK` is not set, while `CE_OVERRIDES` is optional.
+    pub fn from_env() -> Result<Self, Error> {
+        SinkBinding::from_values(
+            std::env::var(K_SINK).ok().as_deref(),
+            std::env::var(CE_OVERRIDES).ok().as_deref(),
+        )
+    }
+
+    /// Read the values of the `K_SINK` and `CE_OVERRIDES` environment variables
+    pub fn from_values(sink: Option<&str>, overrides: Option<&str>) -> Result<Self, Error> {
+        let sink = Url::parse(sink.ok_or(Error::MissingSink {})?).context(InvalidSink)?;
+        let overrides = match overrides.filter(|o| !o.trim().is_empty()) {
+            Some(overrides) => Overrides::parse(overrides)?,
+            None => Overrides::default(),
+        };
+        Ok(SinkBinding { sink, overrides })
+    }
+
+    /// The URL the events are sent to
+    pub fn sink(&self) -> &Url {
+        &self.sink
+    }
+
+    /// The overrides of the sent events
+    pub fn overrides(&self) -> &Overrides {
+        &self.overrides
+    }
+
+    /// Get an [`EventSender`](crate::message::EventSender) applying the overrides to the events,
+    /// then posting them to the sink in binary mode with `client`. Enabled with the `reqwest`
+    /// feature.
+    #[cfg(feature = "reqwest")]
+    pub fn event_sender(&self, client: reqwest::Client) -> impl crate::message::EventSender {
+        use crate::message::EventSender;
+
+        let sender = crate::binding::reqwest::event_sender(client, self.sink.clone());
+        let overrides = self.overrides.clone();
+        move |mut event: Event| {
+            overrides.apply(&mut event);
+            sender.send(event)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AttributesReader;
+
+    #[test]
+    fn from_values() {
+        let binding = SinkBinding::from_values(
+            Some("http://sink.default.svc.cluster.local"),
+            Some(r#"{"extensions": {"cluster": "production", "priority": 1}}"#),
+        )
+        .unwrap();
+        assert_eq!(
+            "sink.default.svc.cluster.local",
+            binding.sink().host_str().unwrap()
+        );
+        assert_eq!(
+            vec![
+                ("cluster", &ExtensionValue::from("production")),
+                ("priority", &ExtensionValue::from(1i64))
+            ],
+            binding.overrides().extensions().collect::<Vec<_>>()
+        );
+
+        let binding =
+            SinkBinding::from_values(Some("http://sink.default.svc.cluster.local"), Some(""))
+                .unwrap();
+        assert_eq!(&Overrides::default(), binding.overrides());
+        let binding = SinkBinding::from_values(Some("http://localhost"), Some("{}")).unwrap();
+        assert_eq!(&Overrides::default(), binding.overrides());
+
+        assert!(matches!(
+            SinkBinding::from_values(None, None),
+            Err(Error::MissingSink {})
+        ));
+        assert!(matches!(
+            SinkBinding::from_values(Some("not a url"), None),
+            Err(Error::InvalidSink { .. })
+        ));
+        assert!(matches!(
+            SinkBinding::from_values(Some("http://localhost"), Some("{")),
+            Err(Error::InvalidOverrides { .. })
+        ));
+    }
+
+    #[test]
+    fn interceptor() {
+        let overrides = Overrides::parse(r#"{"extensions": {"cluster": "production"}}"#).unwrap();
+        let mut event = Event::default();
+        event.set_extension("cluster", "staging");
+        overrides.on_send(&mut event).unwrap();
+        assert_eq!(Some(&"production".into()), event.get_extension("cluster"));
+    }
+}
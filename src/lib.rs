@@ -17,22 +17,160 @@
 //! println!("CloudEvent Time: {}", event.get_time().unwrap());
 //! ```
 //!
-//! If you're looking for Protocol Binding implementations, look at crates:
+//! The [`event!`] macro creates the same event with the defaults of the missing attributes, e.g.
+//! `event!(type: "example.demo", source: "http://localhost:8080")`.
+//!
+//! If you're looking for Protocol Binding implementations, look at the [`binding`] module:
+//!
+//! * `binding::http`, enabled with the `http` feature: binary and structured mode HTTP binding for the [http](https://github.com/hyperium/http) crate types
+//! * `binding::actix`, enabled with the `actix` feature: [`Event`] extractor and responder for [Actix Web](https://github.com/actix/actix-web) handlers
+//! * `binding::axum`, enabled with the `axum` feature: [`Event`] extractor and response for [axum](https://github.com/tokio-rs/axum) handlers
+//! * `binding::warp`, enabled with the `warp` feature: [`Event`] filter and reply for [warp](https://github.com/seanmonstar/warp)
+//! * `binding::rocket`, enabled with the `rocket` feature: [`Event`] data guard and responder for [Rocket](https://rocket.rs) routes
+//! * `binding::poem`, enabled with the `poem` feature: [`Event`] extractor and response for [Poem](https://github.com/poem-web/poem) handlers
+//! * `binding::salvo`, enabled with the `salvo` feature: [`Event`] extractor and writer for [Salvo](https://salvo.rs) handlers
+//! * `binding::tide`, enabled with the `tide` feature: extension traits to read and write [`Event`]s with [Tide](https://github.com/http-rs/tide) requests and responses, and webhook validation middleware
+//! * `binding::hyper`, enabled with the `hyper` feature: [hyper](https://hyper.rs) `Service` calling an event handler, for servers not built on a framework
+//! * `binding::tower`, enabled with the `tower` feature: [tower](https://github.com/tower-rs/tower) `Layer` converting the HTTP requests and responses of any tower-based stack to [`Event`]s
+//! * `binding::rdkafka`, enabled with the `rdkafka` feature: Kafka binding for the [rdkafka](https://github.com/fede1024/rust-rdkafka) producer and consumer types
+//! * `binding::reqwest`, enabled with the `reqwest` feature: extension traits to send and receive [`Event`]s with [reqwest](https://github.com/seanmonstar/reqwest)
+//! * `binding::amqp`, enabled with the `amqp` feature: AMQP 1.0 binding for the [fe2o3-amqp](https://github.com/minghuaw/fe2o3-amqp) message types
+//! * `binding::azure`, enabled with the `azure` feature: converters of the [Azure Event Grid](https://learn.microsoft.com/azure/event-grid/) event schema, and [Azure Service Bus](https://learn.microsoft.com/azure/service-bus-messaging/) mapping on top of the AMQP binding
+//! * `binding::mqtt`, enabled with the `mqtt` feature: MQTT binding for the [rumqttc](https://github.com/bytebeamio/rumqtt) publish packets
+//! * `binding::tonic`, enabled with the `tonic` feature: [tonic](https://github.com/hyperium/tonic) codec to send and receive [`Event`]s over gRPC, using the [`proto`] types
+//! * `binding::sse`, enabled with the `sse` feature: [Server-Sent Events](https://html.spec.whatwg.org/multipage/server-sent-events.html) codec to push events to browsers
+//! * `binding::websocket`, enabled with the `websocket` feature: WebSocket binding with the `cloudevents.json`, `cloudevents.proto` and `cloudevents.avro` subprotocols, adapting any stream and sink of messages
+//!
+//! and at crates:
 //!
 //! * `cloudevents-sdk-actix-web`: Integration with [Actix Web](https://github.com/actix/actix-web)
 //! * `cloudevents-sdk-reqwest`: Integration with [reqwest](https://github.com/seanmonstar/reqwest)
 //!
+//! The [`binding::transcode`] module moves messages between the bindings, e.g. from a Kafka
+//! binary mode record to an HTTP structured mode request, without building the [`Event`].
+//!
+//! The bindings read the structured mode messages in the JSON event format, and in the
+//! [`message::EventFormat`]s registered with [`message::set_event_formats`].
+//! The `msgpack` and `cbor` features provide the `msgpack` and `cbor` structured formats, compact
+//! binary encodings of the JSON event format.
+//!
+//! The `compression` feature compresses the structured mode payloads with gzip or zstd: the HTTP
+//! binding decompresses the bodies according to their `content-encoding`, and
+//! `message::CompressedFormat` compresses the payloads of any transport. The decompressed
+//! payloads are bounded, by `message::DEFAULT_DECOMPRESSED_LIMIT` unless configured otherwise.
+//!
+//! The `yaml` feature reads and writes events as YAML documents, e.g. for test fixtures, see
+//! `yaml`.
+//!
+//! The `integrity` feature provides `integrity`, computing the SHA-256 digest of the canonical
+//! serialization of an event, carried in the `datadigest` extension and verified on receipt.
+//! The `security` feature provides `security`, signing the canonical serialization with JWS,
+//! and encrypting the data of the events with AES-GCM.
+//!
+//! The `stream` feature provides the `message::EventStream` and `message::EventSink` traits, to
+//! compose pipelines of events with the [futures](https://docs.rs/futures) combinators, adapted
+//! from the Kafka consumer and producer and the reqwest client.
+//! It also provides the `message::AsyncBinarySerializer` and `message::AsyncBinaryDeserializer`
+//! traits, streaming the payload of the binary mode messages, implemented for the HTTP bodies by
+//! `binding::http::StreamingDeserializer`, and `event::StreamingEvent`, an event with its data
+//! streamed, so the bindings can proxy large payloads without holding them in memory.
+//!
+//! The `retry` feature provides `message::RetryingSender`, retrying the failed sends of any
+//! transport with an exponential backoff.
+//!
+//! The `opentelemetry` feature instruments the bindings with `binding::telemetry`, recording
+//! [OpenTelemetry](https://opentelemetry.io) spans of the sent and received events and
+//! propagating the trace context with the distributed tracing extension.
+//!
+//! The `tracing` feature logs the serialization, deserialization and validation failures with
+//! [tracing](https://docs.rs/tracing), see `logging`.
+//!
+//! The `prometheus` feature provides `metrics::PrometheusMetrics`, exporting the [`metrics`] of
+//! the bindings traffic in the Prometheus text format.
+//!
+//! The `macros` feature provides `#[derive(EventData)]`, declaring the `type`, `source` and
+//! `datacontenttype` of the events carrying a Rust type, converted with `event::ToEvent` and
+//! `event::TryFromEvent`, and `#[cloudevent_handler]`, registering async functions taking these
+//! types with `router::EventRouter`.
+//!
+//! The `time` feature sets and reads the `time` attribute as a
+//! [`time::OffsetDateTime`](https://docs.rs/time), keeping its offset, for the applications
+//! using the `time` crate. The event still stores the `time` attribute with `chrono`, which
+//! remains a dependency, and rejects the offsets of a day or more that `chrono` can't represent.
+//!
+//! Structured and batched mode messages are parsed with [simd-json](https://github.com/simd-lite/simd-json)
+//! when the `simd-json` feature is enabled, which is faster on CPUs with SIMD instructions.
+//!
 
 extern crate serde;
 extern crate serde_json;
 extern crate serde_value;
 extern crate snafu;
 
+// The code generated by the macros refers to the crate as `::cloudevents`
+#[cfg(test)]
+extern crate self as cloudevents;
+
+/// Provides the [CloudEvents Adapters](https://github.com/cloudevents/spec/blob/main/cloudevents/adapters/README.md) converting third-party webhooks to events
+pub mod adapter;
+/// Provides the Avro Event Format, enabled with the `avro` feature
+#[cfg(feature = "avro")]
+pub mod avro;
+/// Provides protocol binding implementations, enabled through cargo features
+pub mod binding;
+/// Provides the CBOR structured format, enabled with the `cbor` feature
+#[cfg(feature = "cbor")]
+pub mod cbor;
+/// Provides the [CloudEvents conformance](https://github.com/cloudevents/conformance) test vectors loader, enabled with the `conformance` feature
+#[cfg(feature = "conformance")]
+pub mod conformance;
+/// Provides the resources of the [CloudEvents Discovery API](https://github.com/cloudevents/spec/blob/v1.0.2/discovery/spec.md), and its client with the `reqwest` feature
+pub mod discovery;
 /// Provides [`Event`] data structure, [`EventBuilder`] and other facilities to work with [`Event`]
 pub mod event;
+/// Provides typed APIs for the CloudEvents documented extensions
+pub mod extensions;
+/// Provides the [CloudEvents Subscriptions API](https://github.com/cloudevents/spec/blob/main/subscriptions/spec.md) filter dialects
+pub mod filter;
+/// Provides the canonical serialization and the digest of events, enabled with the `integrity` feature
+#[cfg(feature = "integrity")]
+pub mod integrity;
+/// Provides the helpers for the event sources bound to a sink by a Knative SinkBinding
+pub mod knative;
+/// Provides structured logging of the failures with tracing, enabled with the `tracing` feature
+#[cfg(feature = "tracing")]
+pub mod logging;
 /// Provides facilities to implement Protocol Bindings
 pub mod message;
+/// Provides the hooks to monitor the traffic of the bindings
+pub mod metrics;
+/// Provides the MessagePack structured format, enabled with the `msgpack` feature
+#[cfg(feature = "msgpack")]
+pub mod msgpack;
+/// Provides the NDJSON format of a batch of events, with one JSON event per line
+pub mod ndjson;
+/// Provides the Protobuf Event Format types, enabled with the `protobuf` feature
+#[cfg(feature = "protobuf")]
+pub mod proto;
+/// Provides [`EventRouter`](router::EventRouter) to dispatch events to handlers by `type`, `source` and `subject`
+pub mod router;
+/// Provides the resolvers of the `dataschema` of the events, working without network access
+pub mod schema;
+/// Provides the JWS signatures and the data encryption of events, enabled with the `security` feature
+#[cfg(feature = "security")]
+pub mod security;
+/// Provides the [CloudEvents SQL](https://github.com/cloudevents/spec/blob/main/cesql/spec.md) expression language to filter events
+pub mod sql;
+/// Provides the resources of the [CloudEvents Subscriptions API](https://github.com/cloudevents/spec/blob/main/subscriptions/spec.md), and its client with the `reqwest` feature
+pub mod subscriptions;
+/// Provides facilities to test producers and consumers of events, enabled with the `testing` feature
+#[cfg(feature = "testing")]
+pub mod testing;
+/// Provides the YAML representation of events, enabled with the `yaml` feature
+#[cfg(feature = "yaml")]
+pub mod yaml;
 
+pub use event::Batch;
 pub use event::Event;
 pub use event::{AttributesReader, AttributesWriter};
 pub use event::{EventBuilder, EventBuilderV03, EventBuilderV10};
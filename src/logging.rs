@@ -0,0 +1,290 @@
+//! Structured logging of the serialization, deserialization and validation failures with
+//! [`tracing`](https://docs.rs/tracing), enabled with the `tracing` feature.
+//!
+//! The failures are logged as `WARN` events with the `cloudevents` target, with the `id`, `type`,
+//! `source` and `specversion` fields of the event when available, and the `error` field.
+//! The data of the events is never logged, unless a [`Redactor`] is installed with
+//! [`set_redactor`]: the `data` field then logs the data of [`Event::redacted`].
+//!
+//! ```
+//! use cloudevents::event::{set_redactor, DataRedactor};
+//!
+//! // Log the data with the card numbers masked
+//! set_redactor(DataRedactor::new().path("/card/number"));
+//! ```
+//!
+//! [`Redactor`]: crate::event::Redactor
+//! [`set_redactor`]: crate::event::set_redactor
+
+use crate::event::{AttributesReader, Data, SpecVersion, ValidationError};
+use crate::message::{Encoding, Error};
+use crate::Event;
+
+/// Target of the logged events
+pub const TARGET: &str = "cloudevents";
+
+/// Render the data of `event` redacted by the installed redactor, if any
+fn redacted_data(event: &Event) -> Option<String> {
+    match crate::event::redacted_with_installed(event)?.data? {
+        Data::Json(value) => Some(value.to_string()),
+        Data::String(s) => Some(s),
+        Data::Binary(b) => Some(format!("<{} bytes>", b.len())),
+    }
+}
+
+/// Fields of an event captured before it's consumed by the serialization, to log its failure
+pub(crate) struct EventFields {
+    id: String,
+    ty: String,
+    source: String,
+    specversion: SpecVersion,
+    data: Option<String>,
+}
+
+impl EventFields {
+    /// Capture the fields of `event`, or return `None` if the failures aren't logged
+    pub(crate) fn capture(event: &Event) -> Option<Self> {
+        if !tracing::enabled!(target: TARGET, tracing::Level::WARN) {
+            return None;
+        }
+        Some(EventFields {
+            id: event.get_id().to_string(),
+            ty: event.get_type().to_string(),
+            source: event.get_source().to_string(),
+            specversion: event.get_specversion(),
+            data: redacted_data(event),
+        })
+    }
+
+    pub(crate) fn serialization_failed(&self, error: &Error) {
+        tracing::warn!(
+            target: TARGET,
+            id = %self.id,
+            r#type = %self.ty,
+            source = %self.source,
+            specversion = %self.specversion,
+            data = self.data.as_deref(),
+            error = %error,
+            "Failed to serialize event"
+        );
+    }
+}
+
+/// Log the serialization failure of `event`, when the result of the serialization is an error
+pub(crate) fn inspect_serialization<R>(event: Option<EventFields>, result: &Result<R, Error>) {
+    if let (Some(event), Err(error)) = (event, result) {
+        event.serialization_failed(error);
+    }
+}
+
+/// Log the deserialization failure of a message in the `encoding` content mode
+pub(crate) fn deserialization_failed(encoding: Encoding, error: &Error) {
+    tracing::warn!(
+        target: TARGET,
+        encoding = ?encoding,
+        error = %error,
+        "Failed to deserialize event"
+    );
+}
+
+/// Log the validation failure of `event`
+pub(crate) fn validation_failed(event: &Event, errors: &[ValidationError]) {
+    let errors = errors
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<String>>()
+        .join("; ");
+    tracing::warn!(
+        target: TARGET,
+        id = event.get_id(),
+        r#type = event.get_type(),
+        source = %event.get_source(),
+        specversion = %event.get_specversion(),
+        data = redacted_data(event).as_deref(),
+        error = %errors,
+        "Invalid event"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::{clear_redactor, set_redactor, AttributesWriter};
+    use crate::message::{
+        BinaryDeserializer, BinarySerializer, Bytes, MessageAttributeValue, MessageDeserializer,
+        Result, StructuredDeserializer, StructuredSerializer,
+    };
+    use crate::{EventBuilder, EventBuilderV10};
+    use std::fmt::Debug;
+    use std::sync::{Arc, Mutex};
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Metadata, Subscriber};
+
+    #[derive(Default)]
+    struct Fields(Vec<(String, String)>);
+
+    /// Subscriber recording the fields of the events
+    #[derive(Default, Clone)]
+    struct Recorder(Arc<Mutex<Vec<Fields>>>);
+
+    impl Visit for Fields {
+        fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+            self.0
+                .push((field.name().to_string(), format!("{:?}", value)));
+        }
+
+        fn record_str(&mut self, field: &Field, value: &str) {
+            self.0.push((field.name().to_string(), value.to_string()));
+        }
+    }
+
+    impl Subscriber for Recorder {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, _: &Attributes<'_>) -> Id {
+            Id::from_u64(1)
+        }
+
+        fn record(&self, _: &Id, _: &Record<'_>) {}
+
+        fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+        fn event(&self, event: &tracing::Event<'_>) {
+            let mut fields = Fields::default();
+            event.record(&mut fields);
+            self.0.lock().unwrap().push(fields);
+        }
+
+        fn enter(&self, _: &Id) {}
+
+        fn exit(&self, _: &Id) {}
+    }
+
+    impl Recorder {
+        fn field(&self, index: usize, name: &str) -> Option<String> {
+            self.0.lock().unwrap()[index]
+                .0
+                .iter()
+                .find(|(n, _)| n == name)
+                .map(|(_, v)| v.clone())
+        }
+    }
+
+    /// Message of unknown encoding
+    struct Unknown;
+
+    impl StructuredDeserializer for Unknown {
+        fn deserialize_structured<R, V: StructuredSerializer<R>>(self, _: V) -> Result<R> {
+            Err(Error::WrongEncoding {})
+        }
+    }
+
+    impl BinaryDeserializer for Unknown {
+        fn deserialize_binary<R, V: BinarySerializer<R>>(self, _: V) -> Result<R> {
+            Err(Error::WrongEncoding {})
+        }
+    }
+
+    impl MessageDeserializer for Unknown {
+        fn encoding(&self) -> Encoding {
+            Encoding::UNKNOWN
+        }
+    }
+
+    /// Serializer rejecting the extensions
+    struct NoExtensions;
+
+    impl BinarySerializer<()> for NoExtensions {
+        fn set_spec_version(self, _: SpecVersion) -> Result<Self> {
+            Ok(self)
+        }
+
+        fn set_attribute(self, _: &str, _: MessageAttributeValue) -> Result<Self> {
+            Ok(self)
+        }
+
+        fn set_extension(self, name: &str, _: MessageAttributeValue) -> Result<Self> {
+            Err(Error::Other {
+                source: format!("unsupported extension {}", name).into(),
+            })
+        }
+
+        fn end_with_data(self, _: Bytes) -> Result<()> {
+            Ok(())
+        }
+
+        fn end(self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    fn event() -> Event {
+        EventBuilderV10::new()
+            .id("0001")
+            .source("http://localhost/")
+            .ty("example.test")
+            .data("text/plain", "secret")
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn failures_are_logged() {
+        // The logged data depends on the installed redactor
+        let _lock = crate::event::INSTALLED_REDACTOR_LOCK
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let recorder = Recorder::default();
+        tracing::subscriber::with_default(recorder.clone(), || {
+            let mut invalid = event();
+            invalid.set_id("");
+            assert!(invalid.validate().is_err());
+
+            assert!(MessageDeserializer::into_event(Unknown).is_err());
+
+            let mut unserializable = event();
+            AttributesWriter::set_extension(&mut unserializable, "someext", "value");
+            assert!(unserializable.deserialize_binary(NoExtensions).is_err());
+
+            set_redactor(|e: &mut Event| e.write_data("text/plain", "<redacted>"));
+            assert!(invalid.validate().is_err());
+            clear_redactor();
+        });
+
+        assert_eq!(4, recorder.0.lock().unwrap().len());
+        assert_eq!(
+            Some(String::from("Invalid event")),
+            recorder.field(0, "message")
+        );
+        assert_eq!(Some(String::from("")), recorder.field(0, "id"));
+        assert_eq!(
+            Some(String::from("example.test")),
+            recorder.field(0, "type")
+        );
+        assert_eq!(Some(String::from("1.0")), recorder.field(0, "specversion"));
+        assert_eq!(None, recorder.field(0, "data"));
+
+        assert_eq!(
+            Some(String::from("Failed to deserialize event")),
+            recorder.field(1, "message")
+        );
+
+        assert_eq!(
+            Some(String::from("Failed to serialize event")),
+            recorder.field(2, "message")
+        );
+        assert_eq!(Some(String::from("0001")), recorder.field(2, "id"));
+        assert_eq!(
+            Some(String::from("http://localhost/")),
+            recorder.field(2, "source")
+        );
+
+        assert_eq!(Some(String::from("<redacted>")), recorder.field(3, "data"));
+        for fields in recorder.0.lock().unwrap().iter() {
+            assert!(fields.0.iter().all(|(_, v)| !v.contains("secret")));
+        }
+    }
+}
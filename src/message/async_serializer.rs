@@ -0,0 +1,264 @@
+use super::{BinaryMessage, BinarySerializer, MessageAttributeValue, Result, StructuredSerializer};
+use crate::event::{EventBinarySerializer, SpecVersion};
+use crate::Event;
+use bytes::{Bytes, BytesMut};
+use futures::{Future, Stream, StreamExt};
+use std::pin::Pin;
+
+/// Stream of the chunks of the payload of a message
+pub type DataStream<'a> = Pin<Box<dyn Stream<Item = Result<Bytes>> + Send + 'a>>;
+
+/// Future returned by the asynchronous serializers and deserializers
+pub type SerializerFuture<'a, R> = Pin<Box<dyn Future<Output = Result<R>> + Send + 'a>>;
+
+/// Asynchronous variant of [`BinarySerializer`], receiving the payload of a binary mode message
+/// as a [`DataStream`], so it can be written to its destination without buffering it.
+///
+/// Any [`BinarySerializer`] is adapted with [`BufferingSerializer`], buffering the payload.
+pub trait AsyncBinarySerializer<R>: Sized + Send {
+    fn set_spec_version(self, spec_version: SpecVersion) -> Result<Self>;
+
+    fn set_attribute(self, name: &str, value: MessageAttributeValue) -> Result<Self>;
+
+    fn set_extension(self, name: &str, value: MessageAttributeValue) -> Result<Self>;
+
+    /// End the message with the payload streamed by `data`
+    fn end_with_stream<'a>(self, data: DataStream<'a>) -> SerializerFuture<'a, R>
+    where
+        Self: 'a,
+        R: 'a;
+
+    fn end(self) -> Result<R>;
+}
+
+/// Asynchronous variant of [`BinaryDeserializer`](super::BinaryDeserializer), streaming the
+/// payload of a binary mode message to an [`AsyncBinarySerializer`]
+pub trait AsyncBinaryDeserializer: Sized + Send {
+    /// Deserialize the message to [`AsyncBinarySerializer`]
+    fn deserialize_binary_async<'a, R, V>(self, serializer: V) -> SerializerFuture<'a, R>
+    where
+        Self: 'a,
+        R: Send + 'a,
+        V: AsyncBinarySerializer<R> + 'a;
+
+    /// Convert this Message to [`Event`], buffering its payload
+    fn into_event_async<'a>(self) -> SerializerFuture<'a, Event>
+    where
+        Self: 'a,
+    {
+        self.deserialize_binary_async(BufferingSerializer::new(EventBinarySerializer::new()))
+    }
+}
+
+/// Asynchronous variant of [`StructuredDeserializer`](super::StructuredDeserializer), reading
+/// the payload of a structured mode message from a stream.
+///
+/// The payload of a structured mode message is always collected before it's passed to the
+/// [`StructuredSerializer`], since the event can't be parsed before the payload is complete.
+pub trait AsyncStructuredDeserializer: Sized + Send {
+    /// Deserialize the message to [`StructuredSerializer`]
+    fn deserialize_structured_async<'a, R, V>(self, serializer: V) -> SerializerFuture<'a, R>
+    where
+        Self: 'a,
+        R: Send + 'a,
+        V: StructuredSerializer<R> + Send + 'a;
+}
+
+/// [`AsyncBinarySerializer`] collecting the streamed payload to pass it to a [`BinarySerializer`]
+pub struct BufferingSerializer<S> {
+    serializer: S,
+}
+
+impl<S> BufferingSerializer<S> {
+    /// Create a new [`BufferingSerializer`] writing to `serializer`
+    pub fn new(serializer: S) -> Self {
+        BufferingSerializer { serializer }
+    }
+}
+
+impl<R: Send, S: BinarySerializer<R> + Send> AsyncBinarySerializer<R> for BufferingSerializer<S> {
+    fn set_spec_version(self, spec_version: SpecVersion) -> Result<Self> {
+        Ok(BufferingSerializer::new(
+            self.serializer.set_spec_version(spec_version)?,
+        ))
+    }
+
+    fn set_attribute(self, name: &str, value: MessageAttributeValue) -> Result<Self> {
+        Ok(BufferingSerializer::new(
+            self.serializer.set_attribute(name, value)?,
+        ))
+    }
+
+    fn set_extension(self, name: &str, value: MessageAttributeValue) -> Result<Self> {
+        Ok(BufferingSerializer::new(
+            self.serializer.set_extension(name, value)?,
+        ))
+    }
+
+    fn end_with_stream<'a>(self, data: DataStream<'a>) -> SerializerFuture<'a, R>
+    where
+        Self: 'a,
+        R: 'a,
+    {
+        Box::pin(async move {
+            let data = collect(data).await?;
+            if data.is_empty() {
+                self.serializer.end()
+            } else {
+                self.serializer.end_with_data(data)
+            }
+        })
+    }
+
+    fn end(self) -> Result<R> {
+        self.serializer.end()
+    }
+}
+
+/// Write the attributes of `message` to `serializer`, ending it with `data`, or the payload of
+/// `message` if `data` is `None`
+pub(crate) async fn write_binary<'a, R, V>(
+    message: BinaryMessage,
+    data: Option<DataStream<'a>>,
+    mut serializer: V,
+) -> Result<R>
+where
+    R: 'a,
+    V: AsyncBinarySerializer<R> + 'a,
+{
+    serializer = serializer.set_spec_version(message.spec_version())?;
+    for (name, value) in message.attributes() {
+        serializer = serializer.set_attribute(name, value.clone())?;
+    }
+    for (name, value) in message.extensions() {
+        serializer = serializer.set_extension(name, value.clone())?;
+    }
+    let data = data.or_else(|| {
+        message.data().cloned().map(|data| {
+            Box::pin(futures::stream::once(futures::future::ready(Ok(data)))) as DataStream<'a>
+        })
+    });
+    match data {
+        Some(data) => serializer.end_with_stream(data).await,
+        None => serializer.end(),
+    }
+}
+
+/// Collect the chunks of `data`
+pub async fn collect(mut data: DataStream<'_>) -> Result<Bytes> {
+    let first = match data.next().await {
+        Some(chunk) => chunk?,
+        None => return Ok(Bytes::new()),
+    };
+    // A payload of a single chunk is not copied
+    let second = match data.next().await {
+        Some(chunk) => chunk?,
+        None => return Ok(first),
+    };
+    let mut buffer = BytesMut::with_capacity(first.len() + second.len());
+    buffer.extend_from_slice(&first);
+    buffer.extend_from_slice(&second);
+    while let Some(chunk) = data.next().await {
+        buffer.extend_from_slice(&chunk?);
+    }
+    Ok(buffer.freeze())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::message::Error;
+    use crate::{EventBuilder, EventBuilderV10};
+    use futures::stream;
+
+    /// Serializer counting the streamed chunks without buffering them
+    #[derive(Default)]
+    struct ChunkCounter {
+        attributes: usize,
+    }
+
+    impl AsyncBinarySerializer<(usize, usize)> for ChunkCounter {
+        fn set_spec_version(self, _: SpecVersion) -> Result<Self> {
+            Ok(self)
+        }
+
+        fn set_attribute(mut self, _: &str, _: MessageAttributeValue) -> Result<Self> {
+            self.attributes += 1;
+            Ok(self)
+        }
+
+        fn set_extension(self, _: &str, _: MessageAttributeValue) -> Result<Self> {
+            Ok(self)
+        }
+
+        fn end_with_stream<'a>(self, data: DataStream<'a>) -> SerializerFuture<'a, (usize, usize)>
+        where
+            Self: 'a,
+        {
+            Box::pin(async move {
+                let chunks = data.count().await;
+                Ok((self.attributes, chunks))
+            })
+        }
+
+        fn end(self) -> Result<(usize, usize)> {
+            Ok((self.attributes, 0))
+        }
+    }
+
+    fn chunks(chunks: Vec<&'static [u8]>) -> DataStream<'static> {
+        Box::pin(stream::iter(chunks.into_iter().map(|c| Ok(Bytes::from(c)))))
+    }
+
+    #[tokio::test]
+    async fn buffering() {
+        let event = BufferingSerializer::new(EventBinarySerializer::new())
+            .set_spec_version(SpecVersion::V10)
+            .unwrap()
+            .set_attribute("id", MessageAttributeValue::from("0001"))
+            .unwrap()
+            .set_attribute("source", MessageAttributeValue::from("http://localhost/"))
+            .unwrap()
+            .set_attribute("type", MessageAttributeValue::from("example.test"))
+            .unwrap()
+            .set_attribute("datacontenttype", MessageAttributeValue::from("text/plain"))
+            .unwrap()
+            .end_with_stream(chunks(vec![b"hel", b"lo", b""]))
+            .await
+            .unwrap();
+        let expected = EventBuilderV10::new()
+            .id("0001")
+            .source("http://localhost/")
+            .ty("example.test")
+            .data("text/plain", b"hello".to_vec())
+            .build()
+            .unwrap();
+        assert_eq!(expected, event);
+
+        let (attributes, chunks) = ChunkCounter::default()
+            .set_attribute("id", MessageAttributeValue::from("0001"))
+            .unwrap()
+            .end_with_stream(chunks(vec![b"a", b"b", b"c"]))
+            .await
+            .unwrap();
+        assert_eq!((1, 3), (attributes, chunks));
+    }
+
+    #[tokio::test]
+    async fn collect_chunks() {
+        assert!(collect(chunks(vec![])).await.unwrap().is_empty());
+
+        let single = Bytes::from_static(b"single");
+        let ptr = single.as_ptr();
+        let data: DataStream = Box::pin(stream::iter(vec![Ok(single)]));
+        assert_eq!(ptr, collect(data).await.unwrap().as_ptr());
+
+        let failing: DataStream = Box::pin(stream::iter(vec![
+            Ok(Bytes::from_static(b"a")),
+            Err(Error::Other {
+                source: "connection reset".into(),
+            }),
+        ]));
+        assert!(collect(failing).await.is_err());
+    }
+}
@@ -0,0 +1,250 @@
+use super::{Error, EventFormat, Result};
+use crate::Event;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use std::io::{Read, Write};
+
+/// Magic bytes starting a gzip stream
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// Magic bytes starting a zstd frame
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+
+/// Default maximum size of a decompressed payload, so a small compressed payload of an untrusted
+/// peer can't expand to an unbounded amount of memory
+pub const DEFAULT_DECOMPRESSED_LIMIT: usize = 16 * 1024 * 1024;
+
+/// Compression of the payload of a message, named after its HTTP `content-encoding`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Compression {
+    /// [gzip](https://www.rfc-editor.org/rfc/rfc1952)
+    Gzip,
+    /// [zstd](https://www.rfc-editor.org/rfc/rfc8878)
+    Zstd,
+}
+
+impl Compression {
+    /// Get the compression of the `content-encoding` header value, returning `None` if it's
+    /// not supported
+    pub fn from_content_encoding(content_encoding: &str) -> Option<Compression> {
+        match content_encoding.trim() {
+            ce if ce.eq_ignore_ascii_case("gzip") || ce.eq_ignore_ascii_case("x-gzip") => {
+                Some(Compression::Gzip)
+            }
+            ce if ce.eq_ignore_ascii_case("zstd") => Some(Compression::Zstd),
+            _ => None,
+        }
+    }
+
+    /// Value of the `content-encoding` header of the compressed payloads
+    pub fn content_encoding(&self) -> &'static str {
+        match self {
+            Compression::Gzip => "gzip",
+            Compression::Zstd => "zstd",
+        }
+    }
+
+    /// Returns `true` if `bytes` look compressed with this compression
+    pub fn is_compressed(&self, bytes: &[u8]) -> bool {
+        match self {
+            Compression::Gzip => bytes.starts_with(&GZIP_MAGIC),
+            Compression::Zstd => bytes.starts_with(&ZSTD_MAGIC),
+        }
+    }
+
+    /// Compress `bytes`
+    pub fn compress(&self, bytes: &[u8]) -> Result<Vec<u8>> {
+        match self {
+            Compression::Gzip => {
+                let mut encoder = GzEncoder::new(Vec::new(), flate2::Compression::default());
+                encoder.write_all(bytes)?;
+                Ok(encoder.finish()?)
+            }
+            Compression::Zstd => Ok(zstd::encode_all(bytes, 0)?),
+        }
+    }
+
+    /// Decompress `bytes`, failing with [`Error::EventTooLarge`] beyond
+    /// [`DEFAULT_DECOMPRESSED_LIMIT`]
+    pub fn decompress(&self, bytes: &[u8]) -> Result<Vec<u8>> {
+        self.decompress_with_limit(bytes, DEFAULT_DECOMPRESSED_LIMIT)
+    }
+
+    /// Decompress `bytes`, failing with [`Error::EventTooLarge`] as soon as the decompressed
+    /// payload exceeds `limit` bytes, e.g. the [`SizeLimitPolicy::limit`](super::SizeLimitPolicy::limit)
+    /// of the events
+    pub fn decompress_with_limit(&self, bytes: &[u8], limit: usize) -> Result<Vec<u8>> {
+        match self {
+            Compression::Gzip => read_limited(GzDecoder::new(bytes), limit),
+            Compression::Zstd => read_limited(zstd::Decoder::new(bytes)?, limit),
+        }
+    }
+}
+
+/// Read `reader` to the end, without reading more than one byte past `limit`
+fn read_limited(reader: impl Read, limit: usize) -> Result<Vec<u8>> {
+    let mut decompressed = Vec::new();
+    reader
+        .take(limit as u64 + 1)
+        .read_to_end(&mut decompressed)?;
+    if decompressed.len() > limit {
+        // The actual size is unknown, as the payload isn't decompressed further
+        return Err(Error::EventTooLarge {
+            size: decompressed.len(),
+            limit,
+        });
+    }
+    Ok(decompressed)
+}
+
+/// [`EventFormat`] compressing the payloads encoded by another format, for the transports
+/// without a `content-encoding`, e.g. to send large JSON events through a Kafka topic.
+///
+/// The content type is the one of the wrapped format. The payloads which aren't compressed are
+/// decoded as is, so the consumers can read both the compressed and uncompressed payloads.
+///
+/// ```
+/// use cloudevents::message::{CompressedFormat, Compression, EventFormat, JsonFormat};
+/// use cloudevents::{EventBuilder, EventBuilderV10};
+///
+/// let event = EventBuilderV10::new()
+///     .id("0001")
+///     .source("http://localhost/")
+///     .ty("example.demo")
+///     .data("text/plain", "a".repeat(1024))
+///     .build()
+///     .unwrap();
+///
+/// let format = CompressedFormat::new(JsonFormat, Compression::Gzip);
+/// let payload = format.encode(&event).unwrap();
+/// assert!(payload.len() < JsonFormat.encode(&event).unwrap().len());
+/// assert_eq!(event, format.decode(&payload).unwrap());
+/// ```
+#[derive(Debug, Clone)]
+pub struct CompressedFormat<F> {
+    format: F,
+    compression: Compression,
+    limit: usize,
+}
+
+impl<F: EventFormat> CompressedFormat<F> {
+    /// Create a new [`CompressedFormat`] compressing the payloads of `format` with `compression`
+    pub fn new(format: F, compression: Compression) -> Self {
+        CompressedFormat {
+            format,
+            compression,
+            limit: DEFAULT_DECOMPRESSED_LIMIT,
+        }
+    }
+
+    /// Reject the payloads decompressed to more than `limit` bytes, instead of
+    /// [`DEFAULT_DECOMPRESSED_LIMIT`]
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = limit;
+        self
+    }
+}
+
+impl<F: EventFormat> EventFormat for CompressedFormat<F> {
+    fn content_type(&self) -> &str {
+        self.format.content_type()
+    }
+
+    fn encode(&self, event: &Event) -> Result<Vec<u8>> {
+        self.compression.compress(&self.format.encode(event)?)
+    }
+
+    fn decode(&self, bytes: &[u8]) -> Result<Event> {
+        if self.compression.is_compressed(bytes) {
+            self.format
+                .decode(&self.compression.decompress_with_limit(bytes, self.limit)?)
+        } else {
+            self.format.decode(bytes)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::message::JsonFormat;
+    use crate::{EventBuilder, EventBuilderV10};
+
+    #[test]
+    fn gzip() {
+        assert_eq!(
+            Some(Compression::Gzip),
+            Compression::from_content_encoding(" GZip ")
+        );
+        assert_eq!(None, Compression::from_content_encoding("br"));
+
+        let compressed = Compression::Gzip.compress(b"hello").unwrap();
+        assert!(Compression::Gzip.is_compressed(&compressed));
+        assert_eq!(
+            b"hello".to_vec(),
+            Compression::Gzip.decompress(&compressed).unwrap()
+        );
+        assert!(matches!(
+            Compression::Gzip.decompress(&compressed[..compressed.len() - 4]),
+            Err(Error::IOError { .. })
+        ));
+    }
+
+    #[test]
+    fn zstd() {
+        assert_eq!(
+            Some(Compression::Zstd),
+            Compression::from_content_encoding("zstd")
+        );
+        let compressed = Compression::Zstd.compress(b"hello").unwrap();
+        assert!(Compression::Zstd.is_compressed(&compressed));
+        assert!(!Compression::Gzip.is_compressed(&compressed));
+        assert_eq!(
+            b"hello".to_vec(),
+            Compression::Zstd.decompress(&compressed).unwrap()
+        );
+    }
+
+    #[test]
+    fn decompression_limit() {
+        let payload = vec![0u8; 64 * 1024];
+        for compression in &[Compression::Gzip, Compression::Zstd] {
+            let compressed = compression.compress(&payload).unwrap();
+            assert!(matches!(
+                compression.decompress_with_limit(&compressed, 1024),
+                Err(Error::EventTooLarge { limit: 1024, .. })
+            ));
+            assert_eq!(
+                payload,
+                compression
+                    .decompress_with_limit(&compressed, payload.len())
+                    .unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn uncompressed_payloads_are_decoded() {
+        let event = EventBuilderV10::new()
+            .id("0001")
+            .source("http://localhost/")
+            .ty("example.test")
+            .build()
+            .unwrap();
+        let format = CompressedFormat::new(JsonFormat, Compression::Gzip);
+        assert_eq!("application/cloudevents+json", format.content_type());
+        assert_eq!(
+            event,
+            format.decode(&JsonFormat.encode(&event).unwrap()).unwrap()
+        );
+
+        let format = CompressedFormat::new(JsonFormat, Compression::Zstd);
+        let payload = format.encode(&event).unwrap();
+        assert_eq!(event, format.decode(&payload).unwrap());
+        assert!(matches!(
+            format.limit(16).decode(&payload),
+            Err(Error::EventTooLarge { limit: 16, .. })
+        ));
+    }
+}
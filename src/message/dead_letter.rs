@@ -0,0 +1,162 @@
+use super::{EventSender, Result};
+use crate::event::AttributesWriter;
+use crate::Event;
+use std::fmt::Display;
+use std::future::Future;
+
+/// Extension set by [`DeadLetterPolicy`] to the reason the event processing failed
+pub const DEADLETTER_REASON: &str = "deadletterreason";
+
+/// Extension set by [`DeadLetterPolicy`] to the name of the consumer which failed processing the
+/// event, when configured
+pub const DEADLETTER_CONSUMER: &str = "deadletterconsumer";
+
+/// Policy forwarding the events a consumer failed to process to a dead letter destination,
+/// annotated with the [`DEADLETTER_REASON`] and [`DEADLETTER_CONSUMER`] extensions.
+///
+/// The destination is any [`EventSender`], e.g. the senders of the Kafka and reqwest bindings,
+/// and the handler any function processing the event, e.g. in a Kafka consumer loop or an HTTP
+/// server handler.
+///
+/// ```
+/// use cloudevents::message::{DeadLetterPolicy, DEADLETTER_REASON};
+/// use cloudevents::{AttributesReader, Event, EventBuilder, EventBuilderV10};
+/// use std::sync::Mutex;
+///
+/// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+/// let dead_letters = Mutex::new(Vec::new());
+/// let policy = DeadLetterPolicy::new(|event: Event| {
+///     dead_letters.lock().unwrap().push(event);
+///     async { Ok(()) }
+/// })
+/// .consumer("orders");
+///
+/// let event = EventBuilderV10::new()
+///     .id("0001")
+///     .source("http://localhost/")
+///     .ty("example.demo")
+///     .build()
+///     .unwrap();
+/// policy
+///     .handle(event, |_| async { Err("unknown order") })
+///     .await
+///     .unwrap();
+///
+/// let dead_letters = dead_letters.lock().unwrap();
+/// assert_eq!(
+///     Some(&"unknown order".into()),
+///     dead_letters[0].get_extension(DEADLETTER_REASON)
+/// );
+/// # });
+/// ```
+#[derive(Debug, Clone)]
+pub struct DeadLetterPolicy<S> {
+    destination: S,
+    consumer: Option<String>,
+}
+
+impl<S: EventSender> DeadLetterPolicy<S> {
+    /// Create a new [`DeadLetterPolicy`] forwarding the events to `destination`
+    pub fn new(destination: S) -> Self {
+        DeadLetterPolicy {
+            destination,
+            consumer: None,
+        }
+    }
+
+    /// Set the consumer name written to the [`DEADLETTER_CONSUMER`] extension
+    pub fn consumer(mut self, consumer: impl Into<String>) -> Self {
+        self.consumer = Some(consumer.into());
+        self
+    }
+
+    /// Write the failure `reason` and the consumer name to the extensions of `event`
+    pub fn annotate(&self, event: &mut Event, reason: &impl Display) {
+        event.set_extension(DEADLETTER_REASON, reason.to_string());
+        if let Some(consumer) = &self.consumer {
+            event.set_extension(DEADLETTER_CONSUMER, consumer.as_str());
+        }
+    }
+
+    /// Annotate `event` with the failure `reason` and send it to the dead letter destination
+    pub async fn forward(&self, mut event: Event, reason: &impl Display) -> Result<()> {
+        self.annotate(&mut event, reason);
+        self.destination.send(event).await
+    }
+
+    /// Process `event` with `handler`, forwarding it to the dead letter destination if the
+    /// handler fails. Fails only if the event can't be forwarded.
+    pub async fn handle<H, Fut, E>(&self, event: Event, handler: H) -> Result<()>
+    where
+        H: FnOnce(Event) -> Fut,
+        Fut: Future<Output = std::result::Result<(), E>>,
+        E: Display,
+    {
+        match handler(event.clone()).await {
+            Ok(()) => Ok(()),
+            Err(reason) => self.forward(event, &reason).await,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::message::Error;
+    use crate::{AttributesReader, EventBuilder, EventBuilderV10};
+    use std::sync::Mutex;
+
+    fn event() -> Event {
+        EventBuilderV10::new()
+            .id("0001")
+            .source("http://localhost/")
+            .ty("example.test")
+            .build()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn forward_failed_events() {
+        let dead_letters = Mutex::new(Vec::new());
+        let policy = DeadLetterPolicy::new(|event: Event| {
+            dead_letters.lock().unwrap().push(event);
+            async { Ok(()) }
+        })
+        .consumer("test");
+
+        policy
+            .handle(event(), |_| async { Ok::<(), String>(()) })
+            .await
+            .unwrap();
+        assert!(dead_letters.lock().unwrap().is_empty());
+
+        policy
+            .handle(event(), |_| async { Err(Error::WrongEncoding {}) })
+            .await
+            .unwrap();
+        let mut expected = event();
+        expected.set_extension(DEADLETTER_REASON, "Wrong encoding");
+        expected.set_extension(DEADLETTER_CONSUMER, "test");
+        assert_eq!(vec![expected], *dead_letters.lock().unwrap());
+    }
+
+    #[tokio::test]
+    async fn destination_error() {
+        let policy = DeadLetterPolicy::new(|_: Event| async {
+            Err(Error::Other {
+                source: "unavailable".into(),
+            })
+        });
+
+        let result = policy.handle(event(), |_| async { Err("failed") }).await;
+        assert!(matches!(result, Err(Error::Other { .. })));
+
+        let mut annotated = event();
+        policy.annotate(&mut annotated, &"failed");
+        assert_eq!(
+            Some(&"failed".into()),
+            annotated.get_extension(DEADLETTER_REASON)
+        );
+        assert_eq!(None, annotated.get_extension(DEADLETTER_CONSUMER));
+    }
+}
@@ -0,0 +1,105 @@
+use crate::Event;
+use std::ops::Deref;
+use std::time::Duration;
+
+/// Outcome of the processing of a [`Delivery`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    /// The event was processed, and must not be delivered again
+    Ack,
+    /// The event couldn't be processed, and should be delivered again
+    Nack,
+    /// The event couldn't be processed, and should be delivered again after the delay
+    RetryAfter(Duration),
+}
+
+/// Settles a [`Delivery`] on the transport it was received from, e.g. committing the offset of a
+/// Kafka message or replying to an HTTP request.
+pub trait Acknowledger {
+    /// Value returned when the delivery is settled
+    type Output;
+
+    /// Settle the delivery with `outcome`
+    fn settle(self, outcome: Outcome) -> Self::Output;
+}
+
+/// [`Event`] received by an at-least-once consumer, which must be acknowledged after it's
+/// processed, with [`Delivery::ack`], [`Delivery::nack`] or [`Delivery::retry_after`].
+///
+/// It dereferences to the received [`Event`].
+///
+/// ```
+/// use cloudevents::message::{Acknowledger, Delivery, Outcome};
+/// use cloudevents::{AttributesReader, EventBuilder, EventBuilderV10};
+///
+/// /// Acknowledger returning the outcome
+/// struct Recorder;
+///
+/// impl Acknowledger for Recorder {
+///     type Output = Outcome;
+///
+///     fn settle(self, outcome: Outcome) -> Outcome {
+///         outcome
+///     }
+/// }
+///
+/// let event = EventBuilderV10::new()
+///     .id("0001")
+///     .source("http://localhost/")
+///     .ty("example.demo")
+///     .build()
+///     .unwrap();
+///
+/// let delivery = Delivery::new(event, Recorder);
+/// assert_eq!("0001", delivery.get_id());
+/// assert_eq!(Outcome::Ack, delivery.ack());
+/// ```
+#[derive(Debug, Clone)]
+pub struct Delivery<A> {
+    event: Event,
+    acknowledger: A,
+}
+
+impl<A: Acknowledger> Delivery<A> {
+    /// Create a new [`Delivery`] of `event`, settled with `acknowledger`
+    pub fn new(event: Event, acknowledger: A) -> Self {
+        Delivery {
+            event,
+            acknowledger,
+        }
+    }
+
+    /// Get the delivered [`Event`]
+    pub fn event(&self) -> &Event {
+        &self.event
+    }
+
+    /// Split the delivery in the event and its [`Acknowledger`], to settle it after the event
+    /// is consumed
+    pub fn into_parts(self) -> (Event, A) {
+        (self.event, self.acknowledger)
+    }
+
+    /// Acknowledge the event was processed
+    pub fn ack(self) -> A::Output {
+        self.acknowledger.settle(Outcome::Ack)
+    }
+
+    /// Reject the event, asking for it to be delivered again
+    pub fn nack(self) -> A::Output {
+        self.acknowledger.settle(Outcome::Nack)
+    }
+
+    /// Reject the event, asking for it to be delivered again after `delay`
+    pub fn retry_after(self, delay: Duration) -> A::Output {
+        self.acknowledger.settle(Outcome::RetryAfter(delay))
+    }
+}
+
+impl<A> Deref for Delivery<A> {
+    type Target = Event;
+
+    fn deref(&self) -> &Event {
+        &self.event
+    }
+}
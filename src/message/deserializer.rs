@@ -1,5 +1,14 @@
-use super::{BinarySerializer, Encoding, Result, StructuredSerializer};
+use super::{
+    BatchSerializer, BinarySerializer, Encoding, Error, ParseDiagnostic, ParseMode,
+    ParsingBinarySerializer, ParsingStructuredSerializer, Result, StructuredSerializer,
+};
+use crate::event::{
+    Batch, EventBatchSerializer, EventBinarySerializer, EventStructuredSerializer, SpecVersion,
+};
 use crate::Event;
+use serde::Deserialize;
+use std::borrow::Cow;
+use std::convert::TryFrom;
 
 /// Deserializer trait for a Message that can be encoded as structured mode
 pub trait StructuredDeserializer
@@ -14,7 +23,7 @@ where
 
     /// Convert this Message to [`Event`]
     fn into_event(self) -> Result<Event> {
-        self.deserialize_structured(Event::default())
+        self.deserialize_structured(EventStructuredSerializer {})
     }
 }
 
@@ -28,7 +37,21 @@ where
 
     /// Convert this Message to [`Event`]
     fn into_event(self) -> Result<Event> {
-        self.deserialize_binary(Event::default())
+        self.deserialize_binary(EventBinarySerializer::new())
+    }
+}
+
+/// Deserializer trait for a Message that can be encoded as batched mode
+pub trait BatchDeserializer
+where
+    Self: Sized,
+{
+    /// Deserialize the message to [`BatchSerializer`]
+    fn deserialize_batch<R: Sized, V: BatchSerializer<R>>(self, serializer: V) -> Result<R>;
+
+    /// Convert this Message to [`Batch`]
+    fn into_batch(self) -> Result<Batch> {
+        self.deserialize_batch(EventBatchSerializer {})
     }
 }
 
@@ -40,9 +63,52 @@ where
     /// Get this message [`Encoding`]
     fn encoding(&self) -> Encoding;
 
+    /// Get this message [`SpecVersion`], read from the `specversion` attribute in binary mode
+    /// or with [`structured_spec_version`] in structured mode, before deserializing the rest of it.
+    ///
+    /// The default implementation fails with [`Error::WrongEncoding`], protocol bindings
+    /// should override it.
+    fn spec_version(&self) -> Result<SpecVersion> {
+        Err(Error::WrongEncoding {})
+    }
+
     /// Convert this Message to [`Event`]
     fn into_event(self) -> Result<Event> {
-        self.deserialize_to(Event::default())
+        let encoding = self.encoding();
+        let result = match encoding {
+            Encoding::BINARY => BinaryDeserializer::into_event(self),
+            Encoding::STRUCTURED => StructuredDeserializer::into_event(self),
+            Encoding::BATCH => Err(Error::UnsupportedContentMode {
+                content_mode: Encoding::BATCH,
+            }),
+            _ => Err(Error::WrongEncoding {}),
+        };
+        #[cfg(feature = "tracing")]
+        if let Err(error) = &result {
+            crate::logging::deserialization_failed(encoding, error);
+        }
+        result
+    }
+
+    /// Convert this Message to [`Event`] checking it with the provided [`ParseMode`],
+    /// returning the [`ParseDiagnostic`]s collected in lenient mode
+    fn into_event_with_mode(self, mode: ParseMode) -> Result<(Event, Vec<ParseDiagnostic>)> {
+        let encoding = self.encoding();
+        let result = match encoding {
+            Encoding::BINARY => self.deserialize_binary(ParsingBinarySerializer::new(mode)),
+            Encoding::STRUCTURED => {
+                self.deserialize_structured(ParsingStructuredSerializer::new(mode))
+            }
+            Encoding::BATCH => Err(Error::UnsupportedContentMode {
+                content_mode: Encoding::BATCH,
+            }),
+            _ => Err(Error::WrongEncoding {}),
+        };
+        #[cfg(feature = "tracing")]
+        if let Err(error) = &result {
+            crate::logging::deserialization_failed(encoding, error);
+        }
+        result
     }
 
     /// Deserialize the message to [`BinarySerializer`]
@@ -51,7 +117,7 @@ where
             return self.deserialize_binary(serializer);
         }
 
-        return MessageDeserializer::into_event(self)?.deserialize_binary(serializer);
+        MessageDeserializer::into_event(self)?.deserialize_binary(serializer)
     }
 
     /// Deserialize the message to [`StructuredSerializer`]
@@ -63,7 +129,7 @@ where
             return self.deserialize_structured(serializer);
         }
 
-        return MessageDeserializer::into_event(self)?.deserialize_structured(serializer);
+        MessageDeserializer::into_event(self)?.deserialize_structured(serializer)
     }
 
     /// Deserialize the message to a serializer, depending on the message encoding
@@ -79,3 +145,39 @@ where
         }
     }
 }
+
+/// Read the `specversion` of a JSON structured mode message, without building the whole [`Event`]
+pub fn structured_spec_version(bytes: &[u8]) -> Result<SpecVersion> {
+    #[derive(Deserialize)]
+    struct SpecVersionField<'a> {
+        #[serde(borrow)]
+        specversion: Cow<'a, str>,
+    }
+
+    let field: SpecVersionField = serde_json::from_slice(bytes)?;
+    Ok(SpecVersion::try_from(field.specversion.as_ref())?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_structured_spec_version() {
+        let v03 = json!({"specversion": "0.3", "id": "0001", "data": {"specversion": "1.0"}});
+        assert_eq!(
+            SpecVersion::V03,
+            structured_spec_version(&serde_json::to_vec(&v03).unwrap()).unwrap()
+        );
+
+        let v10 = json!({"id": "0001", "specversion": "1.0"});
+        assert_eq!(
+            SpecVersion::V10,
+            structured_spec_version(&serde_json::to_vec(&v10).unwrap()).unwrap()
+        );
+
+        assert!(structured_spec_version(br#"{"specversion": "2.0"}"#).is_err());
+        assert!(structured_spec_version(br#"{"id": "0001"}"#).is_err());
+    }
+}
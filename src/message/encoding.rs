@@ -5,5 +5,8 @@ use std::fmt::Debug;
 pub enum Encoding {
     STRUCTURED,
     BINARY,
+    /// Several events in a single message, encoded with the
+    /// [JSON batch format](https://github.com/cloudevents/spec/blob/v1.0/json-format.md#4-json-batch-format)
+    BATCH,
     UNKNOWN,
 }
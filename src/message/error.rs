@@ -1,10 +1,36 @@
+use super::Encoding;
 use snafu::Snafu;
 
-/// Represents an error during serialization/deserialization process
+/// Represents an error during serialization/deserialization process.
+///
+/// Server side protocol bindings can use [`Error::status_code`] to reply to a request which
+/// couldn't be read as an event.
 #[derive(Debug, Snafu)]
+#[non_exhaustive]
 pub enum Error {
     #[snafu(display("Wrong encoding"))]
     WrongEncoding {},
+    #[snafu(display("Unsupported content mode {:?}", content_mode))]
+    UnsupportedContentMode { content_mode: Encoding },
+    #[snafu(display("Missing required attribute {}", attribute_name))]
+    MissingRequiredAttribute { attribute_name: &'static str },
+    #[snafu(display(
+        "Attribute '{}' is not a valid {}: {}",
+        attribute_name,
+        expected,
+        source
+    ))]
+    WrongAttributeType {
+        attribute_name: &'static str,
+        expected: &'static str,
+        source: Box<Error>,
+    },
+    #[snafu(display("{}", diagnostic))]
+    StrictModeViolation { diagnostic: super::ParseDiagnostic },
+    #[snafu(display("Error while decoding the payload: {}", source))]
+    PayloadDecodeError {
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
     #[snafu(display("{}", source))]
     #[snafu(context(false))]
     InvalidSpecVersion {
@@ -12,12 +38,27 @@ pub enum Error {
     },
     #[snafu(display("Unrecognized attribute name: {}", name))]
     UnrecognizedAttributeName { name: String },
+    #[snafu(display("Error while building the final event: {}", source))]
+    EventBuilderError {
+        source: crate::event::EventBuilderError,
+    },
     #[snafu(display("Error while parsing a time string: {}", source))]
     #[snafu(context(false))]
     ParseTimeError { source: chrono::ParseError },
     #[snafu(display("Error while parsing a url: {}", source))]
     #[snafu(context(false))]
     ParseUrlError { source: url::ParseError },
+    #[snafu(display("Error while parsing a uri reference: {}", source))]
+    #[snafu(context(false))]
+    ParseUriReferenceError {
+        source: crate::event::InvalidUriReference,
+    },
+    #[snafu(display("Error while parsing a boolean: {}", source))]
+    #[snafu(context(false))]
+    ParseBoolError { source: std::str::ParseBoolError },
+    #[snafu(display("Error while parsing an integer: {}", source))]
+    #[snafu(context(false))]
+    ParseIntError { source: std::num::ParseIntError },
     #[snafu(display("Error while decoding base64: {}", source))]
     #[snafu(context(false))]
     Base64DecodingError { source: base64::DecodeError },
@@ -27,9 +68,153 @@ pub enum Error {
     #[snafu(display("IO Error: {}", source))]
     #[snafu(context(false))]
     IOError { source: std::io::Error },
+    #[snafu(display("Event of {} bytes exceeds the limit of {} bytes", size, limit))]
+    EventTooLarge { size: usize, limit: usize },
+    #[snafu(display("Giving up after {} attempts: {}", attempts, source))]
+    RetriesExhausted { attempts: u32, source: Box<Error> },
     #[snafu(display("Other error: {}", source))]
-    Other { source: Box<dyn std::error::Error> },
+    Other {
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+}
+
+impl Error {
+    /// Get the HTTP status code a server should reply with when a request fails with this error:
+    /// `415 Unsupported Media Type` for an unsupported content mode, `413 Payload Too Large` for
+    /// an oversized event, `500 Internal Server Error` for I/O errors and `400 Bad Request` for any
+    /// other malformed message.
+    pub fn status_code(&self) -> u16 {
+        match self {
+            Error::UnsupportedContentMode { .. } => 415,
+            Error::EventTooLarge { .. } => 413,
+            Error::IOError { .. } => 500,
+            _ => 400,
+        }
+    }
+
+    /// Returns `true` if sending the event again may succeed, for the transport and I/O errors,
+    /// and `false` for the errors of a malformed event or message.
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, Error::IOError { .. } | Error::Other { .. })
+    }
+
+    /// Get the kind of the error, the snake case name of its variant, e.g. `wrong_encoding`,
+    /// to label the metrics and logs with a low cardinality value
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Error::WrongEncoding {} => "wrong_encoding",
+            Error::UnsupportedContentMode { .. } => "unsupported_content_mode",
+            Error::MissingRequiredAttribute { .. } => "missing_required_attribute",
+            Error::WrongAttributeType { .. } => "wrong_attribute_type",
+            Error::StrictModeViolation { .. } => "strict_mode_violation",
+            Error::PayloadDecodeError { .. } => "payload_decode_error",
+            Error::InvalidSpecVersion { .. } => "invalid_spec_version",
+            Error::UnrecognizedAttributeName { .. } => "unrecognized_attribute_name",
+            Error::EventBuilderError { .. } => "event_builder_error",
+            Error::ParseTimeError { .. } => "parse_time_error",
+            Error::ParseUrlError { .. } => "parse_url_error",
+            Error::ParseUriReferenceError { .. } => "parse_uri_reference_error",
+            Error::ParseBoolError { .. } => "parse_bool_error",
+            Error::ParseIntError { .. } => "parse_int_error",
+            Error::Base64DecodingError { .. } => "base64_decoding_error",
+            Error::SerdeJsonError { .. } => "serde_json_error",
+            Error::IOError { .. } => "io_error",
+            Error::EventTooLarge { .. } => "event_too_large",
+            Error::RetriesExhausted { .. } => "retries_exhausted",
+            Error::Other { .. } => "other",
+        }
+    }
+
+    /// Wrap the error returned while converting the value of `attribute_name`
+    /// to the `expected` type
+    pub(crate) fn wrong_attribute_type(
+        attribute_name: &'static str,
+        expected: &'static str,
+    ) -> impl FnOnce(Error) -> Error {
+        move |source| Error::WrongAttributeType {
+            attribute_name,
+            expected,
+            source: Box::new(source),
+        }
+    }
+}
+
+impl From<crate::event::EventBuilderError> for Error {
+    fn from(source: crate::event::EventBuilderError) -> Self {
+        match source {
+            crate::event::EventBuilderError::MissingRequiredAttribute { attribute_name } => {
+                Error::MissingRequiredAttribute { attribute_name }
+            }
+            source => Error::EventBuilderError { source },
+        }
+    }
 }
 
 /// Result type alias for return values during serialization/deserialization process
 pub type Result<T> = std::result::Result<T, Error>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn status_code() {
+        assert_eq!(400, Error::WrongEncoding {}.status_code());
+        assert_eq!(
+            400,
+            Error::MissingRequiredAttribute {
+                attribute_name: "id"
+            }
+            .status_code()
+        );
+        assert_eq!(
+            415,
+            Error::UnsupportedContentMode {
+                content_mode: Encoding::BATCH
+            }
+            .status_code()
+        );
+        assert_eq!(
+            500,
+            Error::from(std::io::Error::from(std::io::ErrorKind::Other)).status_code()
+        );
+    }
+
+    #[test]
+    fn retryable() {
+        assert!(Error::from(std::io::Error::from(std::io::ErrorKind::Other)).is_retryable());
+        assert!(Error::Other {
+            source: "timeout".into()
+        }
+        .is_retryable());
+        assert!(!Error::WrongEncoding {}.is_retryable());
+    }
+
+    #[test]
+    fn kind() {
+        assert_eq!("wrong_encoding", Error::WrongEncoding {}.kind());
+        assert_eq!(
+            "io_error",
+            Error::from(std::io::Error::from(std::io::ErrorKind::Other)).kind()
+        );
+        assert_eq!(
+            "other",
+            Error::Other {
+                source: "timeout".into()
+            }
+            .kind()
+        );
+    }
+
+    #[test]
+    fn builder_error() {
+        assert!(matches!(
+            Error::from(crate::event::EventBuilderError::MissingRequiredAttribute {
+                attribute_name: "id"
+            }),
+            Error::MissingRequiredAttribute {
+                attribute_name: "id"
+            }
+        ));
+    }
+}
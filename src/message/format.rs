@@ -0,0 +1,241 @@
+use super::Result;
+use crate::event::{AttributesReader, SpecVersion};
+use crate::Event;
+use std::sync::{Arc, RwLock};
+
+/// Content type of the JSON event format
+pub const CLOUDEVENTS_JSON_CONTENT_TYPE: &str = "application/cloudevents+json";
+
+/// Structured mode event format, encoding an [`Event`] to the payload of a message of
+/// [`content_type`](EventFormat::content_type) and decoding it back.
+///
+/// The formats registered in the installed [`EventFormats`] are picked up by the HTTP, Kafka,
+/// AMQP and MQTT bindings: a message with the content type of a registered format is
+/// deserialized as a structured mode message, decoding its payload with the format.
+pub trait EventFormat: Send + Sync {
+    /// Media type of the format, e.g. `application/cloudevents+json`
+    fn content_type(&self) -> &str;
+
+    /// Encode `event` to the payload of a structured mode message
+    fn encode(&self, event: &Event) -> Result<Vec<u8>>;
+
+    /// Decode the payload of a structured mode message
+    fn decode(&self, bytes: &[u8]) -> Result<Event>;
+}
+
+/// The JSON event format, registered in every [`EventFormats`]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JsonFormat;
+
+impl EventFormat for JsonFormat {
+    fn content_type(&self) -> &str {
+        CLOUDEVENTS_JSON_CONTENT_TYPE
+    }
+
+    fn encode(&self, event: &Event) -> Result<Vec<u8>> {
+        Ok(serde_json::to_vec(event)?)
+    }
+
+    fn decode(&self, bytes: &[u8]) -> Result<Event> {
+        crate::event::parse_json(bytes.to_vec())
+    }
+}
+
+/// Registry of [`EventFormat`]s keyed by content type.
+///
+/// The registry installed with [`set_event_formats`] is used by all the bindings, and the HTTP
+/// deserializer can be given its own registry with `HeaderMapDeserializer::with_formats`.
+///
+/// ```
+/// use cloudevents::message::{EventFormat, EventFormats, JsonFormat, Result};
+/// use cloudevents::Event;
+///
+/// /// JSON event format with a vendor content type
+/// struct VendorFormat;
+///
+/// impl EventFormat for VendorFormat {
+///     fn content_type(&self) -> &str {
+///         "application/vnd.example.cloudevents+json"
+///     }
+///
+///     fn encode(&self, event: &Event) -> Result<Vec<u8>> {
+///         JsonFormat.encode(event)
+///     }
+///
+///     fn decode(&self, bytes: &[u8]) -> Result<Event> {
+///         JsonFormat.decode(bytes)
+///     }
+/// }
+///
+/// let formats = EventFormats::new().with(VendorFormat);
+/// assert!(formats
+///     .get("application/vnd.example.cloudevents+json; charset=utf-8")
+///     .is_some());
+/// assert!(formats.get("application/cloudevents+json").is_some());
+/// assert!(formats.get("application/json").is_none());
+/// ```
+#[derive(Clone)]
+pub struct EventFormats {
+    formats: Vec<Arc<dyn EventFormat>>,
+}
+
+impl EventFormats {
+    /// Create a new registry with the [`JsonFormat`]
+    pub fn new() -> Self {
+        EventFormats {
+            formats: vec![Arc::new(JsonFormat)],
+        }
+    }
+
+    /// Register `format`, replacing the format previously registered for its content type
+    pub fn with(mut self, format: impl EventFormat + 'static) -> Self {
+        self.formats
+            .retain(|f| !f.content_type().eq_ignore_ascii_case(format.content_type()));
+        self.formats.push(Arc::new(format));
+        self
+    }
+
+    /// Get the format registered for `content_type`, ignoring its parameters and case
+    pub fn get(&self, content_type: &str) -> Option<&dyn EventFormat> {
+        let media_type = media_type(content_type);
+        self.formats
+            .iter()
+            .find(|f| f.content_type().eq_ignore_ascii_case(media_type))
+            .map(|f| f.as_ref())
+    }
+
+    /// Returns `true` if a format is registered for `content_type`
+    pub fn contains(&self, content_type: &str) -> bool {
+        self.get(content_type).is_some()
+    }
+}
+
+// Only called by the bindings reading structured mode messages
+#[cfg_attr(
+    not(any(
+        feature = "http",
+        feature = "rdkafka",
+        feature = "amqp",
+        feature = "mqtt"
+    )),
+    allow(dead_code)
+)]
+impl EventFormats {
+    /// Normalize the payload of a structured mode message of `content_type` to the JSON event
+    /// format read by the [`StructuredSerializer`](super::StructuredSerializer)s
+    pub(crate) fn to_json(&self, content_type: Option<&str>, bytes: Vec<u8>) -> Result<Vec<u8>> {
+        match self.non_json(content_type) {
+            Some(format) => Ok(serde_json::to_vec(&format.decode(&bytes)?)?),
+            None => Ok(bytes),
+        }
+    }
+
+    /// Read the spec version of a structured mode message of `content_type`
+    #[cfg_attr(
+        not(any(feature = "http", feature = "rdkafka", feature = "mqtt")),
+        allow(dead_code)
+    )]
+    pub(crate) fn spec_version(
+        &self,
+        content_type: Option<&str>,
+        bytes: &[u8],
+    ) -> Result<SpecVersion> {
+        match self.non_json(content_type) {
+            Some(format) => Ok(format.decode(bytes)?.get_specversion()),
+            None => super::structured_spec_version(bytes),
+        }
+    }
+
+    /// Get the format registered for `content_type`, if it's not the JSON event format
+    fn non_json(&self, content_type: Option<&str>) -> Option<&dyn EventFormat> {
+        content_type
+            .filter(|ct| !media_type(ct).eq_ignore_ascii_case(CLOUDEVENTS_JSON_CONTENT_TYPE))
+            .and_then(|ct| self.get(ct))
+    }
+}
+
+impl Default for EventFormats {
+    fn default() -> Self {
+        EventFormats::new()
+    }
+}
+
+impl std::fmt::Debug for EventFormats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list()
+            .entries(self.formats.iter().map(|f| f.content_type()))
+            .finish()
+    }
+}
+
+fn media_type(content_type: &str) -> &str {
+    content_type.split(';').next().unwrap_or_default().trim()
+}
+
+static FORMATS: RwLock<Option<Arc<EventFormats>>> = RwLock::new(None);
+
+/// Install the [`EventFormats`] used by the bindings, replacing the previous one
+pub fn set_event_formats(formats: EventFormats) {
+    if let Ok(mut current) = FORMATS.write() {
+        *current = Some(Arc::new(formats));
+    }
+}
+
+/// Remove the installed [`EventFormats`], so the bindings only read the JSON event format
+pub fn clear_event_formats() {
+    if let Ok(mut current) = FORMATS.write() {
+        *current = None;
+    }
+}
+
+/// Get the installed [`EventFormats`], or a registry of the [`JsonFormat`] if none is installed
+pub fn event_formats() -> Arc<EventFormats> {
+    FORMATS
+        .read()
+        .ok()
+        .and_then(|formats| formats.clone())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EventBuilder, EventBuilderV10};
+
+    /// JSON event format with a vendor content type
+    struct VendorFormat;
+
+    impl EventFormat for VendorFormat {
+        fn content_type(&self) -> &str {
+            "application/vnd.example+json"
+        }
+
+        fn encode(&self, event: &Event) -> Result<Vec<u8>> {
+            JsonFormat.encode(event)
+        }
+
+        fn decode(&self, bytes: &[u8]) -> Result<Event> {
+            JsonFormat.decode(bytes)
+        }
+    }
+
+    #[test]
+    fn registry() {
+        let formats = EventFormats::new().with(VendorFormat).with(VendorFormat);
+        assert_eq!(2, formats.formats.len());
+        assert!(formats.contains("Application/Vnd.Example+JSON ; charset=utf-8"));
+        assert!(!formats.contains("application/json"));
+
+        let event = EventBuilderV10::new()
+            .id("0001")
+            .source("http://localhost/")
+            .ty("example.test")
+            .build()
+            .unwrap();
+        let bytes = VendorFormat.encode(&event).unwrap();
+        let json = formats
+            .to_json(Some("application/vnd.example+json"), bytes)
+            .unwrap();
+        assert_eq!(event, JsonFormat.decode(&json).unwrap());
+    }
+}
@@ -0,0 +1,342 @@
+use super::Result;
+use crate::{Batch, Event};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, RwLock};
+
+/// Future returned by the hooks of [`AsyncEventInterceptor`]
+pub type InterceptorFuture<'a> = Pin<Box<dyn Future<Output = Result<()>> + Send + 'a>>;
+
+/// Hooks called on each event before it's sent and after it's received, e.g. to stamp an
+/// extension, validate or audit the events.
+///
+/// An error rejects the event: sending fails with it, and the HTTP extractors reply with its
+/// [status code](super::Error::status_code). Both hooks accept the event by default.
+pub trait EventInterceptor: Send + Sync {
+    /// Called before `event` is serialized to be sent
+    fn on_send(&self, event: &mut Event) -> Result<()> {
+        let _ = event;
+        Ok(())
+    }
+
+    /// Called after `event` is received and parsed
+    fn on_receive(&self, event: &mut Event) -> Result<()> {
+        let _ = event;
+        Ok(())
+    }
+}
+
+/// Asynchronous variant of [`EventInterceptor`], e.g. to look up the extensions to stamp in a
+/// remote service
+pub trait AsyncEventInterceptor: Send + Sync {
+    /// Called before `event` is serialized to be sent
+    fn on_send<'a>(&'a self, event: &'a mut Event) -> InterceptorFuture<'a> {
+        let _ = event;
+        Box::pin(std::future::ready(Ok(())))
+    }
+
+    /// Called after `event` is received and parsed
+    fn on_receive<'a>(&'a self, event: &'a mut Event) -> InterceptorFuture<'a> {
+        let _ = event;
+        Box::pin(std::future::ready(Ok(())))
+    }
+}
+
+enum Stage {
+    Sync(Box<dyn EventInterceptor>),
+    Async(Box<dyn AsyncEventInterceptor>),
+}
+
+/// Chain of [`EventInterceptor`]s and [`AsyncEventInterceptor`]s.
+///
+/// The `on_send` hooks are called in the order the interceptors are added, and the `on_receive`
+/// hooks in the reverse order, so the first interceptor is the closest to the application. The
+/// chain stops at the first interceptor rejecting the event.
+///
+/// A [`Pipeline`] is an [`AsyncEventInterceptor`] itself, so pipelines can be composed. The
+/// pipeline installed with [`set_pipeline`] is honored by the bindings performing I/O:
+///
+/// * the `on_send` hooks by the reqwest and Kafka [`EventSender`](super::EventSender)s, and the
+///   sinks built on them
+/// * the `on_receive` hooks by the reqwest `ResponseExt`, the Kafka event stream, and the
+///   extractors of the actix, axum, poem, rocket, salvo, tide and warp servers, the hyper
+///   service and the tower layer
+///
+/// The responders and replies of the servers, which are synchronous, don't call the `on_send`
+/// hooks. The amqp, mqtt and tonic bindings only convert the messages, and the streams and
+/// sinks of the sse and websocket bindings only adapt the transport's own, so none of them call
+/// the pipeline either: call [`Pipeline::send`] and [`Pipeline::receive`] around them.
+///
+/// ```
+/// use cloudevents::message::{EventInterceptor, Pipeline, Result};
+/// use cloudevents::{AttributesReader, AttributesWriter, Event, EventBuilder, EventBuilderV10};
+///
+/// struct Tenant(&'static str);
+///
+/// impl EventInterceptor for Tenant {
+///     fn on_send(&self, event: &mut Event) -> Result<()> {
+///         event.set_extension("tenant", self.0);
+///         Ok(())
+///     }
+/// }
+///
+/// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+/// let pipeline = Pipeline::new().with(Tenant("acme"));
+///
+/// let mut event = EventBuilderV10::new()
+///     .id("0001")
+///     .source("http://localhost/")
+///     .ty("example.demo")
+///     .build()
+///     .unwrap();
+/// pipeline.send(&mut event).await.unwrap();
+/// assert_eq!(Some(&"acme".into()), event.get_extension("tenant"));
+/// # });
+/// ```
+#[derive(Default)]
+pub struct Pipeline {
+    stages: Vec<Stage>,
+}
+
+impl Pipeline {
+    /// Create a new empty [`Pipeline`]
+    pub fn new() -> Self {
+        Pipeline::default()
+    }
+
+    /// Append `interceptor` to the pipeline
+    pub fn with(mut self, interceptor: impl EventInterceptor + 'static) -> Self {
+        self.stages.push(Stage::Sync(Box::new(interceptor)));
+        self
+    }
+
+    /// Append the asynchronous `interceptor` to the pipeline
+    pub fn with_async(mut self, interceptor: impl AsyncEventInterceptor + 'static) -> Self {
+        self.stages.push(Stage::Async(Box::new(interceptor)));
+        self
+    }
+
+    /// Returns `true` if the pipeline has no interceptors
+    pub fn is_empty(&self) -> bool {
+        self.stages.is_empty()
+    }
+
+    /// Call the `on_send` hooks on `event`
+    pub async fn send(&self, event: &mut Event) -> Result<()> {
+        for stage in &self.stages {
+            match stage {
+                Stage::Sync(interceptor) => interceptor.on_send(event)?,
+                Stage::Async(interceptor) => interceptor.on_send(event).await?,
+            }
+        }
+        Ok(())
+    }
+
+    /// Call the `on_receive` hooks on `event`
+    pub async fn receive(&self, event: &mut Event) -> Result<()> {
+        for stage in self.stages.iter().rev() {
+            match stage {
+                Stage::Sync(interceptor) => interceptor.on_receive(event)?,
+                Stage::Async(interceptor) => interceptor.on_receive(event).await?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl AsyncEventInterceptor for Pipeline {
+    fn on_send<'a>(&'a self, event: &'a mut Event) -> InterceptorFuture<'a> {
+        Box::pin(self.send(event))
+    }
+
+    fn on_receive<'a>(&'a self, event: &'a mut Event) -> InterceptorFuture<'a> {
+        Box::pin(self.receive(event))
+    }
+}
+
+impl std::fmt::Debug for Pipeline {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Pipeline")
+            .field("stages", &self.stages.len())
+            .finish()
+    }
+}
+
+static PIPELINE: RwLock<Option<Arc<Pipeline>>> = RwLock::new(None);
+
+/// Install the [`Pipeline`] honored by the bindings, replacing the previous one
+pub fn set_pipeline(pipeline: Pipeline) {
+    if let Ok(mut current) = PIPELINE.write() {
+        *current = Some(Arc::new(pipeline));
+    }
+}
+
+/// Remove the installed [`Pipeline`]
+pub fn clear_pipeline() {
+    if let Ok(mut current) = PIPELINE.write() {
+        *current = None;
+    }
+}
+
+fn installed_pipeline() -> Option<Arc<Pipeline>> {
+    PIPELINE.read().ok()?.clone()
+}
+
+/// Call the `on_send` hooks of the installed pipeline on `event`
+// Only called by the bindings performing I/O
+#[cfg_attr(not(any(feature = "reqwest", feature = "rdkafka")), allow(dead_code))]
+pub(crate) async fn intercept_send(event: &mut Event) -> Result<()> {
+    match installed_pipeline() {
+        Some(pipeline) => pipeline.send(event).await,
+        None => Ok(()),
+    }
+}
+
+/// Received values intercepted by the `on_receive` hooks of the installed pipeline
+#[cfg_attr(not(event_receiver), allow(dead_code))]
+pub(crate) trait Received {
+    /// Call the `on_receive` hooks of the installed pipeline on each event received from
+    /// `destination` with the messaging `system`, in its receive span with the `opentelemetry`
+    /// feature
+    fn intercept_receive<'a>(
+        &'a mut self,
+        system: &'static str,
+        destination: Option<&'a str>,
+    ) -> InterceptorFuture<'a>;
+}
+
+/// Call the `on_receive` hooks of the installed pipeline on `event`, see
+/// [`Received::intercept_receive`]
+#[cfg_attr(not(event_receiver), allow(dead_code))]
+async fn receive(event: &mut Event, system: &'static str, destination: Option<&str>) -> Result<()> {
+    #[cfg(feature = "opentelemetry")]
+    let context = crate::binding::telemetry::receive_context(event, system, destination);
+    #[cfg(not(feature = "opentelemetry"))]
+    let _ = (system, destination);
+
+    let intercept = async {
+        match installed_pipeline() {
+            Some(pipeline) => pipeline.receive(event).await,
+            None => Ok(()),
+        }
+    };
+    #[cfg(feature = "opentelemetry")]
+    let intercept = crate::binding::telemetry::in_context(context, intercept);
+    intercept.await
+}
+
+impl Received for Event {
+    fn intercept_receive<'a>(
+        &'a mut self,
+        system: &'static str,
+        destination: Option<&'a str>,
+    ) -> InterceptorFuture<'a> {
+        Box::pin(receive(self, system, destination))
+    }
+}
+
+impl Received for Batch {
+    fn intercept_receive<'a>(
+        &'a mut self,
+        system: &'static str,
+        destination: Option<&'a str>,
+    ) -> InterceptorFuture<'a> {
+        Box::pin(async move {
+            for event in self.iter_mut() {
+                receive(event, system, destination).await?;
+            }
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::{AttributesReader, AttributesWriter, ExtensionValue};
+    use crate::message::Error;
+    use crate::{EventBuilder, EventBuilderV10};
+
+    /// Interceptor appending its name to the `trail` extension
+    struct Trail(&'static str);
+
+    impl Trail {
+        fn append(&self, event: &mut Event) {
+            let trail = match event.get_extension("trail") {
+                Some(ExtensionValue::String(trail)) => format!("{},{}", trail, self.0),
+                _ => self.0.to_string(),
+            };
+            event.set_extension("trail", trail);
+        }
+    }
+
+    impl EventInterceptor for Trail {
+        fn on_send(&self, event: &mut Event) -> Result<()> {
+            self.append(event);
+            Ok(())
+        }
+
+        fn on_receive(&self, event: &mut Event) -> Result<()> {
+            self.append(event);
+            Ok(())
+        }
+    }
+
+    struct AsyncTrail(Trail);
+
+    impl AsyncEventInterceptor for AsyncTrail {
+        fn on_send<'a>(&'a self, event: &'a mut Event) -> InterceptorFuture<'a> {
+            Box::pin(async move { self.0.on_send(event) })
+        }
+    }
+
+    /// Interceptor rejecting the received events
+    struct Reject;
+
+    impl EventInterceptor for Reject {
+        fn on_receive(&self, _: &mut Event) -> Result<()> {
+            Err(Error::Other {
+                source: "rejected".into(),
+            })
+        }
+    }
+
+    fn event() -> Event {
+        EventBuilderV10::new()
+            .id("0001")
+            .source("http://localhost/")
+            .ty("example.test")
+            .build()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn hooks_order() {
+        let pipeline = Pipeline::new()
+            .with(Trail("a"))
+            .with_async(AsyncTrail(Trail("b")))
+            .with_async(Pipeline::new().with(Trail("c")));
+
+        let mut e = event();
+        pipeline.send(&mut e).await.unwrap();
+        assert_eq!(Some(&"a,b,c".into()), e.get_extension("trail"));
+
+        // The asynchronous interceptor has the default on_receive
+        let mut e = event();
+        pipeline.receive(&mut e).await.unwrap();
+        assert_eq!(Some(&"c,a".into()), e.get_extension("trail"));
+    }
+
+    #[tokio::test]
+    async fn rejection_stops_the_chain() {
+        let pipeline = Pipeline::new().with(Trail("a")).with(Reject);
+
+        let mut e = event();
+        assert!(matches!(
+            pipeline.receive(&mut e).await,
+            Err(Error::Other { .. })
+        ));
+        assert!(e.get_extension("trail").is_none());
+        assert!(pipeline.send(&mut e).await.is_ok());
+    }
+}
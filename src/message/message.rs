@@ -0,0 +1,326 @@
+use super::{
+    BinaryDeserializer, BinarySerializer, Encoding, Error, MessageAttributeValue,
+    MessageDeserializer, Result, StructuredDeserializer, StructuredSerializer,
+};
+use crate::binding::transcode::JsonStructuredSerializer;
+use crate::event::SpecVersion;
+use crate::Event;
+use bytes::Bytes;
+
+/// A message read from a protocol binding, in binary or structured mode, holding its content
+/// without parsing it to an [`Event`].
+///
+/// It's built from any [`MessageDeserializer`] with [`Message::from_deserializer`], so a binding
+/// can defer the decision of the content mode, and a proxy can forward the messages to another
+/// binding in the mode they were received, without re-encoding them:
+///
+/// ```
+/// # #[cfg(feature = "http")]
+/// # {
+/// use cloudevents::binding::http::{event_to_binary, HeaderMapDeserializer, HeaderMapSerializer};
+/// use cloudevents::message::Message;
+/// use cloudevents::{EventBuilder, EventBuilderV10};
+///
+/// let event = EventBuilderV10::new()
+///     .id("0001")
+///     .source("http://localhost/")
+///     .ty("example.demo")
+///     .build()
+///     .unwrap();
+/// let (headers, body) = event_to_binary(event.clone()).unwrap();
+///
+/// let message = Message::from_deserializer(HeaderMapDeserializer::new(&headers, body)).unwrap();
+/// assert!(matches!(message, Message::Binary(_)));
+///
+/// let (forwarded, _) = message
+///     .clone()
+///     .into_binary(HeaderMapSerializer::default())
+///     .unwrap();
+/// assert_eq!(headers, forwarded);
+/// assert_eq!(event, message.into_event().unwrap());
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum Message {
+    /// A binary mode message
+    Binary(BinaryMessage),
+    /// A structured mode message in the JSON event format
+    Structured(StructuredMessage),
+}
+
+/// Content of a binary mode [`Message`]: the attributes, the extensions and the payload, as they
+/// were read from the protocol binding
+#[derive(Debug, Clone, PartialEq)]
+pub struct BinaryMessage {
+    spec_version: SpecVersion,
+    attributes: Vec<(String, MessageAttributeValue<'static>)>,
+    extensions: Vec<(String, MessageAttributeValue<'static>)>,
+    data: Option<Bytes>,
+}
+
+impl BinaryMessage {
+    /// The spec version of the message
+    pub fn spec_version(&self) -> SpecVersion {
+        self.spec_version.clone()
+    }
+
+    /// The attributes of the message, in the order they were read
+    pub fn attributes(&self) -> impl Iterator<Item = (&str, &MessageAttributeValue<'static>)> {
+        self.attributes
+            .iter()
+            .map(|(name, value)| (name.as_str(), value))
+    }
+
+    /// The extensions of the message, in the order they were read
+    pub fn extensions(&self) -> impl Iterator<Item = (&str, &MessageAttributeValue<'static>)> {
+        self.extensions
+            .iter()
+            .map(|(name, value)| (name.as_str(), value))
+    }
+
+    /// The payload of the message, if any
+    pub fn data(&self) -> Option<&Bytes> {
+        self.data.as_ref()
+    }
+}
+
+/// Content of a structured mode [`Message`]: the payload in the JSON event format
+#[derive(Debug, Clone, PartialEq)]
+pub struct StructuredMessage {
+    payload: Vec<u8>,
+}
+
+impl StructuredMessage {
+    /// The payload of the message in the JSON event format
+    pub fn payload(&self) -> &[u8] {
+        &self.payload
+    }
+}
+
+impl Message {
+    /// Read `message` in the content mode it's encoded with.
+    ///
+    /// Fails with [`Error::UnsupportedContentMode`] for a batched mode message.
+    pub fn from_deserializer(message: impl MessageDeserializer) -> Result<Message> {
+        match message.encoding() {
+            Encoding::BINARY => message.deserialize_binary(MessageCollector::default()),
+            Encoding::STRUCTURED => message.deserialize_structured(MessageCollector::default()),
+            Encoding::BATCH => Err(Error::UnsupportedContentMode {
+                content_mode: Encoding::BATCH,
+            }),
+            _ => Err(Error::WrongEncoding {}),
+        }
+    }
+
+    /// Write `event` to a binary mode message
+    pub fn from_event(event: Event) -> Result<Message> {
+        event.deserialize_binary(MessageCollector::default())
+    }
+
+    /// Parse the message to an [`Event`]
+    pub fn into_event(self) -> Result<Event> {
+        MessageDeserializer::into_event(self)
+    }
+
+    /// Write the message to `serializer` in binary mode, parsing it to an event if it's in
+    /// structured mode
+    pub fn into_binary<R, S: BinarySerializer<R>>(self, serializer: S) -> Result<R> {
+        self.deserialize_to_binary(serializer)
+    }
+
+    /// Write the message to `serializer` in structured mode, writing it in the JSON event format
+    /// if it's in binary mode
+    pub fn into_structured<R, S: StructuredSerializer<R>>(self, serializer: S) -> Result<R> {
+        match self {
+            Message::Structured(_) => self.deserialize_structured(serializer),
+            Message::Binary(_) => {
+                self.deserialize_binary(JsonStructuredSerializer::new(serializer))
+            }
+        }
+    }
+}
+
+impl From<BinaryMessage> for Message {
+    fn from(message: BinaryMessage) -> Self {
+        Message::Binary(message)
+    }
+}
+
+impl From<StructuredMessage> for Message {
+    fn from(message: StructuredMessage) -> Self {
+        Message::Structured(message)
+    }
+}
+
+impl BinaryDeserializer for Message {
+    fn deserialize_binary<R: Sized, V: BinarySerializer<R>>(self, mut serializer: V) -> Result<R> {
+        let message = match self {
+            Message::Binary(message) => message,
+            Message::Structured(_) => return Err(Error::WrongEncoding {}),
+        };
+        serializer = serializer.set_spec_version(message.spec_version)?;
+        for (name, value) in message.attributes {
+            serializer = serializer.set_attribute(&name, value)?;
+        }
+        for (name, value) in message.extensions {
+            serializer = serializer.set_extension(&name, value)?;
+        }
+        match message.data {
+            Some(data) => serializer.end_with_data(data),
+            None => serializer.end(),
+        }
+    }
+}
+
+impl StructuredDeserializer for Message {
+    fn deserialize_structured<R: Sized, V: StructuredSerializer<R>>(
+        self,
+        serializer: V,
+    ) -> Result<R> {
+        match self {
+            Message::Structured(message) => serializer.set_structured_event(message.payload),
+            Message::Binary(_) => Err(Error::WrongEncoding {}),
+        }
+    }
+}
+
+impl MessageDeserializer for Message {
+    fn encoding(&self) -> Encoding {
+        match self {
+            Message::Binary(_) => Encoding::BINARY,
+            Message::Structured(_) => Encoding::STRUCTURED,
+        }
+    }
+
+    fn spec_version(&self) -> Result<SpecVersion> {
+        match self {
+            Message::Binary(message) => Ok(message.spec_version()),
+            Message::Structured(message) => super::structured_spec_version(&message.payload),
+        }
+    }
+}
+
+/// Serializer collecting the content of a message into a [`Message`]
+#[derive(Default)]
+struct MessageCollector {
+    spec_version: Option<SpecVersion>,
+    attributes: Vec<(String, MessageAttributeValue<'static>)>,
+    extensions: Vec<(String, MessageAttributeValue<'static>)>,
+}
+
+impl MessageCollector {
+    fn end_with(self, data: Option<Bytes>) -> Result<Message> {
+        let spec_version = self.spec_version.ok_or(Error::MissingRequiredAttribute {
+            attribute_name: "specversion",
+        })?;
+        Ok(Message::Binary(BinaryMessage {
+            spec_version,
+            attributes: self.attributes,
+            extensions: self.extensions,
+            data,
+        }))
+    }
+}
+
+impl BinarySerializer<Message> for MessageCollector {
+    fn set_spec_version(mut self, spec_version: SpecVersion) -> Result<Self> {
+        self.spec_version = Some(spec_version);
+        Ok(self)
+    }
+
+    fn set_attribute(mut self, name: &str, value: MessageAttributeValue) -> Result<Self> {
+        self.attributes.push((name.to_string(), value.into_owned()));
+        Ok(self)
+    }
+
+    fn set_extension(mut self, name: &str, value: MessageAttributeValue) -> Result<Self> {
+        self.extensions.push((name.to_string(), value.into_owned()));
+        Ok(self)
+    }
+
+    fn end_with_data(self, bytes: Bytes) -> Result<Message> {
+        self.end_with(Some(bytes))
+    }
+
+    fn end(self) -> Result<Message> {
+        self.end_with(None)
+    }
+}
+
+impl StructuredSerializer<Message> for MessageCollector {
+    fn set_structured_event(self, bytes: Vec<u8>) -> Result<Message> {
+        Ok(Message::Structured(StructuredMessage { payload: bytes }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EventBuilder, EventBuilderV10};
+    use serde_json::json;
+
+    fn event() -> Event {
+        EventBuilderV10::new()
+            .id("0001")
+            .source("http://localhost/")
+            .ty("example.test")
+            .extension("someint", 10)
+            .data("application/json", json!({"hello": "world"}))
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn binary() {
+        let message = Message::from_event(event()).unwrap();
+        let binary = match &message {
+            Message::Binary(binary) => binary,
+            m => panic!("unexpected message {:?}", m),
+        };
+        assert_eq!(SpecVersion::V10, binary.spec_version());
+        assert_eq!(
+            Some(&MessageAttributeValue::Integer(10)),
+            binary
+                .extensions()
+                .find(|(n, _)| *n == "someint")
+                .map(|(_, v)| v)
+        );
+        assert_eq!(SpecVersion::V10, message.spec_version().unwrap());
+        assert_eq!(event(), message.clone().into_event().unwrap());
+
+        let structured: Vec<u8> = message.into_structured(Payload).unwrap();
+        assert_eq!(
+            serde_json::to_value(event()).unwrap(),
+            serde_json::from_slice::<serde_json::Value>(&structured).unwrap()
+        );
+    }
+
+    #[test]
+    fn structured() {
+        let payload = serde_json::to_vec(&event()).unwrap();
+        let message = Message::Structured(StructuredMessage {
+            payload: payload.clone(),
+        });
+        assert_eq!(SpecVersion::V10, message.spec_version().unwrap());
+        assert_eq!(payload, message.clone().into_structured(Payload).unwrap());
+        assert_eq!(event(), message.clone().into_event().unwrap());
+        let binary = message
+            .clone()
+            .into_binary(MessageCollector::default())
+            .unwrap();
+        assert_eq!(event(), binary.into_event().unwrap());
+        assert!(matches!(
+            message.deserialize_binary(Event::default()),
+            Err(Error::WrongEncoding {})
+        ));
+    }
+
+    /// Serializer collecting the structured mode payload
+    struct Payload;
+
+    impl StructuredSerializer<Vec<u8>> for Payload {
+        fn set_structured_event(self, bytes: Vec<u8>) -> Result<Vec<u8>> {
+            Ok(bytes)
+        }
+    }
+}
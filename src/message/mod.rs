@@ -1,11 +1,67 @@
+#[cfg(feature = "stream")]
+mod async_serializer;
+#[cfg(feature = "compression")]
+mod compression;
+mod dead_letter;
+mod delivery;
 mod deserializer;
 mod encoding;
 mod error;
+mod format;
+mod interceptor;
+#[allow(clippy::module_inception)]
+mod message;
+mod parse_mode;
+#[cfg(feature = "retry")]
+mod retry;
+mod sender;
 mod serializer;
+mod size_limit;
+#[cfg(feature = "stream")]
+mod stream;
 mod types;
 
+#[cfg(feature = "stream")]
+pub(crate) use async_serializer::write_binary;
+#[cfg(feature = "stream")]
+pub use async_serializer::{
+    collect, AsyncBinaryDeserializer, AsyncBinarySerializer, AsyncStructuredDeserializer,
+    BufferingSerializer, DataStream, SerializerFuture,
+};
+#[cfg(feature = "compression")]
+pub use compression::{CompressedFormat, Compression, DEFAULT_DECOMPRESSED_LIMIT};
+pub use dead_letter::{DeadLetterPolicy, DEADLETTER_CONSUMER, DEADLETTER_REASON};
+pub use delivery::{Acknowledger, Delivery, Outcome};
 pub use deserializer::*;
 pub use encoding::*;
 pub use error::*;
+pub use format::{
+    clear_event_formats, event_formats, set_event_formats, EventFormat, EventFormats, JsonFormat,
+    CLOUDEVENTS_JSON_CONTENT_TYPE,
+};
+pub use interceptor::{
+    clear_pipeline, set_pipeline, AsyncEventInterceptor, EventInterceptor, InterceptorFuture,
+    Pipeline,
+};
+// Only used by the bindings performing I/O
+#[cfg_attr(
+    not(any(feature = "reqwest", feature = "rdkafka")),
+    allow(unused_imports)
+)]
+pub(crate) use interceptor::intercept_send;
+#[cfg_attr(not(event_receiver), allow(unused_imports))]
+pub(crate) use interceptor::Received;
+pub use message::{BinaryMessage, Message, StructuredMessage};
+pub use parse_mode::{ParseDiagnostic, ParseMode};
+pub(crate) use parse_mode::{ParsingBinarySerializer, ParsingStructuredSerializer};
+#[cfg(feature = "retry")]
+pub use retry::{Backoff, RetryingSender};
+pub use sender::EventSender;
 pub use serializer::*;
+pub use size_limit::{SizeLimitPolicy, KAFKA_MAX_MESSAGE_SIZE, SQS_MAX_MESSAGE_SIZE};
+#[cfg(feature = "stream")]
+pub use stream::{EventSink, EventStream, InFlightSink};
 pub use types::MessageAttributeValue;
+
+/// Re-export of the [`Bytes`](bytes::Bytes) type taken by [`BinarySerializer::end_with_data`]
+pub use bytes::Bytes;
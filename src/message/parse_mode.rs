@@ -0,0 +1,438 @@
+use super::{BinarySerializer, Error, MessageAttributeValue, Result, StructuredSerializer};
+use crate::event::{
+    invalid_extension_name_reason, is_long_extension_name, parse_json, EventBinarySerializer,
+    SpecVersion, UriReference,
+};
+use crate::Event;
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use serde_json::{Map, Value};
+use std::convert::{TryFrom, TryInto};
+use std::fmt;
+
+/// How strictly a message is checked by
+/// [`MessageDeserializer::into_event_with_mode`](super::MessageDeserializer::into_event_with_mode).
+///
+/// Both modes look for:
+///
+/// * attributes which are not context attributes of the message spec version,
+///   for example `schemaurl` in a v1.0 message
+/// * malformed `time`, `source` and `dataschema`/`schemaurl` values
+/// * extension names not following the
+///   [naming convention](https://github.com/cloudevents/spec/blob/v1.0/spec.md#attribute-naming-convention)
+///
+/// Both also report the extension names longer than 20 characters as
+/// [`ParseDiagnostic::LongExtensionName`], which the spec only recommends against, so it
+/// doesn't fail the strict mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseMode {
+    /// Fail with [`Error::StrictModeViolation`] on the first violation found
+    Strict,
+    /// Report the violations as [`ParseDiagnostic`]s and still produce an [`Event`]:
+    /// unknown attributes are kept as extensions and malformed optional attributes are dropped.
+    /// A message with a missing or malformed required attribute is still rejected.
+    Lenient,
+}
+
+/// Violation found while parsing a message with a [`ParseMode`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseDiagnostic {
+    /// The attribute is not a context attribute of the message spec version
+    UnknownAttribute { name: String },
+    /// The attribute value can't be parsed to the attribute type
+    MalformedAttribute {
+        name: String,
+        value: String,
+        reason: String,
+    },
+    /// The extension name doesn't follow the naming convention
+    InvalidExtensionName { name: String, reason: &'static str },
+    /// The extension name is longer than the
+    /// [`MAX_EXTENSION_NAME_LENGTH`](crate::event::MAX_EXTENSION_NAME_LENGTH) recommended by
+    /// the spec, which doesn't make it invalid
+    LongExtensionName { name: String },
+}
+
+impl fmt::Display for ParseDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseDiagnostic::UnknownAttribute { name } => {
+                write!(f, "Unknown attribute '{}'", name)
+            }
+            ParseDiagnostic::MalformedAttribute {
+                name,
+                value,
+                reason,
+            } => write!(
+                f,
+                "Malformed attribute '{}' value '{}': {}",
+                name, value, reason
+            ),
+            ParseDiagnostic::InvalidExtensionName { name, reason } => {
+                write!(f, "Invalid extension name '{}': {}", name, reason)
+            }
+            ParseDiagnostic::LongExtensionName { name } => write!(
+                f,
+                "Extension name '{}' is longer than {} characters",
+                name,
+                crate::event::MAX_EXTENSION_NAME_LENGTH
+            ),
+        }
+    }
+}
+
+/// Collects the [`ParseDiagnostic`]s, or fails on the first one in strict mode
+struct Diagnostics {
+    mode: ParseMode,
+    diagnostics: Vec<ParseDiagnostic>,
+}
+
+impl Diagnostics {
+    fn new(mode: ParseMode) -> Self {
+        Diagnostics {
+            mode,
+            diagnostics: Vec::new(),
+        }
+    }
+
+    fn report(&mut self, diagnostic: ParseDiagnostic) -> Result<()> {
+        match self.mode {
+            ParseMode::Strict => Err(Error::StrictModeViolation { diagnostic }),
+            ParseMode::Lenient => {
+                self.diagnostics.push(diagnostic);
+                Ok(())
+            }
+        }
+    }
+
+    /// Collect a diagnostic which doesn't fail the strict mode
+    fn warn(&mut self, diagnostic: ParseDiagnostic) {
+        self.diagnostics.push(diagnostic);
+    }
+
+    /// Check the name of an extension of a `spec_version` message
+    fn check_extension_name(&mut self, spec_version: &SpecVersion, name: &str) -> Result<()> {
+        if is_attribute_of_other_version(spec_version, name) {
+            self.report(ParseDiagnostic::UnknownAttribute {
+                name: name.to_string(),
+            })
+        } else if let Some(reason) = invalid_extension_name_reason(name) {
+            self.report(ParseDiagnostic::InvalidExtensionName {
+                name: name.to_string(),
+                reason,
+            })
+        } else {
+            if is_long_extension_name(name) {
+                self.warn(ParseDiagnostic::LongExtensionName {
+                    name: name.to_string(),
+                });
+            }
+            Ok(())
+        }
+    }
+
+    /// Check the value of a context attribute, returning `false` if it's malformed
+    fn check_attribute_value(&mut self, name: &str, value: &MessageAttributeValue) -> Result<bool> {
+        let reason = match name {
+            "time" => TryInto::<DateTime<Utc>>::try_into(value.clone()).err(),
+            "source" | "dataschema" | "schemaurl" => {
+                TryInto::<UriReference>::try_into(value.clone()).err()
+            }
+            _ => None,
+        };
+        match reason {
+            Some(reason) => {
+                self.report(ParseDiagnostic::MalformedAttribute {
+                    name: name.to_string(),
+                    value: value.to_string(),
+                    reason: reason.to_string(),
+                })?;
+                Ok(false)
+            }
+            None => Ok(true),
+        }
+    }
+}
+
+fn is_attribute_of_other_version(spec_version: &SpecVersion, name: &str) -> bool {
+    !spec_version.attribute_names().contains(&name)
+        && SpecVersion::all_attribute_names().any(|n| n == name)
+}
+
+/// Required attributes can't be dropped when malformed
+fn is_required(name: &str) -> bool {
+    matches!(name, "id" | "type" | "source" | "specversion")
+}
+
+/// [`BinarySerializer`] checking the message with a [`ParseMode`] before building the [`Event`]
+pub(crate) struct ParsingBinarySerializer {
+    inner: EventBinarySerializer,
+    spec_version: Option<SpecVersion>,
+    diagnostics: Diagnostics,
+}
+
+impl ParsingBinarySerializer {
+    pub(crate) fn new(mode: ParseMode) -> Self {
+        ParsingBinarySerializer {
+            inner: EventBinarySerializer::new(),
+            spec_version: None,
+            diagnostics: Diagnostics::new(mode),
+        }
+    }
+
+    fn spec_version(&self) -> Result<SpecVersion> {
+        self.spec_version
+            .clone()
+            .ok_or(Error::MissingRequiredAttribute {
+                attribute_name: "specversion",
+            })
+    }
+}
+
+impl BinarySerializer<(Event, Vec<ParseDiagnostic>)> for ParsingBinarySerializer {
+    fn set_spec_version(mut self, spec_version: SpecVersion) -> Result<Self> {
+        self.spec_version = Some(spec_version.clone());
+        self.inner = self.inner.set_spec_version(spec_version)?;
+        Ok(self)
+    }
+
+    fn set_attribute(mut self, name: &str, value: MessageAttributeValue) -> Result<Self> {
+        let spec_version = self.spec_version()?;
+        if !spec_version.attribute_names().contains(&name) {
+            self.diagnostics.report(ParseDiagnostic::UnknownAttribute {
+                name: name.to_string(),
+            })?;
+            self.inner = self.inner.set_extension(name, value)?;
+            return Ok(self);
+        }
+        if self.diagnostics.check_attribute_value(name, &value)? || is_required(name) {
+            self.inner = self.inner.set_attribute(name, value)?;
+        }
+        Ok(self)
+    }
+
+    fn set_extension(mut self, name: &str, value: MessageAttributeValue) -> Result<Self> {
+        let spec_version = self.spec_version()?;
+        self.diagnostics.check_extension_name(&spec_version, name)?;
+        self.inner = self.inner.set_extension(name, value)?;
+        Ok(self)
+    }
+
+    fn end_with_data(self, bytes: Bytes) -> Result<(Event, Vec<ParseDiagnostic>)> {
+        Ok((
+            self.inner.end_with_data(bytes)?,
+            self.diagnostics.diagnostics,
+        ))
+    }
+
+    fn end(self) -> Result<(Event, Vec<ParseDiagnostic>)> {
+        Ok((self.inner.end()?, self.diagnostics.diagnostics))
+    }
+}
+
+/// [`StructuredSerializer`] checking a JSON structured message with a [`ParseMode`]
+/// before parsing the [`Event`]
+pub(crate) struct ParsingStructuredSerializer {
+    diagnostics: Diagnostics,
+}
+
+impl ParsingStructuredSerializer {
+    pub(crate) fn new(mode: ParseMode) -> Self {
+        ParsingStructuredSerializer {
+            diagnostics: Diagnostics::new(mode),
+        }
+    }
+}
+
+impl StructuredSerializer<(Event, Vec<ParseDiagnostic>)> for ParsingStructuredSerializer {
+    fn set_structured_event(mut self, bytes: Vec<u8>) -> Result<(Event, Vec<ParseDiagnostic>)> {
+        let mut map: Map<String, Value> = parse_json(bytes)?;
+        let spec_version = match map.get("specversion") {
+            Some(Value::String(s)) => SpecVersion::try_from(s.as_str())?,
+            _ => {
+                return Err(Error::MissingRequiredAttribute {
+                    attribute_name: "specversion",
+                })
+            }
+        };
+
+        let mut names: Vec<String> = map.keys().cloned().collect();
+        names.sort_unstable();
+        for name in names {
+            if spec_version.attribute_names().contains(&name.as_str()) {
+                let valid = match &map[&name] {
+                    Value::String(s) => self
+                        .diagnostics
+                        .check_attribute_value(&name, &MessageAttributeValue::from(s.as_str()))?,
+                    _ => true,
+                };
+                if !valid && !is_required(&name) {
+                    map.remove(&name);
+                }
+            } else if !is_data_member(&spec_version, &name) {
+                self.diagnostics
+                    .check_extension_name(&spec_version, &name)?;
+            }
+        }
+
+        let event =
+            serde_json::from_value(Value::Object(map)).map_err(|e| Error::PayloadDecodeError {
+                source: Box::new(e),
+            })?;
+        Ok((event, self.diagnostics.diagnostics))
+    }
+}
+
+/// Members of the JSON event format which are not context attributes
+fn is_data_member(spec_version: &SpecVersion, name: &str) -> bool {
+    match spec_version {
+        SpecVersion::V03 => matches!(name, "data" | "datacontentencoding"),
+        SpecVersion::V10 => matches!(name, "data" | "data_base64"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::{AttributesReader, ExtensionValue};
+    use serde_json::json;
+
+    fn binary(mode: ParseMode) -> Result<(Event, Vec<ParseDiagnostic>)> {
+        ParsingBinarySerializer::new(mode)
+            .set_spec_version(SpecVersion::V10)?
+            .set_attribute("id", MessageAttributeValue::from("0001"))?
+            .set_attribute("type", MessageAttributeValue::from("example.test"))?
+            .set_attribute("source", MessageAttributeValue::from("http://localhost/"))?
+            .set_attribute("time", MessageAttributeValue::from("yesterday"))?
+            .set_attribute(
+                "schemaurl",
+                MessageAttributeValue::from("http://localhost/"),
+            )?
+            .set_extension("some_ext", MessageAttributeValue::from("value"))?
+            .end()
+    }
+
+    #[test]
+    fn binary_lenient() {
+        let (event, diagnostics) = binary(ParseMode::Lenient).unwrap();
+        assert_eq!("0001", event.get_id());
+        assert!(event.get_time().is_none());
+        assert_eq!(
+            Some(&ExtensionValue::from("http://localhost/")),
+            event.get_extension("schemaurl")
+        );
+        assert_eq!(
+            Some(&ExtensionValue::from("value")),
+            event.get_extension("some_ext")
+        );
+
+        assert_eq!(3, diagnostics.len());
+        assert!(matches!(
+            &diagnostics[0],
+            ParseDiagnostic::MalformedAttribute { name, .. } if name == "time"
+        ));
+        assert_eq!(
+            ParseDiagnostic::UnknownAttribute {
+                name: String::from("schemaurl")
+            },
+            diagnostics[1]
+        );
+        assert!(matches!(
+            &diagnostics[2],
+            ParseDiagnostic::InvalidExtensionName { name, .. } if name == "some_ext"
+        ));
+    }
+
+    #[test]
+    fn binary_strict() {
+        assert!(matches!(
+            binary(ParseMode::Strict),
+            Err(Error::StrictModeViolation {
+                diagnostic: ParseDiagnostic::MalformedAttribute { .. }
+            })
+        ));
+    }
+
+    fn structured() -> Vec<u8> {
+        serde_json::to_vec(&json!({
+            "specversion": "1.0",
+            "id": "0001",
+            "type": "example.test",
+            "source": "http://localhost/",
+            "time": "yesterday",
+            "schemaurl": "http://localhost/",
+            "Upper": "value",
+            "data": {"hello": "world"}
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn structured_lenient() {
+        let (event, diagnostics) = ParsingStructuredSerializer::new(ParseMode::Lenient)
+            .set_structured_event(structured())
+            .unwrap();
+        assert_eq!("0001", event.get_id());
+        assert!(event.get_time().is_none());
+        assert_eq!(
+            Some(&ExtensionValue::from("value")),
+            event.get_extension("Upper")
+        );
+
+        let names: Vec<&str> = diagnostics
+            .iter()
+            .map(|d| match d {
+                ParseDiagnostic::UnknownAttribute { name }
+                | ParseDiagnostic::MalformedAttribute { name, .. }
+                | ParseDiagnostic::InvalidExtensionName { name, .. }
+                | ParseDiagnostic::LongExtensionName { name } => name.as_str(),
+            })
+            .collect();
+        assert_eq!(vec!["Upper", "schemaurl", "time"], names);
+    }
+
+    #[test]
+    fn structured_strict() {
+        assert!(matches!(
+            ParsingStructuredSerializer::new(ParseMode::Strict).set_structured_event(structured()),
+            Err(Error::StrictModeViolation {
+                diagnostic: ParseDiagnostic::InvalidExtensionName { .. }
+            })
+        ));
+
+        let valid = serde_json::to_vec(&json!({
+            "specversion": "1.0",
+            "id": "0001",
+            "type": "example.test",
+            "source": "http://localhost/",
+            "someext": "value"
+        }))
+        .unwrap();
+        let (_, diagnostics) = ParsingStructuredSerializer::new(ParseMode::Strict)
+            .set_structured_event(valid)
+            .unwrap();
+        assert!(diagnostics.is_empty());
+
+        // A long extension name is only reported
+        let long = serde_json::to_vec(&json!({
+            "specversion": "1.0",
+            "id": "0001",
+            "type": "example.test",
+            "source": "http://localhost/",
+            "averyveryverylongextensionname": "value"
+        }))
+        .unwrap();
+        let (event, diagnostics) = ParsingStructuredSerializer::new(ParseMode::Strict)
+            .set_structured_event(long)
+            .unwrap();
+        assert!(event
+            .get_extension("averyveryverylongextensionname")
+            .is_some());
+        assert_eq!(
+            vec![ParseDiagnostic::LongExtensionName {
+                name: String::from("averyveryverylongextensionname")
+            }],
+            diagnostics
+        );
+    }
+}
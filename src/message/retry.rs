@@ -0,0 +1,304 @@
+use super::{Error, EventSender, Result};
+use crate::Event;
+use std::collections::hash_map::RandomState;
+use std::convert::TryFrom;
+use std::hash::{BuildHasher, Hasher};
+use std::time::Duration;
+
+/// Exponential backoff between the attempts of [`RetryingSender`]: the n-th retry waits
+/// `initial * multiplier^(n - 1)`, up to `max`, randomized by `± jitter * delay`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Backoff {
+    initial: Duration,
+    max: Duration,
+    multiplier: f64,
+    jitter: f64,
+}
+
+impl Backoff {
+    /// Create a new [`Backoff`] doubling the delay from `initial` up to `max`, without jitter
+    pub fn new(initial: Duration, max: Duration) -> Self {
+        Backoff {
+            initial,
+            max,
+            multiplier: 2.0,
+            jitter: 0.0,
+        }
+    }
+
+    /// Set the factor multiplying the delay after each retry
+    pub fn multiplier(mut self, multiplier: f64) -> Self {
+        self.multiplier = multiplier;
+        self
+    }
+
+    /// Set the fraction of the delay to randomize, between `0.0` and `1.0`
+    pub fn jitter(mut self, jitter: f64) -> Self {
+        self.jitter = jitter.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Get the delay before the `retry`-th retry, starting from 1, without jitter
+    pub fn delay(&self, retry: u32) -> Duration {
+        let exponent = i32::try_from(retry.saturating_sub(1)).unwrap_or(i32::MAX);
+        let factor = self.multiplier.powi(exponent);
+        let delay = self.initial.as_secs_f64() * factor;
+        if delay.is_finite() && delay < self.max.as_secs_f64() {
+            Duration::from_secs_f64(delay)
+        } else {
+            self.max
+        }
+    }
+
+    fn jittered_delay(&self, retry: u32) -> Duration {
+        let delay = self.delay(retry);
+        if self.jitter == 0.0 {
+            return delay;
+        }
+        // Uniform in [-1, 1], from the random keys of RandomState
+        let random = RandomState::new().build_hasher().finish() as f64 / u64::MAX as f64;
+        let factor = 1.0 + self.jitter * (2.0 * random - 1.0);
+        Duration::from_secs_f64(delay.as_secs_f64() * factor)
+    }
+}
+
+impl Default for Backoff {
+    /// 100 milliseconds doubling up to 10 seconds, with a 20% jitter
+    fn default() -> Self {
+        Backoff::new(Duration::from_millis(100), Duration::from_secs(10)).jitter(0.2)
+    }
+}
+
+type Classifier = Box<dyn Fn(&Error) -> bool + Send + Sync>;
+type DeadLetter = Box<dyn Fn(Event, &Error) + Send + Sync>;
+
+/// Wrapper of an [`EventSender`] retrying the failed sends with a [`Backoff`], enabled with the
+/// `retry` feature.
+///
+/// By default an event is sent at most 3 times, retrying the errors for which
+/// [`Error::is_retryable`] returns `true`. When an event is given up, because it failed with a
+/// non retryable error or it exhausted its attempts, it's passed to the dead letter hook, and
+/// [`RetryingSender::send`] fails with [`Error::RetriesExhausted`] or the non retryable error.
+///
+/// ```
+/// use cloudevents::message::{Backoff, Error, RetryingSender};
+/// use cloudevents::{AttributesReader, Event, EventBuilder, EventBuilderV10};
+/// use std::sync::atomic::{AtomicU32, Ordering};
+/// use std::time::Duration;
+///
+/// # tokio::runtime::Builder::new_current_thread().enable_time().build().unwrap().block_on(async {
+/// let attempts = AtomicU32::new(0);
+/// let sender = RetryingSender::new(|_: Event| {
+///     let attempt = attempts.fetch_add(1, Ordering::SeqCst);
+///     async move {
+///         match attempt {
+///             0 => Err(Error::Other { source: "connection reset".into() }),
+///             _ => Ok(()),
+///         }
+///     }
+/// })
+/// .max_attempts(5)
+/// .backoff(Backoff::new(Duration::from_millis(10), Duration::from_secs(1)))
+/// .on_dead_letter(|event, error| eprintln!("dropped {}: {}", event.get_id(), error));
+///
+/// let event = EventBuilderV10::new()
+///     .id("0001")
+///     .source("http://localhost/")
+///     .ty("example.demo")
+///     .build()
+///     .unwrap();
+/// sender.send(event).await.unwrap();
+/// assert_eq!(2, attempts.load(Ordering::SeqCst));
+/// # });
+/// ```
+pub struct RetryingSender<S> {
+    sender: S,
+    backoff: Backoff,
+    max_attempts: u32,
+    retryable: Classifier,
+    dead_letter: Option<DeadLetter>,
+}
+
+impl<S: EventSender> RetryingSender<S> {
+    /// Create a new [`RetryingSender`] wrapping `sender`
+    pub fn new(sender: S) -> Self {
+        RetryingSender {
+            sender,
+            backoff: Backoff::default(),
+            max_attempts: 3,
+            retryable: Box::new(Error::is_retryable),
+            dead_letter: None,
+        }
+    }
+
+    /// Set the maximum number of attempts for each event, including the first one
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Set the [`Backoff`] between the attempts
+    pub fn backoff(mut self, backoff: Backoff) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    /// Set the function classifying the errors to retry, instead of [`Error::is_retryable`]
+    pub fn retry_if(mut self, retryable: impl Fn(&Error) -> bool + Send + Sync + 'static) -> Self {
+        self.retryable = Box::new(retryable);
+        self
+    }
+
+    /// Set the hook receiving the events which couldn't be sent, with the last error
+    pub fn on_dead_letter(
+        mut self,
+        dead_letter: impl Fn(Event, &Error) + Send + Sync + 'static,
+    ) -> Self {
+        self.dead_letter = Some(Box::new(dead_letter));
+        self
+    }
+
+    /// Get the wrapped [`EventSender`]
+    pub fn inner(&self) -> &S {
+        &self.sender
+    }
+
+    /// Send `event`, retrying on failure
+    pub async fn send(&self, event: Event) -> Result<()> {
+        let mut attempt = 1;
+        loop {
+            let error = match self.sender.send(event.clone()).await {
+                Ok(()) => return Ok(()),
+                Err(error) => error,
+            };
+            if !(self.retryable)(&error) {
+                return Err(self.give_up(event, error));
+            }
+            if attempt >= self.max_attempts {
+                let error = Error::RetriesExhausted {
+                    attempts: attempt,
+                    source: Box::new(error),
+                };
+                return Err(self.give_up(event, error));
+            }
+            tokio::time::sleep(self.backoff.jittered_delay(attempt)).await;
+            attempt += 1;
+        }
+    }
+
+    fn give_up(&self, event: Event, error: Error) -> Error {
+        if let Some(dead_letter) = &self.dead_letter {
+            dead_letter(event, &error);
+        }
+        error
+    }
+}
+
+impl<S: std::fmt::Debug> std::fmt::Debug for RetryingSender<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RetryingSender")
+            .field("sender", &self.sender)
+            .field("backoff", &self.backoff)
+            .field("max_attempts", &self.max_attempts)
+            .field("dead_letter", &self.dead_letter.is_some())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EventBuilder, EventBuilderV10};
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::{Arc, Mutex};
+
+    fn event() -> Event {
+        EventBuilderV10::new()
+            .id("0001")
+            .source("http://localhost/")
+            .ty("example.test")
+            .build()
+            .unwrap()
+    }
+
+    fn transient() -> Error {
+        Error::Other {
+            source: "unavailable".into(),
+        }
+    }
+
+    fn no_backoff() -> Backoff {
+        Backoff::new(Duration::ZERO, Duration::ZERO)
+    }
+
+    #[test]
+    fn backoff_delay() {
+        let backoff = Backoff::new(Duration::from_millis(100), Duration::from_secs(1));
+        assert_eq!(Duration::from_millis(100), backoff.delay(1));
+        assert_eq!(Duration::from_millis(400), backoff.delay(3));
+        assert_eq!(Duration::from_secs(1), backoff.delay(5));
+        assert_eq!(Duration::from_secs(1), backoff.delay(u32::MAX));
+
+        let backoff = backoff.multiplier(3.0).jitter(0.5);
+        assert_eq!(Duration::from_millis(300), backoff.delay(2));
+        for _ in 0..10 {
+            let delay = backoff.jittered_delay(2);
+            assert!(delay >= Duration::from_millis(150) && delay <= Duration::from_millis(450));
+        }
+    }
+
+    #[tokio::test]
+    async fn retries_exhausted() {
+        let attempts = AtomicU32::new(0);
+        let dead_letters = Arc::new(Mutex::new(Vec::new()));
+        let recorded = dead_letters.clone();
+        let sender = RetryingSender::new(|_: Event| {
+            attempts.fetch_add(1, Ordering::SeqCst);
+            async { Err(transient()) }
+        })
+        .max_attempts(4)
+        .backoff(no_backoff())
+        .on_dead_letter(move |event, error| {
+            recorded.lock().unwrap().push((event, error.to_string()))
+        });
+
+        let result = sender.send(event()).await;
+        assert!(matches!(
+            result,
+            Err(Error::RetriesExhausted { attempts: 4, .. })
+        ));
+        assert_eq!(4, attempts.load(Ordering::SeqCst));
+        assert_eq!(
+            vec![(
+                event(),
+                String::from("Giving up after 4 attempts: Other error: unavailable")
+            )],
+            *dead_letters.lock().unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn non_retryable_error() {
+        let attempts = AtomicU32::new(0);
+        let sender = RetryingSender::new(|_: Event| {
+            attempts.fetch_add(1, Ordering::SeqCst);
+            async { Err(Error::WrongEncoding {}) }
+        })
+        .backoff(no_backoff());
+
+        assert!(matches!(
+            sender.send(event()).await,
+            Err(Error::WrongEncoding {})
+        ));
+        assert_eq!(1, attempts.load(Ordering::SeqCst));
+
+        let sender = RetryingSender::new(|_: Event| async { Err(Error::WrongEncoding {}) })
+            .backoff(no_backoff())
+            .retry_if(|_| true)
+            .max_attempts(2);
+        assert!(matches!(
+            sender.send(event()).await,
+            Err(Error::RetriesExhausted { attempts: 2, .. })
+        ));
+    }
+}
@@ -0,0 +1,28 @@
+use super::Result;
+use crate::Event;
+use std::future::Future;
+
+/// Sender of events through a transport, e.g. to wrap it with `RetryingSender` or to forward
+/// events with [`DeadLetterPolicy`](super::DeadLetterPolicy).
+///
+/// This trait is implemented for closures accepting an [`Event`] and returning a [`Future`],
+/// so any transport can be adapted without implementing it.
+pub trait EventSender {
+    /// Future completing when the event is sent
+    type Future: Future<Output = Result<()>>;
+
+    /// Send the event
+    fn send(&self, event: Event) -> Self::Future;
+}
+
+impl<F, Fut> EventSender for F
+where
+    F: Fn(Event) -> Fut,
+    Fut: Future<Output = Result<()>>,
+{
+    type Future = Fut;
+
+    fn send(&self, event: Event) -> Fut {
+        self(event)
+    }
+}
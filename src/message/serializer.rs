@@ -1,11 +1,17 @@
 use super::{MessageAttributeValue, Result};
 use crate::event::SpecVersion;
+use bytes::Bytes;
 
 /// Serializer for structured mode messages
 pub trait StructuredSerializer<RETURN: Sized> {
     fn set_structured_event(self, bytes: Vec<u8>) -> Result<RETURN>;
 }
 
+/// Serializer for batched mode messages
+pub trait BatchSerializer<RETURN: Sized> {
+    fn set_batch(self, bytes: Vec<u8>) -> Result<RETURN>;
+}
+
 /// Serializer for binary mode messages
 pub trait BinarySerializer<RETURN: Sized>
 where
@@ -17,7 +23,9 @@ where
 
     fn set_extension(self, name: &str, value: MessageAttributeValue) -> Result<Self>;
 
-    fn end_with_data(self, bytes: Vec<u8>) -> Result<RETURN>;
+    /// End the message with the payload `bytes`. The payload is passed as [`Bytes`], so it can be
+    /// shared with the protocol buffers without copying it.
+    fn end_with_data(self, bytes: Bytes) -> Result<RETURN>;
 
     fn end(self) -> Result<RETURN>;
 }
@@ -0,0 +1,243 @@
+use super::{Error, EventFormat, EventInterceptor, JsonFormat, Result};
+use crate::extensions::{DataStore, DatarefExtension};
+use crate::Event;
+use std::sync::Arc;
+
+/// Default maximum size of a Kafka message, the `message.max.bytes` of the brokers
+pub const KAFKA_MAX_MESSAGE_SIZE: usize = 1_048_576;
+
+/// Maximum size of an Amazon SQS message
+pub const SQS_MAX_MESSAGE_SIZE: usize = 262_144;
+
+/// Policy enforcing a maximum encoded size of the events before they're sent, so an oversized
+/// event fails early with [`Error::EventTooLarge`] instead of an opaque transport error.
+///
+/// The size is the [`Event::encoded_size`] in the JSON event format by default, or in the
+/// [`format`](SizeLimitPolicy::format) the events are sent with. When a [`DataStore`] is
+/// configured with [`externalize`](SizeLimitPolicy::externalize), the data of an oversized event
+/// is moved to the store with the [`DatarefExtension`] instead, and the event is rejected only if
+/// it's still too large without its data.
+///
+/// The policy is an [`EventInterceptor`], checking the events in its `on_send` hook.
+///
+/// ```
+/// use cloudevents::message::{Error, SizeLimitPolicy};
+/// use cloudevents::{EventBuilder, EventBuilderV10};
+///
+/// let policy = SizeLimitPolicy::new(1024);
+/// let mut event = EventBuilderV10::new()
+///     .id("0001")
+///     .source("http://localhost/")
+///     .ty("example.demo")
+///     .data("text/plain", "a".repeat(2048))
+///     .build()
+///     .unwrap();
+/// assert!(matches!(
+///     policy.enforce(&mut event),
+///     Err(Error::EventTooLarge { limit: 1024, .. })
+/// ));
+/// ```
+#[derive(Clone)]
+pub struct SizeLimitPolicy {
+    limit: usize,
+    format: Arc<dyn EventFormat>,
+    store: Option<Arc<dyn DataStore + Send + Sync>>,
+}
+
+impl SizeLimitPolicy {
+    /// Create a new [`SizeLimitPolicy`] rejecting the events larger than `limit` bytes
+    pub fn new(limit: usize) -> Self {
+        SizeLimitPolicy {
+            limit,
+            format: Arc::new(JsonFormat),
+            store: None,
+        }
+    }
+
+    /// Measure the events encoded with `format`
+    pub fn format(mut self, format: impl EventFormat + 'static) -> Self {
+        self.format = Arc::new(format);
+        self
+    }
+
+    /// Move the data of the oversized events to `store`, instead of rejecting them
+    pub fn externalize(mut self, store: impl DataStore + Send + Sync + 'static) -> Self {
+        self.store = Some(Arc::new(store));
+        self
+    }
+
+    /// Maximum size of the events, in bytes
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+
+    /// Check the size of `event`, externalizing its data if it's too large and a store is
+    /// configured.
+    ///
+    /// The data isn't stored if the event is too large even without it, and it's deleted from
+    /// the store, see [`DataStore::delete`], and restored in the event if the event is still too
+    /// large with its `dataref`.
+    pub fn enforce(&self, event: &mut Event) -> Result<()> {
+        let size = event.encoded_size(self.format.as_ref())?;
+        if size <= self.limit {
+            return Ok(());
+        }
+        let too_large = |size| Error::EventTooLarge {
+            size,
+            limit: self.limit,
+        };
+        let store = match &self.store {
+            Some(store) if event.data.is_some() => store,
+            _ => return Err(too_large(size)),
+        };
+
+        let data = event.data.take();
+        let without_data = event.encoded_size(self.format.as_ref());
+        event.data = data;
+        if without_data? > self.limit {
+            return Err(too_large(size));
+        }
+
+        let to_other = |e| Error::Other {
+            source: Box::new(e),
+        };
+        DatarefExtension::externalize(event, store).map_err(to_other)?;
+        let externalized = event.encoded_size(self.format.as_ref())?;
+        if externalized > self.limit {
+            let dataref = DatarefExtension::from_event(event)
+                .map_err(to_other)?
+                .map(|extension| extension.dataref().to_string());
+            DatarefExtension::resolve(event, store).map_err(to_other)?;
+            if let Some(dataref) = dataref {
+                store.delete(&dataref).map_err(|source| {
+                    to_other(crate::extensions::Error::DataStoreError { source })
+                })?;
+            }
+            return Err(too_large(externalized));
+        }
+        Ok(())
+    }
+}
+
+impl EventInterceptor for SizeLimitPolicy {
+    fn on_send(&self, event: &mut Event) -> Result<()> {
+        self.enforce(event)
+    }
+}
+
+impl std::fmt::Debug for SizeLimitPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SizeLimitPolicy")
+            .field("limit", &self.limit)
+            .field("format", &self.format.content_type())
+            .field("externalize", &self.store.is_some())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::{AttributesReader, Data};
+    use crate::{EventBuilder, EventBuilderV10};
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct MemoryStore(Mutex<HashMap<String, Data>>);
+
+    impl DataStore for MemoryStore {
+        fn store(
+            &self,
+            event: &Event,
+            data: Data,
+        ) -> std::result::Result<String, Box<dyn std::error::Error + Send + Sync>> {
+            let dataref = format!("memory://{}", event.get_id());
+            self.0.lock().unwrap().insert(dataref.clone(), data);
+            Ok(dataref)
+        }
+
+        fn load(
+            &self,
+            dataref: &str,
+        ) -> std::result::Result<Data, Box<dyn std::error::Error + Send + Sync>> {
+            self.0
+                .lock()
+                .unwrap()
+                .get(dataref)
+                .cloned()
+                .ok_or_else(|| "not found".into())
+        }
+
+        fn delete(
+            &self,
+            dataref: &str,
+        ) -> std::result::Result<(), Box<dyn std::error::Error + Send + Sync>> {
+            self.0.lock().unwrap().remove(dataref);
+            Ok(())
+        }
+    }
+
+    fn event(data_len: usize) -> Event {
+        EventBuilderV10::new()
+            .id("0001")
+            .source("http://localhost/")
+            .ty("example.test")
+            .data("text/plain", "a".repeat(data_len))
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn reject() {
+        let small = event(10);
+        let limit = small.encoded_size(&JsonFormat).unwrap();
+        let policy = SizeLimitPolicy::new(limit);
+
+        assert!(policy.on_send(&mut small.clone()).is_ok());
+        let mut large = event(11);
+        assert!(matches!(
+            policy.on_send(&mut large),
+            Err(Error::EventTooLarge { size, limit: l }) if size == limit + 1 && l == limit
+        ));
+        assert_eq!(
+            413,
+            Error::EventTooLarge { size: 2, limit: 1 }.status_code()
+        );
+    }
+
+    #[test]
+    fn externalize() {
+        let store = Arc::new(MemoryStore::default());
+        let policy = SizeLimitPolicy::new(1024).externalize(store.clone());
+
+        let mut e = event(2048);
+        policy.enforce(&mut e).unwrap();
+        assert!(e.get_data::<Data>().is_none());
+        assert_eq!(1, store.0.lock().unwrap().len());
+        assert!(DatarefExtension::resolve(&mut e, &store).unwrap());
+        assert_eq!(event(2048), e);
+
+        // Too large even without its data, the data isn't stored
+        let store = Arc::new(MemoryStore::default());
+        let policy = SizeLimitPolicy::new(10).externalize(store.clone());
+        assert!(matches!(
+            policy.enforce(&mut event(2048)),
+            Err(Error::EventTooLarge { .. })
+        ));
+        assert!(store.0.lock().unwrap().is_empty());
+
+        // Too large with its dataref, the stored data is deleted and restored
+        let mut without_data = event(0);
+        without_data.data = None;
+        let limit = without_data.encoded_size(&JsonFormat).unwrap() + 5;
+        let policy = SizeLimitPolicy::new(limit).externalize(store.clone());
+        let mut e = event(2048);
+        assert!(matches!(
+            policy.enforce(&mut e),
+            Err(Error::EventTooLarge { size, .. }) if size > limit
+        ));
+        assert!(store.0.lock().unwrap().is_empty());
+        assert_eq!(event(2048), e);
+    }
+}
@@ -0,0 +1,214 @@
+use super::{Error, Result};
+use crate::Event;
+use futures::stream::FuturesUnordered;
+use futures::{Future, Sink, Stream, StreamExt};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// [`Stream`] of events received from a protocol binding.
+///
+/// It's implemented for every `Stream<Item = Result<Event>>`, so the events can be processed with
+/// the [`StreamExt`] and `TryStreamExt` combinators.
+pub trait EventStream: Stream<Item = Result<Event>> {}
+
+impl<S: Stream<Item = Result<Event>> + ?Sized> EventStream for S {}
+
+/// [`Sink`] of events sent through a protocol binding.
+///
+/// It's implemented for every `Sink<Event, Error = Error>`, so an [`EventStream`] can be
+/// forwarded to it with `TryStreamExt::forward`, honoring its backpressure.
+pub trait EventSink: Sink<Event, Error = Error> {}
+
+impl<S: Sink<Event, Error = Error> + ?Sized> EventSink for S {}
+
+/// [`EventSink`] starting an asynchronous send operation for each event, with at most
+/// `capacity` operations in flight. When the limit is reached, the sink is not ready until one
+/// of the pending operations completes.
+///
+/// The protocol bindings use it to implement their sinks, but it can adapt any transport.
+///
+/// ```
+/// use cloudevents::message::InFlightSink;
+/// use cloudevents::{AttributesReader, EventBuilder, EventBuilderV10};
+/// use futures::{future, SinkExt};
+///
+/// # futures::executor::block_on(async {
+/// let mut sink = InFlightSink::new(8, |event| {
+///     println!("sending {}", event.get_id());
+///     Ok(future::ok(()))
+/// });
+///
+/// let event = EventBuilderV10::new()
+///     .id("0001")
+///     .source("http://localhost/")
+///     .ty("example.demo")
+///     .build()
+///     .unwrap();
+/// sink.send(event).await.unwrap();
+/// # });
+/// ```
+pub struct InFlightSink<S, F> {
+    send: S,
+    in_flight: FuturesUnordered<F>,
+    capacity: usize,
+}
+
+impl<S, F> InFlightSink<S, F>
+where
+    S: FnMut(Event) -> Result<F>,
+    F: Future<Output = Result<()>>,
+{
+    /// Create a new [`InFlightSink`] calling `send` to start sending each event.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is zero.
+    pub fn new(capacity: usize, send: S) -> Self {
+        assert!(capacity > 0, "the capacity must be greater than zero");
+        InFlightSink {
+            send,
+            in_flight: FuturesUnordered::new(),
+            capacity,
+        }
+    }
+
+    /// Get the number of send operations in flight
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.len()
+    }
+
+    /// Poll the operations in flight until at most `limit` are left
+    fn poll_until(&mut self, cx: &mut Context<'_>, limit: usize) -> Poll<Result<()>> {
+        while self.in_flight.len() > limit {
+            match self.in_flight.poll_next_unpin(cx) {
+                Poll::Ready(Some(Ok(()))) => {}
+                Poll::Ready(Some(Err(e))) => return Poll::Ready(Err(e)),
+                Poll::Ready(None) => break,
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+        Poll::Ready(Ok(()))
+    }
+}
+
+// The fields are never pinned, the futures in flight are pinned by `FuturesUnordered`
+impl<S, F> Unpin for InFlightSink<S, F> {}
+
+impl<S, F> Sink<Event> for InFlightSink<S, F>
+where
+    S: FnMut(Event) -> Result<F>,
+    F: Future<Output = Result<()>>,
+{
+    type Error = Error;
+
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        let this = self.get_mut();
+        let limit = this.capacity - 1;
+        this.poll_until(cx, limit)
+    }
+
+    fn start_send(self: Pin<&mut Self>, event: Event) -> Result<()> {
+        let this = self.get_mut();
+        let operation = (this.send)(event)?;
+        this.in_flight.push(operation);
+        Ok(())
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        self.get_mut().poll_until(cx, 0)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        self.poll_flush(cx)
+    }
+}
+
+impl<S, F> std::fmt::Debug for InFlightSink<S, F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("InFlightSink")
+            .field("in_flight", &self.in_flight.len())
+            .field("capacity", &self.capacity)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AttributesReader, EventBuilder, EventBuilderV10};
+    use futures::channel::oneshot;
+    use futures::executor::block_on;
+    use futures::{stream, SinkExt};
+    use std::sync::{Arc, Mutex};
+
+    fn event(id: &str) -> Event {
+        EventBuilderV10::new()
+            .id(id)
+            .source("http://localhost/")
+            .ty("example.test")
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn forward_stream() {
+        let sent = Arc::new(Mutex::new(Vec::new()));
+        let recorded = sent.clone();
+        let sink = InFlightSink::new(2, move |event: Event| {
+            recorded.lock().unwrap().push(event.get_id().to_string());
+            Ok(futures::future::ok(()))
+        });
+
+        let events = stream::iter(vec![Ok(event("1")), Ok(event("2")), Ok(event("3"))]);
+        block_on(events.forward(sink)).unwrap();
+        assert_eq!(vec!["1", "2", "3"], *sent.lock().unwrap());
+    }
+
+    #[test]
+    fn backpressure() {
+        let mut senders = Vec::new();
+        let mut receivers = Vec::new();
+        for _ in 0..2 {
+            let (tx, rx) = oneshot::channel::<()>();
+            senders.push(tx);
+            receivers.push(rx);
+        }
+        let mut sink = InFlightSink::new(1, move |_| {
+            let rx = receivers.remove(0);
+            Ok(async move { rx.await.map_err(|e| Error::Other { source: e.into() }) })
+        });
+
+        let waker = futures::task::noop_waker();
+        let mut cx = Context::from_waker(&waker);
+        assert!(matches!(
+            sink.poll_ready_unpin(&mut cx),
+            Poll::Ready(Ok(()))
+        ));
+        sink.start_send_unpin(event("1")).unwrap();
+        assert!(sink.poll_ready_unpin(&mut cx).is_pending());
+
+        senders.remove(0).send(()).unwrap();
+        assert!(matches!(
+            sink.poll_ready_unpin(&mut cx),
+            Poll::Ready(Ok(()))
+        ));
+        assert_eq!(0, sink.in_flight());
+
+        sink.start_send_unpin(event("2")).unwrap();
+        drop(senders);
+        assert!(matches!(
+            sink.poll_flush_unpin(&mut cx),
+            Poll::Ready(Err(Error::Other { .. }))
+        ));
+    }
+
+    #[test]
+    fn send_error() {
+        let mut sink = InFlightSink::new(1, |event: Event| match event.get_id() {
+            "invalid" => Err(Error::WrongEncoding {}),
+            _ => Ok(futures::future::ok(())),
+        });
+        assert!(block_on(sink.send(event("1"))).is_ok());
+        assert!(block_on(sink.send(event("invalid"))).is_err());
+    }
+}
@@ -1,25 +1,81 @@
-use crate::event::ExtensionValue;
-use chrono::{DateTime, Utc};
+use crate::event::{ExtensionValue, UriReference};
+use chrono::{DateTime, FixedOffset, SecondsFormat, Utc};
+use std::borrow::Cow;
 use std::convert::TryInto;
+use std::fmt;
 use url::Url;
 
-/// Union type representing a [CloudEvent context attribute type](https://github.com/cloudevents/spec/blob/v1.0/spec.md#type-system)
-pub enum MessageAttributeValue {
+/// Union type representing a [CloudEvent context attribute type](https://github.com/cloudevents/spec/blob/v1.0/spec.md#type-system).
+///
+/// Its [`Display`](fmt::Display) implementation produces the canonical string encoding defined by
+/// the spec, used by the protocol bindings which carry attributes as strings.
+/// The `TryInto` implementations parse the canonical string encoding back.
+///
+/// Values can borrow from the attributes they're read from, so an [`Event`](crate::Event)
+/// can be serialized to a protocol binding without cloning its attributes.
+///
+/// ```
+/// use cloudevents::message::MessageAttributeValue;
+/// use std::convert::TryInto;
+///
+/// assert_eq!("true", MessageAttributeValue::Boolean(true).to_string());
+/// assert_eq!("AQI=", MessageAttributeValue::from(vec![1, 2]).to_string());
+///
+/// let sampled: bool = MessageAttributeValue::from("true").try_into().unwrap();
+/// assert!(sampled);
+/// let rate: i64 = MessageAttributeValue::from("10").try_into().unwrap();
+/// assert_eq!(10, rate);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum MessageAttributeValue<'a> {
     Boolean(bool),
     Integer(i64),
-    String(String),
-    Binary(Vec<u8>),
-    Uri(Url),
-    UriRef(Url),
-    DateTime(DateTime<Utc>),
+    String(Cow<'a, str>),
+    Binary(Cow<'a, [u8]>),
+    Uri(Cow<'a, Url>),
+    UriRef(Cow<'a, UriReference>),
+    /// Timestamp, keeping the offset it was produced with
+    DateTime(DateTime<FixedOffset>),
 }
 
-impl TryInto<DateTime<Utc>> for MessageAttributeValue {
+impl MessageAttributeValue<'_> {
+    /// Get the canonical string encoding of this value, borrowed if this value is a string,
+    /// an URI or an URI-reference
+    pub fn as_str(&self) -> Cow<'_, str> {
+        match self {
+            MessageAttributeValue::String(s) => Cow::Borrowed(s),
+            MessageAttributeValue::Uri(u) => Cow::Borrowed(u.as_str()),
+            MessageAttributeValue::UriRef(u) => Cow::Borrowed(u.as_str()),
+            v => Cow::Owned(v.to_string()),
+        }
+    }
+
+    /// Convert this value to an owned one, cloning the borrowed data
+    pub fn into_owned(self) -> MessageAttributeValue<'static> {
+        match self {
+            MessageAttributeValue::Boolean(b) => MessageAttributeValue::Boolean(b),
+            MessageAttributeValue::Integer(i) => MessageAttributeValue::Integer(i),
+            MessageAttributeValue::String(s) => {
+                MessageAttributeValue::String(s.into_owned().into())
+            }
+            MessageAttributeValue::Binary(v) => {
+                MessageAttributeValue::Binary(v.into_owned().into())
+            }
+            MessageAttributeValue::Uri(u) => MessageAttributeValue::Uri(Cow::Owned(u.into_owned())),
+            MessageAttributeValue::UriRef(u) => {
+                MessageAttributeValue::UriRef(Cow::Owned(u.into_owned()))
+            }
+            MessageAttributeValue::DateTime(d) => MessageAttributeValue::DateTime(d),
+        }
+    }
+}
+
+impl TryInto<DateTime<Utc>> for MessageAttributeValue<'_> {
     type Error = super::Error;
 
     fn try_into(self) -> Result<DateTime<Utc>, Self::Error> {
         match self {
-            MessageAttributeValue::DateTime(d) => Ok(d),
+            MessageAttributeValue::DateTime(d) => Ok(d.into()),
             v => Ok(DateTime::<Utc>::from(DateTime::parse_from_rfc3339(
                 v.to_string().as_ref(),
             )?)),
@@ -27,48 +83,244 @@ impl TryInto<DateTime<Utc>> for MessageAttributeValue {
     }
 }
 
-impl TryInto<Url> for MessageAttributeValue {
+impl TryInto<DateTime<FixedOffset>> for MessageAttributeValue<'_> {
+    type Error = super::Error;
+
+    fn try_into(self) -> Result<DateTime<FixedOffset>, Self::Error> {
+        match self {
+            MessageAttributeValue::DateTime(d) => Ok(d),
+            v => Ok(DateTime::parse_from_rfc3339(v.to_string().as_ref())?),
+        }
+    }
+}
+
+impl TryInto<Url> for MessageAttributeValue<'_> {
     type Error = super::Error;
 
     fn try_into(self) -> Result<Url, Self::Error> {
         match self {
-            MessageAttributeValue::Uri(u) => Ok(u),
-            MessageAttributeValue::UriRef(u) => Ok(u),
+            MessageAttributeValue::Uri(u) => Ok(u.into_owned()),
+            MessageAttributeValue::UriRef(u) => Ok(u.to_url()?),
             v => Ok(Url::parse(v.to_string().as_ref())?),
         }
     }
 }
 
-impl ToString for MessageAttributeValue {
-    fn to_string(&self) -> String {
+impl TryInto<UriReference> for MessageAttributeValue<'_> {
+    type Error = super::Error;
+
+    fn try_into(self) -> Result<UriReference, Self::Error> {
+        match self {
+            MessageAttributeValue::Uri(u) => Ok(u.into_owned().into()),
+            MessageAttributeValue::UriRef(u) => Ok(u.into_owned()),
+            v => Ok(UriReference::parse(v.to_string().as_ref())?),
+        }
+    }
+}
+
+impl TryInto<bool> for MessageAttributeValue<'_> {
+    type Error = super::Error;
+
+    fn try_into(self) -> Result<bool, Self::Error> {
         match self {
-            MessageAttributeValue::Boolean(b) => b.to_string(),
-            MessageAttributeValue::Integer(i) => i.to_string(),
-            MessageAttributeValue::String(s) => s.clone(),
-            MessageAttributeValue::Binary(v) => base64::encode(v),
-            MessageAttributeValue::Uri(u) => u.to_string(),
-            MessageAttributeValue::UriRef(u) => u.to_string(),
-            MessageAttributeValue::DateTime(d) => d.to_rfc3339(),
+            MessageAttributeValue::Boolean(b) => Ok(b),
+            v => Ok(v.to_string().parse()?),
         }
     }
 }
 
-impl Into<MessageAttributeValue> for ExtensionValue {
-    fn into(self) -> MessageAttributeValue {
+impl TryInto<i64> for MessageAttributeValue<'_> {
+    type Error = super::Error;
+
+    fn try_into(self) -> Result<i64, Self::Error> {
         match self {
-            ExtensionValue::String(s) => MessageAttributeValue::String(s),
+            MessageAttributeValue::Integer(i) => Ok(i),
+            v => Ok(v.to_string().parse()?),
+        }
+    }
+}
+
+impl TryInto<Vec<u8>> for MessageAttributeValue<'_> {
+    type Error = super::Error;
+
+    fn try_into(self) -> Result<Vec<u8>, Self::Error> {
+        match self {
+            MessageAttributeValue::Binary(b) => Ok(b.into_owned()),
+            v => Ok(base64::decode(v.to_string())?),
+        }
+    }
+}
+
+impl fmt::Display for MessageAttributeValue<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MessageAttributeValue::Boolean(b) => write!(f, "{}", b),
+            MessageAttributeValue::Integer(i) => write!(f, "{}", i),
+            MessageAttributeValue::String(s) => f.write_str(s),
+            MessageAttributeValue::Binary(v) => f.write_str(&base64::encode(v)),
+            MessageAttributeValue::Uri(u) => f.write_str(u.as_str()),
+            MessageAttributeValue::UriRef(u) => f.write_str(u.as_str()),
+            MessageAttributeValue::DateTime(d) => {
+                f.write_str(&d.to_rfc3339_opts(SecondsFormat::AutoSi, true))
+            }
+        }
+    }
+}
+
+impl From<DateTime<Utc>> for MessageAttributeValue<'_> {
+    fn from(d: DateTime<Utc>) -> Self {
+        MessageAttributeValue::DateTime(d.into())
+    }
+}
+
+impl From<DateTime<FixedOffset>> for MessageAttributeValue<'_> {
+    fn from(d: DateTime<FixedOffset>) -> Self {
+        MessageAttributeValue::DateTime(d)
+    }
+}
+
+impl From<bool> for MessageAttributeValue<'_> {
+    fn from(b: bool) -> Self {
+        MessageAttributeValue::Boolean(b)
+    }
+}
+
+impl From<i64> for MessageAttributeValue<'_> {
+    fn from(i: i64) -> Self {
+        MessageAttributeValue::Integer(i)
+    }
+}
+
+impl From<String> for MessageAttributeValue<'_> {
+    fn from(s: String) -> Self {
+        MessageAttributeValue::String(Cow::Owned(s))
+    }
+}
+
+impl<'a> From<&'a str> for MessageAttributeValue<'a> {
+    fn from(s: &'a str) -> Self {
+        MessageAttributeValue::String(Cow::Borrowed(s))
+    }
+}
+
+impl From<Vec<u8>> for MessageAttributeValue<'_> {
+    fn from(v: Vec<u8>) -> Self {
+        MessageAttributeValue::Binary(Cow::Owned(v))
+    }
+}
+
+impl From<Url> for MessageAttributeValue<'_> {
+    fn from(u: Url) -> Self {
+        MessageAttributeValue::Uri(Cow::Owned(u))
+    }
+}
+
+impl From<UriReference> for MessageAttributeValue<'_> {
+    fn from(u: UriReference) -> Self {
+        MessageAttributeValue::UriRef(Cow::Owned(u))
+    }
+}
+
+impl From<ExtensionValue> for MessageAttributeValue<'_> {
+    fn from(that: ExtensionValue) -> Self {
+        match that {
+            ExtensionValue::String(s) => MessageAttributeValue::String(Cow::Owned(s)),
             ExtensionValue::Boolean(b) => MessageAttributeValue::Boolean(b),
             ExtensionValue::Integer(i) => MessageAttributeValue::Integer(i),
         }
     }
 }
 
-impl Into<ExtensionValue> for MessageAttributeValue {
-    fn into(self) -> ExtensionValue {
-        match self {
+impl<'a> From<&'a ExtensionValue> for MessageAttributeValue<'a> {
+    fn from(that: &'a ExtensionValue) -> Self {
+        match that {
+            ExtensionValue::String(s) => MessageAttributeValue::String(Cow::Borrowed(s)),
+            ExtensionValue::Boolean(b) => MessageAttributeValue::Boolean(*b),
+            ExtensionValue::Integer(i) => MessageAttributeValue::Integer(*i),
+        }
+    }
+}
+
+impl From<MessageAttributeValue<'_>> for String {
+    fn from(that: MessageAttributeValue) -> Self {
+        match that {
+            MessageAttributeValue::String(s) => s.into_owned(),
+            v => v.to_string(),
+        }
+    }
+}
+
+impl From<MessageAttributeValue<'_>> for ExtensionValue {
+    fn from(that: MessageAttributeValue) -> Self {
+        match that {
             MessageAttributeValue::Integer(i) => ExtensionValue::Integer(i),
             MessageAttributeValue::Boolean(b) => ExtensionValue::Boolean(b),
+            MessageAttributeValue::String(s) => ExtensionValue::String(s.into_owned()),
             v => ExtensionValue::String(v.to_string()),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn canonical_strings() {
+        assert_eq!("false", MessageAttributeValue::Boolean(false).to_string());
+        assert_eq!("-10", MessageAttributeValue::Integer(-10).to_string());
+        assert_eq!(
+            "aGVsbG8=",
+            MessageAttributeValue::from(b"hello".to_vec()).to_string()
+        );
+        assert_eq!(
+            "2020-03-16T11:50:00Z",
+            MessageAttributeValue::from(Utc.ymd(2020, 3, 16).and_hms(11, 50, 0)).to_string()
+        );
+        assert_eq!(
+            "2020-03-16T19:50:00+08:00",
+            MessageAttributeValue::from(
+                FixedOffset::east(8 * 3600)
+                    .ymd(2020, 3, 16)
+                    .and_hms(19, 50, 0)
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn try_into_from_strings() {
+        let b: bool = MessageAttributeValue::from("false").try_into().unwrap();
+        assert!(!b);
+        let i: i64 = MessageAttributeValue::from("-10").try_into().unwrap();
+        assert_eq!(-10, i);
+        let v: Vec<u8> = MessageAttributeValue::from("aGVsbG8=").try_into().unwrap();
+        assert_eq!(b"hello".to_vec(), v);
+        let t: DateTime<Utc> = MessageAttributeValue::from("2020-03-16T11:50:00Z")
+            .try_into()
+            .unwrap();
+        assert_eq!(Utc.ymd(2020, 3, 16).and_hms(11, 50, 0), t);
+
+        let invalid: Result<bool, _> = MessageAttributeValue::from("True").try_into();
+        assert!(invalid.is_err());
+        let invalid: Result<i64, _> = MessageAttributeValue::from("1.5").try_into();
+        assert!(invalid.is_err());
+        let invalid: Result<Vec<u8>, _> = MessageAttributeValue::from("!").try_into();
+        assert!(invalid.is_err());
+    }
+
+    #[test]
+    fn extension_value_roundtrip() {
+        for value in [
+            ExtensionValue::Boolean(true),
+            ExtensionValue::Integer(10),
+            ExtensionValue::from("value"),
+        ] {
+            assert_eq!(
+                value,
+                ExtensionValue::from(MessageAttributeValue::from(value.clone()))
+            );
+        }
+    }
+}
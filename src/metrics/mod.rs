@@ -0,0 +1,167 @@
+//! Hooks to monitor the CloudEvents traffic of the bindings.
+//!
+//! The bindings performing I/O report to the [`Metrics`] installed with [`set_metrics`] the
+//! events they send and receive, with their size in bytes, the latency of their serialization,
+//! and the messages they fail to parse, labeled with the [kind](crate::message::Error::kind) of
+//! the error. Each call is labeled with the name of the binding transport, `kafka` or `http`:
+//!
+//! * the reqwest and Kafka [`EventSender`](crate::message::EventSender)s, and the sinks built on
+//!   them, report the sent events
+//! * the reqwest `ResponseExt`, the Kafka event stream and deliveries, the extractors of the
+//!   actix, axum, poem, rocket, salvo, tide and warp servers, the hyper service and the tower
+//!   layer report the received events, except the batches
+//!
+//! The amqp, mqtt, sse, tonic and websocket bindings don't report to the metrics.
+//!
+//! The `prometheus` feature provides `PrometheusMetrics`, rendering the metrics in the
+//! [Prometheus text format](https://prometheus.io/docs/instrumenting/exposition_formats/).
+//!
+//! ```
+//! use cloudevents::metrics::{set_metrics, Metrics};
+//! use std::sync::atomic::{AtomicUsize, Ordering};
+//! use std::sync::Arc;
+//!
+//! #[derive(Default)]
+//! struct ParseFailures(AtomicUsize);
+//!
+//! impl Metrics for ParseFailures {
+//!     fn parse_failure(&self, _binding: &'static str, _kind: &'static str) {
+//!         self.0.fetch_add(1, Ordering::Relaxed);
+//!     }
+//! }
+//!
+//! let failures = Arc::new(ParseFailures::default());
+//! set_metrics(failures.clone());
+//! ```
+
+#[cfg(feature = "prometheus")]
+mod prometheus;
+
+#[cfg(feature = "prometheus")]
+pub use self::prometheus::PrometheusMetrics;
+
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+/// Receiver of the metrics of the bindings. All the methods do nothing by default.
+///
+/// It's implemented by `Arc`s of [`Metrics`], to keep a reference to the installed metrics.
+pub trait Metrics: Send + Sync {
+    /// An event of `bytes` bytes was sent with `binding`
+    fn event_sent(&self, binding: &'static str, bytes: usize) {
+        let _ = (binding, bytes);
+    }
+
+    /// An event of `bytes` bytes was received with `binding`
+    fn event_received(&self, binding: &'static str, bytes: usize) {
+        let _ = (binding, bytes);
+    }
+
+    /// An event was serialized in `latency` to be sent with `binding`
+    fn serialization_latency(&self, binding: &'static str, latency: Duration) {
+        let _ = (binding, latency);
+    }
+
+    /// A message received with `binding` couldn't be parsed, failing with an error of `kind`
+    fn parse_failure(&self, binding: &'static str, kind: &'static str) {
+        let _ = (binding, kind);
+    }
+}
+
+impl<M: Metrics + ?Sized> Metrics for Arc<M> {
+    fn event_sent(&self, binding: &'static str, bytes: usize) {
+        (**self).event_sent(binding, bytes)
+    }
+
+    fn event_received(&self, binding: &'static str, bytes: usize) {
+        (**self).event_received(binding, bytes)
+    }
+
+    fn serialization_latency(&self, binding: &'static str, latency: Duration) {
+        (**self).serialization_latency(binding, latency)
+    }
+
+    fn parse_failure(&self, binding: &'static str, kind: &'static str) {
+        (**self).parse_failure(binding, kind)
+    }
+}
+
+static METRICS: RwLock<Option<Box<dyn Metrics>>> = RwLock::new(None);
+
+/// Install the [`Metrics`] the bindings report to, replacing the previous one
+pub fn set_metrics(metrics: impl Metrics + 'static) {
+    if let Ok(mut current) = METRICS.write() {
+        *current = Some(Box::new(metrics));
+    }
+}
+
+/// Remove the installed [`Metrics`], so the bindings no longer report metrics
+pub fn clear_metrics() {
+    if let Ok(mut current) = METRICS.write() {
+        *current = None;
+    }
+}
+
+/// Report to the installed metrics, if any
+// Only called by the bindings performing I/O
+#[cfg_attr(not(io_binding), allow(dead_code))]
+pub(crate) fn record(report: impl FnOnce(&dyn Metrics)) {
+    if let Ok(metrics) = METRICS.read() {
+        if let Some(metrics) = metrics.as_deref() {
+            report(metrics)
+        }
+    }
+}
+
+/// Report the result of parsing a message of `bytes` bytes received with `binding`
+// Only called by the bindings performing I/O
+#[cfg_attr(not(io_binding), allow(dead_code))]
+pub(crate) fn record_receive<T>(
+    binding: &'static str,
+    bytes: usize,
+    result: &crate::message::Result<T>,
+) {
+    record(|metrics| match result {
+        Ok(_) => metrics.event_received(binding, bytes),
+        Err(e) => metrics.parse_failure(binding, e.kind()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::message::Error;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct Recorder(Mutex<Vec<String>>);
+
+    impl Metrics for Recorder {
+        fn event_received(&self, binding: &'static str, bytes: usize) {
+            if binding == "test" {
+                self.0.lock().unwrap().push(format!("received {}", bytes));
+            }
+        }
+
+        fn parse_failure(&self, binding: &'static str, kind: &'static str) {
+            if binding == "test" {
+                self.0.lock().unwrap().push(format!("failed {}", kind));
+            }
+        }
+    }
+
+    #[test]
+    fn record_to_installed_metrics() {
+        let recorder = Arc::new(Recorder::default());
+        set_metrics(recorder.clone());
+        record_receive("test", 10, &Ok(()));
+        record_receive::<()>("test", 5, &Err(Error::WrongEncoding {}));
+        clear_metrics();
+        record_receive("test", 1, &Ok(()));
+
+        assert_eq!(
+            vec!["received 10", "failed wrong_encoding"],
+            *recorder.0.lock().unwrap()
+        );
+    }
+}
@@ -0,0 +1,230 @@
+use super::Metrics;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Upper bounds in seconds of the buckets of the serialization latency histogram
+const LATENCY_BUCKETS: [f64; 8] = [0.00001, 0.00005, 0.0001, 0.0005, 0.001, 0.005, 0.01, 0.05];
+
+#[derive(Debug, Default)]
+struct Histogram {
+    buckets: [u64; LATENCY_BUCKETS.len()],
+    sum: f64,
+    count: u64,
+}
+
+#[derive(Debug, Default)]
+struct Registry {
+    sent: BTreeMap<&'static str, u64>,
+    sent_bytes: BTreeMap<&'static str, u64>,
+    received: BTreeMap<&'static str, u64>,
+    received_bytes: BTreeMap<&'static str, u64>,
+    serialization: BTreeMap<&'static str, Histogram>,
+    parse_failures: BTreeMap<(&'static str, &'static str), u64>,
+}
+
+/// [`Metrics`] implementation collecting counters and a latency histogram, enabled with the
+/// `prometheus` feature, and rendering them in the
+/// [Prometheus text format](https://prometheus.io/docs/instrumenting/exposition_formats/)
+/// to be served by a scrape endpoint.
+///
+/// The metrics are labeled with the `binding`, and the parse failures with the error `kind`:
+///
+/// * `cloudevents_events_sent_total` and `cloudevents_events_received_total`
+/// * `cloudevents_sent_bytes_total` and `cloudevents_received_bytes_total`
+/// * `cloudevents_serialization_duration_seconds`, a histogram
+/// * `cloudevents_parse_failures_total`
+///
+/// ```
+/// use cloudevents::metrics::{set_metrics, Metrics, PrometheusMetrics};
+/// use std::sync::Arc;
+///
+/// let metrics = Arc::new(PrometheusMetrics::new());
+/// set_metrics(metrics.clone());
+///
+/// metrics.event_sent("kafka", 128);
+/// assert!(metrics
+///     .render()
+///     .contains("cloudevents_events_sent_total{binding=\"kafka\"} 1\n"));
+/// ```
+#[derive(Debug, Default)]
+pub struct PrometheusMetrics {
+    registry: Mutex<Registry>,
+}
+
+impl PrometheusMetrics {
+    /// Create a new [`PrometheusMetrics`], with all the metrics at zero
+    pub fn new() -> Self {
+        PrometheusMetrics::default()
+    }
+
+    /// Render the metrics in the Prometheus text format
+    pub fn render(&self) -> String {
+        let registry = match self.registry.lock() {
+            Ok(registry) => registry,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        let mut out = String::new();
+        write_counter(
+            &mut out,
+            "cloudevents_events_sent_total",
+            "Number of events sent",
+            &registry.sent,
+        );
+        write_counter(
+            &mut out,
+            "cloudevents_sent_bytes_total",
+            "Size in bytes of the events sent",
+            &registry.sent_bytes,
+        );
+        write_counter(
+            &mut out,
+            "cloudevents_events_received_total",
+            "Number of events received",
+            &registry.received,
+        );
+        write_counter(
+            &mut out,
+            "cloudevents_received_bytes_total",
+            "Size in bytes of the events received",
+            &registry.received_bytes,
+        );
+
+        let name = "cloudevents_serialization_duration_seconds";
+        write_header(
+            &mut out,
+            name,
+            "Duration of the serialization of the events sent",
+            "histogram",
+        );
+        for (binding, histogram) in &registry.serialization {
+            for (bound, count) in LATENCY_BUCKETS.iter().zip(histogram.buckets.iter()) {
+                let _ = writeln!(
+                    out,
+                    "{}_bucket{{binding=\"{}\",le=\"{}\"}} {}",
+                    name, binding, bound, count
+                );
+            }
+            let _ = writeln!(
+                out,
+                "{}_bucket{{binding=\"{}\",le=\"+Inf\"}} {}",
+                name, binding, histogram.count
+            );
+            let _ = writeln!(
+                out,
+                "{}_sum{{binding=\"{}\"}} {}",
+                name, binding, histogram.sum
+            );
+            let _ = writeln!(
+                out,
+                "{}_count{{binding=\"{}\"}} {}",
+                name, binding, histogram.count
+            );
+        }
+
+        let name = "cloudevents_parse_failures_total";
+        write_header(
+            &mut out,
+            name,
+            "Number of messages which couldn't be parsed",
+            "counter",
+        );
+        for ((binding, kind), value) in &registry.parse_failures {
+            let _ = writeln!(
+                out,
+                "{}{{binding=\"{}\",kind=\"{}\"}} {}",
+                name, binding, kind, value
+            );
+        }
+        out
+    }
+
+    fn update(&self, update: impl FnOnce(&mut Registry)) {
+        match self.registry.lock() {
+            Ok(mut registry) => update(&mut registry),
+            Err(poisoned) => update(&mut poisoned.into_inner()),
+        }
+    }
+}
+
+impl Metrics for PrometheusMetrics {
+    fn event_sent(&self, binding: &'static str, bytes: usize) {
+        self.update(|registry| {
+            *registry.sent.entry(binding).or_default() += 1;
+            *registry.sent_bytes.entry(binding).or_default() += bytes as u64;
+        })
+    }
+
+    fn event_received(&self, binding: &'static str, bytes: usize) {
+        self.update(|registry| {
+            *registry.received.entry(binding).or_default() += 1;
+            *registry.received_bytes.entry(binding).or_default() += bytes as u64;
+        })
+    }
+
+    fn serialization_latency(&self, binding: &'static str, latency: Duration) {
+        let seconds = latency.as_secs_f64();
+        self.update(|registry| {
+            let histogram = registry.serialization.entry(binding).or_default();
+            for (bound, count) in LATENCY_BUCKETS.iter().zip(histogram.buckets.iter_mut()) {
+                if seconds <= *bound {
+                    *count += 1;
+                }
+            }
+            histogram.sum += seconds;
+            histogram.count += 1;
+        })
+    }
+
+    fn parse_failure(&self, binding: &'static str, kind: &'static str) {
+        self.update(|registry| {
+            *registry.parse_failures.entry((binding, kind)).or_default() += 1;
+        })
+    }
+}
+
+fn write_header(out: &mut String, name: &str, help: &str, ty: &str) {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} {}", name, ty);
+}
+
+fn write_counter(out: &mut String, name: &str, help: &str, values: &BTreeMap<&'static str, u64>) {
+    write_header(out, name, help, "counter");
+    for (binding, value) in values {
+        let _ = writeln!(out, "{}{{binding=\"{}\"}} {}", name, binding, value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render() {
+        let metrics = PrometheusMetrics::new();
+        metrics.event_sent("kafka", 100);
+        metrics.event_sent("kafka", 50);
+        metrics.event_received("http", 10);
+        metrics.serialization_latency("kafka", Duration::from_micros(200));
+        metrics.parse_failure("http", "wrong_encoding");
+        metrics.parse_failure("http", "wrong_encoding");
+
+        let rendered = metrics.render();
+        for expected in &[
+            "# TYPE cloudevents_events_sent_total counter\n",
+            "cloudevents_events_sent_total{binding=\"kafka\"} 2\n",
+            "cloudevents_sent_bytes_total{binding=\"kafka\"} 150\n",
+            "cloudevents_events_received_total{binding=\"http\"} 1\n",
+            "cloudevents_received_bytes_total{binding=\"http\"} 10\n",
+            "# TYPE cloudevents_serialization_duration_seconds histogram\n",
+            "cloudevents_serialization_duration_seconds_bucket{binding=\"kafka\",le=\"0.0001\"} 0\n",
+            "cloudevents_serialization_duration_seconds_bucket{binding=\"kafka\",le=\"0.0005\"} 1\n",
+            "cloudevents_serialization_duration_seconds_bucket{binding=\"kafka\",le=\"+Inf\"} 1\n",
+            "cloudevents_serialization_duration_seconds_count{binding=\"kafka\"} 1\n",
+            "cloudevents_parse_failures_total{binding=\"http\",kind=\"wrong_encoding\"} 2\n",
+        ] {
+            assert!(rendered.contains(expected), "missing {}", expected);
+        }
+    }
+}
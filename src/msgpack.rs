@@ -0,0 +1,167 @@
+//! [MessagePack](https://msgpack.org) structured format of an [`Event`], enabled with the
+//! `msgpack` feature.
+//!
+//! The event is encoded with [`rmp_serde`] with the same fields as the JSON event format, as a
+//! MessagePack map, so it's smaller, e.g. to send structured mode messages on a constrained link.
+//! Integers are encoded in their smallest MessagePack representation. Run
+//! `cargo bench --bench formats --features msgpack` to compare its speed with the JSON format.
+//!
+//! ```
+//! use cloudevents::msgpack::{decode_event, encode_event};
+//! use cloudevents::{EventBuilder, EventBuilderV10};
+//! use serde_json::json;
+//!
+//! let event = EventBuilderV10::new()
+//!     .id("0001")
+//!     .source("http://localhost/")
+//!     .ty("example.demo")
+//!     .data("application/json", json!({"hello": "world"}))
+//!     .build()
+//!     .unwrap();
+//!
+//! let bytes = encode_event(&event).unwrap();
+//! assert!(bytes.len() < serde_json::to_vec(&event).unwrap().len());
+//! assert_eq!(event, decode_event(&bytes).unwrap());
+//! ```
+
+use crate::message::{Error, EventFormat, Result};
+use crate::Event;
+use serde::Deserialize;
+
+/// Content type of the MessagePack structured format
+pub const CLOUDEVENTS_MSGPACK_CONTENT_TYPE: &str = "application/cloudevents+msgpack";
+
+/// [`EventFormat`] of the MessagePack structured format, to register in the
+/// [`EventFormats`](crate::message::EventFormats) read by the bindings
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MsgPackFormat;
+
+impl EventFormat for MsgPackFormat {
+    fn content_type(&self) -> &str {
+        CLOUDEVENTS_MSGPACK_CONTENT_TYPE
+    }
+
+    fn encode(&self, event: &Event) -> Result<Vec<u8>> {
+        encode_event(event)
+    }
+
+    fn decode(&self, bytes: &[u8]) -> Result<Event> {
+        decode_event(bytes)
+    }
+}
+
+macro_rules! msgpack_error {
+    ($($arg:tt)*) => {
+        Error::Other {
+            source: format!($($arg)*).into(),
+        }
+    };
+}
+
+/// Maximum nesting of arrays and maps, to bound the recursion on untrusted input
+const MAX_DEPTH: usize = 128;
+
+/// Encode an [`Event`] with the MessagePack structured format
+pub fn encode_event(event: &Event) -> Result<Vec<u8>> {
+    rmp_serde::to_vec_named(event).map_err(|e| msgpack_error!("{}", e))
+}
+
+/// Decode an [`Event`] encoded with the MessagePack structured format
+pub fn decode_event(bytes: &[u8]) -> Result<Event> {
+    let mut rest = bytes;
+    let mut deserializer = rmp_serde::Deserializer::new(&mut rest);
+    deserializer.set_max_depth(MAX_DEPTH);
+    let event = Event::deserialize(&mut deserializer).map_err(|e| msgpack_error!("{}", e))?;
+    if !rest.is_empty() {
+        return Err(msgpack_error!(
+            "Trailing bytes after the event at offset {}",
+            bytes.len() - rest.len()
+        ));
+    }
+    Ok(event)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EventBuilder, EventBuilderV03, EventBuilderV10};
+    use chrono::{TimeZone, Utc};
+    use serde_json::json;
+
+    #[test]
+    fn roundtrip() {
+        let events = vec![
+            EventBuilderV10::new()
+                .id("0001")
+                .source("http://localhost/")
+                .ty("example.test")
+                .time(Utc.timestamp(1_600_000_000, 0))
+                .extension("someint", -1_000_000)
+                .extension("somebool", true)
+                .data(
+                    "application/json",
+                    json!({
+                        "n": [0, 200, 70000, -20, -200, 5_000_000_000u64, 1.5],
+                        "s": "x".repeat(300)
+                    }),
+                )
+                .build()
+                .unwrap(),
+            EventBuilderV10::new()
+                .id("0002")
+                .source("http://localhost/")
+                .ty("example.test")
+                .data("application/octet-stream", vec![1u8, 2, 3])
+                .build()
+                .unwrap(),
+            EventBuilderV03::new()
+                .id("0003")
+                .source("http://localhost/")
+                .ty("example.test")
+                .data("text/plain", "hello")
+                .build()
+                .unwrap(),
+        ];
+
+        for event in events {
+            let bytes = encode_event(&event).unwrap();
+            assert_eq!(event, decode_event(&bytes).unwrap());
+        }
+    }
+
+    #[test]
+    fn encoding() {
+        let event = EventBuilderV10::new()
+            .id("0001")
+            .source("http://localhost/")
+            .ty("example.test")
+            .extension("someint", 200)
+            .build()
+            .unwrap();
+        let bytes = encode_event(&event).unwrap();
+
+        // A map of the fields of the JSON event format, with the smallest integers
+        assert_eq!(
+            serde_json::to_value(&event).unwrap(),
+            rmp_serde::from_slice::<serde_json::Value>(&bytes).unwrap()
+        );
+        assert!(bytes.windows(2).any(|w| w == [0xcc, 0xc8]));
+    }
+
+    #[test]
+    fn invalid_input() {
+        let event = EventBuilderV10::new()
+            .id("0001")
+            .source("http://localhost/")
+            .ty("example.test")
+            .build()
+            .unwrap();
+        let bytes = encode_event(&event).unwrap();
+
+        assert!(decode_event(&bytes[..bytes.len() - 1]).is_err());
+        assert!(decode_event(&[bytes.as_slice(), &[0xc0]].concat()).is_err());
+        assert!(decode_event(&[0xc1]).is_err());
+        assert!(decode_event(&[0x91; 1000]).is_err());
+        assert!(decode_event(&[0xdd, 0xff, 0xff, 0xff, 0xff]).is_err());
+    }
+}
@@ -0,0 +1,147 @@
+//! [NDJSON](https://github.com/ndjson/ndjson-spec) (JSON Lines) format of a batch of events,
+//! with one event per line in the JSON event format, e.g. to ship events as logs, store them in
+//! files or pipe them between command line tools.
+//!
+//! The input is parsed event by event with the parser of [`BatchReader`], so a stream of any
+//! length can be processed without buffering it: [`read_events`] reads the events of a [`Read`],
+//! and [`NdjsonDecoder`] the events of the chunks received from the network. Both stop after the
+//! first invalid event.
+//!
+//! ```
+//! use cloudevents::ndjson::{read_events, write_events};
+//! use cloudevents::{Event, EventBuilder, EventBuilderV10};
+//!
+//! let events: Vec<Event> = (1..=3)
+//!     .map(|i| {
+//!         EventBuilderV10::new()
+//!             .id(i.to_string())
+//!             .source("http://localhost/")
+//!             .ty("example.demo")
+//!             .build()
+//!             .unwrap()
+//!     })
+//!     .collect();
+//!
+//! let mut buffer = Vec::new();
+//! write_events(&mut buffer, &events).unwrap();
+//! assert_eq!(3, buffer.iter().filter(|b| **b == b'\n').count());
+//!
+//! let read: Vec<Event> = read_events(&buffer[..]).collect::<Result<_, _>>().unwrap();
+//! assert_eq!(events, read);
+//! ```
+
+use crate::event::{BatchReader, EventSplitter};
+use crate::message::Result;
+use crate::Event;
+use std::io::{Read, Write};
+
+/// Content type of an NDJSON stream
+pub const NDJSON_CONTENT_TYPE: &str = "application/x-ndjson";
+
+/// Write `events` to `writer`, one per line
+pub fn write_events<'a, W: Write>(
+    mut writer: W,
+    events: impl IntoIterator<Item = &'a Event>,
+) -> Result<()> {
+    for event in events {
+        serde_json::to_writer(&mut writer, event)?;
+        writer.write_all(b"\n")?;
+    }
+    Ok(())
+}
+
+/// Encode `events` in NDJSON
+pub fn encode_events<'a>(events: impl IntoIterator<Item = &'a Event>) -> Result<Vec<u8>> {
+    let mut buffer = Vec::new();
+    write_events(&mut buffer, events)?;
+    Ok(buffer)
+}
+
+/// Get the iterator of the events read from `reader`, skipping the blank lines
+pub fn read_events<R: Read>(reader: R) -> BatchReader<R> {
+    BatchReader::new(reader)
+}
+
+/// Incremental decoder of NDJSON input, parsing each event as soon as it's complete
+#[derive(Debug, Default)]
+pub struct NdjsonDecoder {
+    splitter: EventSplitter,
+}
+
+impl NdjsonDecoder {
+    /// Create a new [`NdjsonDecoder`]
+    pub fn new() -> Self {
+        NdjsonDecoder::default()
+    }
+
+    /// Decode the events completed by `chunk`
+    pub fn feed(&mut self, chunk: &[u8]) -> Vec<Result<Event>> {
+        let mut events = Vec::new();
+        let mut rest = chunk;
+        while !rest.is_empty() {
+            let (consumed, event) = self.splitter.next_event(rest);
+            events.extend(event);
+            rest = &rest[consumed..];
+        }
+        events
+    }
+
+    /// Check the end of the input, returning an error if the last event is incomplete
+    pub fn finish(&mut self) -> Option<Result<Event>> {
+        self.splitter.finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EventBuilder, EventBuilderV10};
+    use serde_json::json;
+
+    fn event(id: &str) -> Event {
+        EventBuilderV10::new()
+            .id(id)
+            .source("http://localhost/")
+            .ty("example.test")
+            .data("application/json", json!({"multi": "line\ntext"}))
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn read_skips_blank_lines() {
+        let input = format!(
+            "{}\r\n\n  \n{}\nnot an event\n{}",
+            serde_json::to_string(&event("1")).unwrap(),
+            serde_json::to_string(&event("2")).unwrap(),
+            serde_json::to_string(&event("3")).unwrap()
+        );
+        let events: Vec<Result<Event>> = read_events(input.as_bytes()).collect();
+
+        assert_eq!(3, events.len());
+        assert_eq!(&event("1"), events[0].as_ref().unwrap());
+        assert_eq!(&event("2"), events[1].as_ref().unwrap());
+        assert!(events[2].is_err());
+    }
+
+    #[test]
+    fn decode_chunks() {
+        let expected = vec![event("1"), event("2")];
+        let input = encode_events(&expected).unwrap();
+
+        for size in 1..input.len() {
+            let mut decoder = NdjsonDecoder::new();
+            let mut events = Vec::new();
+            for chunk in input.chunks(size) {
+                events.extend(decoder.feed(chunk));
+            }
+            assert!(decoder.finish().is_none());
+            let events: Vec<Event> = events.into_iter().map(Result::unwrap).collect();
+            assert_eq!(expected, events, "chunks of {}", size);
+        }
+
+        let mut decoder = NdjsonDecoder::new();
+        assert_eq!(1, decoder.feed(&input[..input.len() - 2]).len());
+        assert!(decoder.finish().unwrap().is_err());
+    }
+}
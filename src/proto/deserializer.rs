@@ -0,0 +1,214 @@
+use super::{
+    CloudEvent, CloudEventAttributeValue, CloudEventAttributeValueAttr, CloudEventData,
+    PROTOBUF_CONTENT_TYPE,
+};
+use crate::event::DataAttributesWriter;
+use crate::event::{is_json_content_type, Data, SpecVersion, UriReference};
+use crate::message::{BinaryDeserializer, BinarySerializer, Error, MessageAttributeValue, Result};
+use crate::{AttributesReader, Event};
+use bytes::Bytes;
+use chrono::{TimeZone, Utc};
+use std::convert::TryFrom;
+
+impl TryFrom<CloudEventAttributeValue> for MessageAttributeValue<'_> {
+    type Error = Error;
+
+    fn try_from(value: CloudEventAttributeValue) -> Result<Self> {
+        match value.attr {
+            Some(CloudEventAttributeValueAttr::CeBoolean(b)) => {
+                Ok(MessageAttributeValue::Boolean(b))
+            }
+            Some(CloudEventAttributeValueAttr::CeInteger(i)) => {
+                Ok(MessageAttributeValue::Integer(i.into()))
+            }
+            Some(CloudEventAttributeValueAttr::CeString(s)) => Ok(MessageAttributeValue::from(s)),
+            Some(CloudEventAttributeValueAttr::CeBytes(b)) => Ok(MessageAttributeValue::from(b)),
+            Some(CloudEventAttributeValueAttr::CeUri(u)) => {
+                Ok(MessageAttributeValue::from(url::Url::parse(&u)?))
+            }
+            Some(CloudEventAttributeValueAttr::CeUriRef(u)) => {
+                Ok(MessageAttributeValue::from(UriReference::parse(&u)?))
+            }
+            Some(CloudEventAttributeValueAttr::CeTimestamp(t)) => Utc
+                .timestamp_opt(t.seconds, t.nanos as u32)
+                .single()
+                .map(MessageAttributeValue::from)
+                .ok_or_else(|| Error::Other {
+                    source: format!("Invalid timestamp: {:?}", t).into(),
+                }),
+            None => Err(Error::Other {
+                source: "Missing attribute value".into(),
+            }),
+        }
+    }
+}
+
+impl BinaryDeserializer for CloudEvent {
+    fn deserialize_binary<R: Sized, V: BinarySerializer<R>>(self, mut visitor: V) -> Result<R> {
+        let spec_version = SpecVersion::try_from(self.spec_version.as_str())?;
+        visitor = visitor.set_spec_version(spec_version.clone())?;
+
+        visitor = visitor.set_attribute("id", MessageAttributeValue::from(self.id))?;
+        visitor = visitor.set_attribute("source", MessageAttributeValue::from(self.source))?;
+        visitor = visitor.set_attribute("type", MessageAttributeValue::from(self.r#type))?;
+
+        let attributes = spec_version.attribute_names();
+
+        for (name, value) in self.attributes {
+            let value = MessageAttributeValue::try_from(value)?;
+            if attributes.contains(&name.as_str()) {
+                visitor = visitor.set_attribute(&name, value)?
+            } else {
+                visitor = visitor.set_extension(&name, value)?
+            }
+        }
+
+        match self.data {
+            Some(CloudEventData::BinaryData(b)) => visitor.end_with_data(Bytes::from(b)),
+            Some(CloudEventData::TextData(s)) => visitor.end_with_data(Bytes::from(s)),
+            Some(CloudEventData::ProtoData(a)) => visitor.end_with_data(Bytes::from(a.value)),
+            None => visitor.end(),
+        }
+    }
+}
+
+impl TryFrom<CloudEvent> for Event {
+    type Error = Error;
+
+    fn try_from(mut proto: CloudEvent) -> Result<Self> {
+        let data = proto.data.take();
+        let mut event = BinaryDeserializer::into_event(proto)?;
+
+        event.data = match data {
+            Some(CloudEventData::BinaryData(b)) => Some(Data::Binary(b)),
+            Some(CloudEventData::TextData(s)) => match event.get_datacontenttype() {
+                Some(ct) if is_json_content_type(ct) => Some(Data::Json(serde_json::from_str(&s)?)),
+                _ => Some(Data::String(s)),
+            },
+            Some(CloudEventData::ProtoData(a)) => {
+                if event.get_datacontenttype().is_none() {
+                    event
+                        .attributes
+                        .set_datacontenttype(Some(PROTOBUF_CONTENT_TYPE));
+                }
+                if event.get_dataschema().is_none() {
+                    if let Ok(type_url) = url::Url::parse(&a.type_url) {
+                        event.attributes.set_dataschema(Some(type_url));
+                    }
+                }
+                Some(Data::Binary(a.value))
+            }
+            None => None,
+        };
+
+        Ok(event)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EventBuilder, EventBuilderV03, EventBuilderV10};
+    use prost::Message;
+    use serde_json::json;
+
+    fn roundtrip(event: Event) -> Event {
+        let bytes = CloudEvent::from(event).encode_to_vec();
+        Event::try_from(CloudEvent::decode(bytes.as_slice()).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_roundtrip_v10() {
+        let event = EventBuilderV10::new()
+            .id("0001")
+            .ty("example.test")
+            .source("http://localhost/")
+            .subject("cloudevent")
+            .time(Utc.timestamp(1_600_000_000, 123_000_000))
+            .data_with_schema(
+                "application/json",
+                "http://localhost/schema",
+                json!({"hello": "world"}),
+            )
+            .extension("someint", 10)
+            .extension("somebool", true)
+            .extension("somestring", "hello")
+            .build()
+            .unwrap();
+
+        assert_eq!(event.clone(), roundtrip(event));
+    }
+
+    #[test]
+    fn test_roundtrip_v03() {
+        let event = EventBuilderV03::new()
+            .id("0001")
+            .ty("example.test")
+            .source("http://localhost/")
+            .data("text/plain", String::from("hello"))
+            .build()
+            .unwrap();
+
+        assert_eq!(event.clone(), roundtrip(event));
+    }
+
+    #[test]
+    fn test_roundtrip_binary_data() {
+        let event = EventBuilderV10::new()
+            .id("0001")
+            .ty("example.test")
+            .source("http://localhost/")
+            .data("application/octet-stream", vec![0u8, 1, 2, 3])
+            .build()
+            .unwrap();
+
+        assert_eq!(event.clone(), roundtrip(event));
+    }
+
+    #[test]
+    fn test_attribute_types() {
+        let event = EventBuilderV10::new()
+            .id("0001")
+            .ty("example.test")
+            .source("http://localhost/")
+            .time(Utc.timestamp(1_600_000_000, 0))
+            .extension("someint", 10)
+            .extension("bigint", i64::from(i32::MAX) + 1)
+            .build()
+            .unwrap();
+
+        let proto = CloudEvent::from(event);
+
+        assert_eq!("0001", proto.id);
+        assert_eq!("1.0", proto.spec_version);
+        assert_eq!(
+            Some(CloudEventAttributeValueAttr::CeTimestamp(
+                prost_types::Timestamp {
+                    seconds: 1_600_000_000,
+                    nanos: 0
+                }
+            )),
+            proto.attributes["time"].attr
+        );
+        assert_eq!(
+            Some(CloudEventAttributeValueAttr::CeInteger(10)),
+            proto.attributes["someint"].attr
+        );
+        assert_eq!(
+            Some(CloudEventAttributeValueAttr::CeString(String::from(
+                "2147483648"
+            ))),
+            proto.attributes["bigint"].attr
+        );
+    }
+
+    #[test]
+    fn test_invalid_spec_version() {
+        let proto = CloudEvent {
+            spec_version: String::from("2.0"),
+            ..Default::default()
+        };
+
+        assert!(Event::try_from(proto).is_err());
+    }
+}
@@ -0,0 +1,139 @@
+use super::{CloudEvent, CloudEventBatch};
+use crate::message::{Error, Result};
+use crate::Event;
+use prost::Message;
+use std::convert::TryFrom;
+
+/// Content type of a structured mode message encoded with the Protobuf Event Format
+pub const CLOUDEVENTS_PROTOBUF_CONTENT_TYPE: &str = "application/cloudevents+protobuf";
+/// Content type of a batch of events encoded with the Protobuf Event Format
+pub const CLOUDEVENTS_BATCH_PROTOBUF_CONTENT_TYPE: &str = "application/cloudevents-batch+protobuf";
+
+/// Encode an [`Event`] with the Protobuf Event Format
+pub fn encode_event(event: Event) -> Vec<u8> {
+    CloudEvent::from(event).encode_to_vec()
+}
+
+/// Decode an [`Event`] encoded with the Protobuf Event Format
+pub fn decode_event(bytes: &[u8]) -> Result<Event> {
+    Event::try_from(CloudEvent::decode(bytes).map_err(|e| Error::Other {
+        source: Box::new(e),
+    })?)
+}
+
+/// Encode a batch of [`Event`]s with the Protobuf Event Format
+pub fn encode_batch(events: Vec<Event>) -> Vec<u8> {
+    CloudEventBatch {
+        events: events.into_iter().map(CloudEvent::from).collect(),
+    }
+    .encode_to_vec()
+}
+
+/// Decode a batch of [`Event`]s encoded with the Protobuf Event Format
+pub fn decode_batch(bytes: &[u8]) -> Result<Vec<Event>> {
+    CloudEventBatch::decode(bytes)
+        .map_err(|e| Error::Other {
+            source: Box::new(e),
+        })?
+        .events
+        .into_iter()
+        .map(Event::try_from)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::Data;
+    use crate::proto::{CloudEventData, PROTOBUF_CONTENT_TYPE};
+    use crate::{AttributesReader, EventBuilder, EventBuilderV10};
+    use prost_types::Any;
+    use serde_json::json;
+
+    #[test]
+    fn test_event_roundtrip() {
+        let event = EventBuilderV10::new()
+            .id("0001")
+            .ty("example.test")
+            .source("http://localhost/")
+            .data("application/json", json!({"hello": "world"}))
+            .extension("someint", 10)
+            .build()
+            .unwrap();
+
+        assert_eq!(event.clone(), decode_event(&encode_event(event)).unwrap());
+    }
+
+    #[test]
+    fn test_batch_roundtrip() {
+        let events = vec![
+            EventBuilderV10::new()
+                .id("0001")
+                .ty("example.test")
+                .source("http://localhost/")
+                .build()
+                .unwrap(),
+            EventBuilderV10::new()
+                .id("0002")
+                .ty("example.test")
+                .source("http://localhost/")
+                .data("application/octet-stream", vec![0u8, 1, 2])
+                .build()
+                .unwrap(),
+        ];
+
+        assert_eq!(events.clone(), decode_batch(&encode_batch(events)).unwrap());
+    }
+
+    #[test]
+    fn test_proto_data() {
+        let event = EventBuilderV10::new()
+            .id("0001")
+            .ty("example.test")
+            .source("http://localhost/")
+            .data_with_schema(
+                PROTOBUF_CONTENT_TYPE,
+                "http://localhost/example.Message",
+                vec![8u8, 1],
+            )
+            .build()
+            .unwrap();
+
+        let proto = CloudEvent::from(event.clone());
+        assert_eq!(
+            Some(CloudEventData::ProtoData(Any {
+                type_url: String::from("http://localhost/example.Message"),
+                value: vec![8u8, 1],
+            })),
+            proto.data
+        );
+
+        assert_eq!(event, decode_event(&proto.encode_to_vec()).unwrap());
+    }
+
+    #[test]
+    fn test_proto_data_without_content_type() {
+        let proto = CloudEvent {
+            id: String::from("0001"),
+            source: String::from("http://localhost/"),
+            spec_version: String::from("1.0"),
+            r#type: String::from("example.test"),
+            data: Some(CloudEventData::ProtoData(Any {
+                type_url: String::from("type.googleapis.com/example.Message"),
+                value: vec![8u8, 1],
+            })),
+            ..Default::default()
+        };
+
+        let event = decode_event(&proto.encode_to_vec()).unwrap();
+
+        assert_eq!(Some(PROTOBUF_CONTENT_TYPE), event.get_datacontenttype());
+        assert_eq!(None, event.get_dataschema());
+        assert_eq!(Some(Data::Binary(vec![8u8, 1])), event.get_data::<Data>());
+    }
+
+    #[test]
+    fn test_decode_invalid_bytes() {
+        assert!(decode_event(&[0xff, 0xff, 0xff]).is_err());
+    }
+}
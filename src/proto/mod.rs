@@ -0,0 +1,104 @@
+//! Types of the [Protobuf Event Format](https://github.com/cloudevents/spec/blob/v1.0.1/protobuf-format.md),
+//! compiled with [`prost`](https://docs.rs/prost) from the official `cloudevents.proto`,
+//! and conversions from and to [`Event`].
+//!
+//! [`encode_event`] and [`decode_event`] implement the format for structured mode messages,
+//! with content type [`CLOUDEVENTS_PROTOBUF_CONTENT_TYPE`].
+//!
+//! ```
+//! use cloudevents::proto::CloudEvent;
+//! use cloudevents::{Event, EventBuilder, EventBuilderV10};
+//! use prost::Message;
+//! use std::convert::TryFrom;
+//!
+//! let event = EventBuilderV10::new()
+//!     .id("0001")
+//!     .source("http://localhost/")
+//!     .ty("example.demo")
+//!     .build()
+//!     .unwrap();
+//!
+//! let bytes = CloudEvent::from(event.clone()).encode_to_vec();
+//! let decoded = CloudEvent::decode(bytes.as_slice()).unwrap();
+//! assert_eq!(event, Event::try_from(decoded).unwrap());
+//! ```
+
+#[rustfmt::skip]
+#[path = "io.cloudevents.v1.rs"]
+mod generated;
+mod deserializer;
+mod format;
+
+pub use format::{decode_batch, decode_event, encode_batch, encode_event};
+pub use format::{CLOUDEVENTS_BATCH_PROTOBUF_CONTENT_TYPE, CLOUDEVENTS_PROTOBUF_CONTENT_TYPE};
+pub use generated::cloud_event::cloud_event_attribute_value::Attr as CloudEventAttributeValueAttr;
+pub use generated::cloud_event::CloudEventAttributeValue;
+pub use generated::cloud_event::Data as CloudEventData;
+pub use generated::{CloudEvent, CloudEventBatch};
+
+/// `datacontenttype` of events whose data is a protobuf message, carried as `proto_data`
+pub const PROTOBUF_CONTENT_TYPE: &str = "application/protobuf";
+
+use crate::event::{AttributeValue, Data};
+use crate::{AttributesReader, Event};
+use prost_types::{Any, Timestamp};
+use std::convert::TryFrom;
+
+impl From<AttributeValue<'_>> for CloudEventAttributeValue {
+    fn from(value: AttributeValue<'_>) -> Self {
+        let attr = match value {
+            AttributeValue::SpecVersion(s) => CloudEventAttributeValueAttr::CeString(s.to_string()),
+            AttributeValue::String(s) => CloudEventAttributeValueAttr::CeString(s.to_string()),
+            AttributeValue::URI(u) => CloudEventAttributeValueAttr::CeUri(u.to_string()),
+            AttributeValue::URIRef(u) => CloudEventAttributeValueAttr::CeUriRef(u.to_string()),
+            AttributeValue::Boolean(b) => CloudEventAttributeValueAttr::CeBoolean(*b),
+            // The CloudEvents Integer type is 32-bit, so larger values are sent as strings
+            AttributeValue::Integer(i) => match i32::try_from(*i) {
+                Ok(i) => CloudEventAttributeValueAttr::CeInteger(i),
+                Err(_) => CloudEventAttributeValueAttr::CeString(i.to_string()),
+            },
+            AttributeValue::Time(t) => CloudEventAttributeValueAttr::CeTimestamp(Timestamp {
+                seconds: t.timestamp(),
+                nanos: t.timestamp_subsec_nanos() as i32,
+            }),
+        };
+        CloudEventAttributeValue { attr: Some(attr) }
+    }
+}
+
+impl From<Event> for CloudEvent {
+    fn from(event: Event) -> Self {
+        let mut proto = CloudEvent::default();
+
+        for (name, value) in event.attributes_iter() {
+            match (name, value) {
+                ("id", AttributeValue::String(s)) => proto.id = s.to_string(),
+                ("source", AttributeValue::URIRef(u)) => proto.source = u.to_string(),
+                ("specversion", AttributeValue::SpecVersion(s)) => {
+                    proto.spec_version = s.to_string()
+                }
+                ("type", AttributeValue::String(s)) => proto.r#type = s.to_string(),
+                (name, value) => {
+                    proto.attributes.insert(name.to_string(), value.into());
+                }
+            }
+        }
+
+        // Binary data of a protobuf message is carried as proto_data,
+        // using the dataschema as the type url of the message
+        let is_protobuf = event.get_datacontenttype() == Some(PROTOBUF_CONTENT_TYPE);
+        let type_url = event
+            .get_dataschema()
+            .map(|u| u.to_string())
+            .unwrap_or_default();
+
+        proto.data = event.data.map(|data| match data {
+            Data::Binary(b) if is_protobuf => CloudEventData::ProtoData(Any { type_url, value: b }),
+            Data::Binary(b) => CloudEventData::BinaryData(b),
+            Data::String(s) => CloudEventData::TextData(s),
+            Data::Json(j) => CloudEventData::TextData(j.to_string()),
+        });
+
+        proto
+    }
+}
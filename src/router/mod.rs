@@ -0,0 +1,352 @@
+use crate::event::{AttributesReader, Event, TypedEventError};
+use std::future::Future;
+use std::pin::Pin;
+
+#[cfg(feature = "macros")]
+pub use cloudevents_sdk_macros::cloudevent_handler;
+
+/// Matcher of a string attribute value, used by [`Route`]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum Matcher {
+    /// Matches any value, including a missing one
+    #[default]
+    Any,
+    /// Matches a value equal to the provided one
+    Exact(String),
+    /// Matches a value starting with the provided one
+    Prefix(String),
+    /// Matches a value ending with the provided one
+    Suffix(String),
+}
+
+impl Matcher {
+    /// Create a new [`Matcher::Exact`]
+    pub fn exact(value: impl Into<String>) -> Self {
+        Matcher::Exact(value.into())
+    }
+
+    /// Create a new [`Matcher::Prefix`]
+    pub fn prefix(value: impl Into<String>) -> Self {
+        Matcher::Prefix(value.into())
+    }
+
+    /// Create a new [`Matcher::Suffix`]
+    pub fn suffix(value: impl Into<String>) -> Self {
+        Matcher::Suffix(value.into())
+    }
+
+    /// Returns `true` if `value` matches. A missing value is matched only by [`Matcher::Any`].
+    pub fn matches(&self, value: Option<&str>) -> bool {
+        match (self, value) {
+            (Matcher::Any, _) => true,
+            (Matcher::Exact(expected), Some(value)) => value == expected,
+            (Matcher::Prefix(prefix), Some(value)) => value.starts_with(prefix.as_str()),
+            (Matcher::Suffix(suffix), Some(value)) => value.ends_with(suffix.as_str()),
+            (_, None) => false,
+        }
+    }
+}
+
+/// Conditions on `type`, `source` and `subject` an [`Event`] must satisfy to be dispatched to a
+/// handler of [`EventRouter`]. By default, a [`Route`] matches any event.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Route {
+    ty: Matcher,
+    source: Matcher,
+    subject: Matcher,
+}
+
+impl Route {
+    /// Create a new [`Route`] matching any event
+    pub fn new() -> Self {
+        Route::default()
+    }
+
+    /// Set the [`Matcher`] of the `type` attribute
+    pub fn ty(mut self, matcher: Matcher) -> Self {
+        self.ty = matcher;
+        self
+    }
+
+    /// Set the [`Matcher`] of the `source` attribute
+    pub fn source(mut self, matcher: Matcher) -> Self {
+        self.source = matcher;
+        self
+    }
+
+    /// Set the [`Matcher`] of the `subject` attribute
+    pub fn subject(mut self, matcher: Matcher) -> Self {
+        self.subject = matcher;
+        self
+    }
+
+    /// Returns `true` if `event` satisfies all the matchers of this route
+    pub fn matches(&self, event: &Event) -> bool {
+        self.ty.matches(Some(event.get_type()))
+            && self.source.matches(Some(event.get_source().as_str()))
+            && self.subject.matches(event.get_subject())
+    }
+}
+
+/// Handler of the events dispatched by [`EventRouter`].
+///
+/// This trait is implemented for closures accepting an [`Event`]. Use `Box<dyn Fn(Event) -> R>`
+/// as handler type to register different closures in the same router.
+pub trait EventHandler {
+    /// Value returned by the handler, e.g. a [`Future`](std::future::Future) for async handlers
+    type Output;
+
+    /// Handle the event
+    fn handle(&self, event: Event) -> Self::Output;
+}
+
+impl<F, R> EventHandler for F
+where
+    F: Fn(Event) -> R,
+{
+    type Output = R;
+
+    fn handle(&self, event: Event) -> R {
+        self(event)
+    }
+}
+
+/// Future returned by an [`AsyncHandler`], failing if the event can't be converted to the
+/// argument of the handler
+pub type HandlerFuture<R> = Pin<Box<dyn Future<Output = Result<R, TypedEventError>> + Send>>;
+
+/// Async handler registered with [`EventRouter::handler`]
+pub type AsyncHandler<R> = Box<dyn Fn(Event) -> HandlerFuture<R> + Send + Sync>;
+
+/// Async handler with its [`Route`], registered with [`EventRouter::handler`].
+///
+/// With the `macros` feature, it's implemented by the async functions annotated with
+/// `#[cloudevent_handler]`, which become unit structs with the name of the function. The
+/// function takes one argument converted from the dispatched event with
+/// [`TryFromEvent`](crate::event::TryFromEvent), e.g. an [`EventData`](crate::event::EventData)
+/// type or a [`TypedEvent`](crate::event::TypedEvent), and its future must be [`Send`]:
+///
+/// ```ignore
+/// #[cloudevent_handler(type = "com.example.order.created")]
+/// async fn on_order_created(order: OrderCreated) -> String {
+///     format!("order {}", order.id)
+/// }
+///
+/// let router = EventRouter::new().handler(on_order_created);
+/// let reply = router.route(event).unwrap().await?;
+/// ```
+///
+/// The `type` argument of the attribute is optional when the argument implements
+/// [`EventData`](crate::event::EventData), matching its `TYPE`.
+pub trait RouteHandler<R> {
+    /// The route of the events dispatched to this handler
+    fn route(&self) -> Route;
+
+    /// Convert to the handler registered in [`EventRouter`]
+    fn into_handler(self) -> AsyncHandler<R>;
+}
+
+/// Router dispatching each [`Event`] to the handler of the first registered [`Route`] it matches,
+/// or to the fallback handler when no route matches.
+///
+/// ```
+/// use cloudevents::router::{EventRouter, Matcher, Route};
+/// use cloudevents::{AttributesReader, Event, EventBuilder, EventBuilderV10};
+///
+/// let router = EventRouter::<Box<dyn Fn(Event) -> String>>::new()
+///     .add(
+///         Route::new().ty(Matcher::prefix("com.example.order.")),
+///         Box::new(|e: Event| format!("order {}", e.get_subject().unwrap_or_default())),
+///     )
+///     .add(
+///         Route::new().source(Matcher::suffix("/payments")),
+///         Box::new(|e: Event| format!("payment {}", e.get_id())),
+///     );
+///
+/// let event = EventBuilderV10::new()
+///     .id("0001")
+///     .source("https://example.com/payments")
+///     .ty("com.example.payment.received")
+///     .build()
+///     .unwrap();
+/// assert_eq!("payment 0001", router.route(event).unwrap());
+///
+/// let event = EventBuilderV10::new()
+///     .id("0002")
+///     .source("https://example.com/")
+///     .ty("com.example.user.created")
+///     .build()
+///     .unwrap();
+/// assert!(router.route(event).is_none());
+/// ```
+#[derive(Debug, Clone)]
+pub struct EventRouter<H> {
+    routes: Vec<(Route, H)>,
+    fallback: Option<H>,
+}
+
+impl<H> EventRouter<H> {
+    /// Create a new [`EventRouter`] without routes
+    pub fn new() -> Self {
+        EventRouter {
+            routes: Vec::new(),
+            fallback: None,
+        }
+    }
+
+    /// Register `handler` for the events matching `route`.
+    /// Routes are evaluated in registration order.
+    pub fn add(mut self, route: Route, handler: H) -> Self {
+        self.routes.push((route, handler));
+        self
+    }
+
+    /// Set the handler of the events not matching any route
+    pub fn fallback(mut self, handler: H) -> Self {
+        self.fallback = Some(handler);
+        self
+    }
+
+    /// Get the handler `event` would be dispatched to
+    pub fn find(&self, event: &Event) -> Option<&H> {
+        self.routes
+            .iter()
+            .find(|(route, _)| route.matches(event))
+            .map(|(_, handler)| handler)
+            .or(self.fallback.as_ref())
+    }
+
+    /// Dispatch `event` to the matching handler, returning its output,
+    /// or `None` if no handler matches
+    pub fn route(&self, event: Event) -> Option<H::Output>
+    where
+        H: EventHandler,
+    {
+        self.find(&event).map(|handler| handler.handle(event))
+    }
+}
+
+impl<R> EventRouter<AsyncHandler<R>> {
+    /// Register `handler` for the events matching its [`Route`]
+    pub fn handler(self, handler: impl RouteHandler<R>) -> Self {
+        let route = handler.route();
+        self.add(route, handler.into_handler())
+    }
+}
+
+impl<H> Default for EventRouter<H> {
+    fn default() -> Self {
+        EventRouter::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EventBuilder, EventBuilderV10};
+
+    fn event(ty: &str, source: &str, subject: Option<&str>) -> Event {
+        let mut builder = EventBuilderV10::new().id("0001").ty(ty).source(source);
+        if let Some(subject) = subject {
+            builder = builder.subject(subject);
+        }
+        builder.build().unwrap()
+    }
+
+    #[test]
+    fn matchers() {
+        assert!(Matcher::Any.matches(None));
+        assert!(Matcher::exact("a.b").matches(Some("a.b")));
+        assert!(!Matcher::exact("a.b").matches(Some("a.bc")));
+        assert!(Matcher::prefix("a.").matches(Some("a.b")));
+        assert!(!Matcher::prefix("a.").matches(None));
+        assert!(Matcher::suffix(".b").matches(Some("a.b")));
+        assert!(!Matcher::suffix(".b").matches(Some("a.c")));
+    }
+
+    #[test]
+    fn route_in_registration_order() {
+        let router = EventRouter::new()
+            .add(
+                Route::new()
+                    .ty(Matcher::exact("example.created"))
+                    .subject(Matcher::prefix("orders/")),
+                1,
+            )
+            .add(Route::new().source(Matcher::exact("/orders")), 2)
+            .add(Route::new(), 3);
+
+        assert_eq!(
+            Some(&1),
+            router.find(&event("example.created", "/orders", Some("orders/1")))
+        );
+        assert_eq!(
+            Some(&2),
+            router.find(&event("example.created", "/orders", None))
+        );
+        assert_eq!(Some(&3), router.find(&event("example.deleted", "/", None)));
+    }
+
+    #[test]
+    fn route_dispatch_and_fallback() {
+        let created: fn(Event) -> &'static str = |_| "created";
+        let fallback: fn(Event) -> &'static str = |_| "fallback";
+        let router = EventRouter::new()
+            .add(Route::new().ty(Matcher::suffix(".created")), created)
+            .fallback(fallback);
+
+        assert_eq!(
+            Some("created"),
+            router.route(event("example.created", "/", None))
+        );
+        assert_eq!(
+            Some("fallback"),
+            router.route(event("example.deleted", "/", None))
+        );
+
+        let router: EventRouter<fn(Event)> = EventRouter::new();
+        assert_eq!(None, router.route(event("example.created", "/", None)));
+    }
+
+    #[cfg(feature = "macros")]
+    #[tokio::test]
+    async fn handler_macro() {
+        use crate::event::{EventData, ToEvent, TypedEventError};
+        use serde::{Deserialize, Serialize};
+
+        #[derive(Serialize, Deserialize, EventData)]
+        #[event(type = "example.created")]
+        struct Created {
+            id: u64,
+        }
+
+        #[cloudevent_handler]
+        async fn on_created(created: Created) -> u64 {
+            created.id
+        }
+
+        #[cloudevent_handler(type = "example.deleted")]
+        async fn on_deleted(deleted: Created) -> u64 {
+            deleted.id
+        }
+
+        let router = EventRouter::new().handler(on_created).handler(on_deleted);
+        assert_eq!(
+            1,
+            router
+                .route(Created { id: 1 }.to_event().unwrap())
+                .unwrap()
+                .await
+                .unwrap()
+        );
+        // The events routed to on_deleted fail the type check of Created
+        assert!(matches!(
+            router
+                .route(event("example.deleted", "/", None))
+                .unwrap()
+                .await,
+            Err(TypedEventError::UnexpectedType { .. })
+        ));
+        assert!(router.route(event("example.updated", "/", None)).is_none());
+    }
+}
@@ -0,0 +1,125 @@
+use super::{Error, FailureMode, SchemaResolver};
+use bytes::Bytes;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// [`SchemaResolver`] caching the schemas resolved by another resolver for a TTL.
+///
+/// In [`FailureMode::Open`], an expired schema is still served when the wrapped resolver fails,
+/// so the cache keeps working offline. In [`FailureMode::Closed`], the default, the failure is
+/// returned once the schema is expired.
+#[derive(Debug)]
+pub struct CachingResolver<R> {
+    resolver: R,
+    ttl: Duration,
+    mode: FailureMode,
+    cache: Mutex<HashMap<String, (Instant, Bytes)>>,
+}
+
+impl<R: SchemaResolver> CachingResolver<R> {
+    /// Create a new [`CachingResolver`] caching the schemas resolved by `resolver` for `ttl`
+    pub fn new(resolver: R, ttl: Duration) -> Self {
+        CachingResolver {
+            resolver,
+            ttl,
+            mode: FailureMode::Closed,
+            cache: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Set the [`FailureMode`] when the wrapped resolver fails to resolve an expired schema
+    pub fn failure_mode(mut self, mode: FailureMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Remove all the cached schemas
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, (Instant, Bytes)>> {
+        match self.cache.lock() {
+            Ok(cache) => cache,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+}
+
+impl<R: SchemaResolver> SchemaResolver for CachingResolver<R> {
+    fn resolve(&self, uri: &str) -> Result<Bytes, Error> {
+        let cached = self.lock().get(uri).cloned();
+        if let Some((resolved_at, schema)) = &cached {
+            if resolved_at.elapsed() < self.ttl {
+                return Ok(schema.clone());
+            }
+        }
+
+        match self.resolver.resolve(uri) {
+            Ok(schema) => {
+                self.lock()
+                    .insert(uri.to_string(), (Instant::now(), schema.clone()));
+                Ok(schema)
+            }
+            Err(e) => match (cached, self.mode) {
+                (Some((_, schema)), FailureMode::Open) => Ok(schema),
+                _ => Err(e),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+    /// Resolver counting the resolutions, which fail once it's offline
+    #[derive(Default)]
+    struct Remote {
+        calls: AtomicUsize,
+        offline: AtomicBool,
+    }
+
+    impl SchemaResolver for &Remote {
+        fn resolve(&self, uri: &str) -> Result<Bytes, Error> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            if self.offline.load(Ordering::SeqCst) {
+                Err(Error::NotFound {
+                    uri: uri.to_string(),
+                })
+            } else {
+                Ok(Bytes::from("{}"))
+            }
+        }
+    }
+
+    #[test]
+    fn ttl() {
+        let remote = Remote::default();
+        let cache = CachingResolver::new(&remote, Duration::from_secs(3600));
+        for _ in 0..3 {
+            assert_eq!(Bytes::from("{}"), cache.resolve("schema").unwrap());
+        }
+        assert_eq!(1, remote.calls.load(Ordering::SeqCst));
+
+        cache.clear();
+        cache.resolve("schema").unwrap();
+        assert_eq!(2, remote.calls.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn failure_modes() {
+        let remote = Remote::default();
+        let closed = CachingResolver::new(&remote, Duration::ZERO);
+        let open = CachingResolver::new(&remote, Duration::ZERO).failure_mode(FailureMode::Open);
+        closed.resolve("schema").unwrap();
+        open.resolve("schema").unwrap();
+
+        remote.offline.store(true, Ordering::SeqCst);
+        assert!(closed.resolve("schema").is_err());
+        assert_eq!(Bytes::from("{}"), open.resolve("schema").unwrap());
+        assert!(open.resolve("other").is_err());
+    }
+}
@@ -0,0 +1,116 @@
+use super::{Error, SchemaResolver};
+use bytes::Bytes;
+use std::path::{Component, Path, PathBuf};
+use url::Url;
+
+/// [`SchemaResolver`] reading the schemas mirrored in a local directory.
+///
+/// An absolute `http` or `https` URI is mapped to the path made of its host and path under the
+/// root directory, e.g. `https://example.com/schemas/order.json` to
+/// `<root>/example.com/schemas/order.json`, a relative URI reference to its path under the root
+/// directory, and a `file` URI to its path. The paths escaping the root directory are rejected.
+#[derive(Debug, Clone)]
+pub struct FileSystemResolver {
+    root: PathBuf,
+}
+
+impl FileSystemResolver {
+    /// Create a new [`FileSystemResolver`] reading the schemas under `root`
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        FileSystemResolver { root: root.into() }
+    }
+
+    /// Get the path of the schema identified by `uri`
+    pub fn path(&self, uri: &str) -> Result<PathBuf, Error> {
+        let unsupported = |reason| Error::UnsupportedUri {
+            uri: uri.to_string(),
+            reason,
+        };
+        let relative = match Url::parse(uri) {
+            Ok(url) if url.scheme() == "file" => {
+                return url
+                    .to_file_path()
+                    .map_err(|_| unsupported("not a local file path"))
+            }
+            Ok(url) if url.scheme() == "http" || url.scheme() == "https" => {
+                let host = url.host_str().ok_or_else(|| unsupported("missing host"))?;
+                format!("{}{}", host, url.path())
+            }
+            Ok(_) => return Err(unsupported("unsupported scheme")),
+            Err(_) => uri.split(['?', '#']).next().unwrap_or_default().to_string(),
+        };
+
+        let relative = Path::new(relative.trim_start_matches('/'));
+        if relative
+            .components()
+            .any(|c| !matches!(c, Component::Normal(_)))
+        {
+            return Err(unsupported("path escaping the root directory"));
+        }
+        Ok(self.root.join(relative))
+    }
+}
+
+impl SchemaResolver for FileSystemResolver {
+    fn resolve(&self, uri: &str) -> Result<Bytes, Error> {
+        let path = self.path(uri)?;
+        match std::fs::read(path) {
+            Ok(schema) => Ok(Bytes::from(schema)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Err(Error::NotFound {
+                uri: uri.to_string(),
+            }),
+            Err(source) => Err(Error::Io {
+                uri: uri.to_string(),
+                source,
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn paths() {
+        let resolver = FileSystemResolver::new("/schemas");
+        assert_eq!(
+            PathBuf::from("/schemas/example.com/v1/order.json"),
+            resolver
+                .path("https://example.com/v1/order.json?version=2")
+                .unwrap()
+        );
+        assert_eq!(
+            PathBuf::from("/schemas/v1/order.json"),
+            resolver.path("/v1/order.json#/definitions").unwrap()
+        );
+        assert_eq!(
+            PathBuf::from("/tmp/order.json"),
+            resolver.path("file:///tmp/order.json").unwrap()
+        );
+        for uri in &["../order.json", "/v1/../../order.json", "urn:order"] {
+            assert!(matches!(
+                resolver.path(uri),
+                Err(Error::UnsupportedUri { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn resolve() {
+        let root = std::env::temp_dir().join(format!("cloudevents-schemas-{}", std::process::id()));
+        std::fs::create_dir_all(root.join("example.com")).unwrap();
+        std::fs::write(root.join("example.com/order.json"), "{}").unwrap();
+
+        let resolver = FileSystemResolver::new(&root);
+        assert_eq!(
+            Bytes::from("{}"),
+            resolver.resolve("https://example.com/order.json").unwrap()
+        );
+        assert!(matches!(
+            resolver.resolve("https://example.com/missing.json"),
+            Err(Error::NotFound { .. })
+        ));
+        std::fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,38 @@
+use super::{Error, SchemaResolver};
+use bytes::Bytes;
+use std::collections::HashMap;
+
+/// [`SchemaResolver`] serving the schemas registered in memory
+#[derive(Debug, Clone, Default)]
+pub struct InMemoryResolver {
+    schemas: HashMap<String, Bytes>,
+}
+
+impl InMemoryResolver {
+    /// Create a new empty [`InMemoryResolver`]
+    pub fn new() -> Self {
+        InMemoryResolver::default()
+    }
+
+    /// Register the `schema` document identified by `uri`
+    pub fn with(mut self, uri: impl Into<String>, schema: impl Into<Bytes>) -> Self {
+        self.insert(uri, schema);
+        self
+    }
+
+    /// Register the `schema` document identified by `uri`, replacing the previous one
+    pub fn insert(&mut self, uri: impl Into<String>, schema: impl Into<Bytes>) {
+        self.schemas.insert(uri.into(), schema.into());
+    }
+}
+
+impl SchemaResolver for InMemoryResolver {
+    fn resolve(&self, uri: &str) -> Result<Bytes, Error> {
+        self.schemas
+            .get(uri)
+            .cloned()
+            .ok_or_else(|| Error::NotFound {
+                uri: uri.to_string(),
+            })
+    }
+}
@@ -0,0 +1,130 @@
+//! Resolution of the `dataschema` of the events without network access, e.g. to validate the
+//! data against its schema in an air-gapped environment.
+//!
+//! A [`SchemaResolver`] maps a schema URI to the schema document:
+//!
+//! * [`InMemoryResolver`] serves the schemas registered in memory, e.g. embedded in the binary
+//! * [`FileSystemResolver`] reads the schemas mirrored in a local directory
+//! * [`CachingResolver`] caches the schemas resolved by another resolver for a TTL
+//!
+//! [`resolve_dataschema`] resolves the schema of an event, failing closed or open with the
+//! [`FailureMode`] when the schema can't be resolved.
+//!
+//! ```
+//! use cloudevents::schema::{resolve_dataschema, FailureMode, InMemoryResolver};
+//! use cloudevents::{EventBuilder, EventBuilderV10};
+//!
+//! let resolver = InMemoryResolver::new()
+//!     .with("https://example.com/schemas/order.json", r#"{"type": "object"}"#);
+//!
+//! let event = EventBuilderV10::new()
+//!     .id("0001")
+//!     .source("http://localhost/")
+//!     .ty("com.example.order")
+//!     .data_with_schema(
+//!         "application/json",
+//!         "https://example.com/schemas/order.json",
+//!         serde_json::json!({}),
+//!     )
+//!     .build()
+//!     .unwrap();
+//!
+//! let schema = resolve_dataschema(&resolver, &event, FailureMode::Closed).unwrap();
+//! assert_eq!(Some(&br#"{"type": "object"}"#[..]), schema.as_deref());
+//! ```
+
+mod cache;
+mod fs;
+mod memory;
+
+pub use cache::CachingResolver;
+pub use fs::FileSystemResolver;
+pub use memory::InMemoryResolver;
+
+use crate::{AttributesReader, Event};
+use bytes::Bytes;
+use snafu::Snafu;
+
+/// Represents an error while resolving a schema
+#[derive(Debug, Snafu)]
+pub enum Error {
+    #[snafu(display("Schema {} not found", uri))]
+    NotFound { uri: String },
+    #[snafu(display("Schema URI {} can't be resolved: {}", uri, reason))]
+    UnsupportedUri { uri: String, reason: &'static str },
+    #[snafu(display("Error while reading schema {}: {}", uri, source))]
+    Io { uri: String, source: std::io::Error },
+}
+
+/// Resolver of the schema documents referenced by the `dataschema` attribute
+pub trait SchemaResolver: Send + Sync {
+    /// Resolve the schema document identified by `uri`
+    fn resolve(&self, uri: &str) -> Result<Bytes, Error>;
+}
+
+impl<R: SchemaResolver + ?Sized> SchemaResolver for std::sync::Arc<R> {
+    fn resolve(&self, uri: &str) -> Result<Bytes, Error> {
+        (**self).resolve(uri)
+    }
+}
+
+/// Behavior when a schema can't be resolved
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FailureMode {
+    /// Fail with the resolution error
+    Closed,
+    /// Proceed without the schema
+    Open,
+}
+
+/// Resolve the schema referenced by the `dataschema` of `event`, returning `None` if the event
+/// has no `dataschema`, or if the resolution fails in [`FailureMode::Open`]
+pub fn resolve_dataschema(
+    resolver: &dyn SchemaResolver,
+    event: &Event,
+    mode: FailureMode,
+) -> Result<Option<Bytes>, Error> {
+    let uri = match event.get_dataschema() {
+        Some(uri) => uri,
+        None => return Ok(None),
+    };
+    match (resolver.resolve(uri.as_str()), mode) {
+        (Ok(schema), _) => Ok(Some(schema)),
+        (Err(_), FailureMode::Open) => Ok(None),
+        (Err(e), FailureMode::Closed) => Err(e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::UriReference;
+    use crate::{EventBuilder, EventBuilderV10};
+
+    #[test]
+    fn failure_modes() {
+        let resolver = InMemoryResolver::new();
+        let mut event = EventBuilderV10::new()
+            .id("0001")
+            .source("http://localhost/")
+            .ty("example.test")
+            .build()
+            .unwrap();
+        assert!(resolve_dataschema(&resolver, &event, FailureMode::Closed)
+            .unwrap()
+            .is_none());
+
+        event.write_data_with_schema(
+            "application/json",
+            UriReference::parse("https://example.com/missing.json").unwrap(),
+            serde_json::json!({}),
+        );
+        assert!(matches!(
+            resolve_dataschema(&resolver, &event, FailureMode::Closed),
+            Err(Error::NotFound { .. })
+        ));
+        assert!(resolve_dataschema(&resolver, &event, FailureMode::Open)
+            .unwrap()
+            .is_none());
+    }
+}
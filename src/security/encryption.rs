@@ -0,0 +1,315 @@
+use super::Error;
+use crate::event::{is_json_content_type, Data, DataAttributesWriter, ExtensionValue};
+use crate::message::{EventInterceptor, Result as MessageResult};
+use crate::{AttributesReader, AttributesWriter, Event};
+use ring::aead::{self, Aad, LessSafeKey, Nonce, UnboundKey, NONCE_LEN};
+use ring::rand::{SecureRandom, SystemRandom};
+use std::fmt;
+
+/// `datacontenttype` of the encrypted data
+pub const ENCRYPTED_CONTENT_TYPE: &str = "application/vnd.cloudevents.encrypted";
+
+/// Name of the extension carrying the algorithm of the [`Cipher`] the data is encrypted with
+pub const ENCRYPTION: &str = "encryption";
+
+/// Name of the extension carrying the `datacontenttype` of the data before it was encrypted
+pub const ORIGINALCONTENTTYPE: &str = "originalcontenttype";
+
+/// Symmetric cipher of the data of the events, e.g. [`AesGcmCipher`] or a client of a key
+/// management service.
+///
+/// The ciphertext must be authenticated, so [`decrypt`](Cipher::decrypt) fails if it was
+/// modified, or if it's decrypted with other associated data than it was encrypted with.
+pub trait Cipher: Send + Sync {
+    /// Name of the algorithm, written in the `encryption` extension, e.g. `A256GCM`
+    fn algorithm(&self) -> &str;
+
+    /// Encrypt `plaintext`, authenticating `aad` with it
+    fn encrypt(&self, plaintext: &[u8], aad: &[u8]) -> Result<Vec<u8>, Error>;
+
+    /// Decrypt `ciphertext` encrypted with [`encrypt`](Cipher::encrypt) and `aad`
+    fn decrypt(&self, ciphertext: &[u8], aad: &[u8]) -> Result<Vec<u8>, Error>;
+}
+
+/// [`Cipher`] with AES-GCM and a 128 or 256 bits key.
+///
+/// The ciphertext is prefixed with the random nonce it's encrypted with.
+pub struct AesGcmCipher {
+    key: LessSafeKey,
+    algorithm: &'static str,
+    rng: SystemRandom,
+}
+
+impl AesGcmCipher {
+    /// Create a new [`AesGcmCipher`] with the 16 or 32 bytes `key`
+    pub fn new(key: &[u8]) -> Result<Self, Error> {
+        let (algorithm, name) = match key.len() {
+            16 => (&aead::AES_128_GCM, "A128GCM"),
+            32 => (&aead::AES_256_GCM, "A256GCM"),
+            len => {
+                return Err(Error::EncryptionFailed {
+                    reason: format!("expected a key of 16 or 32 bytes, got {} bytes", len),
+                })
+            }
+        };
+        let key = UnboundKey::new(algorithm, key).map_err(|e| Error::EncryptionFailed {
+            reason: e.to_string(),
+        })?;
+        Ok(AesGcmCipher {
+            key: LessSafeKey::new(key),
+            algorithm: name,
+            rng: SystemRandom::new(),
+        })
+    }
+}
+
+impl Cipher for AesGcmCipher {
+    fn algorithm(&self) -> &str {
+        self.algorithm
+    }
+
+    fn encrypt(&self, plaintext: &[u8], aad: &[u8]) -> Result<Vec<u8>, Error> {
+        let mut nonce = [0; NONCE_LEN];
+        self.rng
+            .fill(&mut nonce)
+            .map_err(|e| Error::EncryptionFailed {
+                reason: e.to_string(),
+            })?;
+        let mut in_out = plaintext.to_vec();
+        self.key
+            .seal_in_place_append_tag(
+                Nonce::assume_unique_for_key(nonce),
+                Aad::from(aad),
+                &mut in_out,
+            )
+            .map_err(|e| Error::EncryptionFailed {
+                reason: e.to_string(),
+            })?;
+        let mut ciphertext = nonce.to_vec();
+        ciphertext.append(&mut in_out);
+        Ok(ciphertext)
+    }
+
+    fn decrypt(&self, ciphertext: &[u8], aad: &[u8]) -> Result<Vec<u8>, Error> {
+        if ciphertext.len() < NONCE_LEN {
+            return Err(Error::DecryptionFailed {});
+        }
+        let (nonce, ciphertext) = ciphertext.split_at(NONCE_LEN);
+        let nonce =
+            Nonce::try_assume_unique_for_key(nonce).map_err(|_| Error::DecryptionFailed {})?;
+        let mut in_out = ciphertext.to_vec();
+        let plaintext = self
+            .key
+            .open_in_place(nonce, Aad::from(aad), &mut in_out)
+            .map_err(|_| Error::DecryptionFailed {})?;
+        Ok(plaintext.to_vec())
+    }
+}
+
+impl fmt::Debug for AesGcmCipher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AesGcmCipher")
+            .field("algorithm", &self.algorithm)
+            .finish()
+    }
+}
+
+/// Returns `true` if the data of `event` is encrypted
+pub fn is_encrypted(event: &Event) -> bool {
+    event.get_datacontenttype() == Some(ENCRYPTED_CONTENT_TYPE)
+}
+
+/// Encrypt the data of `event` with `cipher`.
+///
+/// The `datacontenttype` is set to [`ENCRYPTED_CONTENT_TYPE`], and the original one is kept in
+/// the `originalcontenttype` extension. The ciphertext is bound to the `id` of the event, so it
+/// can't be moved to another event. The events without data, or with data already encrypted,
+/// are left unchanged.
+pub fn encrypt_data(event: &mut Event, cipher: &dyn Cipher) -> Result<(), Error> {
+    if is_encrypted(event) {
+        return Ok(());
+    }
+    let plaintext = match &event.data {
+        Some(Data::Json(v)) => serde_json::to_vec(v).map_err(|e| Error::EncryptionFailed {
+            reason: e.to_string(),
+        })?,
+        Some(Data::String(s)) => s.as_bytes().to_vec(),
+        Some(Data::Binary(b)) => b.clone(),
+        None => return Ok(()),
+    };
+    let ciphertext = cipher.encrypt(&plaintext, event.get_id().as_bytes())?;
+
+    match event.get_datacontenttype().map(String::from) {
+        Some(content_type) => event.set_extension(ORIGINALCONTENTTYPE, content_type),
+        None => {
+            event.remove_extension(ORIGINALCONTENTTYPE);
+        }
+    }
+    event.set_extension(ENCRYPTION, cipher.algorithm());
+    event
+        .attributes
+        .set_datacontenttype(Some(ENCRYPTED_CONTENT_TYPE));
+    event.data = Some(Data::Binary(ciphertext));
+    Ok(())
+}
+
+/// Decrypt the data of `event` encrypted with [`encrypt_data`] and `cipher`, restoring its
+/// original `datacontenttype`.
+///
+/// The data of a JSON `datacontenttype` is restored as [`Data::Json`], and any other data as
+/// [`Data::Binary`]. The events with data not encrypted are left unchanged.
+pub fn decrypt_data(event: &mut Event, cipher: &dyn Cipher) -> Result<(), Error> {
+    if !is_encrypted(event) {
+        return Ok(());
+    }
+    match event.get_extension(ENCRYPTION) {
+        Some(ExtensionValue::String(algorithm)) if algorithm == cipher.algorithm() => {}
+        Some(ExtensionValue::String(algorithm)) => {
+            return Err(Error::UnsupportedEncryption {
+                algorithm: algorithm.clone(),
+            })
+        }
+        _ => {
+            return Err(Error::UnsupportedEncryption {
+                algorithm: String::new(),
+            })
+        }
+    }
+    let plaintext = match &event.data {
+        Some(Data::Binary(b)) => cipher.decrypt(b, event.get_id().as_bytes())?,
+        _ => return Err(Error::DecryptionFailed {}),
+    };
+
+    let content_type = match event.remove_extension(ORIGINALCONTENTTYPE) {
+        Some(ExtensionValue::String(content_type)) => Some(content_type),
+        _ => None,
+    };
+    event.remove_extension(ENCRYPTION);
+    let json = content_type
+        .as_deref()
+        .map(|ct| is_json_content_type(ct.split(';').next().unwrap_or_default().trim()))
+        .unwrap_or(true);
+    event.data = Some(match serde_json::from_slice(&plaintext) {
+        Ok(value) if json => Data::Json(value),
+        _ => Data::Binary(plaintext),
+    });
+    event.attributes.set_datacontenttype(content_type);
+    Ok(())
+}
+
+/// [`EventInterceptor`] encrypting the data of the sent events, and decrypting the data of the
+/// received events, with a [`Cipher`].
+///
+/// It should be added before a [`SigningInterceptor`](super::SigningInterceptor), so the
+/// signature covers the encrypted data.
+pub struct EncryptionInterceptor<C> {
+    cipher: C,
+}
+
+impl<C: Cipher> EncryptionInterceptor<C> {
+    /// Create a new [`EncryptionInterceptor`] with `cipher`
+    pub fn new(cipher: C) -> Self {
+        EncryptionInterceptor { cipher }
+    }
+}
+
+impl<C: Cipher> EventInterceptor for EncryptionInterceptor<C> {
+    fn on_send(&self, event: &mut Event) -> MessageResult<()> {
+        encrypt_data(event, &self.cipher).map_err(|e| crate::message::Error::Other {
+            source: Box::new(e),
+        })
+    }
+
+    fn on_receive(&self, event: &mut Event) -> MessageResult<()> {
+        decrypt_data(event, &self.cipher).map_err(|e| crate::message::Error::Other {
+            source: Box::new(e),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EventBuilder, EventBuilderV10};
+    use serde_json::json;
+
+    fn event() -> Event {
+        EventBuilderV10::new()
+            .id("0001")
+            .source("http://localhost/")
+            .ty("example.test")
+            .data("application/json", json!({"card": "4111111111111111"}))
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn round_trip() {
+        let cipher = AesGcmCipher::new(&[7; 32]).unwrap();
+        let original = event();
+
+        let mut e = original.clone();
+        encrypt_data(&mut e, &cipher).unwrap();
+        assert_eq!(Some(ENCRYPTED_CONTENT_TYPE), e.get_datacontenttype());
+        assert_eq!(Some(&"A256GCM".into()), e.get_extension(ENCRYPTION));
+        assert_eq!(
+            Some(&"application/json".into()),
+            e.get_extension(ORIGINALCONTENTTYPE)
+        );
+        let ciphertext = match e.data.clone() {
+            Some(Data::Binary(b)) => b,
+            data => panic!("unexpected data {:?}", data),
+        };
+        assert!(!ciphertext.windows(16).any(|w| w == b"4111111111111111"));
+
+        // Encrypting twice doesn't change the data
+        encrypt_data(&mut e, &cipher).unwrap();
+        assert_eq!(Some(Data::Binary(ciphertext)), e.data);
+
+        decrypt_data(&mut e, &cipher).unwrap();
+        assert_eq!(original, e);
+    }
+
+    #[test]
+    fn tampering() {
+        let cipher = AesGcmCipher::new(&[7; 16]).unwrap();
+
+        let mut e = event();
+        encrypt_data(&mut e, &cipher).unwrap();
+        let mut moved = e.clone();
+        moved.set_id("0002");
+        assert!(matches!(
+            decrypt_data(&mut moved, &cipher),
+            Err(Error::DecryptionFailed {})
+        ));
+
+        let other = AesGcmCipher::new(&[8; 16]).unwrap();
+        assert!(matches!(
+            decrypt_data(&mut e.clone(), &other),
+            Err(Error::DecryptionFailed {})
+        ));
+        assert!(matches!(
+            decrypt_data(&mut e, &AesGcmCipher::new(&[7; 32]).unwrap()),
+            Err(Error::UnsupportedEncryption { .. })
+        ));
+        assert!(AesGcmCipher::new(&[7; 10]).is_err());
+    }
+
+    #[test]
+    fn interceptor() {
+        let interceptor = EncryptionInterceptor::new(AesGcmCipher::new(&[7; 32]).unwrap());
+        let mut e = EventBuilderV10::new()
+            .id("0001")
+            .source("http://localhost/")
+            .ty("example.test")
+            .data("application/octet-stream", vec![1u8, 2, 3])
+            .build()
+            .unwrap();
+        let original = e.clone();
+
+        interceptor.on_send(&mut e).unwrap();
+        assert!(is_encrypted(&e));
+        interceptor.on_receive(&mut e).unwrap();
+        assert_eq!(original, e);
+    }
+}
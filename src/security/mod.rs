@@ -0,0 +1,567 @@
+//! Signatures of the events, enabled with the `security` feature: an event is signed with a
+//! [`Signer`], and the signature is carried in the `signature` extension, as a
+//! [JWS](https://www.rfc-editor.org/rfc/rfc7515) in compact serialization with detached payload.
+//! The signed payload is the [canonical serialization](crate::integrity::canonical_json) of the
+//! event without the `signature` extension, so the signature doesn't depend on the protocol
+//! binding or the content mode the event was transferred with.
+//!
+//! The consumers verify the signature with the public key resolved by a [`KeyResolver`] from the
+//! `kid` header of the JWS.
+//!
+//! The data of the events can also be encrypted with a [`Cipher`] like [`AesGcmCipher`], so the
+//! sensitive payloads can cross untrusted brokers: [`encrypt_data`] replaces the data with its
+//! ciphertext of the [`ENCRYPTED_CONTENT_TYPE`], and [`decrypt_data`] restores it.
+//!
+//! ```
+//! use cloudevents::security::{sign, verify, Ed25519Signer, PublicKey, PublicKeys};
+//! use cloudevents::{AttributesWriter, EventBuilder, EventBuilderV10};
+//!
+//! # let pkcs8 = ring::signature::Ed25519KeyPair::generate_pkcs8(
+//! #     &ring::rand::SystemRandom::new(),
+//! # )
+//! # .unwrap();
+//! let signer = Ed25519Signer::from_pkcs8(pkcs8.as_ref(), Some("key-1")).unwrap();
+//! let keys = PublicKeys::new().with("key-1", PublicKey::ed25519(signer.public_key()));
+//!
+//! let mut event = EventBuilderV10::new()
+//!     .id("0001")
+//!     .source("http://localhost/")
+//!     .ty("com.example.order")
+//!     .build()
+//!     .unwrap();
+//! sign(&mut event, &signer).unwrap();
+//! assert!(verify(&event, &keys).is_ok());
+//!
+//! event.set_type("com.example.refund");
+//! assert!(verify(&event, &keys).is_err());
+//! ```
+
+mod encryption;
+
+pub use encryption::{
+    decrypt_data, encrypt_data, is_encrypted, AesGcmCipher, Cipher, EncryptionInterceptor,
+    ENCRYPTED_CONTENT_TYPE, ENCRYPTION, ORIGINALCONTENTTYPE,
+};
+
+use crate::event::ExtensionValue;
+use crate::integrity::canonical_json_excluding;
+use crate::message::{EventInterceptor, Result as MessageResult};
+use crate::{AttributesReader, AttributesWriter, Event};
+use ring::rand::SystemRandom;
+use ring::signature::{self, Ed25519KeyPair, KeyPair, RsaKeyPair, UnparsedPublicKey};
+use serde::{Deserialize, Serialize};
+use snafu::Snafu;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+/// Name of the extension carrying the signature of the event
+pub const SIGNATURE: &str = "signature";
+
+/// Represents an error while signing, verifying, encrypting or decrypting an event
+#[derive(Debug, Snafu)]
+pub enum Error {
+    #[snafu(display("Error while signing the event: {}", reason))]
+    SigningFailed { reason: String },
+    #[snafu(display("Missing {} extension", SIGNATURE))]
+    MissingSignature {},
+    #[snafu(display("Malformed signature: {}", reason))]
+    MalformedSignature { reason: String },
+    #[snafu(display("No key found for kid {:?}", key_id))]
+    UnknownKey { key_id: Option<String> },
+    #[snafu(display("Algorithm {} doesn't match the {} key", algorithm, expected))]
+    AlgorithmMismatch {
+        algorithm: String,
+        expected: Algorithm,
+    },
+    #[snafu(display("The event doesn't match its signature"))]
+    InvalidSignature {},
+    #[snafu(display("Error while encrypting the data: {}", reason))]
+    EncryptionFailed { reason: String },
+    #[snafu(display("Unsupported encryption {:?}", algorithm))]
+    UnsupportedEncryption { algorithm: String },
+    #[snafu(display("The data can't be decrypted"))]
+    DecryptionFailed {},
+}
+
+/// JWS signature algorithm
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Algorithm {
+    /// EdDSA with the Ed25519 curve
+    EdDSA,
+    /// RSASSA-PKCS1-v1_5 with SHA-256
+    RS256,
+}
+
+impl Algorithm {
+    /// Name of the algorithm in the `alg` header of the JWS
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Algorithm::EdDSA => "EdDSA",
+            Algorithm::RS256 => "RS256",
+        }
+    }
+}
+
+impl fmt::Display for Algorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Signer of the JWS carried by the `signature` extension, e.g. backed by a local key like
+/// [`Ed25519Signer`] and [`RsaSigner`], or by a remote key management service
+pub trait Signer: Send + Sync {
+    /// Algorithm of the signatures
+    fn algorithm(&self) -> Algorithm;
+
+    /// Identifier of the key, written in the `kid` header of the JWS
+    fn key_id(&self) -> Option<&str> {
+        None
+    }
+
+    /// Sign `message`
+    fn sign(&self, message: &[u8]) -> Result<Vec<u8>, Error>;
+}
+
+/// [`Signer`] with an Ed25519 key pair
+pub struct Ed25519Signer {
+    key_pair: Ed25519KeyPair,
+    key_id: Option<String>,
+}
+
+impl Ed25519Signer {
+    /// Create a new [`Ed25519Signer`] with the PKCS#8 serialized key pair, identified by `key_id`
+    pub fn from_pkcs8(pkcs8: &[u8], key_id: Option<&str>) -> Result<Self, Error> {
+        let key_pair = Ed25519KeyPair::from_pkcs8(pkcs8).map_err(|e| Error::SigningFailed {
+            reason: e.to_string(),
+        })?;
+        Ok(Ed25519Signer {
+            key_pair,
+            key_id: key_id.map(String::from),
+        })
+    }
+
+    /// The 32 bytes of the public key
+    pub fn public_key(&self) -> &[u8] {
+        self.key_pair.public_key().as_ref()
+    }
+}
+
+impl Signer for Ed25519Signer {
+    fn algorithm(&self) -> Algorithm {
+        Algorithm::EdDSA
+    }
+
+    fn key_id(&self) -> Option<&str> {
+        self.key_id.as_deref()
+    }
+
+    fn sign(&self, message: &[u8]) -> Result<Vec<u8>, Error> {
+        Ok(self.key_pair.sign(message).as_ref().to_vec())
+    }
+}
+
+impl fmt::Debug for Ed25519Signer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Ed25519Signer")
+            .field("key_id", &self.key_id)
+            .finish()
+    }
+}
+
+/// [`Signer`] with an RSA key pair, signing with RS256
+pub struct RsaSigner {
+    key_pair: RsaKeyPair,
+    key_id: Option<String>,
+    rng: SystemRandom,
+}
+
+impl RsaSigner {
+    /// Create a new [`RsaSigner`] with the PKCS#8 serialized key pair, identified by `key_id`
+    pub fn from_pkcs8(pkcs8: &[u8], key_id: Option<&str>) -> Result<Self, Error> {
+        Self::new(RsaKeyPair::from_pkcs8(pkcs8), key_id)
+    }
+
+    /// Create a new [`RsaSigner`] with the PKCS#1 DER serialized private key, identified by
+    /// `key_id`
+    pub fn from_der(der: &[u8], key_id: Option<&str>) -> Result<Self, Error> {
+        Self::new(RsaKeyPair::from_der(der), key_id)
+    }
+
+    fn new(
+        key_pair: Result<RsaKeyPair, ring::error::KeyRejected>,
+        key_id: Option<&str>,
+    ) -> Result<Self, Error> {
+        let key_pair = key_pair.map_err(|e| Error::SigningFailed {
+            reason: e.to_string(),
+        })?;
+        Ok(RsaSigner {
+            key_pair,
+            key_id: key_id.map(String::from),
+            rng: SystemRandom::new(),
+        })
+    }
+
+    /// The PKCS#1 DER serialized public key
+    pub fn public_key(&self) -> &[u8] {
+        self.key_pair.public_key().as_ref()
+    }
+}
+
+impl Signer for RsaSigner {
+    fn algorithm(&self) -> Algorithm {
+        Algorithm::RS256
+    }
+
+    fn key_id(&self) -> Option<&str> {
+        self.key_id.as_deref()
+    }
+
+    fn sign(&self, message: &[u8]) -> Result<Vec<u8>, Error> {
+        let mut signature = vec![0; self.key_pair.public_modulus_len()];
+        self.key_pair
+            .sign(
+                &signature::RSA_PKCS1_SHA256,
+                &self.rng,
+                message,
+                &mut signature,
+            )
+            .map_err(|e| Error::SigningFailed {
+                reason: e.to_string(),
+            })?;
+        Ok(signature)
+    }
+}
+
+impl fmt::Debug for RsaSigner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RsaSigner")
+            .field("key_id", &self.key_id)
+            .finish()
+    }
+}
+
+/// Public key verifying the signatures of an [`Algorithm`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PublicKey {
+    algorithm: Algorithm,
+    bytes: Vec<u8>,
+}
+
+impl PublicKey {
+    /// Create a new Ed25519 public key from its 32 bytes
+    pub fn ed25519(bytes: impl Into<Vec<u8>>) -> Self {
+        PublicKey {
+            algorithm: Algorithm::EdDSA,
+            bytes: bytes.into(),
+        }
+    }
+
+    /// Create a new RSA public key from its PKCS#1 DER serialization
+    pub fn rsa(der: impl Into<Vec<u8>>) -> Self {
+        PublicKey {
+            algorithm: Algorithm::RS256,
+            bytes: der.into(),
+        }
+    }
+
+    /// Algorithm of the signatures verified with the key
+    pub fn algorithm(&self) -> Algorithm {
+        self.algorithm
+    }
+
+    fn verify(&self, message: &[u8], signature: &[u8]) -> Result<(), Error> {
+        let result = match self.algorithm {
+            Algorithm::EdDSA => {
+                UnparsedPublicKey::new(&signature::ED25519, &self.bytes).verify(message, signature)
+            }
+            Algorithm::RS256 => {
+                UnparsedPublicKey::new(&signature::RSA_PKCS1_2048_8192_SHA256, &self.bytes)
+                    .verify(message, signature)
+            }
+        };
+        result.map_err(|_| Error::InvalidSignature {})
+    }
+}
+
+/// Resolver of the [`PublicKey`] verifying a signature by the `kid` header of the JWS, e.g.
+/// [`PublicKeys`] or a client of a key management service.
+///
+/// It's implemented by the functions with the signature of [`KeyResolver::resolve`], and by
+/// `Arc`s of [`KeyResolver`].
+pub trait KeyResolver: Send + Sync {
+    /// Resolve the key identified by `key_id`, returning `None` if it's unknown
+    fn resolve(&self, key_id: Option<&str>) -> Option<PublicKey>;
+}
+
+impl<F> KeyResolver for F
+where
+    F: Fn(Option<&str>) -> Option<PublicKey> + Send + Sync,
+{
+    fn resolve(&self, key_id: Option<&str>) -> Option<PublicKey> {
+        self(key_id)
+    }
+}
+
+impl<R: KeyResolver + ?Sized> KeyResolver for Arc<R> {
+    fn resolve(&self, key_id: Option<&str>) -> Option<PublicKey> {
+        (**self).resolve(key_id)
+    }
+}
+
+/// [`KeyResolver`] of a fixed set of keys
+#[derive(Debug, Clone, Default)]
+pub struct PublicKeys {
+    keys: HashMap<String, PublicKey>,
+}
+
+impl PublicKeys {
+    /// Create a new empty set of keys
+    pub fn new() -> Self {
+        PublicKeys::default()
+    }
+
+    /// Add `key`, identified by `key_id`, replacing the key previously added with it
+    pub fn with(mut self, key_id: impl Into<String>, key: PublicKey) -> Self {
+        self.insert(key_id, key);
+        self
+    }
+
+    /// Add `key`, identified by `key_id`, replacing the key previously added with it
+    pub fn insert(&mut self, key_id: impl Into<String>, key: PublicKey) {
+        self.keys.insert(key_id.into(), key);
+    }
+}
+
+impl KeyResolver for PublicKeys {
+    fn resolve(&self, key_id: Option<&str>) -> Option<PublicKey> {
+        self.keys.get(key_id?).cloned()
+    }
+}
+
+/// Protected header of the JWS
+#[derive(Serialize, Deserialize)]
+struct Header {
+    alg: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    kid: Option<String>,
+}
+
+fn encode(bytes: &[u8]) -> String {
+    base64::encode_config(bytes, base64::URL_SAFE_NO_PAD)
+}
+
+fn decode(part: &str) -> Result<Vec<u8>, Error> {
+    base64::decode_config(part, base64::URL_SAFE_NO_PAD).map_err(|e| Error::MalformedSignature {
+        reason: e.to_string(),
+    })
+}
+
+/// JWS signing input of `event` with the encoded `header`
+fn signing_input(header: &str, event: &Event) -> Vec<u8> {
+    let payload = canonical_json_excluding(event, &[SIGNATURE]);
+    format!("{}.{}", header, encode(&payload)).into_bytes()
+}
+
+/// Sign `event` with `signer`, and set the signature in the `signature` extension.
+///
+/// The signature covers all the attributes, extensions and data of the event, so the event must
+/// not be modified after it's signed.
+pub fn sign(event: &mut Event, signer: &dyn Signer) -> Result<(), Error> {
+    let header = Header {
+        alg: signer.algorithm().as_str().to_string(),
+        kid: signer.key_id().map(String::from),
+    };
+    let header = encode(
+        &serde_json::to_vec(&header).map_err(|e| Error::SigningFailed {
+            reason: e.to_string(),
+        })?,
+    );
+    let signature = signer.sign(&signing_input(&header, event))?;
+    event.set_extension(SIGNATURE, format!("{}..{}", header, encode(&signature)));
+    Ok(())
+}
+
+/// Verify the signature of `event` in the `signature` extension, with the key resolved by
+/// `resolver`
+pub fn verify(event: &Event, resolver: &dyn KeyResolver) -> Result<(), Error> {
+    let jws = match event.get_extension(SIGNATURE) {
+        Some(ExtensionValue::String(jws)) => jws,
+        Some(_) => {
+            return Err(Error::MalformedSignature {
+                reason: "not a string".to_string(),
+            })
+        }
+        None => return Err(Error::MissingSignature {}),
+    };
+    let (header, signature) = match jws.split('.').collect::<Vec<_>>()[..] {
+        [header, "", signature] => (header, signature),
+        _ => {
+            return Err(Error::MalformedSignature {
+                reason: "expected a JWS with detached payload".to_string(),
+            })
+        }
+    };
+    let parsed: Header =
+        serde_json::from_slice(&decode(header)?).map_err(|e| Error::MalformedSignature {
+            reason: e.to_string(),
+        })?;
+    let key = resolver
+        .resolve(parsed.kid.as_deref())
+        .ok_or_else(|| Error::UnknownKey {
+            key_id: parsed.kid.clone(),
+        })?;
+    if parsed.alg != key.algorithm().as_str() {
+        return Err(Error::AlgorithmMismatch {
+            algorithm: parsed.alg,
+            expected: key.algorithm(),
+        });
+    }
+    key.verify(&signing_input(header, event), &decode(signature)?)
+}
+
+/// [`EventInterceptor`] signing the sent events with a [`Signer`].
+///
+/// It should be the last interceptor modifying the sent events.
+pub struct SigningInterceptor<S> {
+    signer: S,
+}
+
+impl<S: Signer> SigningInterceptor<S> {
+    /// Create a new [`SigningInterceptor`] signing with `signer`
+    pub fn new(signer: S) -> Self {
+        SigningInterceptor { signer }
+    }
+}
+
+impl<S: Signer> EventInterceptor for SigningInterceptor<S> {
+    fn on_send(&self, event: &mut Event) -> MessageResult<()> {
+        sign(event, &self.signer).map_err(|e| crate::message::Error::Other {
+            source: Box::new(e),
+        })
+    }
+}
+
+/// [`EventInterceptor`] rejecting the received events without a valid signature
+pub struct VerifyingInterceptor<R> {
+    resolver: R,
+}
+
+impl<R: KeyResolver> VerifyingInterceptor<R> {
+    /// Create a new [`VerifyingInterceptor`] verifying with the keys resolved by `resolver`
+    pub fn new(resolver: R) -> Self {
+        VerifyingInterceptor { resolver }
+    }
+}
+
+impl<R: KeyResolver> EventInterceptor for VerifyingInterceptor<R> {
+    fn on_receive(&self, event: &mut Event) -> MessageResult<()> {
+        verify(event, &self.resolver).map_err(|e| crate::message::Error::Other {
+            source: Box::new(e),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EventBuilder, EventBuilderV10};
+    use serde_json::json;
+
+    fn event() -> Event {
+        EventBuilderV10::new()
+            .id("0001")
+            .source("http://localhost/")
+            .ty("example.test")
+            .data("application/json", json!({"hello": "world"}))
+            .build()
+            .unwrap()
+    }
+
+    fn ed25519_signer(key_id: &str) -> Ed25519Signer {
+        let pkcs8 = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new()).unwrap();
+        Ed25519Signer::from_pkcs8(pkcs8.as_ref(), Some(key_id)).unwrap()
+    }
+
+    #[test]
+    fn ed25519() {
+        let signer = ed25519_signer("key-1");
+        let keys = PublicKeys::new().with("key-1", PublicKey::ed25519(signer.public_key()));
+
+        let mut e = event();
+        assert!(matches!(verify(&e, &keys), Err(Error::MissingSignature {})));
+
+        sign(&mut e, &signer).unwrap();
+        assert!(verify(&e, &keys).is_ok());
+
+        e.set_extension("tampered", true);
+        assert!(matches!(verify(&e, &keys), Err(Error::InvalidSignature {})));
+
+        sign(&mut e, &ed25519_signer("key-2")).unwrap();
+        assert!(matches!(
+            verify(&e, &keys),
+            Err(Error::UnknownKey { key_id: Some(_) })
+        ));
+
+        e.set_extension(SIGNATURE, "not a jws");
+        assert!(matches!(
+            verify(&e, &keys),
+            Err(Error::MalformedSignature { .. })
+        ));
+    }
+
+    #[test]
+    fn rs256() {
+        let signer =
+            RsaSigner::from_der(include_bytes!("testdata/rsa-private-key.der"), None).unwrap();
+        let public_key = PublicKey::rsa(&include_bytes!("testdata/rsa-public-key.der")[..]);
+        assert_eq!(public_key, PublicKey::rsa(signer.public_key()));
+
+        let mut e = event();
+        sign(&mut e, &signer).unwrap();
+        let resolver = move |key_id: Option<&str>| match key_id {
+            None => Some(public_key.clone()),
+            Some(_) => None,
+        };
+        assert!(verify(&e, &resolver).is_ok());
+
+        let ed25519 = ed25519_signer("key-1");
+        let resolver = |_: Option<&str>| Some(PublicKey::ed25519(ed25519.public_key()));
+        assert!(matches!(
+            verify(&e, &resolver),
+            Err(Error::AlgorithmMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn interceptors() {
+        let signer = ed25519_signer("key-1");
+        let keys = PublicKeys::new().with("key-1", PublicKey::ed25519(signer.public_key()));
+        let signing = SigningInterceptor::new(signer);
+        let verifying = VerifyingInterceptor::new(keys);
+
+        let mut e = event();
+        assert!(verifying.on_receive(&mut e).is_err());
+        signing.on_send(&mut e).unwrap();
+        assert!(verifying.on_receive(&mut e).is_ok());
+    }
+
+    #[cfg(feature = "http")]
+    #[test]
+    fn binary_mode_round_trip() {
+        use crate::binding::http::{event_to_binary, to_event};
+
+        let signer = ed25519_signer("key-1");
+        let keys = PublicKeys::new().with("key-1", PublicKey::ed25519(signer.public_key()));
+
+        let mut e = event();
+        e.set_extension("someint", 10);
+        e.set_extension("somebool", true);
+        sign(&mut e, &signer).unwrap();
+
+        let (headers, body) = event_to_binary(e).unwrap();
+        let received = to_event(&headers, body).unwrap();
+        assert!(verify(&received, &keys).is_ok());
+    }
+}
@@ -0,0 +1,304 @@
+use super::parser::{Ast, BinaryOperator, Function};
+use super::{EvaluationError, Type, Value};
+use crate::event::{AttributeValue, Event};
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+
+type Result<T> = std::result::Result<T, EvaluationError>;
+
+impl Ast {
+    pub(crate) fn evaluate(&self, event: &Event) -> Result<Value> {
+        match self {
+            Ast::Literal(value) => Ok(value.clone()),
+            Ast::Attribute(name) => match event.attribute(name) {
+                Some(AttributeValue::Boolean(b)) => Ok(Value::Boolean(*b)),
+                Some(AttributeValue::Integer(i)) => i32::try_from(*i)
+                    .map(Value::Integer)
+                    .map_err(|_| EvaluationError::Overflow {}),
+                Some(value) => Ok(Value::String(value.to_string())),
+                None => Err(EvaluationError::MissingAttribute { name: name.clone() }),
+            },
+            Ast::Exists(name) => Ok(Value::Boolean(event.attribute(name).is_some())),
+            Ast::Not(ast) => Ok(Value::Boolean(!ast.evaluate(event)?.into_boolean()?)),
+            Ast::Negate(ast) => ast
+                .evaluate(event)?
+                .into_integer()?
+                .checked_neg()
+                .map(Value::Integer)
+                .ok_or(EvaluationError::Overflow {}),
+            Ast::Binary(operator, left, right) => evaluate_binary(*operator, left, right, event),
+            Ast::Like {
+                value,
+                pattern,
+                negated,
+            } => {
+                let value = value.evaluate(event)?.into_string();
+                Ok(Value::Boolean(like(&value, pattern) != *negated))
+            }
+            Ast::In {
+                value,
+                set,
+                negated,
+            } => {
+                let value = value.evaluate(event)?;
+                for element in set {
+                    if equal(value.clone(), element.evaluate(event)?)? {
+                        return Ok(Value::Boolean(!*negated));
+                    }
+                }
+                Ok(Value::Boolean(*negated))
+            }
+            Ast::Function(function, arguments) => {
+                let arguments = arguments
+                    .iter()
+                    .map(|a| a.evaluate(event))
+                    .collect::<Result<Vec<Value>>>()?;
+                evaluate_function(*function, arguments)
+            }
+        }
+    }
+}
+
+fn evaluate_binary(
+    operator: BinaryOperator,
+    left: &Ast,
+    right: &Ast,
+    event: &Event,
+) -> Result<Value> {
+    let left = left.evaluate(event)?;
+    let value = match operator {
+        BinaryOperator::And => {
+            Value::Boolean(left.into_boolean()? && right.evaluate(event)?.into_boolean()?)
+        }
+        BinaryOperator::Or => {
+            Value::Boolean(left.into_boolean()? || right.evaluate(event)?.into_boolean()?)
+        }
+        BinaryOperator::Xor => {
+            Value::Boolean(left.into_boolean()? != right.evaluate(event)?.into_boolean()?)
+        }
+        BinaryOperator::Equal => Value::Boolean(equal(left, right.evaluate(event)?)?),
+        BinaryOperator::NotEqual => Value::Boolean(!equal(left, right.evaluate(event)?)?),
+        BinaryOperator::Less
+        | BinaryOperator::LessOrEqual
+        | BinaryOperator::Greater
+        | BinaryOperator::GreaterOrEqual => {
+            let ordering = compare(left, right.evaluate(event)?)?;
+            Value::Boolean(match operator {
+                BinaryOperator::Less => ordering == Ordering::Less,
+                BinaryOperator::LessOrEqual => ordering != Ordering::Greater,
+                BinaryOperator::Greater => ordering == Ordering::Greater,
+                _ => ordering != Ordering::Less,
+            })
+        }
+        _ => {
+            let (left, right) = (left.into_integer()?, right.evaluate(event)?.into_integer()?);
+            let result = match operator {
+                BinaryOperator::Add => left.checked_add(right),
+                BinaryOperator::Subtract => left.checked_sub(right),
+                BinaryOperator::Multiply => left.checked_mul(right),
+                _ if right == 0 => return Err(EvaluationError::DivisionByZero {}),
+                BinaryOperator::Divide => left.checked_div(right),
+                _ => left.checked_rem(right),
+            };
+            Value::Integer(result.ok_or(EvaluationError::Overflow {})?)
+        }
+    };
+    Ok(value)
+}
+
+/// Compare values of different types casting both to boolean if one is boolean,
+/// or to integer if one is integer
+fn equal(left: Value, right: Value) -> Result<bool> {
+    Ok(match (left, right) {
+        (Value::Boolean(l), r) => l == r.into_boolean()?,
+        (l, Value::Boolean(r)) => l.into_boolean()? == r,
+        (Value::String(l), Value::String(r)) => l == r,
+        (l, r) => l.into_integer()? == r.into_integer()?,
+    })
+}
+
+/// Compare strings lexicographically, otherwise cast both values to integer
+fn compare(left: Value, right: Value) -> Result<Ordering> {
+    Ok(match (left, right) {
+        (Value::String(l), Value::String(r)) => l.cmp(&r),
+        (l, r) => l.into_integer()?.cmp(&r.into_integer()?),
+    })
+}
+
+fn evaluate_function(function: Function, arguments: Vec<Value>) -> Result<Value> {
+    let invalid = |reason: &str| EvaluationError::InvalidArgument {
+        function: function.name(),
+        reason: reason.to_string(),
+    };
+
+    Ok(match (function, arguments.as_slice()) {
+        (Function::Concat, values) => {
+            Value::String(values.iter().cloned().map(Value::into_string).collect())
+        }
+        (Function::ConcatWs, [delimiter, values @ ..]) => {
+            let values: Vec<String> = values.iter().cloned().map(Value::into_string).collect();
+            Value::String(values.join(&delimiter.clone().into_string()))
+        }
+        (Function::Abs, [value]) => Value::Integer(
+            value
+                .clone()
+                .into_integer()?
+                .checked_abs()
+                .ok_or(EvaluationError::Overflow {})?,
+        ),
+        (Function::Length, [value]) => Value::Integer(
+            i32::try_from(value.clone().into_string().chars().count())
+                .map_err(|_| EvaluationError::Overflow {})?,
+        ),
+        (Function::Lower, [value]) => Value::String(value.clone().into_string().to_lowercase()),
+        (Function::Upper, [value]) => Value::String(value.clone().into_string().to_uppercase()),
+        (Function::Trim, [value]) => Value::String(value.clone().into_string().trim().to_string()),
+        (Function::Left, [value, length]) | (Function::Right, [value, length]) => {
+            let value = value.clone().into_string();
+            let length = length.clone().into_integer()?;
+            if length < 0 {
+                return Err(invalid("length must not be negative"));
+            }
+            let count = value.chars().count();
+            let length = (length as usize).min(count);
+            Value::String(if function == Function::Left {
+                value.chars().take(length).collect()
+            } else {
+                value.chars().skip(count - length).collect()
+            })
+        }
+        (Function::Substring, [value, position, length @ ..]) => {
+            let value = value.clone().into_string();
+            let count = value.chars().count() as i64;
+            let start = match i64::from(position.clone().into_integer()?) {
+                p if p > 0 => p - 1,
+                p if p < 0 => count + p,
+                _ => return Err(invalid("position must not be 0")),
+            };
+            if start < 0 || start > count {
+                return Err(invalid("position out of bounds"));
+            }
+            let length = match length {
+                [length] => {
+                    let length = length.clone().into_integer()?;
+                    if length < 0 {
+                        return Err(invalid("length must not be negative"));
+                    }
+                    length as usize
+                }
+                _ => usize::MAX,
+            };
+            Value::String(value.chars().skip(start as usize).take(length).collect())
+        }
+        (Function::Int, [value]) => Value::Integer(value.clone().into_integer()?),
+        (Function::Bool, [value]) => Value::Boolean(value.clone().into_boolean()?),
+        (Function::String, [value]) => Value::String(value.clone().into_string()),
+        (Function::IsInt, [value]) => Value::Boolean(value.clone().into_integer().is_ok()),
+        (Function::IsBool, [value]) => Value::Boolean(value.clone().into_boolean().is_ok()),
+        // The number of arguments is checked while parsing
+        _ => return Err(invalid("wrong number of arguments")),
+    })
+}
+
+impl Value {
+    pub(crate) fn into_boolean(self) -> Result<bool> {
+        match self {
+            Value::Boolean(b) => Ok(b),
+            Value::String(s) if s.eq_ignore_ascii_case("true") => Ok(true),
+            Value::String(s) if s.eq_ignore_ascii_case("false") => Ok(false),
+            value => Err(EvaluationError::Cast {
+                value,
+                target: Type::Boolean,
+            }),
+        }
+    }
+
+    pub(crate) fn into_integer(self) -> Result<i32> {
+        match self {
+            Value::Integer(i) => Ok(i),
+            Value::String(s) => match s.parse() {
+                Ok(i) => Ok(i),
+                Err(_) => Err(EvaluationError::Cast {
+                    value: Value::String(s),
+                    target: Type::Integer,
+                }),
+            },
+            value => Err(EvaluationError::Cast {
+                value,
+                target: Type::Integer,
+            }),
+        }
+    }
+
+    pub(crate) fn into_string(self) -> String {
+        match self {
+            Value::String(s) => s,
+            value => value.to_string(),
+        }
+    }
+}
+
+/// Match `value` against a LIKE `pattern`, where `%` matches any sequence of characters,
+/// `_` matches a single character and `\` escapes the next character
+fn like(value: &str, pattern: &str) -> bool {
+    enum Part {
+        Any,
+        One,
+        Char(char),
+    }
+
+    let mut parts = Vec::new();
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        parts.push(match c {
+            '%' => Part::Any,
+            '_' => Part::One,
+            '\\' => Part::Char(chars.next().unwrap_or('\\')),
+            c => Part::Char(c),
+        });
+    }
+
+    // matched[i] is true if the first i characters of value match the pattern parsed so far
+    let value: Vec<char> = value.chars().collect();
+    let mut matched = vec![false; value.len() + 1];
+    matched[0] = true;
+    for part in &parts {
+        let mut next = vec![false; value.len() + 1];
+        for i in 0..=value.len() {
+            next[i] = match part {
+                Part::Any => matched[i] || (i > 0 && next[i - 1]),
+                Part::One => i > 0 && matched[i - 1],
+                Part::Char(c) => i > 0 && matched[i - 1] && value[i - 1] == *c,
+            };
+        }
+        matched = next;
+    }
+    matched[value.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn like_patterns() {
+        assert!(like("com.example.created", "com.example.%"));
+        assert!(like("com.example.", "com.example.%"));
+        assert!(!like("org.example.created", "com.example.%"));
+        assert!(like("abc", "a_c"));
+        assert!(!like("abbc", "a_c"));
+        assert!(like("a%c", "a\\%c"));
+        assert!(!like("abc", "a\\%c"));
+        assert!(like("", "%"));
+        assert!(like("xaybz", "%a%b%"));
+    }
+
+    #[test]
+    fn casts() {
+        assert_eq!(Ok(true), Value::String(String::from("TRUE")).into_boolean());
+        assert_eq!(Ok(-10), Value::String(String::from("-10")).into_integer());
+        assert!(Value::Integer(1).into_boolean().is_err());
+        assert!(Value::Boolean(true).into_integer().is_err());
+        assert_eq!("false", Value::Boolean(false).into_string());
+    }
+}
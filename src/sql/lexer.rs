@@ -0,0 +1,155 @@
+use super::ParseError;
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Token {
+    /// Identifier, keyword or function name
+    Word(String),
+    Integer(i64),
+    String(String),
+    LeftParen,
+    RightParen,
+    Comma,
+    Plus,
+    Minus,
+    Star,
+    Slash,
+    Percent,
+    Equal,
+    NotEqual,
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+}
+
+/// Split `input` in tokens, each one with its byte position
+pub(crate) fn tokenize(input: &str) -> Result<Vec<(usize, Token)>, ParseError> {
+    let mut tokens = Vec::new();
+    let mut chars = input.char_indices().peekable();
+
+    while let Some((position, c)) = chars.next() {
+        let token = match c {
+            c if c.is_whitespace() => continue,
+            '(' => Token::LeftParen,
+            ')' => Token::RightParen,
+            ',' => Token::Comma,
+            '+' => Token::Plus,
+            '-' => Token::Minus,
+            '*' => Token::Star,
+            '/' => Token::Slash,
+            '%' => Token::Percent,
+            '=' => Token::Equal,
+            '!' => match chars.next() {
+                Some((_, '=')) => Token::NotEqual,
+                _ => return Err(ParseError::new(position, "expected '=' after '!'")),
+            },
+            '<' => match chars.peek() {
+                Some((_, '=')) => {
+                    chars.next();
+                    Token::LessOrEqual
+                }
+                Some((_, '>')) => {
+                    chars.next();
+                    Token::NotEqual
+                }
+                _ => Token::Less,
+            },
+            '>' => match chars.peek() {
+                Some((_, '=')) => {
+                    chars.next();
+                    Token::GreaterOrEqual
+                }
+                _ => Token::Greater,
+            },
+            '\'' | '"' => {
+                let quote = c;
+                let mut value = String::new();
+                loop {
+                    match chars.next() {
+                        // Only the enclosing quote is unescaped, other escapes are kept
+                        // as is for the LIKE patterns
+                        Some((_, '\\')) => match chars.next() {
+                            Some((_, c)) if c == quote => value.push(c),
+                            Some((_, c)) => {
+                                value.push('\\');
+                                value.push(c);
+                            }
+                            None => break,
+                        },
+                        Some((_, c)) if c == quote => break,
+                        Some((_, c)) => value.push(c),
+                        None => {
+                            return Err(ParseError::new(position, "unterminated string literal"))
+                        }
+                    }
+                }
+                Token::String(value)
+            }
+            c if is_word_char(c) => {
+                let mut word = c.to_string();
+                while let Some((_, c)) = chars.peek() {
+                    if !is_word_char(*c) {
+                        break;
+                    }
+                    word.push(*c);
+                    chars.next();
+                }
+                if word.bytes().all(|b| b.is_ascii_digit()) {
+                    Token::Integer(
+                        word.parse().map_err(|_| {
+                            ParseError::new(position, "integer literal out of range")
+                        })?,
+                    )
+                } else {
+                    Token::Word(word)
+                }
+            }
+            c => {
+                return Err(ParseError::new(
+                    position,
+                    format!("unexpected character '{}'", c),
+                ))
+            }
+        };
+        tokens.push((position, token));
+    }
+
+    Ok(tokens)
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokens() {
+        let tokens: Vec<Token> = tokenize("type<>'a\\'b' AND x>=10")
+            .unwrap()
+            .into_iter()
+            .map(|(_, t)| t)
+            .collect();
+        assert_eq!(
+            vec![
+                Token::Word(String::from("type")),
+                Token::NotEqual,
+                Token::String(String::from("a'b")),
+                Token::Word(String::from("AND")),
+                Token::Word(String::from("x")),
+                Token::GreaterOrEqual,
+                Token::Integer(10),
+            ],
+            tokens
+        );
+
+        assert_eq!(
+            vec![(0, Token::String(String::from("10\\%")))],
+            tokenize("\"10\\%\"").unwrap()
+        );
+        assert_eq!(4, tokenize("a = 'b").unwrap_err().position());
+        assert!(tokenize("a # b").is_err());
+    }
+}
@@ -0,0 +1,338 @@
+mod eval;
+mod lexer;
+mod parser;
+
+use crate::event::Event;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use snafu::Snafu;
+use std::fmt;
+use std::str::FromStr;
+
+/// A parsed [CloudEvents SQL](https://github.com/cloudevents/spec/blob/main/cesql/spec.md)
+/// expression, which can be evaluated against the attributes and extensions of an [`Event`].
+///
+/// Keywords and function names are case insensitive. Integers are 32 bits signed: integer
+/// extensions of [`Event`] out of this range fail the evaluation with an overflow.
+///
+/// ```
+/// use cloudevents::sql::{Expression, Value};
+/// use cloudevents::{EventBuilder, EventBuilderV10};
+///
+/// let event = EventBuilderV10::new()
+///     .id("0001")
+///     .source("http://localhost/")
+///     .ty("com.example.order.created")
+///     .subject("orders")
+///     .extension("quantity", 3)
+///     .build()
+///     .unwrap();
+///
+/// let expression = Expression::parse("type LIKE 'com.example.%' AND subject = 'orders'").unwrap();
+/// assert!(expression.matches(&event));
+///
+/// let expression = Expression::parse("quantity * 2 > 10 OR NOT EXISTS priority").unwrap();
+/// assert_eq!(Ok(Value::Boolean(true)), expression.evaluate(&event));
+///
+/// let expression = Expression::parse("CONCAT(subject, '/', id)").unwrap();
+/// assert_eq!(Ok(Value::from("orders/0001")), expression.evaluate(&event));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Expression {
+    expression: String,
+    ast: parser::Ast,
+}
+
+impl Expression {
+    /// Parse a CESQL expression
+    pub fn parse(expression: &str) -> Result<Self, ParseError> {
+        Ok(Expression {
+            expression: expression.to_string(),
+            ast: parser::parse(expression)?,
+        })
+    }
+
+    /// Evaluate the expression against `event`
+    pub fn evaluate(&self, event: &Event) -> Result<Value, EvaluationError> {
+        self.ast.evaluate(event)
+    }
+
+    /// Returns `true` if the expression evaluates to `TRUE` against `event`.
+    /// An evaluation error, like a missing attribute, doesn't match.
+    pub fn matches(&self, event: &Event) -> bool {
+        matches!(self.evaluate(event).and_then(Value::into_boolean), Ok(true))
+    }
+}
+
+impl FromStr for Expression {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Expression::parse(s)
+    }
+}
+
+impl fmt::Display for Expression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.expression)
+    }
+}
+
+impl Serialize for Expression {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.expression)
+    }
+}
+
+impl<'de> Deserialize<'de> for Expression {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Expression::parse(&String::deserialize(deserializer)?).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Value of a CESQL expression
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Value {
+    Boolean(bool),
+    Integer(i32),
+    String(String),
+}
+
+impl Value {
+    /// Get the [`Type`] of this value
+    pub fn value_type(&self) -> Type {
+        match self {
+            Value::Boolean(_) => Type::Boolean,
+            Value::Integer(_) => Type::Integer,
+            Value::String(_) => Type::String,
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Boolean(b) => b.fmt(f),
+            Value::Integer(i) => i.fmt(f),
+            Value::String(s) => f.write_str(s),
+        }
+    }
+}
+
+impl From<bool> for Value {
+    fn from(value: bool) -> Self {
+        Value::Boolean(value)
+    }
+}
+
+impl From<i32> for Value {
+    fn from(value: i32) -> Self {
+        Value::Integer(value)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(value: &str) -> Self {
+        Value::String(value.to_string())
+    }
+}
+
+impl From<String> for Value {
+    fn from(value: String) -> Self {
+        Value::String(value)
+    }
+}
+
+/// Type of a CESQL [`Value`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Type {
+    Boolean,
+    Integer,
+    String,
+}
+
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Type::Boolean => "Boolean",
+            Type::Integer => "Integer",
+            Type::String => "String",
+        })
+    }
+}
+
+/// Error returned when an expression is not valid CESQL
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    position: usize,
+    message: String,
+}
+
+impl ParseError {
+    pub(crate) fn new(position: usize, message: impl Into<String>) -> Self {
+        ParseError {
+            position,
+            message: message.into(),
+        }
+    }
+
+    /// Get the byte position of the error in the expression
+    pub fn position(&self) -> usize {
+        self.position
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Invalid CESQL expression at position {}: {}",
+            self.position, self.message
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Error returned while evaluating an [`Expression`]
+#[derive(Debug, Clone, PartialEq, Snafu)]
+#[non_exhaustive]
+pub enum EvaluationError {
+    #[snafu(display("Missing attribute '{}'", name))]
+    MissingAttribute { name: String },
+    #[snafu(display("Cannot cast {} '{}' to {}", value.value_type(), value, target))]
+    Cast { value: Value, target: Type },
+    #[snafu(display("Division by zero"))]
+    DivisionByZero {},
+    #[snafu(display("Integer overflow"))]
+    Overflow {},
+    #[snafu(display("Invalid argument of {}: {}", function, reason))]
+    InvalidArgument {
+        function: &'static str,
+        reason: String,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EventBuilder, EventBuilderV10};
+
+    fn event() -> Event {
+        EventBuilderV10::new()
+            .id("0001")
+            .source("http://localhost/orders")
+            .ty("com.example.order.created")
+            .subject("orders")
+            .extension("quantity", 3)
+            .extension("priority", "10")
+            .extension("urgent", true)
+            .build()
+            .unwrap()
+    }
+
+    fn evaluate(expression: &str) -> Result<Value, EvaluationError> {
+        Expression::parse(expression).unwrap().evaluate(&event())
+    }
+
+    #[test]
+    fn evaluate_expressions() {
+        for (expression, expected) in &[
+            (
+                "type LIKE 'com.example.%' AND subject = 'orders'",
+                Value::from(true),
+            ),
+            ("type NOT LIKE '%.created'", Value::from(false)),
+            ("source = 'http://localhost/orders'", Value::from(true)),
+            ("specversion = '1.0'", Value::from(true)),
+            ("quantity + 1 = 4 AND quantity % 2 = 1", Value::from(true)),
+            ("priority > 9", Value::from(true)),
+            ("priority = 10 AND '10' = priority", Value::from(true)),
+            ("urgent AND urgent = 'true'", Value::from(true)),
+            ("NOT urgent OR FALSE", Value::from(false)),
+            ("urgent XOR TRUE", Value::from(false)),
+            (
+                "EXISTS subject AND NOT EXISTS dataschema",
+                Value::from(true),
+            ),
+            ("subject IN ('users', 'orders')", Value::from(true)),
+            ("quantity NOT IN (1, 2, '3')", Value::from(false)),
+            (
+                "'a' < 'b' AND 2 <= 2 AND -1 >= -2 AND 3 > 2",
+                Value::from(true),
+            ),
+            ("-(2 - 3) * 4 / 2", Value::from(2)),
+            ("ABS(-3) + LENGTH('héllo')", Value::from(8)),
+            (
+                "CONCAT_WS(', ', subject, id, quantity)",
+                Value::from("orders, 0001, 3"),
+            ),
+            ("concat() = ''", Value::from(true)),
+            (
+                "UPPER(LEFT(subject, 3)) = 'ORD' AND LOWER(RIGHT('ABC', 10)) = 'abc'",
+                Value::from(true),
+            ),
+            ("TRIM('  a  ')", Value::from("a")),
+            ("SUBSTRING('abcdef', 2, 3)", Value::from("bcd")),
+            ("SUBSTRING('abcdef', -2)", Value::from("ef")),
+            ("INT('12') + 1", Value::from(13)),
+            ("BOOL('false')", Value::from(false)),
+            ("STRING(quantity)", Value::from("3")),
+            ("IS_INT(subject) OR IS_BOOL('TRUE')", Value::from(true)),
+        ] {
+            assert_eq!(Ok(expected.clone()), evaluate(expression), "{}", expression);
+        }
+    }
+
+    #[test]
+    fn evaluation_errors() {
+        assert_eq!(
+            Err(EvaluationError::MissingAttribute {
+                name: String::from("missing")
+            }),
+            evaluate("missing = 'a'")
+        );
+        assert_eq!(
+            Err(EvaluationError::Cast {
+                value: Value::from("orders"),
+                target: Type::Integer
+            }),
+            evaluate("subject + 1")
+        );
+        assert_eq!(Err(EvaluationError::DivisionByZero {}), evaluate("1 / 0"));
+        assert_eq!(
+            Err(EvaluationError::Overflow {}),
+            evaluate("2147483647 + 1")
+        );
+        assert_eq!(Err(EvaluationError::Overflow {}), evaluate("65536 * 65536"));
+        assert!(matches!(
+            evaluate("SUBSTRING('abc', 0)"),
+            Err(EvaluationError::InvalidArgument {
+                function: "SUBSTRING",
+                ..
+            })
+        ));
+
+        // Short circuit skips the evaluation of the missing attribute
+        assert_eq!(Ok(Value::from(false)), evaluate("FALSE AND missing"));
+        assert!(!Expression::parse("missing = 'a'")
+            .unwrap()
+            .matches(&event()));
+    }
+
+    #[test]
+    fn parse_errors() {
+        let error = Expression::parse("subject = ").unwrap_err();
+        assert_eq!(10, error.position());
+        assert_eq!(
+            "Invalid CESQL expression at position 10: unexpected end of expression",
+            error.to_string()
+        );
+        assert!("type LIKE".parse::<Expression>().is_err());
+    }
+}
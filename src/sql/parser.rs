@@ -0,0 +1,568 @@
+use super::lexer::{tokenize, Token};
+use super::{ParseError, Value};
+use std::convert::TryFrom;
+
+/// Parsed expression
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Ast {
+    Literal(Value),
+    Attribute(String),
+    Exists(String),
+    Not(Box<Ast>),
+    Negate(Box<Ast>),
+    Binary(BinaryOperator, Box<Ast>, Box<Ast>),
+    Like {
+        value: Box<Ast>,
+        pattern: String,
+        negated: bool,
+    },
+    In {
+        value: Box<Ast>,
+        set: Vec<Ast>,
+        negated: bool,
+    },
+    Function(Function, Vec<Ast>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum BinaryOperator {
+    And,
+    Or,
+    Xor,
+    Equal,
+    NotEqual,
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Modulo,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Function {
+    Abs,
+    Length,
+    Concat,
+    ConcatWs,
+    Lower,
+    Upper,
+    Trim,
+    Left,
+    Right,
+    Substring,
+    Int,
+    Bool,
+    String,
+    IsInt,
+    IsBool,
+}
+
+impl Function {
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name.to_ascii_uppercase().as_str() {
+            "ABS" => Function::Abs,
+            "LENGTH" => Function::Length,
+            "CONCAT" => Function::Concat,
+            "CONCAT_WS" => Function::ConcatWs,
+            "LOWER" => Function::Lower,
+            "UPPER" => Function::Upper,
+            "TRIM" => Function::Trim,
+            "LEFT" => Function::Left,
+            "RIGHT" => Function::Right,
+            "SUBSTRING" => Function::Substring,
+            "INT" => Function::Int,
+            "BOOL" => Function::Bool,
+            "STRING" => Function::String,
+            "IS_INT" => Function::IsInt,
+            "IS_BOOL" => Function::IsBool,
+            _ => return None,
+        })
+    }
+
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            Function::Abs => "ABS",
+            Function::Length => "LENGTH",
+            Function::Concat => "CONCAT",
+            Function::ConcatWs => "CONCAT_WS",
+            Function::Lower => "LOWER",
+            Function::Upper => "UPPER",
+            Function::Trim => "TRIM",
+            Function::Left => "LEFT",
+            Function::Right => "RIGHT",
+            Function::Substring => "SUBSTRING",
+            Function::Int => "INT",
+            Function::Bool => "BOOL",
+            Function::String => "STRING",
+            Function::IsInt => "IS_INT",
+            Function::IsBool => "IS_BOOL",
+        }
+    }
+
+    fn accepts(self, arguments: usize) -> bool {
+        match self {
+            Function::Concat => true,
+            Function::ConcatWs => arguments >= 1,
+            Function::Left | Function::Right => arguments == 2,
+            Function::Substring => arguments == 2 || arguments == 3,
+            _ => arguments == 1,
+        }
+    }
+}
+
+/// Maximum depth of the parsed expression, which bounds the recursion of both the parser and the
+/// evaluation
+const MAX_DEPTH: usize = 128;
+
+const KEYWORDS: [&str; 9] = [
+    "AND", "OR", "XOR", "NOT", "LIKE", "IN", "EXISTS", "TRUE", "FALSE",
+];
+
+/// Recursive descent parser. From the lowest to the highest precedence:
+///
+/// * `AND`, `OR`, `XOR`, right associative
+/// * `=`, `!=`, `<>`
+/// * `<`, `<=`, `>`, `>=`
+/// * `+`, `-`
+/// * `*`, `/`, `%`
+/// * `LIKE`, `NOT LIKE`, `IN`, `NOT IN`
+/// * unary `NOT` and `-`
+/// * literals, attributes, `EXISTS`, function invocations and parenthesized expressions
+///
+/// Every operator, parenthesis, function invocation and `IN` set adds a level of depth, up to
+/// [`MAX_DEPTH`].
+struct Parser {
+    tokens: Vec<(usize, Token)>,
+    next: usize,
+    end: usize,
+    depth: usize,
+}
+
+pub(crate) fn parse(input: &str) -> Result<Ast, ParseError> {
+    let mut parser = Parser {
+        tokens: tokenize(input)?,
+        next: 0,
+        end: input.len(),
+        depth: 0,
+    };
+    let ast = parser.logic()?;
+    match parser.peek() {
+        None => Ok(ast),
+        Some(_) => Err(parser.error("unexpected token")),
+    }
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.next).map(|(_, t)| t)
+    }
+
+    fn position(&self) -> usize {
+        self.tokens
+            .get(self.next)
+            .map(|(p, _)| *p)
+            .unwrap_or(self.end)
+    }
+
+    fn error(&self, message: &str) -> ParseError {
+        match self.peek() {
+            None => ParseError::new(self.position(), "unexpected end of expression"),
+            Some(_) => ParseError::new(self.position(), message),
+        }
+    }
+
+    fn advance(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.next).map(|(_, t)| t.clone());
+        self.next += 1;
+        token
+    }
+
+    fn is_keyword(&self, keyword: &str) -> bool {
+        self.is_keyword_at(self.next, keyword)
+    }
+
+    fn is_keyword_at(&self, index: usize, keyword: &str) -> bool {
+        match self.tokens.get(index) {
+            Some((_, Token::Word(word))) => word.eq_ignore_ascii_case(keyword),
+            _ => false,
+        }
+    }
+
+    /// Go one level deeper in the expression. Callers save `depth` and restore it once they
+    /// return their node, while an error aborts the whole parsing anyway.
+    fn deepen(&mut self) -> Result<(), ParseError> {
+        if self.depth == MAX_DEPTH {
+            return Err(ParseError::new(
+                self.position(),
+                format!("expression nested deeper than {}", MAX_DEPTH),
+            ));
+        }
+        self.depth += 1;
+        Ok(())
+    }
+
+    fn expect(&mut self, token: Token, message: &str) -> Result<(), ParseError> {
+        if self.peek() == Some(&token) {
+            self.next += 1;
+            Ok(())
+        } else {
+            Err(self.error(message))
+        }
+    }
+
+    fn logic(&mut self) -> Result<Ast, ParseError> {
+        let left = self.equality()?;
+        let operator = if self.is_keyword("AND") {
+            BinaryOperator::And
+        } else if self.is_keyword("OR") {
+            BinaryOperator::Or
+        } else if self.is_keyword("XOR") {
+            BinaryOperator::Xor
+        } else {
+            return Ok(left);
+        };
+        self.next += 1;
+        let depth = self.depth;
+        self.deepen()?;
+        let right = self.logic()?;
+        self.depth = depth;
+        Ok(Ast::Binary(operator, Box::new(left), Box::new(right)))
+    }
+
+    fn equality(&mut self) -> Result<Ast, ParseError> {
+        let depth = self.depth;
+        let mut left = self.relational()?;
+        loop {
+            let operator = match self.peek() {
+                Some(Token::Equal) => BinaryOperator::Equal,
+                Some(Token::NotEqual) => BinaryOperator::NotEqual,
+                _ => {
+                    self.depth = depth;
+                    return Ok(left);
+                }
+            };
+            self.next += 1;
+            self.deepen()?;
+            left = Ast::Binary(operator, Box::new(left), Box::new(self.relational()?));
+        }
+    }
+
+    fn relational(&mut self) -> Result<Ast, ParseError> {
+        let depth = self.depth;
+        let mut left = self.additive()?;
+        loop {
+            let operator = match self.peek() {
+                Some(Token::Less) => BinaryOperator::Less,
+                Some(Token::LessOrEqual) => BinaryOperator::LessOrEqual,
+                Some(Token::Greater) => BinaryOperator::Greater,
+                Some(Token::GreaterOrEqual) => BinaryOperator::GreaterOrEqual,
+                _ => {
+                    self.depth = depth;
+                    return Ok(left);
+                }
+            };
+            self.next += 1;
+            self.deepen()?;
+            left = Ast::Binary(operator, Box::new(left), Box::new(self.additive()?));
+        }
+    }
+
+    fn additive(&mut self) -> Result<Ast, ParseError> {
+        let depth = self.depth;
+        let mut left = self.multiplicative()?;
+        loop {
+            let operator = match self.peek() {
+                Some(Token::Plus) => BinaryOperator::Add,
+                Some(Token::Minus) => BinaryOperator::Subtract,
+                _ => {
+                    self.depth = depth;
+                    return Ok(left);
+                }
+            };
+            self.next += 1;
+            self.deepen()?;
+            left = Ast::Binary(operator, Box::new(left), Box::new(self.multiplicative()?));
+        }
+    }
+
+    fn multiplicative(&mut self) -> Result<Ast, ParseError> {
+        let depth = self.depth;
+        let mut left = self.postfix()?;
+        loop {
+            let operator = match self.peek() {
+                Some(Token::Star) => BinaryOperator::Multiply,
+                Some(Token::Slash) => BinaryOperator::Divide,
+                Some(Token::Percent) => BinaryOperator::Modulo,
+                _ => {
+                    self.depth = depth;
+                    return Ok(left);
+                }
+            };
+            self.next += 1;
+            self.deepen()?;
+            left = Ast::Binary(operator, Box::new(left), Box::new(self.postfix()?));
+        }
+    }
+
+    fn postfix(&mut self) -> Result<Ast, ParseError> {
+        let depth = self.depth;
+        let mut value = self.unary()?;
+        loop {
+            let negated = self.is_keyword("NOT");
+            let operator = if negated { self.next + 1 } else { self.next };
+            if self.is_keyword_at(operator, "LIKE") {
+                self.next = operator + 1;
+                self.deepen()?;
+                let pattern = match self.advance() {
+                    Some(Token::String(pattern)) => pattern,
+                    _ => {
+                        self.next -= 1;
+                        return Err(self.error("expected a string literal pattern after LIKE"));
+                    }
+                };
+                value = Ast::Like {
+                    value: Box::new(value),
+                    pattern,
+                    negated,
+                };
+            } else if self.is_keyword_at(operator, "IN") {
+                self.next = operator + 1;
+                self.deepen()?;
+                self.expect(Token::LeftParen, "expected '(' after IN")?;
+                let mut set = vec![self.logic()?];
+                while self.peek() == Some(&Token::Comma) {
+                    self.next += 1;
+                    set.push(self.logic()?);
+                }
+                self.expect(Token::RightParen, "expected ')'")?;
+                value = Ast::In {
+                    value: Box::new(value),
+                    set,
+                    negated,
+                };
+            } else {
+                self.depth = depth;
+                return Ok(value);
+            }
+        }
+    }
+
+    fn unary(&mut self) -> Result<Ast, ParseError> {
+        let depth = self.depth;
+        let ast = if self.is_keyword("NOT") {
+            self.next += 1;
+            self.deepen()?;
+            Ast::Not(Box::new(self.unary()?))
+        } else if self.peek() == Some(&Token::Minus) {
+            self.next += 1;
+            match self.tokens.get(self.next) {
+                // Folded in the literal, as -2147483648 is in range while 2147483648 isn't
+                Some((position, Token::Integer(i))) => {
+                    let literal = integer(*position, -i)?;
+                    self.next += 1;
+                    literal
+                }
+                _ => {
+                    self.deepen()?;
+                    Ast::Negate(Box::new(self.unary()?))
+                }
+            }
+        } else {
+            return self.primary();
+        };
+        self.depth = depth;
+        Ok(ast)
+    }
+
+    fn primary(&mut self) -> Result<Ast, ParseError> {
+        let position = self.position();
+        match self.advance() {
+            Some(Token::Integer(i)) => integer(position, i),
+            Some(Token::String(s)) => Ok(Ast::Literal(Value::String(s))),
+            Some(Token::LeftParen) => {
+                let depth = self.depth;
+                self.deepen()?;
+                let ast = self.logic()?;
+                self.expect(Token::RightParen, "expected ')'")?;
+                self.depth = depth;
+                Ok(ast)
+            }
+            Some(Token::Word(word)) if self.peek() == Some(&Token::LeftParen) => {
+                let function = Function::from_name(&word).ok_or_else(|| {
+                    ParseError::new(position, format!("unknown function '{}'", word))
+                })?;
+                self.next += 1;
+                let depth = self.depth;
+                self.deepen()?;
+                let mut arguments = Vec::new();
+                if self.peek() != Some(&Token::RightParen) {
+                    arguments.push(self.logic()?);
+                    while self.peek() == Some(&Token::Comma) {
+                        self.next += 1;
+                        arguments.push(self.logic()?);
+                    }
+                }
+                self.expect(Token::RightParen, "expected ')'")?;
+                if !function.accepts(arguments.len()) {
+                    return Err(ParseError::new(
+                        position,
+                        format!("wrong number of arguments for {}", function.name()),
+                    ));
+                }
+                self.depth = depth;
+                Ok(Ast::Function(function, arguments))
+            }
+            Some(Token::Word(word)) if word.eq_ignore_ascii_case("TRUE") => {
+                Ok(Ast::Literal(Value::Boolean(true)))
+            }
+            Some(Token::Word(word)) if word.eq_ignore_ascii_case("FALSE") => {
+                Ok(Ast::Literal(Value::Boolean(false)))
+            }
+            Some(Token::Word(word)) if word.eq_ignore_ascii_case("EXISTS") => {
+                let position = self.position();
+                match self.advance() {
+                    Some(Token::Word(name)) if !is_keyword(&name) => Ok(Ast::Exists(name)),
+                    _ => Err(ParseError::new(
+                        position,
+                        "expected an attribute name after EXISTS",
+                    )),
+                }
+            }
+            Some(Token::Word(word)) if !is_keyword(&word) => Ok(Ast::Attribute(word)),
+            _ => {
+                self.next -= 1;
+                Err(self.error("expected an expression"))
+            }
+        }
+    }
+}
+
+/// CESQL integers are 32 bits
+fn integer(position: usize, value: i64) -> Result<Ast, ParseError> {
+    i32::try_from(value)
+        .map(|i| Ast::Literal(Value::Integer(i)))
+        .map_err(|_| ParseError::new(position, "integer literal out of range"))
+}
+
+fn is_keyword(word: &str) -> bool {
+    KEYWORDS.iter().any(|k| k.eq_ignore_ascii_case(word))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attribute(name: &str) -> Box<Ast> {
+        Box::new(Ast::Attribute(String::from(name)))
+    }
+
+    fn string(value: &str) -> Box<Ast> {
+        Box::new(Ast::Literal(Value::String(String::from(value))))
+    }
+
+    #[test]
+    fn precedence() {
+        assert_eq!(
+            Ast::Binary(
+                BinaryOperator::And,
+                Box::new(Ast::Like {
+                    value: attribute("type"),
+                    pattern: String::from("com.example.%"),
+                    negated: false
+                }),
+                Box::new(Ast::Binary(
+                    BinaryOperator::Equal,
+                    attribute("subject"),
+                    string("orders")
+                ))
+            ),
+            parse("type LIKE 'com.example.%' and subject = 'orders'").unwrap()
+        );
+
+        assert_eq!(
+            Ast::Binary(
+                BinaryOperator::Equal,
+                Box::new(Ast::Binary(
+                    BinaryOperator::Add,
+                    Box::new(Ast::Literal(Value::Integer(1))),
+                    Box::new(Ast::Binary(
+                        BinaryOperator::Multiply,
+                        Box::new(Ast::Literal(Value::Integer(-2))),
+                        Box::new(Ast::Literal(Value::Integer(3)))
+                    ))
+                )),
+                Box::new(Ast::Negate(attribute("x")))
+            ),
+            parse("1 + -2 * 3 = -x").unwrap()
+        );
+
+        assert_eq!(
+            Ast::In {
+                value: attribute("source"),
+                set: vec![*string("a"), *string("b")],
+                negated: true
+            },
+            parse("source NOT IN ('a', 'b')").unwrap()
+        );
+    }
+
+    #[test]
+    fn functions() {
+        assert_eq!(
+            Ast::Function(
+                Function::Concat,
+                vec![*attribute("type"), *string("-"), *attribute("source")]
+            ),
+            parse("concat(type, '-', source)").unwrap()
+        );
+        assert_eq!(
+            Ast::Function(Function::Concat, vec![]),
+            parse("CONCAT()").unwrap()
+        );
+        assert!(parse("LEFT('a')").is_err());
+        assert!(parse("UNKNOWN('a')").is_err());
+    }
+
+    #[test]
+    fn errors() {
+        for (expression, position) in &[
+            ("", 0),
+            ("(a = 'b'", 8),
+            ("a = ", 4),
+            ("a LIKE b", 7),
+            ("EXISTS 'a'", 7),
+            ("a b", 2),
+            ("AND", 0),
+            ("2147483648", 0),
+            ("1 - -2147483649", 5),
+        ] {
+            assert_eq!(
+                *position,
+                parse(expression).unwrap_err().position(),
+                "{}",
+                expression
+            );
+        }
+        assert_eq!(
+            Ast::Literal(Value::Integer(i32::MIN)),
+            parse("-2147483648").unwrap()
+        );
+    }
+
+    #[test]
+    fn depth() {
+        let nested = |open: &str, close: &str, depth: usize| {
+            parse(&format!("{}a{}", open.repeat(depth), close.repeat(depth)))
+        };
+        assert!(nested("(", ")", MAX_DEPTH).is_ok());
+        for (open, close) in &[("(", ")"), ("NOT ", ""), ("- ", ""), ("LOWER(", ")")] {
+            assert!(nested(open, close, 1000).is_err(), "{}", open);
+        }
+        assert!(parse(&vec!["1"; 1000].join(" + ")).is_err());
+        assert!(parse(&vec!["a"; 1000].join(" AND ")).is_err());
+    }
+}
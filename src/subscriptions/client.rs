@@ -0,0 +1,177 @@
+use super::Subscription;
+use reqwest::{Client, RequestBuilder, StatusCode, Url};
+use serde::de::DeserializeOwned;
+use snafu::{ResultExt, Snafu};
+
+/// Represents an error of a [`SubscriptionsClient`] request
+#[derive(Debug, Snafu)]
+pub enum Error {
+    #[snafu(display("Subscriptions API request failed: {}", source))]
+    Request { source: reqwest::Error },
+    #[snafu(display("Subscriptions API replied {}: {}", status, body))]
+    Status { status: u16, body: String },
+    #[snafu(display("Invalid Subscriptions API response: {}", source))]
+    InvalidResponse { source: serde_json::Error },
+    #[snafu(display("Invalid subscription id {}", id))]
+    InvalidId { id: String },
+}
+
+/// Client of the Subscriptions API of a broker, managing its subscriptions with the
+/// `/subscriptions` resources under a base URL. Enabled with the `reqwest` feature.
+///
+/// ```no_run
+/// use cloudevents::subscriptions::{Protocol, Subscription, SubscriptionsClient};
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = SubscriptionsClient::new(
+///     reqwest::Client::new(),
+///     "https://broker.example.com/".parse()?,
+/// );
+///
+/// let subscription = Subscription::new("https://consumer.example.com/events", Protocol::Http)?;
+/// let created = client.create(&subscription).await?;
+/// assert_eq!(1, client.list().await?.len());
+/// client.delete(created.id.as_deref().unwrap_or_default()).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct SubscriptionsClient {
+    client: Client,
+    base: Url,
+}
+
+impl SubscriptionsClient {
+    /// Create a new [`SubscriptionsClient`] sending the requests with `client` to the
+    /// Subscriptions API at `base`
+    pub fn new(client: Client, base: Url) -> Self {
+        SubscriptionsClient { client, base }
+    }
+
+    /// Create `subscription`, returning it with the `id` assigned by the broker
+    pub async fn create(&self, subscription: &Subscription) -> Result<Subscription, Error> {
+        send_json(self.create_request(subscription)).await
+    }
+
+    /// List the subscriptions
+    pub async fn list(&self) -> Result<Vec<Subscription>, Error> {
+        send_json(self.client.get(self.collection())).await
+    }
+
+    /// Get the subscription `id`
+    pub async fn get(&self, id: &str) -> Result<Subscription, Error> {
+        send_json(self.client.get(self.resource(id)?)).await
+    }
+
+    /// Delete the subscription `id`
+    pub async fn delete(&self, id: &str) -> Result<(), Error> {
+        send(self.client.delete(self.resource(id)?))
+            .await
+            .map(|_| ())
+    }
+
+    fn create_request(&self, subscription: &Subscription) -> RequestBuilder {
+        self.client
+            .post(self.collection())
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            // Serializing a Subscription can't fail, its maps have string keys
+            .body(serde_json::to_vec(subscription).unwrap_or_default())
+    }
+
+    fn collection(&self) -> Url {
+        let mut url = self.base.clone();
+        if let Ok(mut segments) = url.path_segments_mut() {
+            segments.pop_if_empty().push("subscriptions");
+        }
+        url
+    }
+
+    fn resource(&self, id: &str) -> Result<Url, Error> {
+        let mut url = self.collection();
+        match url.path_segments_mut() {
+            Ok(mut segments) if !id.is_empty() => {
+                segments.push(id);
+            }
+            _ => return Err(Error::InvalidId { id: id.to_string() }),
+        }
+        Ok(url)
+    }
+}
+
+/// Send `request`, failing if the response status is not a success
+async fn send(request: RequestBuilder) -> Result<bytes::Bytes, Error> {
+    let response = request.send().await.context(Request)?;
+    let status = response.status();
+    let body = response.bytes().await.context(Request)?;
+    check_status(status, &body)?;
+    Ok(body)
+}
+
+/// Send `request`, parsing the JSON body of the response
+async fn send_json<T: DeserializeOwned>(request: RequestBuilder) -> Result<T, Error> {
+    let body = send(request).await?;
+    serde_json::from_slice(&body).context(InvalidResponse)
+}
+
+fn check_status(status: StatusCode, body: &[u8]) -> Result<(), Error> {
+    if status.is_success() {
+        Ok(())
+    } else {
+        Err(Error::Status {
+            status: status.as_u16(),
+            body: String::from_utf8_lossy(body).into_owned(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::subscriptions::Protocol;
+
+    fn client(base: &str) -> SubscriptionsClient {
+        SubscriptionsClient::new(Client::new(), base.parse().unwrap())
+    }
+
+    #[test]
+    fn urls() {
+        let client = client("https://broker.example.com/api/");
+        assert_eq!(
+            "https://broker.example.com/api/subscriptions",
+            client.collection().as_str()
+        );
+        assert_eq!(
+            "https://broker.example.com/api/subscriptions/a%2Fb",
+            client.resource("a/b").unwrap().as_str()
+        );
+        assert!(matches!(client.resource(""), Err(Error::InvalidId { .. })));
+    }
+
+    #[test]
+    fn create_request() {
+        let subscription = Subscription::new("http://localhost:8080", Protocol::Http).unwrap();
+        let request = client("https://broker.example.com")
+            .create_request(&subscription)
+            .build()
+            .unwrap();
+        assert_eq!(reqwest::Method::POST, request.method());
+        assert_eq!(
+            "https://broker.example.com/subscriptions",
+            request.url().as_str()
+        );
+        let body = request.body().and_then(|b| b.as_bytes()).unwrap();
+        assert_eq!(
+            subscription,
+            serde_json::from_slice::<Subscription>(body).unwrap()
+        );
+    }
+
+    #[test]
+    fn status() {
+        assert!(check_status(StatusCode::CREATED, b"").is_ok());
+        assert!(matches!(
+            check_status(StatusCode::NOT_FOUND, b"no such subscription"),
+            Err(Error::Status { status: 404, body }) if body == "no such subscription"
+        ));
+    }
+}
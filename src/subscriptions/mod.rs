@@ -0,0 +1,232 @@
+//! Resources of the [CloudEvents Subscriptions API](https://github.com/cloudevents/spec/blob/main/subscriptions/spec.md),
+//! serialized with their JSON representation.
+//!
+//! A [`Subscription`] delivers the events of a producer matching its [`Filter`]s to a sink, with
+//! the [`Protocol`] and the settings it's configured with. With the `reqwest` feature,
+//! [`SubscriptionsClient`] creates, lists and deletes the subscriptions of a compliant broker.
+//!
+//! ```
+//! use cloudevents::filter::Filter;
+//! use cloudevents::subscriptions::{Protocol, Subscription};
+//! use serde_json::json;
+//!
+//! let subscription = Subscription::new("https://consumer.example.com/events", Protocol::Http)
+//!     .unwrap()
+//!     .source("https://producer.example.com/orders")
+//!     .types(vec!["com.example.order.created".to_string()])
+//!     .filter(Filter::exact("subject", "books"))
+//!     .protocol_setting("method", json!("POST"));
+//!
+//! assert_eq!(
+//!     json!({
+//!         "sink": "https://consumer.example.com/events",
+//!         "protocol": "HTTP",
+//!         "source": "https://producer.example.com/orders",
+//!         "types": ["com.example.order.created"],
+//!         "filters": [{"exact": {"subject": "books"}}],
+//!         "protocolsettings": {"method": "POST"}
+//!     }),
+//!     serde_json::to_value(&subscription).unwrap()
+//! );
+//! ```
+
+#[cfg(feature = "reqwest")]
+mod client;
+
+#[cfg(feature = "reqwest")]
+pub use client::{Error, SubscriptionsClient};
+
+use crate::filter::Filter;
+use crate::{AttributesReader, Event};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use url::Url;
+
+/// Subscription resource of the Subscriptions API
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Subscription {
+    /// Identifier of the subscription, assigned by the broker when it's created
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// URL the events are delivered to
+    pub sink: Url,
+    /// Protocol the events are delivered with
+    pub protocol: Protocol,
+    /// `source` of the delivered events
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    /// `type`s of the delivered events, any type if empty
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub types: Vec<String>,
+    /// Configuration of the subscription specific to the producer
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub config: BTreeMap<String, String>,
+    /// Filters the delivered events must all match
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub filters: Vec<Filter>,
+    /// Credential the broker authenticates to the sink with
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sinkcredential: Option<SinkCredential>,
+    /// Settings of the delivery specific to the [`Protocol`], e.g. the `headers` of HTTP or the
+    /// `topicname` of MQTT and Kafka
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub protocolsettings: BTreeMap<String, Value>,
+}
+
+impl Subscription {
+    /// Create a new [`Subscription`] delivering all the events to `sink` with `protocol`
+    pub fn new(sink: &str, protocol: Protocol) -> Result<Self, url::ParseError> {
+        Ok(Subscription {
+            id: None,
+            sink: Url::parse(sink)?,
+            protocol,
+            source: None,
+            types: Vec::new(),
+            config: BTreeMap::new(),
+            filters: Vec::new(),
+            sinkcredential: None,
+            protocolsettings: BTreeMap::new(),
+        })
+    }
+
+    /// Deliver the events with `source` only
+    pub fn source(mut self, source: impl Into<String>) -> Self {
+        self.source = Some(source.into());
+        self
+    }
+
+    /// Deliver the events of `types` only
+    pub fn types(mut self, types: Vec<String>) -> Self {
+        self.types = types;
+        self
+    }
+
+    /// Deliver the events matching `filter` only
+    pub fn filter(mut self, filter: Filter) -> Self {
+        self.filters.push(filter);
+        self
+    }
+
+    /// Set the protocol setting `name`
+    pub fn protocol_setting(mut self, name: impl Into<String>, value: Value) -> Self {
+        self.protocolsettings.insert(name.into(), value);
+        self
+    }
+
+    /// Authenticate to the sink with `credential`
+    pub fn sink_credential(mut self, credential: SinkCredential) -> Self {
+        self.sinkcredential = Some(credential);
+        self
+    }
+
+    /// Returns `true` if `event` is delivered by this subscription, checking its `source`,
+    /// `types` and `filters`
+    pub fn matches(&self, event: &Event) -> bool {
+        self.source
+            .as_deref()
+            .is_none_or(|s| event.get_source().as_str() == s)
+            && (self.types.is_empty() || self.types.iter().any(|t| t == event.get_type()))
+            && self.filters.iter().all(|f| f.matches(event))
+    }
+}
+
+/// Protocol the events of a [`Subscription`] are delivered with
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum Protocol {
+    /// [HTTP](https://github.com/cloudevents/spec/blob/main/cloudevents/bindings/http-protocol-binding.md)
+    Http,
+    /// [MQTT](https://github.com/cloudevents/spec/blob/main/cloudevents/bindings/mqtt-protocol-binding.md) 3.1.1
+    Mqtt3,
+    /// [MQTT](https://github.com/cloudevents/spec/blob/main/cloudevents/bindings/mqtt-protocol-binding.md) 5.0
+    Mqtt5,
+    /// [AMQP](https://github.com/cloudevents/spec/blob/main/cloudevents/bindings/amqp-protocol-binding.md) 1.0
+    Amqp,
+    /// [Kafka](https://github.com/cloudevents/spec/blob/main/cloudevents/bindings/kafka-protocol-binding.md)
+    Kafka,
+    /// [NATS](https://github.com/cloudevents/spec/blob/main/cloudevents/bindings/nats-protocol-binding.md)
+    Nats,
+}
+
+/// Credential the broker authenticates to the sink of a [`Subscription`] with
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum SinkCredential {
+    /// Access token sent with each delivery request
+    #[serde(rename = "ACCESSTOKEN")]
+    AccessToken {
+        accesstoken: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        accesstokenexpiresat: Option<String>,
+    },
+    /// Refresh token the access tokens are obtained with
+    #[serde(rename = "REFRESHTOKEN")]
+    RefreshToken {
+        refreshtoken: String,
+        tokenendpoint: Url,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        accesstoken: Option<String>,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EventBuilder, EventBuilderV10};
+    use serde_json::json;
+
+    #[test]
+    fn deserialize() {
+        let subscription: Subscription = serde_json::from_value(json!({
+            "id": "sub-1",
+            "sink": "mqtt://broker.example.com",
+            "protocol": "MQTT5",
+            "config": {"region": "eu"},
+            "sinkcredential": {"type": "ACCESSTOKEN", "accesstoken": "secret"},
+            "protocolsettings": {"topicname": "orders", "qos": 1}
+        }))
+        .unwrap();
+        assert_eq!(Some("sub-1"), subscription.id.as_deref());
+        assert_eq!(Protocol::Mqtt5, subscription.protocol);
+        assert_eq!(Some(&"eu".to_string()), subscription.config.get("region"));
+        assert_eq!(
+            Some(SinkCredential::AccessToken {
+                accesstoken: "secret".to_string(),
+                accesstokenexpiresat: None
+            }),
+            subscription.sinkcredential
+        );
+        assert_eq!(Some(&json!(1)), subscription.protocolsettings.get("qos"));
+        assert!(
+            serde_json::from_value::<Subscription>(json!({"sink": "http://localhost"})).is_err()
+        );
+    }
+
+    #[test]
+    fn matches() {
+        let event = EventBuilderV10::new()
+            .id("0001")
+            .source("http://localhost/")
+            .ty("example.test")
+            .subject("books")
+            .build()
+            .unwrap();
+        let subscription = Subscription::new("http://localhost:8080", Protocol::Http).unwrap();
+        assert!(subscription.matches(&event));
+        assert!(subscription
+            .clone()
+            .source("http://localhost/")
+            .types(vec![
+                "example.other".to_string(),
+                "example.test".to_string()
+            ])
+            .filter(Filter::exact("subject", "books"))
+            .matches(&event));
+        assert!(!subscription
+            .clone()
+            .types(vec!["example.other".to_string()])
+            .matches(&event));
+        assert!(!subscription.source("http://other/").matches(&event));
+    }
+}
@@ -0,0 +1,119 @@
+use crate::Event;
+use std::collections::BTreeSet;
+use std::fmt::Write;
+
+/// Asserts that two [`Event`](crate::Event)s are equal. On failure, the panic message lists
+/// only the attributes, extensions and data which differ.
+///
+/// ```should_panic
+/// use cloudevents::{assert_event_eq, AttributesWriter, EventBuilder, EventBuilderV10};
+///
+/// let left = EventBuilderV10::new()
+///     .id("0001")
+///     .source("http://localhost/")
+///     .ty("example.test")
+///     .build()
+///     .unwrap();
+/// let mut right = left.clone();
+/// right.set_id("0002");
+///
+/// // Panics with:
+/// // assertion failed: `(left == right)`
+/// //   id: `0001` != `0002`
+/// assert_event_eq!(left, right);
+/// ```
+#[macro_export]
+macro_rules! assert_event_eq {
+    ($left:expr, $right:expr $(,)?) => {
+        if let Some(diff) = $crate::testing::event_diff(&$left, &$right) {
+            panic!("assertion failed: `(left == right)`\n{}", diff)
+        }
+    };
+    ($left:expr, $right:expr, $($arg:tt)+) => {
+        if let Some(diff) = $crate::testing::event_diff(&$left, &$right) {
+            panic!(
+                "assertion failed: `(left == right)`: {}\n{}",
+                format_args!($($arg)+),
+                diff
+            )
+        }
+    };
+}
+
+/// Describe the differences between two events, one per line, or `None` if they are equal
+#[doc(hidden)]
+pub fn event_diff(left: &Event, right: &Event) -> Option<String> {
+    if left == right {
+        return None;
+    }
+
+    let names: BTreeSet<&str> = left.iter().chain(right.iter()).map(|(n, _)| n).collect();
+    let mut diff = String::new();
+    for name in names {
+        let (l, r) = (left.attribute(name), right.attribute(name));
+        if l != r {
+            let _ = writeln!(diff, "  {}: {} != {}", name, describe(l), describe(r));
+        }
+    }
+    if left.data != right.data {
+        let _ = writeln!(diff, "  data: {:?} != {:?}", left.data, right.data);
+    }
+    Some(diff)
+}
+
+fn describe(value: Option<impl std::fmt::Display>) -> String {
+    match value {
+        Some(value) => format!("`{}`", value),
+        None => String::from("<missing>"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AttributesWriter, EventBuilder, EventBuilderV10};
+    use serde_json::json;
+
+    fn event() -> Event {
+        EventBuilderV10::new()
+            .id("0001")
+            .source("http://localhost/")
+            .ty("example.test")
+            .extension("someint", 10)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn equal_events() {
+        assert_eq!(None, event_diff(&event(), &event()));
+        assert_event_eq!(event(), event());
+        assert_event_eq!(event(), event(), "events of {}", "test");
+    }
+
+    #[test]
+    fn diff() {
+        let mut right = event();
+        right.set_id("0002");
+        right.set_subject(Some("orders"));
+        right.set_extension("someint", 11);
+        right.write_data("application/json", json!({"hello": "world"}));
+
+        assert_eq!(
+            Some(String::from(
+                "  datacontenttype: <missing> != `application/json`\n  id: `0001` != `0002`\n  \
+                 someint: `10` != `11`\n  subject: <missing> != `orders`\n  \
+                 data: None != Some(Json(Object {\"hello\": String(\"world\")}))\n"
+            )),
+            event_diff(&event(), &right)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "id: `0001` != `0002`")]
+    fn assert_panics() {
+        let mut right = event();
+        right.set_id("0002");
+        assert_event_eq!(event(), right);
+    }
+}
@@ -0,0 +1,182 @@
+use crate::filter::Filter;
+use crate::Event;
+use futures::channel::mpsc::{unbounded, UnboundedReceiver, UnboundedSender};
+use futures::stream::Stream;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+
+/// In-memory broker delivering each published [`Event`] to the [`Subscription`]s whose
+/// [`Filter`] matches it, to test producers and consumers without a real transport.
+///
+/// Clones share the same subscriptions, so a clone can be handed to each producer.
+///
+/// ```
+/// use cloudevents::filter::Filter;
+/// use cloudevents::testing::InMemoryBroker;
+/// use cloudevents::{AttributesReader, EventBuilder, EventBuilderV10};
+/// use futures::StreamExt;
+///
+/// let broker = InMemoryBroker::new();
+/// let mut orders = broker.subscribe(Filter::prefix("type", "com.example.order."));
+/// let mut all = broker.subscribe_all();
+///
+/// let event = EventBuilderV10::new()
+///     .id("0001")
+///     .source("http://localhost/")
+///     .ty("com.example.order.created")
+///     .build()
+///     .unwrap();
+/// assert_eq!(2, broker.publish(event));
+///
+/// futures::executor::block_on(async {
+///     assert_eq!("0001", orders.next().await.unwrap().get_id());
+///     assert_eq!("0001", all.next().await.unwrap().get_id());
+/// });
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct InMemoryBroker {
+    subscribers: Arc<Mutex<Vec<Subscriber>>>,
+}
+
+#[derive(Debug)]
+struct Subscriber {
+    filter: Filter,
+    sender: UnboundedSender<Event>,
+}
+
+impl InMemoryBroker {
+    /// Create a new [`InMemoryBroker`] without subscriptions
+    pub fn new() -> Self {
+        InMemoryBroker::default()
+    }
+
+    /// Subscribe to the events matching `filter`
+    pub fn subscribe(&self, filter: Filter) -> Subscription {
+        let (sender, receiver) = unbounded();
+        self.subscribers
+            .lock()
+            .unwrap()
+            .push(Subscriber { filter, sender });
+        Subscription { receiver }
+    }
+
+    /// Subscribe to all the events
+    pub fn subscribe_all(&self) -> Subscription {
+        self.subscribe(Filter::All(Vec::new()))
+    }
+
+    /// Deliver `event` to the matching subscriptions, returning the number of deliveries.
+    /// Dropped subscriptions are removed.
+    pub fn publish(&self, event: Event) -> usize {
+        let mut subscribers = self.subscribers.lock().unwrap();
+        subscribers.retain(|s| !s.sender.is_closed());
+        subscribers
+            .iter()
+            .filter(|s| s.filter.matches(&event))
+            .filter(|s| s.sender.unbounded_send(event.clone()).is_ok())
+            .count()
+    }
+
+    /// Get the number of subscriptions which are not dropped
+    pub fn subscriptions(&self) -> usize {
+        self.subscribers
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|s| !s.sender.is_closed())
+            .count()
+    }
+}
+
+/// [`Stream`] of the events delivered by [`InMemoryBroker`] to a subscription.
+/// Dropping it unsubscribes.
+#[derive(Debug)]
+pub struct Subscription {
+    receiver: UnboundedReceiver<Event>,
+}
+
+impl Subscription {
+    /// Get the next event already delivered, without waiting
+    pub fn try_recv(&mut self) -> Option<Event> {
+        self.receiver.try_recv().ok()
+    }
+
+    /// Get all the events already delivered, without waiting
+    pub fn drain(&mut self) -> Vec<Event> {
+        std::iter::from_fn(|| self.try_recv()).collect()
+    }
+}
+
+impl Stream for Subscription {
+    type Item = Event;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.receiver).poll_next(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AttributesReader, EventBuilder, EventBuilderV10};
+    use futures::StreamExt;
+
+    fn event(id: &str, ty: &str) -> Event {
+        EventBuilderV10::new()
+            .id(id)
+            .source("http://localhost/")
+            .ty(ty)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn fan_out_with_filters() {
+        let broker = InMemoryBroker::new();
+        let mut created = broker.subscribe(Filter::suffix("type", ".created"));
+        let mut deleted = broker.subscribe(Filter::suffix("type", ".deleted"));
+        let mut all = broker.clone().subscribe_all();
+
+        assert_eq!(2, broker.publish(event("1", "example.created")));
+        assert_eq!(2, broker.publish(event("2", "example.deleted")));
+        assert_eq!(1, broker.publish(event("3", "example.updated")));
+
+        let ids = |events: Vec<Event>| -> Vec<String> {
+            events.iter().map(|e| e.get_id().to_string()).collect()
+        };
+        assert_eq!(vec!["1"], ids(created.drain()));
+        assert_eq!(vec!["2"], ids(deleted.drain()));
+        assert_eq!(vec!["1", "2", "3"], ids(all.drain()));
+        assert!(all.try_recv().is_none());
+    }
+
+    #[test]
+    fn async_subscriber() {
+        let broker = InMemoryBroker::new();
+        let subscription = broker.subscribe_all();
+
+        let producer = broker.clone();
+        let handle = std::thread::spawn(move || {
+            for i in 0..3 {
+                producer.publish(event(&i.to_string(), "example.test"));
+            }
+        });
+
+        let events: Vec<Event> =
+            futures::executor::block_on(subscription.take(3).collect::<Vec<Event>>());
+        handle.join().unwrap();
+        assert_eq!(3, events.len());
+    }
+
+    #[test]
+    fn dropped_subscription() {
+        let broker = InMemoryBroker::new();
+        let subscription = broker.subscribe_all();
+        assert_eq!(1, broker.subscriptions());
+
+        drop(subscription);
+        assert_eq!(0, broker.subscriptions());
+        assert_eq!(0, broker.publish(event("1", "example.test")));
+    }
+}
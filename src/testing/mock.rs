@@ -0,0 +1,271 @@
+use crate::event::SpecVersion;
+use crate::message::{
+    BinaryDeserializer, BinarySerializer, Error, MessageAttributeValue, Result,
+    StructuredDeserializer, StructuredSerializer,
+};
+use crate::Event;
+use bytes::Bytes;
+use std::sync::{Arc, Mutex};
+
+/// Message recorded by [`MockSink`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum RecordedMessage {
+    /// Message serialized with [`BinarySerializer`]
+    Binary(BinaryMessage),
+    /// Message serialized with [`StructuredSerializer`]
+    Structured(Vec<u8>),
+}
+
+impl RecordedMessage {
+    /// Parse the recorded message back to [`Event`]
+    pub fn to_event(&self) -> Result<Event> {
+        match self {
+            RecordedMessage::Binary(message) => BinaryDeserializer::into_event(message.clone()),
+            RecordedMessage::Structured(bytes) => Ok(serde_json::from_slice(bytes)?),
+        }
+    }
+}
+
+/// Attributes, extensions and data of a binary message recorded by [`MockSink`],
+/// in the order they were serialized
+#[derive(Debug, Clone, PartialEq)]
+pub struct BinaryMessage {
+    pub spec_version: SpecVersion,
+    pub attributes: Vec<(String, MessageAttributeValue<'static>)>,
+    pub extensions: Vec<(String, MessageAttributeValue<'static>)>,
+    pub data: Option<Vec<u8>>,
+}
+
+impl BinaryMessage {
+    /// Get the value of the attribute `name`
+    pub fn attribute(&self, name: &str) -> Option<&MessageAttributeValue<'static>> {
+        find(&self.attributes, name)
+    }
+
+    /// Get the value of the extension `name`
+    pub fn extension(&self, name: &str) -> Option<&MessageAttributeValue<'static>> {
+        find(&self.extensions, name)
+    }
+}
+
+fn find<'a>(
+    values: &'a [(String, MessageAttributeValue<'static>)],
+    name: &str,
+) -> Option<&'a MessageAttributeValue<'static>> {
+    values.iter().find(|(n, _)| n == name).map(|(_, v)| v)
+}
+
+impl BinaryDeserializer for BinaryMessage {
+    fn deserialize_binary<R: Sized, V: BinarySerializer<R>>(self, mut visitor: V) -> Result<R> {
+        visitor = visitor.set_spec_version(self.spec_version)?;
+        for (name, value) in self.attributes {
+            visitor = visitor.set_attribute(&name, value)?;
+        }
+        for (name, value) in self.extensions {
+            visitor = visitor.set_extension(&name, value)?;
+        }
+        match self.data {
+            Some(data) => visitor.end_with_data(Bytes::from(data)),
+            None => visitor.end(),
+        }
+    }
+}
+
+/// Sink implementing [`BinarySerializer`] and [`StructuredSerializer`], recording every message
+/// serialized into it, to test the code writing events to a protocol binding.
+///
+/// Clones share the recorded messages, so a clone can be passed by value to the serializers.
+///
+/// ```
+/// use cloudevents::message::{BinaryDeserializer, MessageAttributeValue};
+/// use cloudevents::testing::{MockSink, RecordedMessage};
+/// use cloudevents::{EventBuilder, EventBuilderV10};
+///
+/// let event = EventBuilderV10::new()
+///     .id("0001")
+///     .source("http://localhost/")
+///     .ty("example.test")
+///     .build()
+///     .unwrap();
+///
+/// let sink = MockSink::new();
+/// event.clone().deserialize_binary(sink.clone()).unwrap();
+/// sink.send_structured(event.clone()).unwrap();
+///
+/// match &sink.messages()[0] {
+///     RecordedMessage::Binary(message) => assert_eq!(
+///         Some(&MessageAttributeValue::from(String::from("0001"))),
+///         message.attribute("id")
+///     ),
+///     _ => unreachable!(),
+/// }
+/// assert_eq!(vec![event.clone(), event], sink.events());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MockSink {
+    messages: Arc<Mutex<Vec<RecordedMessage>>>,
+    pending: Option<BinaryMessage>,
+}
+
+impl MockSink {
+    /// Create a new empty [`MockSink`]
+    pub fn new() -> Self {
+        MockSink::default()
+    }
+
+    /// Serialize `event` in binary mode into this sink
+    pub fn send_binary(&self, event: Event) -> Result<()> {
+        BinaryDeserializer::deserialize_binary(event, self.clone())
+    }
+
+    /// Serialize `event` in structured mode into this sink
+    pub fn send_structured(&self, event: Event) -> Result<()> {
+        StructuredDeserializer::deserialize_structured(event, self.clone())
+    }
+
+    /// Get the recorded messages
+    pub fn messages(&self) -> Vec<RecordedMessage> {
+        self.messages.lock().unwrap().clone()
+    }
+
+    /// Get the recorded messages parsed back to [`Event`]s.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a recorded message is not a valid event.
+    pub fn events(&self) -> Vec<Event> {
+        self.messages()
+            .iter()
+            .map(|m| m.to_event().expect("recorded message is not a valid event"))
+            .collect()
+    }
+
+    /// Get the number of recorded messages
+    pub fn len(&self) -> usize {
+        self.messages.lock().unwrap().len()
+    }
+
+    /// Returns `true` if no message was recorded
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Remove all the recorded messages
+    pub fn clear(&self) {
+        self.messages.lock().unwrap().clear()
+    }
+
+    fn pending(&mut self) -> Result<&mut BinaryMessage> {
+        self.pending
+            .as_mut()
+            .ok_or(Error::MissingRequiredAttribute {
+                attribute_name: "specversion",
+            })
+    }
+
+    fn record(&self, message: RecordedMessage) {
+        self.messages.lock().unwrap().push(message)
+    }
+}
+
+impl BinarySerializer<()> for MockSink {
+    fn set_spec_version(mut self, spec_version: SpecVersion) -> Result<Self> {
+        self.pending = Some(BinaryMessage {
+            spec_version,
+            attributes: Vec::new(),
+            extensions: Vec::new(),
+            data: None,
+        });
+        Ok(self)
+    }
+
+    fn set_attribute(mut self, name: &str, value: MessageAttributeValue) -> Result<Self> {
+        self.pending()?
+            .attributes
+            .push((name.to_string(), value.into_owned()));
+        Ok(self)
+    }
+
+    fn set_extension(mut self, name: &str, value: MessageAttributeValue) -> Result<Self> {
+        self.pending()?
+            .extensions
+            .push((name.to_string(), value.into_owned()));
+        Ok(self)
+    }
+
+    fn end_with_data(mut self, bytes: Bytes) -> Result<()> {
+        self.pending()?.data = Some(Vec::from(bytes));
+        self.end()
+    }
+
+    fn end(mut self) -> Result<()> {
+        let message = self.pending()?.clone();
+        self.record(RecordedMessage::Binary(message));
+        Ok(())
+    }
+}
+
+impl StructuredSerializer<()> for MockSink {
+    fn set_structured_event(self, bytes: Vec<u8>) -> Result<()> {
+        self.record(RecordedMessage::Structured(bytes));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EventBuilder, EventBuilderV03};
+    use serde_json::json;
+
+    #[test]
+    fn record_binary_and_structured() {
+        let event = EventBuilderV03::new()
+            .id("0001")
+            .source("http://localhost/")
+            .ty("example.test")
+            .extension("someint", 10)
+            .data("application/json", json!({"hello": "world"}))
+            .build()
+            .unwrap();
+
+        let sink = MockSink::new();
+        sink.send_binary(event.clone()).unwrap();
+        sink.send_structured(event.clone()).unwrap();
+        assert_eq!(2, sink.len());
+
+        let messages = sink.messages();
+        match &messages[0] {
+            RecordedMessage::Binary(message) => {
+                assert_eq!(SpecVersion::V03, message.spec_version);
+                assert_eq!(
+                    Some(&MessageAttributeValue::from(String::from("example.test"))),
+                    message.attribute("type")
+                );
+                assert_eq!(
+                    Some(&MessageAttributeValue::Integer(10)),
+                    message.extension("someint")
+                );
+                assert_eq!(Some(br#"{"hello":"world"}"#.to_vec()), message.data);
+            }
+            m => panic!("unexpected message {:?}", m),
+        }
+        match &messages[1] {
+            RecordedMessage::Structured(bytes) => {
+                assert_eq!(event, serde_json::from_slice::<Event>(bytes).unwrap())
+            }
+            m => panic!("unexpected message {:?}", m),
+        }
+        assert_eq!(vec![event.clone(), event], sink.events());
+
+        sink.clear();
+        assert!(sink.is_empty());
+    }
+
+    #[test]
+    fn missing_spec_version() {
+        assert!(MockSink::new()
+            .set_attribute("id", MessageAttributeValue::from("0001"))
+            .is_err());
+    }
+}
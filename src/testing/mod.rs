@@ -0,0 +1,8 @@
+mod assert;
+mod broker;
+mod mock;
+
+#[doc(hidden)]
+pub use assert::event_diff;
+pub use broker::{InMemoryBroker, Subscription};
+pub use mock::{BinaryMessage, MockSink, RecordedMessage};
@@ -0,0 +1,140 @@
+//! YAML representation of events, enabled with the `yaml` feature, e.g. to store
+//! human-editable events in test fixtures, command line tools configuration and GitOps
+//! repositories.
+//!
+//! The events have the same fields as in the JSON event format. A YAML stream can hold several
+//! events, one per document:
+//!
+//! ```
+//! use cloudevents::yaml;
+//! use cloudevents::AttributesReader;
+//!
+//! let events = yaml::from_documents(
+//!     r#"
+//! specversion: "1.0"
+//! id: "0001"
+//! source: http://localhost/
+//! type: example.demo
+//! datacontenttype: application/json
+//! data:
+//!   hello: world
+//! ---
+//! specversion: "1.0"
+//! id: "0002"
+//! source: http://localhost/
+//! type: example.demo
+//! "#,
+//! )
+//! .unwrap();
+//! assert_eq!(2, events.len());
+//! assert_eq!("0002", events[1].get_id());
+//!
+//! let yaml = yaml::to_string(&events[0]).unwrap();
+//! assert_eq!(events[0], yaml::from_str(&yaml).unwrap());
+//! ```
+
+use crate::message::{Error, Result};
+use crate::Event;
+use serde::Deserialize;
+use std::io::{Read, Write};
+
+fn yaml_error(e: serde_yaml::Error) -> Error {
+    Error::Other {
+        source: Box::new(e),
+    }
+}
+
+/// Serialize `event` to a YAML document
+pub fn to_string(event: &Event) -> Result<String> {
+    serde_yaml::to_string(event).map_err(yaml_error)
+}
+
+/// Serialize `event` to a YAML document written to `writer`
+pub fn to_writer<W: Write>(writer: W, event: &Event) -> Result<()> {
+    serde_yaml::to_writer(writer, event).map_err(yaml_error)
+}
+
+/// Serialize `events` to a YAML stream, one document per event
+pub fn to_documents<'a>(events: impl IntoIterator<Item = &'a Event>) -> Result<String> {
+    let mut out = String::new();
+    for event in events {
+        out.push_str("---\n");
+        out.push_str(&to_string(event)?);
+    }
+    Ok(out)
+}
+
+/// Parse an event from a YAML document
+pub fn from_str(yaml: &str) -> Result<Event> {
+    serde_yaml::from_str(yaml).map_err(yaml_error)
+}
+
+/// Parse an event from a YAML document read from `reader`
+pub fn from_reader<R: Read>(reader: R) -> Result<Event> {
+    serde_yaml::from_reader(reader).map_err(yaml_error)
+}
+
+/// Parse the events of a YAML stream, one per document. The empty documents are skipped.
+pub fn from_documents(yaml: &str) -> Result<Vec<Event>> {
+    let mut events = Vec::new();
+    for document in serde_yaml::Deserializer::from_str(yaml) {
+        match serde_yaml::Value::deserialize(document).map_err(yaml_error)? {
+            serde_yaml::Value::Null => continue,
+            value => events.push(serde_yaml::from_value(value).map_err(yaml_error)?),
+        }
+    }
+    Ok(events)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EventBuilder, EventBuilderV03, EventBuilderV10};
+    use chrono::{TimeZone, Utc};
+    use serde_json::json;
+
+    #[test]
+    fn roundtrip() {
+        let events = vec![
+            EventBuilderV10::new()
+                .id("0001")
+                .source("http://localhost/")
+                .ty("example.test")
+                .time(Utc.timestamp(1_600_000_000, 0))
+                .extension("someint", 10)
+                .extension("somebool", true)
+                .data("application/json", json!({"hello": ["world", 1, null]}))
+                .build()
+                .unwrap(),
+            EventBuilderV10::new()
+                .id("0002")
+                .source("http://localhost/")
+                .ty("example.test")
+                .data("application/octet-stream", vec![1u8, 2, 3])
+                .build()
+                .unwrap(),
+            EventBuilderV03::new()
+                .id("0003")
+                .source("http://localhost/")
+                .ty("example.test")
+                .data("text/plain", "multi\nline")
+                .build()
+                .unwrap(),
+        ];
+
+        for event in &events {
+            assert_eq!(event, &from_str(&to_string(event).unwrap()).unwrap());
+        }
+        assert_eq!(
+            events,
+            from_documents(&to_documents(&events).unwrap()).unwrap()
+        );
+    }
+
+    #[test]
+    fn invalid_document() {
+        assert!(from_str("specversion: '1.0'\nid: '0001'\n").is_err());
+        assert!(from_documents("---\n---\nnot: [an, event]\n").is_err());
+        assert!(from_documents("").unwrap().is_empty());
+    }
+}
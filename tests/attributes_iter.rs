@@ -1,6 +1,6 @@
 mod test_data;
-use cloudevents::event::AttributeValue;
 use cloudevents::event::SpecVersion;
+use cloudevents::event::{AttributeValue, AttributesReader};
 use test_data::*;
 
 #[test]
@@ -24,3 +24,63 @@ fn iter_v03_test() {
         iter_v03.next().unwrap()
     );
 }
+
+#[test]
+fn iter_v10_extensions_test() {
+    let in_event = v10::full_no_data();
+    let (string_ext_name, string_ext_value) = string_extension();
+    let (int_ext_name, int_ext_value) = int_extension();
+
+    assert_eq!(3, in_event.iter_extensions().count());
+    assert!(in_event
+        .attributes_iter()
+        .any(|(name, value)| name == string_ext_name
+            && value == AttributeValue::String(&string_ext_value)));
+    assert!(in_event
+        .attributes_iter()
+        .any(|(name, value)| name == int_ext_name
+            && value == AttributeValue::Integer(&int_ext_value)));
+}
+
+#[test]
+fn iter_extensions_sorted_test() {
+    let in_event = v10::full_no_data();
+    let mut ext_names: Vec<&str> = in_event
+        .iter_extensions()
+        .map(|(name, _)| name.as_str())
+        .collect();
+    ext_names.sort_unstable();
+
+    let names: Vec<&str> = in_event.iter().map(|(name, _)| name).collect();
+    assert_eq!(
+        vec!["specversion", "id", "type", "source", "subject", "time"],
+        names[..names.len() - ext_names.len()].to_vec()
+    );
+    assert_eq!(ext_names, names[names.len() - ext_names.len()..].to_vec());
+}
+
+#[test]
+fn iter_spec_order_test() {
+    use cloudevents::event::{ATTRIBUTE_NAMES_V03, ATTRIBUTE_NAMES_V10};
+
+    for (in_event, attribute_names) in [
+        (v03::full_json_data(), &ATTRIBUTE_NAMES_V03),
+        (v10::full_json_data(), &ATTRIBUTE_NAMES_V10),
+    ] {
+        let mut ext_names: Vec<&str> = in_event
+            .iter_extensions()
+            .map(|(name, _)| name.as_str())
+            .collect();
+        ext_names.sort_unstable();
+
+        let names: Vec<&str> = in_event.iter().map(|(name, _)| name).collect();
+        assert_eq!(
+            attribute_names
+                .iter()
+                .copied()
+                .chain(ext_names)
+                .collect::<Vec<_>>(),
+            names
+        );
+    }
+}
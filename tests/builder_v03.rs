@@ -69,7 +69,7 @@ fn source_invalid_url() {
     let res = EventBuilderV03::new().source("").build();
     assert_match_pattern!(
         res,
-        Err(EventBuilderError::ParseUrlError {
+        Err(EventBuilderError::ParseUriReferenceError {
             attribute_name: "source",
             ..
         })
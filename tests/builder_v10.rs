@@ -3,7 +3,7 @@ mod util;
 
 use chrono::{DateTime, Utc};
 use cloudevents::event::{
-    AttributesReader, EventBuilder, EventBuilderError, ExtensionValue, SpecVersion,
+    AttributesReader, EventBuilder, EventBuilderError, ExtensionValue, SpecVersion, UriReference,
 };
 use cloudevents::EventBuilderV10;
 use url::Url;
@@ -69,15 +69,108 @@ fn source_invalid_url() {
     let res = EventBuilderV10::new().source("").build();
     assert_match_pattern!(
         res,
-        Err(EventBuilderError::ParseUrlError {
+        Err(EventBuilderError::ParseUriReferenceError {
             attribute_name: "source",
             ..
         })
     );
 }
 
+#[test]
+fn source_relative_uri_reference() {
+    let event = EventBuilderV10::new()
+        .id("0001")
+        .ty("example.test")
+        .source("/sensors/tn-1234567/alerts")
+        .build()
+        .unwrap();
+    assert_eq!("/sensors/tn-1234567/alerts", event.get_source().as_str());
+
+    let json = serde_json::to_value(&event).unwrap();
+    assert_eq!("/sensors/tn-1234567/alerts", json["source"]);
+    assert_eq!(event, serde_json::from_value(json).unwrap());
+}
+
 #[test]
 fn default_builds() {
     let res = EventBuilderV10::default().build();
     assert_match_pattern!(res, Ok(_));
 }
+
+#[test]
+fn time_from_system_time() {
+    let time = std::time::UNIX_EPOCH + std::time::Duration::from_secs(61);
+    let event = EventBuilderV10::new()
+        .id("0001")
+        .ty("example.test")
+        .source("http://localhost/")
+        .time(time)
+        .build()
+        .unwrap();
+    assert_eq!(Some(&DateTime::<Utc>::from(time)), event.get_time());
+}
+
+#[cfg(feature = "time")]
+#[test]
+fn time_from_offset_date_time() {
+    use chrono::TimeZone;
+
+    let time = time::OffsetDateTime::from_unix_timestamp(1_600_000_000).unwrap()
+        + time::Duration::milliseconds(250);
+    let time = time.to_offset(time::UtcOffset::from_hms(2, 0, 0).unwrap());
+    let event = EventBuilderV10::new()
+        .id("0001")
+        .ty("example.test")
+        .source("http://localhost/")
+        .time(time)
+        .build()
+        .unwrap();
+    assert_eq!(
+        Some(&Utc.timestamp(1_600_000_000, 250_000_000)),
+        event.get_time()
+    );
+    assert_eq!(
+        Some(7200),
+        event
+            .get_time_with_offset()
+            .map(|t| t.offset().local_minus_utc())
+    );
+    assert_eq!(Some(time), event.get_offset_date_time());
+    assert_eq!(
+        r#""2020-09-13T14:26:40.250+02:00""#,
+        serde_json::to_string(&serde_json::to_value(&event).unwrap()["time"]).unwrap()
+    );
+
+    // The offsets of a day or more are valid in time, but not in chrono
+    let time = time.to_offset(time::UtcOffset::from_hms(25, 0, 0).unwrap());
+    let result = EventBuilderV10::new()
+        .id("0001")
+        .ty("example.test")
+        .source("http://localhost/")
+        .time(time)
+        .build();
+    assert!(matches!(
+        result,
+        Err(EventBuilderError::ParseTimeError { .. })
+    ));
+}
+
+#[test]
+fn build_static_attributes() {
+    let event = EventBuilderV10::new()
+        .id("0001")
+        .static_ty("example.test")
+        .source(UriReference::from_static("/orders").unwrap())
+        .build()
+        .unwrap();
+    assert_eq!("example.test", event.get_type());
+    assert_eq!(
+        EventBuilderV10::new()
+            .id("0001")
+            .ty("example.test")
+            .source("/orders")
+            .build()
+            .unwrap(),
+        event
+    );
+}
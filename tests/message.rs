@@ -69,3 +69,20 @@ fn message_v10_invalid_attribute_name() {
             .to_string()
     )
 }
+
+#[test]
+fn message_v10_time_offset_roundtrip_binary() -> Result<()> {
+    use cloudevents::{AttributesReader, AttributesWriter};
+
+    let mut event = v10::minimal();
+    event.set_time_with_offset(Some(
+        chrono::DateTime::parse_from_rfc3339("2023-01-01T10:00:00+08:00").unwrap(),
+    ));
+    let out = BinaryDeserializer::into_event(event.clone())?;
+    assert_eq!(event, out);
+    assert_eq!(
+        "2023-01-01T10:00:00+08:00",
+        out.get_time_with_offset().unwrap().to_rfc3339()
+    );
+    Ok(())
+}
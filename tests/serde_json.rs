@@ -1,4 +1,5 @@
 use claim::*;
+use cloudevents::event::{AttributesReader, AttributesV03, AttributesV10};
 use cloudevents::Event;
 use rstest::rstest;
 use serde_json::Value;
@@ -87,3 +88,56 @@ fn deserialize_should_succeed(in_json: Value, out_event: Event) {
     let deserialize_json = deserialize_result.unwrap();
     assert_eq!(deserialize_json, out_event)
 }
+
+#[test]
+fn attributes_v03_roundtrip() {
+    let attributes: AttributesV03 = serde_json::from_value(v03::full_no_data_json()).unwrap();
+    assert_eq!(v03::full_no_data().get_id(), attributes.get_id());
+    assert_eq!(
+        v03::full_no_data().get_extensions().len(),
+        attributes.iter_extensions().count()
+    );
+    assert_eq!(
+        v03::full_no_data_json(),
+        serde_json::to_value(attributes).unwrap()
+    );
+}
+
+#[test]
+fn attributes_v10_roundtrip() {
+    let attributes: AttributesV10 = serde_json::from_value(v10::full_no_data_json()).unwrap();
+    assert_eq!(v10::full_no_data().get_id(), attributes.get_id());
+    assert_eq!(
+        v10::full_no_data().get_extensions().len(),
+        attributes.iter_extensions().count()
+    );
+    assert_eq!(
+        v10::full_no_data_json(),
+        serde_json::to_value(attributes).unwrap()
+    );
+}
+
+#[test]
+fn attributes_wrong_spec_version() {
+    assert_err!(serde_json::from_value::<AttributesV10>(
+        v03::full_no_data_json()
+    ));
+    assert_err!(serde_json::from_value::<AttributesV03>(
+        v10::full_no_data_json()
+    ));
+}
+
+#[test]
+fn time_offset_roundtrip() {
+    let input = r#"{"specversion":"1.0","id":"0001","type":"test_event.test_application","source":"http://localhost/","time":"2023-01-01T10:00:00+08:00"}"#;
+    let event: Event = serde_json::from_str(input).unwrap();
+    assert_eq!(
+        "2023-01-01T02:00:00+00:00",
+        event.get_time().unwrap().to_rfc3339()
+    );
+    assert_eq!(
+        "2023-01-01T10:00:00+08:00",
+        event.get_time_with_offset().unwrap().to_rfc3339()
+    );
+    assert_eq!(input, serde_json::to_string(&event).unwrap());
+}
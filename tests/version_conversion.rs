@@ -1,6 +1,7 @@
 mod test_data;
 use cloudevents::event::{EventBuilderV03, EventBuilderV10};
 use cloudevents::EventBuilder;
+use serde_json::Value;
 use test_data::*;
 
 #[test]
@@ -16,3 +17,24 @@ fn v03_to_v10() {
     let out_event = EventBuilderV10::from(in_event).build().unwrap();
     assert_eq!(v10::full_json_data(), out_event)
 }
+
+#[test]
+fn v10_binary_data_to_v03() {
+    let in_event = v10::full_xml_binary_data();
+    let out_event = EventBuilderV03::from(in_event).build().unwrap();
+    assert_eq!(v03::full_xml_binary_data(), out_event);
+    assert_eq!(
+        v03::full_xml_base64_data_json(),
+        serde_json::to_value(out_event).unwrap()
+    );
+}
+
+#[test]
+fn v03_binary_data_to_v10() {
+    let in_event: cloudevents::Event =
+        serde_json::from_value(v03::full_xml_base64_data_json()).unwrap();
+    let out_event = EventBuilderV10::from(in_event).build().unwrap();
+    let out_json: Value = serde_json::to_value(out_event).unwrap();
+    assert_eq!(v10::full_xml_base64_data_json(), out_json);
+    assert!(out_json.get("datacontentencoding").is_none());
+}